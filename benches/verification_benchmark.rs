@@ -2,103 +2,27 @@
 //!
 //! These benchmarks measure the performance of verification operations.
 //!
-//! Run with: cargo build --release && cargo run --release --bin verification_benchmark
+//! Run with: cargo bench --bench verification_benchmark
 //!
-//! Note: This is a simple benchmark implementation using std::time.
-//! For more sophisticated benchmarking, consider using criterion.rs
-//! (but that would add a dependency, breaking RSR Bronze compliance).
+//! Timing uses rhodibot's std-only harness (`rhodibot::bench`), which is also
+//! behind `rhodibot bench [path]`. Criterion would add a dependency, breaking
+//! RSR Bronze compliance.
 
+use rhodibot::bench::{bench_verification, run, BenchStats, DEFAULT_ITERATIONS};
+use rhodibot::config::Config;
 use std::path::PathBuf;
-use std::process::Command;
-use std::time::{Duration, Instant};
-
-/// Number of iterations for each benchmark
-const ITERATIONS: u32 = 100;
-
-/// Number of warmup iterations
-const WARMUP: u32 = 5;
-
-/// Benchmark a function and return statistics
-struct BenchmarkResult {
-    name: String,
-    min: Duration,
-    max: Duration,
-    avg: Duration,
-    iterations: u32,
-}
-
-impl BenchmarkResult {
-    fn print(&self) {
-        println!(
-            "{}: min={}μs, max={}μs, avg={}μs ({} iterations)",
-            self.name,
-            self.min.as_micros(),
-            self.max.as_micros(),
-            self.avg.as_micros(),
-            self.iterations
-        );
-    }
-}
-
-/// Run a benchmark with warmup and statistics
-fn benchmark<F: FnMut()>(name: &str, mut f: F) -> BenchmarkResult {
-    // Warmup phase
-    for _ in 0..WARMUP {
-        f();
-    }
-
-    let mut times = Vec::with_capacity(ITERATIONS as usize);
-
-    // Measurement phase
-    for _ in 0..ITERATIONS {
-        let start = Instant::now();
-        f();
-        times.push(start.elapsed());
-    }
-
-    let min = *times.iter().min().unwrap();
-    let max = *times.iter().max().unwrap();
-    let total: Duration = times.iter().sum();
-    let avg = total / ITERATIONS;
-
-    BenchmarkResult {
-        name: name.to_string(),
-        min,
-        max,
-        avg,
-        iterations: ITERATIONS,
-    }
-}
-
-/// Run a benchmark for command execution (fewer iterations)
-fn benchmark_command(name: &str, binary_path: &str, args: &[&str]) -> BenchmarkResult {
-    const CMD_ITERATIONS: u32 = 10;
-
-    // Warmup
-    for _ in 0..2 {
-        let _ = Command::new(binary_path).args(args).output();
-    }
-
-    let mut times = Vec::with_capacity(CMD_ITERATIONS as usize);
 
-    for _ in 0..CMD_ITERATIONS {
-        let start = Instant::now();
-        let _ = Command::new(binary_path).args(args).output();
-        times.push(start.elapsed());
-    }
-
-    let min = *times.iter().min().unwrap();
-    let max = *times.iter().max().unwrap();
-    let total: Duration = times.iter().sum();
-    let avg = total / CMD_ITERATIONS;
-
-    BenchmarkResult {
-        name: name.to_string(),
-        min,
-        max,
-        avg,
-        iterations: CMD_ITERATIONS,
-    }
+/// Print one benchmark line
+fn print_stats(name: &str, stats: &BenchStats) {
+    println!(
+        "{}: min={}μs, avg={}μs, p95={}μs, max={}μs ({} iterations)",
+        name,
+        stats.min.as_micros(),
+        stats.avg.as_micros(),
+        stats.p95.as_micros(),
+        stats.max.as_micros(),
+        stats.iterations
+    );
 }
 
 fn main() {
@@ -108,43 +32,26 @@ fn main() {
 
     let current_dir = std::env::current_dir().expect("Cannot get current directory");
 
-    // Build release binary first
-    println!("Building release binary...");
-    let build_result = Command::new("cargo")
-        .args(["build", "--release"])
-        .current_dir(&current_dir)
-        .output()
-        .expect("Failed to build release binary");
-
-    if !build_result.status.success() {
-        eprintln!("Failed to build release binary");
-        std::process::exit(1);
-    }
-    println!("Build complete.\n");
-
-    let binary_path = current_dir.join("target/release/aletheia");
-    let binary_str = binary_path.to_str().unwrap();
-
     println!("┌─────────────────────────────────────────────────────────────┐");
     println!("│ Micro-benchmarks (filesystem operations)                    │");
     println!("└─────────────────────────────────────────────────────────────┘\n");
 
     // Benchmark 1: Path validation
-    let path_validation = benchmark("Path validation", || {
+    let path_validation = run(DEFAULT_ITERATIONS, || {
         let path = PathBuf::from(&current_dir);
         let _ = path.exists();
         let _ = path.is_dir();
     });
-    path_validation.print();
+    print_stats("Path validation", &path_validation);
 
     // Benchmark 2: File existence checks
-    let file_checks = benchmark("File existence check (single)", || {
+    let file_checks = run(DEFAULT_ITERATIONS, || {
         let _ = current_dir.join("README.md").is_file();
     });
-    file_checks.print();
+    print_stats("File existence check (single)", &file_checks);
 
     // Benchmark 3: Multiple file checks
-    let multi_file = benchmark("File existence checks (16 files)", || {
+    let multi_file = run(DEFAULT_ITERATIONS, || {
         let files = [
             "README.md",
             "LICENSE.txt",
@@ -167,60 +74,46 @@ fn main() {
             let _ = current_dir.join(file).exists();
         }
     });
-    multi_file.print();
+    print_stats("File existence checks (16 files)", &multi_file);
 
     // Benchmark 4: Directory checks
-    let dir_checks = benchmark("Directory existence checks", || {
+    let dir_checks = run(DEFAULT_ITERATIONS, || {
         let _ = current_dir.join("src").is_dir();
         let _ = current_dir.join("tests").is_dir();
         let _ = current_dir.join(".well-known").is_dir();
     });
-    dir_checks.print();
+    print_stats("Directory existence checks", &dir_checks);
 
     // Benchmark 5: Symlink checks
-    let symlink_checks = benchmark("Symlink detection", || {
+    let symlink_checks = run(DEFAULT_ITERATIONS, || {
         let _ = current_dir.join("README.md").symlink_metadata();
     });
-    symlink_checks.print();
+    print_stats("Symlink detection", &symlink_checks);
 
     // Benchmark 6: Canonicalization
-    let canon = benchmark("Path canonicalization", || {
+    let canon = run(DEFAULT_ITERATIONS, || {
         let _ = current_dir.canonicalize();
     });
-    canon.print();
+    print_stats("Path canonicalization", &canon);
 
     println!("\n┌─────────────────────────────────────────────────────────────┐");
     println!("│ End-to-end benchmarks (full verification)                   │");
     println!("└─────────────────────────────────────────────────────────────┘\n");
 
-    // Benchmark: Full verification (human format)
-    let human_format = benchmark_command("Full verification (human)", binary_str, &[]);
-    human_format.print();
-
-    // Benchmark: Full verification (JSON format)
-    let json_format = benchmark_command(
-        "Full verification (JSON)",
-        binary_str,
-        &["--format", "json"],
-    );
-    json_format.print();
-
-    // Benchmark: Quiet mode
-    let quiet_mode = benchmark_command("Full verification (quiet)", binary_str, &["-q"]);
-    quiet_mode.print();
-
-    // Benchmark: Verbose mode
-    let verbose_mode = benchmark_command("Full verification (verbose)", binary_str, &["-v"]);
-    verbose_mode.print();
+    // Benchmark: Full verification, in-process
+    let config = Config::discover(&current_dir).unwrap_or_default();
+    let (verification, _) = bench_verification(&current_dir, &config, DEFAULT_ITERATIONS);
+    print_stats("Full verification", &verification);
 
     println!("\n┌─────────────────────────────────────────────────────────────┐");
     println!("│ Summary                                                     │");
     println!("└─────────────────────────────────────────────────────────────┘\n");
 
     println!(
-        "Average full verification time: {}μs ({:.2}ms)",
-        human_format.avg.as_micros(),
-        human_format.avg.as_secs_f64() * 1000.0
+        "Average full verification time: {}μs ({:.2}ms, p95 {:.2}ms)",
+        verification.avg.as_micros(),
+        verification.avg.as_secs_f64() * 1000.0,
+        verification.p95.as_secs_f64() * 1000.0
     );
 
     println!("\nPerformance Targets:");
@@ -228,7 +121,7 @@ fn main() {
     println!("  Excellent:   <5ms per verification");
     println!("  Outstanding: <2ms per verification");
 
-    let avg_ms = human_format.avg.as_secs_f64() * 1000.0;
+    let avg_ms = verification.avg.as_secs_f64() * 1000.0;
     if avg_ms < 2.0 {
         println!("\n  Status: ⭐ OUTSTANDING ({:.2}ms < 2ms)", avg_ms);
     } else if avg_ms < 5.0 {
@@ -238,18 +131,4 @@ fn main() {
     } else {
         println!("\n  Status: ⚠️  NEEDS IMPROVEMENT ({:.2}ms >= 10ms)", avg_ms);
     }
-
-    // Memory info (if available on Linux)
-    #[cfg(target_os = "linux")]
-    {
-        if let Ok(output) = Command::new("ls").args(["-lh", binary_str]).output() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if let Some(line) = stdout.lines().next() {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 5 {
-                    println!("\nBinary size: {}", parts[4]);
-                }
-            }
-        }
-    }
 }
@@ -1,44 +1,38 @@
-//! Simple example of using Aletheia as a library
+//! Simple example of using the Aletheia verification engine as a library
 //!
-//! This example shows how to programmatically verify RSR compliance
-//! Note: Aletheia is currently a binary-only tool, but this demonstrates
-//! the API design if library usage is added in the future.
+//! This example shows how to programmatically verify RSR compliance.
+//! The engine is provided by the `rhodibot` library crate, which the
+//! `aletheia` binary is built on.
+
+use rhodibot::verify_repository;
 
 fn main() {
     println!("Aletheia RSR Compliance Verification Example");
     println!("============================================\n");
 
-    // Example 1: Verify current directory
-    println!("Example 1: Verify current directory");
-    let current_dir = std::env::current_dir().expect("Cannot get current directory");
-    println!("Checking: {}", current_dir.display());
-    println!("Run: cargo run\n");
-
-    // Example 2: Verify specific path
-    println!("Example 2: Verify specific repository");
-    println!("Run: cargo run -- /path/to/repository\n");
+    // Verify the repository given on the command line, or the current directory
+    let repo_path = std::env::args()
+        .nth(1)
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::env::current_dir().expect("Cannot get current directory"));
+    println!("Checking: {}\n", repo_path.display());
 
-    // Example 3: Expected output
-    println!("Example 3: Expected output format");
-    println!(
-        "
-🔍 Aletheia - RSR Compliance Verification Report
-━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
-Repository: /path/to/repository
+    let report = verify_repository(&repo_path);
 
-📋 Documentation
-  ✅ README.md [Bronze]
-  ✅ LICENSE.txt [Bronze]
-  ✅ SECURITY.md [Bronze]
-  ❌ CONTRIBUTING.md [Bronze]
-  ...
+    for check in &report.checks {
+        let icon = if check.passed() { "✅" } else { "❌" };
+        println!("  {} {} / {}", icon, check.category, check.item);
+    }
 
-Score: 14/16 checks passed (87.5%)
-⚠️  Bronze-level RSR compliance: NOT MET
-    "
+    println!(
+        "\nScore: {}/{} checks passed ({:.1}%)",
+        report.passed_count(),
+        report.total_count(),
+        report.percentage()
     );
+    println!("Bronze compliant: {}", report.bronze_compliance());
 
-    println!("\nNote: To use Aletheia, run the binary:");
+    println!("\nNote: To use Aletheia from the command line, run the binary:");
     println!("  cargo run                    # Verify current directory");
     println!("  cargo run -- /path/to/repo   # Verify specific repository");
 }
@@ -0,0 +1,8 @@
+//! C library for embedding the rhodibot verifier
+//!
+//! Builds `librhodibot_ffi.so` (`rhodibot_ffi.dll`, `librhodibot_ffi.dylib`)
+//! from the `ffi` feature of the `rhodibot` crate, so the command-line tool
+//! and the Rust library are not built as a C library too. See
+//! [`rhodibot::ffi`] for the interface.
+
+pub use rhodibot::ffi::{rhodibot_free_string, rhodibot_verify};
@@ -0,0 +1,393 @@
+//! Declarative command-line parsing
+//!
+//! The rhodibot and aletheia binaries describe their command line as a
+//! [`Spec`] (commands, options and the positional argument) and leave the
+//! parsing and the help text to this module, so both accept the same
+//! syntax:
+//!
+//! - options anywhere on the line, before or after positionals
+//! - `--name value`, `--name=value` and `-n value`
+//! - repeated options, collected in order ([`Matches::values`])
+//! - `--` ending option parsing
+//! - `-h`/`--help` and `-V`/`--version`, with help generated from the spec
+//!
+//! Values are returned as strings; validating them stays with the binary.
+
+/// A command-line option
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Opt {
+    /// Long name, without the leading `--`
+    pub long: &'static str,
+    pub short: Option<char>,
+    /// Placeholder shown in help (e.g. `FORMAT`); `None` for flags
+    pub value: Option<&'static str>,
+    /// Help text; continuation lines are indented automatically
+    pub help: &'static str,
+}
+
+impl Opt {
+    /// An option without a value
+    pub const fn flag(long: &'static str, short: Option<char>, help: &'static str) -> Self {
+        Self {
+            long,
+            short,
+            value: None,
+            help,
+        }
+    }
+
+    /// An option taking a value
+    pub const fn value(
+        long: &'static str,
+        short: Option<char>,
+        value: &'static str,
+        help: &'static str,
+    ) -> Self {
+        Self {
+            long,
+            short,
+            value: Some(value),
+            help,
+        }
+    }
+}
+
+/// A subcommand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Command {
+    pub name: &'static str,
+    pub help: &'static str,
+}
+
+/// Description of a binary's command line
+#[derive(Debug, Clone, Copy)]
+pub struct Spec {
+    /// Binary name, as shown in usage and `--version`
+    pub name: &'static str,
+    pub version: &'static str,
+    /// Heading printed at the top of the help text
+    pub about: &'static str,
+    pub commands: &'static [Command],
+    /// Placeholder and help for positional arguments (e.g. `PATH`)
+    pub positional: Option<(&'static str, &'static str)>,
+    pub options: &'static [Opt],
+    /// Free-form sections appended to the generated help
+    pub after_help: &'static str,
+}
+
+/// Column at which option help text starts
+const OPTION_COLUMN: usize = 29;
+/// Column at which command help text starts
+const COMMAND_COLUMN: usize = 16;
+/// Column at which positional argument help text starts
+const ARG_COLUMN: usize = 14;
+
+/// Built-in options every spec accepts
+const HELP: Opt = Opt::flag("help", Some('h'), "Print help information");
+const VERSION: Opt = Opt::flag("version", Some('V'), "Print version information");
+
+/// Append `left` and `help` as a help entry, wrapping to `column`
+fn push_entry(out: &mut String, left: &str, help: &str, column: usize) {
+    out.push_str(left);
+    if left.len() + 2 <= column {
+        out.push_str(&" ".repeat(column - left.len()));
+    } else {
+        out.push('\n');
+        out.push_str(&" ".repeat(column));
+    }
+    let mut lines = help.lines();
+    out.push_str(lines.next().unwrap_or(""));
+    out.push('\n');
+    for line in lines {
+        out.push_str(&" ".repeat(column));
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+impl Spec {
+    fn find_long(&self, long: &str) -> Option<&Opt> {
+        self.options
+            .iter()
+            .chain([&HELP, &VERSION])
+            .find(|opt| opt.long == long)
+    }
+
+    fn find_short(&self, short: char) -> Option<&Opt> {
+        self.options
+            .iter()
+            .chain([&HELP, &VERSION])
+            .find(|opt| opt.short == Some(short))
+    }
+
+    /// `--version` output, e.g. `rhodibot 0.1.0`
+    pub fn version_line(&self) -> String {
+        format!("{} {}", self.name, self.version)
+    }
+
+    /// Generated help text
+    pub fn help(&self) -> String {
+        let mut out = format!("{}\n\nUSAGE:\n    {}", self.about, self.name);
+        if !self.commands.is_empty() {
+            out.push_str(" [COMMAND]");
+        }
+        out.push_str(" [OPTIONS]");
+        if let Some((name, _)) = self.positional {
+            out.push_str(&format!(" [{}]", name));
+        }
+        out.push('\n');
+
+        if !self.commands.is_empty() {
+            out.push_str("\nCOMMANDS:\n");
+            for command in self.commands {
+                let left = format!("    {}", command.name);
+                push_entry(&mut out, &left, command.help, COMMAND_COLUMN);
+            }
+        }
+        if let Some((name, help)) = self.positional {
+            out.push_str("\nARGS:\n");
+            push_entry(&mut out, &format!("    [{}]", name), help, ARG_COLUMN);
+        }
+
+        out.push_str("\nOPTIONS:\n");
+        for opt in self.options.iter().chain([&HELP, &VERSION]) {
+            let mut left = match opt.short {
+                Some(short) => format!("    -{}, --{}", short, opt.long),
+                None => format!("        --{}", opt.long),
+            };
+            if let Some(value) = opt.value {
+                left.push_str(&format!(" <{}>", value));
+            }
+            push_entry(&mut out, &left, opt.help, OPTION_COLUMN);
+        }
+
+        if !self.after_help.is_empty() {
+            out.push('\n');
+            out.push_str(self.after_help);
+        }
+        out
+    }
+
+    /// Parse `args` (without the program name)
+    pub fn parse<I, S>(&self, args: I) -> Result<Parsed, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut matches = Matches::default();
+        let mut args = args.into_iter().map(Into::into);
+        let mut options_done = false;
+
+        while let Some(arg) = args.next() {
+            let option = if options_done || arg == "-" || !arg.starts_with('-') {
+                None
+            } else if arg == "--" {
+                options_done = true;
+                continue;
+            } else if let Some(long) = arg.strip_prefix("--") {
+                let (name, inline) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(value.to_string())),
+                    None => (long, None),
+                };
+                let opt = self
+                    .find_long(name)
+                    .ok_or_else(|| format!("Unknown option: --{}", name))?;
+                Some((opt, inline))
+            } else {
+                let mut chars = arg[1..].chars();
+                let opt = match (chars.next(), chars.next()) {
+                    (Some(short), None) => self.find_short(short),
+                    _ => None,
+                };
+                Some((opt.ok_or_else(|| format!("Unknown option: {}", arg))?, None))
+            };
+
+            let Some((opt, inline)) = option else {
+                let is_command = self.commands.iter().any(|c| c.name == arg);
+                if matches.command.is_none() && matches.positionals.is_empty() && is_command {
+                    matches.command = Some(arg);
+                } else {
+                    matches.positionals.push(arg);
+                }
+                continue;
+            };
+
+            match opt.long {
+                "help" => return Ok(Parsed::Help),
+                "version" => return Ok(Parsed::Version),
+                _ => {}
+            }
+            let value = match (opt.value, inline) {
+                (None, Some(_)) => return Err(format!("--{} takes no value", opt.long)),
+                (None, None) => None,
+                (Some(_), Some(value)) => Some(value),
+                (Some(_), None) => Some(
+                    args.next()
+                        .ok_or_else(|| format!("--{} requires an argument", opt.long))?,
+                ),
+            };
+            matches.occurrences.push((opt.long, value));
+        }
+        Ok(Parsed::Run(matches))
+    }
+}
+
+/// Outcome of parsing a command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Parsed {
+    /// `-h`/`--help` was given; print [`Spec::help`]
+    Help,
+    /// `-V`/`--version` was given
+    Version,
+    Run(Matches),
+}
+
+/// Options and arguments found on a command line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Matches {
+    /// The command, if the first positional named one
+    pub command: Option<String>,
+    /// Positional arguments after the command, in order
+    pub positionals: Vec<String>,
+    /// Every option given, in order, by long name
+    occurrences: Vec<(&'static str, Option<String>)>,
+}
+
+impl Matches {
+    /// Whether the option was given
+    pub fn flag(&self, long: &str) -> bool {
+        self.occurrences.iter().any(|(name, _)| *name == long)
+    }
+
+    /// Value of the option's last occurrence
+    pub fn value(&self, long: &str) -> Option<&str> {
+        self.values(long).pop()
+    }
+
+    /// Values of every occurrence of a repeatable option, in order
+    pub fn values(&self, long: &str) -> Vec<&str> {
+        self.occurrences
+            .iter()
+            .filter(|(name, _)| *name == long)
+            .filter_map(|(_, value)| value.as_deref())
+            .collect()
+    }
+
+    /// Which of `longs` was given last, for options that override each other
+    /// (e.g. `--quiet` and `--verbose`)
+    pub fn last_of(&self, longs: &[&str]) -> Option<&'static str> {
+        self.occurrences
+            .iter()
+            .rev()
+            .map(|(name, _)| *name)
+            .find(|name| longs.contains(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: Spec = Spec {
+        name: "tool",
+        version: "1.2.3",
+        about: "Tool - does things",
+        commands: &[
+            Command {
+                name: "check",
+                help: "Check things (default)",
+            },
+            Command {
+                name: "standard",
+                help: "Print the standard:\n  standard list",
+            },
+        ],
+        positional: Some(("PATH", "Repository path")),
+        options: &[
+            Opt::value("format", Some('f'), "FORMAT", "Output format"),
+            Opt::value("skip", None, "ID", "Leave out a check\n(repeatable)"),
+            Opt::flag("quiet", Some('q'), "Quiet mode"),
+            Opt::flag("verbose", Some('v'), "Verbose mode"),
+            Opt::value("otel-endpoint", None, "URL", "Collector URL"),
+        ],
+        after_help: "EXAMPLES:\n    tool check .\n",
+    };
+
+    fn run(args: &[&str]) -> Matches {
+        match SPEC.parse(args.iter().copied()).unwrap() {
+            Parsed::Run(matches) => matches,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_options_anywhere() {
+        let matches = run(&["check", ".", "-f", "json", "--skip=A", "--skip", "B", "-q"]);
+        assert_eq!(matches.command.as_deref(), Some("check"));
+        assert_eq!(matches.positionals, vec!["."]);
+        assert_eq!(matches.value("format"), Some("json"));
+        assert_eq!(matches.values("skip"), vec!["A", "B"]);
+        assert!(matches.flag("quiet"));
+        assert!(!matches.flag("verbose"));
+
+        let matches = run(&["--format=sarif", "--format", "tap", "repo", "check"]);
+        assert_eq!(matches.value("format"), Some("tap"));
+        assert_eq!(matches.command, None);
+        assert_eq!(matches.positionals, vec!["repo", "check"]);
+    }
+
+    #[test]
+    fn test_subcommand_arguments_and_separator() {
+        let matches = run(&["standard", "show", "bronze", "-v"]);
+        assert_eq!(matches.command.as_deref(), Some("standard"));
+        assert_eq!(matches.positionals, vec!["show", "bronze"]);
+
+        let matches = run(&["-q", "--", "-odd-dir", "--format"]);
+        assert_eq!(matches.positionals, vec!["-odd-dir", "--format"]);
+        assert_eq!(matches.last_of(&["quiet", "verbose"]), Some("quiet"));
+        assert_eq!(run(&["-q", "-v"]).last_of(&["quiet", "verbose"]), Some("verbose"));
+        assert_eq!(run(&["-"]).positionals, vec!["-"]);
+    }
+
+    #[test]
+    fn test_errors_and_builtins() {
+        assert_eq!(SPEC.parse(["--help", "--bogus"]), Ok(Parsed::Help));
+        assert_eq!(SPEC.parse(["check", "-V"]), Ok(Parsed::Version));
+        assert_eq!(
+            SPEC.parse(["--bogus"]),
+            Err("Unknown option: --bogus".to_string())
+        );
+        assert_eq!(SPEC.parse(["-x"]), Err("Unknown option: -x".to_string()));
+        assert_eq!(SPEC.parse(["-qv"]), Err("Unknown option: -qv".to_string()));
+        assert_eq!(
+            SPEC.parse(["--format"]),
+            Err("--format requires an argument".to_string())
+        );
+        assert_eq!(
+            SPEC.parse(["--quiet=yes"]),
+            Err("--quiet takes no value".to_string())
+        );
+    }
+
+    #[test]
+    fn test_generated_help() {
+        let help = SPEC.help();
+        assert!(help.starts_with("Tool - does things\n\nUSAGE:\n"));
+        assert!(help.contains("\n    tool [COMMAND] [OPTIONS] [PATH]\n"));
+        assert!(help.contains("\nCOMMANDS:\n    check       Check things (default)\n"));
+        assert!(help.contains("    standard    Print the standard:\n"));
+        assert!(help.contains("\n                  standard list\n"));
+        assert!(help.contains("\nARGS:\n    [PATH]    Repository path\n"));
+        assert!(help.contains("    -f, --format <FORMAT>    Output format\n"));
+        assert!(help.contains(
+            "        --skip <ID>          Leave out a check\n\
+             \x20                            (repeatable)\n"
+        ));
+        assert!(help.contains(
+            "        --otel-endpoint <URL>\n                             Collector URL\n"
+        ));
+        assert!(help.contains("    -V, --version            Print version information\n"));
+        assert!(help.ends_with("\nEXAMPLES:\n    tool check .\n"));
+        assert_eq!(SPEC.version_line(), "tool 1.2.3");
+    }
+}
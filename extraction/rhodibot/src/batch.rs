@@ -0,0 +1,140 @@
+//! Verification of many repositories in one run
+//!
+//! `rhodibot batch repos.txt` reads a list of repository paths, verifies each
+//! one and aggregates the results. Each repository keeps its own exit code;
+//! the batch as a whole exits with the worst of them.
+
+use crate::{exit_codes, ComplianceReport};
+use std::path::{Path, PathBuf};
+
+/// Outcome of verifying one repository in a batch
+#[derive(Debug)]
+pub struct BatchEntry {
+    /// Repository path as written in the list
+    pub source: String,
+    /// The report, or why the repository could not be verified
+    pub outcome: Result<ComplianceReport, String>,
+    /// Exit code a single `rhodibot check` of this repository would give
+    pub exit_code: i32,
+}
+
+/// Aggregated results of a batch run
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    pub entries: Vec<BatchEntry>,
+}
+
+impl BatchReport {
+    /// Create an empty batch report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a verified repository
+    pub fn add_report(&mut self, source: &str, report: ComplianceReport, exit_code: i32) {
+        self.entries.push(BatchEntry {
+            source: source.to_string(),
+            outcome: Ok(report),
+            exit_code,
+        });
+    }
+
+    /// Record a repository that could not be verified
+    pub fn add_error(&mut self, source: &str, message: &str, exit_code: i32) {
+        self.entries.push(BatchEntry {
+            source: source.to_string(),
+            outcome: Err(message.to_string()),
+            exit_code,
+        });
+    }
+
+    /// Most severe exit code of any repository (0 for an empty batch)
+    ///
+    /// Exit codes up to 4 grow with severity: unreadable repositories outrank
+    /// security warnings, which outrank compliance failures. Compliance
+    /// through waivers ranks between success and failure.
+    pub fn worst_exit_code(&self) -> i32 {
+        let severity = |code: i32| match code {
+            exit_codes::WAIVED_COMPLIANCE => 1,
+            code => code * 2,
+        };
+        self.entries
+            .iter()
+            .map(|e| e.exit_code)
+            .max_by_key(|&code| severity(code))
+            .unwrap_or(exit_codes::SUCCESS)
+    }
+
+    /// Number of repositories with a successful result, including those
+    /// compliant through waivers
+    pub fn passed_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.exit_code, exit_codes::SUCCESS | exit_codes::WAIVED_COMPLIANCE))
+            .count()
+    }
+
+    /// Number of repositories that could not be verified
+    pub fn error_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.outcome.is_err()).count()
+    }
+}
+
+/// Parse a repository list: one path per line, `#` comments and blank lines
+/// ignored
+///
+/// Relative paths are resolved against `base`, normally the directory that
+/// contains the list file, so lists can be committed alongside the repos.
+pub fn parse_repo_list(text: &str, base: &Path) -> Vec<(String, PathBuf)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| (line.to_string(), base.join(line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_repo_list() {
+        let repos = parse_repo_list(
+            "# platform repos\n\nservices/api\n  /srv/git/web  \n",
+            Path::new("/work"),
+        );
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[0], ("services/api".to_string(), PathBuf::from("/work/services/api")));
+        assert_eq!(repos[1].1, PathBuf::from("/srv/git/web"));
+    }
+
+    #[test]
+    fn test_worst_exit_code() {
+        let mut batch = BatchReport::new();
+        assert_eq!(batch.worst_exit_code(), exit_codes::SUCCESS);
+
+        batch.add_report("a", ComplianceReport::new(PathBuf::from("a")), exit_codes::SUCCESS);
+        batch.add_report(
+            "w",
+            ComplianceReport::new(PathBuf::from("w")),
+            exit_codes::WAIVED_COMPLIANCE,
+        );
+        assert_eq!(batch.worst_exit_code(), exit_codes::WAIVED_COMPLIANCE);
+        batch.add_report(
+            "b",
+            ComplianceReport::new(PathBuf::from("b")),
+            exit_codes::SECURITY_WARNING,
+        );
+        batch.add_report(
+            "c",
+            ComplianceReport::new(PathBuf::from("c")),
+            exit_codes::COMPLIANCE_FAILED,
+        );
+        assert_eq!(batch.worst_exit_code(), exit_codes::SECURITY_WARNING);
+        assert_eq!(batch.passed_count(), 2);
+
+        batch.add_error("d", "Path does not exist", exit_codes::INVALID_PATH);
+        assert_eq!(batch.worst_exit_code(), exit_codes::INVALID_PATH);
+        assert_eq!(batch.error_count(), 1);
+    }
+}
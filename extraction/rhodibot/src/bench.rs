@@ -0,0 +1,117 @@
+//! In-process benchmark harness
+//!
+//! A dependency-free stand-in for criterion: run a closure a number of times
+//! after a short warmup and summarise the timings. `rhodibot bench` uses it to
+//! time whole verifications without spawning a process per iteration, so the
+//! numbers reflect the engine rather than process startup.
+
+use crate::config::Config;
+use crate::{verify_repository_with_config, ComplianceReport};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Default number of measured iterations
+pub const DEFAULT_ITERATIONS: u32 = 100;
+
+/// Unmeasured iterations run first to warm caches
+pub const WARMUP_ITERATIONS: u32 = 5;
+
+/// Summary of benchmark timings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub iterations: u32,
+    pub min: Duration,
+    pub avg: Duration,
+    /// 95th percentile (nearest rank)
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl BenchStats {
+    /// Summarise a set of samples; `None` if there are none
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let total: Duration = sorted.iter().sum();
+        // Nearest-rank percentile: the smallest sample with at least 95% of
+        // samples at or below it
+        let rank = (n * 95 + 99) / 100;
+        Some(Self {
+            iterations: n as u32,
+            min: sorted[0],
+            avg: total / n as u32,
+            p95: sorted[rank.max(1) - 1],
+            max: sorted[n - 1],
+        })
+    }
+}
+
+/// Time `iterations` runs of `f` after [`WARMUP_ITERATIONS`] warmup runs
+///
+/// At least one iteration is always measured.
+pub fn run(iterations: u32, mut f: impl FnMut()) -> BenchStats {
+    for _ in 0..WARMUP_ITERATIONS {
+        f();
+    }
+    let samples: Vec<Duration> = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect();
+    BenchStats::from_samples(&samples).expect("at least one sample")
+}
+
+/// Benchmark full verifications of a repository
+///
+/// Returns the timings and the report from the last iteration.
+pub fn bench_verification(
+    repo_path: &Path,
+    config: &Config,
+    iterations: u32,
+) -> (BenchStats, ComplianceReport) {
+    let mut report = None;
+    let stats = run(iterations, || {
+        report = Some(verify_repository_with_config(repo_path, config));
+    });
+    (stats, report.expect("verification ran"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = BenchStats::from_samples(&samples).unwrap();
+        assert_eq!(stats.iterations, 20);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(20));
+        assert_eq!(stats.avg, Duration::from_micros(10_500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+
+        let one = BenchStats::from_samples(&[Duration::from_millis(7)]).unwrap();
+        assert_eq!(one.p95, Duration::from_millis(7));
+        assert!(BenchStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_run_counts_iterations() {
+        let mut calls = 0;
+        let stats = run(3, || calls += 1);
+        assert_eq!(calls, WARMUP_ITERATIONS + 3);
+        assert_eq!(stats.iterations, 3);
+        assert!(stats.min <= stats.p95 && stats.p95 <= stats.max);
+
+        let (stats, report) =
+            bench_verification(Path::new("/nonexistent/rhodibot"), &Config::default(), 2);
+        assert_eq!(stats.iterations, 2);
+        assert!(!report.bronze_compliance());
+    }
+}
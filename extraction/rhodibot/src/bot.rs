@@ -3,10 +3,22 @@
 //! This module provides integration with various CI/CD platforms:
 //! - GitHub Actions
 //! - GitLab CI
+//! - TeamCity
+//! - Jenkins
+//! - CircleCI
+//! - Azure DevOps
+//! - Buildkite
 //! - Generic CI environments
 
-use crate::{ComplianceLevel, ComplianceReport, WarningLevel};
+use crate::output::{relative_display, write_atomic};
+use crate::subprocess;
+use crate::{CheckStatus, ComplianceReport, WarningLevel};
 use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Detected CI/CD platform
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,6 +28,9 @@ pub enum CIPlatform {
     CircleCI,
     Travis,
     Jenkins,
+    TeamCity,
+    AzureDevOps,
+    Buildkite,
     Unknown,
 }
 
@@ -32,6 +47,12 @@ pub fn detect() -> Self {
             CIPlatform::Travis
         } else if env::var("JENKINS_URL").is_ok() {
             CIPlatform::Jenkins
+        } else if env::var("TEAMCITY_VERSION").is_ok() {
+            CIPlatform::TeamCity
+        } else if env::var("TF_BUILD").is_ok() {
+            CIPlatform::AzureDevOps
+        } else if env::var("BUILDKITE").is_ok() {
+            CIPlatform::Buildkite
         } else {
             CIPlatform::Unknown
         }
@@ -45,9 +66,266 @@ pub fn name(&self) -> &'static str {
             CIPlatform::CircleCI => "CircleCI",
             CIPlatform::Travis => "Travis CI",
             CIPlatform::Jenkins => "Jenkins",
+            CIPlatform::TeamCity => "TeamCity",
+            CIPlatform::AzureDevOps => "Azure DevOps",
+            CIPlatform::Buildkite => "Buildkite",
             CIPlatform::Unknown => "Unknown",
         }
     }
+
+    /// Where `setup-ci` writes the platform's configuration, relative to the
+    /// repository root, for platforms rhodibot has a configuration for
+    pub fn config_file(&self) -> Option<&'static str> {
+        match self {
+            CIPlatform::GitHubActions => Some(".github/workflows/rhodibot.yml"),
+            CIPlatform::GitLabCI => Some(".gitlab-ci.yml"),
+            _ => None,
+        }
+    }
+
+    /// The configuration `setup-ci` writes for the platform
+    pub fn generate_config(&self) -> Option<String> {
+        match self {
+            CIPlatform::GitHubActions => Some(generate_github_actions_workflow()),
+            CIPlatform::GitLabCI => Some(generate_gitlab_ci_config()),
+            _ => None,
+        }
+    }
+
+    /// Emit the platform's native extras for a report
+    ///
+    /// Extras written to files (job summaries, outputs) are always produced.
+    /// Extras printed as log commands are only produced when `stdout_free` is
+    /// set, i.e. when stdout does not carry a machine-readable report that
+    /// they would corrupt.
+    pub fn output_report(&self, report: &ComplianceReport, stdout_free: bool) {
+        match self {
+            CIPlatform::GitHubActions => {
+                github_actions::set_outputs(report);
+                github_actions::write_summary(report);
+                if stdout_free {
+                    github_actions::annotate(report);
+                }
+            }
+            CIPlatform::GitLabCI => {
+                let path = std::path::Path::new(gitlab_ci::DOTENV_FILE);
+                if let Err(e) = gitlab_ci::write_dotenv(report, path) {
+                    eprintln!("Warning: cannot write {}: {}", path.display(), e);
+                }
+                if stdout_free {
+                    gitlab_ci::output_report(report);
+                }
+            }
+            CIPlatform::TeamCity => {
+                if stdout_free {
+                    teamcity::output_report(report);
+                }
+            }
+            CIPlatform::Jenkins => {
+                let path = std::path::Path::new(jenkins::JUNIT_FILE);
+                if let Err(e) = jenkins::write_junit(report, path) {
+                    eprintln!("Warning: cannot write {}: {}", path.display(), e);
+                }
+                if stdout_free {
+                    jenkins::output_report(report);
+                }
+            }
+            CIPlatform::CircleCI => {
+                let dir = std::path::Path::new(circleci::TEST_RESULTS_DIR);
+                if let Err(e) = circleci::write_test_results(report, dir) {
+                    eprintln!("Warning: cannot write test results to {}: {}", dir.display(), e);
+                }
+            }
+            CIPlatform::AzureDevOps => {
+                if stdout_free {
+                    azure_devops::output_report(report);
+                }
+            }
+            CIPlatform::Buildkite => {
+                let path = std::path::Path::new(buildkite::ANNOTATION_FILE);
+                if let Err(e) = buildkite::write_annotation(report, path) {
+                    eprintln!("Warning: cannot write {}: {}", path.display(), e);
+                }
+            }
+            CIPlatform::Travis | CIPlatform::Unknown => {}
+        }
+    }
+}
+
+/// Longest a platform API request may take
+const API_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Longest curl runs before it is killed, should it not stop at [`API_TIMEOUT`]
+const API_KILL_TIMEOUT: Duration = Duration::from_secs(65);
+
+/// Seconds curl may spend connecting to a platform API
+const API_CONNECT_TIMEOUT: &str = "10";
+
+/// Longest a git command run for a pull request may take
+const GIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Media type GitHub's REST API asks clients to accept
+const GITHUB_ACCEPT: &str = "Accept: application/vnd.github+json";
+
+/// Send a JSON request with `headers` using the system `curl` binary
+///
+/// Rhodibot links no network code; the few opt-in integrations that talk to a
+/// platform API shell out to `curl` instead. The headers, which carry the
+/// token, go to curl as a config on stdin so they never appear in the process
+/// list. The body, too large for a config line when it is a SARIF upload, is
+/// written to a private temporary directory (see [`write_private`]); an empty
+/// body sends none, as for `GET`. Returns the response body on success.
+pub(crate) fn curl_request(
+    method: &str,
+    url: &str,
+    headers: &[&str],
+    body: &str,
+) -> Result<String, String> {
+    let mut command = Command::new("curl");
+    command
+        .args(["-sS", "--fail", "-K", "-", "-X", method])
+        .args(["--connect-timeout", API_CONNECT_TIMEOUT])
+        .arg("--max-time")
+        .arg(API_TIMEOUT.as_secs().to_string())
+        .args(["-H", "Content-Type: application/json"]);
+    let body_dir = if body.is_empty() {
+        None
+    } else {
+        let dir = write_private(REQUEST_BODY, body.as_bytes())
+            .map_err(|e| format!("cannot write request body: {}", e))?;
+        command.arg("--data-binary").arg(format!("@{}", dir.join(REQUEST_BODY).display()));
+        Some(dir)
+    };
+    command.arg(url);
+    let mut config = String::new();
+    for header in headers {
+        config.push_str(&format!("header = {}\n", curl_config_quote(header)));
+    }
+
+    // curl enforces --max-time itself; the kill is for a curl that hangs
+    let result = match subprocess::run(&mut command, Some(config.as_bytes()), API_KILL_TIMEOUT) {
+        Ok(output) if output.status.success() => {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        }
+        Ok(output) => Err(format!(
+            "{} {} failed: {}",
+            method,
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+        Err(e) if e.kind() == io::ErrorKind::TimedOut => {
+            Err(format!("{} {} {}", method, url, e))
+        }
+        Err(e) => Err(format!("cannot run curl: {}", e)),
+    };
+
+    if let Some(dir) = body_dir {
+        let _ = fs::remove_dir_all(dir);
+    }
+    result
+}
+
+/// File name of a request body in its directory
+const REQUEST_BODY: &str = "request.json";
+
+/// Write `contents` to a file `name` in a new temporary directory that only
+/// the current user can enter, returning the directory, for the caller to
+/// remove
+///
+/// Neither the directory nor the file may exist beforehand, links included,
+/// so another user of a shared temporary directory can neither read the
+/// contents nor redirect the write.
+pub(crate) fn write_private(name: &str, contents: &[u8]) -> io::Result<PathBuf> {
+    use std::io::Write;
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let dir = env::temp_dir().join(format!("rhodibot-private-{}-{}", std::process::id(), nanos));
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(&dir)?;
+    let written = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(dir.join(name))
+        .and_then(|mut file| file.write_all(contents));
+    match written {
+        Ok(()) => Ok(dir),
+        Err(e) => {
+            let _ = fs::remove_dir_all(&dir);
+            Err(e)
+        }
+    }
+}
+
+/// Quote `value` for a curl config file, escaping what ends the string
+fn curl_config_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Render a Markdown summary of the report for job summaries and annotations
+pub fn render_summary_markdown(report: &ComplianceReport) -> String {
+    let mut md = String::new();
+    md.push_str("## 🤖 Rhodibot RSR Compliance Report\n\n");
+
+    if report.bronze_compliance() && !report.has_critical_warnings() {
+        md.push_str("✅ **Bronze-level RSR compliance: ACHIEVED**\n\n");
+    } else {
+        md.push_str("❌ **Bronze-level RSR compliance: NOT MET**\n\n");
+    }
+
+    md.push_str(&format!(
+        "**Score**: {}/{} checks passed ({:.1}%)\n\n",
+        report.passed_count(),
+        report.total_count(),
+        report.percentage()
+    ));
+
+    md.push_str("### Checks\n\n");
+    md.push_str("| Category | Item | Status |\n");
+    md.push_str("|----------|------|--------|\n");
+    for check in &report.checks {
+        let status = match check.status {
+            _ if check.waived => "🔕",
+            CheckStatus::Pass => "✅",
+            CheckStatus::Fail | CheckStatus::Error => "❌",
+            CheckStatus::Skipped | CheckStatus::NotApplicable => "⏭️",
+        };
+        md.push_str(&format!(
+            "| {} | {} | {} |\n",
+            check.category, check.item, status
+        ));
+    }
+
+    if !report.warnings.is_empty() {
+        md.push_str("\n### Security Warnings\n\n");
+        for warning_item in &report.warnings {
+            let icon = match warning_item.level {
+                WarningLevel::Info => "ℹ️",
+                WarningLevel::Warning => "⚠️",
+                WarningLevel::Critical => "🚨",
+            };
+            md.push_str(&format!("- {} {}\n", icon, warning_item.message));
+        }
+    }
+
+    md
 }
 
 /// GitHub Actions specific output commands
@@ -60,7 +338,7 @@ pub fn set_output(name: &str, value: &str) {
         if let Ok(output_file) = env::var("GITHUB_OUTPUT") {
             if let Ok(mut file) = std::fs::OpenOptions::new()
                 .append(true)
-                .open(&output_file)
+                .open(output_file)
             {
                 use std::io::Write;
                 let _ = writeln!(file, "{}={}", name, value);
@@ -71,35 +349,50 @@ pub fn set_output(name: &str, value: &str) {
         }
     }
 
-    /// Add a warning annotation
-    pub fn warning(message: &str, file: Option<&str>, line: Option<u32>) {
-        let mut cmd = format!("::warning");
+    /// Escape the message part of a workflow command
+    pub fn escape_data(value: &str) -> String {
+        value
+            .replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    }
+
+    /// Escape a property value (`file=...`) of a workflow command
+    pub fn escape_property(value: &str) -> String {
+        escape_data(value).replace(':', "%3A").replace(',', "%2C")
+    }
+
+    /// Build an annotation command (`::warning file=...::message`)
+    pub fn annotation(kind: &str, message: &str, file: Option<&str>, line: Option<u32>) -> String {
+        let mut cmd = format!("::{}", kind);
         if let Some(f) = file {
-            cmd.push_str(&format!(" file={}", f));
+            cmd.push_str(&format!(" file={}", escape_property(f)));
             if let Some(l) = line {
                 cmd.push_str(&format!(",line={}", l));
             }
         }
-        cmd.push_str(&format!("::{}", message));
-        println!("{}", cmd);
+        cmd.push_str(&format!("::{}", escape_data(message)));
+        cmd
+    }
+
+    /// Add a notice annotation
+    pub fn notice(message: &str, file: Option<&str>, line: Option<u32>) {
+        println!("{}", annotation("notice", message, file, line));
+    }
+
+    /// Add a warning annotation
+    pub fn warning(message: &str, file: Option<&str>, line: Option<u32>) {
+        println!("{}", annotation("warning", message, file, line));
     }
 
     /// Add an error annotation
     pub fn error(message: &str, file: Option<&str>, line: Option<u32>) {
-        let mut cmd = format!("::error");
-        if let Some(f) = file {
-            cmd.push_str(&format!(" file={}", f));
-            if let Some(l) = line {
-                cmd.push_str(&format!(",line={}", l));
-            }
-        }
-        cmd.push_str(&format!("::{}", message));
-        println!("{}", cmd);
+        println!("{}", annotation("error", message, file, line));
     }
 
     /// Start a log group
     pub fn group(title: &str) {
-        println!("::group::{}", title);
+        println!("::group::{}", escape_data(title));
     }
 
     /// End a log group
@@ -113,7 +406,7 @@ pub fn summary(markdown: &str) {
             if let Ok(mut file) = std::fs::OpenOptions::new()
                 .append(true)
                 .create(true)
-                .open(&summary_file)
+                .open(summary_file)
             {
                 use std::io::Write;
                 let _ = writeln!(file, "{}", markdown);
@@ -123,7 +416,13 @@ pub fn summary(markdown: &str) {
 
     /// Output report as GitHub Actions annotations and summary
     pub fn output_report(report: &ComplianceReport) {
-        // Set outputs
+        set_outputs(report);
+        annotate(report);
+        write_summary(report);
+    }
+
+    /// Set step outputs describing the report
+    pub fn set_outputs(report: &ComplianceReport) {
         set_output("passed", &report.passed_count().to_string());
         set_output("total", &report.total_count().to_string());
         set_output("percentage", &format!("{:.1}", report.percentage()));
@@ -132,115 +431,841 @@ pub fn output_report(report: &ComplianceReport) {
             "has_warnings",
             &report.has_critical_warnings().to_string(),
         );
+        let level = report
+            .highest_level()
+            .map_or("none".to_string(), |l| l.display_name().to_lowercase());
+        set_output("level_achieved", &level);
+        if let Some(ref commit) = report.metadata.commit {
+            set_output("commit", commit);
+        }
+        if let Some(ref branch) = report.metadata.branch {
+            set_output("branch", branch);
+        }
+    }
 
-        // Output annotations for failed checks
+    /// Render the check log and annotations
+    ///
+    /// Each category is wrapped in a collapsible group listing its checks,
+    /// with a warning annotation for every failure. Security warnings follow
+    /// as notice, warning or error annotations by severity.
+    pub fn render_annotations(report: &ComplianceReport) -> String {
+        let mut out = String::new();
+        let mut categories: Vec<&str> = Vec::new();
         for check in &report.checks {
-            if !check.passed {
-                warning(
-                    &format!("RSR check failed: {} - {}", check.category, check.item),
-                    None,
-                    None,
-                );
+            if !categories.contains(&check.category.as_str()) {
+                categories.push(&check.category);
             }
         }
 
-        // Output annotations for security warnings
-        for warning_item in &report.warnings {
-            let file = warning_item.path.as_ref().map(|p| p.to_string_lossy());
-            match warning_item.level {
-                WarningLevel::Critical => {
-                    error(&warning_item.message, file.as_deref(), None);
-                }
-                _ => {
-                    warning(&warning_item.message, file.as_deref(), None);
+        for category in categories {
+            out.push_str(&format!("::group::{}\n", escape_data(category)));
+            for check in report.checks.iter().filter(|c| c.category == category) {
+                let status = match check.status {
+                    _ if check.waived => "✓",
+                    CheckStatus::Pass => "✓",
+                    CheckStatus::Fail | CheckStatus::Error => "✗",
+                    CheckStatus::Skipped | CheckStatus::NotApplicable => "-",
+                };
+                out.push_str(&format!("{} {} ({})\n", status, check.item, check.id));
+                if check.is_failure() {
+                    out.push_str(&annotation(
+                        "warning",
+                        &format!("RSR check failed: {} - {}", check.category, check.item),
+                        None,
+                        None,
+                    ));
+                    out.push('\n');
                 }
             }
+            out.push_str("::endgroup::\n");
         }
 
-        // Generate job summary
-        let mut md = String::new();
-        md.push_str("## 🤖 Rhodibot RSR Compliance Report\n\n");
-
-        if report.bronze_compliance() && !report.has_critical_warnings() {
-            md.push_str("✅ **Bronze-level RSR compliance: ACHIEVED**\n\n");
-        } else {
-            md.push_str("❌ **Bronze-level RSR compliance: NOT MET**\n\n");
+        // Annotations are placed on files in the checkout, by relative path
+        for warning_item in &report.warnings {
+            let file = warning_item
+                .path
+                .as_deref()
+                .map(|p| relative_display(p, &report.repository_path));
+            let kind = match warning_item.level {
+                WarningLevel::Info => "notice",
+                WarningLevel::Warning => "warning",
+                WarningLevel::Critical => "error",
+            };
+            out.push_str(&annotation(kind, &warning_item.message, file.as_deref(), None));
+            out.push('\n');
         }
+        out
+    }
 
-        md.push_str(&format!(
-            "**Score**: {}/{} checks passed ({:.1}%)\n\n",
-            report.passed_count(),
-            report.total_count(),
-            report.percentage()
-        ));
+    /// Print the check log and annotations
+    pub fn annotate(report: &ComplianceReport) {
+        print!("{}", render_annotations(report));
+    }
 
-        md.push_str("### Checks\n\n");
-        md.push_str("| Category | Item | Status |\n");
-        md.push_str("|----------|------|--------|\n");
-        for check in &report.checks {
-            let status = if check.passed { "✅" } else { "❌" };
-            md.push_str(&format!(
-                "| {} | {} | {} |\n",
-                check.category, check.item, status
-            ));
-        }
+    /// Append the report to the job summary
+    pub fn write_summary(report: &ComplianceReport) {
+        summary(&render_summary_markdown(report));
+    }
 
-        if !report.warnings.is_empty() {
-            md.push_str("\n### Security Warnings\n\n");
-            for warning_item in &report.warnings {
-                let icon = match warning_item.level {
-                    WarningLevel::Info => "ℹ️",
-                    WarningLevel::Warning => "⚠️",
-                    WarningLevel::Critical => "🚨",
-                };
-                md.push_str(&format!("- {} {}\n", icon, warning_item.message));
-            }
-        }
+    /// Build the JSON body for the code-scanning SARIF upload endpoint
+    pub fn sarif_upload_body(sarif: &str, commit_sha: &str, git_ref: &str) -> String {
+        let encoded = crate::codec::base64_encode(&crate::codec::gzip_store(sarif.as_bytes()));
+        format!(
+            "{{\"commit_sha\":\"{}\",\"ref\":\"{}\",\"sarif\":\"{}\",\"tool_name\":\"rhodibot\"}}",
+            crate::json::escape(commit_sha),
+            crate::json::escape(git_ref),
+            encoded
+        )
+    }
+
+    /// Upload a SARIF report to GitHub code scanning
+    ///
+    /// Opt-in: needs `GITHUB_TOKEN` (with `security-events: write`) plus the
+    /// `GITHUB_REPOSITORY`, `GITHUB_SHA` and `GITHUB_REF` variables that Actions
+    /// sets. The report is gzipped, base64-encoded and POSTed to the
+    /// code-scanning API via the system `curl` binary.
+    pub fn upload_sarif(sarif: &str) -> Result<(), String> {
+        let var = |name: &str| env::var(name).map_err(|_| format!("{} is not set", name));
+        let token = var("GITHUB_TOKEN")?;
+        let repository = var("GITHUB_REPOSITORY")?;
+        let commit_sha = var("GITHUB_SHA")?;
+        let git_ref = var("GITHUB_REF")?;
+        let api_url =
+            env::var("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".to_string());
 
-        summary(&md);
+        let url = format!(
+            "{}/repos/{}/code-scanning/sarifs",
+            api_url.trim_end_matches('/'),
+            repository
+        );
+        let body = sarif_upload_body(sarif, &commit_sha, &git_ref);
+        let auth_header = format!("Authorization: Bearer {}", token);
+        super::curl_request("POST", &url, &[&auth_header, super::GITHUB_ACCEPT], &body)
+            .map(|_| ())
     }
 }
 
 /// GitLab CI specific output
 pub mod gitlab_ci {
     use super::*;
+    use crate::output::write_atomic;
+    use std::io;
+    use std::path::Path;
+
+    /// Dotenv artifact file name referenced by the generated GitLab config
+    pub const DOTENV_FILE: &str = "rhodibot.env";
+
+    /// Render the compliance variables as `KEY=value` lines
+    pub fn render_dotenv(report: &ComplianceReport) -> String {
+        let level = report
+            .highest_level()
+            .map_or("none".to_string(), |l| l.display_name().to_lowercase());
+        let mut out = String::new();
+        out.push_str(&format!("RHODIBOT_PASSED={}\n", report.passed_count()));
+        out.push_str(&format!("RHODIBOT_TOTAL={}\n", report.total_count()));
+        out.push_str(&format!("RHODIBOT_PERCENTAGE={:.1}\n", report.percentage()));
+        out.push_str(&format!(
+            "RHODIBOT_BRONZE_COMPLIANT={}\n",
+            report.bronze_compliance()
+        ));
+        out.push_str(&format!(
+            "RHODIBOT_HAS_WARNINGS={}\n",
+            report.has_critical_warnings()
+        ));
+        out.push_str(&format!("RHODIBOT_LEVEL={}\n", level));
+        out
+    }
+
+    /// Write the compliance variables to a dotenv file so downstream jobs can
+    /// consume them via `artifacts:reports:dotenv`
+    pub fn write_dotenv(report: &ComplianceReport, path: &Path) -> io::Result<()> {
+        write_atomic(path, &render_dotenv(report))
+    }
 
     /// Output report in GitLab CI compatible format
     pub fn output_report(report: &ComplianceReport) {
-        // GitLab uses dotenv artifacts for passing variables
-        // Output to console in a parseable format
-        println!("RHODIBOT_PASSED={}", report.passed_count());
-        println!("RHODIBOT_TOTAL={}", report.total_count());
-        println!("RHODIBOT_PERCENTAGE={:.1}", report.percentage());
-        println!("RHODIBOT_BRONZE_COMPLIANT={}", report.bronze_compliance());
-        println!(
-            "RHODIBOT_HAS_WARNINGS={}",
-            report.has_critical_warnings()
-        );
+        print!("{}", render_dotenv(report));
 
-        // Output sections
-        println!("\n\\e[0Ksection_start:{}:rhodibot_report[collapsed=false]\\r\\e[0K\x1b[36mRhodibot Report\x1b[0m",
+        // Collapsible log section
+        let now = || {
             std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs()
+        };
+        let no_color = crate::env::no_color();
+        let paint = |color: &str, text: &str| {
+            if no_color {
+                text.to_string()
+            } else {
+                format!("\x1b[{}m{}\x1b[0m", color, text)
+            }
+        };
+        println!(
+            "\x1b[0Ksection_start:{}:rhodibot_report[collapsed=false]\r\x1b[0K{}",
+            now(),
+            paint("36", "Rhodibot Report")
         );
 
         for check in &report.checks {
-            let status = if check.passed { "✓" } else { "✗" };
-            let color = if check.passed { "32" } else { "31" };
-            println!(
-                "\x1b[{}m[{}]\x1b[0m {} - {}",
-                color, status, check.category, check.item
-            );
+            let (status, color) = match check.status {
+                _ if check.waived => ("[✓]", "32"),
+                CheckStatus::Pass => ("[✓]", "32"),
+                CheckStatus::Fail | CheckStatus::Error => ("[✗]", "31"),
+                CheckStatus::Skipped | CheckStatus::NotApplicable => ("[-]", "33"),
+            };
+            println!("{} {} - {}", paint(color, status), check.category, check.item);
         }
 
-        println!("section_end:{}:rhodibot_report\\r\\e[0K",
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs()
+        println!("\x1b[0Ksection_end:{}:rhodibot_report\r\x1b[0K", now());
+    }
+}
+
+/// Jenkins JUnit results and console markers
+pub mod jenkins {
+    use super::*;
+    use crate::output::{render_junit, write_atomic};
+    use std::io;
+    use std::path::Path;
+
+    /// JUnit file picked up with `junit 'rhodibot-junit.xml'`
+    pub const JUNIT_FILE: &str = "rhodibot-junit.xml";
+
+    /// Write the report as JUnit XML for the `junit` pipeline step
+    pub fn write_junit(report: &ComplianceReport, path: &Path) -> io::Result<()> {
+        write_atomic(path, &render_junit(report))
+    }
+
+    /// Render console markers, one line per failed check or security warning
+    ///
+    /// Lines use the `ERROR:`/`WARNING:` prefixes matched by the default rules
+    /// of the Log Parser and Warnings plugins.
+    pub fn render_markers(report: &ComplianceReport) -> String {
+        let mut out = String::new();
+        for check in report.checks.iter().filter(|c| c.is_failure()) {
+            out.push_str(&format!(
+                "[rhodibot] ERROR: {} RSR {} check failed: {} - {}\n",
+                check.id,
+                check.required_for.display_name(),
+                check.category,
+                check.item
+            ));
+        }
+        for warning_item in &report.warnings {
+            let prefix = match warning_item.level {
+                WarningLevel::Critical => "ERROR",
+                WarningLevel::Warning => "WARNING",
+                WarningLevel::Info => "INFO",
+            };
+            out.push_str(&format!(
+                "[rhodibot] {}: {}\n",
+                prefix,
+                warning_item.message.replace('\n', " ")
+            ));
+        }
+        out
+    }
+
+    /// Print the console markers
+    pub fn output_report(report: &ComplianceReport) {
+        print!("{}", render_markers(report));
+    }
+}
+
+/// CircleCI test results
+pub mod circleci {
+    use super::*;
+    use crate::output::{render_junit, write_atomic};
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    /// Directory passed to `store_test_results`
+    pub const TEST_RESULTS_DIR: &str = "test-results";
+
+    /// Write JUnit results into `dir` using CircleCI's one-subdirectory-per-tool
+    /// layout (`<dir>/rhodibot/results.xml`), returning the file written
+    pub fn write_test_results(report: &ComplianceReport, dir: &Path) -> io::Result<PathBuf> {
+        let path = dir.join("rhodibot").join("results.xml");
+        write_atomic(&path, &render_junit(report))?;
+        Ok(path)
+    }
+}
+
+/// Azure DevOps logging commands
+pub mod azure_devops {
+    use super::*;
+
+    /// Escape the message part of a `##vso[...]` logging command
+    pub fn escape_data(value: &str) -> String {
+        value
+            .replace('%', "%AZP25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    }
+
+    /// Escape a `key=value` property of a logging command
+    pub fn escape_property(value: &str) -> String {
+        escape_data(value).replace(';', "%3B").replace(']', "%5D")
+    }
+
+    /// Render `task.logissue` commands for failed checks and security warnings
+    pub fn render_issues(report: &ComplianceReport) -> String {
+        let mut out = String::new();
+        for check in report.checks.iter().filter(|c| c.is_failure()) {
+            out.push_str(&format!(
+                "##vso[task.logissue type=warning;code={}]{}\n",
+                escape_property(&check.id),
+                escape_data(&format!(
+                    "RSR check failed: {} - {}",
+                    check.category, check.item
+                ))
+            ));
+        }
+        for warning_item in &report.warnings {
+            let kind = match warning_item.level {
+                WarningLevel::Critical => "error",
+                _ => "warning",
+            };
+            let source = warning_item
+                .path
+                .as_deref()
+                .map(|p| relative_display(p, &report.repository_path))
+                .map(|p| format!("sourcepath={};", escape_property(&p)))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "##vso[task.logissue type={};{}]{}\n",
+                kind,
+                source,
+                escape_data(&warning_item.message)
+            ));
+        }
+        out
+    }
+
+    /// Print the logging commands
+    pub fn output_report(report: &ComplianceReport) {
+        print!("{}", render_issues(report));
+    }
+}
+
+/// Buildkite annotations
+pub mod buildkite {
+    use super::*;
+    use crate::output::write_atomic;
+    use std::io;
+    use std::path::Path;
+
+    /// Annotation file, for `buildkite-agent annotate --style <style> < rhodibot-annotation.md`
+    pub const ANNOTATION_FILE: &str = "rhodibot-annotation.md";
+
+    /// Annotation style matching the report outcome
+    pub fn annotation_style(report: &ComplianceReport) -> &'static str {
+        if report.has_critical_warnings() || !report.bronze_compliance() {
+            "error"
+        } else if report.warnings.is_empty() {
+            "success"
+        } else {
+            "warning"
+        }
+    }
+
+    /// Write the Markdown annotation body
+    pub fn write_annotation(report: &ComplianceReport, path: &Path) -> io::Result<()> {
+        write_atomic(path, &render_summary_markdown(report))
+    }
+}
+
+/// TeamCity service messages
+pub mod teamcity {
+    use super::*;
+
+    /// Escape a value for a `##teamcity[...]` service message attribute
+    pub fn escape(value: &str) -> String {
+        let mut out = String::with_capacity(value.len());
+        for c in value.chars() {
+            match c {
+                '|' => out.push_str("||"),
+                '\'' => out.push_str("|'"),
+                '\n' => out.push_str("|n"),
+                '\r' => out.push_str("|r"),
+                '[' => out.push_str("|["),
+                ']' => out.push_str("|]"),
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    /// Render the report as service messages: one test per check, build
+    /// statistics for the score, and a build problem per critical warning
+    pub fn render_report(report: &ComplianceReport) -> String {
+        let mut out = String::new();
+        out.push_str("##teamcity[testSuiteStarted name='rhodibot']\n");
+        for check in &report.checks {
+            let name = escape(&format!("{} {}", check.id, check.item));
+            out.push_str(&format!("##teamcity[testStarted name='{}']\n", name));
+            if !check.passed() {
+                out.push_str(&format!(
+                    "##teamcity[testFailed name='{}' message='{}']\n",
+                    name,
+                    escape(&format!(
+                        "RSR {} check failed: {} - {}",
+                        check.required_for.display_name(),
+                        check.category,
+                        check.item
+                    ))
+                ));
+            }
+            out.push_str(&format!("##teamcity[testFinished name='{}']\n", name));
+        }
+        out.push_str("##teamcity[testSuiteFinished name='rhodibot']\n");
+
+        out.push_str(&format!(
+            "##teamcity[buildStatisticValue key='rhodibot.passed' value='{}']\n",
+            report.passed_count()
+        ));
+        out.push_str(&format!(
+            "##teamcity[buildStatisticValue key='rhodibot.total' value='{}']\n",
+            report.total_count()
+        ));
+        out.push_str(&format!(
+            "##teamcity[buildStatisticValue key='rhodibot.percentage' value='{:.1}']\n",
+            report.percentage()
+        ));
+        for warning_item in &report.warnings {
+            if warning_item.level == WarningLevel::Critical {
+                out.push_str(&format!(
+                    "##teamcity[buildProblem description='{}']\n",
+                    escape(&warning_item.message)
+                ));
+            }
+        }
+        out
+    }
+
+    /// Print the report as TeamCity service messages
+    pub fn output_report(report: &ComplianceReport) {
+        print!("{}", render_report(report));
+    }
+}
+
+/// Tracking issue for failing checks (opt-in with `--track-issue`)
+///
+/// Keeps one open issue titled "RSR compliance: N failing checks" on GitHub
+/// or GitLab, with the Markdown report as its body. A failing run opens it,
+/// or updates the open one; a compliant run closes it. The issue is found by
+/// its `rsr-compliance` label, so it can be renamed or edited freely.
+///
+/// GitHub needs `GITHUB_TOKEN` (with `issues: write`) and the
+/// `GITHUB_REPOSITORY` variable Actions sets. GitLab needs `GITLAB_TOKEN`, a
+/// token with the `api` scope (the job token cannot write issues), and the
+/// `CI_PROJECT_ID` and `CI_API_V4_URL` variables GitLab CI sets.
+pub mod issues {
+    use super::*;
+    use crate::json::{self, Value};
+
+    /// Label that marks the tracking issue
+    pub const LABEL: &str = "rsr-compliance";
+
+    /// What a run does to the tracking issue
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum IssueAction {
+        Open,
+        Update(u64),
+        Close(u64),
+        /// Compliant, and no issue is open
+        Nothing,
+    }
+
+    /// Decide what to do, given the open tracking issue (if any)
+    pub fn plan(open_issue: Option<u64>, compliant: bool) -> IssueAction {
+        match (open_issue, compliant) {
+            (Some(number), true) => IssueAction::Close(number),
+            (Some(number), false) => IssueAction::Update(number),
+            (None, false) => IssueAction::Open,
+            (None, true) => IssueAction::Nothing,
+        }
+    }
+
+    /// Issue title for the report
+    pub fn title(report: &ComplianceReport) -> String {
+        let failing = report.checks.iter().filter(|c| c.is_failure()).count();
+        let noun = if failing == 1 { "check" } else { "checks" };
+        format!("RSR compliance: {} failing {}", failing, noun)
+    }
+
+    /// Issue body for the report
+    pub fn body(report: &ComplianceReport) -> String {
+        format!(
+            "{}\n_Maintained by rhodibot; closed automatically once the repository is \
+             compliant._\n",
+            render_summary_markdown(report)
+        )
+    }
+
+    /// Issue tracker API of a repository
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Tracker {
+        GitHub { api_url: String, repository: String },
+        GitLab { api_url: String, project: String },
+    }
+
+    impl Tracker {
+        /// The tracker for `platform` and its auth header, from the environment
+        pub fn from_env(platform: CIPlatform) -> Result<(Self, String), String> {
+            let var = |name: &str| env::var(name).map_err(|_| format!("{} is not set", name));
+            match platform {
+                CIPlatform::GitHubActions => {
+                    let token = var("GITHUB_TOKEN")?;
+                    let tracker = Tracker::GitHub {
+                        api_url: env::var("GITHUB_API_URL")
+                            .unwrap_or_else(|_| "https://api.github.com".to_string()),
+                        repository: var("GITHUB_REPOSITORY")?,
+                    };
+                    Ok((tracker, format!("Authorization: Bearer {}", token)))
+                }
+                CIPlatform::GitLabCI => {
+                    let token = var("GITLAB_TOKEN")?;
+                    let tracker = Tracker::GitLab {
+                        api_url: var("CI_API_V4_URL")?,
+                        project: var("CI_PROJECT_ID")?,
+                    };
+                    Ok((tracker, format!("PRIVATE-TOKEN: {}", token)))
+                }
+                other => Err(format!(
+                    "tracking issues need GitHub Actions or GitLab CI, not {} (see --ci)",
+                    other.name()
+                )),
+            }
+        }
+
+        /// Headers of an API request: the auth header, and for GitHub the
+        /// media type its API expects
+        pub fn headers<'a>(&self, auth_header: &'a str) -> Vec<&'a str> {
+            match self {
+                Tracker::GitHub { .. } => vec![auth_header, super::GITHUB_ACCEPT],
+                Tracker::GitLab { .. } => vec![auth_header],
+            }
+        }
+
+        /// Base URL of the repository's issues
+        fn issues_url(&self) -> String {
+            match self {
+                Tracker::GitHub { api_url, repository } => {
+                    format!("{}/repos/{}/issues", api_url.trim_end_matches('/'), repository)
+                }
+                Tracker::GitLab { api_url, project } => {
+                    format!("{}/projects/{}/issues", api_url.trim_end_matches('/'), project)
+                }
+            }
+        }
+
+        /// URL listing the open issues with the tracking label
+        pub fn search_url(&self) -> String {
+            let state = match self {
+                Tracker::GitHub { .. } => "open",
+                Tracker::GitLab { .. } => "opened",
+            };
+            format!("{}?state={}&labels={}&per_page=100", self.issues_url(), state, LABEL)
+        }
+
+        /// Number of the open tracking issue in a response to [`Tracker::search_url`]
+        pub fn parse_open_issue(&self, response: &str) -> Result<Option<u64>, String> {
+            let doc = json::parse(response)?;
+            let issues = doc.as_array().ok_or("expected a list of issues")?;
+            let number_key = match self {
+                Tracker::GitHub { .. } => "number",
+                Tracker::GitLab { .. } => "iid",
+            };
+            // GitHub lists pull requests among issues
+            let number = issues
+                .iter()
+                .filter(|issue| issue.get("pull_request").is_none())
+                .find_map(|issue| match issue.get(number_key) {
+                    Some(Value::Number(n)) => Some(*n as u64),
+                    _ => None,
+                });
+            Ok(number)
+        }
+
+        /// Method, URL and JSON body that carry out `action`
+        pub fn request(
+            &self,
+            action: IssueAction,
+            report: &ComplianceReport,
+        ) -> Option<(&'static str, String, String)> {
+            let title = json::string(&title(report));
+            let body = json::string(&body(report));
+            let url = |number: u64| format!("{}/{}", self.issues_url(), number);
+            let request = match (self, action) {
+                (_, IssueAction::Nothing) => return None,
+                (Tracker::GitHub { .. }, IssueAction::Open) => (
+                    "POST",
+                    self.issues_url(),
+                    format!("{{\"title\":{},\"body\":{},\"labels\":[\"{}\"]}}", title, body, LABEL),
+                ),
+                (Tracker::GitHub { .. }, IssueAction::Update(n)) => {
+                    ("PATCH", url(n), format!("{{\"title\":{},\"body\":{}}}", title, body))
+                }
+                (Tracker::GitHub { .. }, IssueAction::Close(n)) => (
+                    "PATCH",
+                    url(n),
+                    format!(
+                        "{{\"state\":\"closed\",\"state_reason\":\"completed\",\"body\":{}}}",
+                        body
+                    ),
+                ),
+                (Tracker::GitLab { .. }, IssueAction::Open) => (
+                    "POST",
+                    self.issues_url(),
+                    format!(
+                        "{{\"title\":{},\"description\":{},\"labels\":\"{}\"}}",
+                        title, body, LABEL
+                    ),
+                ),
+                (Tracker::GitLab { .. }, IssueAction::Update(n)) => (
+                    "PUT",
+                    url(n),
+                    format!("{{\"title\":{},\"description\":{}}}", title, body),
+                ),
+                (Tracker::GitLab { .. }, IssueAction::Close(n)) => (
+                    "PUT",
+                    url(n),
+                    format!("{{\"state_event\":\"close\",\"description\":{}}}", body),
+                ),
+            };
+            Some(request)
+        }
+    }
+
+    /// Open, update or close the tracking issue, returning what was done
+    ///
+    /// `compliant` is whether the run met its policy.
+    pub fn sync(
+        report: &ComplianceReport,
+        platform: CIPlatform,
+        compliant: bool,
+    ) -> Result<IssueAction, String> {
+        let (tracker, auth_header) = Tracker::from_env(platform)?;
+        let headers = tracker.headers(&auth_header);
+        let response = curl_request("GET", &tracker.search_url(), &headers, "")?;
+        let action = plan(tracker.parse_open_issue(&response)?, compliant);
+        if let Some((method, url, body)) = tracker.request(action, report) {
+            curl_request(method, &url, &headers, &body)?;
+        }
+        Ok(action)
+    }
+}
+
+/// Pull request for the files `fix` wrote (`fix --create-pr`)
+///
+/// The files are committed to the `rhodibot/fix` branch, which is pushed to
+/// `origin` (replacing what an earlier run pushed), and a pull request (or
+/// GitLab merge request) is opened from it into the branch the job runs on.
+/// If one is already open, the push updates it. The tokens and variables are
+/// those of [`issues`]; on GitHub the checkout's credentials push the branch,
+/// so the workflow needs `contents: write` as well as `pull-requests: write`.
+pub mod pull_request {
+    use super::issues::Tracker;
+    use super::*;
+    use crate::codec::base64_encode;
+    use crate::fix::Fix;
+    use crate::json::{self, Value};
+
+    /// Branch the files are committed to
+    pub const BRANCH: &str = "rhodibot/fix";
+    /// Title of the pull request and subject of its commit
+    pub const TITLE: &str = "Add missing RSR files";
+    /// Commit identity unless `GIT_AUTHOR_NAME` and friends say otherwise
+    const IDENTITY: &[(&str, &str)] = &[
+        ("GIT_AUTHOR_NAME", "rhodibot"),
+        ("GIT_AUTHOR_EMAIL", "rhodibot@users.noreply.github.com"),
+        ("GIT_COMMITTER_NAME", "rhodibot"),
+        ("GIT_COMMITTER_EMAIL", "rhodibot@users.noreply.github.com"),
+    ];
+
+    /// What happened to the pull request, with its web URL
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Outcome {
+        Opened(String),
+        /// An open pull request from [`BRANCH`] now has the new commit
+        Updated(String),
+    }
+
+    /// Pull request description listing the files
+    pub fn body(fixes: &[Fix]) -> String {
+        let mut body = String::from("Rhodibot created these files for failing RSR checks:\n\n");
+        for fix in fixes {
+            body.push_str(&format!("- `{}` ({})\n", fix.path.display(), fix.id));
+        }
+        body.push_str(
+            "\nEach is a starter version; replace the `TODO(rhodibot)` placeholders before \
+             merging.\n",
         );
+        body
+    }
+
+    /// URL listing open pull requests from [`BRANCH`]
+    pub fn search_url(tracker: &Tracker) -> String {
+        match tracker {
+            Tracker::GitHub { api_url, repository } => {
+                let owner = repository.split('/').next().unwrap_or_default();
+                format!(
+                    "{}/repos/{}/pulls?state=open&head={}:{}",
+                    api_url.trim_end_matches('/'),
+                    repository,
+                    owner,
+                    BRANCH
+                )
+            }
+            Tracker::GitLab { api_url, project } => format!(
+                "{}/projects/{}/merge_requests?state=opened&source_branch={}",
+                api_url.trim_end_matches('/'),
+                project,
+                BRANCH
+            ),
+        }
+    }
+
+    /// Web URL of a pull request, or of the first in a list of them
+    pub fn web_url(response: &str) -> Result<Option<String>, String> {
+        let doc = json::parse(response)?;
+        let request = match doc.as_array() {
+            Some(list) => list.first(),
+            None => Some(&doc),
+        };
+        let url = request.and_then(|r| {
+            // GitHub calls it html_url, GitLab web_url
+            let url = r.get("html_url").or_else(|| r.get("web_url"));
+            url.and_then(Value::as_str).map(str::to_string)
+        });
+        Ok(url)
+    }
+
+    /// URL and JSON body that open a pull request from [`BRANCH`] into `base`
+    pub fn create_request(tracker: &Tracker, base: &str, fixes: &[Fix]) -> (String, String) {
+        let title = json::string(TITLE);
+        let body = json::string(&body(fixes));
+        match tracker {
+            Tracker::GitHub { api_url, repository } => (
+                format!("{}/repos/{}/pulls", api_url.trim_end_matches('/'), repository),
+                format!(
+                    "{{\"title\":{},\"head\":\"{}\",\"base\":{},\"body\":{}}}",
+                    title,
+                    BRANCH,
+                    json::string(base),
+                    body
+                ),
+            ),
+            Tracker::GitLab { api_url, project } => (
+                format!("{}/projects/{}/merge_requests", api_url.trim_end_matches('/'), project),
+                format!(
+                    "{{\"source_branch\":\"{}\",\"target_branch\":{},\"title\":{},\
+                     \"description\":{},\"remove_source_branch\":true}}",
+                    BRANCH,
+                    json::string(base),
+                    title,
+                    body
+                ),
+            ),
+        }
+    }
+
+    /// Branch the job runs on, which the pull request targets
+    fn base_branch(platform: CIPlatform) -> Result<String, String> {
+        let name = match platform {
+            CIPlatform::GitHubActions => "GITHUB_REF_NAME",
+            _ => "CI_COMMIT_BRANCH",
+        };
+        env::var(name)
+            .ok()
+            .filter(|branch| !branch.is_empty())
+            .ok_or_else(|| format!("{} is not set; run the job on a branch", name))
+    }
+
+    /// Run git in `repo_path` with `envs` set
+    fn git(repo_path: &Path, args: &[&str], envs: &[(&str, String)]) -> Result<(), String> {
+        let mut command = Command::new("git");
+        command
+            .arg("-C")
+            .arg(repo_path)
+            .args(args)
+            .envs(envs.iter().map(|(k, v)| (*k, v.as_str())))
+            // Never block on a credential prompt
+            .env("GIT_TERMINAL_PROMPT", "0");
+        let output = subprocess::run(&mut command, None, GIT_TIMEOUT).map_err(|e| {
+            match e.kind() {
+                io::ErrorKind::TimedOut => format!("git {} {}", args[0], e),
+                _ => format!("cannot run git: {}", e),
+            }
+        })?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    /// Commit `fixes` to [`BRANCH`] and push it, then check out the
+    /// previous branch again
+    ///
+    /// `push_header` authenticates the push; it goes through git's
+    /// environment so the token never appears in the process list.
+    fn push(repo_path: &Path, fixes: &[Fix], push_header: Option<&str>) -> Result<(), String> {
+        let mut envs: Vec<(&str, String)> = IDENTITY
+            .iter()
+            .filter(|(name, _)| env::var_os(name).is_none())
+            .map(|(name, value)| (*name, value.to_string()))
+            .collect();
+        if let Some(header) = push_header {
+            envs.push(("GIT_CONFIG_COUNT", "1".to_string()));
+            envs.push(("GIT_CONFIG_KEY_0", "http.extraHeader".to_string()));
+            envs.push(("GIT_CONFIG_VALUE_0", header.to_string()));
+        }
+        let paths: Vec<String> = fixes.iter().map(|f| f.path.display().to_string()).collect();
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+
+        git(repo_path, &["checkout", "-q", "-B", BRANCH], &envs)?;
+        let result = git(repo_path, &[&["add", "--"][..], &paths].concat(), &envs)
+            .and_then(|()| {
+                let commit = [&["commit", "-q", "-m", TITLE, "--"][..], &paths].concat();
+                git(repo_path, &commit, &envs)
+            })
+            .and_then(|()| git(repo_path, &["push", "-q", "--force", "origin", BRANCH], &envs));
+        let _ = git(repo_path, &["checkout", "-q", "-"], &envs);
+        result
+    }
+
+    /// Commit `fixes` (already written) and open or update the pull request
+    ///
+    /// Fails before touching the repository if the CI environment lacks the
+    /// token or variables, so the files are then simply left uncommitted.
+    pub fn open(repo_path: &Path, fixes: &[Fix], platform: CIPlatform) -> Result<Outcome, String> {
+        let (tracker, auth_header) = Tracker::from_env(platform)?;
+        let base = base_branch(platform)?;
+        let push_header = match tracker {
+            // The checkout already holds credentials for origin
+            Tracker::GitHub { .. } => None,
+            Tracker::GitLab { .. } => {
+                let token = auth_header.trim_start_matches("PRIVATE-TOKEN: ");
+                let basic = base64_encode(format!("oauth2:{}", token).as_bytes());
+                Some(format!("Authorization: Basic {}", basic))
+            }
+        };
+        push(repo_path, fixes, push_header.as_deref())?;
+
+        let headers = tracker.headers(&auth_header);
+        let existing = curl_request("GET", &search_url(&tracker), &headers, "")?;
+        if let Some(url) = web_url(&existing)? {
+            return Ok(Outcome::Updated(url));
+        }
+        let (url, body) = create_request(&tracker, &base, fixes);
+        let created = curl_request("POST", &url, &headers, &body)?;
+        Ok(Outcome::Opened(web_url(&created)?.unwrap_or(url)))
     }
 }
 
@@ -269,7 +1294,7 @@ pub fn generate_github_actions_workflow() -> String {
         uses: actions/checkout@v4
 
       - name: Install Rust
-        uses: dtolnay/rust-action@stable
+        uses: dtolnay/rust-toolchain@stable
 
       - name: Install Rhodibot
         run: cargo install rhodibot
@@ -325,6 +1350,115 @@ pub fn generate_gitlab_ci_config() -> String {
 "#.to_string()
 }
 
+/// Write the CI configuration for `platform` into the repository at
+/// `repo_path`, returning its path
+///
+/// An existing file is only replaced with `force`, since `.gitlab-ci.yml`
+/// in particular usually holds the project's other jobs.
+pub fn setup_ci(repo_path: &Path, platform: CIPlatform, force: bool) -> Result<PathBuf, String> {
+    let (Some(file), Some(config)) = (platform.config_file(), platform.generate_config()) else {
+        return Err(format!("no CI configuration available for {}", platform.name()));
+    };
+    let path = repo_path.join(file);
+    if path.exists() && !force {
+        return Err(format!(
+            "{} already exists; pass --force to replace it",
+            path.display()
+        ));
+    }
+    write_atomic(&path, &config).map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// File name of the composite action written by `generate-action`
+pub const ACTION_FILE: &str = "action.yml";
+
+/// Generate a GitHub Actions composite action wrapping the binary
+///
+/// Placed at the root of a repository, it lets workflows use that
+/// repository as a step (`uses: hyperpolymath/rhodibot@v1`). Inputs reach
+/// the binary through its `RHODIBOT_*` environment variables rather than
+/// being spliced into the script, so they cannot inject shell commands.
+/// The outputs are the ones `set_outputs` writes under GitHub Actions.
+pub fn generate_github_action() -> String {
+    r#"# Rhodibot RSR Compliance Check
+# Composite action generated by `rhodibot generate-action`
+
+name: 'Rhodibot RSR Compliance'
+description: 'Check a repository for Rhodium Standard Repository compliance'
+author: 'hyperpolymath'
+branding:
+  icon: 'check-circle'
+  color: 'orange'
+
+inputs:
+  path:
+    description: 'Repository path to check'
+    required: false
+    default: '.'
+  level:
+    description: 'Compliance level required for success: bronze, silver, gold or platinum
+      (default: fail_on_level from .rhodibot.toml)'
+    required: false
+    default: ''
+  format:
+    description: 'Output format: human, json, sarif, tap, codequality or prometheus'
+    required: false
+    default: 'human'
+  fail-on-warning:
+    description: 'Fail on Warning-level security warnings (true or false)'
+    required: false
+    default: ''
+  version:
+    description: 'rhodibot version to install if it is not on PATH (default: latest)'
+    required: false
+    default: ''
+
+outputs:
+  passed:
+    description: 'Number of checks passed'
+    value: ${{ steps.check.outputs.passed }}
+  total:
+    description: 'Number of checks run'
+    value: ${{ steps.check.outputs.total }}
+  percentage:
+    description: 'Percentage of checks passed'
+    value: ${{ steps.check.outputs.percentage }}
+  bronze_compliant:
+    description: 'Whether Bronze compliance is met (true or false)'
+    value: ${{ steps.check.outputs.bronze_compliant }}
+  has_warnings:
+    description: 'Whether there are critical security warnings (true or false)'
+    value: ${{ steps.check.outputs.has_warnings }}
+  level_achieved:
+    description: 'Highest compliance level achieved (bronze, silver, ... or none)'
+    value: ${{ steps.check.outputs.level_achieved }}
+
+runs:
+  using: 'composite'
+  steps:
+    - name: Install rhodibot
+      shell: bash
+      env:
+        RHODIBOT_VERSION: ${{ inputs.version }}
+      run: |
+        if ! command -v rhodibot >/dev/null 2>&1; then
+          cargo install rhodibot --locked ${RHODIBOT_VERSION:+--version "$RHODIBOT_VERSION"}
+        fi
+
+    - name: Run RSR compliance check
+      id: check
+      shell: bash
+      env:
+        CHECK_PATH: ${{ inputs.path }}
+        RHODIBOT_LEVEL: ${{ inputs.level }}
+        RHODIBOT_FORMAT: ${{ inputs.format }}
+        RHODIBOT_FAIL_ON_WARNING: ${{ inputs.fail-on-warning }}
+        RHODIBOT_CI: github
+      run: rhodibot check "$CHECK_PATH"
+"#.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -337,6 +1471,267 @@ fn test_ci_platform_detection() {
         let _ = platform.name();
     }
 
+    #[test]
+    fn test_sarif_upload_body() {
+        let body = github_actions::sarif_upload_body("{}", "abc123", "refs/heads/main");
+        assert!(body.starts_with("{\"commit_sha\":\"abc123\",\"ref\":\"refs/heads/main\""));
+        // gzip magic bytes 1f 8b 08 encode to "H4sI" in base64
+        assert!(body.contains("\"sarif\":\"H4sI"));
+        assert!(body.ends_with("\"tool_name\":\"rhodibot\"}"));
+    }
+
+    #[test]
+    fn test_write_private() {
+        let dir = write_private(REQUEST_BODY, b"{}").unwrap();
+        assert_eq!(fs::read_to_string(dir.join(REQUEST_BODY)).unwrap(), "{}");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&dir).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_curl_config_quote() {
+        assert_eq!(curl_config_quote("Authorization: Bearer t"), "\"Authorization: Bearer t\"");
+        assert_eq!(
+            curl_config_quote("{\"body\":\"a\\\\b\nc\"}"),
+            r#""{\"body\":\"a\\\\b\nc\"}""#
+        );
+    }
+
+    #[test]
+    fn test_github_annotations() {
+        use crate::ComplianceLevel;
+        use std::path::PathBuf;
+
+        assert_eq!(github_actions::escape_data("100%\r\nok"), "100%25%0D%0Aok");
+        assert_eq!(github_actions::escape_property("a:b,c"), "a%3Ab%2Cc");
+        assert_eq!(
+            github_actions::annotation("error", "bad\nthing", Some("src/a,b.rs"), Some(3)),
+            "::error file=src/a%2Cb.rs,line=3::bad%0Athing"
+        );
+
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_check("Build System", "justfile", false, ComplianceLevel::Bronze);
+        report.add_check("Build System", "flake.nix", false, ComplianceLevel::Bronze);
+        report.checks[2].status = CheckStatus::NotApplicable;
+        report.add_warning(WarningLevel::Info, "Large file", None);
+
+        let out = github_actions::render_annotations(&report);
+        assert!(out.starts_with("::group::Documentation\n✓ README.md"));
+        assert!(out.contains(
+            "::group::Build System\n✗ justfile (rsr/build-system/justfile)\n::warning::RSR check failed: Build System - justfile\n\
+             - flake.nix (rsr/build-system/flake.nix)\n::endgroup::\n"
+        ));
+        assert!(out.ends_with("::notice::Large file\n"));
+
+        report.add_warning(WarningLevel::Warning, "Symlink", Some(PathBuf::from("/repo/a/b")));
+        let out = github_actions::render_annotations(&report);
+        assert!(out.ends_with("::warning file=a/b::Symlink\n"), "{}", out);
+
+        let summary = render_summary_markdown(&report);
+        assert!(summary.contains("| Build System | justfile | ❌ |\n"));
+        assert!(summary.contains("| Build System | flake.nix | ⏭️ |\n"));
+    }
+
+    #[test]
+    fn test_gitlab_dotenv() {
+        use crate::ComplianceLevel;
+        use std::path::PathBuf;
+
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_check("Documentation", "FUNDING.yml", false, ComplianceLevel::Silver);
+        let env = gitlab_ci::render_dotenv(&report);
+        assert!(env.contains("RHODIBOT_PASSED=1\n"));
+        assert!(env.contains("RHODIBOT_BRONZE_COMPLIANT=true\n"));
+        assert!(env.contains("RHODIBOT_LEVEL=bronze\n"));
+
+        let path = std::env::temp_dir().join("rhodibot_test_dotenv/rhodibot.env");
+        gitlab_ci::write_dotenv(&report, &path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), env);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_jenkins_and_circleci_adapters() {
+        use crate::ComplianceLevel;
+        use std::path::PathBuf;
+
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", false, ComplianceLevel::Bronze);
+        report.add_warning(WarningLevel::Warning, "Large file", None);
+
+        let markers = jenkins::render_markers(&report);
+        assert!(markers.contains(
+            "[rhodibot] ERROR: rsr/documentation/readme.md RSR Bronze check failed: Documentation - README.md\n"
+        ));
+        assert!(markers.contains("[rhodibot] WARNING: Large file\n"));
+
+        let dir = std::env::temp_dir().join("rhodibot_test_ci_results");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = circleci::write_test_results(&report, &dir).unwrap();
+        assert_eq!(path, dir.join("rhodibot/results.xml"));
+        assert!(std::fs::read_to_string(&path).unwrap().contains("<failure "));
+
+        jenkins::write_junit(&report, &dir.join("junit.xml")).unwrap();
+        assert!(dir.join("junit.xml").is_file());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_azure_devops_and_buildkite_adapters() {
+        use crate::ComplianceLevel;
+        use std::path::PathBuf;
+
+        assert_eq!(azure_devops::escape_data("100%\nok"), "100%AZP25%0Aok");
+        assert_eq!(azure_devops::escape_property("a;b]"), "a%3Bb%5D");
+
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", false, ComplianceLevel::Bronze);
+        report.add_warning(
+            WarningLevel::Critical,
+            "Symlink escapes",
+            Some(PathBuf::from("/repo/link")),
+        );
+        let issues = azure_devops::render_issues(&report);
+        assert!(issues.contains(
+            "##vso[task.logissue type=warning;code=rsr/documentation/readme.md]RSR check failed: Documentation - README.md\n"
+        ));
+        assert!(issues.contains("##vso[task.logissue type=error;sourcepath=link;]Symlink escapes\n"));
+
+        assert_eq!(buildkite::annotation_style(&report), "error");
+        let path = std::env::temp_dir().join("rhodibot_test_buildkite/annotation.md");
+        buildkite::write_annotation(&report, &path).unwrap();
+        let md = std::fs::read_to_string(&path).unwrap();
+        assert!(md.starts_with("## 🤖 Rhodibot RSR Compliance Report"));
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_teamcity_service_messages() {
+        use crate::ComplianceLevel;
+        use std::path::PathBuf;
+
+        assert_eq!(teamcity::escape("a|b'c[d]\ne"), "a||b|'c|[d|]|ne");
+
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_check("Documentation", "LICENSE.txt", false, ComplianceLevel::Bronze);
+        report.add_warning(WarningLevel::Critical, "Symlink 'x' escapes", None);
+
+        let out = teamcity::render_report(&report);
+        assert_eq!(out.matches("##teamcity[testStarted").count(), 2);
+        assert_eq!(out.matches("##teamcity[testFailed").count(), 1);
+        assert!(out.contains("##teamcity[buildStatisticValue key='rhodibot.passed' value='1']\n"));
+        assert!(out.contains("##teamcity[buildStatisticValue key='rhodibot.total' value='2']\n"));
+        assert!(out.contains("value='50.0'"));
+        assert!(out.contains("##teamcity[buildProblem description='Symlink |'x|' escapes']"));
+    }
+
+    #[test]
+    fn test_tracking_issue() {
+        use crate::ComplianceLevel;
+        use issues::{IssueAction, Tracker};
+        use std::path::PathBuf;
+
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", false, ComplianceLevel::Bronze);
+        assert_eq!(issues::title(&report), "RSR compliance: 1 failing check");
+        report.add_check("Build System", "justfile", false, ComplianceLevel::Bronze);
+        assert_eq!(issues::title(&report), "RSR compliance: 2 failing checks");
+
+        assert_eq!(issues::plan(None, false), IssueAction::Open);
+        assert_eq!(issues::plan(Some(7), false), IssueAction::Update(7));
+        assert_eq!(issues::plan(Some(7), true), IssueAction::Close(7));
+        assert_eq!(issues::plan(None, true), IssueAction::Nothing);
+
+        let github = Tracker::GitHub {
+            api_url: "https://api.github.com/".to_string(),
+            repository: "org/repo".to_string(),
+        };
+        assert_eq!(
+            github.search_url(),
+            "https://api.github.com/repos/org/repo/issues\
+             ?state=open&labels=rsr-compliance&per_page=100"
+        );
+        let listed = r#"[{"number": 3, "pull_request": {}}, {"number": 12}]"#;
+        assert_eq!(github.parse_open_issue(listed), Ok(Some(12)));
+        assert_eq!(github.parse_open_issue("[]"), Ok(None));
+        assert!(github.parse_open_issue("{}").is_err());
+        let (method, url, body) = github.request(IssueAction::Open, &report).unwrap();
+        assert_eq!(method, "POST");
+        assert_eq!(url, "https://api.github.com/repos/org/repo/issues");
+        assert!(body.starts_with("{\"title\":\"RSR compliance: 2 failing checks\",\"body\":"));
+        assert!(body.ends_with(",\"labels\":[\"rsr-compliance\"]}"));
+        let (method, url, body) = github.request(IssueAction::Close(12), &report).unwrap();
+        assert_eq!(method, "PATCH");
+        assert_eq!(url, "https://api.github.com/repos/org/repo/issues/12");
+        assert!(body.starts_with("{\"state\":\"closed\","));
+        assert_eq!(github.headers("Authorization: x"), ["Authorization: x", GITHUB_ACCEPT]);
+
+        let gitlab = Tracker::GitLab {
+            api_url: "https://gitlab.com/api/v4".to_string(),
+            project: "42".to_string(),
+        };
+        assert_eq!(gitlab.parse_open_issue(r#"[{"iid": 5, "id": 900}]"#), Ok(Some(5)));
+        let (method, url, body) = gitlab.request(IssueAction::Update(5), &report).unwrap();
+        assert_eq!(method, "PUT");
+        assert_eq!(url, "https://gitlab.com/api/v4/projects/42/issues/5");
+        assert!(body.contains("\"description\":"));
+        assert!(gitlab.request(IssueAction::Nothing, &report).is_none());
+        assert_eq!(gitlab.headers("PRIVATE-TOKEN: x"), ["PRIVATE-TOKEN: x"]);
+        assert!(Tracker::from_env(CIPlatform::Jenkins).is_err());
+    }
+
+    #[test]
+    fn test_pull_request() {
+        use crate::fix::Fix;
+        use issues::Tracker;
+        use std::path::PathBuf;
+
+        let fixes = [Fix {
+            id: "RSR-DOC-003".to_string(),
+            path: PathBuf::from("SECURITY.md"),
+            contents: String::new(),
+        }];
+        assert!(pull_request::body(&fixes).contains("\n- `SECURITY.md` (RSR-DOC-003)\n"));
+
+        let github = Tracker::GitHub {
+            api_url: "https://api.github.com".to_string(),
+            repository: "org/repo".to_string(),
+        };
+        assert_eq!(
+            pull_request::search_url(&github),
+            "https://api.github.com/repos/org/repo/pulls?state=open&head=org:rhodibot/fix"
+        );
+        let (url, body) = pull_request::create_request(&github, "main", &fixes);
+        assert_eq!(url, "https://api.github.com/repos/org/repo/pulls");
+        assert!(body.starts_with(
+            "{\"title\":\"Add missing RSR files\",\"head\":\"rhodibot/fix\",\"base\":\"main\","
+        ));
+
+        let gitlab = Tracker::GitLab {
+            api_url: "https://gitlab.com/api/v4".to_string(),
+            project: "42".to_string(),
+        };
+        let (url, body) = pull_request::create_request(&gitlab, "main", &fixes);
+        assert_eq!(url, "https://gitlab.com/api/v4/projects/42/merge_requests");
+        assert!(body.starts_with("{\"source_branch\":\"rhodibot/fix\",\"target_branch\":"));
+        assert!(body.ends_with(",\"remove_source_branch\":true}"));
+
+        let listed = r#"[{"number": 9, "html_url": "https://github.com/org/repo/pull/9"}]"#;
+        let url = pull_request::web_url(listed).unwrap();
+        assert_eq!(url.as_deref(), Some("https://github.com/org/repo/pull/9"));
+        let created = r#"{"iid": 3, "web_url": "https://gitlab.com/g/p/-/merge_requests/3"}"#;
+        assert!(pull_request::web_url(created).unwrap().unwrap().ends_with("/3"));
+        assert_eq!(pull_request::web_url("[]"), Ok(None));
+    }
+
     #[test]
     fn test_generate_github_workflow() {
         let workflow = generate_github_actions_workflow();
@@ -344,6 +1739,43 @@ fn test_generate_github_workflow() {
         assert!(workflow.contains("actions/checkout"));
     }
 
+    #[test]
+    fn test_generate_github_action() {
+        let action = generate_github_action();
+        assert!(action.contains("  using: 'composite'\n"));
+        for input in ["path", "level", "format", "fail-on-warning"] {
+            assert!(action.contains(&format!("\n  {}:\n    description: ", input)));
+        }
+        // Every output set under GitHub Actions is declared
+        for output in ["passed", "total", "percentage", "bronze_compliant", "has_warnings"] {
+            assert!(action.contains(&format!("value: ${{{{ steps.check.outputs.{} }}}}", output)));
+        }
+        // Inputs only reach the script through the environment
+        let run_lines = action.lines().filter(|line| line.contains("rhodibot check"));
+        assert!(run_lines.into_iter().all(|line| !line.contains("${{")));
+        // The action shipped with the crate is the generated one
+        assert_eq!(include_str!("../action.yml"), action);
+    }
+
+    #[test]
+    fn test_setup_ci() {
+        let dir = env::temp_dir().join("rhodibot_setup_ci");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let path = setup_ci(&dir, CIPlatform::GitHubActions, false).unwrap();
+        assert_eq!(path, dir.join(".github/workflows/rhodibot.yml"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), generate_github_actions_workflow());
+
+        std::fs::write(dir.join(".gitlab-ci.yml"), "build:\n  script: make\n").unwrap();
+        let err = setup_ci(&dir, CIPlatform::GitLabCI, false).unwrap_err();
+        assert!(err.ends_with(".gitlab-ci.yml already exists; pass --force to replace it"));
+        let path = setup_ci(&dir, CIPlatform::GitLabCI, true).unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), generate_gitlab_ci_config());
+
+        assert!(setup_ci(&dir, CIPlatform::Jenkins, true).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_generate_gitlab_config() {
         let config = generate_gitlab_ci_config();
@@ -0,0 +1,269 @@
+//! Assembling reports outside of `verify_repository`
+//!
+//! Tools that run their own checks, or post-process a verification result,
+//! build a [`ComplianceReport`] with a [`ReportBuilder`]. The builder can
+//! declare the order categories are displayed in and attach
+//! [`ReportMetadata`] (commit, branch, remote, CI run) that the JSON, SARIF and HTML
+//! renderers include. [`ReportBuilder::build`] groups the checks by category
+//! in the declared order; the finished report is a plain value, so nothing
+//! the builder guaranteed changes unless the caller mutates it.
+
+use crate::catalog::CheckSpec;
+use crate::{CheckStatus, ComplianceLevel, ComplianceReport, StandardVersion, WarningLevel};
+use std::path::PathBuf;
+
+/// Metadata key for the commit SHA that was verified
+pub const COMMIT_KEY: &str = "commit";
+/// Metadata key for the branch that was verified
+pub const BRANCH_KEY: &str = "branch";
+/// Metadata key for the URL of the repository's remote
+pub const REMOTE_KEY: &str = "remote";
+/// Metadata key for the URL of the CI run that produced the report
+pub const CI_RUN_URL_KEY: &str = "ci_run_url";
+
+/// Where a report came from, for tracing it back to a revision and CI run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportMetadata {
+    /// Commit SHA that was verified
+    pub commit: Option<String>,
+    /// Branch that was verified
+    pub branch: Option<String>,
+    /// URL of the repository's remote
+    pub remote: Option<String>,
+    /// URL of the CI run that produced the report
+    pub ci_run_url: Option<String>,
+    /// Further key/value pairs, in the order they were set
+    pub extra: Vec<(String, String)>,
+}
+
+impl ReportMetadata {
+    /// No metadata was set; renderers then leave the section out
+    pub fn is_empty(&self) -> bool {
+        self.commit.is_none()
+            && self.branch.is_none()
+            && self.remote.is_none()
+            && self.ci_run_url.is_none()
+            && self.extra.is_empty()
+    }
+
+    /// Set `key`, replacing an earlier value
+    ///
+    /// `commit`, `branch`, `remote` and `ci_run_url` fill their fields; any other key
+    /// goes into [`ReportMetadata::extra`].
+    pub fn set(&mut self, key: &str, value: &str) {
+        let value = value.to_string();
+        match key {
+            COMMIT_KEY => self.commit = Some(value),
+            BRANCH_KEY => self.branch = Some(value),
+            REMOTE_KEY => self.remote = Some(value),
+            CI_RUN_URL_KEY => self.ci_run_url = Some(value),
+            _ => match self.extra.iter_mut().find(|(k, _)| k == key) {
+                Some(entry) => entry.1 = value,
+                None => self.extra.push((key.to_string(), value)),
+            },
+        }
+    }
+
+    /// Value of `key`, if set
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries().into_iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// All entries as `(key, value)`, the known keys first
+    pub fn entries(&self) -> Vec<(&str, &str)> {
+        let known = [
+            (COMMIT_KEY, &self.commit),
+            (BRANCH_KEY, &self.branch),
+            (REMOTE_KEY, &self.remote),
+            (CI_RUN_URL_KEY, &self.ci_run_url),
+        ];
+        known
+            .into_iter()
+            .filter_map(|(key, value)| value.as_deref().map(|v| (key, v)))
+            .chain(self.extra.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+            .collect()
+    }
+}
+
+/// Builder for a [`ComplianceReport`]
+///
+/// ```rust
+/// use rhodibot::builder::ReportBuilder;
+/// use rhodibot::ComplianceLevel;
+/// use std::path::PathBuf;
+///
+/// let report = ReportBuilder::new(PathBuf::from("/srv/app"))
+///     .category_order(&["Security", "Documentation"])
+///     .commit("4f2a9c1")
+///     .branch("main")
+///     .check("Documentation", "README.md", true, ComplianceLevel::Bronze)
+///     .check("Security", "SECURITY.md", false, ComplianceLevel::Bronze)
+///     .build();
+/// assert_eq!(report.checks[0].category, "Security");
+/// assert_eq!(report.metadata.commit.as_deref(), Some("4f2a9c1"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReportBuilder {
+    report: ComplianceReport,
+    category_order: Vec<String>,
+}
+
+impl ReportBuilder {
+    /// Start an empty report for the repository at `repository_path`
+    pub fn new(repository_path: PathBuf) -> Self {
+        Self::from_report(ComplianceReport::new(repository_path))
+    }
+
+    /// Start from an existing report, e.g. the result of a verification
+    pub fn from_report(report: ComplianceReport) -> Self {
+        Self {
+            report,
+            category_order: Vec::new(),
+        }
+    }
+
+    /// Display categories in this order
+    ///
+    /// Categories not listed follow the listed ones, in the order their
+    /// first check was added; checks within a category keep their order.
+    pub fn category_order<S: AsRef<str>>(mut self, categories: &[S]) -> Self {
+        self.category_order = categories.iter().map(|c| c.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Record the commit SHA that was verified
+    pub fn commit(self, sha: &str) -> Self {
+        self.metadata(COMMIT_KEY, sha)
+    }
+
+    /// Record the branch that was verified
+    pub fn branch(self, name: &str) -> Self {
+        self.metadata(BRANCH_KEY, name)
+    }
+
+    /// Record the URL of the repository's remote
+    pub fn remote(self, url: &str) -> Self {
+        self.metadata(REMOTE_KEY, url)
+    }
+
+    /// Record the URL of the CI run producing the report
+    pub fn ci_run_url(self, url: &str) -> Self {
+        self.metadata(CI_RUN_URL_KEY, url)
+    }
+
+    /// Attach a metadata entry (see [`ReportMetadata::set`])
+    pub fn metadata(mut self, key: &str, value: &str) -> Self {
+        self.report.metadata.set(key, value);
+        self
+    }
+
+    /// Version of the RSR standard the checks were made against
+    pub fn standard_version(mut self, version: StandardVersion) -> Self {
+        self.report.standard_version = version;
+        self
+    }
+
+    /// Add the result of a catalog check
+    pub fn spec_check(mut self, spec: &CheckSpec, passed: bool) -> Self {
+        self.report.add_spec_check(spec, passed);
+        self
+    }
+
+    /// Add a catalog check with a status other than pass or fail, e.g. one
+    /// that does not apply to the repository
+    pub fn spec_check_status(mut self, spec: &CheckSpec, status: CheckStatus) -> Self {
+        self.report.add_spec_check(spec, false);
+        if let Some(check) = self.report.checks.last_mut() {
+            check.status = status;
+        }
+        self
+    }
+
+    /// Add the result of an ad-hoc check
+    pub fn check(
+        mut self,
+        category: &str,
+        item: &str,
+        passed: bool,
+        level: ComplianceLevel,
+    ) -> Self {
+        self.report.add_check(category, item, passed, level);
+        self
+    }
+
+    /// Add a security warning
+    pub fn warning(mut self, level: WarningLevel, message: &str, path: Option<PathBuf>) -> Self {
+        self.report.add_warning(level, message, path);
+        self
+    }
+
+    /// Finish the report, with checks grouped in the declared category order
+    pub fn build(self) -> ComplianceReport {
+        let mut report = self.report;
+        let mut categories = self.category_order;
+        for check in &report.checks {
+            if !categories.contains(&check.category) {
+                categories.push(check.category.clone());
+            }
+        }
+        // Stable, so checks keep their order within a category
+        report
+            .checks
+            .sort_by_key(|check| categories.iter().position(|c| *c == check.category));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog;
+
+    #[test]
+    fn test_category_order() {
+        let report = ReportBuilder::new(PathBuf::from("/srv/app"))
+            .category_order(&["Well-Known", "Documentation"])
+            .check("Build System", "justfile", true, ComplianceLevel::Bronze)
+            .spec_check(catalog::lookup("RSR-DOC-001").unwrap(), true)
+            .check("Well-Known", "security.txt", false, ComplianceLevel::Bronze)
+            .check("Build System", "flake.nix", false, ComplianceLevel::Silver)
+            .spec_check(catalog::lookup("RSR-DOC-002").unwrap(), false)
+            .build();
+        let order: Vec<(&str, &str)> = report
+            .checks
+            .iter()
+            .map(|c| (c.category.as_str(), c.item.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("Well-Known", "security.txt"),
+                ("Documentation", "README.md"),
+                ("Documentation", "LICENSE.txt"),
+                ("Build System", "justfile"),
+                ("Build System", "flake.nix"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_metadata() {
+        let report = ReportBuilder::new(PathBuf::from("/srv/app"))
+            .ci_run_url("https://ci.example/runs/7")
+            .metadata("pipeline", "nightly")
+            .commit("abc123")
+            .metadata("pipeline", "release")
+            .build();
+        assert_eq!(
+            report.metadata.entries(),
+            vec![
+                ("commit", "abc123"),
+                ("ci_run_url", "https://ci.example/runs/7"),
+                ("pipeline", "release"),
+            ]
+        );
+        assert_eq!(report.metadata.get("pipeline"), Some("release"));
+        assert!(report.metadata.get("branch").is_none());
+        assert!(ComplianceReport::new(PathBuf::from("/srv/app")).metadata.is_empty());
+    }
+}
@@ -0,0 +1,545 @@
+//! Small std-only encoders
+//!
+//! Rhodibot has no dependencies, so the handful of binary encodings needed by
+//! the CI integrations (gzip, base64) and report signing (SHA-256, HMAC, hex)
+//! are implemented here, as is the zlib decoder that reads git's loose
+//! objects.
+
+/// CRC-32 (IEEE 802.3) checksum as used by gzip
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xffff_ffff;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Wrap `data` in a gzip container using stored (uncompressed) deflate blocks
+///
+/// The output is valid gzip that any decoder accepts. It is not compressed,
+/// which keeps the implementation small; reports are only a few kilobytes.
+pub fn gzip_store(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xffff;
+
+    let mut out = Vec::with_capacity(data.len() + 32);
+    // Header: magic, deflate method, no flags, no mtime, no extra flags, unknown OS
+    out.extend_from_slice(&[0x1f, 0x8b, 0x08, 0x00, 0, 0, 0, 0, 0x00, 0xff]);
+
+    let mut chunks = data.chunks(MAX_BLOCK).peekable();
+    if chunks.peek().is_none() {
+        // A single, final, empty stored block
+        out.extend_from_slice(&[0x01, 0x00, 0x00, 0xff, 0xff]);
+    }
+    while let Some(chunk) = chunks.next() {
+        let is_final = chunks.peek().is_none();
+        let len = chunk.len() as u16;
+        out.push(u8::from(is_final));
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(chunk);
+    }
+
+    out.extend_from_slice(&crc32(data).to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out
+}
+
+/// Standard base64 encoding with padding (RFC 4648)
+pub fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        if chunk.len() > 1 {
+            out.push(ALPHABET[(n >> 6) as usize & 63] as char);
+        } else {
+            out.push('=');
+        }
+        if chunk.len() > 2 {
+            out.push(ALPHABET[n as usize & 63] as char);
+        } else {
+            out.push('=');
+        }
+    }
+    out
+}
+
+/// SHA-256 round constants (FIPS 180-4)
+static SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 digest (FIPS 180-4)
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(SHA256_K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (state, value) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0u8; 32];
+    for (out, word) in digest.chunks_mut(4).zip(h) {
+        out.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// HMAC-SHA256 (RFC 2104)
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    const BLOCK: usize = 64;
+
+    let mut block_key = [0u8; BLOCK];
+    if key.len() > BLOCK {
+        block_key[..32].copy_from_slice(&sha256(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner: Vec<u8> = block_key.iter().map(|b| b ^ 0x36).collect();
+    inner.extend_from_slice(data);
+    let mut outer: Vec<u8> = block_key.iter().map(|b| b ^ 0x5c).collect();
+    outer.extend_from_slice(&sha256(&inner));
+    sha256(&outer)
+}
+
+/// Lowercase hexadecimal encoding
+pub fn hex_encode(data: &[u8]) -> String {
+    use std::fmt::Write;
+
+    data.iter().fold(String::with_capacity(data.len() * 2), |mut out, b| {
+        let _ = write!(out, "{:02x}", b);
+        out
+    })
+}
+
+/// Decode hexadecimal (either case); `None` for odd lengths or other characters
+pub fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Compare two byte strings in time independent of where they differ
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Base length of the deflate length symbols 257..=285
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+/// Base distance of the deflate distance symbols 0..=29
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// Order in which a dynamic block lists the code length code lengths
+const CODE_LENGTH_ORDER: [u8; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Least-significant-bit-first reader over deflate data
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buffer: u32,
+    available: u32,
+}
+
+impl BitReader<'_> {
+    /// The next `n` (at most 16) bits, or `None` at the end of the data
+    fn bits(&mut self, n: u32) -> Option<u32> {
+        while self.available < n {
+            self.buffer |= u32::from(*self.data.get(self.pos)?) << self.available;
+            self.pos += 1;
+            self.available += 8;
+        }
+        let value = self.buffer & ((1 << n) - 1);
+        self.buffer >>= n;
+        self.available -= n;
+        Some(value)
+    }
+
+    /// Drop the rest of the current byte, for a stored block
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.available = 0;
+    }
+}
+
+/// Canonical Huffman code, as symbol counts per length and symbols in code order
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// The code given by each symbol's code length (0 for unused symbols)
+    fn new(lengths: &[u8]) -> Option<Self> {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[usize::from(len)] += 1;
+        }
+        counts[0] = 0;
+        // Reject over-subscribed codes
+        let mut left: i32 = 1;
+        for &count in &counts[1..] {
+            left = (left << 1) - i32::from(count);
+            if left < 0 {
+                return None;
+            }
+        }
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[usize::from(offsets[usize::from(len)])] = symbol as u16;
+                offsets[usize::from(len)] += 1;
+            }
+        }
+        Some(Self { counts, symbols })
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Option<u16> {
+        // Codes of each length follow those of the previous length
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for &count in &self.counts[1..] {
+            code |= reader.bits(1)? as i32;
+            let count = i32::from(count);
+            if code - first < count {
+                return self.symbols.get((index + code - first) as usize).copied();
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        None
+    }
+}
+
+/// Decompress zlib data (RFC 1950), such as a git loose object
+///
+/// Stops with `None` on malformed input or once the output would exceed
+/// `limit` bytes. The Adler-32 trailer is not verified.
+pub fn zlib_decompress(data: &[u8], limit: usize) -> Option<Vec<u8>> {
+    let (&cmf, &flg) = (data.first()?, data.get(1)?);
+    let check = (u16::from(cmf) << 8 | u16::from(flg)) % 31;
+    // Deflate only, and no preset dictionary
+    if cmf & 0x0f != 8 || check != 0 || flg & 0x20 != 0 {
+        return None;
+    }
+    inflate(&data[2..], limit)
+}
+
+/// Decompress raw deflate data (RFC 1951)
+fn inflate(data: &[u8], limit: usize) -> Option<Vec<u8>> {
+    let mut reader = BitReader {
+        data,
+        pos: 0,
+        buffer: 0,
+        available: 0,
+    };
+    let mut out = Vec::new();
+    loop {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align();
+                let header = data.get(reader.pos..reader.pos + 4)?;
+                let len = u16::from_le_bytes([header[0], header[1]]);
+                if len != !u16::from_le_bytes([header[2], header[3]]) {
+                    return None;
+                }
+                let start = reader.pos + 4;
+                let block = data.get(start..start + usize::from(len))?;
+                if out.len() + block.len() > limit {
+                    return None;
+                }
+                out.extend_from_slice(block);
+                reader.pos = start + block.len();
+            }
+            1 => {
+                let mut lengths = [8u8; 288];
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                let literals = Huffman::new(&lengths)?;
+                let distances = Huffman::new(&[5; 30])?;
+                inflate_block(&mut reader, &mut out, &literals, &distances, limit)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut reader)?;
+                inflate_block(&mut reader, &mut out, &literals, &distances, limit)?;
+            }
+            _ => return None,
+        }
+        if last {
+            return Some(out);
+        }
+    }
+}
+
+/// Read the literal/length and distance codes of a dynamic block
+fn dynamic_codes(reader: &mut BitReader) -> Option<(Huffman, Huffman)> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+    if literal_count > 286 || distance_count > 30 {
+        return None;
+    }
+    let mut code_lengths = [0u8; 19];
+    for &index in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[usize::from(index)] = reader.bits(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&code_lengths)?;
+
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let symbol = code_length_code.decode(reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => (*lengths.last()?, 3 + reader.bits(2)?),
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?),
+        };
+        for _ in 0..repeat {
+            lengths.push(value);
+        }
+    }
+    if lengths.len() != literal_count + distance_count || lengths[256] == 0 {
+        return None;
+    }
+    let (literal_lengths, distance_lengths) = lengths.split_at(literal_count);
+    Some((Huffman::new(literal_lengths)?, Huffman::new(distance_lengths)?))
+}
+
+/// Decode one Huffman-coded block into `out`
+fn inflate_block(
+    reader: &mut BitReader,
+    out: &mut Vec<u8>,
+    literals: &Huffman,
+    distances: &Huffman,
+    limit: usize,
+) -> Option<()> {
+    loop {
+        let symbol = usize::from(literals.decode(reader)?);
+        if symbol == 256 {
+            return Some(());
+        }
+        if out.len() >= limit {
+            return None;
+        }
+        if symbol < 256 {
+            out.push(symbol as u8);
+            continue;
+        }
+        let index = symbol - 257;
+        let length = usize::from(*LENGTH_BASE.get(index)?)
+            + reader.bits(u32::from(LENGTH_EXTRA[index]))? as usize;
+        let index = usize::from(distances.decode(reader)?);
+        let distance = usize::from(*DIST_BASE.get(index)?)
+            + reader.bits(u32::from(DIST_EXTRA[index]))? as usize;
+        if distance > out.len() || out.len() + length > limit {
+            return None;
+        }
+        // Byte by byte, as the copy may overlap what it produces
+        let start = out.len() - distance;
+        for i in 0..length {
+            out.push(out[start + i]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_known_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_zlib_decompress() {
+        let decompress = |hex: &str| zlib_decompress(&hex_decode(hex).unwrap(), 1 << 20);
+        // Fixed Huffman codes
+        let object = decompress("78da4bcac94f523065c848cdc9c9070019aa0409");
+        assert_eq!(object.as_deref(), Some(&b"blob 5\0hello"[..]));
+        // Stored block
+        let stored = decompress("7801010c00f3ff73746f72656420626c6f636b1f8004bd");
+        assert_eq!(stored.as_deref(), Some(&b"stored block"[..]));
+        // Dynamic Huffman codes
+        let dynamic = decompress(
+            "78da1dcc510d00300c42412b08a883a981c2fc4b58bbbf970bc18941287481de6eea20d2a4572f3b7f\
+             9233f0394da7207a7a0f3a0f458c16d9",
+        );
+        assert_eq!(
+            dynamic.as_deref(),
+            Some(&b"deed a bead, add a cab; ebb a dad, face a bee; dab a decade, bad deed ace"[..])
+        );
+
+        let data = hex_decode("78da4bcac94f523065c848cdc9c9070019aa0409").unwrap();
+        assert_eq!(zlib_decompress(&data, 11), None);
+        assert_eq!(zlib_decompress(&data[..10], 1 << 20), None);
+        assert_eq!(zlib_decompress(b"not zlib", 1 << 20), None);
+    }
+
+    #[test]
+    fn test_gzip_store_layout() {
+        let data = b"hello";
+        let gz = gzip_store(data);
+        assert_eq!(&gz[..3], &[0x1f, 0x8b, 0x08]);
+        // Final stored block header followed by the raw bytes
+        assert_eq!(&gz[10..15], &[0x01, 0x05, 0x00, 0xfa, 0xff]);
+        assert_eq!(&gz[15..20], data);
+        assert_eq!(&gz[20..24], &crc32(data).to_le_bytes());
+        assert_eq!(&gz[24..28], &5u32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_gzip_store_splits_large_input() {
+        let data = vec![b'x'; 70_000];
+        let gz = gzip_store(&data);
+        // Two stored blocks: 65535 + 4465 bytes, 5 header bytes each
+        assert_eq!(gz.len(), 10 + 5 + 65_535 + 5 + 4_465 + 8);
+        assert_eq!(gz[10], 0x00);
+        assert_eq!(gz[10 + 5 + 65_535], 0x01);
+    }
+
+    #[test]
+    fn test_sha256_known_values() {
+        assert_eq!(
+            hex_encode(&sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex_encode(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // Two-block message (FIPS 180-4 example)
+        assert_eq!(
+            hex_encode(&sha256(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc4231() {
+        // Test case 2
+        assert_eq!(
+            hex_encode(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Test case 6: key longer than the block size
+        assert_eq!(
+            hex_encode(&hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        assert_eq!(hex_encode(&[0x00, 0xab, 0xff]), "00abff");
+        assert_eq!(hex_decode("00ABff"), Some(vec![0x00, 0xab, 0xff]));
+        assert_eq!(hex_decode("abc"), None);
+        assert_eq!(hex_decode("zz"), None);
+        assert!(constant_time_eq(b"same", b"same"));
+        assert!(!constant_time_eq(b"same", b"sane"));
+        assert!(!constant_time_eq(b"same", b"same!"));
+    }
+}
@@ -0,0 +1,146 @@
+//! Collecting results from several threads into one report
+//!
+//! [`ComplianceReport`] methods take `&mut self`, so only one caller can add
+//! to a report at a time. A [`ReportCollector`] keeps the report behind a
+//! mutex and takes `&self`, so checks running on scoped threads, or plugin
+//! checks handed a shared reference, can record their results and warnings
+//! concurrently:
+//!
+//! ```
+//! use rhodibot::collector::ReportCollector;
+//! use rhodibot::{catalog, ComplianceReport};
+//! use std::path::PathBuf;
+//!
+//! let collector = ReportCollector::new(ComplianceReport::new(PathBuf::from("/repo")));
+//! std::thread::scope(|scope| {
+//!     for id in ["RSR-DOC-002", "RSR-DOC-001"] {
+//!         let collector = &collector;
+//!         scope.spawn(move || collector.add_spec_check(catalog::lookup(id).unwrap(), true));
+//!     }
+//! });
+//! let report = collector.into_report();
+//! assert_eq!(report.checks[0].id, "RSR-DOC-001");
+//! ```
+//!
+//! Results arrive in whatever order the threads finish; [`into_report`]
+//! puts catalog checks back in catalog order so the report does not depend
+//! on scheduling.
+//!
+//! [`into_report`]: ReportCollector::into_report
+
+use crate::catalog::{self, CheckSpec};
+use crate::{CheckResult, ComplianceLevel, ComplianceReport, WarningLevel};
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// A report that several threads can add to
+#[derive(Debug)]
+pub struct ReportCollector {
+    report: Mutex<ComplianceReport>,
+}
+
+impl ReportCollector {
+    /// Collect into `report`, keeping what it already holds
+    pub fn new(report: ComplianceReport) -> Self {
+        Self {
+            report: Mutex::new(report),
+        }
+    }
+
+    /// The report, even if a thread panicked while holding it
+    ///
+    /// The results of the other threads are kept. The `add_` methods each
+    /// make a single push, so they cannot leave the report half-updated;
+    /// a closure passed to [`with`](Self::with) can.
+    fn lock(&self) -> MutexGuard<'_, ComplianceReport> {
+        self.report.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Add the result of a catalogued check
+    pub fn add_spec_check(&self, spec: &CheckSpec, passed: bool) {
+        self.lock().add_spec_check(spec, passed);
+    }
+
+    /// Add the result of an ad-hoc check
+    pub fn add_check(&self, category: &str, item: &str, passed: bool, level: ComplianceLevel) {
+        self.lock().add_check(category, item, passed, level);
+    }
+
+    /// Add a finished check result as it is
+    pub fn add_result(&self, result: CheckResult) {
+        self.lock().checks.push(result);
+    }
+
+    /// Add a security warning, unless the same one is already recorded
+    pub fn add_warning(&self, level: WarningLevel, message: &str, path: Option<PathBuf>) {
+        self.lock().add_warning(level, message, path);
+    }
+
+    /// Run `f` with exclusive access to the report, e.g. to read it
+    ///
+    /// Other threads wait until `f` returns, so keep it short. Changes are
+    /// not rolled back: if `f` panics, what it changed so far stays in the
+    /// report.
+    pub fn with<T>(&self, f: impl FnOnce(&mut ComplianceReport) -> T) -> T {
+        f(&mut self.lock())
+    }
+
+    /// The collected report, with catalog checks in catalog order
+    ///
+    /// Checks that are not in the catalog follow them in the order they
+    /// were added.
+    pub fn into_report(self) -> ComplianceReport {
+        let mut report = self.report.into_inner().unwrap_or_else(PoisonError::into_inner);
+        let position = |check: &CheckResult| {
+            catalog::BUILTIN_CHECKS
+                .iter()
+                .position(|spec| spec.id == check.id)
+                .unwrap_or(catalog::BUILTIN_CHECKS.len())
+        };
+        // Stable, so equal positions keep their arrival order
+        report.checks.sort_by_key(position);
+        report
+    }
+}
+
+impl From<ComplianceReport> for ReportCollector {
+    fn from(report: ComplianceReport) -> Self {
+        Self::new(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_collect_from_threads() {
+        let collector = ReportCollector::new(ComplianceReport::new(PathBuf::from("/repo")));
+        let specs = &catalog::BUILTIN_CHECKS[..8];
+        thread::scope(|scope| {
+            for spec in specs.iter().rev() {
+                let collector = &collector;
+                scope.spawn(move || {
+                    collector.add_spec_check(spec, true);
+                    collector.add_warning(WarningLevel::Info, "shared", None);
+                });
+            }
+            scope.spawn(|| collector.add_check("Extras", "NOTES.md", false, ComplianceLevel::Gold));
+        });
+        // A thread that panics while holding the report does not lose it
+        let _ = thread::scope(|scope| {
+            scope
+                .spawn(|| collector.with(|_| panic!("plugin failed")))
+                .join()
+        });
+
+        assert_eq!(collector.with(|report| report.total_count()), 9);
+        let report = collector.into_report();
+        let ids: Vec<&str> = report.checks.iter().map(|c| c.id.as_str()).collect();
+        let expected: Vec<&str> = specs.iter().map(|spec| spec.id).collect();
+        assert_eq!(ids[..8], expected[..]);
+        assert_eq!(ids[8], "rsr/extras/notes.md");
+        assert_eq!(report.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,1161 @@
+//! Configuration file support
+//!
+//! Rhodibot reads an optional `.rhodibot.toml` from the repository root, or the
+//! file passed with `--config`. Only the TOML subset of the [`toml`] module
+//! is understood: `[section]` headers and `key = value` pairs where the value
+//! is a string, a boolean, a number, or an array of strings.
+//!
+//! The only top-level key, `standard_version`, pins the version of the RSR
+//! standard the repository is checked against; without it the latest
+//! version this release knows is used.
+//!
+//! ```toml
+//! standard_version = "1.0"
+//!
+//! [policy]
+//! fail_on_warning = true
+//! fail_below_percentage = 90
+//! fail_on_level = "silver"
+//! distinguish_waived = true
+//!
+//! [checks]
+//! disable = ["RSR-BLD-002"]
+//!
+//! # Further names a built-in check accepts for its file or directory
+//! [checks.alternates]
+//! "RSR-DOC-002" = ["LICENSE", "LICENSE.md", "COPYING"]
+//!
+//! # Required documentation files shorter than this, or holding only
+//! # placeholder text, fail their check
+//! [documentation]
+//! min_bytes = 64
+//! min_lines = 3
+//! detect_placeholders = true
+//!
+//! # Time budgets in seconds; checks over budget are reported as errors
+//! [limits]
+//! check_timeout = 30
+//! run_timeout = 120
+//!
+//! # Symlinks into these directories are not treated as escapes
+//! [security]
+//! allow_symlink_targets = ["../shared"]
+//!
+//! # What the repository holds: "code" (the default), "docs", "data" or "meta"
+//! [repository]
+//! type = "code"
+//!
+//! # Where sources and tests live; "auto" detects it from the manifest
+//! [source]
+//! layout = "go"
+//!
+//! # Directory the site is published from, holding .well-known/; "auto"
+//! # detects it, "." is the repository root
+//! [site]
+//! web_root = "public"
+//!
+//! # Warn about http://, raw IP and malformed links in README, SECURITY and
+//! # CONTRIBUTING; online also requests each link
+//! [links]
+//! audit = true
+//! online = false
+//! allow = ["http://legacy.example.org/"]
+//!
+//! # Further .well-known entries, with the level that requires them
+//! [well_known]
+//! "dnt-policy.txt" = "silver"
+//! "openpgpkey/" = "gold"
+//!
+//! # Organization rules, checked alongside the RSR ones
+//! [rules.architecture]
+//! path = "docs/ARCHITECTURE.md"
+//! category = "Documentation"
+//! level = "silver"
+//! description = "Architecture overview for new contributors"
+//!
+//! # Content assertions on a file
+//! [rules.disclosure]
+//! path = "SECURITY.md"
+//! category = "Security"
+//! must_contain = "disclosure"
+//! must_match = ["(?i)contact"]
+//! ```
+
+pub mod pack;
+pub mod toml;
+
+use crate::catalog::{self, CheckSpec};
+use crate::layout::Layout;
+use crate::pattern::LinePattern;
+use crate::profile::{self, RepositoryType};
+use crate::vfs::{FileSystem, RealFs};
+use crate::{ComplianceLevel, StandardVersion};
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+use toml::Value;
+
+/// Default configuration file name, looked up in the repository root
+pub const DEFAULT_CONFIG_FILE: &str = ".rhodibot.toml";
+
+/// Namespace of the IDs of rules declared in the configuration file
+pub const CUSTOM_NAMESPACE: &str = "custom";
+
+/// Error raised while reading or parsing a configuration file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    /// 1-based line number, or 0 when the error is not tied to a line
+    pub line: usize,
+    pub message: String,
+}
+
+impl ConfigError {
+    pub(crate) fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Exit-code policy (`[policy]` section)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyConfig {
+    /// Treat Warning-level security warnings as failures
+    pub fail_on_warning: bool,
+    /// Fail when the pass percentage is below this value
+    pub fail_below_percentage: Option<f64>,
+    /// Compliance level that must be achieved for a successful exit
+    pub fail_on_level: ComplianceLevel,
+    /// Exit with a distinct code when the level is met only through waivers
+    pub distinguish_waived: bool,
+}
+
+impl Default for PolicyConfig {
+    fn default() -> Self {
+        Self {
+            fail_on_warning: false,
+            fail_below_percentage: None,
+            fail_on_level: ComplianceLevel::Bronze,
+            distinguish_waived: false,
+        }
+    }
+}
+
+/// Check selection (`[checks]` section)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChecksConfig {
+    /// IDs of built-in checks that are not run
+    pub disable: Vec<String>,
+    /// When non-empty, only checks in these categories run (`--only-category`)
+    pub only_categories: Vec<String>,
+    /// Names accepted by a built-in check after the ones in the standard,
+    /// by check ID (`[checks.alternates]`)
+    pub alternates: Vec<(String, Vec<String>)>,
+    /// Stop at the first unwaived failure of a check required for
+    /// `policy.fail_on_level`; later checks are skipped (`--fail-fast`)
+    pub fail_fast: bool,
+}
+
+impl ChecksConfig {
+    /// Whether the check with this ID has been disabled
+    pub fn is_disabled(&self, id: &str) -> bool {
+        self.disable.iter().any(|d| d == id)
+    }
+
+    /// Configured alternative names for the check with this ID
+    pub fn alternates(&self, id: &str) -> &[String] {
+        self.alternates
+            .iter()
+            .find(|(check, _)| check == id)
+            .map_or(&[], |(_, names)| names.as_slice())
+    }
+
+    /// Whether checks in `category` run under `only_categories`
+    pub fn selects_category(&self, category: &str) -> bool {
+        self.only_categories.is_empty()
+            || self.only_categories.iter().any(|c| c.eq_ignore_ascii_case(category))
+    }
+
+    /// Whether a built-in check is left out of the run, by ID or category
+    pub fn skips(&self, spec: &CheckSpec) -> bool {
+        self.is_disabled(spec.id) || !self.selects_category(spec.category)
+    }
+}
+
+/// Substance required of documentation files (`[documentation]` section)
+///
+/// Applies to the files whose existence the Documentation checks require
+/// (README, LICENSE, SECURITY and the rest).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentationConfig {
+    /// Smallest accepted file size in bytes
+    pub min_bytes: usize,
+    /// Fewest accepted non-blank lines
+    pub min_lines: usize,
+    /// Fail files that hold only placeholder text such as "TODO" or lorem ipsum
+    pub detect_placeholders: bool,
+}
+
+impl Default for DocumentationConfig {
+    fn default() -> Self {
+        Self {
+            min_bytes: 1,
+            min_lines: 1,
+            detect_placeholders: true,
+        }
+    }
+}
+
+/// Time budgets (`[limits]` section)
+///
+/// External commands a check runs (`git log`, `curl` for the link audit)
+/// are killed when the check's budget or the run's is spent, and the check
+/// is reported as an error. A check working in-process is reported as an
+/// error once it returns over `check_timeout`, and once the run has taken
+/// longer than `run_timeout` the remaining checks are not started and are
+/// reported as errors, so a slow filesystem or network mount ends the run
+/// instead of stalling CI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitsConfig {
+    /// Longest a single check may take
+    pub check_timeout: Duration,
+    /// Longest the whole verification may take
+    pub run_timeout: Duration,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            check_timeout: Duration::from_secs(60),
+            run_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Symlink handling (`[security]` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecurityConfig {
+    /// Directories outside the repository that symlinks may point into,
+    /// absolute or relative to the repository root (e.g. `../shared`)
+    pub allow_symlink_targets: Vec<String>,
+}
+
+/// Optional commit history check (`[version_control]` section)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionControlConfig {
+    /// Check recent commits and the branch name against Conventional
+    /// Commits (see [`crate::conventional`])
+    pub conventional_commits: bool,
+    /// Number of most recent commits checked
+    pub commits: usize,
+    /// Run `git log` for commits that are packed, which cannot be read from
+    /// `.git` directly
+    pub allow_git: bool,
+}
+
+impl Default for VersionControlConfig {
+    fn default() -> Self {
+        Self {
+            conventional_commits: false,
+            commits: 20,
+            allow_git: true,
+        }
+    }
+}
+
+/// Kind of repository (`[repository]` section)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RepositoryConfig {
+    /// Decides which categories apply (`type`, see [`crate::profile`])
+    pub kind: RepositoryType,
+}
+
+/// Link audit of the main documentation files (`[links]` section)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinksConfig {
+    /// Warn about links that break the policy (see [`crate::links`])
+    pub audit: bool,
+    /// Also request every link and warn about those that are not live
+    pub online: bool,
+    /// URL prefixes that are never reported
+    pub allow: Vec<String>,
+}
+
+impl Default for LinksConfig {
+    fn default() -> Self {
+        Self {
+            audit: true,
+            online: false,
+            allow: Vec::new(),
+        }
+    }
+}
+
+impl LinksConfig {
+    /// Whether `url` starts with an allowed prefix
+    pub fn allows(&self, url: &str) -> bool {
+        self.allow.iter().any(|prefix| url.starts_with(prefix.as_str()))
+    }
+}
+
+/// Published site (`[site]` section)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SiteConfig {
+    /// Directory holding `.well-known/`, relative to the repository root
+    /// and empty for the root itself; `None` (`"auto"`) detects it (see
+    /// [`crate::site`])
+    pub web_root: Option<String>,
+}
+
+/// Source Structure layout (`[source]` section)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourceConfig {
+    /// Layout the Source Structure checks follow; `None` (`"auto"`) detects
+    /// it from the repository's manifest
+    pub layout: Option<Layout>,
+}
+
+/// A further `.well-known` entry the repository must have (`[well_known]`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WellKnownEntry {
+    /// Path below `.well-known/`; a trailing `/` asks for a directory
+    pub name: String,
+    /// Lowest compliance level that requires the entry
+    pub level: ComplianceLevel,
+}
+
+impl WellKnownEntry {
+    /// Whether the entry must be a directory rather than a file
+    pub fn is_dir(&self) -> bool {
+        self.name.ends_with('/')
+    }
+
+    /// Path of the entry relative to the repository root
+    pub fn path(&self) -> String {
+        format!(".well-known/{}", self.name.trim_end_matches('/'))
+    }
+}
+
+/// A "file must exist" or "directory must exist" rule (`[rules.NAME]`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomRule {
+    /// Name from the section header
+    pub name: String,
+    /// [`CUSTOM_NAMESPACE`], or the name of the [`pack::RulePack`] the rule
+    /// came from; the check's ID is `NAMESPACE/NAME`
+    pub namespace: String,
+    /// Path relative to the repository root; a trailing `/` asks for a
+    /// directory
+    pub path: String,
+    /// Category the check is reported under, built-in or new
+    pub category: String,
+    /// Lowest compliance level that requires the path (default Bronze)
+    pub level: ComplianceLevel,
+    pub description: Option<String>,
+    /// Text the file must contain, each taken literally
+    pub must_contain: Vec<String>,
+    /// Patterns some line of the file must match (see [`crate::pattern`])
+    pub must_match: Vec<LinePattern>,
+}
+
+impl CustomRule {
+    /// Stable identifier of the rule's check
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.namespace, self.name)
+    }
+
+    /// Whether the path must be a directory rather than a file
+    pub fn is_dir(&self) -> bool {
+        self.path.ends_with('/')
+    }
+
+    /// Whether the rule checks the file's content as well as its existence
+    pub fn has_assertions(&self) -> bool {
+        !self.must_contain.is_empty() || !self.must_match.is_empty()
+    }
+
+    /// Problems with the content of the rule's file, empty if it satisfies
+    /// every assertion
+    pub fn check_content(&self, text: &str) -> Vec<String> {
+        let missing = self
+            .must_contain
+            .iter()
+            .filter(|needle| !text.contains(needle.as_str()))
+            .map(|needle| format!("does not contain '{}'", needle));
+        let unmatched = self
+            .must_match
+            .iter()
+            .filter(|pattern| pattern.find_line(text).is_none())
+            .map(|pattern| format!("no line matches '{}'", pattern));
+        missing.chain(unmatched).collect()
+    }
+
+    /// Read the keys of a `[rules.NAME]` section
+    fn parse(name: &str, table: &toml::Table) -> Result<Self, ConfigError> {
+        if name.is_empty() || name.contains('.') {
+            return Err(ConfigError::new(
+                table.line,
+                format!("malformed rule name '{}'", name),
+            ));
+        }
+        let mut path = None;
+        let mut category = None;
+        let mut level = ComplianceLevel::Bronze;
+        let mut description = None;
+        let mut must_contain = Vec::new();
+        let mut must_match = Vec::new();
+        for entry in &table.entries {
+            let (key, value, line_no) = (entry.key.as_str(), &entry.value, entry.line);
+            match key {
+                "path" => {
+                    let value = expect_string(key, value, line_no)?;
+                    if !is_inside(&value) {
+                        return Err(ConfigError::new(
+                            line_no,
+                            format!("'{}' is not a path inside the repository", value),
+                        ));
+                    }
+                    path = Some(value);
+                }
+                "category" => {
+                    let value = expect_string(key, value, line_no)?;
+                    if value.trim().is_empty() {
+                        return Err(ConfigError::new(line_no, "'category' must not be empty"));
+                    }
+                    category = Some(value);
+                }
+                "level" => {
+                    level = expect_string(key, value, line_no)?
+                        .parse()
+                        .map_err(|e: String| ConfigError::new(line_no, e))?;
+                }
+                "description" => description = Some(expect_string(key, value, line_no)?),
+                "must_contain" => must_contain = expect_strings(key, value, line_no)?,
+                "must_match" => {
+                    must_match = expect_strings(key, value, line_no)?
+                        .iter()
+                        .map(|p| LinePattern::parse(p).map_err(|e| ConfigError::new(line_no, e)))
+                        .collect::<Result<_, _>>()?;
+                }
+                _ => {
+                    return Err(ConfigError::new(
+                        line_no,
+                        format!("unknown key '{}' in [rules.{}]", key, name),
+                    ));
+                }
+            }
+        }
+        let missing = |key: &str| {
+            ConfigError::new(table.line, format!("[rules.{}] has no '{}'", name, key))
+        };
+        let rule = Self {
+            name: name.to_string(),
+            namespace: CUSTOM_NAMESPACE.to_string(),
+            path: path.ok_or_else(|| missing("path"))?,
+            category: category.ok_or_else(|| missing("category"))?,
+            level,
+            description,
+            must_contain,
+            must_match,
+        };
+        if rule.is_dir() && rule.has_assertions() {
+            return Err(ConfigError::new(
+                table.line,
+                format!("[rules.{}] asserts content of a directory", name),
+            ));
+        }
+        Ok(rule)
+    }
+}
+
+/// Whether `path` is relative and stays inside the directory it is
+/// relative to (a trailing `/` is allowed)
+fn is_inside(path: &str) -> bool {
+    !path.contains('\\')
+        && path
+            .trim_end_matches('/')
+            .split('/')
+            .all(|part| !part.is_empty() && part != "." && part != "..")
+}
+
+/// Parsed configuration file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    /// RSR standard version to check against (`standard_version`)
+    pub standard_version: StandardVersion,
+    pub policy: PolicyConfig,
+    pub checks: ChecksConfig,
+    pub documentation: DocumentationConfig,
+    pub limits: LimitsConfig,
+    pub security: SecurityConfig,
+    pub version_control: VersionControlConfig,
+    pub repository: RepositoryConfig,
+    pub source: SourceConfig,
+    pub site: SiteConfig,
+    pub links: LinksConfig,
+    /// Entries required in `.well-known/` on top of the RSR ones
+    pub well_known: Vec<WellKnownEntry>,
+    /// Organization rules (`[rules.NAME]` sections) in file order, then
+    /// the rules of each added [`pack::RulePack`]
+    pub rules: Vec<CustomRule>,
+}
+
+fn expect_bool(key: &str, value: &Value, line: usize) -> Result<bool, ConfigError> {
+    match value {
+        Value::Bool(b) => Ok(*b),
+        other => Err(ConfigError::new(
+            line,
+            format!("'{}' must be a boolean, found {}", key, other.type_name()),
+        )),
+    }
+}
+
+fn expect_number(key: &str, value: &Value, line: usize) -> Result<f64, ConfigError> {
+    match value {
+        Value::Integer(n) => Ok(*n as f64),
+        Value::Float(n) => Ok(*n),
+        other => Err(ConfigError::new(
+            line,
+            format!("'{}' must be a number, found {}", key, other.type_name()),
+        )),
+    }
+}
+
+fn expect_count(key: &str, value: &Value, line: usize) -> Result<usize, ConfigError> {
+    match value {
+        Value::Integer(n) if *n >= 0 => Ok(*n as usize),
+        Value::Integer(n) => Err(ConfigError::new(
+            line,
+            format!("'{}' must not be negative, found {}", key, n),
+        )),
+        other => Err(ConfigError::new(
+            line,
+            format!("'{}' must be a non-negative integer, found {}", key, other.type_name()),
+        )),
+    }
+}
+
+/// A positive number of seconds
+fn expect_seconds(key: &str, value: &Value, line: usize) -> Result<Duration, ConfigError> {
+    let seconds = expect_number(key, value, line)?;
+    if !(seconds > 0.0 && seconds.is_finite()) {
+        return Err(ConfigError::new(
+            line,
+            format!("'{}' must be a positive number of seconds", key),
+        ));
+    }
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+fn expect_array(key: &str, value: &Value, line: usize) -> Result<Vec<String>, ConfigError> {
+    match value {
+        Value::Array(items) => Ok(items.clone()),
+        other => Err(ConfigError::new(
+            line,
+            format!("'{}' must be an array, found {}", key, other.type_name()),
+        )),
+    }
+}
+
+/// A string or an array of strings, as a list
+fn expect_strings(key: &str, value: &Value, line: usize) -> Result<Vec<String>, ConfigError> {
+    match value {
+        Value::String(s) => Ok(vec![s.clone()]),
+        Value::Array(items) => Ok(items.clone()),
+        other => Err(ConfigError::new(
+            line,
+            format!("'{}' must be a string or an array, found {}", key, other.type_name()),
+        )),
+    }
+}
+
+pub(crate) fn expect_string(key: &str, value: &Value, line: usize) -> Result<String, ConfigError> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        other => Err(ConfigError::new(
+            line,
+            format!("'{}' must be a string, found {}", key, other.type_name()),
+        )),
+    }
+}
+
+impl Config {
+    /// Parse configuration from a string
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let doc = toml::parse(text)?;
+        let mut config = Config::default();
+        // Documentation thresholds set explicitly, which the repository type
+        // does not change
+        let mut documentation_keys: Vec<&str> = Vec::new();
+
+        for entry in &doc.root.entries {
+            let (key, value, line_no) = (entry.key.as_str(), &entry.value, entry.line);
+            match key {
+                "standard_version" => {
+                    let version = expect_string(key, value, line_no)?;
+                    config.standard_version = version
+                        .parse()
+                        .map_err(|e: String| ConfigError::new(line_no, e))?;
+                }
+                _ => {
+                    return Err(ConfigError::new(
+                        line_no,
+                        format!("key '{}' must be inside a section", key),
+                    ));
+                }
+            }
+        }
+
+        for table in &doc.tables {
+            if let Some(name) = table.name.strip_prefix("rules.") {
+                config.rules.push(CustomRule::parse(name, table)?);
+                continue;
+            }
+            let section = table.name.as_str();
+            let known = [
+                "policy",
+                "checks",
+                "checks.alternates",
+                "documentation",
+                "limits",
+                "links",
+                "repository",
+                "security",
+                "site",
+                "source",
+                "version_control",
+                "well_known",
+            ];
+            if !known.contains(&section) {
+                return Err(ConfigError::new(
+                    table.line,
+                    format!("unknown section [{}]", section),
+                ));
+            }
+            for entry in &table.entries {
+                let (key, value, line_no) = (entry.key.as_str(), &entry.value, entry.line);
+                match (section, key) {
+                    ("policy", "fail_on_warning") => {
+                        config.policy.fail_on_warning = expect_bool(key, value, line_no)?;
+                    }
+                    ("policy", "fail_below_percentage") => {
+                        let pct = expect_number(key, value, line_no)?;
+                        if !(0.0..=100.0).contains(&pct) {
+                            return Err(ConfigError::new(
+                                line_no,
+                                "'fail_below_percentage' must be between 0 and 100",
+                            ));
+                        }
+                        config.policy.fail_below_percentage = Some(pct);
+                    }
+                    ("policy", "fail_on_level") => {
+                        let name = expect_string(key, value, line_no)?;
+                        config.policy.fail_on_level = name
+                            .parse()
+                            .map_err(|e: String| ConfigError::new(line_no, e))?;
+                    }
+                    ("policy", "distinguish_waived") => {
+                        config.policy.distinguish_waived = expect_bool(key, value, line_no)?;
+                    }
+                    ("checks", "disable") => {
+                        let ids = expect_array(key, value, line_no)?;
+                        if let Some(unknown) = ids.iter().find(|id| catalog::lookup(id).is_none())
+                        {
+                            return Err(ConfigError::new(
+                                line_no,
+                                format!("unknown check id '{}'", unknown),
+                            ));
+                        }
+                        config.checks.disable = ids;
+                    }
+                    ("checks.alternates", id) => {
+                        if catalog::lookup(id).is_none() {
+                            return Err(ConfigError::new(
+                                line_no,
+                                format!("unknown check id '{}'", id),
+                            ));
+                        }
+                        let names = expect_strings(id, value, line_no)?;
+                        if let Some(name) = names.iter().find(|name| !is_inside(name)) {
+                            return Err(ConfigError::new(
+                                line_no,
+                                format!("'{}' is not a path inside the repository", name),
+                            ));
+                        }
+                        config.checks.alternates.push((id.to_string(), names));
+                    }
+                    ("documentation", "min_bytes") => {
+                        config.documentation.min_bytes = expect_count(key, value, line_no)?;
+                        documentation_keys.push(key);
+                    }
+                    ("documentation", "min_lines") => {
+                        config.documentation.min_lines = expect_count(key, value, line_no)?;
+                        documentation_keys.push(key);
+                    }
+                    ("documentation", "detect_placeholders") => {
+                        config.documentation.detect_placeholders =
+                            expect_bool(key, value, line_no)?;
+                    }
+                    ("limits", "check_timeout") => {
+                        config.limits.check_timeout = expect_seconds(key, value, line_no)?;
+                    }
+                    ("limits", "run_timeout") => {
+                        config.limits.run_timeout = expect_seconds(key, value, line_no)?;
+                    }
+                    ("security", "allow_symlink_targets") => {
+                        let targets = expect_array(key, value, line_no)?;
+                        if targets.iter().any(|t| t.trim().is_empty()) {
+                            return Err(ConfigError::new(
+                                line_no,
+                                "'allow_symlink_targets' entries must not be empty",
+                            ));
+                        }
+                        config.security.allow_symlink_targets = targets;
+                    }
+                    ("links", "audit") => {
+                        config.links.audit = expect_bool(key, value, line_no)?;
+                    }
+                    ("links", "online") => {
+                        config.links.online = expect_bool(key, value, line_no)?;
+                    }
+                    ("links", "allow") => {
+                        config.links.allow = expect_strings(key, value, line_no)?;
+                    }
+                    ("repository", "type") => {
+                        config.repository.kind = expect_string(key, value, line_no)?
+                            .parse()
+                            .map_err(|e: String| ConfigError::new(line_no, e))?;
+                    }
+                    ("site", "web_root") => {
+                        let web_root = expect_string(key, value, line_no)?;
+                        config.site.web_root = match web_root.trim() {
+                            "auto" => None,
+                            "." | "./" => Some(String::new()),
+                            dir if is_inside(dir) => Some(dir.trim_end_matches('/').to_string()),
+                            dir => {
+                                return Err(ConfigError::new(
+                                    line_no,
+                                    format!("'{}' is not a directory inside the repository", dir),
+                                ));
+                            }
+                        };
+                    }
+                    ("source", "layout") => {
+                        let layout = expect_string(key, value, line_no)?;
+                        config.source.layout = if layout.trim().eq_ignore_ascii_case("auto") {
+                            None
+                        } else {
+                            Some(layout.parse().map_err(|e: String| ConfigError::new(line_no, e))?)
+                        };
+                    }
+                    ("version_control", "conventional_commits") => {
+                        config.version_control.conventional_commits =
+                            expect_bool(key, value, line_no)?;
+                    }
+                    ("version_control", "commits") => {
+                        let commits = expect_count(key, value, line_no)?;
+                        if commits == 0 {
+                            return Err(ConfigError::new(line_no, "'commits' must be at least 1"));
+                        }
+                        config.version_control.commits = commits;
+                    }
+                    ("version_control", "allow_git") => {
+                        config.version_control.allow_git = expect_bool(key, value, line_no)?;
+                    }
+                    ("well_known", name) => {
+                        if !is_inside(name) {
+                            return Err(ConfigError::new(
+                                line_no,
+                                format!("'{}' is not a path inside .well-known/", name),
+                            ));
+                        }
+                        let level = expect_string(name, value, line_no)?
+                            .parse()
+                            .map_err(|e: String| ConfigError::new(line_no, e))?;
+                        config.well_known.push(WellKnownEntry {
+                            name: name.to_string(),
+                            level,
+                        });
+                    }
+                    (section, key) => {
+                        return Err(ConfigError::new(
+                            line_no,
+                            format!("unknown key '{}' in [{}]", key, section),
+                        ));
+                    }
+                }
+            }
+        }
+
+        if config.repository.kind == RepositoryType::Docs {
+            let strict = profile::STRICT_DOCUMENTATION;
+            if !documentation_keys.contains(&"min_bytes") {
+                config.documentation.min_bytes = strict.min_bytes;
+            }
+            if !documentation_keys.contains(&"min_lines") {
+                config.documentation.min_lines = strict.min_lines;
+            }
+        }
+        Ok(config)
+    }
+
+    /// Add the rules of a rule pack
+    ///
+    /// A pack's name can only be used once, so its check IDs stay unique.
+    pub fn add_rule_pack(&mut self, pack: pack::RulePack) -> Result<(), ConfigError> {
+        if self.rules.iter().any(|rule| rule.namespace == pack.name) {
+            return Err(ConfigError::new(
+                0,
+                format!("rule pack '{}' is loaded twice", pack.name),
+            ));
+        }
+        self.rules.extend(pack.rules);
+        Ok(())
+    }
+
+    /// Load configuration from a file
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        Self::load_with_fs(&RealFs, path)
+    }
+
+    /// Load configuration from a file in `fs`
+    pub fn load_with_fs(fs: &dyn FileSystem, path: &Path) -> Result<Self, ConfigError> {
+        let text = fs.read_to_string(path).map_err(|e| {
+            ConfigError::new(0, format!("cannot read {}: {}", path.display(), e))
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Load `.rhodibot.toml` from the repository root, or defaults if absent
+    pub fn discover(repo_path: &Path) -> Result<Self, ConfigError> {
+        Self::discover_with_fs(&RealFs, repo_path)
+    }
+
+    /// Like [`Config::discover`], for a repository in `fs`
+    pub fn discover_with_fs(fs: &dyn FileSystem, repo_path: &Path) -> Result<Self, ConfigError> {
+        let path = repo_path.join(DEFAULT_CONFIG_FILE);
+        if fs.is_file(&path) {
+            Self::load_with_fs(fs, &path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_policy_section() {
+        let config = Config::parse(
+            r#"
+# Organization policy
+[policy]
+fail_on_warning = true
+fail_below_percentage = 90 # percent
+fail_on_level = "silver"
+distinguish_waived = true
+"#,
+        )
+        .unwrap();
+        assert!(config.policy.fail_on_warning);
+        assert!(config.policy.distinguish_waived);
+        assert_eq!(config.policy.fail_below_percentage, Some(90.0));
+        assert_eq!(config.policy.fail_on_level, ComplianceLevel::Silver);
+    }
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.policy.fail_on_level, ComplianceLevel::Bronze);
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let err = Config::parse("[policy]\nfail_on_warning = \"yes\"\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("boolean"));
+
+        let err = Config::parse("[policy]\nfail_on_level = \"mithril\"").unwrap_err();
+        assert!(err.to_string().starts_with("line 2:"));
+
+        let err = Config::parse("[policy]\nfail_below_percentage = 150").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_parse_checks_section() {
+        let config =
+            Config::parse("[checks]\ndisable = [\"RSR-BLD-002\", \"RSR-BLD-003\",]").unwrap();
+        assert_eq!(config.checks.disable, vec!["RSR-BLD-002", "RSR-BLD-003"]);
+        assert!(config.checks.is_disabled("RSR-BLD-003"));
+        assert!(!config.checks.is_disabled("RSR-DOC-001"));
+
+        let checks = ChecksConfig {
+            disable: vec!["RSR-DOC-002".to_string()],
+            only_categories: vec!["documentation".to_string()],
+            ..ChecksConfig::default()
+        };
+        assert!(!checks.skips(catalog::lookup("RSR-DOC-001").unwrap()));
+        assert!(checks.skips(catalog::lookup("RSR-DOC-002").unwrap()));
+        assert!(checks.skips(catalog::lookup("RSR-SRC-001").unwrap()));
+
+        let err = Config::parse("[checks]\ndisable = [\"RSR-NOPE-1\"]").unwrap_err();
+        assert!(err.message.contains("RSR-NOPE-1"));
+        assert!(Config::parse("[checks]\ndisable = [1]").is_err());
+        assert!(Config::parse("[checks]\ndisable = \"RSR-BLD-002\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_check_alternates() {
+        let config = Config::parse(
+            "[checks.alternates]\n\"RSR-DOC-002\" = [\"LICENSE\", \"COPYING\"]\n\
+             \"RSR-SRC-001\" = \"lib\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.checks.alternates("RSR-DOC-002"), ["LICENSE", "COPYING"]);
+        assert_eq!(config.checks.alternates("RSR-SRC-001"), ["lib"]);
+        assert!(config.checks.alternates("RSR-DOC-001").is_empty());
+
+        let err = Config::parse("[checks.alternates]\n\"RSR-NOPE-1\" = \"X\"").unwrap_err();
+        assert_eq!(err.line, 2);
+        let err = Config::parse("[checks.alternates]\n\"RSR-DOC-002\" = \"../LICENSE\"");
+        assert!(err.unwrap_err().message.contains("not a path inside"));
+    }
+
+    #[test]
+    fn test_parse_documentation_section() {
+        assert_eq!(Config::default().documentation.min_lines, 1);
+        let config = Config::parse(
+            "[documentation]\nmin_bytes = 64\nmin_lines = 3\ndetect_placeholders = false\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.documentation,
+            DocumentationConfig {
+                min_bytes: 64,
+                min_lines: 3,
+                detect_placeholders: false,
+            }
+        );
+        let err = Config::parse("[documentation]\nmin_lines = -1").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(Config::parse("[documentation]\nmin_bytes = \"64\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_limits_section() {
+        assert_eq!(Config::default().limits.run_timeout, Duration::from_secs(300));
+        let config = Config::parse("[limits]\ncheck_timeout = 0.5\nrun_timeout = 20\n").unwrap();
+        assert_eq!(config.limits.check_timeout, Duration::from_millis(500));
+        assert_eq!(config.limits.run_timeout, Duration::from_secs(20));
+
+        let err = Config::parse("[limits]\nrun_timeout = 0").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: 'run_timeout' must be a positive number of seconds");
+        assert!(Config::parse("[limits]\ncheck_timeout = \"30s\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_security_section() {
+        let config =
+            Config::parse("[security]\nallow_symlink_targets = [\"../shared\", \"/opt/org\"]\n")
+                .unwrap();
+        assert_eq!(config.security.allow_symlink_targets, vec!["../shared", "/opt/org"]);
+
+        let err = Config::parse("[security]\nallow_symlink_targets = [\"\"]").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(Config::parse("[security]\nallow_symlinks = true").is_err());
+    }
+
+    #[test]
+    fn test_parse_version_control_section() {
+        let config = Config::parse(
+            "[version_control]\nconventional_commits = true\ncommits = 50\nallow_git = false\n",
+        )
+        .unwrap();
+        assert!(config.version_control.conventional_commits);
+        assert_eq!(config.version_control.commits, 50);
+        assert!(!config.version_control.allow_git);
+        assert!(!Config::default().version_control.conventional_commits);
+
+        let err = Config::parse("[version_control]\ncommits = 0\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: 'commits' must be at least 1");
+    }
+
+    #[test]
+    fn test_parse_repository_section() {
+        let config = Config::parse("[repository]\ntype = \"meta\"\n").unwrap();
+        assert_eq!(config.repository.kind, RepositoryType::Meta);
+        assert_eq!(config.documentation, DocumentationConfig::default());
+
+        // Docs repositories get stricter thresholds unless they are set
+        let config =
+            Config::parse("[documentation]\nmin_lines = 2\n[repository]\ntype = \"docs\"\n");
+        let documentation = config.unwrap().documentation;
+        assert_eq!(documentation.min_bytes, profile::STRICT_DOCUMENTATION.min_bytes);
+        assert_eq!(documentation.min_lines, 2);
+
+        let err = Config::parse("[repository]\ntype = \"library\"\n").unwrap_err();
+        assert!(err.to_string().starts_with("line 2: unknown repository type 'library'"));
+    }
+
+    #[test]
+    fn test_parse_links_section() {
+        let config = Config::parse(
+            "[links]\naudit = true\nonline = true\nallow = [\"http://old.example.org/\"]\n",
+        )
+        .unwrap();
+        assert!(config.links.online);
+        assert!(config.links.allows("http://old.example.org/page"));
+        assert!(!config.links.allows("http://example.org/"));
+        assert!(Config::default().links.audit);
+        assert!(Config::parse("[links]\nonline = \"yes\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_site_section() {
+        let web_root = |text: &str| Config::parse(text).map(|config| config.site.web_root);
+        assert_eq!(web_root("[site]\nweb_root = \"public/\""), Ok(Some("public".to_string())));
+        assert_eq!(web_root("[site]\nweb_root = \".\""), Ok(Some(String::new())));
+        assert_eq!(web_root("[site]\nweb_root = \"auto\""), Ok(None));
+        assert_eq!(web_root(""), Ok(None));
+
+        let err = web_root("[site]\nweb_root = \"../www\"").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: '../www' is not a directory inside the repository");
+    }
+
+    #[test]
+    fn test_parse_source_section() {
+        let config = Config::parse("[source]\nlayout = \"python\"\n").unwrap();
+        assert_eq!(config.source.layout, Some(Layout::Python));
+        let config = Config::parse("[source]\nlayout = \"auto\"\n").unwrap();
+        assert_eq!(config.source.layout, None);
+
+        let err = Config::parse("[source]\nlayout = \"cobol\"\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(err.message.starts_with("unknown layout 'cobol'"));
+    }
+
+    #[test]
+    fn test_parse_standard_version() {
+        let config = Config::parse("standard_version = \"1.0\"\n[policy]\n").unwrap();
+        assert_eq!(config.standard_version, StandardVersion::V1_0);
+        assert_eq!(Config::default().standard_version, StandardVersion::LATEST);
+
+        let err = Config::parse("standard_version = \"7.0\"").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("'7.0'"));
+        assert!(Config::parse("standard_version = 1.0").is_err());
+        assert!(Config::parse("[policy]\nstandard_version = \"1.0\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_well_known_section() {
+        let config = Config::parse(
+            "[well_known]\n\"dnt-policy.txt\" = \"silver\"\n\"openpgpkey/\" = \"gold\"\n",
+        )
+        .unwrap();
+        let entries: Vec<(String, bool, ComplianceLevel)> = config
+            .well_known
+            .iter()
+            .map(|e| (e.path(), e.is_dir(), e.level))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (".well-known/dnt-policy.txt".to_string(), false, ComplianceLevel::Silver),
+                (".well-known/openpgpkey".to_string(), true, ComplianceLevel::Gold),
+            ]
+        );
+
+        let err = Config::parse("[well_known]\n\"funding.json\" = \"mithril\"").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(Config::parse("[well_known]\n\"funding.json\" = true").is_err());
+        let err = Config::parse("[well_known]\n\"../LICENSE\" = \"bronze\"").unwrap_err();
+        assert!(err.message.contains("not a path inside .well-known/"));
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let config = Config::parse(
+            r#"
+[rules.architecture]
+path = "docs/ARCHITECTURE.md"
+category = "Documentation"
+level = "silver"
+description = "Architecture overview"
+
+[rules.adr]
+path = "docs/adr/"
+category = "Governance"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.rules.len(), 2);
+        let adr = &config.rules[1];
+        assert_eq!(adr.id(), "custom/adr");
+        assert!(adr.is_dir() && !config.rules[0].is_dir());
+        assert_eq!(adr.level, ComplianceLevel::Bronze);
+        assert_eq!(adr.description, None);
+        assert_eq!(config.rules[0].level, ComplianceLevel::Silver);
+
+        let err = Config::parse("[rules.x]\ncategory = \"Policy\"\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 1: [rules.x] has no 'path'");
+        let err = Config::parse("[rules.x]\npath = \"/etc/passwd\"\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(Config::parse("[rules.x]\npath = \"a\"\ncategory = \"\"\n").is_err());
+        assert!(Config::parse("[rules.x]\npath = \"a\"\nkind = \"file\"\n").is_err());
+        assert!(Config::parse("[rules]\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_rule_assertions() {
+        let config = Config::parse(
+            r#"
+[rules.disclosure]
+path = "SECURITY.md"
+category = "Security"
+must_contain = "disclosure"
+must_match = ["(?i)^contact:", "*@*"]
+"#,
+        )
+        .unwrap();
+        let rule = &config.rules[0];
+        assert_eq!(rule.must_contain, vec!["disclosure"]);
+        assert_eq!(rule.must_match.len(), 2);
+        let good = "Coordinated disclosure.\nCONTACT: sec@example.org\n";
+        assert!(rule.check_content(good).is_empty());
+        assert_eq!(
+            rule.check_content("Email sec@example.org\n"),
+            vec!["does not contain 'disclosure'", "no line matches '(?i)^contact:'"]
+        );
+
+        let dir = "[rules.x]\npath = \"docs/\"\ncategory = \"Policy\"\nmust_contain = \"a\"\n";
+        assert!(Config::parse(dir).unwrap_err().message.contains("directory"));
+        let trailing_escape = "[rules.x]\npath = \"a\"\nmust_match = \"ends\\\\\"\n";
+        assert_eq!(Config::parse(trailing_escape).unwrap_err().line, 3);
+        assert!(Config::parse("[rules.x]\npath = \"a\"\nmust_contain = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_unknown_keys_and_sections_rejected() {
+        assert!(Config::parse("[nonsense]").is_err());
+        assert!(Config::parse("[policy]\nfail_on_warnings = true").is_err());
+        assert!(Config::parse("fail_on_warning = true").is_err());
+    }
+}
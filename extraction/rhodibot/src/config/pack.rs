@@ -0,0 +1,143 @@
+//! Rule packs: directories of rule files shared across an organization
+//!
+//! A pack is a directory of `*.toml` files, each holding `[rules.NAME]`
+//! sections in the same form as `.rhodibot.toml`. It is versioned on its own
+//! (e.g. as a git repository of organization policies) and passed with
+//! `--rules DIR`. The pack's checks run alongside the built-in standard
+//! with IDs namespaced by the directory's name: the rule `adr` in
+//! `org-policies/` is reported as `org-policies/adr`.
+
+use super::{toml, ConfigError, CustomRule, CUSTOM_NAMESPACE};
+use crate::vfs::{FileSystem, RealFs};
+use std::path::{Path, PathBuf};
+
+/// The rules of one pack, namespaced by its name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RulePack {
+    /// Namespace of the pack's check IDs
+    pub name: String,
+    /// Rules in file name order, then in order within each file
+    pub rules: Vec<CustomRule>,
+}
+
+impl RulePack {
+    /// Load every `*.toml` file directly inside `dir`
+    pub fn load(dir: &Path) -> Result<Self, ConfigError> {
+        Self::load_with_fs(&RealFs, dir)
+    }
+
+    /// Like [`RulePack::load`], for a directory in `fs`
+    pub fn load_with_fs(fs: &dyn FileSystem, dir: &Path) -> Result<Self, ConfigError> {
+        let cannot_read = |e: std::io::Error| {
+            ConfigError::new(0, format!("cannot read rule pack {}: {}", dir.display(), e))
+        };
+        let name = fs
+            .canonicalize(dir)
+            .map_err(cannot_read)?
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let paths: Vec<PathBuf> = fs
+            .read_dir(dir)
+            .map_err(cannot_read)?
+            .into_iter()
+            .filter(|path| path.extension().map_or(false, |e| e == "toml") && fs.is_file(path))
+            .collect();
+        if paths.is_empty() {
+            return Err(ConfigError::new(
+                0,
+                format!("rule pack {} contains no .toml files", dir.display()),
+            ));
+        }
+
+        let mut files = Vec::new();
+        for path in paths {
+            let text = fs.read_to_string(&path).map_err(|e| {
+                ConfigError::new(0, format!("cannot read {}: {}", path.display(), e))
+            })?;
+            files.push((path.display().to_string(), text));
+        }
+        Self::parse(&name, &files)
+    }
+
+    /// Build the pack `name` from `(file name, contents)` pairs
+    pub fn parse(name: &str, files: &[(String, String)]) -> Result<Self, ConfigError> {
+        let valid = !name.is_empty()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid || name == CUSTOM_NAMESPACE {
+            return Err(ConfigError::new(
+                0,
+                format!(
+                    "'{}' cannot name a rule pack; use letters, digits, '-' and '_' \
+                     (and not '{}')",
+                    name, CUSTOM_NAMESPACE
+                ),
+            ));
+        }
+
+        let mut rules: Vec<CustomRule> = Vec::new();
+        for (file, text) in files {
+            let in_file = |e: ConfigError| ConfigError::new(0, format!("{}: {}", file, e));
+            let only_rules = |line: usize| {
+                in_file(ConfigError::new(
+                    line,
+                    "rule files may only contain [rules.NAME] sections",
+                ))
+            };
+            let doc = toml::parse(text).map_err(in_file)?;
+            if let Some(entry) = doc.root.entries.first() {
+                return Err(only_rules(entry.line));
+            }
+            for table in &doc.tables {
+                let Some(rule_name) = table.name.strip_prefix("rules.") else {
+                    return Err(only_rules(table.line));
+                };
+                let mut rule = CustomRule::parse(rule_name, table).map_err(in_file)?;
+                if rules.iter().any(|r| r.name == rule.name) {
+                    return Err(in_file(ConfigError::new(
+                        table.line,
+                        format!("rule '{}' is already defined in pack '{}'", rule.name, name),
+                    )));
+                }
+                rule.namespace = name.to_string();
+                rules.push(rule);
+            }
+        }
+        Ok(Self {
+            name: name.to_string(),
+            rules,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, text: &str) -> (String, String) {
+        (name.to_string(), text.to_string())
+    }
+
+    #[test]
+    fn test_parse_pack() {
+        let pack = RulePack::parse(
+            "org-policies",
+            &[
+                file("docs.toml", "[rules.adr]\npath = \"docs/adr/\"\ncategory = \"Policy\"\n"),
+                file("sec.toml", "[rules.sbom]\npath = \"sbom.json\"\ncategory = \"Security\"\n"),
+            ],
+        )
+        .unwrap();
+        let ids: Vec<String> = pack.rules.iter().map(CustomRule::id).collect();
+        assert_eq!(ids, ["org-policies/adr", "org-policies/sbom"]);
+
+        let twice = file("b.toml", "[rules.adr]\npath = \"ADR.md\"\ncategory = \"Governance\"\n");
+        let err = RulePack::parse("org", &[twice.clone(), twice]).unwrap_err();
+        assert_eq!(err.to_string(), "b.toml: line 1: rule 'adr' is already defined in pack 'org'");
+
+        let err = RulePack::parse("org", &[file("a.toml", "[policy]\nfail_on_warning = true\n")]);
+        assert!(err.unwrap_err().message.contains("may only contain [rules.NAME]"));
+        assert!(RulePack::parse("custom", &[]).is_err());
+        assert!(RulePack::parse("org policies", &[]).is_err());
+    }
+}
@@ -0,0 +1,516 @@
+//! Minimal TOML reader (std-only)
+//!
+//! Rhodibot's own files (`.rhodibot.toml`, `.rhodibot/suppressions.toml`)
+//! use a small subset of TOML, read here without dependencies:
+//!
+//! - `[table]` headers; the name is taken as written, so `[a.b]` is one
+//!   table called `a.b`
+//! - `key = value` pairs with bare (`A-Za-z0-9_-`) or quoted keys
+//! - basic strings (`"..."`, with the TOML escapes) and literal strings
+//!   (`'...'`)
+//! - booleans, integers (optionally signed, with `_` separators) and floats
+//! - arrays of strings, which may span lines and end with a comma
+//! - `#` comments
+//!
+//! Dotted keys, inline tables, arrays of tables, multi-line strings and
+//! dates are rejected, as are duplicate keys and tables; every error names
+//! its line.
+
+use super::ConfigError;
+
+/// A value on the right-hand side of `key = value`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Array(Vec<String>),
+}
+
+impl Value {
+    /// Name of the value's type, for error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Bool(_) => "boolean",
+            Value::Integer(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Array(_) => "array",
+        }
+    }
+}
+
+/// One `key = value` pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub key: String,
+    pub value: Value,
+    /// 1-based line of the key
+    pub line: usize,
+}
+
+/// A table and its entries, in file order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+    /// Name in the header; empty for the root table
+    pub name: String,
+    /// 1-based line of the header; 0 for the root table
+    pub line: usize,
+    pub entries: Vec<Entry>,
+}
+
+impl Table {
+    /// Value of `key` in this table
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries.iter().find(|e| e.key == key).map(|e| &e.value)
+    }
+}
+
+/// A parsed file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Document {
+    /// Entries before the first header
+    pub root: Table,
+    /// Tables in file order
+    pub tables: Vec<Table>,
+}
+
+impl Document {
+    /// The table named `name`
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+}
+
+/// Parse a TOML document
+pub fn parse(text: &str) -> Result<Document, ConfigError> {
+    let mut parser = Parser {
+        text,
+        pos: 0,
+        line: 1,
+    };
+    let mut doc = Document::default();
+    loop {
+        parser.skip_blank();
+        match parser.peek() {
+            None => return Ok(doc),
+            Some('\n') => {
+                parser.bump();
+            }
+            Some('#') => parser.end_of_line()?,
+            Some('[') => {
+                let table = parser.header()?;
+                if doc.table(&table.name).is_some() {
+                    return Err(ConfigError::new(
+                        table.line,
+                        format!("duplicate table [{}]", table.name),
+                    ));
+                }
+                doc.tables.push(table);
+            }
+            Some(_) => {
+                let entry = parser.entry()?;
+                let table = doc.tables.last_mut().unwrap_or(&mut doc.root);
+                if table.get(&entry.key).is_some() {
+                    return Err(ConfigError::new(
+                        entry.line,
+                        format!("duplicate key '{}'", entry.key),
+                    ));
+                }
+                table.entries.push(entry);
+            }
+        }
+    }
+}
+
+/// Whether `c` may appear in a bare key
+fn is_bare_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    /// Byte offset of the next character
+    pos: usize,
+    /// 1-based line of the next character
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, message: impl Into<String>) -> ConfigError {
+        ConfigError::new(self.line, message)
+    }
+
+    /// Skip spaces and tabs (and the `\r` of a CRLF line ending)
+    fn skip_blank(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\r')) {
+            self.bump();
+        }
+    }
+
+    /// Skip whitespace, line breaks and comments between array elements
+    fn skip_array_space(&mut self) {
+        loop {
+            match self.peek() {
+                Some(' ' | '\t' | '\r' | '\n') => {
+                    self.bump();
+                }
+                Some('#') => {
+                    while !matches!(self.peek(), None | Some('\n')) {
+                        self.bump();
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Accept an optional comment and the end of the line
+    fn end_of_line(&mut self) -> Result<(), ConfigError> {
+        self.skip_blank();
+        if self.peek() == Some('#') {
+            while !matches!(self.peek(), None | Some('\n')) {
+                self.bump();
+            }
+        }
+        match self.bump() {
+            None | Some('\n') => Ok(()),
+            Some(c) => Err(self.error(format!("unexpected '{}' after value", c))),
+        }
+    }
+
+    /// `[name]`
+    fn header(&mut self) -> Result<Table, ConfigError> {
+        let line = self.line;
+        self.bump();
+        if self.peek() == Some('[') {
+            return Err(self.error("arrays of tables are not supported"));
+        }
+        let rest = &self.text[self.pos..];
+        let close = rest.find(']').filter(|&i| !rest[..i].contains('\n'));
+        let Some(close) = close else {
+            return Err(self.error("malformed section header"));
+        };
+        let name = rest[..close].trim().to_string();
+        if name.is_empty() || !name.chars().all(|c| is_bare_key_char(c) || c == '.') {
+            return Err(self.error("malformed section header"));
+        }
+        self.pos += close + 1;
+        self.end_of_line()?;
+        Ok(Table {
+            name,
+            line,
+            entries: Vec::new(),
+        })
+    }
+
+    /// `key = value`
+    fn entry(&mut self) -> Result<Entry, ConfigError> {
+        let line = self.line;
+        let key = match self.peek() {
+            Some('"') => self.basic_string()?,
+            Some('\'') => self.literal_string()?,
+            _ => {
+                let start = self.pos;
+                while self.peek().map_or(false, is_bare_key_char) {
+                    self.bump();
+                }
+                self.text[start..self.pos].to_string()
+            }
+        };
+        self.skip_blank();
+        match self.bump() {
+            Some('=') if !key.is_empty() => {}
+            Some('.') => return Err(self.error("dotted keys are not supported")),
+            _ => return Err(ConfigError::new(line, "expected 'key = value'")),
+        }
+        self.skip_blank();
+        let value = self.value()?;
+        self.end_of_line()?;
+        Ok(Entry { key, value, line })
+    }
+
+    fn value(&mut self) -> Result<Value, ConfigError> {
+        match self.peek() {
+            Some('"') if self.text[self.pos..].starts_with("\"\"\"") => {
+                Err(self.error("multi-line strings are not supported"))
+            }
+            Some('"') => self.basic_string().map(Value::String),
+            Some('\'') => self.literal_string().map(Value::String),
+            Some('[') => self.array(),
+            Some('{') => Err(self.error("inline tables are not supported")),
+            None | Some('\n' | '#') => Err(self.error("missing value")),
+            Some(_) => {
+                let start = self.pos;
+                while !matches!(
+                    self.peek(),
+                    None | Some(' ' | '\t' | '\r' | '\n' | ',' | ']' | '#')
+                ) {
+                    self.bump();
+                }
+                let token = &self.text[start..self.pos];
+                match token {
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    _ => parse_number(token)
+                        .ok_or_else(|| self.error(format!("invalid value '{}'", token))),
+                }
+            }
+        }
+    }
+
+    /// `"..."` with escapes
+    fn basic_string(&mut self) -> Result<String, ConfigError> {
+        self.bump();
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n') => return Err(self.error("unterminated string")),
+                Some('"') => return Ok(out),
+                Some('\\') => out.push(self.escape()?),
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    /// The character of an escape sequence, after its backslash
+    fn escape(&mut self) -> Result<char, ConfigError> {
+        let digits = match self.bump() {
+            Some('b') => return Ok('\u{8}'),
+            Some('t') => return Ok('\t'),
+            Some('n') => return Ok('\n'),
+            Some('f') => return Ok('\u{c}'),
+            Some('r') => return Ok('\r'),
+            Some('"') => return Ok('"'),
+            Some('\\') => return Ok('\\'),
+            Some('u') => 4,
+            Some('U') => 8,
+            Some(c) => return Err(self.error(format!("invalid escape '\\{}'", c))),
+            None => return Err(self.error("unterminated string")),
+        };
+        let hex = self.text[self.pos..].get(..digits).unwrap_or("");
+        let c = u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.len() == digits && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error(format!("invalid unicode escape '{}'", hex)))?;
+        self.pos += digits;
+        Ok(c)
+    }
+
+    /// `'...'`, taken verbatim
+    fn literal_string(&mut self) -> Result<String, ConfigError> {
+        self.bump();
+        let start = self.pos;
+        loop {
+            match self.bump() {
+                None | Some('\n') => return Err(self.error("unterminated string")),
+                Some('\'') => return Ok(self.text[start..self.pos - 1].to_string()),
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// `[ "a", "b", ]`, possibly over several lines
+    fn array(&mut self) -> Result<Value, ConfigError> {
+        self.bump();
+        let mut items = Vec::new();
+        loop {
+            self.skip_array_space();
+            match self.peek() {
+                Some(']') => {
+                    self.bump();
+                    return Ok(Value::Array(items));
+                }
+                None => return Err(self.error("unterminated array")),
+                _ => {}
+            }
+            match self.value()? {
+                Value::String(s) => items.push(s),
+                other => {
+                    return Err(self.error(format!(
+                        "array elements must be strings, found {}",
+                        other.type_name()
+                    )))
+                }
+            }
+            self.skip_array_space();
+            match self.bump() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(items)),
+                None => return Err(self.error("unterminated array")),
+                Some(c) => {
+                    return Err(self.error(format!("expected ',' or ']' in array, found '{}'", c)))
+                }
+            }
+        }
+    }
+}
+
+/// An integer or float in TOML syntax
+fn parse_number(token: &str) -> Option<Value> {
+    let unsigned = token.strip_prefix(['+', '-']).unwrap_or(token);
+    if matches!(unsigned, "inf" | "nan") {
+        return token.parse().ok().map(Value::Float);
+    }
+    // Underscores only between digits
+    let bytes = unsigned.as_bytes();
+    let separators_ok = bytes.iter().enumerate().all(|(i, &b)| {
+        b != b'_'
+            || (i > 0
+                && bytes[i - 1].is_ascii_digit()
+                && bytes.get(i + 1).map_or(false, u8::is_ascii_digit))
+    });
+    if !separators_ok || !bytes.first().map_or(false, u8::is_ascii_digit) {
+        return None;
+    }
+    let clean: String = token.chars().filter(|&c| c != '_').collect();
+    if unsigned.bytes().all(|b| b.is_ascii_digit() || b == b'_') {
+        return clean.parse().ok().map(Value::Integer);
+    }
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(i) => (&unsigned[..i], Some(&unsigned[i + 1..])),
+        None => (unsigned, None),
+    };
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit() || b == b'_');
+    let mantissa_ok = match mantissa.split_once('.') {
+        Some((int, frac)) => digits(int) && digits(frac),
+        None => digits(mantissa),
+    };
+    let exponent_ok = exponent.map_or(true, |e| digits(e.strip_prefix(['+', '-']).unwrap_or(e)));
+    if mantissa_ok && exponent_ok {
+        clean.parse().ok().map(Value::Float)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(raw: &str) -> Result<Value, ConfigError> {
+        parse(&format!("key = {}", raw)).map(|doc| doc.root.entries[0].value.clone())
+    }
+
+    #[test]
+    fn test_tables_and_entries() {
+        let doc = parse(
+            "# leading comment\n\
+             title = \"demo\"\n\
+             \n\
+             [policy]  # trailing comment\n\
+             fail_on_warning = true\n\
+             \"quoted key\" = 'x'\n\
+             [RSR-BLD-002]\r\n\
+             reason = \"Guix\"\r\n",
+        )
+        .unwrap();
+        assert_eq!(doc.root.get("title"), Some(&Value::String("demo".to_string())));
+        assert_eq!(doc.root.line, 0);
+
+        let names: Vec<(&str, usize)> =
+            doc.tables.iter().map(|t| (t.name.as_str(), t.line)).collect();
+        assert_eq!(names, vec![("policy", 4), ("RSR-BLD-002", 7)]);
+        let policy = doc.table("policy").unwrap();
+        assert_eq!(policy.get("fail_on_warning"), Some(&Value::Bool(true)));
+        assert_eq!(policy.get("quoted key"), Some(&Value::String("x".to_string())));
+        assert_eq!(policy.entries[1].line, 6);
+        assert_eq!(
+            doc.table("RSR-BLD-002").unwrap().get("reason"),
+            Some(&Value::String("Guix".to_string()))
+        );
+        assert!(doc.table("checks").is_none());
+        assert_eq!(parse("").unwrap(), Document::default());
+    }
+
+    #[test]
+    fn test_strings() {
+        let s = |raw: &str| match value(raw) {
+            Ok(Value::String(s)) => s,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(s(r#""a \"quoted\" \\ path\tand\nlines""#), "a \"quoted\" \\ path\tand\nlines");
+        assert_eq!(s(r#""caf\u00e9 \U0001F600""#), "café 😀");
+        assert_eq!(s(r"'C:\no\escapes'"), r"C:\no\escapes");
+        assert_eq!(s("\"# not a comment\""), "# not a comment");
+        assert_eq!(s("\"\""), "");
+
+        assert!(value("\"open").unwrap_err().message.contains("unterminated string"));
+        assert!(value("'open").is_err());
+        assert!(value(r#""bad \q""#).unwrap_err().message.contains("invalid escape"));
+        assert!(value(r#""\u12""#).is_err());
+        assert!(value(r#""\uD800""#).is_err());
+        assert!(value("\"\"\"multi\"\"\"").is_err());
+    }
+
+    #[test]
+    fn test_numbers_and_booleans() {
+        assert_eq!(value("false").unwrap(), Value::Bool(false));
+        assert_eq!(value("42").unwrap(), Value::Integer(42));
+        assert_eq!(value("-17").unwrap(), Value::Integer(-17));
+        assert_eq!(value("+1_000_000").unwrap(), Value::Integer(1_000_000));
+        assert_eq!(value("87.5").unwrap(), Value::Float(87.5));
+        assert_eq!(value("-2.5e-3").unwrap(), Value::Float(-0.0025));
+        assert_eq!(value("1E6").unwrap(), Value::Float(1e6));
+        assert_eq!(value("inf").unwrap(), Value::Float(f64::INFINITY));
+
+        for invalid in ["True", "yes", "1__0", "_1", "1_", ".5", "5.", "1e", "0x1F", "1979-05-27"] {
+            let err = value(invalid).unwrap_err();
+            assert_eq!(err.message, format!("invalid value '{}'", invalid));
+        }
+        assert!(value("99999999999999999999").is_err());
+    }
+
+    #[test]
+    fn test_arrays() {
+        let strings = |items: &[&str]| Value::Array(items.iter().map(|s| s.to_string()).collect());
+        assert_eq!(value("[]").unwrap(), strings(&[]));
+        assert_eq!(value(r#"["a", 'b',]"#).unwrap(), strings(&["a", "b"]));
+        assert_eq!(value(r#"["a,b", "]"]"#).unwrap(), strings(&["a,b", "]"]));
+
+        let doc = parse("list = [\n  \"one\",  # first\n\n  \"two\"\n]\nnext = 1\n").unwrap();
+        assert_eq!(doc.root.get("list"), Some(&strings(&["one", "two"])));
+        assert_eq!(doc.root.entries[1].line, 6);
+
+        let err = value("[1]").unwrap_err();
+        assert_eq!(err.message, "array elements must be strings, found integer");
+        assert!(value(r#"[["a"]]"#).is_err());
+        assert!(value(r#"["a" "b"]"#).unwrap_err().message.contains("expected ','"));
+        assert_eq!(value(r#"["a","#).unwrap_err().message, "unterminated array");
+    }
+
+    #[test]
+    fn test_errors_name_their_line() {
+        let err = |text: &str| parse(text).unwrap_err();
+
+        let e = err("[policy]\nfail_on_warning = true\nfail_on_warning = false\n");
+        assert_eq!((e.line, e.message.as_str()), (3, "duplicate key 'fail_on_warning'"));
+        let e = err("[a]\nx = 1\n[a]\n");
+        assert_eq!((e.line, e.message.as_str()), (3, "duplicate table [a]"));
+        assert_eq!(err("\n\njust words\n").line, 3);
+        assert_eq!(err("[policy\nx = 1").message, "malformed section header");
+        assert_eq!(err("[]").message, "malformed section header");
+        assert_eq!(err("[[bin]]").message, "arrays of tables are not supported");
+        assert_eq!(err("a.b = 1").message, "dotted keys are not supported");
+        assert_eq!(err("a = { b = 1 }").message, "inline tables are not supported");
+        assert_eq!(err("a =\n").message, "missing value");
+        assert_eq!(err("a = 1 2").message, "unexpected '2' after value");
+        assert_eq!(err("= 1").message, "expected 'key = value'");
+        assert_eq!(err("a = \"x\" \"y\"").line, 1);
+    }
+}
@@ -0,0 +1,145 @@
+//! Committed conformity documents (`conformity --write`, `RSR-CNF-001`)
+//!
+//! `rhodibot conformity --write` stores the conformity statement in the
+//! repository as `CONFORMITY.md`. Its requirement tables record the result
+//! of every check, so a later verification can tell whether the published
+//! statement still holds: a check whose result changed, or that was added or
+//! removed since, is drift. The date and level lines are not compared, so
+//! re-verifying an unchanged repository never drifts.
+
+use crate::{catalog, CheckResult, CheckStatus, ComplianceReport};
+
+/// File the conformity statement is written to
+pub const CONFORMITY_FILE: &str = "CONFORMITY.md";
+/// Check that compares the committed statement with the current results
+pub const DRIFT_CHECK: &str = "RSR-CNF-001";
+
+/// Result of a check as written in the requirement tables
+pub fn status(check: &CheckResult) -> &'static str {
+    match check.status {
+        _ if check.waived => "Waived",
+        CheckStatus::Pass => "Pass",
+        CheckStatus::Fail => "Fail",
+        CheckStatus::Skipped => "Skipped",
+        CheckStatus::NotApplicable => "N/A",
+        CheckStatus::Error => "Error",
+    }
+}
+
+/// Check results recorded in a conformity document, as `(id, status)`
+///
+/// Only rows of the `## ... Requirements` tables count; the waiver table
+/// has the same shape but records something else.
+pub fn recorded_results(doc: &str) -> Vec<(&str, &str)> {
+    let mut results = Vec::new();
+    let mut in_requirements = false;
+    for line in doc.lines() {
+        let line = line.trim();
+        if let Some(heading) = line.strip_prefix("## ") {
+            in_requirements = heading.contains(" Requirements");
+            continue;
+        }
+        let Some(row) = line.strip_prefix('|').and_then(|l| l.strip_suffix('|')) else {
+            continue;
+        };
+        let cells: Vec<&str> = row.split('|').map(str::trim).collect();
+        if let (true, [id, _, status]) = (in_requirements, cells.as_slice()) {
+            if id.starts_with("RSR-") || id.starts_with("rsr/") {
+                results.push((*id, *status));
+            }
+        }
+    }
+    results
+}
+
+/// Differences between a committed conformity document and `report`
+///
+/// The drift check itself is left out, as are checks skipped in this run.
+pub fn drift(doc: &str, report: &ComplianceReport) -> Vec<String> {
+    let recorded = recorded_results(doc);
+    let mut problems = Vec::new();
+    for check in report.checks.iter().filter(|c| c.id != DRIFT_CHECK) {
+        match recorded.iter().find(|(id, _)| *id == check.id) {
+            Some((_, was)) if *was == status(check) => {}
+            Some((_, was)) => problems.push(format!(
+                "{} is {} but {} says {}",
+                check.id,
+                status(check),
+                CONFORMITY_FILE,
+                was
+            )),
+            None => problems.push(format!("{} is missing from {}", check.id, CONFORMITY_FILE)),
+        }
+    }
+    for (id, _) in recorded {
+        let current = id == DRIFT_CHECK
+            || report.checks.iter().any(|c| c.id == id)
+            || report.skipped_checks.iter().any(|s| s == id);
+        if !current {
+            problems.push(format!("{} in {} is no longer checked", id, CONFORMITY_FILE));
+        }
+    }
+    problems
+}
+
+/// Mark the drift check as passing in a report a document is written from
+///
+/// The document about to be written matches the report by construction, so
+/// it should not record its own predecessor's drift.
+pub fn mark_in_sync(report: &mut ComplianceReport) {
+    if let Some(check) = report.checks.iter_mut().find(|c| c.id == DRIFT_CHECK) {
+        check.status = CheckStatus::Pass;
+        check.waived = false;
+        check.description = catalog::lookup(DRIFT_CHECK).map(|spec| spec.description.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_conformity_doc;
+    use std::path::PathBuf;
+
+    fn sample_report() -> ComplianceReport {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/project"));
+        report.add_spec_check(catalog::lookup("RSR-DOC-001").unwrap(), true);
+        report.add_spec_check(catalog::lookup("RSR-DOC-002").unwrap(), false);
+        report.add_spec_check(catalog::lookup(DRIFT_CHECK).unwrap(), false);
+        report
+    }
+
+    #[test]
+    fn test_written_document_is_in_sync() {
+        let mut report = sample_report();
+        mark_in_sync(&mut report);
+        assert!(report.checks[2].passed());
+
+        let doc = generate_conformity_doc(&report);
+        assert_eq!(
+            recorded_results(&doc),
+            vec![("RSR-DOC-001", "Pass"), ("RSR-DOC-002", "Fail"), (DRIFT_CHECK, "Pass")]
+        );
+        assert!(drift(&doc, &report).is_empty());
+    }
+
+    #[test]
+    fn test_drift() {
+        let doc = generate_conformity_doc(&sample_report());
+        let mut report = sample_report();
+        report.checks[1].status = CheckStatus::Pass;
+        report.add_check("Extras", "SUPPORT.md", true, crate::ComplianceLevel::Silver);
+        report.checks.remove(0);
+
+        assert_eq!(
+            drift(&doc, &report),
+            vec![
+                "RSR-DOC-002 is Pass but CONFORMITY.md says Fail",
+                "rsr/extras/support.md is missing from CONFORMITY.md",
+                "RSR-DOC-001 in CONFORMITY.md is no longer checked",
+            ]
+        );
+
+        report.skipped_checks.push("RSR-DOC-001".to_string());
+        assert_eq!(drift(&doc, &report).len(), 2);
+    }
+}
@@ -0,0 +1,224 @@
+//! Contact validation for security.txt, humans.txt and MAINTAINERS.md
+//!
+//! These files exist so people can reach the maintainers, and a contact
+//! that cannot work defeats them. Every contact found in them is checked
+//! for syntax, as an email address, a `mailto:` URI or an `https://` URL,
+//! and for placeholders left over from a template such as
+//! `user@example.org`. The problems are reported as warnings rather than
+//! check failures: the files exist, they just need another look.
+//!
+//! security.txt is held to RFC 9116: it needs a `Contact:` field, and each
+//! one must be a `mailto:`, `https:` or `tel:` URI rather than a bare
+//! address.
+
+use crate::content::Problem;
+use crate::links;
+
+/// Domains reserved for documentation by RFC 2606
+const PLACEHOLDER_DOMAINS: &[&str] = &["example.com", "example.net", "example.org"];
+
+/// Top-level domains that never resolve (RFC 2606)
+const PLACEHOLDER_TLDS: &[&str] = &["example", "invalid", "test", "localhost"];
+
+/// Local parts that stand for "your address here"
+const PLACEHOLDER_USERS: &[&str] = &[
+    "user", "username", "you", "yourname", "your.name", "your-name", "name", "email", "someone",
+    "john.doe", "jane.doe",
+];
+
+/// Characters allowed in the local part of an address besides letters and
+/// digits (RFC 5322 dot-atoms)
+const LOCAL_PART_SYMBOLS: &str = "!#$%&'*+/=?^_`{|}~.-";
+
+/// Characters that separate contacts in Markdown and plain text
+fn separates(c: char) -> bool {
+    c.is_whitespace() || "<>()[]|\"'`,;*".contains(c)
+}
+
+/// Whether `domain` is reserved for examples or never resolves
+fn is_placeholder_domain(domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    PLACEHOLDER_DOMAINS
+        .iter()
+        .any(|reserved| domain == *reserved || domain.ends_with(&format!(".{}", reserved)))
+        || domain.rsplit('.').next().map_or(false, |tld| PLACEHOLDER_TLDS.contains(&tld))
+}
+
+/// Why `address` is not a usable email address, or `None` if it is one
+pub fn email_problem(address: &str) -> Option<String> {
+    let (user, domain) = address.rsplit_once('@').unwrap_or((address, ""));
+    let valid_user = !user.is_empty()
+        && user.len() <= 64
+        && !user.starts_with('.')
+        && !user.ends_with('.')
+        && !user.contains("..")
+        && user.chars().all(|c| c.is_alphanumeric() || LOCAL_PART_SYMBOLS.contains(c));
+    let valid_tld = domain
+        .rsplit('.')
+        .next()
+        .map_or(false, |tld| tld.chars().count() >= 2 && !tld.chars().all(|c| c.is_ascii_digit()));
+    if !valid_user || !domain.contains('.') || !links::is_valid_host(domain) || !valid_tld {
+        return Some(format!("'{}' is not a valid email address", address));
+    }
+    if PLACEHOLDER_USERS.contains(&user.to_ascii_lowercase().as_str())
+        || is_placeholder_domain(domain)
+    {
+        return Some(format!("'{}' is a placeholder address", address));
+    }
+    None
+}
+
+/// Why the contact `value` cannot reach anyone, or `None` if it can
+///
+/// `mailto:`, `https:` and `tel:` URIs are accepted, and with `bare_email`
+/// plain addresses too.
+pub fn contact_problem(value: &str, bare_email: bool) -> Option<String> {
+    let lower = value.to_ascii_lowercase();
+    if lower.starts_with("mailto:") {
+        let address = value["mailto:".len()..].split('?').next().unwrap_or_default();
+        return email_problem(address);
+    }
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        if let Some(problem) = links::problem(value) {
+            return Some(format!("'{}' {}", value, problem));
+        }
+        if is_placeholder_domain(links::host(value)) {
+            return Some(format!("'{}' is a placeholder URL", value));
+        }
+        return None;
+    }
+    if let Some(number) = lower.strip_prefix("tel:") {
+        let valid = number.chars().any(|c| c.is_ascii_digit())
+            && number.chars().all(|c| c.is_ascii_digit() || "+-.() ".contains(c));
+        return (!valid).then(|| format!("'{}' is not a valid telephone URI", value));
+    }
+    if bare_email && value.contains('@') {
+        return email_problem(value);
+    }
+    Some(format!("'{}' is not a mailto:, https: or tel: URI", value))
+}
+
+/// Problems with the email addresses, `mailto:` links and URLs in `line`
+///
+/// Words with an `@` but no dot after it, such as `@handle` mentions, are
+/// not addresses and are left alone, as are SSH remotes like
+/// `git@host:org/repo`.
+fn line_problems(line: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    for token in line.split(separates) {
+        let token = token.trim_end_matches(['.', ':', '!', '?']);
+        let lower = token.to_ascii_lowercase();
+        let is_address = !token.contains("://")
+            && token.split_once('@').map_or(false, |(_, domain)| {
+                domain.contains('.') && !domain.contains([':', '/'])
+            });
+        let is_contact = is_address
+            || lower.starts_with("mailto:")
+            || lower.starts_with("http://")
+            || lower.starts_with("https://");
+        if !is_contact {
+            continue;
+        }
+        if let Some(problem) = contact_problem(token, true) {
+            if !problems.contains(&problem) {
+                problems.push(problem);
+            }
+        }
+    }
+    problems
+}
+
+/// Validate the `Contact:` fields of `.well-known/security.txt`
+pub fn validate_security_txt(text: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut contacts = 0;
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if line.starts_with('#') || !key.trim().eq_ignore_ascii_case("contact") {
+            continue;
+        }
+        contacts += 1;
+        if let Some(problem) = contact_problem(value.trim(), false) {
+            problems.push(Problem::at(index + 1, problem));
+        }
+    }
+    if contacts == 0 {
+        problems.push(Problem::file("no 'Contact:' field"));
+    }
+    problems
+}
+
+/// Validate the contacts in `.well-known/humans.txt` or `MAINTAINERS.md`
+///
+/// Both are free-form, so only what looks like an address or a URL is
+/// checked; a file that gives no contact at all is the content checks'
+/// business.
+pub fn validate_contacts(text: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        for problem in line_problems(line) {
+            problems.push(Problem::at(index + 1, problem));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_problem() {
+        for address in ["ada@lovelace.dev", "security+rsr@mail.b.org", "o'neil@bücher.de"] {
+            assert_eq!(email_problem(address), None, "{}", address);
+        }
+        for address in ["ada", "@x.org", "ada@localhost", "a..b@x.org", "ada@x.1", "ada@-x.org"] {
+            assert!(email_problem(address).unwrap().contains("not a valid"), "{}", address);
+        }
+        for address in ["ada@example.com", "ada@mail.example.org", "user@rsr.dev", "a@b.invalid"] {
+            assert!(email_problem(address).unwrap().contains("placeholder"), "{}", address);
+        }
+    }
+
+    #[test]
+    fn test_validate_security_txt() {
+        let text = "# Contact: ignored\n\
+                    Contact: mailto:security@rsr.dev\n\
+                    Contact: https://rsr.dev/security\n\
+                    Contact: tel:+1-201-555-0123\n\
+                    Contact: security@rsr.dev\n\
+                    CONTACT: http://rsr.dev/security\n\
+                    Contact: https://example.com/report\n\
+                    Expires: 2030-01-01T00:00:00Z\n";
+        let problems: Vec<String> =
+            validate_security_txt(text).iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            problems,
+            [
+                "line 5: 'security@rsr.dev' is not a mailto:, https: or tel: URI",
+                "line 6: 'http://rsr.dev/security' uses http:// instead of https://",
+                "line 7: 'https://example.com/report' is a placeholder URL",
+            ]
+        );
+        assert_eq!(validate_security_txt("Expires: 2030\n")[0].message, "no 'Contact:' field");
+    }
+
+    #[test]
+    fn test_validate_contacts() {
+        let text = "# Maintainers\n\
+                    | Ada | <ada@rsr.dev> | [@ada](https://github.com/ada) |\n\
+                    | Bob | [mail](mailto:you@example.com) | bob..x@rsr.dev |\n\
+                    Contact: TODO\n";
+        let problems: Vec<String> = validate_contacts(text).iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            problems,
+            [
+                "line 3: 'you@example.com' is a placeholder address",
+                "line 3: 'bob..x@rsr.dev' is not a valid email address",
+            ]
+        );
+    }
+}
@@ -0,0 +1,782 @@
+//! Content heuristics for compliance files
+//!
+//! Existence checks only prove a file is there. The validators in this module
+//! look inside: each takes the file's text and returns the problems found,
+//! an empty list meaning the content is acceptable. They are deliberately
+//! forgiving heuristics — a file passes if a reader could plausibly find the
+//! required information in it.
+
+use rsr_spec::yaml::{self, Node, Value};
+use std::collections::HashSet;
+use std::fmt;
+
+/// A problem found in a file's content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// 1-based line the problem refers to, when it has one
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Problem {
+    /// A problem with the file as a whole
+    pub fn file(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    /// A problem on a specific line
+    pub fn at(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// A content validator: the problems found in a file's text
+pub type Validator = fn(&str) -> Vec<Problem>;
+
+/// Lowercase words of `text`, split on anything that is not alphanumeric
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Whether `text` contains an email address or a `mailto:` link
+fn has_email(text: &str) -> bool {
+    text.split(|c: char| c.is_whitespace() || "<>()[]\"'`,;".contains(c))
+        .any(|token| {
+            let token = token.trim_start_matches("mailto:").trim_end_matches('.');
+            match token.split_once('@') {
+                Some((user, domain)) => {
+                    !user.is_empty()
+                        && domain.contains('.')
+                        && !domain.starts_with('.')
+                        && !domain.ends_with('.')
+                }
+                None => false,
+            }
+        })
+}
+
+/// Check that a file reaches `min_bytes` bytes and `min_lines` non-blank lines
+pub fn validate_length(text: &str, min_bytes: usize, min_lines: usize) -> Vec<Problem> {
+    let lines = text.lines().filter(|line| !line.trim().is_empty()).count();
+    if lines == 0 && min_lines > 0 {
+        return vec![Problem::file("is empty")];
+    }
+    let mut problems = Vec::new();
+    if text.len() < min_bytes {
+        problems.push(Problem::file(format!(
+            "has {} bytes, at least {} required",
+            text.len(),
+            min_bytes
+        )));
+    }
+    if lines < min_lines {
+        problems.push(Problem::file(format!(
+            "has {} non-blank lines, at least {} required",
+            lines, min_lines
+        )));
+    }
+    problems
+}
+
+/// Share of distinct words two texts have in common, from 0.0 to 1.0
+///
+/// Markup is ignored, so a Markdown and an AsciiDoc rendering of the same
+/// document score close to 1.0.
+pub fn word_similarity(a: &str, b: &str) -> f64 {
+    let a: HashSet<String> = words(a).collect();
+    let b: HashSet<String> = words(b).collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Phrases identifying common license texts, most specific first
+const LICENSE_PHRASES: &[(&str, &[&str])] = &[
+    ("AGPL-3.0", &["gnu affero general public license"]),
+    ("LGPL-3.0", &["gnu lesser general public license", "version 3"]),
+    ("LGPL-2.1", &["gnu lesser general public license"]),
+    ("GPL-3.0", &["gnu general public license", "version 3"]),
+    ("GPL-2.0", &["gnu general public license", "version 2"]),
+    ("MPL-2.0", &["mozilla public license", "2.0"]),
+    ("Apache-2.0", &["apache license", "version 2.0"]),
+    ("MIT", &["permission is hereby granted, free of charge"]),
+    ("ISC", &["permission to use, copy, modify, and/or distribute this software"]),
+    ("BSD-3-Clause", &["redistribution and use in source and binary forms", "neither the name"]),
+    ("BSD-2-Clause", &["redistribution and use in source and binary forms"]),
+    ("Unlicense", &["free and unencumbered software released into the public domain"]),
+];
+
+/// The license a license file grants, as an SPDX expression
+///
+/// An `SPDX-License-Identifier:` line wins; otherwise the text is matched
+/// against well-known license wording.
+pub fn detect_license(text: &str) -> Option<String> {
+    let declared = text.lines().find_map(|line| {
+        let (_, id) = line.split_once("SPDX-License-Identifier:")?;
+        let id = id.trim();
+        (!id.is_empty()).then(|| id.to_string())
+    });
+    if declared.is_some() {
+        return declared;
+    }
+    let lower = text.to_lowercase();
+    LICENSE_PHRASES
+        .iter()
+        .find(|(_, phrases)| phrases.iter().all(|phrase| lower.contains(phrase)))
+        .map(|(id, _)| id.to_string())
+}
+
+/// Lines that only mark a section as unwritten
+const PLACEHOLDER_LINES: &[&str] = &[
+    "todo",
+    "tbd",
+    "tbc",
+    "fixme",
+    "coming soon",
+    "placeholder",
+    "to be written",
+    "to do",
+];
+
+/// Find placeholder text: lorem ipsum filler anywhere, or a file whose
+/// every line besides headings is a marker like "TODO", "TODO: write this"
+/// or "Coming soon"
+pub fn find_placeholder(text: &str) -> Option<Problem> {
+    let lower = text.to_lowercase();
+    // "dolor" keeps a mere mention of lorem ipsum from counting
+    if let Some(offset) = lower.find("lorem ipsum dolor") {
+        let line = lower[..offset].matches('\n').count() + 1;
+        return Some(Problem::at(line, "contains placeholder text 'lorem ipsum'"));
+    }
+    let mut body = lower
+        .lines()
+        // Markdown and AsciiDoc headings
+        .filter(|line| !line.trim_start().starts_with(['#', '=']))
+        .map(|line| line.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|line| !line.is_empty())
+        .peekable();
+    body.peek()?;
+    let only_markers = body.all(|line| {
+        PLACEHOLDER_LINES.iter().any(|marker| {
+            line.strip_prefix(marker).map_or(false, |rest| {
+                rest.is_empty() || rest.trim_start().starts_with([':', '-'])
+            })
+        })
+    });
+    only_markers.then(|| Problem::file("holds only placeholder text (e.g. 'TODO')"))
+}
+
+/// Marker on each placeholder of the starter files `rhodibot fix` writes
+pub const STUB_MARKER: &str = "TODO(rhodibot)";
+
+/// Find the first placeholder of a `rhodibot fix` starter file that has not
+/// been replaced
+///
+/// The marker is rhodibot's own, so unlike [`find_placeholder`] this is no
+/// heuristic and `detect_placeholders = false` does not turn it off. A
+/// marker in a code span (`` `TODO(rhodibot)` ``) is a mention, not a
+/// placeholder.
+pub fn find_stub(text: &str) -> Option<Problem> {
+    let index = text.lines().position(|line| {
+        line.match_indices(STUB_MARKER).any(|(start, _)| !line[..start].ends_with('`'))
+    })?;
+    Some(Problem::at(
+        index + 1,
+        format!("has a placeholder from 'rhodibot fix' to replace ({})", STUB_MARKER),
+    ))
+}
+
+/// Check that CONTRIBUTING explains how to run the tests and submit changes
+pub fn validate_contributing(text: &str) -> Vec<Problem> {
+    let words: Vec<String> = words(text).collect();
+    let lower = text.to_lowercase();
+    let mut problems = Vec::new();
+
+    if !words.iter().any(|w| w.starts_with("test")) {
+        problems.push(Problem::file("does not explain how to run the tests"));
+    }
+
+    let submits = ["pull request", "merge request", "send-email", "mailing list"]
+        .iter()
+        .any(|phrase| lower.contains(phrase))
+        || words.iter().any(|w| {
+            matches!(
+                w.as_str(),
+                "pr" | "prs" | "mr" | "mrs" | "patch" | "patches" | "submit" | "submitting" | "fork"
+            )
+        });
+    if !submits {
+        problems.push(Problem::file(
+            "does not explain how to submit changes (pull/merge request or patch)",
+        ));
+    }
+    problems
+}
+
+/// Check that a code of conduct follows a known template or names how to
+/// reach whoever enforces it
+pub fn validate_code_of_conduct(text: &str) -> Vec<Problem> {
+    let lower = text.to_lowercase();
+    let known_template = ["contributor covenant", "citizen code of conduct"]
+        .iter()
+        .any(|name| lower.contains(name));
+    if known_template {
+        return Vec::new();
+    }
+
+    let mentions_enforcement = words(text).any(|w| {
+        w.starts_with("enforce") || w.starts_with("report") || w == "contact"
+    });
+    let has_contact = has_email(text) || lower.contains("http://") || lower.contains("https://");
+    if mentions_enforcement && has_contact {
+        Vec::new()
+    } else {
+        vec![Problem::file(
+            "is not a known template (e.g. Contributor Covenant) and gives no enforcement contact",
+        )]
+    }
+}
+
+/// Directives of ai.txt that state a training policy
+const AI_POLICY_FIELDS: &[&str] = &["allow", "disallow", "allow-training", "disallow-training"];
+
+/// Validate `.well-known/ai.txt`
+///
+/// The minimal schema follows robots.txt: blank lines, `#` comments and
+/// `Field: value` lines. At least one `User-agent` group is required, and the
+/// file must state a training policy with `Allow`/`Disallow` (or
+/// `Allow-training`/`Disallow-training`) directives inside a group. Other
+/// fields are allowed.
+pub fn validate_ai_txt(text: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut in_group = false;
+    let mut has_policy = false;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((field, value)) = line.split_once(':') else {
+            problems.push(Problem::at(line_no, "expected 'Field: value'"));
+            continue;
+        };
+        let field = field.trim();
+        if field.is_empty() || !field.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            problems.push(Problem::at(line_no, format!("invalid field name '{}'", field)));
+            continue;
+        }
+        if value.trim().is_empty() {
+            problems.push(Problem::at(line_no, format!("'{}' has no value", field)));
+            continue;
+        }
+        let field = field.to_ascii_lowercase();
+        if field == "user-agent" {
+            in_group = true;
+        } else if AI_POLICY_FIELDS.contains(&field.as_str()) {
+            if in_group {
+                has_policy = true;
+            } else {
+                problems.push(Problem::at(
+                    line_no,
+                    format!("'{}' appears before any 'User-agent' line", field),
+                ));
+            }
+        }
+    }
+
+    if !in_group {
+        problems.push(Problem::file("no 'User-agent' line"));
+    } else if !has_policy {
+        problems.push(Problem::file(
+            "no training policy ('Allow'/'Disallow' or 'Allow-training'/'Disallow-training')",
+        ));
+    }
+    problems
+}
+
+/// Fields in a humans.txt section that say how to reach someone
+const HUMANS_CONTACT_FIELDS: &[&str] = &[
+    "contact", "email", "e-mail", "site", "web", "twitter", "mastodon", "github",
+];
+
+/// Validate `.well-known/humans.txt`
+///
+/// The minimal schema follows humanstxt.org: content is grouped under
+/// `/* SECTION */` headings, and a `TEAM` section naming at least one person
+/// or group with a way to contact them is required. Text before the first
+/// heading is not allowed.
+pub fn validate_humans_txt(text: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut section: Option<String> = None;
+    let mut team_line = None;
+    let mut team_entries = 0;
+    let mut team_contact = false;
+
+    for (index, raw) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("/*") {
+            match rest.strip_suffix("*/") {
+                Some(name) if !name.trim().is_empty() => {
+                    let name = name.trim().to_ascii_uppercase();
+                    if name == "TEAM" {
+                        team_line = Some(line_no);
+                    }
+                    section = Some(name);
+                }
+                Some(_) => problems.push(Problem::at(line_no, "section heading has no name")),
+                None => problems.push(Problem::at(
+                    line_no,
+                    "section heading must be written '/* NAME */'",
+                )),
+            }
+            continue;
+        }
+        match section.as_deref() {
+            None => problems.push(Problem::at(line_no, "text before the first '/* SECTION */' heading")),
+            Some("TEAM") => {
+                team_entries += 1;
+                let key = line
+                    .split_once(':')
+                    .map(|(key, _)| key.trim().to_ascii_lowercase())
+                    .unwrap_or_default();
+                if HUMANS_CONTACT_FIELDS.contains(&key.as_str())
+                    || has_email(line)
+                    || line.contains("://")
+                {
+                    team_contact = true;
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    match team_line {
+        None => problems.push(Problem::file("no '/* TEAM */' section")),
+        Some(line) if team_entries == 0 => {
+            problems.push(Problem::at(line, "'/* TEAM */' section is empty"))
+        }
+        Some(line) if !team_contact => problems.push(Problem::at(
+            line,
+            "'/* TEAM */' section gives no contact (e.g. 'Contact:', an email or a URL)",
+        )),
+        Some(_) => {}
+    }
+    problems
+}
+
+/// Parse a CI configuration, or the problem that stops it being read
+fn parse_yaml(text: &str) -> Result<Node, Problem> {
+    yaml::parse(text).map_err(|e| {
+        Problem::at(e.line, format!("invalid YAML at column {}: {}", e.column, e.message))
+    })
+}
+
+/// Whether an environment variable name suggests it holds a credential
+fn is_secret_name(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    ["PASSWORD", "PASSWD", "SECRET", "TOKEN", "API_KEY", "APIKEY", "PRIVATE_KEY", "ACCESS_KEY"]
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+/// Problems for secret-looking variables with literal values in the blocks
+/// whose key is `block` (`variables` in GitLab CI, `env` in GitHub Actions)
+fn plaintext_secrets(doc: &Node, block: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for (_, variables) in doc.pairs().into_iter().filter(|(key, _)| *key == block) {
+        for (name, value) in variables.entries() {
+            let Some(text) = value.as_str() else {
+                continue;
+            };
+            let literal = !text.is_empty() && !text.starts_with('$') && !text.contains("${{");
+            if is_secret_name(name) && literal {
+                problems.push(Problem::at(
+                    value.line,
+                    format!(
+                        "'{}' looks like a secret stored in plain text; use a masked CI/CD \
+                         variable or repository secret",
+                        name
+                    ),
+                ));
+            }
+        }
+    }
+    problems
+}
+
+/// Whether a CI configuration appears to run the tests: a job or stage
+/// named after tests, or a command such as `cargo test` or `just test`
+pub fn has_test_step(text: &str) -> bool {
+    let Ok(doc) = yaml::parse(text) else {
+        return false;
+    };
+    // Declaring a `test` stage does not mean anything runs in it
+    doc.entries()
+        .iter()
+        .any(|(key, node)| names_test(key) || (key != "stages" && runs_test(node)))
+}
+
+fn names_test(text: &str) -> bool {
+    words(text).any(|w| w.starts_with("test"))
+}
+
+/// Whether a step, stage or script below `node` names the tests
+fn runs_test(node: &Node) -> bool {
+    match &node.value {
+        Value::Seq(items) => items
+            .iter()
+            .any(|item| item.as_str().map_or(false, names_test) || runs_test(item)),
+        Value::Map(entries) => entries.iter().any(|(key, value)| match key.as_str() {
+            "stage" | "name" | "run" | "script" => {
+                value.scalars().into_iter().any(|(_, text)| names_test(text))
+            }
+            _ => runs_test(value),
+        }),
+        Value::Null | Value::Scalar(_) => false,
+    }
+}
+
+/// Top-level keys of `.gitlab-ci.yml` that are not jobs
+const GITLAB_RESERVED_KEYS: &[&str] = &[
+    "default",
+    "include",
+    "stages",
+    "variables",
+    "workflow",
+    "image",
+    "services",
+    "cache",
+    "before_script",
+    "after_script",
+];
+
+/// Validate the structure of `.gitlab-ci.yml`
+///
+/// Requires at least one job and flags the deprecated `only: variables` /
+/// `except: variables` form and secrets in plain-text `variables`.
+pub fn validate_gitlab_ci(text: &str) -> Vec<Problem> {
+    let doc = match parse_yaml(text) {
+        Ok(doc) => doc,
+        Err(problem) => return vec![problem],
+    };
+    let mut problems = Vec::new();
+
+    let is_job = |key: &str| !key.starts_with('.') && !GITLAB_RESERVED_KEYS.contains(&key);
+    if !doc.entries().iter().any(|(key, _)| is_job(key)) {
+        problems.push(Problem::file("defines no jobs"));
+    }
+
+    for (keyword, node) in doc.pairs() {
+        if !matches!(keyword, "only" | "except") {
+            continue;
+        }
+        if let Some(variables) = node.get("variables") {
+            problems.push(Problem::at(
+                variables.line,
+                format!("'{}: variables' is deprecated; use 'rules: - if:'", keyword),
+            ));
+        }
+    }
+
+    problems.extend(plaintext_secrets(&doc, "variables"));
+    problems.sort_by_key(|p| p.line);
+    problems
+}
+
+/// Validate the structure of a GitHub Actions workflow
+///
+/// Requires a `jobs` mapping with at least one job and flags secrets in
+/// plain-text `env`, the disabled `::set-env`/`::add-path` commands, and
+/// `pull_request_target` workflows that check out the pull request's code.
+pub fn validate_github_workflow(text: &str) -> Vec<Problem> {
+    let doc = match parse_yaml(text) {
+        Ok(doc) => doc,
+        Err(problem) => return vec![problem],
+    };
+    let mut problems = Vec::new();
+
+    match doc.get("jobs") {
+        None => problems.push(Problem::file("has no 'jobs' section")),
+        Some(jobs) if jobs.entries().is_empty() => {
+            problems.push(Problem::at(jobs.line, "'jobs' section is empty"))
+        }
+        Some(_) => {}
+    }
+
+    let scalars = doc.scalars();
+    let privileged = doc.pairs().iter().any(|(key, _)| *key == "pull_request_target")
+        || scalars.iter().any(|(_, text)| *text == "pull_request_target");
+    for &(line, text) in &scalars {
+        for command in ["::set-env", "::add-path"] {
+            if text.contains(command) {
+                problems.push(Problem::at(
+                    line,
+                    format!(
+                        "uses the disabled '{}' workflow command; write to $GITHUB_ENV or \
+                         $GITHUB_PATH",
+                        command
+                    ),
+                ));
+            }
+        }
+    }
+    for (key, value) in doc.pairs() {
+        let checks_out_head = key == "ref"
+            && value.as_str().map_or(false, |r| r.contains("github.event.pull_request.head"));
+        if privileged && checks_out_head {
+            problems.push(Problem::at(
+                value.line,
+                "checks out untrusted pull request code in a 'pull_request_target' workflow",
+            ));
+        }
+    }
+
+    problems.extend(plaintext_secrets(&doc, "env"));
+    problems.sort_by_key(|p| p.line);
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_problem_display() {
+        assert_eq!(Problem::at(3, "bad").to_string(), "line 3: bad");
+        assert_eq!(Problem::file("bad").to_string(), "bad");
+    }
+
+    #[test]
+    fn test_validate_length() {
+        assert_eq!(validate_length("", 1, 1), [Problem::file("is empty")]);
+        assert_eq!(validate_length(" \n\n", 1, 1), [Problem::file("is empty")]);
+        assert!(validate_length("# Security\n", 1, 1).is_empty());
+        let problems: Vec<String> =
+            validate_length("# Security\n", 64, 3).iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            problems,
+            [
+                "has 11 bytes, at least 64 required",
+                "has 1 non-blank lines, at least 3 required"
+            ]
+        );
+        assert!(validate_length("", 0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_find_placeholder() {
+        assert_eq!(
+            find_placeholder("# Demo\n\nLorem ipsum dolor sit amet.\n"),
+            Some(Problem::at(3, "contains placeholder text 'lorem ipsum'"))
+        );
+        assert!(find_placeholder("# Security Policy\n\nTODO\n").is_some());
+        assert!(find_placeholder("= Maintainers\n\n* TBD\n- Coming soon...\n").is_some());
+        assert!(find_placeholder("# Security\n\nTODO: describe reporting\n").is_some());
+        assert!(find_placeholder("# Changelog\n\n- TODO list export\n").is_none());
+        assert!(find_placeholder("# Changelog\n\n## 1.0\n- Initial release\n").is_none());
+        assert!(find_placeholder("# Title only\n").is_none());
+        assert!(find_placeholder("- Flag placeholder text (lorem ipsum)\n").is_none());
+        assert!(find_placeholder("").is_none());
+    }
+
+    #[test]
+    fn test_find_stub() {
+        assert_eq!(
+            find_stub("# Demo\n\n## Usage\n\nTODO(rhodibot): a short example.\n"),
+            Some(Problem::at(
+                5,
+                "has a placeholder from 'rhodibot fix' to replace (TODO(rhodibot))"
+            ))
+        );
+        assert!(find_stub("# Demo\n\nTODO: a short example.\n").is_none());
+        assert!(find_stub("- Starter files with `TODO(rhodibot)` placeholders\n").is_none());
+    }
+
+    #[test]
+    fn test_word_similarity() {
+        let md = "# Demo\n\nA *tool* for checking repositories.\n";
+        let adoc = "= Demo\n\nA _tool_ for checking repositories.\n";
+        assert_eq!(word_similarity(md, adoc), 1.0);
+        assert!(word_similarity(md, "= Other\n\nSomething else entirely.\n") < 0.5);
+        assert_eq!(word_similarity("", ""), 1.0);
+    }
+
+    #[test]
+    fn test_detect_license() {
+        let spdx = "SPDX-License-Identifier: MIT OR Apache-2.0\n\nFull text...\n";
+        assert_eq!(detect_license(spdx).as_deref(), Some("MIT OR Apache-2.0"));
+        let mit = "MIT License\n\nPermission is hereby granted, free of charge, to any person";
+        assert_eq!(detect_license(mit).as_deref(), Some("MIT"));
+        let lgpl = "GNU LESSER GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007\n\
+                    ... the GNU General Public License ...";
+        assert_eq!(detect_license(lgpl).as_deref(), Some("LGPL-3.0"));
+        let bsd = "Redistribution and use in source and binary forms, with or without";
+        assert_eq!(detect_license(bsd).as_deref(), Some("BSD-2-Clause"));
+        assert_eq!(detect_license("All rights reserved.\n"), None);
+    }
+
+    #[test]
+    fn test_validate_contributing() {
+        let good = "# Contributing\n\nRun `cargo test` before opening a pull request.\n";
+        assert!(validate_contributing(good).is_empty());
+        let patches = "Testing: `just check`.\nSend patches to the mailing list.\n";
+        assert!(validate_contributing(patches).is_empty());
+
+        let problems = validate_contributing("# Contributing\n\nBe nice. Get the latest version.\n");
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].message.contains("tests"));
+        assert!(problems[1].message.contains("submit"));
+    }
+
+    #[test]
+    fn test_validate_code_of_conduct() {
+        let covenant = "# Contributor Covenant Code of Conduct\n\n## Our Pledge\n";
+        assert!(validate_code_of_conduct(covenant).is_empty());
+        let custom = "Be kind.\n\n## Enforcement\n\nReport problems to conduct@example.org.\n";
+        assert!(validate_code_of_conduct(custom).is_empty());
+        let link = "Report incidents via https://example.org/conduct\n";
+        assert!(validate_code_of_conduct(link).is_empty());
+
+        assert_eq!(validate_code_of_conduct("Be excellent to each other.\n").len(), 1);
+        // A contact alone, with no mention of reporting or enforcement
+        assert_eq!(validate_code_of_conduct("Written by dev@example.org\n").len(), 1);
+        assert!(!has_email("user@localhost"));
+        assert!(has_email("(mailto:a@b.io)."));
+    }
+
+    #[test]
+    fn test_validate_ai_txt() {
+        let good = "# Policy\nUser-agent: *\nDisallow: /\nContact: mailto:ai@example.org\n";
+        assert!(validate_ai_txt(good).is_empty());
+        let training = "User-Agent: GPTBot\nAllow-training: conditional  # see LICENSE\n";
+        assert!(validate_ai_txt(training).is_empty());
+
+        assert_eq!(
+            validate_ai_txt("# AI Policy\n"),
+            vec![Problem::file("no 'User-agent' line")]
+        );
+        let problems = validate_ai_txt("Disallow: /\nUser-agent: *\nno colon here\nAllow:\n");
+        let rendered: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "line 1: 'disallow' appears before any 'User-agent' line",
+                "line 3: expected 'Field: value'",
+                "line 4: 'Allow' has no value",
+                "no training policy ('Allow'/'Disallow' or 'Allow-training'/'Disallow-training')",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_humans_txt() {
+        let good = "/* TEAM */\nLead: Ada\nContact: ada@example.org\n\n/* THANKS */\nEveryone\n";
+        assert!(validate_humans_txt(good).is_empty());
+        let twitter = "/* team */\nName: Ada\nTwitter: @ada\n";
+        assert!(validate_humans_txt(twitter).is_empty());
+
+        assert_eq!(
+            validate_humans_txt("# Humans\n"),
+            vec![
+                Problem::at(1, "text before the first '/* SECTION */' heading"),
+                Problem::file("no '/* TEAM */' section"),
+            ]
+        );
+        let problems = validate_humans_txt("/* SITE */\nLanguage: English\n/* TEAM\n/* TEAM */\nName: Ada\n");
+        let rendered: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "line 3: section heading must be written '/* NAME */'",
+                "line 4: '/* TEAM */' section gives no contact (e.g. 'Contact:', an email or a URL)",
+            ]
+        );
+        assert_eq!(
+            validate_humans_txt("/* TEAM */\n\n/* SITE */\nx\n"),
+            vec![Problem::at(1, "'/* TEAM */' section is empty")]
+        );
+    }
+
+    #[test]
+    fn test_has_test_step() {
+        assert!(has_test_step("unit:\n  script:\n    - cargo test\n"));
+        assert!(has_test_step("test:unit:\n  script: make\n"));
+        assert!(has_test_step("build:\n  stage: test\n"));
+        assert!(has_test_step("jobs:\n  ci:\n    steps:\n      - run: just test\n"));
+        assert!(!has_test_step("stages:\n  - test\nbuild:\n  script: make\n"));
+    }
+
+    #[test]
+    fn test_validate_gitlab_ci() {
+        let good = "stages: [test]\n.tmpl:\n  image: rust\ntest:\n  script: cargo test\n  only:\n    - main\n";
+        assert!(validate_gitlab_ci(good).is_empty());
+
+        assert_eq!(
+            validate_gitlab_ci("stages:\n  - build\n.hidden:\n  script: make\n"),
+            vec![Problem::file("defines no jobs")]
+        );
+        let bad = "variables:\n  DEPLOY_TOKEN: \"glpat-123\"\n  REGISTRY_PASSWORD: $CI_REGISTRY_PASSWORD\n\
+                   deploy:\n  script: ./deploy\n  only:\n    variables:\n      - $RELEASE\n";
+        let rendered: Vec<String> = validate_gitlab_ci(bad).iter().map(|p| p.to_string()).collect();
+        assert_eq!(rendered.len(), 2);
+        assert!(rendered[0].starts_with("line 2: 'DEPLOY_TOKEN' looks like a secret"));
+        assert_eq!(rendered[1], "line 8: 'only: variables' is deprecated; use 'rules: - if:'");
+
+        let unreadable = "test:\n  script: [make\n";
+        assert_eq!(
+            validate_gitlab_ci(unreadable),
+            vec![Problem::at(
+                2,
+                "invalid YAML at column 11: multi-line flow sequences are not supported"
+            )]
+        );
+        assert!(!has_test_step(unreadable));
+    }
+
+    #[test]
+    fn test_validate_github_workflow() {
+        let good = "on: push\njobs:\n  test:\n    runs-on: ubuntu-latest\n    env:\n      \
+                    TOKEN: ${{ secrets.TOKEN }}\n    steps:\n      - run: cargo test\n";
+        assert!(validate_github_workflow(good).is_empty());
+
+        assert_eq!(
+            validate_github_workflow("on: push\n"),
+            vec![Problem::file("has no 'jobs' section")]
+        );
+        let bad = "on: pull_request_target\njobs:\n  build:\n    env:\n      API_KEY: abc123\n    \
+                   steps:\n      - uses: actions/checkout@v4\n        with:\n          \
+                   ref: ${{ github.event.pull_request.head.sha }}\n      \
+                   - run: echo \"::set-env name=X::1\"\n";
+        let rendered: Vec<String> =
+            validate_github_workflow(bad).iter().map(|p| p.to_string()).collect();
+        assert_eq!(rendered.len(), 3, "{:?}", rendered);
+        assert!(rendered[0].starts_with("line 5: 'API_KEY' looks like a secret"));
+        assert!(rendered[1].starts_with("line 9: checks out untrusted pull request code"));
+        assert!(rendered[2].starts_with("line 10: uses the disabled '::set-env'"));
+    }
+}
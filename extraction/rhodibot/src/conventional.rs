@@ -0,0 +1,138 @@
+//! Conventional Commits and branch naming (the optional Version Control check)
+//!
+//! With `conventional_commits = true` under `[version_control]`, the subject
+//! lines of the most recent commits must follow Conventional Commits 1.0
+//! (`type(scope)!: description`) and the checked-out branch must be a
+//! default branch or be named `type/description`. Merge commits and the
+//! reverts git writes itself are left alone, as nobody types their subjects.
+
+/// Catalog ID of the check
+pub const CHECK_ID: &str = "RSR-VCS-001";
+
+/// Commit types accepted before the colon (those of the Angular convention)
+pub const TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
+/// Branches that need no type prefix
+const DEFAULT_BRANCHES: &[&str] = &["main", "master", "develop", "trunk"];
+
+/// Branch prefixes accepted besides the commit types, including those of
+/// common bots
+const BRANCH_PREFIXES: &[&str] = &[
+    "feature", "bugfix", "hotfix", "release", "dependabot", "renovate", "rhodibot",
+];
+
+/// Why a commit subject does not follow Conventional Commits, or `None` if
+/// it does (or is a subject git wrote itself)
+pub fn subject_problem(subject: &str) -> Option<String> {
+    if subject.starts_with("Merge ") || subject.starts_with("Revert \"") {
+        return None;
+    }
+    let problem = |reason: &str| Some(format!("'{}' {}", subject, reason));
+    let Some((prefix, description)) = subject.split_once(':') else {
+        return problem("has no 'type: description' prefix");
+    };
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+    let kind = match prefix.split_once('(') {
+        Some((kind, scope)) => {
+            let valid = scope.strip_suffix(')').map_or(false, |scope| {
+                !scope.is_empty() && !scope.contains(['(', ')', ' '])
+            });
+            if !valid {
+                return problem("has a malformed scope");
+            }
+            kind
+        }
+        None => prefix,
+    };
+    if !TYPES.contains(&kind) {
+        return problem(&format!("has unknown type '{}'", kind));
+    }
+    if !description.starts_with(' ') || description.trim().is_empty() {
+        return problem("needs a description after ': '");
+    }
+    None
+}
+
+/// Why a branch name breaks the naming convention, or `None` if it follows it
+pub fn branch_problem(branch: &str) -> Option<String> {
+    if DEFAULT_BRANCHES.contains(&branch) {
+        return None;
+    }
+    match branch.split_once('/') {
+        Some((prefix, rest))
+            if !rest.is_empty()
+                && (TYPES.contains(&prefix) || BRANCH_PREFIXES.contains(&prefix)) =>
+        {
+            None
+        }
+        _ => Some(format!(
+            "branch '{}' is not named type/description (e.g. feat/{})",
+            branch, branch
+        )),
+    }
+}
+
+/// Commit subjects listed before the rest are only counted
+const MAX_LISTED: usize = 5;
+
+/// Problems with the commit `messages` (newest first) and `branch`
+pub fn problems(messages: &[String], branch: Option<&str>) -> Vec<String> {
+    let mut problems: Vec<String> = messages
+        .iter()
+        .filter_map(|message| subject_problem(message.lines().next().unwrap_or_default().trim()))
+        .collect();
+    if problems.len() > MAX_LISTED {
+        let more = problems.len() - MAX_LISTED;
+        problems.truncate(MAX_LISTED);
+        problems.push(format!("{} more commits", more));
+    }
+    problems.extend(branch.and_then(branch_problem));
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_problem() {
+        for subject in [
+            "feat: add drift detection",
+            "fix(parser): accept tabs",
+            "refactor!: drop the old API",
+            "feat(api)!: rename fields",
+            "Merge branch 'main' into feat/x",
+            "Revert \"feat: add drift detection\"",
+        ] {
+            assert_eq!(subject_problem(subject), None, "{}", subject);
+        }
+        assert_eq!(
+            subject_problem("Add drift detection").unwrap(),
+            "'Add drift detection' has no 'type: description' prefix"
+        );
+        assert!(subject_problem("feature: x").unwrap().contains("unknown type 'feature'"));
+        assert!(subject_problem("fix(): x").unwrap().contains("malformed scope"));
+        assert!(subject_problem("fix:x").unwrap().contains("needs a description"));
+        assert!(subject_problem("fix: ").unwrap().contains("needs a description"));
+    }
+
+    #[test]
+    fn test_branch_problem() {
+        for branch in ["main", "feat/drift", "fix/issue-12", "release/1.0", "dependabot/cargo/x"] {
+            assert_eq!(branch_problem(branch), None, "{}", branch);
+        }
+        assert!(branch_problem("my-branch").is_some());
+        assert!(branch_problem("wip/thing").is_some());
+        assert!(branch_problem("feat/").is_some());
+
+        let messages = ["fix: a\n\nbody".to_string(), "oops\n".to_string()];
+        assert_eq!(problems(&messages, None), ["'oops' has no 'type: description' prefix"]);
+        assert_eq!(problems(&messages, Some("topic")).len(), 2);
+        let messages = vec!["wip".to_string(); 8];
+        let listed = problems(&messages, None);
+        assert_eq!(listed.len(), 6);
+        assert_eq!(listed[5], "3 more commits");
+    }
+}
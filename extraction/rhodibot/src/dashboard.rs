@@ -0,0 +1,323 @@
+//! Organization dashboard rendered from batch results
+//!
+//! `rhodibot dashboard repos.txt -o site/` writes a static HTML site: an
+//! `index.html` with one row per repository, sortable by clicking a column
+//! header, and one drill-down page per repository. The pages are
+//! self-contained (inline CSS and a few lines of JavaScript) so they can be
+//! published from any static host or CI artifact.
+
+use crate::batch::{BatchEntry, BatchReport};
+use crate::output::write_atomic;
+use crate::{
+    conformity, exit_codes, format_timestamp, CheckStatus, WarningLevel, STANDARD_VERSION,
+    VERSION,
+};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Default output directory for `rhodibot dashboard`
+pub const DEFAULT_DASHBOARD_DIR: &str = "rhodibot-dashboard";
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#222}\
+table{border-collapse:collapse;width:100%}\
+th,td{padding:.4rem .6rem;border-bottom:1px solid #ddd;text-align:left}\
+th[data-sort]{cursor:pointer;user-select:none}\
+th[data-sort]:after{content:' \\2195';color:#999}\
+.pass{color:#1a7f37}.fail{color:#cf222e}.error{color:#9a6700}\
+.num{text-align:right;font-variant-numeric:tabular-nums}";
+
+/// Sort the index table by the clicked column; numeric when `data-value` is set
+const SORT_SCRIPT: &str = "document.querySelectorAll('th[data-sort]').forEach(function(th,col){\
+th.addEventListener('click',function(){\
+var body=th.closest('table').tBodies[0],asc=th.dataset.dir!=='asc';th.dataset.dir=asc?'asc':'desc';\
+var key=function(r){var c=r.cells[col];return c.dataset.value!==undefined?parseFloat(c.dataset.value):c.textContent.toLowerCase();};\
+Array.from(body.rows).sort(function(a,b){var x=key(a),y=key(b);return (x<y?-1:x>y?1:0)*(asc?1:-1);})\
+.forEach(function(r){body.appendChild(r);});});});";
+
+/// Escape text for HTML element content and attribute values
+fn html_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// File name of the drill-down page for the `index`th repository
+pub fn repo_page_name(index: usize, source: &str) -> String {
+    let slug: String = source
+        .rsplit(|c| c == '/' || c == '\\')
+        .find(|part| !part.is_empty())
+        .unwrap_or("repo")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    format!("repo-{}-{}.html", index + 1, slug.trim_matches('-'))
+}
+
+fn page_header(out: &mut String, title: &str) {
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", html_escape(title)));
+    out.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+    out.push_str(&format!("<h1>{}</h1>\n", html_escape(title)));
+}
+
+/// Append the footer; reproducible pages leave out the generation time
+fn page_footer(out: &mut String, reproducible: bool) {
+    out.push_str(&format!(
+        "<p><small>Generated by rhodibot {} against RSR {}",
+        VERSION, STANDARD_VERSION
+    ));
+    if !reproducible {
+        out.push_str(&format!(" at {}", format_timestamp(crate::env::verification_time())));
+    }
+    out.push_str("</small></p>\n");
+}
+
+fn status_cell(entry: &BatchEntry) -> (&'static str, &'static str) {
+    match (entry.outcome.is_ok(), entry.exit_code) {
+        (true, exit_codes::SUCCESS) => ("pass", "Pass"),
+        (true, exit_codes::SECURITY_WARNING) => ("fail", "Security"),
+        (true, _) => ("fail", "Fail"),
+        (false, _) => ("error", "Error"),
+    }
+}
+
+/// Render the dashboard index page
+pub fn render_index(batch: &BatchReport) -> String {
+    let mut out = String::new();
+    page_header(&mut out, "RSR Compliance Dashboard");
+    out.push_str(&format!(
+        "<p>{} repositories, {} passing, {} could not be verified.</p>\n",
+        batch.entries.len(),
+        batch.passed_count(),
+        batch.error_count()
+    ));
+    out.push_str("<table>\n<thead><tr>");
+    out.push_str("<th data-sort>Repository</th><th data-sort>Status</th>");
+    out.push_str("<th data-sort class=\"num\">Compliance</th><th data-sort>Level</th>");
+    out.push_str("<th data-sort class=\"num\">Warnings</th>");
+    out.push_str("</tr></thead>\n<tbody>\n");
+
+    for (i, entry) in batch.entries.iter().enumerate() {
+        let (class, status) = status_cell(entry);
+        out.push_str(&format!(
+            "<tr><td><a href=\"{}\">{}</a></td><td class=\"{}\">{}</td>",
+            html_escape(&repo_page_name(i, &entry.source)),
+            html_escape(&entry.source),
+            class,
+            status
+        ));
+        match entry.outcome {
+            Ok(ref report) => {
+                // Levels sort by rank; "none" sorts below Bronze
+                let (rank, level) = match report.highest_level() {
+                    Some(level) => (level as i32 + 1, level.display_name()),
+                    None => (0, "none"),
+                };
+                out.push_str(&format!(
+                    "<td class=\"num\" data-value=\"{:.1}\">{:.1}% ({}/{})</td>",
+                    report.percentage(),
+                    report.percentage(),
+                    report.passed_count(),
+                    report.total_count()
+                ));
+                out.push_str(&format!("<td data-value=\"{}\">{}</td>", rank, level));
+                out.push_str(&format!(
+                    "<td class=\"num\" data-value=\"{0}\">{0}</td>",
+                    report.warnings.len()
+                ));
+            }
+            Err(_) => {
+                out.push_str("<td class=\"num\" data-value=\"-1\">-</td>");
+                out.push_str("<td data-value=\"-1\">-</td>");
+                out.push_str("<td class=\"num\" data-value=\"-1\">-</td>");
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+
+    out.push_str("</tbody>\n</table>\n");
+    let reproducible = batch
+        .entries
+        .iter()
+        .any(|entry| entry.outcome.as_ref().map_or(false, |report| report.reproducible));
+    page_footer(&mut out, reproducible);
+    out.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", SORT_SCRIPT));
+    out
+}
+
+/// Render the drill-down page for one repository
+pub fn render_repo_page(entry: &BatchEntry) -> String {
+    let mut out = String::new();
+    page_header(&mut out, &entry.source);
+    out.push_str("<p><a href=\"index.html\">&larr; All repositories</a></p>\n");
+
+    let report = match entry.outcome {
+        Ok(ref report) => report,
+        Err(ref message) => {
+            out.push_str(&format!(
+                "<p class=\"error\">Could not be verified: {}</p>\n",
+                html_escape(message)
+            ));
+            page_footer(&mut out, false);
+            out.push_str("</body>\n</html>\n");
+            return out;
+        }
+    };
+
+    let (class, status) = status_cell(entry);
+    out.push_str(&format!(
+        "<p>Status: <span class=\"{}\">{}</span> &middot; {}/{} checks passed ({:.1}%)",
+        class,
+        status,
+        report.passed_count(),
+        report.total_count(),
+        report.percentage()
+    ));
+    if let Some(timestamp) = report.timestamp() {
+        out.push_str(&format!(" &middot; verified {}", timestamp));
+    }
+    out.push_str("</p>\n");
+    if !report.metadata.is_empty() {
+        let entries: Vec<String> = report
+            .metadata
+            .entries()
+            .into_iter()
+            .map(|(key, value)| {
+                let value = if value.starts_with("https://") || value.starts_with("http://") {
+                    format!("<a href=\"{0}\">{0}</a>", html_escape(value))
+                } else {
+                    format!("<code>{}</code>", html_escape(value))
+                };
+                format!("{}: {}", html_escape(key), value)
+            })
+            .collect();
+        out.push_str(&format!("<p>{}</p>\n", entries.join(" &middot; ")));
+    }
+
+    out.push_str("<table>\n<thead><tr><th>ID</th><th>Category</th><th>Requirement</th>");
+    out.push_str("<th>Level</th><th>Result</th></tr></thead>\n<tbody>\n");
+    for check in &report.checks {
+        let class = match check.status {
+            _ if check.waived => "error",
+            CheckStatus::Pass => "pass",
+            CheckStatus::NotApplicable => "",
+            CheckStatus::Fail | CheckStatus::Skipped | CheckStatus::Error => "fail",
+        };
+        let result = conformity::status(check);
+        out.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td title=\"{}\">{}</td><td>{}</td><td class=\"{}\">{}</td></tr>\n",
+            html_escape(&check.id),
+            html_escape(&check.category),
+            html_escape(check.description.as_deref().unwrap_or("")),
+            html_escape(&check.item),
+            check.required_for.display_name(),
+            class,
+            result
+        ));
+    }
+    out.push_str("</tbody>\n</table>\n");
+
+    if !report.warnings.is_empty() {
+        out.push_str("<h2>Security warnings</h2>\n<ul>\n");
+        for warning in &report.warnings {
+            let level = match warning.level {
+                WarningLevel::Info => "Info",
+                WarningLevel::Warning => "Warning",
+                WarningLevel::Critical => "Critical",
+            };
+            out.push_str(&format!(
+                "<li><strong>{}</strong>: {}</li>\n",
+                level,
+                html_escape(&warning.message)
+            ));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    page_footer(&mut out, report.reproducible);
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Write the index and every repository page into `dir`
+///
+/// Returns the path of the index page.
+pub fn write_dashboard(batch: &BatchReport, dir: &Path) -> io::Result<PathBuf> {
+    for (i, entry) in batch.entries.iter().enumerate() {
+        write_atomic(&dir.join(repo_page_name(i, &entry.source)), &render_repo_page(entry))?;
+    }
+    let index = dir.join("index.html");
+    write_atomic(&index, &render_index(batch))?;
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComplianceLevel, ComplianceReport};
+
+    fn sample_batch() -> BatchReport {
+        let mut good = ComplianceReport::new(PathBuf::from("/srv/good"));
+        good.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        let mut bad = ComplianceReport::new(PathBuf::from("/srv/bad"));
+        bad.add_check("Documentation", "<README>", false, ComplianceLevel::Bronze);
+
+        let mut batch = BatchReport::new();
+        batch.add_report("/srv/good", good, exit_codes::SUCCESS);
+        batch.add_report("/srv/bad", bad, exit_codes::COMPLIANCE_FAILED);
+        batch.add_error("missing", "Path does not exist", exit_codes::INVALID_PATH);
+        batch
+    }
+
+    #[test]
+    fn test_repo_page_name() {
+        assert_eq!(repo_page_name(0, "/srv/git/Web App/"), "repo-1-web-app.html");
+        assert_eq!(repo_page_name(2, "api"), "repo-3-api.html");
+    }
+
+    #[test]
+    fn test_render_index() {
+        let html = render_index(&sample_batch());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<tr><td>").count(), 3);
+        assert!(html.contains("<a href=\"repo-2-bad.html\">/srv/bad</a>"));
+        assert!(html.contains("data-value=\"100.0\">100.0% (1/1)"));
+        assert!(html.contains("<td class=\"error\">Error</td>"));
+        assert!(html.contains("<th data-sort"));
+    }
+
+    #[test]
+    fn test_render_repo_page_escapes_html() {
+        let batch = sample_batch();
+        let html = render_repo_page(&batch.entries[1]);
+        assert!(html.contains("&lt;README&gt;"));
+        assert!(!html.contains("<README>"));
+        assert!(html.contains("<td class=\"fail\">Fail</td>"));
+        assert!(render_repo_page(&batch.entries[2]).contains("Path does not exist"));
+    }
+
+    #[test]
+    fn test_render_repo_page_metadata() {
+        let mut batch = sample_batch();
+        assert!(!render_repo_page(&batch.entries[0]).contains("commit:"));
+
+        if let Ok(ref mut report) = batch.entries[0].outcome {
+            report.metadata.set("commit", "abc123");
+            report.metadata.set("ci_run_url", "https://ci.example/runs/7?a=1&b=2");
+        }
+        let html = render_repo_page(&batch.entries[0]);
+        assert!(html.contains(
+            "<p>commit: <code>abc123</code> &middot; ci_run_url: \
+             <a href=\"https://ci.example/runs/7?a=1&amp;b=2\">"
+        ));
+    }
+}
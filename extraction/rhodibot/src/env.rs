@@ -0,0 +1,215 @@
+//! Environment-variable configuration
+//!
+//! Container-based CI often makes it awkward to thread flags through to the
+//! command that runs rhodibot, so the common settings can also come from
+//! `RHODIBOT_*` variables. They form a layer between the configuration file
+//! and the command line: a variable overrides `.rhodibot.toml`, and a flag
+//! overrides the variable. Empty variables are treated as unset.
+//!
+//! | Variable                     | Equivalent                  |
+//! |------------------------------|-----------------------------|
+//! | `RHODIBOT_FORMAT`            | `--format`                  |
+//! | `RHODIBOT_LEVEL`             | `--level`                   |
+//! | `RHODIBOT_CONFIG`            | `--config`                  |
+//! | `RHODIBOT_FAIL_ON_WARNING`   | `--fail-on-warning`         |
+//! | `RHODIBOT_STANDARD_VERSION`  | `--standard-version`        |
+//! | `RHODIBOT_LOG_LEVEL`         | `--log-level`               |
+//! | `RHODIBOT_CI`                | `--ci`                      |
+//! | `NO_COLOR`                   | disables ANSI colors        |
+//! | `SOURCE_DATE_EPOCH`          | fixes `verified_at`         |
+
+use crate::logging::LogLevel;
+use crate::{ComplianceLevel, StandardVersion};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+pub const FORMAT: &str = "RHODIBOT_FORMAT";
+pub const LEVEL: &str = "RHODIBOT_LEVEL";
+pub const CONFIG: &str = "RHODIBOT_CONFIG";
+pub const FAIL_ON_WARNING: &str = "RHODIBOT_FAIL_ON_WARNING";
+pub const STANDARD_VERSION: &str = "RHODIBOT_STANDARD_VERSION";
+pub const LOG_LEVEL: &str = "RHODIBOT_LOG_LEVEL";
+pub const CI: &str = "RHODIBOT_CI";
+/// See <https://no-color.org>
+pub const NO_COLOR: &str = "NO_COLOR";
+/// See <https://reproducible-builds.org/specs/source-date-epoch/>
+pub const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+/// Settings taken from the environment
+///
+/// `format` and `ci` are kept as strings; the binary validates them with
+/// the same parsers as the corresponding flags.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnvConfig {
+    pub format: Option<String>,
+    /// Compliance level required for success, overriding the config file
+    pub level: Option<ComplianceLevel>,
+    pub config: Option<PathBuf>,
+    pub fail_on_warning: Option<bool>,
+    pub standard_version: Option<StandardVersion>,
+    pub log_level: Option<LogLevel>,
+    pub ci: Option<String>,
+    pub no_color: bool,
+    /// Time to record as `verified_at` instead of the current time
+    pub source_date_epoch: Option<SystemTime>,
+}
+
+/// Parse a boolean variable (`1`/`true`/`yes`/`on` or `0`/`false`/`no`/`off`)
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse a `SOURCE_DATE_EPOCH` value: whole seconds since 1970-01-01 UTC
+fn parse_epoch(value: &str) -> Result<SystemTime, String> {
+    value
+        .parse::<u64>()
+        .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+        .map_err(|_| format!("invalid timestamp '{}' (expected seconds since 1970)", value))
+}
+
+impl EnvConfig {
+    /// Read the settings from the process environment
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Read the settings through `lookup`, which returns a variable's value
+    ///
+    /// Errors name the offending variable.
+    pub fn from_lookup<F>(lookup: F) -> Result<Self, String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let var = |name: &str| lookup(name).filter(|value| !value.is_empty());
+        let invalid = |name: &str, message: String| format!("{}: {}", name, message);
+
+        let level = var(LEVEL)
+            .map(|value| value.parse::<ComplianceLevel>())
+            .transpose()
+            .map_err(|e| invalid(LEVEL, e))?;
+        let fail_on_warning = var(FAIL_ON_WARNING)
+            .map(|value| {
+                parse_bool(&value).ok_or_else(|| {
+                    format!("invalid boolean '{}' (expected true or false)", value)
+                })
+            })
+            .transpose()
+            .map_err(|e| invalid(FAIL_ON_WARNING, e))?;
+        let standard_version = var(STANDARD_VERSION)
+            .map(|value| value.parse::<StandardVersion>())
+            .transpose()
+            .map_err(|e| invalid(STANDARD_VERSION, e))?;
+        let log_level = var(LOG_LEVEL)
+            .map(|value| {
+                LogLevel::parse(&value).ok_or_else(|| {
+                    format!(
+                        "unknown log level '{}' (expected error, warn, info or debug)",
+                        value
+                    )
+                })
+            })
+            .transpose()
+            .map_err(|e| invalid(LOG_LEVEL, e))?;
+        let source_date_epoch = var(SOURCE_DATE_EPOCH)
+            .map(|value| parse_epoch(&value))
+            .transpose()
+            .map_err(|e| invalid(SOURCE_DATE_EPOCH, e))?;
+
+        Ok(Self {
+            format: var(FORMAT),
+            level,
+            config: var(CONFIG).map(PathBuf::from),
+            fail_on_warning,
+            standard_version,
+            log_level,
+            ci: var(CI),
+            no_color: var(NO_COLOR).is_some(),
+            source_date_epoch,
+        })
+    }
+}
+
+/// Whether `NO_COLOR` asks for output without ANSI colors
+pub fn no_color() -> bool {
+    std::env::var_os(NO_COLOR).map_or(false, |value| !value.is_empty())
+}
+
+/// The time set by `SOURCE_DATE_EPOCH`, if any
+pub fn source_date_epoch() -> Result<Option<SystemTime>, String> {
+    std::env::var(SOURCE_DATE_EPOCH)
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(|value| parse_epoch(&value))
+        .transpose()
+        .map_err(|e| format!("{}: {}", SOURCE_DATE_EPOCH, e))
+}
+
+/// Time a verification is recorded at
+///
+/// `SOURCE_DATE_EPOCH` when set, so release builds that embed a conformity
+/// document stay reproducible; the current time otherwise. An invalid value
+/// is ignored here, the binaries reject it up front.
+pub fn verification_time() -> SystemTime {
+    source_date_epoch()
+        .ok()
+        .flatten()
+        .unwrap_or_else(SystemTime::now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn test_from_lookup() {
+        let env = EnvConfig::from_lookup(lookup(&[
+            (FORMAT, "json"),
+            (LEVEL, "Silver"),
+            (CONFIG, "ci/rhodibot.toml"),
+            (FAIL_ON_WARNING, "yes"),
+            (STANDARD_VERSION, "1"),
+            (LOG_LEVEL, "debug"),
+            (CI, "gitlab"),
+            (NO_COLOR, "1"),
+            (SOURCE_DATE_EPOCH, "1768435200"),
+        ]))
+        .unwrap();
+        assert_eq!(env.format.as_deref(), Some("json"));
+        assert_eq!(env.level, Some(ComplianceLevel::Silver));
+        assert_eq!(env.config, Some(PathBuf::from("ci/rhodibot.toml")));
+        assert_eq!(env.fail_on_warning, Some(true));
+        assert_eq!(env.standard_version, Some(StandardVersion::V1_0));
+        assert_eq!(env.log_level, Some(LogLevel::Debug));
+        assert_eq!(env.ci.as_deref(), Some("gitlab"));
+        assert!(env.no_color);
+        let epoch = SystemTime::UNIX_EPOCH + Duration::from_secs(1_768_435_200);
+        assert_eq!(env.source_date_epoch, Some(epoch));
+    }
+
+    #[test]
+    fn test_empty_and_invalid_values() {
+        let env = EnvConfig::from_lookup(lookup(&[(FORMAT, ""), (NO_COLOR, "")])).unwrap();
+        assert_eq!(env, EnvConfig::default());
+
+        let err = EnvConfig::from_lookup(lookup(&[(LEVEL, "mithril")])).unwrap_err();
+        assert!(err.starts_with("RHODIBOT_LEVEL: unknown compliance level 'mithril'"));
+        let err = EnvConfig::from_lookup(lookup(&[(FAIL_ON_WARNING, "maybe")])).unwrap_err();
+        assert!(err.starts_with("RHODIBOT_FAIL_ON_WARNING: invalid boolean"));
+        let off = EnvConfig::from_lookup(lookup(&[(FAIL_ON_WARNING, "0")])).unwrap();
+        assert_eq!(off.fail_on_warning, Some(false));
+        let err = EnvConfig::from_lookup(lookup(&[(SOURCE_DATE_EPOCH, "-1")])).unwrap_err();
+        assert!(err.starts_with("SOURCE_DATE_EPOCH: invalid timestamp '-1'"));
+    }
+}
@@ -0,0 +1,114 @@
+//! C-compatible interface for embedding the verifier (`ffi` feature)
+//!
+//! Lets editors, IDE plugins and tools written in other languages verify a
+//! repository through the C ABI instead of spawning the binary. The report is
+//! returned as the same JSON document `rhodibot check --format json` prints:
+//!
+//! ```c
+//! char *report = rhodibot_verify("/path/to/repo");
+//! if (report != NULL) {
+//!     puts(report);
+//!     rhodibot_free_string(report);
+//! }
+//! ```
+//!
+//! Configuration and suppressions are discovered in the repository as the
+//! command-line tool does. If the path is not a directory or its
+//! configuration is invalid, the result is `{"error": "..."}` instead of a
+//! report. Every non-null string returned here must be released with
+//! [`rhodibot_free_string`] and not with the caller's `free`.
+
+use crate::config::Config;
+use crate::output::render_json_report;
+use crate::suppressions::Suppressions;
+use crate::{json, verify_repository_with_config};
+use std::ffi::{c_char, CStr, CString};
+use std::path::Path;
+
+/// Verify the repository at `path` and return its JSON report
+///
+/// Returns null if `path` is null or not valid UTF-8.
+///
+/// # Safety
+///
+/// `path` must be null or point to a NUL-terminated string that stays valid
+/// for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn rhodibot_verify(path: *const c_char) -> *mut c_char {
+    if path.is_null() {
+        return std::ptr::null_mut();
+    }
+    // SAFETY: the caller guarantees `path` is a valid NUL-terminated string.
+    let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+        return std::ptr::null_mut();
+    };
+    let json = verify_to_json(Path::new(path))
+        .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}\n", json::escape(&e)));
+    // The JSON escapes control characters, so it contains no interior NUL.
+    CString::new(json).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Release a string returned by [`rhodibot_verify`]
+///
+/// # Safety
+///
+/// `report` must be null or a pointer returned by [`rhodibot_verify`] that
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rhodibot_free_string(report: *mut c_char) {
+    if !report.is_null() {
+        // SAFETY: the pointer came from `CString::into_raw` and is freed once.
+        drop(unsafe { CString::from_raw(report) });
+    }
+}
+
+fn verify_to_json(repo_path: &Path) -> Result<String, String> {
+    if !repo_path.is_dir() {
+        return Err(format!("Path is not a directory: {}", repo_path.display()));
+    }
+    let config = Config::discover(repo_path)
+        .map_err(|e| format!("Invalid configuration: {}", e))?;
+    let suppressions = Suppressions::discover(repo_path)
+        .map_err(|e| format!("Invalid suppressions file: {}", e))?;
+    let mut report = verify_repository_with_config(repo_path, &config);
+    report.apply_suppressions(&suppressions);
+    Ok(render_json_report(&report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn verify(path: &str) -> String {
+        let path = CString::new(path).unwrap();
+        unsafe {
+            let report = rhodibot_verify(path.as_ptr());
+            assert!(!report.is_null());
+            let json = CStr::from_ptr(report).to_str().unwrap().to_string();
+            rhodibot_free_string(report);
+            json
+        }
+    }
+
+    #[test]
+    fn test_verify_through_c_abi() {
+        let dir = std::env::temp_dir().join("rhodibot_ffi_verify");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("README.md"), "# Demo\n").unwrap();
+
+        let report = verify(dir.to_str().unwrap());
+        assert!(report.contains("\"tool\": \"rhodibot\""));
+        assert!(report.contains("\"id\": \"RSR-DOC-001\""));
+
+        let missing = verify(dir.join("missing").to_str().unwrap());
+        assert!(missing.starts_with("{\"error\": \"Path is not a directory: "));
+
+        unsafe {
+            assert!(rhodibot_verify(std::ptr::null()).is_null());
+            rhodibot_free_string(std::ptr::null_mut());
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,249 @@
+//! Starter files for failing checks (`fix`)
+//!
+//! `rhodibot fix` writes a starter version of each missing file whose check
+//! failed, so a repository can reach Bronze with a review of placeholders
+//! rather than from a blank page. Only files with a sensible default are
+//! written: a license, a Nix flake or source and test directories need a
+//! decision from the maintainers, so their checks are left to them. Existing
+//! files are never overwritten.
+//!
+//! The placeholders are marked `TODO(rhodibot)`
+//! ([`crate::content::STUB_MARKER`]), and the checks keep failing until
+//! every one has been replaced, so a starter file cannot pass for a
+//! finished one. With `--create-pr` the files go on a branch and into a pull
+//! request instead (see [`crate::bot::pull_request`]).
+
+use crate::bot::CIPlatform;
+use crate::output::write_atomic;
+use crate::site;
+use crate::{format_timestamp, ComplianceReport};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How long the generated security.txt stays valid (RFC 9116 advises less
+/// than a year)
+const SECURITY_TXT_VALIDITY: Duration = Duration::from_secs(364 * 24 * 60 * 60);
+
+/// A file to create for a failing check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// Check the file makes pass
+    pub id: String,
+    /// Path relative to the repository root
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// Files that would make the failing checks of `report` pass once their
+/// placeholders are replaced
+///
+/// Checks that were skipped because a prerequisite failed count too, so a
+/// missing `.well-known/` gets its files in one run. Waived checks and files
+/// that already exist (e.g. with content a later check rejects) are left out.
+/// `.well-known/` files go into the report's web root.
+pub fn plan(report: &ComplianceReport) -> Vec<Fix> {
+    let root = &report.repository_path;
+    let project = project_name(root);
+    let mut fixes: Vec<Fix> = Vec::new();
+    for check in report.checks.iter().filter(|c| !c.passed() && c.is_applicable()) {
+        let Some((path, contents)) = template(&check.id, &project, root) else {
+            continue;
+        };
+        let path = if path.starts_with(".well-known/") {
+            PathBuf::from(site::join(&report.web_root, path))
+        } else {
+            PathBuf::from(path)
+        };
+        if root.join(&path).exists() || fixes.iter().any(|f| f.path == path) {
+            continue;
+        }
+        fixes.push(Fix {
+            id: check.id.clone(),
+            path,
+            contents,
+        });
+    }
+    fixes
+}
+
+/// Write `fixes` into the repository at `repo_path`, returning the paths
+/// written
+///
+/// A file that appeared since [`plan`] ran is left alone.
+pub fn apply(repo_path: &Path, fixes: &[Fix]) -> Result<Vec<PathBuf>, String> {
+    let mut written = Vec::new();
+    for fix in fixes {
+        let path = repo_path.join(&fix.path);
+        if path.exists() {
+            continue;
+        }
+        write_atomic(&path, &fix.contents)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+        written.push(fix.path.clone());
+    }
+    Ok(written)
+}
+
+/// Name of the project: the repository directory's name
+fn project_name(root: &Path) -> String {
+    root.canonicalize()
+        .unwrap_or_else(|_| root.to_path_buf())
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "this project".to_string())
+}
+
+/// Canonical path and starter contents for the check `id`
+fn template(id: &str, project: &str, root: &Path) -> Option<(&'static str, String)> {
+    let file = match id {
+        "RSR-DOC-001" => (
+            "README.md",
+            format!(
+                "# {project}\n\n\
+                 TODO(rhodibot): what {project} does and who it is for.\n\n\
+                 ## Installation\n\nTODO(rhodibot)\n\n\
+                 ## Usage\n\nTODO(rhodibot): a short example.\n\n\
+                 ## License\n\nSee [LICENSE.txt](LICENSE.txt).\n",
+                project = project
+            ),
+        ),
+        "RSR-DOC-003" => (
+            "SECURITY.md",
+            "# Security Policy\n\n\
+             ## Supported Versions\n\n\
+             TODO(rhodibot): the versions that receive security fixes.\n\n\
+             ## Reporting a Vulnerability\n\n\
+             Please do not open a public issue. Report vulnerabilities privately to\n\
+             TODO(rhodibot): a security contact address, or use the repository's private\n\
+             vulnerability reporting. We aim to acknowledge reports within 7 days.\n"
+                .to_string(),
+        ),
+        "RSR-DOC-004" => (
+            "CONTRIBUTING.md",
+            format!(
+                "# Contributing to {project}\n\n\
+                 Thank you for helping! Please open an issue to discuss larger changes\n\
+                 before starting on them.\n\n\
+                 ## Running the tests\n\nTODO(rhodibot): the command that runs the test suite.\n\n\
+                 ## Submitting changes\n\n\
+                 1. Fork the repository and create a branch for your change.\n\
+                 2. Add tests for new behaviour and make sure the suite passes.\n\
+                 3. Open a pull request describing what changed and why.\n\n\
+                 A maintainer reviews every pull request before it is merged.\n",
+                project = project
+            ),
+        ),
+        "RSR-DOC-005" => (
+            "CODE_OF_CONDUCT.md",
+            "# Code of Conduct\n\n\
+             This project follows the Contributor Covenant, version 2.1:\n\
+             https://www.contributor-covenant.org/version/2/1/code_of_conduct/\n\n\
+             ## Enforcement\n\n\
+             Report unacceptable behaviour to TODO(rhodibot): an enforcement contact.\n\
+             All reports are reviewed promptly and handled in confidence.\n"
+                .to_string(),
+        ),
+        "RSR-DOC-006" => (
+            "MAINTAINERS.md",
+            "# Maintainers\n\n\
+             | Name | Contact | Areas |\n\
+             |------|---------|-------|\n\
+             | TODO(rhodibot) | TODO(rhodibot) | All   |\n"
+                .to_string(),
+        ),
+        "RSR-DOC-007" => (
+            "CHANGELOG.md",
+            "# Changelog\n\n\
+             All notable changes to this project are documented in this file.\n\n\
+             The format is based on \
+             [Keep a Changelog](https://keepachangelog.com/en/1.1.0/).\n\n\
+             ## [Unreleased]\n"
+                .to_string(),
+        ),
+        "RSR-WKN-002" => (
+            ".well-known/security.txt",
+            format!(
+                "# TODO(rhodibot): replace the contact with a real address before publishing\n\
+                 Contact: mailto:security@example.invalid\n\
+                 Expires: {}\n\
+                 Policy: SECURITY.md\n",
+                format_timestamp(SystemTime::now() + SECURITY_TXT_VALIDITY)
+            ),
+        ),
+        "RSR-WKN-003" => (
+            ".well-known/ai.txt",
+            "# AI training and usage policy for this repository\n\
+             # Change Disallow to Allow to permit AI training on this code\n\
+             User-agent: *\n\
+             Disallow: /\n"
+                .to_string(),
+        ),
+        "RSR-WKN-004" => (
+            ".well-known/humans.txt",
+            format!(
+                "/* TEAM */\n\
+                 \x20   Project: {project}\n\
+                 \x20   Maintainer: TODO(rhodibot)\n\
+                 \x20   Contact: TODO(rhodibot)\n",
+                project = project
+            ),
+        ),
+        "RSR-BLD-001" if root.join("Cargo.toml").is_file() => (
+            "justfile",
+            "# List the recipes\n\
+             default:\n    @just --list\n\n\
+             build:\n    cargo build --release\n\n\
+             test:\n    cargo test\n\n\
+             release: test build\n"
+                .to_string(),
+        ),
+        "RSR-BLD-003" => (".gitlab-ci.yml", CIPlatform::GitLabCI.generate_config()?),
+        _ => return None,
+    };
+    Some(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ComplianceLevel;
+    use std::fs;
+
+    #[test]
+    fn test_plan_and_apply() {
+        let dir = std::env::temp_dir().join("rhodibot_test_fix");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("SECURITY.md"), "# Security\n").unwrap();
+
+        let mut report = ComplianceReport::new(dir.clone());
+        for (id, passed) in [
+            ("RSR-DOC-001", false),
+            ("RSR-DOC-002", false),
+            ("RSR-DOC-003", false),
+            ("RSR-DOC-007", true),
+            ("RSR-WKN-004", false),
+            ("RSR-BLD-001", false),
+        ] {
+            report.add_spec_check(crate::catalog::lookup(id).unwrap(), passed);
+        }
+        report.add_check("Extras", "NOTES.md", false, ComplianceLevel::Gold);
+
+        let fixes = plan(&report);
+        let paths: Vec<&Path> = fixes.iter().map(|f| f.path.as_path()).collect();
+        // No license is chosen, SECURITY.md exists and there is no Cargo.toml
+        assert_eq!(paths, [Path::new("README.md"), Path::new(".well-known/humans.txt")]);
+        assert_eq!(fixes[0].id, "RSR-DOC-001");
+        assert!(fixes[0].contents.starts_with("# rhodibot_test_fix\n"));
+        assert!(fixes.iter().all(|fix| crate::content::find_stub(&fix.contents).is_some()));
+
+        fs::write(dir.join("README.md"), "# Mine\n").unwrap();
+        let written = apply(&dir, &fixes).unwrap();
+        assert_eq!(written, [PathBuf::from(".well-known/humans.txt")]);
+        assert_eq!(fs::read_to_string(dir.join("README.md")).unwrap(), "# Mine\n");
+        assert!(fs::read_to_string(dir.join(".well-known/humans.txt"))
+            .unwrap()
+            .starts_with("/* TEAM */\n"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
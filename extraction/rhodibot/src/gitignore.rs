@@ -0,0 +1,197 @@
+//! `.gitignore` matching, to explain why a file is not tracked
+//!
+//! Covers what is needed to tell whether one path is ignored: the
+//! `.gitignore` files along the path and `.git/info/exclude`, with `!`
+//! negation, directory-only (`build/`) and anchored (`/out`, `docs/*.md`)
+//! patterns and the `*`, `?` and `**` wildcards. Character classes and the
+//! user's global `core.excludesFile` are not supported.
+
+use crate::vcs;
+use crate::vfs::FileSystem;
+use std::path::Path;
+
+/// One line of an ignore file
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pattern {
+    glob: String,
+    /// `!pattern`: re-include what an earlier pattern ignored
+    negated: bool,
+    /// `pattern/`: matches directories only
+    dir_only: bool,
+    /// Contains a `/` other than a trailing one: matched against the whole
+    /// path below `base` instead of the file name
+    anchored: bool,
+    /// Directory of the ignore file, relative to the repository root, with a
+    /// trailing `/` (empty for the root)
+    base: String,
+}
+
+/// Patterns of the ignore files that apply to a path, lowest precedence first
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    patterns: Vec<Pattern>,
+}
+
+impl IgnoreRules {
+    /// Add the patterns of an ignore file in directory `base` (relative to
+    /// the repository root, `""` for the root)
+    pub fn add(&mut self, contents: &str, base: &str) {
+        let base = if base.is_empty() || base.ends_with('/') {
+            base.to_string()
+        } else {
+            format!("{}/", base)
+        };
+        for line in contents.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let (dir_only, line) = match line.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let anchored = line.contains('/');
+            self.patterns.push(Pattern {
+                glob: line.strip_prefix('/').unwrap_or(line).to_string(),
+                negated,
+                dir_only,
+                anchored,
+                base: base.clone(),
+            });
+        }
+    }
+
+    /// Whether `path` (relative, `/`-separated) is ignored
+    ///
+    /// A path inside an ignored directory is ignored whatever its own
+    /// patterns say, as in git.
+    pub fn is_ignored(&self, path: &str, is_dir: bool) -> bool {
+        let mut end = 0;
+        while let Some(slash) = path[end..].find('/') {
+            end += slash;
+            if self.decides(&path[..end], true) == Some(true) {
+                return true;
+            }
+            end += 1;
+        }
+        self.decides(path, is_dir) == Some(true)
+    }
+
+    /// `Some(ignored)` from the last matching pattern, `None` if none match
+    fn decides(&self, path: &str, is_dir: bool) -> Option<bool> {
+        self.patterns
+            .iter()
+            .rev()
+            .find(|pattern| pattern.matches(path, is_dir))
+            .map(|pattern| !pattern.negated)
+    }
+}
+
+impl Pattern {
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let Some(rel) = path.strip_prefix(self.base.as_str()) else {
+            return false;
+        };
+        let subject = if self.anchored {
+            rel
+        } else {
+            rel.rsplit('/').next().unwrap_or(rel)
+        };
+        glob_match(self.glob.as_bytes(), subject.as_bytes())
+    }
+}
+
+/// Match `text` against a glob where `*` and `?` stay within one path
+/// component and `**` crosses components
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', b'/', rest @ ..] => {
+            glob_match(rest, text)
+                || (0..text.len()).any(|i| text[i] == b'/' && glob_match(rest, &text[i + 1..]))
+        }
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+        [b'*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != b'/')
+            .any(|i| glob_match(rest, &text[i..])),
+        [b'?', rest @ ..] => {
+            matches!(text.first(), Some(&c) if c != b'/') && glob_match(rest, &text[1..])
+        }
+        [b'\\', c, rest @ ..] | [c, rest @ ..] => {
+            text.first() == Some(c) && glob_match(rest, &text[1..])
+        }
+    }
+}
+
+/// Whether git ignores `path` (relative, `/`-separated) in the repository at
+/// `repo_path`
+pub fn is_ignored(fs: &dyn FileSystem, repo_path: &Path, path: &str) -> bool {
+    let mut rules = IgnoreRules::default();
+    if let Some(git_dir) = vcs::git_dir(fs, repo_path) {
+        if let Ok(exclude) = fs.read_to_string(&git_dir.join("info").join("exclude")) {
+            rules.add(&exclude, "");
+        }
+    }
+    let mut dir = String::new();
+    for component in path.split('/') {
+        if let Ok(contents) = fs.read_to_string(&repo_path.join(&dir).join(".gitignore")) {
+            rules.add(&contents, &dir);
+        }
+        dir.push_str(component);
+        dir.push('/');
+    }
+    rules.is_ignored(path, fs.is_dir(&repo_path.join(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vfs::MemoryFs;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match(b"*.md", b"README.md"));
+        assert!(!glob_match(b"*.md", b"docs/README.md"));
+        assert!(glob_match(b"docs/**/*.md", b"docs/a/b/x.md"));
+        assert!(glob_match(b"**/x.md", b"x.md"));
+        assert!(glob_match(b"READ?E.md", b"README.md"));
+        assert!(glob_match(b"\\!important", b"!important"));
+    }
+
+    #[test]
+    fn test_rules() {
+        let mut rules = IgnoreRules::default();
+        rules.add("# build output\n*.log\n/target\nbuild/\n!keep.log\n", "");
+        rules.add("draft-*\n", "docs");
+        assert!(rules.is_ignored("debug.log", false));
+        assert!(!rules.is_ignored("keep.log", false));
+        assert!(rules.is_ignored("target", true));
+        assert!(!rules.is_ignored("src/target", true));
+        assert!(rules.is_ignored("build/out.txt", false));
+        assert!(!rules.is_ignored("build", false));
+        assert!(rules.is_ignored("docs/draft-1.md", false));
+        assert!(!rules.is_ignored("draft-1.md", false));
+    }
+
+    #[test]
+    fn test_is_ignored_reads_files() {
+        let mut fs = MemoryFs::new();
+        fs.add_file("/repo/.git/HEAD", "ref: refs/heads/main\n")
+            .add_file("/repo/.git/info/exclude", "LICENSE.txt\n")
+            .add_file("/repo/.gitignore", ".well-known/\n")
+            .add_file("/repo/.well-known/security.txt", "Contact: x\n")
+            .add_file("/repo/LICENSE.txt", "MIT\n")
+            .add_file("/repo/README.md", "# Demo\n");
+        let repo = Path::new("/repo");
+        assert!(is_ignored(&fs, repo, ".well-known/security.txt"));
+        assert!(is_ignored(&fs, repo, "LICENSE.txt"));
+        assert!(!is_ignored(&fs, repo, "README.md"));
+    }
+}
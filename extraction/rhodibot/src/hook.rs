@@ -0,0 +1,163 @@
+//! Git hook installation (`rhodibot install-hook`)
+//!
+//! Installs a `pre-commit` or `pre-push` hook that runs a quiet, fail-fast
+//! compliance check, so developers see a regression before CI does. The hooks
+//! directory is asked of the system `git`, which honours worktrees,
+//! submodules and `core.hooksPath`.
+//!
+//! The script calls `rhodibot` from `PATH` and skips the check with a note
+//! when it is not installed, so a clone without rhodibot can still push.
+//! A hook that rhodibot did not write is never overwritten.
+
+use crate::subprocess;
+use crate::ComplianceLevel;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Longest `git rev-parse` may take
+const GIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Line that marks a hook script as written by rhodibot
+pub const HOOK_MARKER: &str = "# Installed by rhodibot install-hook";
+
+/// Git hooks rhodibot can install
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    PreCommit,
+    PrePush,
+}
+
+impl HookKind {
+    /// File name of the hook in the hooks directory
+    pub const fn file_name(self) -> &'static str {
+        match self {
+            HookKind::PreCommit => "pre-commit",
+            HookKind::PrePush => "pre-push",
+        }
+    }
+}
+
+impl FromStr for HookKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "pre-commit" => Ok(HookKind::PreCommit),
+            "pre-push" => Ok(HookKind::PrePush),
+            other => Err(format!(
+                "Unknown hook: {}. Use 'pre-commit' or 'pre-push'",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for HookKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.file_name())
+    }
+}
+
+/// The hook script; `level` overrides the repository's configured policy
+pub fn script(kind: HookKind, level: Option<ComplianceLevel>) -> String {
+    let level_arg = level
+        .map(|level| format!(" --level {}", level.display_name().to_lowercase()))
+        .unwrap_or_default();
+    format!(
+        "#!/bin/sh\n\
+         {marker} ({kind}); delete this file to uninstall\n\
+         if ! command -v rhodibot >/dev/null 2>&1; then\n\
+         \x20   echo \"rhodibot not found on PATH; skipping RSR compliance check\" >&2\n\
+         \x20   exit 0\n\
+         fi\n\
+         exec rhodibot check --quiet --fail-fast{level_arg} .\n",
+        marker = HOOK_MARKER,
+        kind = kind,
+        level_arg = level_arg
+    )
+}
+
+/// The hooks directory of the git repository at `repo_path`
+pub fn hooks_dir(repo_path: &Path) -> Result<PathBuf, String> {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(repo_path).args(["rev-parse", "--git-path", "hooks"]);
+    let output = subprocess::run(&mut command, None, GIT_TIMEOUT)
+        .map_err(|e| format!("cannot run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} is not a git repository: {}",
+            repo_path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    // Relative paths are relative to the directory git ran in
+    let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(repo_path.join(dir))
+}
+
+/// Write the hook into the repository at `repo_path`, returning its path
+///
+/// A hook rhodibot installed earlier is replaced; any other existing hook
+/// is left alone and reported as an error.
+pub fn install(
+    repo_path: &Path,
+    kind: HookKind,
+    level: Option<ComplianceLevel>,
+) -> Result<PathBuf, String> {
+    let dir = hooks_dir(repo_path)?;
+    let path = dir.join(kind.file_name());
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if !existing.contains(HOOK_MARKER) {
+            return Err(format!(
+                "{} already exists and was not installed by rhodibot; remove it or add \
+                 'rhodibot check --quiet .' to it yourself",
+                path.display()
+            ));
+        }
+    }
+    fs::create_dir_all(&dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+    fs::write(&path, script(kind, level))
+        .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    make_executable(&path)
+        .map_err(|e| format!("cannot make {} executable: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+}
+
+/// Git for Windows runs hooks through its own shell; no mode bits needed
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script() {
+        let plain = script(HookKind::PrePush, None);
+        assert!(plain.starts_with("#!/bin/sh\n# Installed by rhodibot install-hook (pre-push)"));
+        assert!(plain.ends_with("exec rhodibot check --quiet --fail-fast .\n"));
+        assert!(plain.contains("    exit 0\n"));
+
+        let silver = script(HookKind::PreCommit, Some(ComplianceLevel::Silver));
+        assert!(silver.ends_with("exec rhodibot check --quiet --fail-fast --level silver .\n"));
+    }
+
+    #[test]
+    fn test_hook_kind() {
+        assert_eq!("pre-push".parse(), Ok(HookKind::PrePush));
+        assert_eq!(HookKind::PreCommit.to_string(), "pre-commit");
+        assert!("post-merge".parse::<HookKind>().is_err());
+    }
+}
@@ -0,0 +1,386 @@
+//! JSON string encoding shared by every JSON emitter, and a small reader
+//!
+//! Reports, SARIF, Code Quality, OTLP spans and HTTP responses are written by
+//! hand, so this module is the one place that decides how text becomes a
+//! JSON string literal. The output is always valid RFC 8259. Beyond what the
+//! RFC requires, characters that are invisible or change how surrounding text
+//! is displayed are escaped too, so a hostile file name cannot hide in a
+//! report or reorder it when shown in a terminal or code review.
+//!
+//! [`parse`] reads back the few JSON files rhodibot keeps between runs, such
+//! as the drift state file. It accepts any RFC 8259 document.
+
+/// Escape `s` for use inside a JSON string literal (without the quotes)
+///
+/// Escaped:
+/// - `"` and `\`, as required
+/// - C0 controls, using `\b \f \n \r \t` where available and `\u00XX`
+///   otherwise
+/// - DEL and the C1 controls (U+007F–U+009F)
+/// - U+2028 and U+2029, which are line terminators in JavaScript
+/// - bidirectional formatting controls (U+061C, U+200E, U+200F,
+///   U+202A–U+202E, U+2066–U+2069) and the byte order mark
+///
+/// Everything else, including characters outside the Basic Multilingual
+/// Plane, is emitted as UTF-8.
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    encode_into(&mut out, s, false);
+    out
+}
+
+/// Like [`escape`], but the output is pure ASCII
+///
+/// Non-ASCII characters become `\uXXXX` escapes; characters outside the
+/// Basic Multilingual Plane become a UTF-16 surrogate pair, as RFC 8259
+/// requires.
+pub fn escape_ascii(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    encode_into(&mut out, s, true);
+    out
+}
+
+/// `s` as a complete JSON string literal, including the quotes
+pub fn string(s: &str) -> String {
+    format!("\"{}\"", escape(s))
+}
+
+/// Whether `c` must be written as an escape even in UTF-8 output
+fn needs_escape(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0}'..='\u{1f}'
+            | '\u{7f}'..='\u{9f}'
+            | '\u{61c}'
+            | '\u{200e}'
+            | '\u{200f}'
+            | '\u{2028}'..='\u{202e}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{feff}'
+    )
+}
+
+fn push_unit(out: &mut String, unit: u16) {
+    out.push_str(&format!("\\u{:04x}", unit));
+}
+
+fn encode_into(out: &mut String, s: &str, ascii_only: bool) {
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if needs_escape(c) || (ascii_only && !c.is_ascii()) => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    push_unit(out, *unit);
+                }
+            }
+            c => out.push(c),
+        }
+    }
+}
+
+/// Deepest nesting [`parse`] accepts, so hostile input cannot exhaust the stack
+const MAX_DEPTH: usize = 128;
+
+/// A parsed JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Members in document order
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// The member `key` of an object (the last one, if repeated)
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        *self == Value::Null
+    }
+}
+
+/// Parse a JSON document
+pub fn parse(text: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        bytes: text.as_bytes(),
+        pos: 0,
+    };
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    if parser.pos < parser.bytes.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("invalid JSON at byte {}: {}", self.pos, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.bytes.get(self.pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), String> {
+        if self.bytes[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", literal)))
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b'n') => self.expect("null").map(|_| Value::Null),
+            Some(b't') => self.expect("true").map(|_| Value::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if self.close(b']') {
+                    return Ok(Value::Array(items));
+                }
+                loop {
+                    items.push(self.value(depth + 1)?);
+                    if self.separator(b']')? {
+                        return Ok(Value::Array(items));
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut members = Vec::new();
+                if self.close(b'}') {
+                    return Ok(Value::Object(members));
+                }
+                loop {
+                    self.skip_whitespace();
+                    if self.bytes.get(self.pos) != Some(&b'"') {
+                        return Err(self.error("expected a member name"));
+                    }
+                    let key = self.string()?;
+                    self.skip_whitespace();
+                    self.expect(":")?;
+                    members.push((key, self.value(depth + 1)?));
+                    if self.separator(b'}')? {
+                        return Ok(Value::Object(members));
+                    }
+                }
+            }
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    /// Consume `close` if it is next (an empty array or object)
+    fn close(&mut self, close: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.bytes.get(self.pos) == Some(&close);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// Consume a `,` (false) or `close` (true) after an item
+    fn separator(&mut self, close: u8) -> Result<bool, String> {
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b',') => {
+                self.pos += 1;
+                Ok(false)
+            }
+            Some(&c) if c == close => {
+                self.pos += 1;
+                Ok(true)
+            }
+            _ => Err(self.error(&format!("expected ',' or '{}'", close as char))),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        while matches!(
+            self.bytes.get(self.pos),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.pos += 1;
+        }
+        // The bytes are ASCII, so the slice is valid UTF-8
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap_or_default();
+        text.parse().map(Value::Number).map_err(|_| self.error("invalid number"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .bytes
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error("short \\u escape"))?;
+        let digits = std::str::from_utf8(digits).map_err(|_| self.error("invalid \\u escape"))?;
+        let unit = u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid \\u escape"))?;
+        self.pos += 4;
+        Ok(unit)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1; // opening quote
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            while !matches!(self.bytes.get(self.pos), None | Some(b'"' | b'\\' | 0..=0x1f)) {
+                self.pos += 1;
+            }
+            // Input came from a &str and the run stops at ASCII bytes only
+            out.push_str(std::str::from_utf8(&self.bytes[start..self.pos]).unwrap_or_default());
+            match self.bytes.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escape = self.bytes.get(self.pos).copied();
+                    self.pos += 1;
+                    let c = match escape {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let high = self.hex4()?;
+                            let code = if (0xd800..0xdc00).contains(&high)
+                                && self.bytes[self.pos..].starts_with(b"\\u")
+                            {
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return Err(self.error("unpaired surrogate"));
+                                }
+                                0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                            } else {
+                                high
+                            };
+                            char::from_u32(code).ok_or_else(|| self.error("invalid \\u escape"))?
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    out.push(c);
+                }
+                Some(_) => return Err(self.error("control character in string")),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_required_characters() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape("a\"b\\c"), "a\\\"b\\\\c");
+        assert_eq!(escape("\u{8}\u{c}\n\r\t"), "\\b\\f\\n\\r\\t");
+        assert_eq!(escape("\u{0}\u{1b}\u{1f}"), "\\u0000\\u001b\\u001f");
+        assert_eq!(escape("/"), "/");
+        assert_eq!(string("say \"hi\""), "\"say \\\"hi\\\"\"");
+    }
+
+    #[test]
+    fn test_escape_invisible_and_bidi_characters() {
+        assert_eq!(escape("\u{7f}\u{85}\u{9f}"), "\\u007f\\u0085\\u009f");
+        assert_eq!(escape("a\u{2028}b\u{2029}"), "a\\u2028b\\u2029");
+        assert_eq!(
+            escape("\u{202e}txt.exe\u{202c}"),
+            "\\u202etxt.exe\\u202c"
+        );
+        assert_eq!(escape("\u{2066}\u{2069}\u{61c}\u{200f}"), "\\u2066\\u2069\\u061c\\u200f");
+        assert_eq!(escape("\u{feff}x"), "\\ufeffx");
+        // Visible non-ASCII text is left alone
+        assert_eq!(escape("café ☕ 😀"), "café ☕ 😀");
+        assert_eq!(escape("\u{a0}\u{d7ff}\u{e000}"), "\u{a0}\u{d7ff}\u{e000}");
+    }
+
+    #[test]
+    fn test_escape_ascii() {
+        assert_eq!(escape_ascii("café"), "caf\\u00e9");
+        assert_eq!(escape_ascii("😀"), "\\ud83d\\ude00");
+        assert_eq!(escape_ascii("\u{10000}\u{10ffff}"), "\\ud800\\udc00\\udbff\\udfff");
+        assert_eq!(escape_ascii("\"\n"), "\\\"\\n");
+        assert!(escape_ascii("日本語 \u{e0041} 😀").is_ascii());
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = " {\"a\": [1, -2.5e1, true, null], \
+                    \"b\": {\"c\": \"x\\\"\\u00e9\\ud83d\\ude00\"}} ";
+        let value = parse(text).unwrap();
+        let items = value.get("a").and_then(Value::as_array).unwrap();
+        let expected = [Value::Number(1.0), Value::Number(-25.0), Value::Bool(true), Value::Null];
+        assert_eq!(items, expected);
+        let c = value.get("b").and_then(|b| b.get("c"));
+        assert_eq!(c.and_then(Value::as_str), Some("x\"é😀"));
+        assert_eq!(parse("[]"), Ok(Value::Array(Vec::new())));
+
+        // Whatever escape() writes reads back unchanged
+        let text = "tab\t \u{202e}bidi \u{7f} 😀 \\ \"";
+        assert_eq!(parse(&string(text)).unwrap().as_str(), Some(text));
+        assert_eq!(parse(&format!("\"{}\"", escape_ascii(text))).unwrap().as_str(), Some(text));
+
+        let bad_escapes = ["\"\\x\"", "\"\\ud83d\"", "\"\\ud83d\\u0041\"", "\"a\nb\""];
+        let bad_syntax = ["", "{", "[1,]", "{\"a\" 1}", "\"open", "tru", "1 2"];
+        for bad in bad_syntax.iter().chain(&bad_escapes) {
+            assert!(parse(bad).is_err(), "{:?} should not parse", bad);
+        }
+        assert!(parse(&"[".repeat(MAX_DEPTH + 2)).unwrap_err().contains("nested too deeply"));
+    }
+}
@@ -0,0 +1,275 @@
+//! Source layouts for the Source Structure checks
+//!
+//! The standard's `src/` and `tests/` fit Rust projects, but a Go module
+//! keeps its code in `cmd/`, `pkg/` or the root with tests beside it, and a
+//! documentation site has no code at all. Each [`Layout`] says where its
+//! ecosystem keeps sources and tests. The layout is detected from the
+//! repository's manifest unless `[source] layout` names one:
+//!
+//! - `rust` (`Cargo.toml`, or no known manifest): `src/` and `tests/` or
+//!   `test/`
+//! - `go` (`go.mod`): `cmd/`, `pkg/`, `internal/` or `*.go` files in the root,
+//!   and `*_test.go` files
+//! - `python` (`pyproject.toml`, `setup.py`, `setup.cfg`): `src/`, `lib/` or a
+//!   package directory, and `tests/` or `test/`
+//! - `docs` (`mkdocs.yml`, `book.toml`, `antora.yml`, `_config.yml`, ...):
+//!   no sources or tests
+//! - `node` (`package.json`): `src/` or `lib/`, and `test/`, `tests/`,
+//!   `__tests__/` or `*.test.js` and `*.spec.js` files
+//!
+//! Manifests are tried in that order, so a documentation site built with a
+//! Node tool counts as `docs`. Under `docs` the Source Structure checks do
+//! not apply.
+
+use crate::vfs::FileSystem;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Directory levels searched below the root for source or test files
+const MAX_FILE_DEPTH: usize = 3;
+
+/// Where a kind of project keeps its sources and tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    Rust,
+    Go,
+    Python,
+    Node,
+    /// Documentation only; no sources or tests are required
+    Docs,
+}
+
+impl Layout {
+    /// Every layout, in detection order of their manifests
+    pub const ALL: [Layout; 5] = [
+        Layout::Rust,
+        Layout::Go,
+        Layout::Python,
+        Layout::Docs,
+        Layout::Node,
+    ];
+
+    /// Name in configuration and reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::Rust => "rust",
+            Layout::Go => "go",
+            Layout::Python => "python",
+            Layout::Node => "node",
+            Layout::Docs => "docs",
+        }
+    }
+
+    /// Root files that identify the layout
+    fn manifests(&self) -> &'static [&'static str] {
+        match self {
+            Layout::Rust => &["Cargo.toml"],
+            Layout::Go => &["go.mod"],
+            Layout::Python => &["pyproject.toml", "setup.py", "setup.cfg"],
+            Layout::Docs => &[
+                "mkdocs.yml",
+                "book.toml",
+                "antora.yml",
+                "docusaurus.config.js",
+                "_config.yml",
+                "hugo.toml",
+            ],
+            Layout::Node => &["package.json"],
+        }
+    }
+
+    /// The layout of the repository at `root`, from its manifest
+    ///
+    /// Repositories without a known manifest get the standard's own
+    /// layout, [`Layout::Rust`].
+    pub fn detect(fs: &dyn FileSystem, root: &Path) -> Self {
+        Layout::ALL
+            .into_iter()
+            .find(|layout| layout.manifests().iter().any(|m| fs.is_file(&root.join(m))))
+            .unwrap_or(Layout::Rust)
+    }
+
+    /// Whether the Source Structure checks apply at all
+    pub fn has_code(&self) -> bool {
+        *self != Layout::Docs
+    }
+
+    /// Directories that hold sources
+    pub fn source_dirs(&self) -> &'static [&'static str] {
+        match self {
+            Layout::Rust => &["src"],
+            Layout::Go => &["cmd", "pkg", "internal"],
+            Layout::Python | Layout::Node => &["src", "lib"],
+            Layout::Docs => &[],
+        }
+    }
+
+    /// Directories that hold tests
+    pub fn test_dirs(&self) -> &'static [&'static str] {
+        match self {
+            Layout::Rust | Layout::Python => &["tests", "test"],
+            Layout::Node => &["test", "tests", "__tests__"],
+            Layout::Go | Layout::Docs => &[],
+        }
+    }
+
+    /// File name endings that count as sources in the root directory
+    fn source_suffixes(&self) -> &'static [&'static str] {
+        match self {
+            Layout::Go => &[".go"],
+            _ => &[],
+        }
+    }
+
+    /// File name endings that count as tests anywhere near the sources
+    fn test_suffixes(&self) -> &'static [&'static str] {
+        match self {
+            Layout::Go => &["_test.go"],
+            Layout::Node => &[".test.js", ".test.ts", ".spec.js", ".spec.ts"],
+            _ => &[],
+        }
+    }
+
+    /// Whether `root` has sources other than in [`Layout::source_dirs`]:
+    /// matching files in the root, or a Python package directory
+    pub fn has_source_files(&self, fs: &dyn FileSystem, root: &Path) -> bool {
+        let entries = fs.read_dir(root).unwrap_or_default();
+        entries.iter().any(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+            let regular = fs.symlink_metadata(path).map_or(false, |m| !m.is_symlink());
+            if !regular || name.starts_with('.') {
+                return false;
+            }
+            match self {
+                Layout::Python => fs.is_file(&path.join("__init__.py")),
+                _ => {
+                    fs.is_file(path)
+                        && self.source_suffixes().iter().any(|s| name.ends_with(s))
+                }
+            }
+        })
+    }
+
+    /// Whether `root` has test files outside [`Layout::test_dirs`], such as
+    /// Go's `*_test.go` beside the code they test
+    pub fn has_test_files(&self, fs: &dyn FileSystem, root: &Path) -> bool {
+        !self.test_suffixes().is_empty()
+            && has_file_ending(fs, root, self.test_suffixes(), MAX_FILE_DEPTH)
+    }
+
+    /// What the layout accepts as sources, for reports
+    pub fn describe_sources(&self) -> String {
+        let mut accepted: Vec<String> =
+            self.source_dirs().iter().map(|dir| format!("{}/", dir)).collect();
+        accepted.extend(self.source_suffixes().iter().map(|s| format!("*{} files", s)));
+        if *self == Layout::Python {
+            accepted.push("a package directory".to_string());
+        }
+        describe(accepted)
+    }
+
+    /// What the layout accepts as tests, for reports
+    pub fn describe_tests(&self) -> String {
+        let mut accepted: Vec<String> =
+            self.test_dirs().iter().map(|dir| format!("{}/", dir)).collect();
+        accepted.extend(self.test_suffixes().iter().map(|s| format!("*{} files", s)));
+        describe(accepted)
+    }
+}
+
+/// `a`, `a or b`, `a, b or c`
+fn describe(mut items: Vec<String>) -> String {
+    match items.pop() {
+        Some(last) if !items.is_empty() => format!("{} or {}", items.join(", "), last),
+        Some(last) => last,
+        None => "nothing".to_string(),
+    }
+}
+
+/// Whether a file ending in one of `suffixes` is in `dir`, or up to `depth`
+/// levels below it; hidden directories and symlinks are not followed
+fn has_file_ending(fs: &dyn FileSystem, dir: &Path, suffixes: &[&str], depth: usize) -> bool {
+    let entries = fs.read_dir(dir).unwrap_or_default();
+    entries.iter().any(|path| {
+        let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        match fs.symlink_metadata(path) {
+            Ok(meta) if meta.is_file() => suffixes.iter().any(|s| name.ends_with(s)),
+            Ok(meta) if meta.is_dir() && depth > 0 && !name.starts_with('.') => {
+                has_file_ending(fs, path, suffixes, depth - 1)
+            }
+            _ => false,
+        }
+    })
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Layout::ALL
+            .into_iter()
+            .find(|layout| layout.name().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "unknown layout '{}' (expected auto, rust, go, python, node or docs)",
+                    name
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vfs::MemoryFs;
+
+    #[test]
+    fn test_detect() {
+        let root = Path::new("/repo");
+        let detect = |manifests: &[&str]| {
+            let mut fs = MemoryFs::new();
+            fs.add_dir("/repo");
+            for manifest in manifests {
+                fs.add_file(root.join(manifest), "");
+            }
+            Layout::detect(&fs, root)
+        };
+        assert_eq!(detect(&[]), Layout::Rust);
+        assert_eq!(detect(&["go.mod"]), Layout::Go);
+        assert_eq!(detect(&["setup.cfg"]), Layout::Python);
+        assert_eq!(detect(&["package.json"]), Layout::Node);
+        assert_eq!(detect(&["package.json", "docusaurus.config.js"]), Layout::Docs);
+        assert_eq!(detect(&["Cargo.toml", "package.json"]), Layout::Rust);
+
+        assert_eq!("Python".parse::<Layout>(), Ok(Layout::Python));
+        assert!("java".parse::<Layout>().unwrap_err().contains("unknown layout 'java'"));
+    }
+
+    #[test]
+    fn test_source_and_test_files() {
+        let root = Path::new("/repo");
+        let mut fs = MemoryFs::new();
+        fs.add_file("/repo/go.mod", "module example.org/m\n")
+            .add_file("/repo/main.go", "package main\n")
+            .add_file("/repo/pkg/store/store_test.go", "package store\n")
+            .add_file("/repo/mypkg/__init__.py", "");
+        assert!(Layout::Go.has_source_files(&fs, root));
+        assert!(Layout::Go.has_test_files(&fs, root));
+        assert!(Layout::Python.has_source_files(&fs, root));
+        assert!(!Layout::Node.has_source_files(&fs, root));
+        assert!(!Layout::Node.has_test_files(&fs, root));
+        assert!(!Layout::Rust.has_test_files(&fs, root));
+
+        assert_eq!(Layout::Go.describe_sources(), "cmd/, pkg/, internal/ or *.go files");
+        assert_eq!(Layout::Go.describe_tests(), "*_test.go files");
+        assert_eq!(Layout::Rust.describe_sources(), "src/");
+        assert_eq!(Layout::Docs.describe_tests(), "nothing");
+    }
+}
@@ -9,11 +9,12 @@
 //! # Features
 //!
 //! - Zero dependencies (uses only Rust std library)
-//! - Zero unsafe code
+//! - No unsafe code outside the opt-in C interface (`ffi` feature)
 //! - Offline-first (no network access required)
 //! - Bronze-level RSR compliance checking
 //! - JSON output for CI/CD integration
 //! - Security-aware (symlink detection)
+//! - Builds for `wasm32-wasi`; checks can run on a virtual file tree ([`vfs`])
 //!
 //! # Example
 //!
@@ -25,11 +26,67 @@
 //! println!("Bronze compliant: {}", report.bronze_compliance());
 //! ```
 
+pub mod args;
+pub mod batch;
+pub mod bench;
 pub mod bot;
-
-use std::fs;
+pub mod builder;
+mod codec;
+pub mod collector;
+pub mod config;
+pub mod conformity;
+pub mod contacts;
+pub mod content;
+pub mod conventional;
+pub mod dashboard;
+pub mod env;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fix;
+pub mod gitignore;
+pub mod hook;
+pub mod json;
+pub mod layout;
+pub mod links;
+pub mod logging;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod output;
+pub mod paths;
+pub mod pattern;
+pub mod policy;
+pub mod profile;
+pub mod progress;
+pub mod readme;
+pub mod remote;
+pub mod serve;
+pub mod sign;
+pub mod site;
+pub mod standard;
+pub mod state;
+pub mod style;
+mod subprocess;
+pub mod suppressions;
+pub mod vcs;
+pub mod vfs;
+pub mod warnings;
+pub mod workspace;
+
+pub use rsr_spec::{catalog, ComplianceLevel, StandardVersion, STANDARD_URL, STANDARD_VERSION};
+
+use builder::ReportMetadata;
+use catalog::CheckSpec;
+use config::Config;
+use layout::Layout;
+use logging::{LogLevel, Logger, NoopLogger};
+use progress::{NoProgress, Progress, ProgressSink};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use suppressions::{Suppression, Suppressions};
+use vfs::{FileSystem, RealFs};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -41,6 +98,65 @@ pub mod exit_codes {
     pub const SECURITY_WARNING: i32 = 2;
     pub const INVALID_PATH: i32 = 3;
     pub const INVALID_ARGS: i32 = 4;
+    /// The required level is met only because of active waivers (opt-in
+    /// with `distinguish_waived`)
+    pub const WAIVED_COMPLIANCE: i32 = 5;
+
+    /// Every exit code with its name and when it is returned, for
+    /// `--explain-exit`
+    const CODES: &[(i32, &str, &str)] = &[
+        (
+            SUCCESS,
+            "SUCCESS",
+            "The report meets the required level (fail_on_level, Bronze by\n\
+             default) and the minimum percentage (fail_below_percentage), and has\n\
+             no critical security warnings. A level met only through waivers also\n\
+             exits 0 unless distinguish_waived is set.",
+        ),
+        (
+            COMPLIANCE_FAILED,
+            "COMPLIANCE_FAILED",
+            "A check required for the level in fail_on_level failed, or the pass\n\
+             percentage is below fail_below_percentage. Security warnings are\n\
+             judged first, so a report with both exits 2.",
+        ),
+        (
+            SECURITY_WARNING,
+            "SECURITY_WARNING",
+            "The report has a critical security warning, or with fail_on_warning\n\
+             (--fail-on-warning) a warning of level Warning. This takes precedence\n\
+             over the compliance level.",
+        ),
+        (
+            INVALID_PATH,
+            "INVALID_PATH",
+            "The repository path is not a directory, or a file could not be read\n\
+             or written. No verdict was reached.",
+        ),
+        (
+            INVALID_ARGS,
+            "INVALID_ARGS",
+            "The command line, an RHODIBOT_* environment variable or the\n\
+             configuration file is invalid. No verdict was reached.",
+        ),
+        (
+            WAIVED_COMPLIANCE,
+            "WAIVED_COMPLIANCE",
+            "The required level is met only because active waivers cover failing\n\
+             checks. Returned with distinguish_waived (--distinguish-waived);\n\
+             such a report exits 0 otherwise.",
+        ),
+    ];
+
+    /// Name of an exit code, e.g. `SECURITY_WARNING` for 2
+    pub fn name(code: i32) -> Option<&'static str> {
+        CODES.iter().find(|(c, _, _)| *c == code).map(|(_, name, _)| *name)
+    }
+
+    /// When the exit code is returned
+    pub fn explain(code: i32) -> Option<&'static str> {
+        CODES.iter().find(|(c, _, _)| *c == code).map(|(_, _, text)| *text)
+    }
 }
 
 /// Output format options
@@ -48,56 +164,119 @@ pub mod exit_codes {
 pub enum OutputFormat {
     Human,
     Json,
-    Sarif,  // Future: Static Analysis Results Interchange Format
+    Sarif, // Static Analysis Results Interchange Format 2.1.0
+    Tap,
+    CodeQuality,
+    Prometheus, // Text exposition format, for node_exporter's textfile collector
+    Porcelain,  // Stable tab-separated lines for scripts (`--porcelain`)
+    Markdown,   // The job summary; with `drift`, what changed
 }
 
 /// Verbosity level
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Verbosity {
     Quiet,   // Only pass/fail
+    Summary, // Per-category counts and the verdict
     Normal,  // Standard output
     Verbose, // Include all details
 }
 
-/// RSR Compliance levels
+/// Highest level that has automated checks; `highest_level` never reports
+/// above it, since higher levels would be met vacuously
+const HIGHEST_ASSESSED_LEVEL: ComplianceLevel = ComplianceLevel::Silver;
+
+/// Individual compliance check result
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    /// Stable check identifier (e.g. `RSR-DOC-001`)
+    pub id: String,
+    pub category: String,
+    pub item: String,
+    pub status: CheckStatus,
+    pub required_for: ComplianceLevel,
+    pub description: Option<String>,
+    /// Failed, but counted as passed because of an active suppression
+    pub waived: bool,
+    /// Time spent running the check (zero for ad-hoc checks)
+    pub duration: Duration,
+    /// Prerequisite that did not pass (see [`catalog::PREREQUISITES`]), so
+    /// this check was skipped and counts as failed
+    pub blocked_by: Option<String>,
+}
+
+impl CheckResult {
+    /// Whether the check counts as passed: it passed, or its failure is waived
+    pub fn passed(&self) -> bool {
+        self.status == CheckStatus::Pass || self.waived
+    }
+
+    /// Whether the check counts toward the score and the levels
+    pub fn is_applicable(&self) -> bool {
+        self.status != CheckStatus::NotApplicable
+    }
+
+    /// Whether the check ran and did not pass, and is not waived
+    ///
+    /// Unlike `!passed()`, this leaves out skipped and not applicable
+    /// checks, so it selects the checks that need a fix of their own.
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, CheckStatus::Fail | CheckStatus::Error) && !self.waived
+    }
+
+    /// Why a skipped check was not run: its prerequisite failed, or a
+    /// `--fail-fast` run stopped before it
+    pub fn skip_reason(&self) -> Option<String> {
+        match (self.status, &self.blocked_by) {
+            (CheckStatus::Skipped, Some(prerequisite)) => {
+                Some(format!("prerequisite {} failed", prerequisite))
+            }
+            (CheckStatus::Skipped, None) => Some(FAIL_FAST_REASON.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of a check
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ComplianceLevel {
-    Bronze,
-    Silver,
-    Gold,
-    Platinum,
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// Not run because a prerequisite failed (see `blocked_by`) or a
+    /// `--fail-fast` run had stopped; counts as failed
+    Skipped,
+    /// Does not apply to the repository; left out of the score and levels
+    NotApplicable,
+    /// The check could not be evaluated; counts as failed
+    Error,
 }
 
-impl ComplianceLevel {
-    /// Get the badge color for this compliance level
-    pub fn badge_color(&self) -> &'static str {
+impl CheckStatus {
+    /// Identifier used in machine-readable output
+    pub fn as_str(self) -> &'static str {
         match self {
-            ComplianceLevel::Bronze => "cd7f32",
-            ComplianceLevel::Silver => "c0c0c0",
-            ComplianceLevel::Gold => "ffd700",
-            ComplianceLevel::Platinum => "e5e4e2",
+            CheckStatus::Pass => "pass",
+            CheckStatus::Fail => "fail",
+            CheckStatus::Skipped => "skipped",
+            CheckStatus::NotApplicable => "not_applicable",
+            CheckStatus::Error => "error",
         }
     }
+}
 
-    /// Get the display name for this compliance level
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            ComplianceLevel::Bronze => "Bronze",
-            ComplianceLevel::Silver => "Silver",
-            ComplianceLevel::Gold => "Gold",
-            ComplianceLevel::Platinum => "Platinum",
+impl From<bool> for CheckStatus {
+    fn from(passed: bool) -> Self {
+        if passed {
+            CheckStatus::Pass
+        } else {
+            CheckStatus::Fail
         }
     }
 }
 
-/// Individual compliance check result
-#[derive(Debug, Clone)]
-pub struct CheckResult {
-    pub category: String,
-    pub item: String,
-    pub passed: bool,
-    pub required_for: ComplianceLevel,
-    pub description: Option<String>,
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Security warning levels
@@ -117,12 +296,50 @@ pub struct SecurityWarning {
 }
 
 /// Overall compliance report
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ComplianceReport {
     pub checks: Vec<CheckResult>,
     pub warnings: Vec<SecurityWarning>,
     pub repository_path: PathBuf,
     pub verified_at: SystemTime,
+    /// IDs of checks that were not run because they were disabled
+    pub skipped_checks: Vec<String>,
+    /// Suppressions in effect for this report
+    pub waivers: Vec<Suppression>,
+    /// Suppressions that have expired and no longer apply
+    pub expired_waivers: Vec<Suppression>,
+    /// Wall-clock time of the whole verification run
+    pub duration: Duration,
+    /// Version of the RSR standard the repository was checked against
+    pub standard_version: StandardVersion,
+    /// Leave run-specific data out of rendered output (`--reproducible`)
+    ///
+    /// Set by [`ComplianceReport::make_reproducible`].
+    pub reproducible: bool,
+    /// Show paths relative to the repository root (`--relative-paths`)
+    ///
+    /// Set by [`ComplianceReport::make_paths_relative`].
+    pub relative_paths: bool,
+    /// Commit, branch, remote and CI run the report belongs to
+    pub metadata: ReportMetadata,
+    /// Directory holding `.well-known/`, relative to the repository root;
+    /// empty for the root itself (see [`site`])
+    pub web_root: String,
+}
+
+/// Why the checks after the one that stopped a `fail_fast` run were not run
+const FAIL_FAST_REASON: &str = "not run: --fail-fast";
+
+/// Derive an identifier for an ad-hoc check that is not in the catalog
+fn adhoc_check_id(category: &str, item: &str) -> String {
+    let slug = |s: &str| {
+        s.to_lowercase()
+            .split(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    };
+    format!("rsr/{}/{}", slug(category), slug(item))
 }
 
 impl ComplianceReport {
@@ -132,18 +349,94 @@ pub fn new(path: PathBuf) -> Self {
             checks: Vec::new(),
             warnings: Vec::new(),
             repository_path: path,
-            verified_at: SystemTime::now(),
+            verified_at: env::verification_time(),
+            skipped_checks: Vec::new(),
+            waivers: Vec::new(),
+            expired_waivers: Vec::new(),
+            duration: Duration::ZERO,
+            standard_version: StandardVersion::default(),
+            reproducible: false,
+            relative_paths: false,
+            metadata: ReportMetadata::default(),
+            web_root: String::new(),
+        }
+    }
+
+    /// Canonicalize the report so identical repositories render identically
+    ///
+    /// Paths become relative (see [`ComplianceReport::make_paths_relative`])
+    /// and timings are zeroed. Renderers then leave out the verification
+    /// timestamp, so reports can be signed, cached and diffed byte for byte.
+    pub fn make_reproducible(&mut self) {
+        self.make_paths_relative();
+        for check in &mut self.checks {
+            check.duration = Duration::ZERO;
+        }
+        self.duration = Duration::ZERO;
+        self.reproducible = true;
+    }
+
+    /// Make warning paths relative to the repository root
+    ///
+    /// Renderers then show an absolute repository path as `.`, so published
+    /// reports do not reveal the layout of the machine that produced them.
+    pub fn make_paths_relative(&mut self) {
+        for warning in &mut self.warnings {
+            if let Some(ref path) = warning.path {
+                if let Ok(rel) = path.strip_prefix(&self.repository_path) {
+                    warning.path = Some(rel.to_path_buf());
+                }
+            }
+        }
+        self.relative_paths = true;
+    }
+
+    /// The repository as shown in output
+    ///
+    /// Absolute paths are shown as `.` in reports with relative paths;
+    /// relative paths and remote URLs are already independent of the machine.
+    pub fn repository_display(&self) -> String {
+        if self.relative_paths && self.repository_path.is_absolute() {
+            ".".to_string()
+        } else {
+            self.repository_path.display().to_string()
         }
     }
 
+    /// The `verified_at` timestamp as shown in output, `None` if reproducible
+    pub fn timestamp(&self) -> Option<String> {
+        (!self.reproducible).then(|| format_timestamp(self.verified_at))
+    }
+
+    /// Add the result of a catalogued check
+    pub fn add_spec_check(&mut self, spec: &CheckSpec, passed: bool) {
+        self.checks.push(CheckResult {
+            id: spec.id.to_string(),
+            category: spec.category.to_string(),
+            item: spec.item.to_string(),
+            status: passed.into(),
+            required_for: spec.level,
+            description: Some(spec.description.to_string()),
+            waived: false,
+            duration: Duration::ZERO,
+            blocked_by: None,
+        });
+    }
+
     /// Add a compliance check result
+    ///
+    /// Ad-hoc checks get an identifier derived from their category and item.
     pub fn add_check(&mut self, category: &str, item: &str, passed: bool, level: ComplianceLevel) {
         self.checks.push(CheckResult {
+            id: adhoc_check_id(category, item),
             category: category.to_string(),
             item: item.to_string(),
-            passed,
+            status: passed.into(),
             required_for: level,
             description: None,
+            waived: false,
+            duration: Duration::ZERO,
+            blocked_by: None,
         });
     }
 
@@ -157,16 +450,27 @@ pub fn add_check_with_desc(
         description: &str,
     ) {
         self.checks.push(CheckResult {
+            id: adhoc_check_id(category, item),
             category: category.to_string(),
             item: item.to_string(),
-            passed,
+            status: passed.into(),
             required_for: level,
             description: Some(description.to_string()),
+            waived: false,
+            duration: Duration::ZERO,
+            blocked_by: None,
         });
     }
 
-    /// Add a security warning
+    /// Add a security warning, unless the same warning is already reported
     pub fn add_warning(&mut self, level: WarningLevel, message: &str, path: Option<PathBuf>) {
+        let duplicate = self
+            .warnings
+            .iter()
+            .any(|w| w.level == level && w.message == message && w.path == path);
+        if duplicate {
+            return;
+        }
         self.warnings.push(SecurityWarning {
             level,
             message: message.to_string(),
@@ -176,43 +480,88 @@ pub fn add_warning(&mut self, level: WarningLevel, message: &str, path: Option<P
 
     /// Check if Bronze-level compliance is met
     pub fn bronze_compliance(&self) -> bool {
-        self.checks
-            .iter()
-            .filter(|c| c.required_for == ComplianceLevel::Bronze)
-            .all(|c| c.passed)
+        self.meets_level(ComplianceLevel::Bronze)
     }
 
     /// Check if Silver-level compliance is met
     pub fn silver_compliance(&self) -> bool {
-        self.bronze_compliance()
-            && self
-                .checks
-                .iter()
-                .filter(|c| c.required_for == ComplianceLevel::Silver)
-                .all(|c| c.passed)
+        self.meets_level(ComplianceLevel::Silver)
+    }
+
+    /// The `n` slowest checks, slowest first
+    pub fn slowest_checks(&self, n: usize) -> Vec<&CheckResult> {
+        let mut checks: Vec<&CheckResult> = self.checks.iter().collect();
+        checks.sort_by(|a, b| b.duration.cmp(&a.duration));
+        checks.truncate(n);
+        checks
+    }
+
+    /// Combined time spent in individual checks
+    pub fn checks_duration(&self) -> Duration {
+        self.checks.iter().map(|c| c.duration).sum()
+    }
+
+    /// Check if all checks required for `level`, or any level below it, pass
+    pub fn meets_level(&self, level: ComplianceLevel) -> bool {
+        self.checks
+            .iter()
+            .filter(|c| c.required_for <= level && c.is_applicable())
+            .all(CheckResult::passed)
+    }
+
+    /// Whether `level` is met only because failed checks are waived
+    pub fn meets_level_by_waivers(&self, level: ComplianceLevel) -> bool {
+        self.meets_level(level)
+            && self.checks.iter().any(|c| c.waived && c.required_for <= level)
     }
 
     /// Get the highest compliance level achieved
     pub fn highest_level(&self) -> Option<ComplianceLevel> {
-        if !self.bronze_compliance() || self.has_critical_warnings() {
+        if self.has_critical_warnings() {
             return None;
         }
-        if self.silver_compliance() {
-            // Check for gold and platinum when implemented
-            Some(ComplianceLevel::Silver)
-        } else {
-            Some(ComplianceLevel::Bronze)
+        ComplianceLevel::ALL
+            .into_iter()
+            .filter(|&level| level <= HIGHEST_ASSESSED_LEVEL)
+            .take_while(|&level| self.meets_level(level))
+            .last()
+    }
+
+    /// Apply suppressions, waiving failed checks they cover
+    ///
+    /// Expiry is judged against the report's `verified_at` date. Expired
+    /// suppressions are recorded but leave their checks failing.
+    pub fn apply_suppressions(&mut self, suppressions: &Suppressions) {
+        let today = format_timestamp(self.verified_at);
+        let today = today.get(..10).unwrap_or(&today);
+        for suppression in &suppressions.entries {
+            if suppression.is_expired(today) {
+                self.expired_waivers.push(suppression.clone());
+                continue;
+            }
+            for check in self.checks.iter_mut().filter(|c| c.id == suppression.id) {
+                if !check.passed() && check.is_applicable() {
+                    check.waived = true;
+                }
+            }
+            self.waivers.push(suppression.clone());
         }
     }
 
+    /// Number of checks that pass only because they are waived
+    pub fn waived_count(&self) -> usize {
+        self.checks.iter().filter(|c| c.waived).count()
+    }
+
     /// Count of passed checks
     pub fn passed_count(&self) -> usize {
-        self.checks.iter().filter(|c| c.passed).count()
+        self.checks.iter().filter(|c| c.is_applicable() && c.passed()).count()
     }
 
-    /// Total number of checks
+    /// Number of checks that count toward the score (not applicable ones
+    /// are left out)
     pub fn total_count(&self) -> usize {
-        self.checks.len()
+        self.checks.iter().filter(|c| c.is_applicable()).count()
     }
 
     /// Check if there are any critical warnings
@@ -231,6 +580,31 @@ pub fn percentage(&self) -> f64 {
         }
     }
 
+    /// Warnings grouped by level, message template and top-level directory
+    pub fn warning_groups(&self) -> Vec<warnings::WarningGroup<'_>> {
+        warnings::group(&self.warnings, &self.repository_path)
+    }
+
+    /// `(category, passed, total)` for each category, in order of first
+    /// appearance; checks that do not apply are not counted
+    pub fn category_totals(&self) -> Vec<(&str, usize, usize)> {
+        let mut totals: Vec<(&str, usize, usize)> = Vec::new();
+        for check in &self.checks {
+            let index = match totals.iter().position(|(c, _, _)| *c == check.category) {
+                Some(index) => index,
+                None => {
+                    totals.push((&check.category, 0, 0));
+                    totals.len() - 1
+                }
+            };
+            if check.is_applicable() {
+                totals[index].1 += usize::from(check.passed());
+                totals[index].2 += 1;
+            }
+        }
+        totals
+    }
+
     /// Get checks by category
     pub fn checks_by_category(&self) -> std::collections::HashMap<String, Vec<&CheckResult>> {
         let mut map = std::collections::HashMap::new();
@@ -241,48 +615,136 @@ pub fn checks_by_category(&self) -> std::collections::HashMap<String, Vec<&Check
         }
         map
     }
+
+    /// Fold another report of the same repository into this one
+    ///
+    /// For aggregating several scans or tools:
+    ///
+    /// - checks are deduplicated by ID and the worst result of a check that
+    ///   ran wins (fail or error, then waived, then pass); a skipped or not
+    ///   applicable entry only stands when neither report ran the check. On
+    ///   a tie this report's entry is kept, and checks only `other` has are
+    ///   appended in its order
+    /// - warnings, waivers and expired waivers are combined without
+    ///   duplicates; a check skipped by one report but run by the other
+    ///   counts as run
+    /// - `verified_at` becomes the earlier time, since the result is only as
+    ///   fresh as its oldest part; durations add up
+    /// - the newer standard version is kept; the repository path is this
+    ///   report's
+    /// - metadata keys only `other` sets are added; this report's values win
+    pub fn merge(&mut self, other: ComplianceReport) {
+        /// Lower wins; checks that did not run lose to any that did
+        fn rank(check: &CheckResult) -> u8 {
+            match check.status {
+                _ if check.waived => 1,
+                CheckStatus::Fail | CheckStatus::Error => 0,
+                CheckStatus::Pass => 2,
+                CheckStatus::Skipped => 3,
+                CheckStatus::NotApplicable => 4,
+            }
+        }
+
+        for check in other.checks {
+            match self.checks.iter_mut().find(|c| c.id == check.id) {
+                Some(existing) if rank(&check) < rank(existing) => *existing = check,
+                Some(_) => {}
+                None => self.checks.push(check),
+            }
+        }
+        for warning in other.warnings {
+            let duplicate = self.warnings.iter().any(|w| {
+                w.level == warning.level && w.message == warning.message && w.path == warning.path
+            });
+            if !duplicate {
+                self.warnings.push(warning);
+            }
+        }
+        for id in other.skipped_checks {
+            if !self.skipped_checks.contains(&id) {
+                self.skipped_checks.push(id);
+            }
+        }
+        let checks = &self.checks;
+        self.skipped_checks.retain(|id| !checks.iter().any(|c| &c.id == id));
+        for (mine, theirs) in [
+            (&mut self.waivers, other.waivers),
+            (&mut self.expired_waivers, other.expired_waivers),
+        ] {
+            for waiver in theirs {
+                if !mine.contains(&waiver) {
+                    mine.push(waiver);
+                }
+            }
+        }
+        self.verified_at = self.verified_at.min(other.verified_at);
+        self.duration += other.duration;
+        self.standard_version = self.standard_version.max(other.standard_version);
+        self.reproducible |= other.reproducible;
+        for (key, value) in other.metadata.entries() {
+            if self.metadata.get(key).is_none() {
+                self.metadata.set(key, value);
+            }
+        }
+    }
 }
 
 /// Result of checking a path for existence and symlink status
 struct PathCheckResult {
     exists: bool,
     is_symlink: bool,
+    /// The link is an NTFS junction (Windows only)
+    is_junction: bool,
     escapes_repo: bool,
+    /// The link leaves the repository, but into an allowed target directory
+    allowed_target: bool,
     target: Option<PathBuf>,
 }
 
 /// Check if a path is a symlink and if it escapes the repository root
-fn check_path_security(path: &Path, repo_root: &Path) -> PathCheckResult {
-    let metadata = match fs::symlink_metadata(path) {
+///
+/// Links into one of `allowed_targets` (absolute, or relative to the
+/// repository root) do not count as escaping.
+fn check_path_security(
+    fs: &dyn FileSystem,
+    path: &Path,
+    repo_root: &Path,
+    allowed_targets: &[String],
+) -> PathCheckResult {
+    let metadata = match fs.symlink_metadata(path) {
         Ok(m) => m,
         Err(_) => {
             return PathCheckResult {
                 exists: false,
                 is_symlink: false,
+                is_junction: false,
                 escapes_repo: false,
+                allowed_target: false,
                 target: None,
             }
         }
     };
 
-    let is_symlink = metadata.file_type().is_symlink();
-
-    if !is_symlink {
+    if !metadata.is_symlink() {
         return PathCheckResult {
             exists: true,
             is_symlink: false,
+            is_junction: false,
             escapes_repo: false,
+            allowed_target: false,
             target: None,
         };
     }
 
-    let target = match fs::read_link(path) {
+    let target = match fs.read_link(path) {
         Ok(t) => t,
         Err(_) => {
             return PathCheckResult {
                 exists: true,
                 is_symlink: true,
+                is_junction: false,
                 escapes_repo: false,
+                allowed_target: false,
                 target: None,
             };
         }
@@ -296,310 +758,1367 @@ fn check_path_security(path: &Path, repo_root: &Path) -> PathCheckResult {
             .unwrap_or(target.clone())
     };
 
-    let canonical_root = repo_root
-        .canonicalize()
+    let canonical_root = fs
+        .canonicalize(repo_root)
         .unwrap_or_else(|_| repo_root.to_path_buf());
-    let canonical_target = resolved_target
-        .canonicalize()
+    let canonical_target = fs
+        .canonicalize(&resolved_target)
         .unwrap_or_else(|_| resolved_target.clone());
 
-    let escapes_repo = !canonical_target.starts_with(canonical_root);
+    let inside = |dir: &Path| paths::path_starts_with(&canonical_target, dir);
+    let escapes_repo = !inside(&canonical_root);
+    let allowed_target = escapes_repo
+        && allowed_targets.iter().any(|prefix| {
+            let dir = canonical_root.join(prefix);
+            inside(&fs.canonicalize(&dir).unwrap_or(dir))
+        });
 
     PathCheckResult {
         exists: true,
         is_symlink: true,
-        escapes_repo,
-        target: Some(resolved_target),
+        is_junction: metadata.is_junction,
+        escapes_repo: escapes_repo && !allowed_target,
+        allowed_target,
+        target: Some(paths::normalize(&resolved_target)),
     }
 }
 
 /// Check if a file exists at the given path (with symlink detection)
-fn check_file(base: &Path, filename: &str, report: &mut ComplianceReport) -> bool {
-    let path = base.join(filename);
-    let security = check_path_security(&path, &report.repository_path);
-
-    if security.is_symlink {
-        if security.escapes_repo {
-            report.add_warning(
-                WarningLevel::Critical,
-                &format!(
-                    "Symlink '{}' points outside repository to '{}'",
-                    filename,
-                    security
-                        .target
-                        .as_ref()
-                        .map(|p| p.display().to_string())
-                        .unwrap_or_default()
-                ),
-                Some(path.clone()),
-            );
-        } else {
-            report.add_warning(
-                WarningLevel::Info,
-                &format!("'{}' is a symlink (within repository bounds)", filename),
-                Some(path.clone()),
-            );
-        }
+fn check_file(v: &Verification, filename: &str, report: &mut ComplianceReport) -> bool {
+    let filename = &v.resolve(filename);
+    let path = v.repo_path.join(filename);
+    let allowed = &v.config.security.allow_symlink_targets;
+    let security = check_path_security(v.fs, &path, &report.repository_path, allowed);
+    let target = security
+        .target
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+
+    if security.escapes_repo {
+        report.add_warning(
+            WarningLevel::Critical,
+            &format!("Symlink '{}' points outside repository to '{}'", filename, target),
+            Some(path.clone()),
+        );
+    } else if security.allowed_target {
+        report.add_warning(
+            WarningLevel::Info,
+            &format!("'{}' is a symlink to allowed target '{}'", filename, target),
+            Some(path.clone()),
+        );
+    } else if security.is_symlink {
+        report.add_warning(
+            WarningLevel::Info,
+            &format!("'{}' is a symlink (within repository bounds)", filename),
+            Some(path.clone()),
+        );
     }
 
-    security.exists && path.is_file()
+    security.exists && v.fs.is_file(&path)
 }
 
 /// Check if a directory exists at the given path (with symlink detection)
-fn check_dir(base: &Path, dirname: &str, report: &mut ComplianceReport) -> bool {
-    let path = base.join(dirname);
-    let security = check_path_security(&path, &report.repository_path);
-
-    if security.is_symlink {
-        if security.escapes_repo {
-            report.add_warning(
-                WarningLevel::Critical,
-                &format!(
-                    "Symlink directory '{}' points outside repository to '{}'",
-                    dirname,
-                    security
-                        .target
-                        .as_ref()
-                        .map(|p| p.display().to_string())
-                        .unwrap_or_default()
-                ),
-                Some(path.clone()),
-            );
-        } else {
-            report.add_warning(
-                WarningLevel::Info,
-                &format!(
-                    "'{}' is a symlink directory (within repository bounds)",
-                    dirname
-                ),
-                Some(path.clone()),
-            );
-        }
+fn check_dir(v: &Verification, dirname: &str, report: &mut ComplianceReport) -> bool {
+    let dirname = &v.resolve(dirname);
+    let path = v.repo_path.join(dirname);
+    let allowed = &v.config.security.allow_symlink_targets;
+    let security = check_path_security(v.fs, &path, &report.repository_path, allowed);
+    let target = security
+        .target
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+
+    let kind = if security.is_junction {
+        "Junction"
+    } else {
+        "Symlink directory"
+    };
+    if security.escapes_repo {
+        report.add_warning(
+            WarningLevel::Critical,
+            &format!("{} '{}' points outside repository to '{}'", kind, dirname, target),
+            Some(path.clone()),
+        );
+    } else if security.allowed_target {
+        report.add_warning(
+            WarningLevel::Info,
+            &format!(
+                "'{}' is a {} to allowed target '{}'",
+                dirname,
+                kind.to_lowercase(),
+                target
+            ),
+            Some(path.clone()),
+        );
+    } else if security.is_symlink {
+        report.add_warning(
+            WarningLevel::Info,
+            &format!(
+                "'{}' is a {} (within repository bounds)",
+                dirname,
+                kind.to_lowercase()
+            ),
+            Some(path.clone()),
+        );
     }
 
-    security.exists && path.is_dir()
+    security.exists && v.fs.is_dir(&path)
 }
 
-/// Verify documentation files exist
-fn check_documentation(report: &mut ComplianceReport, repo_path: &Path) {
-    // README can be either .md or .adoc (AsciiDoc is acceptable alternative)
-    let readme_md = check_file(repo_path, "README.md", report);
-    let readme_adoc = if !readme_md {
-        check_file(repo_path, "README.adoc", report)
-    } else {
-        false
-    };
-    report.add_check(
-        "Documentation",
-        "README.md",
-        readme_md || readme_adoc,
-        ComplianceLevel::Bronze,
-    );
+/// State shared by every check during one verification run
+struct Verification<'a> {
+    repo_path: &'a Path,
+    config: &'a Config,
+    hooks: &'a Hooks<'a>,
+    /// Every filesystem access goes through this
+    fs: &'a dyn FileSystem,
+    /// Number of report warnings already passed to the logger
+    logged_warnings: Cell<usize>,
+    /// Number of checks finished or skipped so far
+    completed: Cell<usize>,
+    /// When the run started, for its time budget
+    started: Instant,
+    /// When the running check started, for its time budget
+    check_started: Cell<Instant>,
+    /// Check whose failure stopped a `fail_fast` run
+    stopped_by: RefCell<Option<String>>,
+    /// IDs of checks with an active waiver, whose failures do not stop a
+    /// `fail_fast` run
+    waived: Vec<String>,
+    /// Directory holding `.well-known/`, empty for the repository root
+    web_root: String,
+}
 
-    let other_required_docs = vec![
-        "LICENSE.txt",
-        "SECURITY.md",
-        "CONTRIBUTING.md",
-        "CODE_OF_CONDUCT.md",
-        "MAINTAINERS.md",
-        "CHANGELOG.md",
-    ];
+impl Verification<'_> {
+    /// Spelling of the repository-relative `path` as it is on disk
+    ///
+    /// Each component is looked up in its directory's listing, which the
+    /// run's [`vfs::FsCache`] reads once, instead of being stat'ed by name.
+    /// An exact match wins; otherwise a name that differs only in ASCII case
+    /// is used, so `Readme.md` satisfies `README.md` the same way on
+    /// case-sensitive and case-insensitive filesystems. Components not found
+    /// are kept as given.
+    fn resolve(&self, path: &str) -> String {
+        let mut dir = self.repo_path.to_path_buf();
+        let mut parts = Vec::new();
+        let mut found = true;
+        for part in path.split('/').filter(|part| !part.is_empty()) {
+            let name = if found { self.lookup(&dir, part) } else { None };
+            found = name.is_some();
+            let name = name.unwrap_or_else(|| part.to_string());
+            dir.push(&name);
+            parts.push(name);
+        }
+        let mut resolved = parts.join("/");
+        if path.ends_with('/') {
+            resolved.push('/');
+        }
+        resolved
+    }
 
-    for doc in other_required_docs {
-        let exists = check_file(repo_path, doc, report);
-        report.add_check("Documentation", doc, exists, ComplianceLevel::Bronze);
+    /// Entry of `dir` named `name`, exactly or ignoring ASCII case
+    fn lookup(&self, dir: &Path, name: &str) -> Option<String> {
+        let names: Vec<String> = self
+            .fs
+            .read_dir(dir)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .collect();
+        names
+            .iter()
+            .find(|entry| *entry == name)
+            .or_else(|| names.iter().find(|entry| entry.eq_ignore_ascii_case(name)))
+            .cloned()
     }
-}
 
-/// Verify .well-known directory and required files
-fn check_well_known(report: &mut ComplianceReport, repo_path: &Path) {
-    let has_dir = check_dir(repo_path, ".well-known", report);
-
-    report.add_check(
-        "Well-Known",
-        ".well-known/ directory",
-        has_dir,
-        ComplianceLevel::Bronze,
-    );
+    /// Count a finished check and notify the progress sink
+    fn advance(&self, spec: &CheckSpec) {
+        self.completed.set(self.completed.get() + 1);
+        self.hooks.progress.update(&Progress {
+            completed: self.completed.get(),
+            total: self.config.standard_version.checks().len(),
+            category: spec.category,
+            check_id: spec.id,
+        });
+    }
 
-    let well_known_path = repo_path.join(".well-known");
-    let required_files = vec!["security.txt", "ai.txt", "humans.txt"];
-    for file in required_files {
-        let exists = if has_dir {
-            check_file(&well_known_path, file, report)
-        } else {
-            false
-        };
-        report.add_check("Well-Known", file, exists, ComplianceLevel::Bronze);
+    /// Record the result of `id`, ending a `fail_fast` run if it is a
+    /// failure at or below the target level that no waiver covers
+    fn note_result(&self, id: &str, level: ComplianceLevel, status: CheckStatus) {
+        let stops = self.config.checks.fail_fast
+            && status != CheckStatus::Pass
+            && level <= self.config.policy.fail_on_level
+            && !self.waived.iter().any(|waived| waived == id);
+        if stops && self.stopped_by.borrow().is_none() {
+            self.hooks
+                .logger
+                .log(LogLevel::Info, &format!("stop after {} failed (fail fast)", id));
+            *self.stopped_by.borrow_mut() = Some(id.to_string());
+        }
     }
-}
 
-/// Verify build system files
-fn check_build_system(report: &mut ComplianceReport, repo_path: &Path) {
-    let build_files = vec![
-        ("justfile", ComplianceLevel::Bronze),
-        ("flake.nix", ComplianceLevel::Bronze),
-        (".gitlab-ci.yml", ComplianceLevel::Bronze),
-    ];
+    /// Why no further check may start, once the run is over its time budget
+    fn out_of_time(&self) -> Option<String> {
+        let budget = self.config.limits.run_timeout;
+        (self.started.elapsed() > budget).then(|| {
+            format!(
+                "not run: verification exceeded its {}s time budget",
+                budget.as_secs_f64()
+            )
+        })
+    }
+
+    /// Time the running check has left before its own or the run's budget
+    /// is spent; external commands it runs are killed after this long
+    fn time_left(&self) -> Duration {
+        let limits = &self.config.limits;
+        let check = limits.check_timeout.saturating_sub(self.check_started.get().elapsed());
+        let run = limits.run_timeout.saturating_sub(self.started.elapsed());
+        check.min(run)
+    }
 
-    for (file, level) in build_files {
-        let exists = check_file(repo_path, file, report);
-        report.add_check("Build System", file, exists, level);
+    /// Log security warnings added to the report since the last call
+    fn log_new_warnings(&self, report: &ComplianceReport) {
+        for warning in &report.warnings[self.logged_warnings.get()..] {
+            let level = match warning.level {
+                WarningLevel::Info => LogLevel::Info,
+                WarningLevel::Warning => LogLevel::Warn,
+                WarningLevel::Critical => LogLevel::Error,
+            };
+            self.hooks.logger.log(level, &format!("security: {}", warning.message));
+        }
+        self.logged_warnings.set(report.warnings.len());
     }
 }
 
-/// Verify source code structure
-fn check_source_structure(report: &mut ComplianceReport, repo_path: &Path) {
-    let has_src = check_dir(repo_path, "src", report);
-    let has_tests = check_dir(repo_path, "tests", report) || check_dir(repo_path, "test", report);
-
-    report.add_check(
-        "Source Structure",
-        "src/ directory",
-        has_src,
-        ComplianceLevel::Bronze,
+/// Run a built-in check and record its result, unless disabled by config
+fn run_check(
+    report: &mut ComplianceReport,
+    v: &Verification,
+    id: &str,
+    check: impl FnOnce(&mut ComplianceReport) -> bool,
+) {
+    // Checks that are not part of the selected standard version are not run
+    let Some(spec) = v.config.standard_version.lookup(id) else {
+        return;
+    };
+    let logger = v.hooks.logger;
+    if v.config.checks.skips(spec) {
+        logger.log(LogLevel::Debug, &format!("skip {} (not selected)", id));
+        report.skipped_checks.push(id.to_string());
+        v.advance(spec);
+        return;
+    }
+    let kind = v.config.repository.kind;
+    if !kind.applies_to(spec.category) {
+        logger.log(
+            LogLevel::Debug,
+            &format!("skip {} (does not apply to {} repositories)", id, kind),
+        );
+        report.add_spec_check(spec, false);
+        if let Some(result) = report.checks.last_mut() {
+            result.status = CheckStatus::NotApplicable;
+            result.description = Some(format!(
+                "{} (does not apply to {} repositories)",
+                spec.description, kind
+            ));
+        }
+        v.advance(spec);
+        return;
+    }
+    if v.stopped_by.borrow().is_some() {
+        logger.log(LogLevel::Debug, &format!("skip {} ({})", id, FAIL_FAST_REASON));
+        report.add_spec_check(spec, false);
+        if let Some(result) = report.checks.last_mut() {
+            result.status = CheckStatus::Skipped;
+            result.description = Some(format!("{} ({})", spec.description, FAIL_FAST_REASON));
+        }
+        v.advance(spec);
+        return;
+    }
+    let failed = |id: &str| report.checks.iter().any(|c| c.id == id && !c.passed());
+    if let Some(prerequisite) = catalog::prerequisites(id).find(|p| failed(p)) {
+        logger.log(
+            LogLevel::Debug,
+            &format!("skip {} (prerequisite {} failed)", id, prerequisite),
+        );
+        report.add_spec_check(spec, false);
+        if let Some(result) = report.checks.last_mut() {
+            result.status = CheckStatus::Skipped;
+            result.blocked_by = Some(prerequisite.to_string());
+        }
+        v.advance(spec);
+        return;
+    }
+    if let Some(reason) = v.out_of_time() {
+        logger.log(LogLevel::Warn, &format!("{} {}", id, reason));
+        report.add_spec_check(spec, false);
+        if let Some(result) = report.checks.last_mut() {
+            result.status = CheckStatus::Error;
+            result.description = Some(format!("{} ({})", spec.description, reason));
+        }
+        v.advance(spec);
+        return;
+    }
+    logger.log(
+        LogLevel::Debug,
+        &format!("start {} ({}: {})", id, spec.category, spec.item),
     );
+    let started = Instant::now();
+    v.check_started.set(started);
+    // A panicking check is reported as an error instead of ending the run
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| check(report)));
+    let duration = started.elapsed();
+    v.log_new_warnings(report);
+    let budget = v.config.limits.check_timeout;
+    let error = match outcome {
+        Ok(_) if duration > budget => Some(format!(
+            "took {:.1}s, over its {}s time budget",
+            duration.as_secs_f64(),
+            budget.as_secs_f64()
+        )),
+        Ok(_) => None,
+        Err(ref payload) => Some(format!(
+            "could not be evaluated: {}",
+            payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown cause".to_string())
+        )),
+    };
+    let status = match outcome {
+        Ok(passed) if error.is_none() => CheckStatus::from(passed),
+        _ => CheckStatus::Error,
+    };
+    logger.log(LogLevel::Info, &format!("finish {}: {}", id, status));
+    v.note_result(id, spec.level, status);
+    report.add_spec_check(spec, false);
+    if let Some(result) = report.checks.last_mut() {
+        result.status = status;
+        result.duration = duration;
+        if let Some(error) = error {
+            result.description = Some(format!("{} ({})", spec.description, error));
+        }
+    }
+    v.advance(spec);
+}
 
-    report.add_check(
-        "Source Structure",
-        "tests/ directory",
-        has_tests,
-        ComplianceLevel::Bronze,
-    );
+/// Read a repository file for content validation
+///
+/// Files that are missing, unreadable or links pointing outside the
+/// repository (which are never read) yield a problem instead.
+fn read_repo_file(v: &Verification, relative_path: &str) -> Result<String, content::Problem> {
+    let relative_path = &v.resolve(relative_path);
+    let path = v.repo_path.join(relative_path);
+    let allowed = &v.config.security.allow_symlink_targets;
+    let security = check_path_security(v.fs, &path, v.repo_path, allowed);
+    if !security.exists {
+        return Err(content::Problem::file(format!("{} not found", relative_path)));
+    }
+    if security.escapes_repo {
+        return Err(content::Problem::file(format!(
+            "{} points outside the repository and was not read",
+            relative_path
+        )));
+    }
+    v.fs
+        .read_to_string(&path)
+        .map_err(|e| content::Problem::file(format!("cannot read {}: {}", relative_path, e)))
 }
 
-/// Run all compliance checks on a repository
-pub fn verify_repository(repo_path: &Path) -> ComplianceReport {
-    let mut report = ComplianceReport::new(repo_path.to_path_buf());
+/// Run a built-in check that validates the content of repository files
+///
+/// The first of `relative_paths`, or else one of the check's configured
+/// alternates, must exist; any others that exist are validated together
+/// with it (e.g. a short `CONTRIBUTING.md` pointing at a full
+/// `CONTRIBUTING.adoc`). The check fails if no such file can be read or
+/// `validate` reports problems, which are appended to the check's
+/// description.
+fn run_content_check(
+    report: &mut ComplianceReport,
+    v: &Verification,
+    id: &str,
+    relative_paths: &[&str],
+    validate: content::Validator,
+) {
+    let mut problems = Vec::new();
+    run_check(report, v, id, |_| {
+        let mut first = read_repo_file(v, relative_paths[0]);
+        for alternate in v.config.checks.alternates(id) {
+            if first.is_err() {
+                first = read_repo_file(v, alternate).or(first);
+            }
+        }
+        problems = match first {
+            Ok(mut text) => {
+                for extra in &relative_paths[1..] {
+                    if let Ok(more) = read_repo_file(v, extra) {
+                        text.push('\n');
+                        text.push_str(&more);
+                    }
+                }
+                validate(&text)
+            }
+            Err(problem) => vec![problem],
+        };
+        problems.is_empty()
+    });
 
-    check_documentation(&mut report, repo_path);
-    check_well_known(&mut report, repo_path);
-    check_build_system(&mut report, repo_path);
-    check_source_structure(&mut report, repo_path);
+    let details: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+    append_problems(report, id, &details);
+}
 
-    report
+/// Append content problems to the description of the check just recorded
+fn append_problems(report: &mut ComplianceReport, id: &str, details: &[String]) {
+    if details.is_empty() {
+        return;
+    }
+    if let Some(result) = report.checks.last_mut().filter(|c| c.id == id) {
+        let description = result.description.take().unwrap_or_default();
+        result.description = Some(format!(
+            "{} (problems: {})",
+            description,
+            details.join("; ")
+        ));
+    }
 }
 
-/// Format a SystemTime as a human-readable timestamp (ISO 8601)
-pub fn format_timestamp(time: SystemTime) -> String {
-    match time.duration_since(SystemTime::UNIX_EPOCH) {
-        Ok(duration) => {
-            let secs = duration.as_secs();
-            let days = secs / 86400;
-            let time_secs = secs % 86400;
-            let hours = time_secs / 3600;
-            let minutes = (time_secs % 3600) / 60;
-            let seconds = time_secs % 60;
-
-            let mut year = 1970;
-            let mut remaining_days = days;
-
-            loop {
-                let days_in_year = if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) {
-                    366
-                } else {
-                    365
-                };
-                if remaining_days < days_in_year {
-                    break;
-                }
-                remaining_days -= days_in_year;
-                year += 1;
-            }
+/// Directory holding GitHub Actions workflows
+const GITHUB_WORKFLOWS_DIR: &str = ".github/workflows";
 
-            let is_leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
-            let days_in_months: [u64; 12] = if is_leap {
-                [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-            } else {
-                [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-            };
+/// Validate the content of every CI configuration in the repository
+fn check_ci_content(report: &mut ComplianceReport, v: &Verification) {
+    let mut details = Vec::new();
+    run_check(report, v, "RSR-BLD-004", |_| {
+        let mut configs: Vec<(String, content::Validator)> = Vec::new();
+        if v.fs.is_file(&v.repo_path.join(".gitlab-ci.yml")) {
+            configs.push((".gitlab-ci.yml".to_string(), content::validate_gitlab_ci));
+        }
+        let workflows = v.fs.read_dir(&v.repo_path.join(GITHUB_WORKFLOWS_DIR)).unwrap_or_default();
+        for path in workflows {
+            let is_yaml = matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("yml") | Some("yaml")
+            );
+            if let (true, Some(name)) = (is_yaml, path.file_name()) {
+                let relative = format!("{}/{}", GITHUB_WORKFLOWS_DIR, name.to_string_lossy());
+                configs.push((relative, content::validate_github_workflow));
+            }
+        }
+        if configs.is_empty() {
+            details.push("no CI configuration found".to_string());
+            return false;
+        }
 
-            let mut month = 1;
-            for days_in_month in days_in_months.iter() {
-                if remaining_days < *days_in_month {
-                    break;
+        let mut runs_tests = false;
+        for (relative, validate) in configs {
+            match read_repo_file(v, &relative) {
+                Ok(text) => {
+                    runs_tests |= content::has_test_step(&text);
+                    details.extend(
+                        validate(&text)
+                            .iter()
+                            .map(|problem| format!("{} {}", relative, problem)),
+                    );
                 }
-                remaining_days -= days_in_month;
-                month += 1;
+                Err(problem) => details.push(problem.to_string()),
             }
-            let day = remaining_days + 1;
-
-            format!(
-                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
-                year, month, day, hours, minutes, seconds
-            )
         }
-        Err(_) => "unknown".to_string(),
-    }
+        if !runs_tests {
+            details.push("no CI job runs the tests".to_string());
+        }
+        details.is_empty()
+    });
+    append_problems(report, "RSR-BLD-004", &details);
 }
 
-/// Escape a string for JSON output
-pub fn json_escape(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    for c in s.chars() {
-        match c {
-            '"' => result.push_str("\\\""),
-            '\\' => result.push_str("\\\\"),
-            '\n' => result.push_str("\\n"),
-            '\r' => result.push_str("\\r"),
-            '\t' => result.push_str("\\t"),
-            c if c.is_control() => {
-                result.push_str(&format!("\\u{:04x}", c as u32));
-            }
-            c => result.push(c),
-        }
-    }
-    result
+/// Paths a built-in check looks at, as defined by the RSR standard
+fn spec_paths(id: &str) -> &'static [&'static str] {
+    catalog::lookup(id)
+        .expect("built-in check missing from catalog")
+        .paths
 }
 
-/// Bot action types for CI/CD integration
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum BotAction {
-    /// Check compliance and report
-    Check,
-    /// Check compliance and create PR with fixes
-    Fix,
-    /// Generate badge for README
-    Badge,
-    /// Generate conformity document
-    Conformity,
+/// Names a built-in check accepts: the standard's, then configured alternates
+fn accepted_paths<'a>(v: &'a Verification, id: &str) -> impl Iterator<Item = &'a str> {
+    let alternates = v.config.checks.alternates(id).iter().map(String::as_str);
+    spec_paths(id).iter().copied().chain(alternates)
 }
 
-/// Bot configuration
-#[derive(Debug, Clone)]
-pub struct BotConfig {
-    pub action: BotAction,
-    pub create_pr: bool,
-    pub fail_on_warning: bool,
-    pub target_level: ComplianceLevel,
+/// The first of a built-in check's files that exists
+fn find_spec_file<'a>(
+    v: &'a Verification,
+    id: &str,
+    report: &mut ComplianceReport,
+) -> Option<&'a str> {
+    accepted_paths(v, id).find(|file| check_file(v, file, report))
 }
 
-impl Default for BotConfig {
-    fn default() -> Self {
-        Self {
-            action: BotAction::Check,
-            create_pr: false,
-            fail_on_warning: false,
-            target_level: ComplianceLevel::Bronze,
+/// Check that one of a built-in check's files exists
+fn check_spec_files(v: &Verification, id: &str, report: &mut ComplianceReport) -> bool {
+    find_spec_file(v, id, report).is_some()
+}
+
+/// Lowest share of common words at which two README renderings agree
+const MIN_README_SIMILARITY: f64 = 0.5;
+
+/// License file names compared with each other
+const LICENSE_VARIANTS: &[&str] = &["LICENSE", "LICENSE.txt", "LICENSE.md"];
+
+/// Read every one of `names` that exists, once per spelling on disk
+fn read_variants(v: &Verification, names: &[&str]) -> Vec<(String, String)> {
+    let mut found: Vec<(String, String)> = Vec::new();
+    for name in names {
+        let resolved = v.resolve(name);
+        if found.iter().any(|(seen, _)| *seen == resolved) {
+            continue;
+        }
+        if let Ok(text) = read_repo_file(v, &resolved) {
+            found.push((resolved, text));
         }
     }
+    found
 }
 
-/// Generate RSR badge markdown
-pub fn generate_badge(level: ComplianceLevel) -> String {
-    format!(
-        "[![Rhodium Standard {}](https://img.shields.io/badge/RSR-{}-{})](https://github.com/hyperpolymath/rhodium-standard-repositories)",
-        level.display_name(),
-        level.display_name(),
-        level.badge_color()
-    )
+/// Warn about links in the main documentation files that break the link
+/// policy, or with `online` are not live (see [`links`])
+fn check_links(report: &mut ComplianceReport, v: &Verification) {
+    let config = &v.config.links;
+    if !config.audit
+        || v.stopped_by.borrow().is_some()
+        || !v.config.checks.selects_category("Documentation")
+    {
+        return;
+    }
+    // The audit has the budget of one check for all of its requests
+    v.check_started.set(Instant::now());
+    let mut probed: Vec<String> = Vec::new();
+    for (name, text) in read_variants(v, links::FILES) {
+        for link in links::extract(&text) {
+            if config.allows(&link.url) {
+                continue;
+            }
+            let mut problem = links::problem(&link.url);
+            let probes = config.online && links::is_probed(&link.url);
+            if problem.is_none() && probes && !probed.contains(&link.url) {
+                probed.push(link.url.clone());
+                let timeout = v.time_left().min(links::PROBE_TIMEOUT);
+                if v.out_of_time().is_none() && !timeout.is_zero() {
+                    v.hooks.logger.log(LogLevel::Debug, &format!("request {}", link.url));
+                    problem = links::probe(&link.url, timeout)
+                        .err()
+                        .map(|reason| format!("is not live ({})", reason));
+                }
+            }
+            if let Some(problem) = problem {
+                report.add_warning(
+                    WarningLevel::Warning,
+                    &format!("{} line {}: '{}' {}", name, link.line, link.url, problem),
+                    Some(v.repo_path.join(&name)),
+                );
+            }
+        }
+    }
 }
 
-/// Generate RSR conformity document
-pub fn generate_conformity_doc(report: &ComplianceReport) -> String {
-    let level = report.highest_level();
+/// Warn about contacts in security.txt, humans.txt and MAINTAINERS.md that
+/// are malformed or placeholders (see [`contacts`])
+fn check_contacts(report: &mut ComplianceReport, v: &Verification) {
+    if v.stopped_by.borrow().is_some() {
+        return;
+    }
+    let files: [(String, &str, content::Validator); 3] = [
+        (
+            site::join(&v.web_root, ".well-known/security.txt"),
+            "Well-Known",
+            contacts::validate_security_txt,
+        ),
+        (
+            site::join(&v.web_root, ".well-known/humans.txt"),
+            "Well-Known",
+            contacts::validate_contacts,
+        ),
+        ("MAINTAINERS.md".to_string(), "Documentation", contacts::validate_contacts),
+    ];
+    for (path, category, validate) in files {
+        if !v.config.checks.selects_category(category) {
+            continue;
+        }
+        let Ok(text) = read_repo_file(v, &path) else {
+            continue;
+        };
+        let path = v.resolve(&path);
+        for problem in validate(&text) {
+            let message = match problem.line {
+                Some(line) => format!("{} line {}: {}", path, line, problem.message),
+                None => format!("{}: {}", path, problem.message),
+            };
+            report.add_warning(WarningLevel::Warning, &message, Some(v.repo_path.join(&path)));
+        }
+    }
+}
+
+/// Warn about copies of the README or the license that contradict each other
+///
+/// Both `README.md` and `README.adoc` may exist, as may several license
+/// files, but when they say different things nobody can tell which one the
+/// compliance claim rests on. README renderings conflict when they share
+/// fewer than half their words and neither names the other (a short one
+/// pointing at the full one is fine); license files conflict when the
+/// licenses detected in them differ.
+fn check_conflicting_copies(report: &mut ComplianceReport, v: &Verification) {
+    if v.stopped_by.borrow().is_some() {
+        return;
+    }
+    let runs = |id: &str| catalog::lookup(id).map_or(false, |spec| !v.config.checks.skips(spec));
+    if runs("RSR-DOC-001") {
+        let readmes = read_variants(v, spec_paths("RSR-DOC-001"));
+        for (i, (first, first_text)) in readmes.iter().enumerate() {
+            for (second, second_text) in &readmes[i + 1..] {
+                let points_at_other = first_text.contains(second.as_str())
+                    || second_text.contains(first.as_str());
+                let similarity = content::word_similarity(first_text, second_text);
+                if !points_at_other && similarity < MIN_README_SIMILARITY {
+                    report.add_warning(
+                        WarningLevel::Warning,
+                        &format!(
+                            "'{}' and '{}' diverge ({:.0}% of their words in common); \
+                             keep one, or make one point to the other",
+                            first,
+                            second,
+                            similarity * 100.0
+                        ),
+                        Some(v.repo_path.join(second)),
+                    );
+                }
+            }
+        }
+    }
+    if runs("RSR-DOC-002") {
+        let licenses: Vec<(String, String)> = read_variants(v, LICENSE_VARIANTS)
+            .into_iter()
+            .filter_map(|(name, text)| content::detect_license(&text).map(|id| (name, id)))
+            .collect();
+        if licenses.iter().any(|(_, id)| *id != licenses[0].1) {
+            let detected: Vec<String> =
+                licenses.iter().map(|(name, id)| format!("'{}' is {}", name, id)).collect();
+            report.add_warning(
+                WarningLevel::Warning,
+                &format!("License files disagree: {}", detected.join(", ")),
+                Some(v.repo_path.join(&licenses[0].0)),
+            );
+        }
+    }
+}
+
+/// Problems with a required documentation file's substance: too short, or
+/// only placeholder text (`[documentation]` in the configuration), or a
+/// starter file from `rhodibot fix` still to be filled in
+fn documentation_problems(v: &Verification, text: &str) -> Vec<content::Problem> {
+    let limits = &v.config.documentation;
+    let mut problems = content::validate_length(text, limits.min_bytes, limits.min_lines);
+    if limits.detect_placeholders && problems.is_empty() {
+        problems.extend(content::find_placeholder(text));
+    }
+    if problems.is_empty() {
+        problems.extend(content::find_stub(text));
+    }
+    problems
+}
+
+/// Verify documentation files exist and have content
+fn check_documentation(report: &mut ComplianceReport, v: &Verification) {
+    // README can be either .md or .adoc (AsciiDoc is acceptable alternative)
+    // and the catalog lists both
+    let required_docs = [
+        "RSR-DOC-001",
+        "RSR-DOC-002",
+        "RSR-DOC-003",
+        "RSR-DOC-004",
+        "RSR-DOC-005",
+        "RSR-DOC-006",
+        "RSR-DOC-007",
+    ];
+    for id in required_docs {
+        let mut problems = Vec::new();
+        run_check(report, v, id, |report| {
+            let Some(file) = find_spec_file(v, id, report) else {
+                return false;
+            };
+            // A file that cannot be read as text (or escapes the repository)
+            // is judged on its existence alone
+            if let Ok(text) = read_repo_file(v, file) {
+                problems = documentation_problems(v, &text);
+            }
+            problems.is_empty()
+        });
+        let details: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+        append_problems(report, id, &details);
+    }
+
+    run_content_check(
+        report,
+        v,
+        "RSR-DOC-008",
+        spec_paths("RSR-DOC-008"),
+        content::validate_contributing,
+    );
+    run_content_check(
+        report,
+        v,
+        "RSR-DOC-009",
+        spec_paths("RSR-DOC-009"),
+        content::validate_code_of_conduct,
+    );
+}
+
+/// Verify .well-known directory and required files
+///
+/// The standard's paths are looked up below the web root (see [`site`]);
+/// configured alternates stay relative to the repository root.
+fn check_well_known(report: &mut ComplianceReport, v: &Verification) {
+    let web_paths = |id: &str| -> Vec<String> {
+        let paths = spec_paths(id).iter().map(|path| site::join(&v.web_root, path));
+        paths.chain(v.config.checks.alternates(id).iter().cloned()).collect()
+    };
+    if !v.web_root.is_empty() {
+        v.hooks.logger.log(LogLevel::Debug, &format!("web root: {}/", v.web_root));
+    }
+    let has_dir = web_paths("RSR-WKN-001").iter().any(|dir| check_dir(v, dir, report));
+    run_check(report, v, "RSR-WKN-001", |_| has_dir);
+    if !has_dir && !v.web_root.is_empty() {
+        append_problems(report, "RSR-WKN-001", &[format!("web root: {}/", v.web_root)]);
+    }
+
+    for id in ["RSR-WKN-002", "RSR-WKN-003", "RSR-WKN-004"] {
+        let mut problems = Vec::new();
+        run_check(report, v, id, |report| {
+            let paths = web_paths(id);
+            let Some(file) = paths.iter().find(|file| check_file(v, file, report)) else {
+                return false;
+            };
+            // Starter files from `rhodibot fix` pass once filled in
+            if let Ok(text) = read_repo_file(v, file) {
+                problems.extend(content::find_stub(&text));
+            }
+            problems.is_empty()
+        });
+        let details: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+        append_problems(report, id, &details);
+    }
+
+    for (id, validate) in [
+        ("RSR-WKN-005", content::validate_ai_txt as content::Validator),
+        ("RSR-WKN-006", content::validate_humans_txt),
+    ] {
+        let paths: Vec<String> =
+            spec_paths(id).iter().map(|path| site::join(&v.web_root, path)).collect();
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+        run_content_check(report, v, id, &paths, validate);
+    }
+
+    // Entries the organization requires on top of the standard's
+    if !v.config.checks.selects_category("Well-Known") {
+        return;
+    }
+    for entry in &v.config.well_known {
+        let path = site::join(&v.web_root, &entry.path());
+        let present = has_dir && check_path(v, &path, entry.is_dir(), report);
+        report.add_check_with_desc(
+            "Well-Known",
+            &entry.name,
+            present,
+            entry.level,
+            &format!("{} is required by the repository's configuration", path),
+        );
+    }
+}
+
+/// Verify build system files
+fn check_build_system(report: &mut ComplianceReport, v: &Verification) {
+    for id in ["RSR-BLD-001", "RSR-BLD-002", "RSR-BLD-003"] {
+        run_check(report, v, id, |report| check_spec_files(v, id, report));
+    }
+    check_ci_content(report, v);
+}
+
+/// Verify source code structure
+///
+/// Sources and tests are looked for where the repository's [`Layout`] keeps
+/// them, e.g. `cmd/` or `*_test.go` files for Go. Under the `docs` layout
+/// neither check applies.
+fn check_source_structure(report: &mut ComplianceReport, v: &Verification) {
+    let layout = v
+        .config
+        .source
+        .layout
+        .unwrap_or_else(|| Layout::detect(v.fs, v.repo_path));
+    v.hooks.logger.log(LogLevel::Debug, &format!("source layout: {}", layout));
+    for id in ["RSR-SRC-001", "RSR-SRC-002"] {
+        let sources = id == "RSR-SRC-001";
+        let (dirs, accepted) = if sources {
+            (layout.source_dirs(), layout.describe_sources())
+        } else {
+            (layout.test_dirs(), layout.describe_tests())
+        };
+        let alternates = v.config.checks.alternates(id).iter().map(String::as_str);
+        let mut found = true;
+        run_check(report, v, id, |report| {
+            found = !layout.has_code()
+                || dirs.iter().copied().chain(alternates).any(|dir| check_dir(v, dir, report))
+                || if sources {
+                    layout.has_source_files(v.fs, v.repo_path)
+                } else {
+                    layout.has_test_files(v.fs, v.repo_path)
+                };
+            found
+        });
+        if !found {
+            append_problems(report, id, &[format!("{} layout: no {}", layout, accepted)]);
+        } else if !layout.has_code() {
+            let result = report.checks.last_mut().filter(|c| c.id == id);
+            if let Some(result) = result.filter(|c| c.status == CheckStatus::Pass) {
+                result.status = CheckStatus::NotApplicable;
+            }
+        }
+    }
+}
+
+/// Run the organization rules declared in the configuration
+fn check_custom_rules(report: &mut ComplianceReport, v: &Verification) {
+    for rule in &v.config.rules {
+        let id = rule.id();
+        if v.config.checks.is_disabled(&id) || !v.config.checks.selects_category(&rule.category) {
+            continue;
+        }
+        let kind = if rule.is_dir() { "directory" } else { "file" };
+        let mut description = rule
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("{} must exist ({})", rule.path, kind));
+        let (status, problems) = if v.stopped_by.borrow().is_some() {
+            description = format!("{} ({})", description, FAIL_FAST_REASON);
+            (CheckStatus::Skipped, Vec::new())
+        } else if let Some(reason) = v.out_of_time() {
+            description = format!("{} ({})", description, reason);
+            (CheckStatus::Error, Vec::new())
+        } else {
+            let present = check_path(v, rule.path.trim_end_matches('/'), rule.is_dir(), report);
+            let problems = match (present, rule.has_assertions()) {
+                (true, true) => match read_repo_file(v, &rule.path) {
+                    Ok(text) => rule.check_content(&text),
+                    Err(problem) => vec![problem.to_string()],
+                },
+                _ => Vec::new(),
+            };
+            let status = (present && problems.is_empty()).into();
+            v.note_result(&id, rule.level, status);
+            (status, problems)
+        };
+        report.checks.push(CheckResult {
+            id,
+            category: rule.category.clone(),
+            item: rule.path.clone(),
+            status,
+            required_for: rule.level,
+            description: Some(description),
+            waived: false,
+            duration: Duration::ZERO,
+            blocked_by: None,
+        });
+        append_problems(report, &rule.id(), &problems);
+    }
+}
+
+/// Check recent commit messages and the branch name, when enabled
+///
+/// Commits are read from loose objects, else with `git log` if allowed. A
+/// repository without `.git` has no history to judge, so the check does not
+/// apply; one whose history cannot be read is an error.
+fn check_version_control(report: &mut ComplianceReport, v: &Verification) {
+    let Some(spec) = v.config.standard_version.lookup(conventional::CHECK_ID) else {
+        return;
+    };
+    let vc = &v.config.version_control;
+    // The check is opt-in; until it is enabled it is not part of the report
+    if !vc.conventional_commits {
+        v.advance(spec);
+        return;
+    }
+    if v.config.checks.skips(spec) {
+        v.hooks
+            .logger
+            .log(LogLevel::Debug, &format!("skip {} (not selected)", spec.id));
+        report.skipped_checks.push(spec.id.to_string());
+        v.advance(spec);
+        return;
+    }
+    let mut description = format!(
+        "The last {} commit messages follow Conventional Commits and the branch is \
+         named type/description",
+        vc.commits
+    );
+    let mut problems = Vec::new();
+    let started = Instant::now();
+    v.check_started.set(started);
+    let status = if v.stopped_by.borrow().is_some() {
+        description = format!("{} ({})", description, FAIL_FAST_REASON);
+        CheckStatus::Skipped
+    } else if let Some(reason) = v.out_of_time() {
+        description = format!("{} ({})", description, reason);
+        CheckStatus::Error
+    } else if let Some(info) = vcs::read(v.fs, v.repo_path) {
+        let messages = match vcs::recent_commit_messages(v.fs, v.repo_path, vc.commits) {
+            Some(messages) => Ok(messages),
+            None if vc.allow_git => {
+                vcs::git_log_messages(v.repo_path, vc.commits, v.time_left())
+            }
+            None => Err("packed commits need git and allow_git = true".to_string()),
+        };
+        match messages {
+            Ok(messages) => {
+                problems = conventional::problems(&messages, info.branch.as_deref());
+                let status = problems.is_empty().into();
+                v.note_result(spec.id, spec.level, status);
+                status
+            }
+            Err(reason) => {
+                description =
+                    format!("{} (could not read the commit history: {})", description, reason);
+                CheckStatus::Error
+            }
+        }
+    } else {
+        description = format!("{} (not a git repository)", description);
+        CheckStatus::NotApplicable
+    };
+    report.add_spec_check(spec, false);
+    if let Some(result) = report.checks.last_mut() {
+        result.status = status;
+        result.description = Some(description);
+        result.duration = started.elapsed();
+    }
+    append_problems(report, spec.id, &problems);
+    v.advance(spec);
+}
+
+/// Check that a configured file, or directory if `is_dir`, exists
+fn check_path(
+    v: &Verification,
+    relative_path: &str,
+    is_dir: bool,
+    report: &mut ComplianceReport,
+) -> bool {
+    if is_dir {
+        check_dir(v, relative_path, report)
+    } else {
+        check_file(v, relative_path, report)
+    }
+}
+
+/// Compare the committed conformity document with the results so far
+///
+/// Runs after every other check, so all of them have a result to compare.
+/// Not applicable when no conformity document is committed; only drift from
+/// one that is fails.
+fn check_conformity(report: &mut ComplianceReport, v: &Verification) {
+    let mut details = Vec::new();
+    let mut committed = true;
+    run_check(report, v, conformity::DRIFT_CHECK, |report| {
+        committed = v.fs.exists(&v.repo_path.join(v.resolve(conformity::CONFORMITY_FILE)));
+        if committed {
+            details = match read_repo_file(v, conformity::CONFORMITY_FILE) {
+                Ok(doc) => conformity::drift(&doc, report),
+                Err(problem) => vec![problem.to_string()],
+            };
+        }
+        details.is_empty()
+    });
+    if committed {
+        append_problems(report, conformity::DRIFT_CHECK, &details);
+        return;
+    }
+    let result = report.checks.last_mut().filter(|c| c.id == conformity::DRIFT_CHECK);
+    if let Some(result) = result.filter(|c| c.status == CheckStatus::Pass) {
+        result.status = CheckStatus::NotApplicable;
+        if let Some(description) = &mut result.description {
+            description.push_str(&format!(" (no {} committed)", conformity::CONFORMITY_FILE));
+        }
+    }
+}
+
+/// Warn about files that satisfy a check but are not committed
+///
+/// Such a file passes here and fails in every fresh clone. Only done when
+/// the repository's git index can be read; the warning says whether the
+/// file is ignored or just not added yet.
+fn check_untracked(report: &mut ComplianceReport, v: &Verification) {
+    if v.stopped_by.borrow().is_some() {
+        return;
+    }
+    let Some(tracked) = vcs::tracked_paths(v.fs, v.repo_path) else {
+        return;
+    };
+    // Untracked path and the IDs of the checks it satisfies
+    let mut untracked: Vec<(&str, Vec<&str>)> = Vec::new();
+    for check in report.checks.iter().filter(|c| c.passed()) {
+        let Some(spec) = catalog::lookup(&check.id) else {
+            continue;
+        };
+        let present: Vec<&str> = spec
+            .paths
+            .iter()
+            .copied()
+            // git never tracks its own directory
+            .filter(|path| *path != ".git" && v.fs.exists(&v.repo_path.join(path)))
+            .collect();
+        if present.iter().any(|path| vcs::is_tracked(&tracked, path)) {
+            continue;
+        }
+        for path in present {
+            match untracked.iter_mut().find(|(p, _)| *p == path) {
+                Some((_, ids)) => ids.push(&check.id),
+                None => untracked.push((path, vec![&check.id])),
+            }
+        }
+    }
+    let warnings: Vec<(String, PathBuf)> = untracked
+        .into_iter()
+        .map(|(path, ids)| {
+            let state = if gitignore::is_ignored(v.fs, v.repo_path, path) {
+                "ignored by git"
+            } else {
+                "not tracked by git"
+            };
+            let message = format!(
+                "{} satisfies {} but is {}; fresh clones will fail",
+                path,
+                ids.join(", "),
+                state
+            );
+            (message, v.repo_path.join(path))
+        })
+        .collect();
+    for (message, path) in warnings {
+        report.add_warning(WarningLevel::Warning, &message, Some(path));
+    }
+}
+
+/// Run all compliance checks on a repository
+pub fn verify_repository(repo_path: &Path) -> ComplianceReport {
+    verify_repository_with_config(repo_path, &Config::default())
+}
+
+/// Run all compliance checks on a repository, honouring a configuration
+pub fn verify_repository_with_config(repo_path: &Path, config: &Config) -> ComplianceReport {
+    verify_repository_with_logger(repo_path, config, &NoopLogger)
+}
+
+/// Run all compliance checks, reporting check and security events to `logger`
+pub fn verify_repository_with_logger(
+    repo_path: &Path,
+    config: &Config,
+    logger: &dyn Logger,
+) -> ComplianceReport {
+    let hooks = Hooks {
+        logger,
+        ..Hooks::default()
+    };
+    verify_repository_with_hooks(repo_path, config, &hooks)
+}
+
+/// Observers notified while a repository is verified
+#[derive(Clone, Copy)]
+pub struct Hooks<'a> {
+    /// Receives check start/finish and security events
+    pub logger: &'a dyn Logger,
+    /// Receives a progress update after every check
+    pub progress: &'a dyn ProgressSink,
+}
+
+impl Default for Hooks<'_> {
+    fn default() -> Self {
+        Self {
+            logger: &NoopLogger,
+            progress: &NoProgress,
+        }
+    }
+}
+
+/// Run all compliance checks, notifying the logger and progress sink in `hooks`
+pub fn verify_repository_with_hooks(
+    repo_path: &Path,
+    config: &Config,
+    hooks: &Hooks,
+) -> ComplianceReport {
+    verify_repository_with_fs(repo_path, config, hooks, &RealFs)
+}
+
+/// IDs of the checks the repository's suppressions file waives on `now`,
+/// looked up only for a `fail_fast` run
+fn active_waivers(
+    fs: &dyn FileSystem,
+    repo_path: &Path,
+    config: &Config,
+    now: SystemTime,
+) -> Vec<String> {
+    if !config.checks.fail_fast {
+        return Vec::new();
+    }
+    let today = format_timestamp(now);
+    let today = today.get(..10).unwrap_or(&today);
+    // An unreadable file is reported by the caller that applies it
+    Suppressions::discover_with_fs(fs, repo_path)
+        .map(|suppressions| {
+            suppressions
+                .entries
+                .into_iter()
+                .filter(|s| !s.is_expired(today))
+                .map(|s| s.id)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Run all compliance checks against `fs` instead of the real filesystem
+///
+/// With a [`vfs::MemoryFs`] every check can be exercised without touching
+/// the disk. Lookups go through a [`vfs::FsCache`] for the run, which lists
+/// the repository root and `.well-known/` up front so the checks' names are
+/// found in those listings rather than stat'ed one by one.
+pub fn verify_repository_with_fs(
+    repo_path: &Path,
+    config: &Config,
+    hooks: &Hooks,
+    fs: &dyn FileSystem,
+) -> ComplianceReport {
+    let started = Instant::now();
+    let cache = vfs::FsCache::new(fs);
+    let fs: &dyn FileSystem = &cache;
+    let web_root = match config.site.web_root {
+        Some(ref web_root) => web_root.clone(),
+        None => site::detect(fs, repo_path),
+    };
+    for dir in [repo_path, &repo_path.join(site::join(&web_root, ".well-known"))] {
+        let _ = fs.read_dir(dir);
+    }
+    let mut report = ComplianceReport::new(repo_path.to_path_buf());
+    report.web_root = web_root.clone();
+    report.standard_version = config.standard_version;
+    if let Some(info) = vcs::read(fs, repo_path) {
+        info.fill(&mut report.metadata);
+    }
+    let logger = hooks.logger;
+    let v = Verification {
+        repo_path,
+        config,
+        hooks,
+        fs,
+        logged_warnings: Cell::new(0),
+        completed: Cell::new(0),
+        started,
+        check_started: Cell::new(started),
+        stopped_by: RefCell::new(None),
+        waived: active_waivers(fs, repo_path, config, report.verified_at),
+        web_root,
+    };
+
+    logger.log(
+        LogLevel::Info,
+        &format!("verifying {}", repo_path.display()),
+    );
+    check_documentation(&mut report, &v);
+    check_conflicting_copies(&mut report, &v);
+    check_links(&mut report, &v);
+    check_contacts(&mut report, &v);
+    check_well_known(&mut report, &v);
+    check_build_system(&mut report, &v);
+    check_source_structure(&mut report, &v);
+    check_custom_rules(&mut report, &v);
+    check_version_control(&mut report, &v);
+    check_conformity(&mut report, &v);
+    check_untracked(&mut report, &v);
+    v.log_new_warnings(&report);
+    report.duration = started.elapsed();
+    logger.log(
+        LogLevel::Info,
+        &format!(
+            "verified {}: {}/{} checks passed",
+            repo_path.display(),
+            report.passed_count(),
+            report.total_count()
+        ),
+    );
+
+    report
+}
+
+/// Format a SystemTime as a human-readable timestamp (ISO 8601)
+///
+/// Times before 1970 are supported; years outside 0000–9999 use the ISO 8601
+/// expanded form with an explicit sign (e.g. `+10000-01-01T00:00:00Z`).
+pub fn format_timestamp(time: SystemTime) -> String {
+    // Whole seconds since the epoch, rounded towards negative infinity
+    let secs = match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(duration) => i64::try_from(duration.as_secs()).ok(),
+        Err(e) => {
+            let before = e.duration();
+            i64::try_from(before.as_secs())
+                .ok()
+                .and_then(|s| s.checked_add(i64::from(before.subsec_nanos() > 0)))
+                .map(|s| -s)
+        }
+    };
+    let Some(secs) = secs else {
+        return "unknown".to_string();
+    };
+
+    let days = secs.div_euclid(86400);
+    let time_secs = secs.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+    let hours = time_secs / 3600;
+    let minutes = (time_secs % 3600) / 60;
+    let seconds = time_secs % 60;
+
+    let year = if (0..=9999).contains(&year) {
+        format!("{:04}", year)
+    } else {
+        format!("{:+05}", year)
+    };
+    format!(
+        "{}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, hours, minutes, seconds
+    )
+}
+
+/// Proleptic Gregorian (year, month, day) of a day count since 1970-01-01
+///
+/// Works in 400-year eras, so it is constant-time for any date (Howard
+/// Hinnant's `civil_from_days`).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097); // day of era, 0..=146096
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365; // 0..=399
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // day of year from March 1
+    let mp = (5 * doy + 2) / 153; // month from March, 0..=11
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Escape a string for JSON output
+///
+/// Kept for existing callers; see [`json::escape`].
+pub fn json_escape(s: &str) -> String {
+    json::escape(s)
+}
+
+/// Bot action types for CI/CD integration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotAction {
+    /// Check compliance and report
+    Check,
+    /// Check compliance and create PR with fixes
+    Fix,
+    /// Generate badge for README
+    Badge,
+    /// Generate conformity document
+    Conformity,
+    /// Check every repository in a list file
+    Batch,
+    /// Render an HTML dashboard for every repository in a list file
+    Dashboard,
+    /// Answer verification requests over HTTP
+    Serve,
+    /// Time repeated verifications of a repository
+    Bench,
+    /// Print the RSR requirement catalog
+    Standard,
+    /// Check a report against its detached signature
+    VerifyReport,
+    /// Install a git hook that runs a compliance check
+    InstallHook,
+    /// Write a GitHub Actions composite action wrapping the binary
+    GenerateAction,
+    /// Write a CI workflow running rhodibot into the repository
+    SetupCi,
+    /// Check compliance and report only changes since the stored state
+    Drift,
+}
+
+/// Bot configuration
+#[derive(Debug, Clone)]
+pub struct BotConfig {
+    pub action: BotAction,
+    pub create_pr: bool,
+    pub fail_on_warning: bool,
+    pub target_level: ComplianceLevel,
+    pub fail_below_percentage: Option<f64>,
+    /// Exit with [`exit_codes::WAIVED_COMPLIANCE`] when only waivers meet
+    /// the target level
+    pub distinguish_waived: bool,
+}
+
+impl BotConfig {
+    /// Apply the `[policy]` section of a configuration file
+    pub fn apply_policy(&mut self, policy: &config::PolicyConfig) {
+        self.fail_on_warning = policy.fail_on_warning;
+        self.target_level = policy.fail_on_level;
+        self.fail_below_percentage = policy.fail_below_percentage;
+        self.distinguish_waived = policy.distinguish_waived;
+    }
+
+    /// The exit-code policy in effect, flags included
+    pub fn policy(&self) -> config::PolicyConfig {
+        config::PolicyConfig {
+            fail_on_warning: self.fail_on_warning,
+            fail_below_percentage: self.fail_below_percentage,
+            fail_on_level: self.target_level,
+            distinguish_waived: self.distinguish_waived,
+        }
+    }
+}
+
+/// Exit code for `report` under `policy`
+///
+/// The outcomes are judged in order, and the first that applies wins:
+/// security warnings ([`exit_codes::SECURITY_WARNING`]), a missed level or
+/// percentage ([`exit_codes::COMPLIANCE_FAILED`]), a level met only through
+/// waivers ([`exit_codes::WAIVED_COMPLIANCE`], with `distinguish_waived`),
+/// and otherwise [`exit_codes::SUCCESS`]. [`policy::Policy::evaluate`] gives
+/// the reasons as well.
+pub fn compute_exit_code(report: &ComplianceReport, policy: &config::PolicyConfig) -> i32 {
+    policy.evaluate(report).exit_code
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        Self {
+            action: BotAction::Check,
+            create_pr: false,
+            fail_on_warning: false,
+            target_level: ComplianceLevel::Bronze,
+            fail_below_percentage: None,
+            distinguish_waived: false,
+        }
+    }
+}
+
+/// Generate RSR badge markdown
+pub fn generate_badge(level: ComplianceLevel) -> String {
+    rsr_spec::badge::markdown(level)
+}
+
+/// Generate RSR conformity document
+pub fn generate_conformity_doc(report: &ComplianceReport) -> String {
+    let level = report.highest_level();
     let level_str = level.map(|l| l.display_name()).unwrap_or("Not Met");
-    let timestamp = format_timestamp(report.verified_at);
 
     let mut doc = String::new();
     doc.push_str("# RSR Conformity Statement\n\n");
@@ -612,19 +2131,90 @@ pub fn generate_conformity_doc(report: &ComplianceReport) -> String {
             .unwrap_or_else(|| "Unknown".to_string())
     ));
     doc.push_str(&format!("**RSR Level**: {}\n", level_str));
-    doc.push_str("**Standard**: [Rhodium Standard Repository](https://github.com/hyperpolymath/rhodium-standard-repositories)\n");
-    doc.push_str(&format!("**Last Verified**: {}\n\n", timestamp.split('T').next().unwrap_or(&timestamp)));
-
-    if let Some(l) = level {
-        doc.push_str(&format!("## {} Requirements Met\n\n", l.display_name()));
-        doc.push_str("| Requirement | Status |\n");
-        doc.push_str("|-------------|--------|\n");
-        for check in &report.checks {
-            if check.required_for == l {
-                let status = if check.passed { "Yes" } else { "No" };
-                doc.push_str(&format!("| {} | {} |\n", check.item, status));
-            }
+    doc.push_str(&format!(
+        "**Standard**: [Rhodium Standard Repository {}]({})\n",
+        report.standard_version, STANDARD_URL
+    ));
+    doc.push_str(&format!("**Generated by**: {}\n", output::identity_line(report)));
+    if let Some(ref commit) = report.metadata.commit {
+        doc.push_str(&format!("**Commit**: {}\n", commit));
+    }
+    if let Some(ref branch) = report.metadata.branch {
+        doc.push_str(&format!("**Branch**: {}\n", branch));
+    }
+    if let Some(timestamp) = report.timestamp() {
+        doc.push_str(&format!(
+            "**Last Verified**: {}\n",
+            timestamp.split('T').next().unwrap_or(&timestamp)
+        ));
+    }
+    doc.push('\n');
+
+    for l in ComplianceLevel::ALL {
+        let checks: Vec<&CheckResult> =
+            report.checks.iter().filter(|c| c.required_for == l).collect();
+        let status = if checks.is_empty() {
+            "no automated checks"
+        } else if report.meets_level(l) {
+            "met"
+        } else {
+            "not met"
+        };
+        doc.push_str(&format!("## {} Requirements ({})\n\n", l.display_name(), status));
+        if checks.is_empty() {
+            doc.push_str("No automated checks are defined for this level yet.\n\n");
+            continue;
+        }
+        doc.push_str("| ID | Requirement | Status |\n");
+        doc.push_str("|----|-------------|--------|\n");
+        for check in checks {
+            doc.push_str(&format!(
+                "| {} | {} | {} |\n",
+                check.id,
+                check.item,
+                conformity::status(check)
+            ));
+        }
+        doc.push('\n');
+    }
+
+    if !report.skipped_checks.is_empty() {
+        doc.push_str("## Skipped Checks\n\n");
+        for id in &report.skipped_checks {
+            doc.push_str(&format!("- {}\n", id));
+        }
+        doc.push('\n');
+    }
+
+    if !report.waivers.is_empty() || !report.expired_waivers.is_empty() {
+        doc.push_str("## Waivers\n\n");
+        doc.push_str("| ID | Reason | Expires |\n");
+        doc.push_str("|----|--------|---------|\n");
+        for waiver in &report.waivers {
+            let expires = waiver.expires.as_deref().unwrap_or("never");
+            doc.push_str(&format!("| {} | {} | {} |\n", waiver.id, waiver.reason, expires));
+        }
+        for waiver in &report.expired_waivers {
+            let expires = waiver.expires.as_deref().unwrap_or("");
+            doc.push_str(&format!(
+                "| {} | {} | {} (expired) |\n",
+                waiver.id, waiver.reason, expires
+            ));
         }
+        doc.push('\n');
+    }
+
+    doc.push_str("## Security Warnings\n\n");
+    if report.warnings.is_empty() {
+        doc.push_str("None.\n");
+    }
+    for warning in &report.warnings {
+        let level = match warning.level {
+            WarningLevel::Info => "Info",
+            WarningLevel::Warning => "Warning",
+            WarningLevel::Critical => "Critical",
+        };
+        doc.push_str(&format!("- **{}**: {}\n", level, warning.message));
     }
 
     doc.push_str("\n## Verification\n\n");
@@ -659,7 +2249,7 @@ fn test_add_check() {
         let mut report = ComplianceReport::new(PathBuf::from("/tmp/test"));
         report.add_check("Test", "Item", true, ComplianceLevel::Bronze);
         assert_eq!(report.checks.len(), 1);
-        assert!(report.checks[0].passed);
+        assert!(report.checks[0].passed());
     }
 
     #[test]
@@ -691,6 +2281,8 @@ fn test_add_warning() {
         report.add_warning(WarningLevel::Info, "Test warning", None);
         assert_eq!(report.warnings.len(), 1);
         assert_eq!(report.warnings[0].level, WarningLevel::Info);
+        report.add_warning(WarningLevel::Info, "Test warning", None);
+        assert_eq!(report.warnings.len(), 1);
     }
 
     #[test]
@@ -703,6 +2295,932 @@ fn test_critical_warnings_detection() {
         assert!(report.has_critical_warnings());
     }
 
+    #[test]
+    fn test_compute_exit_code() {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/test"));
+        report.add_check("Test", "Item1", true, ComplianceLevel::Bronze);
+        report.add_check("Test", "Item2", false, ComplianceLevel::Bronze);
+        report.add_check("Test", "Item3", false, ComplianceLevel::Silver);
+        let policy = config::PolicyConfig::default();
+        assert_eq!(compute_exit_code(&report, &policy), exit_codes::COMPLIANCE_FAILED);
+
+        // Waived into Bronze: success, or its own code when asked for
+        report.checks[1].waived = true;
+        assert_eq!(compute_exit_code(&report, &policy), exit_codes::SUCCESS);
+        let distinguish = config::PolicyConfig {
+            distinguish_waived: true,
+            ..config::PolicyConfig::default()
+        };
+        assert_eq!(compute_exit_code(&report, &distinguish), exit_codes::WAIVED_COMPLIANCE);
+
+        // Silver is not met, nor is a minimum percentage above 66.7%
+        let silver = config::PolicyConfig {
+            fail_on_level: ComplianceLevel::Silver,
+            ..config::PolicyConfig::default()
+        };
+        assert_eq!(compute_exit_code(&report, &silver), exit_codes::COMPLIANCE_FAILED);
+        let percentage = config::PolicyConfig {
+            fail_below_percentage: Some(70.0),
+            ..config::PolicyConfig::default()
+        };
+        assert_eq!(compute_exit_code(&report, &percentage), exit_codes::COMPLIANCE_FAILED);
+
+        // Warnings fail only with fail_on_warning; critical ones always do,
+        // ahead of the level
+        report.add_warning(WarningLevel::Warning, "warning", None);
+        assert_eq!(compute_exit_code(&report, &policy), exit_codes::SUCCESS);
+        let strict = config::PolicyConfig {
+            fail_on_warning: true,
+            ..config::PolicyConfig::default()
+        };
+        assert_eq!(compute_exit_code(&report, &strict), exit_codes::SECURITY_WARNING);
+        report.add_warning(WarningLevel::Critical, "critical", None);
+        assert_eq!(compute_exit_code(&report, &silver), exit_codes::SECURITY_WARNING);
+
+        assert_eq!(exit_codes::name(5), Some("WAIVED_COMPLIANCE"));
+        assert!(exit_codes::explain(2).unwrap().contains("fail_on_warning"));
+        assert_eq!(exit_codes::explain(6), None);
+    }
+
+    #[test]
+    fn test_check_ids() {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/test"));
+        report.add_spec_check(catalog::lookup("RSR-BLD-002").unwrap(), true);
+        report.add_check("Custom", "src/ directory", true, ComplianceLevel::Bronze);
+        assert_eq!(report.checks[0].id, "RSR-BLD-002");
+        assert_eq!(report.checks[0].item, "flake.nix");
+        assert!(report.checks[0].description.as_deref().unwrap().contains("reproducible"));
+        assert_eq!(report.checks[1].id, "rsr/custom/src-directory");
+    }
+
+    #[test]
+    fn test_disabled_checks_are_skipped() {
+        let config = Config::parse("[checks]\ndisable = [\"RSR-BLD-002\"]").unwrap();
+        let report = verify_repository_with_config(Path::new("/nonexistent/rhodibot"), &config);
+        // Less the opt-in RSR-VCS-001, which is not enabled
+        assert_eq!(report.checks.len(), catalog::BUILTIN_CHECKS.len() - 2);
+        // No CONFORMITY.md is committed, so there is no drift to check
+        let drift = report.checks.iter().find(|c| c.id == conformity::DRIFT_CHECK).unwrap();
+        assert_eq!(drift.status, CheckStatus::NotApplicable);
+        assert!(report.checks.iter().all(|c| c.id != "RSR-BLD-002"));
+        assert_eq!(report.skipped_checks, vec!["RSR-BLD-002".to_string()]);
+    }
+
+    #[test]
+    fn test_check_timing() {
+        let report = verify_repository(Path::new("/nonexistent/rhodibot"));
+        assert!(report.checks_duration() <= report.duration);
+
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/test"));
+        for (item, micros) in [("a", 5), ("b", 30), ("c", 10)] {
+            report.add_check("Test", item, true, ComplianceLevel::Bronze);
+            report.checks.last_mut().unwrap().duration = Duration::from_micros(micros);
+        }
+        let slowest: Vec<&str> = report.slowest_checks(2).iter().map(|c| c.item.as_str()).collect();
+        assert_eq!(slowest, vec!["b", "c"]);
+        assert_eq!(report.checks_duration(), Duration::from_micros(45));
+    }
+
+    #[test]
+    fn test_merge_not_applicable() {
+        let mut first = ComplianceReport::new(PathBuf::from("/tmp/project"));
+        first.add_check("Docs", "README", true, ComplianceLevel::Bronze);
+        first.add_check("Docs", "LICENSE", false, ComplianceLevel::Bronze);
+        first.checks[1].status = CheckStatus::NotApplicable;
+        let mut second = ComplianceReport::new(PathBuf::from("/tmp/project"));
+        second.add_check("Docs", "README", false, ComplianceLevel::Bronze);
+        second.checks[0].status = CheckStatus::NotApplicable;
+        second.add_check("Docs", "LICENSE", true, ComplianceLevel::Bronze);
+
+        // A check that ran wins over one that did not apply, either way round
+        first.merge(second);
+        let statuses: Vec<CheckStatus> = first.checks.iter().map(|c| c.status).collect();
+        assert_eq!(statuses, [CheckStatus::Pass, CheckStatus::Pass]);
+        assert_eq!(first.total_count(), 2);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut first = ComplianceReport::new(PathBuf::from("/tmp/project"));
+        first.add_check("Docs", "README", true, ComplianceLevel::Bronze);
+        first.add_check("Docs", "LICENSE", false, ComplianceLevel::Bronze);
+        first.add_check("Docs", "NOTICE", true, ComplianceLevel::Bronze);
+        first.checks[2].waived = true;
+        first.add_warning(WarningLevel::Info, "'docs' is a symlink", None);
+        first.skipped_checks.push("rsr/docs/authors".to_string());
+        first.skipped_checks.push("RSR-BLD-002".to_string());
+        first.duration = Duration::from_millis(2);
+
+        let mut second = ComplianceReport::new(PathBuf::from("/elsewhere"));
+        second.verified_at = SystemTime::UNIX_EPOCH;
+        second.add_check("Docs", "README", false, ComplianceLevel::Bronze);
+        second.add_check("Docs", "LICENSE", true, ComplianceLevel::Bronze);
+        second.add_check("Docs", "NOTICE", true, ComplianceLevel::Bronze);
+        second.add_check("Docs", "AUTHORS", true, ComplianceLevel::Silver);
+        second.add_warning(WarningLevel::Info, "'docs' is a symlink", None);
+        second.add_warning(WarningLevel::Critical, "escape", None);
+        second.skipped_checks.push("RSR-BLD-002".to_string());
+        second.duration = Duration::from_millis(3);
+
+        first.merge(second);
+        let results: Vec<(&str, bool, bool)> = first
+            .checks
+            .iter()
+            .map(|c| (c.item.as_str(), c.passed(), c.waived))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                ("README", false, false),
+                ("LICENSE", false, false),
+                ("NOTICE", true, true),
+                ("AUTHORS", true, false),
+            ]
+        );
+        assert_eq!(first.warnings.len(), 2);
+        assert_eq!(first.skipped_checks, vec!["RSR-BLD-002".to_string()]);
+        assert_eq!(first.verified_at, SystemTime::UNIX_EPOCH);
+        assert_eq!(first.duration, Duration::from_millis(5));
+        assert_eq!(first.repository_path, PathBuf::from("/tmp/project"));
+    }
+
+    #[test]
+    fn test_make_reproducible() {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/project"));
+        report.add_spec_check(catalog::lookup("RSR-DOC-001").unwrap(), true);
+        report.checks[0].duration = Duration::from_micros(42);
+        report.duration = Duration::from_millis(3);
+        let link = PathBuf::from("/tmp/project/docs");
+        report.add_warning(WarningLevel::Info, "'docs' is a symlink", Some(link));
+        assert_eq!(report.repository_display(), "/tmp/project");
+        assert!(report.timestamp().is_some());
+
+        report.make_reproducible();
+        assert_eq!(report.repository_display(), ".");
+        assert_eq!(report.timestamp(), None);
+        assert_eq!(report.warnings[0].path, Some(PathBuf::from("docs")));
+        assert_eq!(report.duration, Duration::ZERO);
+        assert_eq!(report.checks_duration(), Duration::ZERO);
+
+        let doc = generate_conformity_doc(&report);
+        assert!(doc.contains("**Project**: project\n"));
+        assert!(!doc.contains("Last Verified"));
+    }
+
+    #[test]
+    fn test_apply_suppressions() {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/test"));
+        // 2026-01-15T00:00:00Z
+        report.verified_at = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_768_435_200);
+        report.add_spec_check(catalog::lookup("RSR-BLD-001").unwrap(), false);
+        report.add_spec_check(catalog::lookup("RSR-BLD-002").unwrap(), false);
+
+        let suppressions = Suppressions::parse(
+            "[RSR-BLD-001]\nreason = \"x\"\nexpires = \"2026-01-15\"\n\
+             [RSR-BLD-002]\nreason = \"y\"\nexpires = \"2026-01-14\"\n",
+        )
+        .unwrap();
+        report.apply_suppressions(&suppressions);
+
+        assert!(report.checks[0].passed() && report.checks[0].waived);
+        assert!(!report.checks[1].passed() && !report.checks[1].waived);
+        assert_eq!(report.waived_count(), 1);
+        assert!(!report.meets_level_by_waivers(ComplianceLevel::Bronze));
+        report.checks.pop();
+        assert!(report.meets_level_by_waivers(ComplianceLevel::Bronze));
+        assert_eq!(report.waivers[0].id, "RSR-BLD-001");
+        assert_eq!(report.expired_waivers[0].id, "RSR-BLD-002");
+    }
+
+    #[test]
+    fn test_conformity_doc_lists_all_levels() {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/project"));
+        report.add_spec_check(catalog::lookup("RSR-DOC-001").unwrap(), true);
+        report.add_spec_check(catalog::lookup("RSR-DOC-002").unwrap(), false);
+        report.add_check("Extras", "SUPPORT.md", false, ComplianceLevel::Silver);
+        report.add_warning(WarningLevel::Critical, "Symlink escapes repository", None);
+
+        let doc = generate_conformity_doc(&report);
+        assert!(doc.contains("**RSR Level**: Not Met"));
+        assert!(doc.contains("## Bronze Requirements (not met)"));
+        assert!(doc.contains("| RSR-DOC-001 | README.md | Pass |"));
+        assert!(doc.contains("| RSR-DOC-002 | LICENSE.txt | Fail |"));
+        assert!(doc.contains("## Silver Requirements (not met)"));
+        assert!(doc.contains("SUPPORT.md | Fail |"));
+        assert!(doc.contains("## Platinum Requirements (no automated checks)"));
+        assert!(doc.contains("- **Critical**: Symlink escapes repository"));
+    }
+
+    /// Logger that keeps every message for inspection
+    struct Recorder(std::cell::RefCell<Vec<(LogLevel, String)>>);
+
+    impl Logger for Recorder {
+        fn log(&self, level: LogLevel, message: &str) {
+            self.0.borrow_mut().push((level, message.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_logger_receives_check_events() {
+        let config = Config::parse("[checks]\ndisable = [\"RSR-BLD-002\"]").unwrap();
+        let recorder = Recorder(Default::default());
+        let report =
+            verify_repository_with_logger(Path::new("/nonexistent/rhodibot"), &config, &recorder);
+        let events = recorder.0.borrow();
+
+        let starts = events.iter().filter(|(_, m)| m.starts_with("start ")).count();
+        let finishes = events.iter().filter(|(_, m)| m.starts_with("finish ")).count();
+        // Checks whose prerequisite failed are skipped without running
+        let blocked = report.checks.iter().filter(|c| c.blocked_by.is_some()).count();
+        assert!(blocked > 0);
+        assert_eq!(starts + blocked, report.checks.len());
+        assert_eq!(finishes + blocked, report.checks.len());
+        assert!(events.contains(&(
+            LogLevel::Debug,
+            "skip RSR-WKN-002 (prerequisite RSR-WKN-001 failed)".to_string()
+        )));
+        assert!(events.contains(&(LogLevel::Info, "finish RSR-DOC-001: fail".to_string())));
+        assert!(events
+            .iter()
+            .any(|(l, m)| *l == LogLevel::Debug && m.starts_with("skip RSR-BLD-002")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_logger_receives_security_events() {
+        let dir = std::env::temp_dir().join(format!("rhodibot_log_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::os::unix::fs::symlink("/etc/hostname", dir.join("README.md")).unwrap();
+
+        let recorder = Recorder(Default::default());
+        verify_repository_with_logger(&dir, &Config::default(), &recorder);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let events = recorder.0.borrow();
+        let security = events
+            .iter()
+            .position(|(l, m)| *l == LogLevel::Error && m.starts_with("security: Symlink 'README.md'"))
+            .expect("critical warning not logged");
+        // Logged before the check that raised it finishes
+        let finish = events
+            .iter()
+            .position(|(_, m)| m.starts_with("finish RSR-DOC-001"))
+            .unwrap();
+        assert!(security < finish);
+    }
+
+    #[test]
+    fn test_progress_sink_sees_every_check() {
+        let updates = std::cell::RefCell::new(Vec::new());
+        let sink = |p: &Progress| {
+            updates
+                .borrow_mut()
+                .push((p.completed, p.total, p.category.to_string(), p.check_id.to_string()))
+        };
+        let hooks = Hooks {
+            progress: &sink,
+            ..Hooks::default()
+        };
+        let config = Config::parse("[checks]\ndisable = [\"RSR-CNF-001\"]").unwrap();
+        verify_repository_with_hooks(Path::new("/nonexistent/rhodibot"), &config, &hooks);
+
+        let updates = updates.borrow();
+        let total = catalog::BUILTIN_CHECKS.len();
+        assert_eq!(updates.len(), total);
+        assert_eq!(
+            updates[0],
+            (1, total, "Documentation".to_string(), "RSR-DOC-001".to_string())
+        );
+        // Disabled checks still advance progress so it reaches the total
+        assert_eq!(
+            updates[total - 1],
+            (total, total, "Conformity".to_string(), "RSR-CNF-001".to_string())
+        );
+    }
+
+    fn verify_in_memory(fs: &vfs::MemoryFs) -> ComplianceReport {
+        verify_repository_with_fs(Path::new("/repo"), &Config::default(), &Hooks::default(), fs)
+    }
+
+    #[test]
+    fn test_in_memory_repository() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/README.adoc", "= Demo\n")
+            .add_file("/repo/LICENSE.txt", "MIT\n")
+            .add_file("/repo/SECURITY.md", "Report to security@example.org\n")
+            .add_file("/repo/CONTRIBUTING.md", "Run the tests\n")
+            .add_file("/repo/CODE_OF_CONDUCT.md", "Be kind\n")
+            .add_file("/repo/MAINTAINERS.md", "- Jo\n")
+            .add_file("/repo/CHANGELOG.md", "## 1.0\n")
+            .add_file("/repo/.well-known/security.txt", "Contact: mailto:security@rsr.dev\n")
+            .add_file("/repo/.well-known/ai.txt", "")
+            .add_file("/repo/.well-known/humans.txt", "")
+            .add_file("/repo/justfile", "")
+            .add_file("/repo/flake.nix", "")
+            .add_file("/repo/.gitlab-ci.yml", "")
+            .add_dir("/repo/src")
+            .add_dir("/repo/test");
+        let report = verify_in_memory(&fs);
+        assert!(report.bronze_compliance());
+        assert!(report.warnings.is_empty());
+
+        // A directory where a file is expected does not count
+        fs.add_dir("/repo/SECURITY.md");
+        let report = verify_in_memory(&fs);
+        assert!(!report.bronze_compliance());
+        assert!(report.checks.iter().any(|c| c.id == "RSR-DOC-003" && !c.passed()));
+    }
+
+    #[test]
+    fn test_conflicting_readme_and_license_copies() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/README.md", "# Demo\n\nChecks repositories for compliance.\n")
+            .add_file("/repo/README.adoc", "= Legacy\n\nAn old prototype, now abandoned.\n")
+            .add_file("/repo/LICENSE", "SPDX-License-Identifier: MIT\n")
+            .add_file("/repo/LICENSE.txt", "Apache License\nVersion 2.0, January 2004\n");
+        let report = verify_in_memory(&fs);
+        let messages: Vec<&str> = report.warnings.iter().map(|w| w.message.as_str()).collect();
+        assert!(messages.iter().any(|m| m.starts_with("'README.md' and 'README.adoc' diverge")));
+        let disagree = "License files disagree: 'LICENSE' is MIT, 'LICENSE.txt' is Apache-2.0";
+        assert!(messages.contains(&disagree));
+
+        // A short README pointing at the full one, and matching licenses
+        fs.add_file("/repo/README.md", "See README.adoc.\n")
+            .add_file("/repo/LICENSE.txt", "SPDX-License-Identifier: MIT\n\nMIT License\n");
+        assert!(verify_in_memory(&fs).warnings.is_empty());
+    }
+
+    #[test]
+    fn test_dependents_of_failed_prerequisites_are_skipped() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/README.md", "# Demo\n");
+        let report = verify_in_memory(&fs);
+        let blocked_by = |id: &str| {
+            let check = report.checks.iter().find(|c| c.id == id).unwrap();
+            assert!(!check.passed(), "{}", id);
+            check.blocked_by.clone()
+        };
+        assert_eq!(blocked_by("RSR-WKN-001"), None);
+        assert_eq!(blocked_by("RSR-WKN-002").as_deref(), Some("RSR-WKN-001"));
+        assert_eq!(blocked_by("RSR-WKN-005").as_deref(), Some("RSR-WKN-003"));
+        assert_eq!(blocked_by("RSR-BLD-001"), None);
+        let status = |id: &str| report.checks.iter().find(|c| c.id == id).unwrap().status;
+        assert_eq!(status("RSR-WKN-002"), CheckStatus::Skipped);
+        assert_eq!(status("RSR-WKN-001"), CheckStatus::Fail);
+
+        // A failing check with a present prerequisite still runs
+        fs.add_dir("/repo/.well-known");
+        let report = verify_in_memory(&fs);
+        let check = report.checks.iter().find(|c| c.id == "RSR-WKN-002").unwrap();
+        assert!(!check.passed() && check.blocked_by.is_none());
+    }
+
+    #[test]
+    fn test_check_status() {
+        let config = Config::default();
+        let hooks = Hooks::default();
+        let fs = vfs::MemoryFs::new();
+        let v = Verification {
+            repo_path: Path::new("/repo"),
+            config: &config,
+            hooks: &hooks,
+            fs: &fs,
+            logged_warnings: Cell::new(0),
+            completed: Cell::new(0),
+            started: Instant::now(),
+            check_started: Cell::new(Instant::now()),
+            stopped_by: RefCell::new(None),
+            waived: Vec::new(),
+            web_root: String::new(),
+        };
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        run_check(&mut report, &v, "RSR-DOC-001", |_| panic!("broken check"));
+        let check = &report.checks[0];
+        assert_eq!(check.status, CheckStatus::Error);
+        assert!(!check.passed() && check.is_failure());
+        let description = check.description.as_deref().unwrap();
+        assert!(description.ends_with("(could not be evaluated: broken check)"));
+
+        // Checks that do not apply are left out of the score and the levels
+        report.add_spec_check(catalog::lookup("RSR-DOC-002").unwrap(), true);
+        report.add_spec_check(catalog::lookup("RSR-DOC-003").unwrap(), false);
+        report.checks[0].status = CheckStatus::Pass;
+        report.checks[2].status = CheckStatus::NotApplicable;
+        assert_eq!((report.passed_count(), report.total_count()), (2, 2));
+        assert!(report.meets_level(ComplianceLevel::Bronze));
+        assert!(!report.checks[2].passed() && !report.checks[2].is_failure());
+    }
+
+    #[test]
+    fn test_fail_fast() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/README.md", "# Demo\n");
+        let mut config = Config::default();
+        config.checks.fail_fast = true;
+        let verify = |fs: &vfs::MemoryFs| {
+            verify_repository_with_fs(Path::new("/repo"), &config, &Hooks::default(), fs)
+        };
+        let report = verify(&fs);
+        let statuses: Vec<(&str, CheckStatus)> =
+            report.checks.iter().take(3).map(|c| (c.id.as_str(), c.status)).collect();
+        assert_eq!(
+            statuses,
+            [
+                ("RSR-DOC-001", CheckStatus::Pass),
+                ("RSR-DOC-002", CheckStatus::Fail),
+                ("RSR-DOC-003", CheckStatus::Skipped),
+            ]
+        );
+        let rest = &report.checks[2..];
+        // Not run, which is not the same as blocked by the failure
+        assert!(rest.iter().all(|c| c.status == CheckStatus::Skipped && c.blocked_by.is_none()));
+        assert_eq!(rest[0].skip_reason().as_deref(), Some("not run: --fail-fast"));
+        assert_eq!(report.checks.len(), verify_in_memory(&fs).checks.len());
+        assert!(report.warnings.is_empty());
+
+        // Waived failures do not stop the run
+        fs.add_file(
+            "/repo/.rhodibot/suppressions.toml",
+            "[RSR-DOC-002]\nreason = \"Dual licensed\"\n",
+        );
+        let report = verify(&fs);
+        let check = |id: &str| report.checks.iter().find(|c| c.id == id).unwrap();
+        assert_eq!(check("RSR-DOC-003").status, CheckStatus::Fail);
+        assert_eq!(check("RSR-DOC-004").status, CheckStatus::Skipped);
+        assert_eq!(check("RSR-DOC-004").blocked_by, None);
+    }
+
+    #[test]
+    fn test_time_budgets() {
+        let mut config = Config::parse("[limits]\ncheck_timeout = 0.001\n").unwrap();
+        let hooks = Hooks::default();
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/README.md", "# Demo\n");
+        let v = Verification {
+            repo_path: Path::new("/repo"),
+            config: &config,
+            hooks: &hooks,
+            fs: &fs,
+            logged_warnings: Cell::new(0),
+            completed: Cell::new(0),
+            started: Instant::now(),
+            check_started: Cell::new(Instant::now()),
+            stopped_by: RefCell::new(None),
+            waived: Vec::new(),
+            web_root: String::new(),
+        };
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        run_check(&mut report, &v, "RSR-DOC-001", |_| {
+            std::thread::sleep(Duration::from_millis(5));
+            true
+        });
+        let check = &report.checks[0];
+        assert_eq!(check.status, CheckStatus::Error);
+        assert!(check.description.as_deref().unwrap().contains(", over its 0.001s time budget)"));
+
+        // Once the run is over budget, no further check starts
+        config.limits.run_timeout = Duration::from_nanos(1);
+        let rule = "[rules.adr]\npath = \"docs/adr/\"\ncategory = \"Docs\"\n";
+        config.rules = Config::parse(rule).unwrap().rules;
+        let report = verify_repository_with_fs(Path::new("/repo"), &config, &hooks, &fs);
+        let mut statuses = report.checks.iter().map(|c| c.status);
+        assert!(statuses.clone().any(|s| s == CheckStatus::Error));
+        assert!(statuses.all(|s| matches!(s, CheckStatus::Error | CheckStatus::Skipped)));
+        let adr = report.checks.iter().find(|c| c.id == "custom/adr").unwrap();
+        let description = adr.description.as_deref().unwrap();
+        assert!(description
+            .ends_with("(not run: verification exceeded its 0.000000001s time budget)"));
+    }
+
+    #[test]
+    fn test_empty_and_placeholder_documentation() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/README.md", "# Demo\n\nTODO\n")
+            .add_file("/repo/SECURITY.md", "")
+            .add_file("/repo/CHANGELOG.md", "# Changelog\n\n- TODO list export\n");
+        let description = |report: &ComplianceReport, id: &str| {
+            let check = report.checks.iter().find(|c| c.id == id).unwrap();
+            (check.passed(), check.description.clone().unwrap_or_default())
+        };
+
+        let report = verify_in_memory(&fs);
+        let (passed, text) = description(&report, "RSR-DOC-003");
+        assert!(!passed);
+        assert!(text.ends_with("(problems: is empty)"), "{}", text);
+        let (passed, text) = description(&report, "RSR-DOC-001");
+        assert!(!passed);
+        assert!(text.contains("holds only placeholder text"), "{}", text);
+        assert!(description(&report, "RSR-DOC-007").0);
+
+        let mut config = Config::default();
+        config.documentation.min_bytes = 0;
+        config.documentation.min_lines = 0;
+        config.documentation.detect_placeholders = false;
+        let report = verify_repository_with_fs(Path::new("/repo"), &config, &Hooks::default(), &fs);
+        assert!(description(&report, "RSR-DOC-001").0);
+        assert!(description(&report, "RSR-DOC-003").0);
+    }
+
+    #[test]
+    fn test_untracked_files_warn() {
+        let index = String::from_utf8(vcs::index_v2(&["README.md"])).unwrap();
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/.git/HEAD", "ref: refs/heads/main\n")
+            .add_file("/repo/.git/index", &index)
+            .add_file("/repo/.gitignore", "SECURITY.md\n")
+            .add_file("/repo/README.md", "# Demo\n")
+            .add_file("/repo/SECURITY.md", "Report to security@example.org\n")
+            .add_file("/repo/CONTRIBUTING.md", "Run `just test`, then open a pull request.\n");
+        let report = verify_in_memory(&fs);
+        let messages: Vec<&str> = report.warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "SECURITY.md satisfies RSR-DOC-003 but is ignored by git; fresh clones will fail",
+                "CONTRIBUTING.md satisfies RSR-DOC-004, RSR-DOC-008 but is not tracked by git; \
+                 fresh clones will fail",
+            ]
+        );
+        assert_eq!(report.warnings[0].path, Some(PathBuf::from("/repo/SECURITY.md")));
+
+        // An unreadable index leaves tracking unknown
+        fs.add_file("/repo/.git/index", "garbage");
+        assert!(verify_in_memory(&fs).warnings.is_empty());
+    }
+
+    #[test]
+    fn test_link_audit() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file(
+            "/repo/README.md",
+            "# Demo\n\nHome: http://demo.example.org\nRun it at http://localhost:8080\n",
+        )
+        .add_file("/repo/SECURITY.md", "# Security\n\nReport at https://203.0.113.9/report\n");
+        let messages = |config: &Config| {
+            let report =
+                verify_repository_with_fs(Path::new("/repo"), config, &Hooks::default(), &fs);
+            report.warnings.into_iter().map(|w| w.message).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(&Config::default()),
+            [
+                "README.md line 3: 'http://demo.example.org' uses http:// instead of https://",
+                "SECURITY.md line 3: 'https://203.0.113.9/report' points at a raw IP address \
+                 instead of a host name",
+            ]
+        );
+
+        let config = Config::parse("[links]\nallow = [\"http://demo.\"]").unwrap();
+        assert_eq!(messages(&config).len(), 1);
+        let config = Config::parse("[links]\naudit = false").unwrap();
+        assert!(messages(&config).is_empty());
+    }
+
+    #[test]
+    fn test_contact_validation() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/MAINTAINERS.md", "# Maintainers\n\n- Jo <jo@example.com>\n")
+            .add_file("/repo/.well-known/security.txt", "Expires: 2030-01-01T00:00:00Z\n")
+            .add_file("/repo/.well-known/humans.txt", "/* TEAM */\nContact: jo@rsr.dev\n");
+        let messages: Vec<String> =
+            verify_in_memory(&fs).warnings.into_iter().map(|w| w.message).collect();
+        assert_eq!(
+            messages,
+            [
+                ".well-known/security.txt: no 'Contact:' field",
+                "MAINTAINERS.md line 3: 'jo@example.com' is a placeholder address",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_web_root() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/mkdocs.yml", "site_name: Demo\n");
+        let verify = |fs: &vfs::MemoryFs, config: &Config| {
+            verify_repository_with_fs(Path::new("/repo"), config, &Hooks::default(), fs)
+        };
+        let report = verify(&fs, &Config::default());
+        assert_eq!(report.web_root, "docs");
+        let wkn = report.checks.iter().find(|c| c.id == "RSR-WKN-001").unwrap();
+        assert!(wkn.description.as_deref().unwrap().ends_with("(problems: web root: docs/)"));
+
+        fs.add_file("/repo/docs/.well-known/security.txt", "Contact: mailto:a@b.org\n")
+            .add_file("/repo/docs/.well-known/ai.txt", "User-agent: *\nDisallow: /\n");
+        let report = verify(&fs, &Config::default());
+        let passed = |report: &ComplianceReport, id: &str| {
+            report.checks.iter().any(|c| c.id == id && c.passed())
+        };
+        assert!(passed(&report, "RSR-WKN-002"));
+        assert!(passed(&report, "RSR-WKN-005"));
+        assert!(!passed(&report, "RSR-WKN-004"));
+
+        // The configured web root wins over detection
+        let config = Config::parse("[site]\nweb_root = \".\"").unwrap();
+        let report = verify(&fs, &config);
+        assert_eq!(report.web_root, "");
+        assert!(!passed(&report, "RSR-WKN-001"));
+    }
+
+    #[test]
+    fn test_configured_well_known_entries() {
+        let config = Config::parse(
+            "[well_known]\n\"dnt-policy.txt\" = \"silver\"\n\"openpgpkey/\" = \"gold\"\n",
+        )
+        .unwrap();
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/.well-known/dnt-policy.txt", "DNT honoured\n")
+            .add_file("/repo/.well-known/openpgpkey", "not a directory\n");
+        let report =
+            verify_repository_with_fs(Path::new("/repo"), &config, &Hooks::default(), &fs);
+        let extras: Vec<(&str, bool, ComplianceLevel)> = report
+            .checks
+            .iter()
+            .filter(|c| c.id.starts_with("rsr/well-known/"))
+            .map(|c| (c.item.as_str(), c.passed(), c.required_for))
+            .collect();
+        assert_eq!(
+            extras,
+            vec![
+                ("dnt-policy.txt", true, ComplianceLevel::Silver),
+                ("openpgpkey/", false, ComplianceLevel::Gold),
+            ]
+        );
+
+        let mut only_docs = config.clone();
+        only_docs.checks.only_categories = vec!["Documentation".to_string()];
+        let report =
+            verify_repository_with_fs(Path::new("/repo"), &only_docs, &Hooks::default(), &fs);
+        assert!(report.checks.iter().all(|c| c.category == "Documentation"));
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let config = Config::parse(
+            "[rules.architecture]\npath = \"docs/ARCHITECTURE.md\"\ncategory = \"Policy\"\n\
+             level = \"gold\"\n\n[rules.adr]\npath = \"docs/adr/\"\ncategory = \"Policy\"\n",
+        )
+        .unwrap();
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/docs/ARCHITECTURE.md", "# Architecture\n");
+        let report =
+            verify_repository_with_fs(Path::new("/repo"), &config, &Hooks::default(), &fs);
+        let rules: Vec<(&str, bool, ComplianceLevel, Option<&str>)> = report
+            .checks
+            .iter()
+            .filter(|c| c.category == "Policy")
+            .map(|c| (c.id.as_str(), c.passed(), c.required_for, c.description.as_deref()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                (
+                    "custom/architecture",
+                    true,
+                    ComplianceLevel::Gold,
+                    Some("docs/ARCHITECTURE.md must exist (file)")
+                ),
+                (
+                    "custom/adr",
+                    false,
+                    ComplianceLevel::Bronze,
+                    Some("docs/adr/ must exist (directory)")
+                ),
+            ]
+        );
+
+        let mut asserted = Config::parse(
+            "[rules.disclosure]\npath = \"SECURITY.md\"\ncategory = \"Security\"\n\
+             must_contain = \"disclosure\"\nmust_match = \"(?i)contact\"\n",
+        )
+        .unwrap();
+        fs.add_file("/repo/SECURITY.md", "Contact security@example.org\n");
+        let report =
+            verify_repository_with_fs(Path::new("/repo"), &asserted, &Hooks::default(), &fs);
+        let check = report.checks.iter().find(|c| c.id == "custom/disclosure").unwrap();
+        assert!(!check.passed());
+        assert_eq!(
+            check.description.as_deref(),
+            Some("SECURITY.md must exist (file) (problems: does not contain 'disclosure')")
+        );
+        asserted.rules[0].must_contain[0] = "security@".to_string();
+        let report =
+            verify_repository_with_fs(Path::new("/repo"), &asserted, &Hooks::default(), &fs);
+        assert!(report.checks.iter().find(|c| c.id == "custom/disclosure").unwrap().passed());
+
+        let mut skipped = config.clone();
+        skipped.checks.disable.push("custom/adr".to_string());
+        let report =
+            verify_repository_with_fs(Path::new("/repo"), &skipped, &Hooks::default(), &fs);
+        assert!(report.checks.iter().all(|c| c.id != "custom/adr"));
+    }
+
+    #[test]
+    fn test_content_checks() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/CONTRIBUTING.md", "See CONTRIBUTING.adoc.\n")
+            .add_file("/repo/CONTRIBUTING.adoc", "Run `just test`, then open a pull request.\n")
+            .add_file("/repo/CODE_OF_CONDUCT.md", "Be nice.\n");
+        let report = verify_in_memory(&fs);
+        let check = |id: &str| report.checks.iter().find(|c| c.id == id).unwrap();
+        assert!(check("RSR-DOC-008").passed());
+        assert!(!check("RSR-DOC-009").passed());
+        let description = check("RSR-DOC-009").description.as_deref().unwrap();
+        assert!(description.ends_with("(problems: is not a known template (e.g. Contributor Covenant) and gives no enforcement contact)"));
+
+        let report = verify_in_memory(&vfs::MemoryFs::new());
+        let check = report.checks.iter().find(|c| c.id == "RSR-DOC-008").unwrap();
+        assert_eq!(check.blocked_by.as_deref(), Some("RSR-DOC-004"));
+
+        let config = Config::parse("[checks]\ndisable = [\"RSR-DOC-004\"]").unwrap();
+        let empty = vfs::MemoryFs::new();
+        let report =
+            verify_repository_with_fs(Path::new("/repo"), &config, &Hooks::default(), &empty);
+        let description = report.checks.iter().find(|c| c.id == "RSR-DOC-008").unwrap();
+        assert!(description
+            .description
+            .as_deref()
+            .unwrap()
+            .contains("CONTRIBUTING.md not found"));
+    }
+
+    #[test]
+    fn test_ci_content_check() {
+        let ci_check = |fs: &vfs::MemoryFs| {
+            let report = verify_in_memory(fs);
+            let check = report.checks.into_iter().find(|c| c.id == "RSR-BLD-004").unwrap();
+            (check.passed(), check.description.unwrap_or_default())
+        };
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_dir("/repo");
+        assert!(ci_check(&fs).1.ends_with("(problems: no CI configuration found)"));
+
+        fs.add_file("/repo/.gitlab-ci.yml", "build:\n  script: make\n")
+            .add_file("/repo/.github/workflows/notes.txt", "not a workflow")
+            .add_file(
+                "/repo/.github/workflows/ci.yml",
+                "on: push\njobs:\n  ci:\n    env:\n      NPM_TOKEN: abc\n",
+            );
+        let (passed, description) = ci_check(&fs);
+        assert!(!passed);
+        assert!(description.contains(
+            "(problems: .github/workflows/ci.yml line 5: 'NPM_TOKEN' looks like a secret"
+        ));
+        assert!(description.ends_with("; no CI job runs the tests)"));
+
+        fs.add_file("/repo/.github/workflows/ci.yml", "on: push\njobs:\n  test:\n    steps:\n      - run: cargo test\n");
+        assert!(ci_check(&fs).0);
+    }
+
+    #[test]
+    fn test_repository_types() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/README.md", "# Standard\n\nWhat it says.\n");
+        let statuses = |config: &str| {
+            let config = Config::parse(config).unwrap();
+            let report =
+                verify_repository_with_fs(Path::new("/repo"), &config, &Hooks::default(), &fs);
+            let status = |id: &str| report.checks.iter().find(|c| c.id == id).unwrap().status;
+            ["RSR-DOC-001", "RSR-BLD-001", "RSR-SRC-001"].map(status)
+        };
+        use CheckStatus::{Fail, NotApplicable, Pass};
+        assert_eq!(statuses(""), [Pass, Fail, Fail]);
+        assert_eq!(statuses("[repository]\ntype = \"data\""), [Pass, Fail, NotApplicable]);
+        assert_eq!(statuses("[repository]\ntype = \"meta\""), [Pass, NotApplicable, NotApplicable]);
+        // A docs repository's README must say more
+        assert_eq!(statuses("[repository]\ntype = \"docs\""), [Fail, Fail, NotApplicable]);
+    }
+
+    #[test]
+    fn test_source_layouts() {
+        let source_checks = |fs: &vfs::MemoryFs, config: &Config| {
+            let report =
+                verify_repository_with_fs(Path::new("/repo"), config, &Hooks::default(), fs);
+            report
+                .checks
+                .into_iter()
+                .filter(|c| c.id.starts_with("RSR-SRC-"))
+                .map(|c| (c.status, c.description.unwrap_or_default()))
+                .collect::<Vec<_>>()
+        };
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/go.mod", "module example.org/m\n")
+            .add_file("/repo/main.go", "package main\n");
+        let checks = source_checks(&fs, &Config::default());
+        assert_eq!(checks[0].0, CheckStatus::Pass);
+        assert_eq!(checks[1].0, CheckStatus::Fail);
+        assert!(checks[1].1.ends_with("(problems: go layout: no *_test.go files)"));
+
+        fs.add_file("/repo/internal/store/store_test.go", "package store\n");
+        let checks = source_checks(&fs, &Config::default());
+        assert!(checks.iter().all(|(status, _)| *status == CheckStatus::Pass));
+
+        // The configured layout wins over detection
+        let config = Config::parse("[source]\nlayout = \"rust\"\n").unwrap();
+        let checks = source_checks(&fs, &config);
+        assert!(checks[0].1.ends_with("(problems: rust layout: no src/)"));
+
+        let config = Config::parse("[source]\nlayout = \"docs\"\n").unwrap();
+        let checks = source_checks(&fs, &config);
+        assert!(checks.iter().all(|(status, _)| *status == CheckStatus::NotApplicable));
+    }
+
+    #[test]
+    fn test_in_memory_symlinks() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/etc/passwd", "root\n")
+            .add_file("/repo/docs/README.md", "# Demo\n")
+            .add_symlink("/repo/README.md", "docs/README.md")
+            .add_symlink("/repo/LICENSE.txt", "../etc/passwd")
+            .add_symlink("/repo/src", "/etc");
+        let report = verify_in_memory(&fs);
+
+        let passed = |id: &str| report.checks.iter().any(|c| c.id == id && c.passed());
+        assert!(passed("RSR-DOC-001"));
+        assert!(passed("RSR-DOC-002"));
+        assert!(passed("RSR-SRC-001"));
+        assert!(report
+            .warnings
+            .iter()
+            .any(|w| w.level == WarningLevel::Info && w.message.contains("'README.md' is a symlink")));
+        assert!(report.warnings.iter().any(|w| {
+            w.level == WarningLevel::Critical && w.message.contains("'LICENSE.txt' points outside")
+        }));
+        assert!(report.warnings.iter().any(|w| {
+            w.level == WarningLevel::Critical
+                && w.message == "Symlink directory 'src' points outside repository to '/etc'"
+        }));
+    }
+
+    #[test]
+    fn test_allowed_symlink_targets() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/shared/LICENSE.txt", "MIT\n")
+            .add_file("/shared-other/SECURITY.md", "# Security\n")
+            .add_dir("/repo")
+            .add_symlink("/repo/LICENSE.txt", "../shared/LICENSE.txt")
+            .add_symlink("/repo/SECURITY.md", "../shared-other/SECURITY.md");
+        let mut config = Config::default();
+        config.security.allow_symlink_targets = vec!["../shared".to_string()];
+        let report = verify_repository_with_fs(Path::new("/repo"), &config, &Hooks::default(), &fs);
+
+        let messages: Vec<(WarningLevel, &str)> =
+            report.warnings.iter().map(|w| (w.level, w.message.as_str())).collect();
+        assert!(messages.contains(&(
+            WarningLevel::Info,
+            "'LICENSE.txt' is a symlink to allowed target '/repo/../shared/LICENSE.txt'"
+        )));
+        assert!(messages.iter().any(|(level, message)| {
+            *level == WarningLevel::Critical && message.starts_with("Symlink 'SECURITY.md' points")
+        }));
+        assert!(report.checks.iter().any(|c| c.id == "RSR-DOC-002" && c.passed()));
+    }
+
+    #[test]
+    fn test_case_insensitive_and_alternate_names() {
+        let mut fs = vfs::MemoryFs::new();
+        fs.add_file("/repo/Readme.md", "# Demo\n")
+            .add_file("/repo/COPYING", "MIT\n")
+            .add_file("/repo/.Well-Known/Security.TXT", "Contact: a@b.org\n")
+            .add_dir("/repo/Src");
+        let passed = |report: &ComplianceReport, id: &str| {
+            report.checks.iter().any(|c| c.id == id && c.passed())
+        };
+
+        let report = verify_in_memory(&fs);
+        assert!(passed(&report, "RSR-DOC-001"));
+        assert!(passed(&report, "RSR-WKN-002"));
+        assert!(passed(&report, "RSR-SRC-001"));
+        assert!(!passed(&report, "RSR-DOC-002"));
+
+        let mut config = Config::default();
+        config.checks.alternates =
+            vec![("RSR-DOC-002".to_string(), vec!["LICENSE".into(), "copying".into()])];
+        let report = verify_repository_with_fs(Path::new("/repo"), &config, &Hooks::default(), &fs);
+        assert!(passed(&report, "RSR-DOC-002"));
+    }
+
+    #[test]
+    fn test_meets_level() {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/test"));
+        report.add_check("Test", "Item1", true, ComplianceLevel::Bronze);
+        report.add_check("Test", "Item2", false, ComplianceLevel::Silver);
+        assert!(report.meets_level(ComplianceLevel::Bronze));
+        assert!(!report.meets_level(ComplianceLevel::Silver));
+        assert!(!report.meets_level(ComplianceLevel::Gold));
+        assert_eq!(report.highest_level(), Some(ComplianceLevel::Bronze));
+        assert!(ComplianceLevel::Bronze < ComplianceLevel::Silver);
+        assert_eq!(ComplianceLevel::ALL.iter().max(), Some(&ComplianceLevel::Platinum));
+        assert_eq!(ComplianceLevel::parse("Silver"), Some(ComplianceLevel::Silver));
+        assert_eq!(ComplianceLevel::parse("tin"), None);
+        assert_eq!("PLATINUM".parse::<ComplianceLevel>(), Ok(ComplianceLevel::Platinum));
+        assert!("tin".parse::<ComplianceLevel>().unwrap_err().contains("'tin'"));
+        for level in ComplianceLevel::ALL {
+            assert_eq!(level.to_string().parse::<ComplianceLevel>(), Ok(level));
+        }
+    }
+
     #[test]
     fn test_generate_badge() {
         let badge = generate_badge(ComplianceLevel::Bronze);
@@ -716,7 +3234,24 @@ fn test_format_timestamp() {
         let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1705322445);
         let formatted = format_timestamp(time);
         assert!(formatted.contains("2024"));
-        assert!(formatted.ends_with("Z"));
+        assert!(formatted.ends_with('Z'));
+
+        let at = |secs: i64| {
+            let offset = Duration::from_secs(secs.unsigned_abs());
+            format_timestamp(if secs < 0 {
+                SystemTime::UNIX_EPOCH - offset
+            } else {
+                SystemTime::UNIX_EPOCH + offset
+            })
+        };
+        assert_eq!(at(0), "1970-01-01T00:00:00Z");
+        assert_eq!(at(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(at(4_107_542_400), "2100-03-01T00:00:00Z");
+        assert_eq!(at(-1), "1969-12-31T23:59:59Z");
+        assert_eq!(at(-2_208_988_800), "1900-01-01T00:00:00Z");
+        assert_eq!(at(253_402_300_800), "+10000-01-01T00:00:00Z");
+        let just_before = SystemTime::UNIX_EPOCH - Duration::from_millis(1);
+        assert_eq!(format_timestamp(just_before), "1969-12-31T23:59:59Z");
     }
 
     #[test]
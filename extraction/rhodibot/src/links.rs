@@ -0,0 +1,270 @@
+//! Link audit of the main documentation files
+//!
+//! The README, SECURITY and CONTRIBUTING files are where people find the
+//! project's home page, its security contact and its issue tracker, so a
+//! link there that is downgraded to plain HTTP, points at a bare IP address
+//! or is malformed is reported as a warning. The audit is offline: it looks
+//! at the URLs only. With `online = true` under `[links]` (or
+//! `--check-links`) each URL is also requested once, through `curl`, and
+//! links that do not answer with a success or redirect are reported too.
+//!
+//! Links to the local machine (`localhost`, `127.0.0.1`, `[::1]`) are left
+//! alone, as they appear in instructions for running the project.
+
+use crate::subprocess;
+use std::io;
+use std::net::Ipv4Addr;
+use std::process::Command;
+use std::time::Duration;
+
+/// Files whose links are audited, when they exist
+pub const FILES: &[&str] = &[
+    "README.md",
+    "README.adoc",
+    "SECURITY.md",
+    "CONTRIBUTING.md",
+    "CONTRIBUTING.adoc",
+];
+
+/// Longest a single liveness request may take
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Extra time a command gets to honor its own timeout before it is killed
+const KILL_GRACE: Duration = Duration::from_secs(1);
+
+/// A URL found in a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub url: String,
+    /// 1-based line number
+    pub line: usize,
+}
+
+/// Characters that end a URL in Markdown or AsciiDoc text
+fn ends_url(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '<' | '>' | '"' | '\'' | '`' | '[' | ']' | '{' | '}' | '|')
+}
+
+/// The `http://` and `https://` URLs in `text`, in order
+///
+/// A closing parenthesis ends a URL unless the URL opened one, so both
+/// `[text](https://example.org)` and Wikipedia-style links come out whole;
+/// trailing sentence punctuation is not part of the URL.
+pub fn extract(text: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let lower = line.to_ascii_lowercase();
+        let mut from = 0;
+        while let Some(found) = lower[from..].find("http") {
+            let start = from + found;
+            from = start + 4;
+            let rest = &lower[start..];
+            if !(rest.starts_with("http://") || rest.starts_with("https://")) {
+                continue;
+            }
+            let preceded = line[..start].chars().next_back();
+            if preceded.map_or(false, |c| c.is_alphanumeric()) {
+                continue;
+            }
+            let mut depth = 0usize;
+            let mut end = line.len();
+            for (offset, c) in line[start..].char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' if depth > 0 => depth -= 1,
+                    ')' => {
+                        end = start + offset;
+                        break;
+                    }
+                    c if ends_url(c) => {
+                        end = start + offset;
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            let url = line[start..end].trim_end_matches(['.', ',', ';', ':', '!', '?', '*', '_']);
+            from = end.max(from);
+            // A bare scheme is prose or a placeholder such as https://<host>
+            if url.ends_with("://") {
+                continue;
+            }
+            links.push(Link {
+                url: url.to_string(),
+                line: index + 1,
+            });
+        }
+    }
+    links
+}
+
+/// Host and port of an `http://` or `https://` URL
+fn authority(url: &str) -> (&str, Option<&str>) {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    if let Some(literal) = authority.strip_prefix('[') {
+        return match literal.split_once(']') {
+            Some((host, port)) => (host, port.strip_prefix(':')),
+            None => (literal, None),
+        };
+    }
+    match authority.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (authority, None),
+    }
+}
+
+/// Whether `host` is a syntactically valid host name: dot-separated labels
+/// of letters, digits and inner hyphens (international names included)
+pub(crate) fn is_valid_host(host: &str) -> bool {
+    let valid_label = |label: &str| {
+        !label.is_empty()
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_alphanumeric() || c == '-' || !c.is_ascii())
+    };
+    host.split('.').all(valid_label)
+}
+
+/// Host of an `http://` or `https://` URL
+pub(crate) fn host(url: &str) -> &str {
+    authority(url).0
+}
+
+/// Whether `host` is the local machine
+fn is_loopback(host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    host == "localhost"
+        || host.ends_with(".localhost")
+        || host == "::1"
+        || host.parse::<Ipv4Addr>().map_or(false, |ip| ip.is_loopback())
+}
+
+/// Why `url` breaks the link policy, or `None` if it does not
+pub fn problem(url: &str) -> Option<String> {
+    let (host, port) = authority(url);
+    // Template variables such as https://$HOST/ are not links yet
+    if host.contains('$') || is_loopback(host) {
+        return None;
+    }
+    if host.is_empty() {
+        return Some("has no host".to_string());
+    }
+    if let Some(port) = port {
+        if port.parse::<u16>().is_err() {
+            return Some(format!("has an invalid port '{}'", port));
+        }
+    }
+    if host.contains(':') || host.parse::<Ipv4Addr>().is_ok() {
+        return Some("points at a raw IP address instead of a host name".to_string());
+    }
+    let host = host.strip_suffix('.').unwrap_or(host);
+    if !is_valid_host(host) {
+        return Some(format!("has an invalid host '{}'", host));
+    }
+    if url.get(..5).map_or(false, |scheme| scheme.eq_ignore_ascii_case("http:")) {
+        return Some("uses http:// instead of https://".to_string());
+    }
+    None
+}
+
+/// Whether the online check requests `url`; links to the local machine and
+/// template variables are not requested
+pub fn is_probed(url: &str) -> bool {
+    let (host, _) = authority(url);
+    !host.contains('$') && !is_loopback(host)
+}
+
+/// Request `url` and report why it is not live
+///
+/// A `HEAD` request comes first; servers that refuse `HEAD` are asked again
+/// with `GET`. Redirects are followed. Each request may take `timeout`.
+pub fn probe(url: &str, timeout: Duration) -> Result<(), String> {
+    let status = |head: bool| -> Result<u16, String> {
+        let mut command = Command::new("curl");
+        command.args(["-sS", "-L", "-o", "/dev/null", "-w", "%{http_code}"]);
+        command.arg("--max-time").arg(format!("{:.3}", timeout.as_secs_f64()));
+        if head {
+            command.arg("-I");
+        }
+        command.arg("--").arg(url);
+        // curl enforces the timeout itself; the kill is for a curl that hangs
+        let output = subprocess::run(&mut command, None, timeout + KILL_GRACE).map_err(|e| {
+            match e.kind() {
+                io::ErrorKind::TimedOut => e.to_string(),
+                _ => format!("cannot run curl: {}", e),
+            }
+        })?;
+        let code = String::from_utf8_lossy(&output.stdout).trim().parse().unwrap_or(0);
+        if code == 0 {
+            // curl: (6) Could not resolve host: example.org
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = stderr.trim().trim_start_matches("curl: ");
+            let message = message
+                .strip_prefix('(')
+                .and_then(|m| m.split_once(") "))
+                .map_or(message, |(_, m)| m);
+            return Err(message.to_string());
+        }
+        Ok(code)
+    };
+    let code = match status(true)? {
+        405 | 501 => status(false)?,
+        code => code,
+    };
+    if code < 400 {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let text = "# Demo\n\
+                    See [the docs](https://example.org/docs). Or <HTTPS://example.org/a>,\n\
+                    https://en.wikipedia.org/wiki/Rust_(programming_language) and\n\
+                    link:https://example.org/x[AsciiDoc]; not xhttp://a or https://<host>\n";
+        let links = extract(text);
+        let urls: Vec<(&str, usize)> = links.iter().map(|l| (l.url.as_str(), l.line)).collect();
+        assert_eq!(
+            urls,
+            [
+                ("https://example.org/docs", 2),
+                ("HTTPS://example.org/a", 2),
+                ("https://en.wikipedia.org/wiki/Rust_(programming_language)", 3),
+                ("https://example.org/x", 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_problem() {
+        for url in [
+            "https://example.org/path?q=1#top",
+            "https://user@example.org:8443/",
+            "https://bücher.example/",
+            "http://localhost:8080/",
+            "http://127.0.0.1:3000",
+            "http://[::1]/",
+            "https://$HOST/api",
+        ] {
+            assert_eq!(problem(url), None, "{}", url);
+        }
+        let problem = |url: &str| problem(url).unwrap();
+        assert_eq!(problem("http://example.org"), "uses http:// instead of https://");
+        assert!(problem("https://192.0.2.7/report").contains("raw IP address"));
+        assert!(problem("https://[2001:db8::1]:8443/").contains("raw IP address"));
+        assert_eq!(problem("https://example.org:80a/"), "has an invalid port '80a'");
+        assert_eq!(problem("https://exa_mple..org/"), "has an invalid host 'exa_mple..org'");
+        assert_eq!(problem("https:///path"), "has no host");
+
+        assert!(is_probed("https://example.org/"));
+        assert!(!is_probed("http://localhost/"));
+    }
+}
@@ -0,0 +1,91 @@
+//! Diagnostic logging hooks
+//!
+//! The verification engine reports what it is doing through the [`Logger`]
+//! trait: each check start and finish, and every security warning as it is
+//! raised. Embedders implement the trait to route these events into their own
+//! logging system; [`StderrLogger`] and [`NoopLogger`] cover the common cases.
+
+use std::fmt;
+
+/// Severity of a log message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Parse a level name (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "debug" => Some(LogLevel::Debug),
+            "info" => Some(LogLevel::Info),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "error" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Receiver for diagnostics emitted during verification
+pub trait Logger {
+    /// Record one message
+    fn log(&self, level: LogLevel, message: &str);
+}
+
+/// Writes messages at or above `min_level` to stderr
+#[derive(Debug, Clone, Copy)]
+pub struct StderrLogger {
+    pub min_level: LogLevel,
+}
+
+impl StderrLogger {
+    /// Create a logger that prints messages at `min_level` and above
+    pub fn new(min_level: LogLevel) -> Self {
+        Self { min_level }
+    }
+}
+
+impl Logger for StderrLogger {
+    fn log(&self, level: LogLevel, message: &str) {
+        if level >= self.min_level {
+            eprintln!("[rhodibot {}] {}", level, message);
+        }
+    }
+}
+
+/// Discards every message
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopLogger;
+
+impl Logger for NoopLogger {
+    fn log(&self, _level: LogLevel, _message: &str) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_level_parse_and_order() {
+        assert_eq!(LogLevel::parse("WARNING"), Some(LogLevel::Warn));
+        assert_eq!(LogLevel::parse("debug"), Some(LogLevel::Debug));
+        assert_eq!(LogLevel::parse("trace"), None);
+        assert!(LogLevel::Error > LogLevel::Info);
+        assert_eq!(LogLevel::Warn.to_string(), "WARN");
+    }
+}
@@ -3,13 +3,47 @@
 //! A command-line tool for verifying Rhodium Standard Repository compliance.
 //! Like Dependabot but for repository standards instead of dependencies.
 
+use rhodibot::args::{Command, Opt, Parsed, Spec};
+use rhodibot::batch::{parse_repo_list, BatchReport};
+use rhodibot::bench::{bench_verification, BenchStats, DEFAULT_ITERATIONS, WARMUP_ITERATIONS};
+use rhodibot::bot::issues::{self, IssueAction};
+use rhodibot::bot::{self, github_actions, pull_request, CIPlatform};
+use rhodibot::fix::{self, Fix};
+use rhodibot::builder::ReportBuilder;
+use rhodibot::config::pack::RulePack;
+use rhodibot::config::Config;
+use rhodibot::conformity;
+use rhodibot::dashboard::{write_dashboard, DEFAULT_DASHBOARD_DIR};
+use rhodibot::env::{self as env_vars, EnvConfig};
+use rhodibot::hook::{self, HookKind};
+use rhodibot::logging::{LogLevel, Logger, NoopLogger, StderrLogger};
+use rhodibot::policy::{Outcome, Policy, Verdict};
+use rhodibot::output::{
+    duration_ms, identity_line, relative_display, render_code_quality, render_json_report,
+    render_json_summary, render_porcelain, render_prometheus, render_sarif, render_tap,
+    write_atomic, SLOWEST_CHECKS,
+};
+use rhodibot::progress::{NoProgress, Progress, ProgressSink};
+use rhodibot::readme;
+use rhodibot::remote::RemoteCheckout;
+use rhodibot::serve::{serve, Request, Response, DEFAULT_LISTEN};
+use rhodibot::sign::{self, Key, Signature};
+use rhodibot::standard;
+use rhodibot::state::{self, ComplianceState, StateChange};
+use rhodibot::style::{Color, ColorChoice, Icon, Style};
+use rhodibot::suppressions::{Suppressions, SUPPRESSIONS_FILE};
 use rhodibot::{
-    exit_codes, format_timestamp, generate_badge, generate_conformity_doc, json_escape,
-    verify_repository, BotAction, BotConfig, ComplianceLevel, ComplianceReport, OutputFormat,
-    Verbosity, WarningLevel, VERSION,
+    compute_exit_code, exit_codes, generate_badge, generate_conformity_doc, json,
+    verify_repository_with_hooks, BotAction, BotConfig, CheckResult, CheckStatus,
+    ComplianceLevel, ComplianceReport, Hooks, OutputFormat, SecurityWarning, StandardVersion,
+    Verbosity, WarningLevel, STANDARD_VERSION, VERSION,
 };
-use std::path::PathBuf;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::time::Duration;
 
 /// CLI options
 struct CliOptions {
@@ -17,46 +51,546 @@ struct CliOptions {
     format: OutputFormat,
     verbosity: Verbosity,
     action: BotAction,
+    output: Option<PathBuf>,
+    config_path: Option<PathBuf>,
+    /// Rule pack directories (`--rules`)
+    rule_packs: Vec<PathBuf>,
+    /// `--fail-on-warning` or `RHODIBOT_FAIL_ON_WARNING`; `None` keeps the
+    /// config file's policy
+    fail_on_warning: Option<bool>,
+    /// `--distinguish-waived`; `false` keeps the config file's policy
+    distinguish_waived: bool,
+    /// Required compliance level (`--level` or `RHODIBOT_LEVEL`)
+    level: Option<ComplianceLevel>,
+    /// Hook to write (`install-hook`)
+    hook: Option<HookKind>,
+    /// CI platform to write the configuration for (`setup-ci`)
+    setup_ci: Option<CIPlatform>,
+    /// Replace an existing CI configuration (`setup-ci --force`)
+    force: bool,
+    /// State file for `drift` (default: <PATH>/.rhodibot/state.json)
+    state: Option<PathBuf>,
+    upload_sarif: bool,
+    /// Commit the files `fix` writes and open a pull request (`--create-pr`)
+    create_pr: bool,
+    /// Open, update or close the tracking issue (`--track-issue`)
+    track_issue: bool,
+    remote: Option<String>,
+    log_level: Option<LogLevel>,
+    /// CI platform to emit extras for; `None` means auto-detect
+    ci: Option<CIPlatform>,
+    /// Address for `serve`
+    listen: String,
+    /// Directory `serve` confines `GET /check` paths to (`--allow-root`)
+    allow_root: Option<PathBuf>,
+    /// OTLP/JSON span file (`otel` feature)
+    otel_file: Option<PathBuf>,
+    /// OTLP collector base URL (`otel` feature)
+    otel_endpoint: Option<String>,
+    /// Measured iterations for `bench`
+    iterations: u32,
+    /// Check IDs to leave out (`--skip`)
+    skip: Vec<String>,
+    /// Categories to restrict the run to (`--only-category`)
+    only_categories: Vec<String>,
+    /// Stop at the first failing required check (`--fail-fast`)
+    fail_fast: bool,
+    /// Request every audited link (`--check-links`)
+    check_links: bool,
+    /// RSR standard version overriding the config's `standard_version`
+    standard_version: Option<StandardVersion>,
+    /// Arguments of `standard` (e.g. `show bronze`) and `verify-report`
+    command_args: Vec<String>,
+    /// Key to sign the `--output` file with (`--sign`)
+    sign: Option<Key>,
+    /// Key to check a signature with (`verify-report --key`)
+    key: Option<Key>,
+    /// Icons and colors for human output (`--no-emoji`, `--color`,
+    /// `--accessible`)
+    style: Style,
+    /// Canonicalize reports (`--reproducible`)
+    reproducible: bool,
+    /// Relative repository and warning paths (`--relative-paths`, or a
+    /// format that needs them)
+    relative_paths: bool,
+    /// Maintain the badge section of README.md (`badge --update-readme`)
+    update_readme: bool,
+    /// Write CONFORMITY.md into the repository (`conformity --write`)
+    write: bool,
+    /// Report metadata as `(key, value)` (`--metadata`)
+    metadata: Vec<(String, String)>,
 }
 
-/// Print help message
-fn print_help() {
-    println!(
-        r#"Rhodibot - RSR Compliance Bot
+/// Command-line interface
+const CLI: Spec = Spec {
+    name: "rhodibot",
+    version: VERSION,
+    about: "Rhodibot - RSR Compliance Bot\n\nLike Dependabot but for Rhodium Standard Repository compliance.",
+    commands: &[
+        Command {
+            name: "check",
+            help: "Check RSR compliance (default)",
+        },
+        Command {
+            name: "drift",
+            help: "Check RSR compliance and compare with the state recorded by the\n\
+                   last run (--state); fails only on new failures or a lower\n\
+                   level, for scheduled jobs",
+        },
+        Command {
+            name: "badge",
+            help: "Generate RSR badge markdown",
+        },
+        Command {
+            name: "conformity",
+            help: "Generate RSR conformity document",
+        },
+        Command {
+            name: "fix",
+            help: "Write starter files for failing checks (--create-pr to open\n\
+                   a pull request with them)",
+        },
+        Command {
+            name: "batch",
+            help: "Check every repository listed in a file (one path per line)",
+        },
+        Command {
+            name: "dashboard",
+            help: "Write an HTML dashboard for a repository list to the --output\n\
+                   directory (default: rhodibot-dashboard)",
+        },
+        Command {
+            name: "bench",
+            help: "Time repeated in-process verifications and report min, avg,\n\
+                   p95 and max (human or json)",
+        },
+        Command {
+            name: "serve",
+            help: "Answer verification requests over HTTP until interrupted:\n\
+                   \x20 GET  /check?path=<dir>   verify a local directory\n\
+                   \x20 POST /check?name=<label> verify an uploaded tar(.gz) archive\n\
+                   \x20 GET  /health             liveness probe\n\
+                   Responses are JSON reports",
+        },
+        Command {
+            name: "standard",
+            help: "Print the requirement catalog of the RSR standard version\n\
+                   (--standard-version, default: latest) without checking a\n\
+                   repository:\n\
+                   \x20 standard list               every check with its level\n\
+                   \x20 standard show <LEVEL|ID>    requirements for a level, or\n\
+                   \x20                             one check in full\n\
+                   \x20 standard export -f json     the whole catalog as JSON (id,\n\
+                   \x20                             category, level, description,\n\
+                   \x20                             remediation, files)",
+        },
+        Command {
+            name: "verify-report",
+            help: "Check a report against its detached signature:\n\
+                   \x20 verify-report REPORT [SIG] --key KEY\n\
+                   SIG defaults to REPORT.sig",
+        },
+        Command {
+            name: "install-hook",
+            help: "Install a git hook running a quiet compliance check:\n\
+                   \x20 install-hook pre-commit|pre-push [PATH] [--level LEVEL]",
+        },
+        Command {
+            name: "generate-action",
+            help: "Write a GitHub Actions composite action (action.yml) wrapping\n\
+                   rhodibot into PATH, or to --output FILE",
+        },
+        Command {
+            name: "setup-ci",
+            help: "Write a CI configuration running rhodibot into the repository:\n\
+                   \x20 setup-ci github [PATH]   .github/workflows/rhodibot.yml\n\
+                   \x20 setup-ci gitlab [PATH]   .gitlab-ci.yml\n\
+                   An existing file is only replaced with --force",
+        },
+    ],
+    positional: Some((
+        "PATH",
+        "Repository path to verify (default: current directory), or\n\
+         the repository list file for `batch` and `dashboard`",
+    )),
+    options: &[
+        Opt::value(
+            "format",
+            Some('f'),
+            "FORMAT",
+            "Output format: human, json, sarif, tap, codequality,\n\
+             prometheus, markdown (default: human)",
+        ),
+        Opt::flag(
+            "porcelain",
+            None,
+            "check: stable tab-separated lines for scripts\n\
+             (check<TAB>ID<TAB>pass|fail|waived, ...)",
+        ),
+        Opt::value(
+            "output",
+            Some('o'),
+            "FILE",
+            "Write output to FILE (atomic write) instead of stdout",
+        ),
+        Opt::value(
+            "config",
+            Some('c'),
+            "FILE",
+            "Configuration file (default: <PATH>/.rhodibot.toml)",
+        ),
+        Opt::value(
+            "rules",
+            None,
+            "DIR",
+            "Add the [rules.NAME] files of a rule pack\n\
+             directory (repeatable); its check IDs are\n\
+             prefixed with the directory name",
+        ),
+        Opt::value(
+            "level",
+            None,
+            "LEVEL",
+            "Compliance level required for success: bronze,\n\
+             silver, gold, platinum (overrides fail_on_level)",
+        ),
+        Opt::flag(
+            "fail-on-warning",
+            None,
+            "Exit with code 2 on Warning-level security warnings",
+        ),
+        Opt::flag(
+            "distinguish-waived",
+            None,
+            "Exit with code 5 when the required level is met\n\
+             only because of waivers",
+        ),
+        Opt::flag(
+            "write",
+            None,
+            "conformity: write CONFORMITY.md into the\n\
+             repository (checked for drift by RSR-CNF-001)",
+        ),
+        Opt::value(
+            "state",
+            None,
+            "FILE",
+            "drift: state file to compare with and update\n\
+             (default: <PATH>/.rhodibot/state.json)",
+        ),
+        Opt::flag(
+            "force",
+            None,
+            "setup-ci: replace an existing CI configuration",
+        ),
+        Opt::flag(
+            "create-pr",
+            None,
+            "fix: commit the files to a branch and open a pull\n\
+             request (in CI, with GITHUB_TOKEN or GITLAB_TOKEN)",
+        ),
+        Opt::flag(
+            "update-readme",
+            None,
+            "badge: write the badge and a compliance summary\n\
+             between <!-- rhodibot:start/end --> markers in\n\
+             README.md",
+        ),
+        Opt::flag(
+            "reproducible",
+            None,
+            "Leave timestamps, timings and absolute paths out\n\
+             of reports, so identical repositories give\n\
+             byte-identical output",
+        ),
+        Opt::flag(
+            "relative-paths",
+            None,
+            "Show the repository and warning paths relative to\n\
+             the repository root (always on for sarif and\n\
+             codequality)",
+        ),
+        Opt::value(
+            "metadata",
+            None,
+            "KEY=VALUE",
+            "Attach metadata to the report (repeatable; e.g.\n\
+             commit=SHA, branch=NAME, ci_run_url=URL); shown\n\
+             in JSON, SARIF and dashboard output",
+        ),
+        Opt::value(
+            "sign",
+            None,
+            "KEY",
+            "Write a detached signature of the --output file to\n\
+             FILE.sig: HMAC-SHA256 with a shared-secret file, or\n\
+             Ed25519 with a PEM key ('ed25519' feature)",
+        ),
+        Opt::value(
+            "key",
+            None,
+            "KEY",
+            "Secret or Ed25519 public key for verify-report",
+        ),
+        Opt::value(
+            "standard-version",
+            None,
+            "VERSION",
+            "RSR standard version to check against (default:\n\
+             the config's standard_version, else the latest)",
+        ),
+        Opt::flag(
+            "upload-sarif",
+            None,
+            "Upload SARIF results to GitHub code scanning\n\
+             (needs GITHUB_TOKEN; uses the system curl)",
+        ),
+        Opt::flag(
+            "track-issue",
+            None,
+            "Keep a tracking issue for failing checks on GitHub\n\
+             or GitLab (needs GITHUB_TOKEN or GITLAB_TOKEN)",
+        ),
+        Opt::value(
+            "log-level",
+            None,
+            "LEVEL",
+            "Log check progress and security events to stderr\n\
+             (error, warn, info, debug)",
+        ),
+        Opt::value(
+            "remote",
+            None,
+            "URL",
+            "Shallow-clone URL with the system git into a\n\
+             temporary directory and verify that instead",
+        ),
+        Opt::value(
+            "listen",
+            None,
+            "ADDR",
+            "Address for `serve` (default: 127.0.0.1:8080)",
+        ),
+        Opt::value(
+            "allow-root",
+            None,
+            "DIR",
+            "Only let `serve` check paths inside DIR\n\
+             (default: any path the server can read)",
+        ),
+        Opt::value(
+            "iterations",
+            None,
+            "N",
+            "Measured iterations for `bench` (default: 100)",
+        ),
+        Opt::value(
+            "otel-file",
+            None,
+            "FILE",
+            "Write OTLP/JSON spans for the run to FILE",
+        ),
+        Opt::value(
+            "otel-endpoint",
+            None,
+            "URL",
+            "POST OTLP/JSON spans to a collector (e.g.\n\
+             http://localhost:4318; uses the system curl)\n\
+             (both need the 'otel' cargo feature)",
+        ),
+        Opt::value(
+            "only-category",
+            None,
+            "NAME",
+            "Run only checks in this category (repeatable;\n\
+             e.g. Documentation, Well-Known, \"Build System\")",
+        ),
+        Opt::value(
+            "skip",
+            None,
+            "ID",
+            "Leave out a check by ID (repeatable; e.g.\n\
+             RSR-BLD-002); skipped checks are listed in the\n\
+             report",
+        ),
+        Opt::flag(
+            "fail-fast",
+            None,
+            "Stop at the first failing check required for the\n\
+             target level; later checks are reported as skipped",
+        ),
+        Opt::flag(
+            "check-links",
+            None,
+            "Also request every link in README, SECURITY and\n\
+             CONTRIBUTING and warn about those that are not live\n\
+             (needs network access and curl)",
+        ),
+        Opt::value(
+            "ci",
+            None,
+            "PLATFORM",
+            "CI extras to emit: auto, none, github, gitlab,\n\
+             teamcity, jenkins, circleci, azure, buildkite\n\
+             (default: auto, detected from the environment)",
+        ),
+        Opt::flag(
+            "quiet",
+            Some('q'),
+            "Quiet mode: only show pass/fail result (with json:\n\
+             the summary object on one line)",
+        ),
+        Opt::flag(
+            "summary",
+            None,
+            "Summary mode: pass counts per category and the\n\
+             verdict, without the individual checks",
+        ),
+        Opt::flag("verbose", Some('v'), "Verbose mode: show all details"),
+        Opt::value(
+            "color",
+            None,
+            "WHEN",
+            "Color human output: auto, always, never (default:\n\
+             auto - only on a terminal and without NO_COLOR)",
+        ),
+        Opt::flag(
+            "no-emoji",
+            None,
+            "Use ASCII icons and rules instead of emoji",
+        ),
+        Opt::flag(
+            "accessible",
+            None,
+            "Screen reader friendly output: words instead of\n\
+             icons, no colors or rules, one check per line",
+        ),
+        Opt::flag(
+            "health",
+            None,
+            "Print a one-line JSON status and exit 0 without\n\
+             checking anything (container health probe)",
+        ),
+        Opt::value(
+            "explain-exit",
+            None,
+            "CODE",
+            "Print what exit code CODE (0-5) means and when\n\
+             it is returned",
+        ),
+    ],
+    after_help: r#"EXIT CODES:
+    0    Success - required compliance level achieved (Bronze by default)
+    1    Failure - required level or minimum percentage not met
+    2    Security - Critical security warnings detected (or warnings with
+         fail_on_warning)
+    3    Error - Invalid path provided
+    4    Error - Invalid arguments
+    5    Waived - required level met only because of active waivers (with
+         distinguish_waived or --distinguish-waived; 0 otherwise)
 
-Like Dependabot but for Rhodium Standard Repository compliance.
+    rhodibot --explain-exit <CODE> says when a code is returned.
 
-USAGE:
-    rhodibot [COMMAND] [OPTIONS] [PATH]
+CONFIGURATION:
+    .rhodibot.toml in the repository root controls the exit-code policy
+    and which checks run:
 
-COMMANDS:
-    check       Check RSR compliance (default)
-    badge       Generate RSR badge markdown
-    conformity  Generate RSR conformity document
+    standard_version = "1.0"        # pin the RSR standard version
 
-ARGS:
-    [PATH]    Repository path to verify (default: current directory)
+    [policy]
+    fail_on_warning = true          # warnings also fail the run
+    fail_below_percentage = 90      # minimum pass percentage
+    fail_on_level = "silver"        # level required for success
+    distinguish_waived = true       # exit 5 if only waivers meet it
 
-OPTIONS:
-    -f, --format <FORMAT>    Output format: human, json (default: human)
-    -q, --quiet              Quiet mode: only show pass/fail result
-    -v, --verbose            Verbose mode: show all details
-    -h, --help               Print help information
-    -V, --version            Print version information
+    [checks]
+    disable = ["RSR-BLD-002"]       # skip checks by ID (see --verbose)
 
-EXIT CODES:
-    0    Success - Bronze compliance achieved
-    1    Failure - Bronze compliance not met
-    2    Security - Critical security warnings detected
-    3    Error - Invalid path provided
-    4    Error - Invalid arguments
+    [checks.alternates]
+    "RSR-DOC-002" = ["COPYING"]     # further names a check accepts
+
+    [documentation]
+    min_bytes = 64                  # shorter required docs fail
+    min_lines = 3                   # as do ones with fewer non-blank lines
+    detect_placeholders = true      # and ones holding only TODO/lorem ipsum
+
+    [repository]
+    type = "docs"                   # code, docs, data or meta; which apply
+
+    [limits]
+    check_timeout = 30              # seconds before a check is an error
+    run_timeout = 120               # seconds before the rest are errors
+
+    [security]
+    allow_symlink_targets = ["../shared"]  # links here are not escapes
+
+    [links]
+    online = false                  # also request links (--check-links)
+    allow = ["http://old.example/"] # URL prefixes never reported
+
+    [site]
+    web_root = "public"             # where .well-known/ is published
+
+    [well_known]
+    "dnt-policy.txt" = "silver"     # extra .well-known entry and its level
+
+    [rules.architecture]            # organization rule, ID custom/architecture
+    path = "docs/ARCHITECTURE.md"   # file; a trailing / asks for a directory
+    category = "Documentation"
+    level = "silver"                # default bronze
+    description = "Architecture overview"
+    must_contain = "Components"     # files only: required text, and lines
+    must_match = "(?i)^## *design"  # matching a pattern (* ? ^ $ (?i))
+
+    .rhodibot/suppressions.toml waives failing checks with a justification;
+    expired waivers stop applying:
+
+    [RSR-WKN-003]
+    reason = "AI policy under legal review"
+    expires = "2026-06-30"
+
+ENVIRONMENT:
+    Variables override .rhodibot.toml; flags override variables:
+
+    RHODIBOT_FORMAT             --format
+    RHODIBOT_LEVEL              --level
+    RHODIBOT_CONFIG             --config
+    RHODIBOT_FAIL_ON_WARNING    --fail-on-warning (true or false)
+    RHODIBOT_STANDARD_VERSION   --standard-version
+    RHODIBOT_LOG_LEVEL          --log-level
+    RHODIBOT_CI                 --ci
+    NO_COLOR                    disable ANSI colors
+    SOURCE_DATE_EPOCH           verification time (seconds since 1970)
 
 EXAMPLES:
     rhodibot                         # Check current directory
     rhodibot check /path/to/repo     # Check specific repository
     rhodibot badge                   # Generate badge for current directory
+    rhodibot badge --update-readme   # Refresh the badge section of README.md
     rhodibot conformity              # Generate conformity document
+    rhodibot conformity --write      # Commit-ready CONFORMITY.md in the repository
     rhodibot --format json           # Output as JSON
+    rhodibot check --only-category Documentation --skip RSR-DOC-006
+    rhodibot check --rules ../org-policies/
+    rhodibot check . -f json -o report.json
+    rhodibot check --remote https://github.com/hyperpolymath/aletheia
+    rhodibot batch repos.txt -f json -o fleet.json
+    rhodibot dashboard repos.txt -o public/
+    rhodibot serve --listen 127.0.0.1:8080 --allow-root /srv/repos
+    rhodibot bench . --iterations 500 -f json
+    rhodibot standard show bronze
+    rhodibot standard export --format json -o rsr-requirements.json
+    rhodibot conformity -o RSR-CONFORMITY.md --sign ci-secret
+    rhodibot check . -f json --reproducible -o report.json
+    rhodibot check . -f sarif --metadata commit=$GITHUB_SHA --metadata branch=main
+    rhodibot verify-report RSR-CONFORMITY.md --key ci-secret
+    rhodibot install-hook pre-push --level silver
+    rhodibot check --fail-fast --level silver --quiet
+    rhodibot check --check-links
+    rhodibot generate-action -o ci/rhodibot/action.yml
+    rhodibot setup-ci github
+    rhodibot fix --create-pr
+    rhodibot drift --state .cache/rhodibot-state.json
 
 CI/CD INTEGRATION:
     # GitHub Actions
@@ -69,295 +603,1746 @@ fn print_help() {
     rhodibot:
       image: hyperpolymath/rhodibot:latest
       script:
-        - rhodibot check .
-"#
-    );
+        - rhodibot check . --format codequality -o gl-code-quality.json
+      artifacts:
+        reports:
+          codequality: gl-code-quality.json
+
+    # Prometheus (node_exporter textfile collector)
+    rhodibot check . -f prometheus -o /var/lib/node_exporter/rhodibot.prom
+"#,
+};
+
+/// Parse an output format name
+fn parse_format(name: &str) -> Result<OutputFormat, String> {
+    match name {
+        "human" => Ok(OutputFormat::Human),
+        "json" => Ok(OutputFormat::Json),
+        "tap" => Ok(OutputFormat::Tap),
+        "sarif" => Ok(OutputFormat::Sarif),
+        "codequality" => Ok(OutputFormat::CodeQuality),
+        "prometheus" => Ok(OutputFormat::Prometheus),
+        "markdown" => Ok(OutputFormat::Markdown),
+        other => Err(format!(
+            "Unknown format: {}. Use 'human', 'json', 'sarif', 'tap', 'codequality', \
+             'prometheus' or 'markdown'",
+            other
+        )),
+    }
+}
+
+/// Parse a `--log-level` value
+fn parse_log_level(name: &str) -> Result<LogLevel, String> {
+    LogLevel::parse(name).ok_or_else(|| {
+        format!(
+            "Unknown log level: {}. Use 'error', 'warn', 'info' or 'debug'",
+            name
+        )
+    })
 }
 
-/// Print version information
-fn print_version() {
-    println!("rhodibot {}", VERSION);
+/// Parse a `--ci` value; `auto` yields `None`
+fn parse_ci(name: &str) -> Result<Option<CIPlatform>, String> {
+    match name {
+        "auto" => Ok(None),
+        "none" => Ok(Some(CIPlatform::Unknown)),
+        "github" => Ok(Some(CIPlatform::GitHubActions)),
+        "gitlab" => Ok(Some(CIPlatform::GitLabCI)),
+        "teamcity" => Ok(Some(CIPlatform::TeamCity)),
+        "jenkins" => Ok(Some(CIPlatform::Jenkins)),
+        "circleci" => Ok(Some(CIPlatform::CircleCI)),
+        "azure" => Ok(Some(CIPlatform::AzureDevOps)),
+        "buildkite" => Ok(Some(CIPlatform::Buildkite)),
+        other => Err(format!(
+            "Unknown CI platform: {}. Use 'auto', 'none', 'github', 'gitlab', 'teamcity', \
+             'jenkins', 'circleci', 'azure' or 'buildkite'",
+            other
+        )),
+    }
+}
+
+/// Parse an `--iterations` value
+fn parse_iterations(value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!("Invalid iteration count: {}. Use a positive number", value)),
+    }
+}
+
+/// Parse a `--skip` value: one or more comma-separated check IDs
+fn parse_skip(value: &str, skip: &mut Vec<String>) -> Result<(), String> {
+    for id in value.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+        let spec = rhodibot::catalog::lookup(id)
+            .ok_or_else(|| format!("Unknown check ID: {}. Use --verbose to list check IDs", id))?;
+        skip.push(spec.id.to_string());
+    }
+    Ok(())
+}
+
+/// Parse a `--metadata` value: `KEY=VALUE`
+fn parse_metadata(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("Invalid metadata: {}. Use KEY=VALUE", value)),
+    }
+}
+
+/// Parse an `--only-category` value: one or more comma-separated categories
+fn parse_only_category(value: &str, categories: &mut Vec<String>) -> Result<(), String> {
+    for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let category = rhodibot::catalog::find_category(name).ok_or_else(|| {
+            format!(
+                "Unknown category: {}. Use {}",
+                name,
+                rhodibot::catalog::categories()
+                    .iter()
+                    .map(|c| format!("'{}'", c))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+        categories.push(category.to_string());
+    }
+    Ok(())
+}
+
+/// One-line JSON naming the tool and version, followed by `fields`
+///
+/// Printed by `--health` and as the `serve` startup banner, for container
+/// probes and supervisors that parse stdout.
+fn status_json(fields: &[(&str, &str)]) -> String {
+    let mut out = format!(
+        "{{\"tool\":\"rhodibot\",\"version\":\"{}\",\"standard_version\":\"{}\"",
+        VERSION, STANDARD_VERSION
+    );
+    for (key, value) in fields {
+        out.push_str(&format!(",\"{}\":{}", key, json::string(value)));
+    }
+    out.push('}');
+    out
 }
 
 /// Parse command line arguments
 fn parse_args() -> Result<CliOptions, String> {
-    let args: Vec<String> = std::env::args().collect();
-    let mut format = OutputFormat::Human;
-    let mut verbosity = Verbosity::Normal;
-    let mut repo_path: Option<PathBuf> = None;
-    let mut action = BotAction::Check;
-
-    let mut i = 1;
-    while i < args.len() {
-        let arg = &args[i];
-        match arg.as_str() {
-            "-h" | "--help" => {
-                print_help();
-                process::exit(exit_codes::SUCCESS);
-            }
-            "-V" | "--version" => {
-                print_version();
-                process::exit(exit_codes::SUCCESS);
-            }
-            "-q" | "--quiet" => {
-                verbosity = Verbosity::Quiet;
-            }
-            "-v" | "--verbose" => {
-                verbosity = Verbosity::Verbose;
-            }
-            "-f" | "--format" => {
-                i += 1;
-                if i >= args.len() {
-                    return Err("--format requires an argument".to_string());
-                }
-                format = match args[i].as_str() {
-                    "human" => OutputFormat::Human,
-                    "json" => OutputFormat::Json,
-                    other => {
-                        return Err(format!("Unknown format: {}. Use 'human' or 'json'", other))
-                    }
-                };
-            }
-            "check" => action = BotAction::Check,
-            "badge" => action = BotAction::Badge,
-            "conformity" => action = BotAction::Conformity,
-            "fix" => action = BotAction::Fix,
-            arg if arg.starts_with('-') => {
-                if let Some(value) = arg.strip_prefix("--format=") {
-                    format = match value {
-                        "human" => OutputFormat::Human,
-                        "json" => OutputFormat::Json,
-                        other => {
-                            return Err(format!("Unknown format: {}. Use 'human' or 'json'", other))
-                        }
-                    };
-                } else {
-                    return Err(format!("Unknown option: {}", arg));
-                }
-            }
-            path => {
-                if repo_path.is_some() {
-                    return Err("Multiple paths provided. Only one path is allowed.".to_string());
-                }
-                repo_path = Some(PathBuf::from(path));
+    let matches = match CLI.parse(std::env::args().skip(1))? {
+        Parsed::Help => {
+            println!("{}", CLI.help());
+            process::exit(exit_codes::SUCCESS);
+        }
+        Parsed::Version => {
+            println!("{}", CLI.version_line());
+            process::exit(exit_codes::SUCCESS);
+        }
+        Parsed::Run(matches) => matches,
+    };
+    // A no-op, so a probe succeeds whatever else the environment holds
+    if matches.flag("health") {
+        println!("{}", status_json(&[("status", "ok")]));
+        process::exit(exit_codes::SUCCESS);
+    }
+    if let Some(code) = matches.value("explain-exit") {
+        let code: i32 = code
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid exit code '{}'", code))?;
+        let (Some(name), Some(text)) = (exit_codes::name(code), exit_codes::explain(code)) else {
+            return Err(format!("Unknown exit code {} (rhodibot exits with 0 to 5)", code));
+        };
+        println!("{} {}\n\n{}", code, name, text);
+        process::exit(exit_codes::SUCCESS);
+    }
+    // Environment variables fill in for flags that were not given
+    let env = EnvConfig::from_env()?;
+    let from_env = |name: &'static str| move |e: String| format!("{}: {}", name, e);
+
+    let action = match matches.command.as_deref() {
+        None | Some("check") => BotAction::Check,
+        Some("drift") => BotAction::Drift,
+        Some("badge") => BotAction::Badge,
+        Some("conformity") => BotAction::Conformity,
+        Some("fix") => BotAction::Fix,
+        Some("batch") => BotAction::Batch,
+        Some("dashboard") => BotAction::Dashboard,
+        Some("serve") => BotAction::Serve,
+        Some("bench") => BotAction::Bench,
+        Some("standard") => BotAction::Standard,
+        Some("verify-report") => BotAction::VerifyReport,
+        Some("install-hook") => BotAction::InstallHook,
+        Some("generate-action") => BotAction::GenerateAction,
+        Some("setup-ci") => BotAction::SetupCi,
+        Some(other) => return Err(format!("Unknown command: {}", other)),
+    };
+    let format = match (matches.value("format"), env.format.as_deref()) {
+        (Some(_), _) if matches.flag("porcelain") => {
+            return Err("--porcelain cannot be combined with --format".to_string());
+        }
+        _ if matches.flag("porcelain") => OutputFormat::Porcelain,
+        (Some(name), _) => parse_format(name)?,
+        (None, Some(name)) => parse_format(name).map_err(from_env(env_vars::FORMAT))?,
+        (None, None) => OutputFormat::Human,
+    };
+    let verbosity = match matches.last_of(&["quiet", "summary", "verbose"]) {
+        Some("quiet") => Verbosity::Quiet,
+        Some("summary") => Verbosity::Summary,
+        Some(_) => Verbosity::Verbose,
+        None => Verbosity::Normal,
+    };
+    let output = matches.value("output").map(PathBuf::from);
+    let config_path = matches.value("config").map(PathBuf::from).or(env.config);
+    let rule_packs = matches.values("rules").into_iter().map(PathBuf::from).collect();
+    let fail_on_warning = if matches.flag("fail-on-warning") {
+        Some(true)
+    } else {
+        env.fail_on_warning
+    };
+    let distinguish_waived = matches.flag("distinguish-waived");
+    let upload_sarif = matches.flag("upload-sarif");
+    let track_issue = matches.flag("track-issue");
+    let remote = matches.value("remote").map(str::to_string);
+    let log_level = match matches.value("log-level") {
+        Some(name) => Some(parse_log_level(name)?),
+        None => env.log_level,
+    };
+    let ci = match (matches.value("ci"), env.ci.as_deref()) {
+        (Some(name), _) => parse_ci(name)?,
+        (None, Some(name)) => parse_ci(name).map_err(from_env(env_vars::CI))?,
+        (None, None) => None,
+    };
+    let listen = matches.value("listen").map(str::to_string);
+    let allow_root = matches.value("allow-root").map(PathBuf::from);
+    let otel_file = matches.value("otel-file").map(PathBuf::from);
+    let otel_endpoint = matches.value("otel-endpoint").map(str::to_string);
+    let iterations = matches.value("iterations").map(parse_iterations).transpose()?;
+    let standard_version = match matches.value("standard-version") {
+        Some(version) => Some(version.parse::<StandardVersion>()?),
+        None => env.standard_version,
+    };
+    let color = matches
+        .value("color")
+        .map(str::parse::<ColorChoice>)
+        .transpose()?
+        .unwrap_or_default();
+    let is_terminal = output.is_none() && io::stdout().is_terminal();
+    let style = if matches.flag("accessible") {
+        Style::ACCESSIBLE
+    } else {
+        Style::detect(color, !matches.flag("no-emoji"), is_terminal)
+    };
+    let mut skip: Vec<String> = Vec::new();
+    for value in matches.values("skip") {
+        parse_skip(value, &mut skip)?;
+    }
+    let mut only_categories: Vec<String> = Vec::new();
+    for value in matches.values("only-category") {
+        parse_only_category(value, &mut only_categories)?;
+    }
+    let metadata = matches
+        .values("metadata")
+        .into_iter()
+        .map(parse_metadata)
+        .collect::<Result<Vec<_>, _>>()?;
+    let sign = matches.value("sign").map(|path| Key::load(Path::new(path))).transpose()?;
+    let key = matches.value("key").map(|path| Key::load(Path::new(path))).transpose()?;
+    let reproducible = matches.flag("reproducible");
+    let fail_fast = matches.flag("fail-fast");
+    let check_links = matches.flag("check-links");
+    // Code scanning services resolve locations against their own checkout
+    let relative_paths = matches.flag("relative-paths")
+        || matches!(format, OutputFormat::Sarif | OutputFormat::CodeQuality);
+    let level = match matches.value("level") {
+        Some(name) => Some(name.parse::<ComplianceLevel>()?),
+        None => env.level,
+    };
+    let update_readme = matches.flag("update-readme");
+    let write = matches.flag("write");
+    let force = matches.flag("force");
+    let create_pr = matches.flag("create-pr");
+    let state = matches.value("state").map(PathBuf::from);
+
+    // `standard` and `verify-report` take their own arguments; everything
+    // else takes one path
+    let mut positionals = matches.positionals;
+    let hook = if action == BotAction::InstallHook {
+        if positionals.is_empty() {
+            return Err("install-hook requires pre-commit or pre-push".to_string());
+        }
+        Some(positionals.remove(0).parse::<HookKind>()?)
+    } else {
+        None
+    };
+    let setup_ci = if action == BotAction::SetupCi {
+        match positionals.first().map(String::as_str) {
+            Some("github") => Some(CIPlatform::GitHubActions),
+            Some("gitlab") => Some(CIPlatform::GitLabCI),
+            Some(other) => {
+                return Err(format!("Unknown CI platform: {}. Use 'github' or 'gitlab'", other));
             }
+            None => return Err("setup-ci requires github or gitlab".to_string()),
+        }
+    } else {
+        None
+    };
+    if setup_ci.is_some() {
+        positionals.remove(0);
+    }
+    let own_args = matches!(action, BotAction::Standard | BotAction::VerifyReport);
+    let (repo_path, command_args) = if own_args {
+        (None, positionals)
+    } else if positionals.len() > 1 {
+        return Err("Multiple paths provided. Only one path is allowed.".to_string());
+    } else {
+        (positionals.pop().map(PathBuf::from), Vec::new())
+    };
+
+    if format == OutputFormat::Porcelain && action != BotAction::Check {
+        return Err("--porcelain is only valid with check".to_string());
+    }
+    if action == BotAction::Dashboard && repo_path.is_none() {
+        return Err("dashboard requires a repository list file".to_string());
+    }
+    if action == BotAction::Batch {
+        if repo_path.is_none() {
+            return Err("batch requires a repository list file".to_string());
+        }
+        if !matches!(format, OutputFormat::Human | OutputFormat::Json) {
+            return Err("batch supports only the 'human' and 'json' formats".to_string());
+        }
+        if remote.is_some() {
+            return Err("--remote cannot be combined with batch".to_string());
+        }
+    }
+
+    if action == BotAction::Serve {
+        if repo_path.is_some() || remote.is_some() {
+            return Err("serve takes no repository; pass paths per request".to_string());
+        }
+    } else if listen.is_some() {
+        return Err("--listen is only valid with serve".to_string());
+    } else if allow_root.is_some() {
+        return Err("--allow-root is only valid with serve".to_string());
+    }
+
+    if action == BotAction::Bench {
+        if !matches!(format, OutputFormat::Human | OutputFormat::Json) {
+            return Err("bench supports only the 'human' and 'json' formats".to_string());
+        }
+    } else if iterations.is_some() {
+        return Err("--iterations is only valid with bench".to_string());
+    }
+
+    if action == BotAction::Standard {
+        if remote.is_some() {
+            return Err("standard takes no repository".to_string());
+        }
+        let export = command_args.first().map(String::as_str) == Some("export");
+        if export && format != OutputFormat::Json {
+            return Err("standard export requires --format json".to_string());
+        }
+        if !export && format != OutputFormat::Human {
+            return Err("standard list and show support only the 'human' format".to_string());
         }
-        i += 1;
+    }
+
+    if sign.is_some() {
+        if !matches!(
+            action,
+            BotAction::Check | BotAction::Badge | BotAction::Conformity | BotAction::Batch
+        ) {
+            return Err("--sign is only valid with check, badge, conformity and batch".to_string());
+        }
+        if output.is_none() {
+            return Err("--sign requires --output FILE".to_string());
+        }
+    }
+    if action == BotAction::InstallHook && remote.is_some() {
+        return Err("--remote cannot be combined with install-hook".to_string());
+    }
+    if action == BotAction::GenerateAction && remote.is_some() {
+        return Err("--remote cannot be combined with generate-action".to_string());
+    }
+    if action == BotAction::Drift {
+        if !matches!(format, OutputFormat::Human | OutputFormat::Json | OutputFormat::Markdown) {
+            return Err(
+                "drift supports only the 'human', 'json' and 'markdown' formats".to_string()
+            );
+        }
+        if remote.is_some() && state.is_none() {
+            return Err("drift with --remote requires --state FILE".to_string());
+        }
+    } else if state.is_some() {
+        return Err("--state is only valid with drift".to_string());
+    }
+    if action == BotAction::SetupCi {
+        if remote.is_some() || output.is_some() {
+            return Err("--remote and --output cannot be combined with setup-ci".to_string());
+        }
+    } else if force {
+        return Err("--force is only valid with setup-ci".to_string());
+    }
+    if action == BotAction::Fix {
+        if remote.is_some() {
+            return Err("--remote cannot be combined with fix".to_string());
+        }
+    } else if create_pr {
+        return Err("--create-pr is only valid with fix".to_string());
+    }
+    if action == BotAction::VerifyReport {
+        if remote.is_some() {
+            return Err("verify-report takes no repository".to_string());
+        }
+        if key.is_none() {
+            return Err("verify-report requires --key".to_string());
+        }
+        if command_args.is_empty() || command_args.len() > 2 {
+            return Err("verify-report requires REPORT and optionally SIG".to_string());
+        }
+    } else if key.is_some() {
+        return Err("--key is only valid with verify-report".to_string());
+    }
+
+    if update_readme {
+        if action != BotAction::Badge {
+            return Err("--update-readme is only valid with badge".to_string());
+        }
+        if output.is_some() || remote.is_some() {
+            return Err("--update-readme cannot be combined with --output or --remote".to_string());
+        }
+    }
+
+    if write {
+        if action != BotAction::Conformity {
+            return Err("--write is only valid with conformity".to_string());
+        }
+        if output.is_some() || remote.is_some() {
+            return Err("--write cannot be combined with --output or --remote".to_string());
+        }
+    }
+
+    if remote.is_some() && repo_path.is_some() {
+        return Err("--remote cannot be combined with a repository path".to_string());
     }
 
     let repo_path =
         repo_path.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
 
-    Ok(CliOptions {
+    let options = CliOptions {
         repo_path,
         format,
         verbosity,
         action,
-    })
+        output,
+        config_path,
+        rule_packs,
+        fail_on_warning,
+        distinguish_waived,
+        level,
+        hook,
+        setup_ci,
+        force,
+        state,
+        upload_sarif,
+        create_pr,
+        track_issue,
+        remote,
+        log_level,
+        ci,
+        listen: listen.unwrap_or_else(|| DEFAULT_LISTEN.to_string()),
+        allow_root,
+        otel_file,
+        otel_endpoint,
+        iterations: iterations.unwrap_or(DEFAULT_ITERATIONS),
+        skip,
+        only_categories,
+        fail_fast,
+        check_links,
+        standard_version,
+        command_args,
+        sign,
+        key,
+        style,
+        reproducible,
+        relative_paths,
+        update_readme,
+        write,
+        metadata,
+    };
+
+    let tracing = options.otel_file.is_some() || options.otel_endpoint.is_some();
+    if tracing && !cfg!(feature = "otel") {
+        return Err(
+            "--otel-file and --otel-endpoint need rhodibot built with the 'otel' feature"
+                .to_string(),
+        );
+    }
+    let multi_repo = matches!(
+        options.action,
+        BotAction::Batch | BotAction::Dashboard | BotAction::Serve | BotAction::Bench
+    );
+    if tracing && multi_repo {
+        return Err("--otel-file and --otel-endpoint apply to single-repository runs".to_string());
+    }
+
+    Ok(options)
 }
 
-/// Print the compliance report (human format)
-fn print_report(report: &ComplianceReport) {
-    println!("🤖 Rhodibot - RSR Compliance Report");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("Repository: {}", report.repository_path.display());
-    println!("Verified:   {}", format_timestamp(report.verified_at));
-    println!();
+/// Icon and trailing note shown for a check in human output
+fn check_marks(check: &CheckResult, style: &Style) -> (String, String) {
+    let icon = match check.status {
+        _ if check.waived => Icon::Waived,
+        CheckStatus::Pass => Icon::Pass,
+        CheckStatus::Fail => Icon::Fail,
+        CheckStatus::Skipped | CheckStatus::NotApplicable => Icon::Skipped,
+        CheckStatus::Error => Icon::Error,
+    };
+    let note = match (check.status, &check.blocked_by) {
+        (CheckStatus::Skipped, Some(prerequisite)) => {
+            format!(" (skipped: {} failed)", prerequisite)
+        }
+        (CheckStatus::Skipped, None) => " (not run: --fail-fast)".to_string(),
+        (CheckStatus::NotApplicable, _) => " (not applicable)".to_string(),
+        (CheckStatus::Error, _) => " (error)".to_string(),
+        _ => String::new(),
+    };
+    (style.icon(icon), note)
+}
 
-    let mut current_category = String::new();
-    for check in &report.checks {
-        if check.category != current_category {
-            println!("\n📋 {}", check.category);
-            current_category = check.category.clone();
+/// One check in human output, with its duration when timed and its
+/// description in verbose mode
+///
+/// Accessible output puts each check on one line, its fields always in the
+/// same order: status, ID, level, category, item, then the duration, the
+/// reason it was skipped and the description where there are any.
+fn check_line(
+    check: &CheckResult,
+    style: &Style,
+    duration: Option<&str>,
+    description: Option<&str>,
+) -> String {
+    if style.accessible {
+        let status = match check.status {
+            _ if check.waived => "WAIVED",
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skipped => "SKIPPED",
+            CheckStatus::NotApplicable => "NOT APPLICABLE",
+            CheckStatus::Error => "ERROR",
+        };
+        let level = check.required_for.to_string();
+        let mut fields = vec![status, &check.id, &level, &check.category, &check.item];
+        fields.extend(duration);
+        let skipped = match &check.blocked_by {
+            Some(prerequisite) => format!("skipped: {} failed", prerequisite),
+            None => "not run: --fail-fast".to_string(),
+        };
+        if check.status == CheckStatus::Skipped {
+            fields.push(&skipped);
         }
+        fields.extend(description);
+        return format!("{}\n", fields.join(", "));
+    }
+    let (icon, note) = check_marks(check, style);
+    let duration = duration.map_or(String::new(), |d| format!(" ({})", d));
+    let mut line = format!(
+        "  {} {} [{}] {}{}{}\n",
+        icon, check.item, check.required_for, check.id, duration, note
+    );
+    if let Some(description) = description {
+        line.push_str(&format!("      {}\n", description));
+    }
+    line
+}
+
+/// Append the checks under one header per category, with the category's
+/// pass count (e.g. `Documentation: 6/7`)
+///
+/// Categories come in the order the report first lists them: catalog order
+/// for a verified report, or the order a `ReportBuilder` declared. A
+/// category's checks stay together even where the report interleaves them,
+/// as organization rules in a built-in category do.
+fn render_categories(
+    report: &ComplianceReport,
+    style: &Style,
+    out: &mut String,
+    render_check: impl Fn(&CheckResult, &mut String),
+) {
+    for (category, passed, total) in report.category_totals() {
+        if style.accessible {
+            out.push_str(&format!("\n{}: {} of {} passed\n", category, passed, total));
+        } else {
+            out.push_str(&format!(
+                "\n{} {}: {}/{}\n",
+                style.icon(Icon::Category),
+                style.paint(Color::Bold, category),
+                passed,
+                total
+            ));
+        }
+        for check in report.checks.iter().filter(|c| c.category == category) {
+            render_check(check, out);
+        }
+    }
+}
+
+fn render_report(report: &ComplianceReport, policy: &Policy, style: &Style) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("{}\n", style.label(Icon::Bot, "Rhodibot - RSR Compliance Report")));
+    out.push_str(&style.rule(46));
+    out.push_str(&format!("Repository: {}\n", report.repository_display()));
+    if let Some(timestamp) = report.timestamp() {
+        out.push_str(&format!("Verified:   {}\n", timestamp));
+    }
+    out.push_str(&format!("Standard:   RSR {}\n", report.standard_version));
+    out.push_str(&format!("Tool:       {}\n", identity_line(report)));
+    out.push('\n');
+
+    render_categories(report, style, &mut out, |check, out| {
+        out.push_str(&check_line(check, style, None, None));
+    });
 
-        let icon = if check.passed { "✅" } else { "❌" };
-        let level = format!("{:?}", check.required_for);
-        println!("  {} {} [{}]", icon, check.item, level);
+    if !report.skipped_checks.is_empty() {
+        out.push_str(&format!(
+            "\n{} Skipped: {}\n",
+            style.icon(Icon::Skipped),
+            report.skipped_checks.join(", ")
+        ));
     }
 
+    render_waivers(report, style, &mut out);
+
     if !report.warnings.is_empty() {
-        println!("\n🛡️  Security Warnings");
-        for warning in &report.warnings {
-            let icon = match warning.level {
-                WarningLevel::Info => "ℹ️ ",
-                WarningLevel::Warning => "⚠️ ",
-                WarningLevel::Critical => "🚨",
+        out.push_str(&format!("\n{}\n", style.label(Icon::Shield, "Security Warnings")));
+        for group in report.warning_groups() {
+            let icon = match group.level {
+                WarningLevel::Info => style.icon(Icon::Info),
+                WarningLevel::Warning => style.icon(Icon::Warning),
+                WarningLevel::Critical => style.icon(Icon::Critical),
+            };
+            let located = |warning: &SecurityWarning| match warning.path {
+                Some(ref path) => format!(
+                    "{} ({})",
+                    warning.message,
+                    relative_display(path, &report.repository_path)
+                ),
+                None => warning.message.clone(),
             };
-            println!("  {} {}", icon, warning.message);
+            if let [warning] = group.warnings[..] {
+                out.push_str(&format!("  {} {}\n", icon, located(warning)));
+                continue;
+            }
+            let place = if group.prefix.is_empty() {
+                String::new()
+            } else {
+                format!(" in {}", group.prefix)
+            };
+            out.push_str(&format!(
+                "  {} {} ({} warnings{})\n",
+                icon,
+                group.template,
+                group.warnings.len(),
+                place
+            ));
+            for warning in group.warnings.iter().take(WARNINGS_PER_GROUP) {
+                out.push_str(&format!("      {}\n", located(warning)));
+            }
+            if group.warnings.len() > WARNINGS_PER_GROUP {
+                out.push_str(&format!(
+                    "      ... and {} more (see --verbose or --format json)\n",
+                    group.warnings.len() - WARNINGS_PER_GROUP
+                ));
+            }
         }
     }
 
-    println!();
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!(
-        "Score: {}/{} checks passed ({:.1}%)",
+    render_verdict(report, policy, style, &mut out);
+    out
+}
+
+/// Append the score and the policy's verdict that end human output
+fn render_verdict(report: &ComplianceReport, policy: &Policy, style: &Style, out: &mut String) {
+    out.push('\n');
+    out.push_str(&style.rule(46));
+    out.push_str(&format!(
+        "Score: {}/{} checks passed ({:.1}%)\n",
         report.passed_count(),
         report.total_count(),
         report.percentage()
-    );
+    ));
 
     if report.has_critical_warnings() {
-        println!("🚨 CRITICAL: Security warnings detected - review required");
+        out.push_str(&format!(
+            "{} {}\n",
+            style.icon(Icon::Critical),
+            style.paint(Color::Red, "CRITICAL: Security warnings detected - review required")
+        ));
     }
 
-    if report.bronze_compliance() && !report.has_critical_warnings() {
-        println!("🏆 Bronze-level RSR compliance: ACHIEVED");
-    } else if report.bronze_compliance() && report.has_critical_warnings() {
-        println!("⚠️  Bronze-level RSR compliance: ACHIEVED (with warnings)");
-    } else {
-        println!("⚠️  Bronze-level RSR compliance: NOT MET");
-    }
-    println!();
-}
-
-/// Print report as JSON
-fn print_json_report(report: &ComplianceReport) {
-    let timestamp = format_timestamp(report.verified_at);
-    let passed = report.passed_count();
-    let total = report.total_count();
-    let percentage = report.percentage();
-    let bronze_compliant = report.bronze_compliance();
-    let has_critical = report.has_critical_warnings();
-
-    println!("{{");
-    println!("  \"tool\": \"rhodibot\",");
-    println!("  \"version\": \"{}\",", VERSION);
-    println!(
-        "  \"repository\": \"{}\",",
-        json_escape(&report.repository_path.display().to_string())
-    );
-    println!("  \"verified_at\": \"{}\",", timestamp);
-    println!("  \"score\": {{");
-    println!("    \"passed\": {},", passed);
-    println!("    \"total\": {},", total);
-    println!("    \"percentage\": {:.1}", percentage);
-    println!("  }},");
-    println!("  \"bronze_compliant\": {},", bronze_compliant);
-    println!("  \"has_critical_warnings\": {},", has_critical);
-
-    println!("  \"checks\": [");
-    for (i, check) in report.checks.iter().enumerate() {
-        let comma = if i < report.checks.len() - 1 { "," } else { "" };
-        println!("    {{");
-        println!("      \"category\": \"{}\",", json_escape(&check.category));
-        println!("      \"item\": \"{}\",", json_escape(&check.item));
-        println!("      \"passed\": {},", check.passed);
-        println!("      \"level\": \"{:?}\"", check.required_for);
-        println!("    }}{}", comma);
-    }
-    println!("  ],");
-
-    println!("  \"warnings\": [");
-    for (i, warning) in report.warnings.iter().enumerate() {
-        let comma = if i < report.warnings.len() - 1 {
-            ","
-        } else {
-            ""
-        };
-        let level = match warning.level {
-            WarningLevel::Info => "info",
-            WarningLevel::Warning => "warning",
-            WarningLevel::Critical => "critical",
+    let verdict = policy.evaluate(report);
+    render_outcome(report, policy, &verdict, style, out);
+    out.push('\n');
+}
+
+/// Append the headline naming the policy's required level and whether it
+/// is met, then the reasons when the run exits non-zero
+fn render_outcome(
+    report: &ComplianceReport,
+    policy: &Policy,
+    verdict: &Verdict,
+    style: &Style,
+    out: &mut String,
+) {
+    let level = policy.fail_on_level;
+    let headline = format!("{}-level RSR compliance", level);
+    match verdict.outcome {
+        Outcome::Pass => {
+            let outcome = style.paint(Color::Green, "ACHIEVED");
+            let label = style.label(Icon::Trophy, &format!("{}: {}", headline, outcome));
+            out.push_str(&format!("{}\n", label));
+        }
+        Outcome::Warn => {
+            let outcome = if report.meets_level_by_waivers(level) {
+                "ACHIEVED (through waivers)"
+            } else {
+                "ACHIEVED (with warnings)"
+            };
+            let outcome = style.paint(Color::Yellow, outcome);
+            let label = style.label(Icon::Trophy, &format!("{}: {}", headline, outcome));
+            out.push_str(&format!("{}\n", label));
+        }
+        Outcome::Fail => out.push_str(&format!(
+            "{} {}: {}\n",
+            style.icon(Icon::Warning),
+            headline,
+            style.paint(Color::Red, "NOT MET")
+        )),
+    }
+    if verdict.exit_code != exit_codes::SUCCESS {
+        for reason in &verdict.reasons {
+            out.push_str(&format!("   - {}\n", reason));
+        }
+    }
+}
+
+/// Render summary mode output: one line per category, then the verdict
+fn render_summary_report(report: &ComplianceReport, policy: &Policy, style: &Style) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("{}\n", style.label(Icon::Bot, "Rhodibot - RSR Compliance Summary")));
+    out.push_str(&style.rule(46));
+    out.push_str(&format!("Repository: {}\n", report.repository_display()));
+    out.push_str(&format!("Standard:   RSR {}\n\n", report.standard_version));
+
+    let totals = report.category_totals();
+    let width = totals.iter().map(|(category, _, _)| category.len()).max().unwrap_or(0);
+    for (category, passed, total) in totals {
+        let icon = if passed == total { Icon::Pass } else { Icon::Fail };
+        if style.accessible {
+            out.push_str(&format!(
+                "{}, {}, {} of {} passed\n",
+                style.icon(icon),
+                category,
+                passed,
+                total
+            ));
+            continue;
+        }
+        out.push_str(&format!(
+            "  {} {:<width$}  {}/{}\n",
+            style.icon(icon),
+            category,
+            passed,
+            total,
+            width = width
+        ));
+    }
+
+    let critical = report
+        .warnings
+        .iter()
+        .filter(|w| w.level == WarningLevel::Critical)
+        .count();
+    if !report.warnings.is_empty() {
+        let count = format!(
+            "Security warnings: {} ({} critical; see the full report)",
+            report.warnings.len(),
+            critical
+        );
+        out.push_str(&format!("\n{}\n", style.label(Icon::Shield, &count)));
+    }
+    if report.waived_count() > 0 {
+        out.push_str(&format!(
+            "{} Waived checks: {}\n",
+            style.icon(Icon::Waived),
+            report.waived_count()
+        ));
+    }
+    render_verdict(report, policy, style, &mut out);
+    out
+}
+
+/// Warnings listed per group in normal human output; the rest are counted
+const WARNINGS_PER_GROUP: usize = 3;
+
+/// Append the active and expired waivers section, if there are any
+fn render_waivers(report: &ComplianceReport, style: &Style, out: &mut String) {
+    if report.waivers.is_empty() && report.expired_waivers.is_empty() {
+        return;
+    }
+    out.push_str(&format!("\n{} Waivers\n", style.icon(Icon::Waived)));
+    for waiver in &report.waivers {
+        let expires = match waiver.expires {
+            Some(ref date) => format!(" (until {})", date),
+            None => String::new(),
         };
-        println!("    {{");
-        println!("      \"level\": \"{}\",", level);
-        println!("      \"message\": \"{}\"", json_escape(&warning.message));
-        println!("    }}{}", comma);
+        out.push_str(&format!("  {}: {}{}\n", waiver.id, waiver.reason, expires));
+    }
+    for waiver in &report.expired_waivers {
+        out.push_str(&format!(
+            "  {} {}: {} (EXPIRED {}, check enforced)\n",
+            style.icon(Icon::Expired),
+            waiver.id,
+            waiver.reason,
+            waiver.expires.as_deref().unwrap_or("")
+        ));
     }
-    println!("  ]");
-    println!("}}");
 }
 
-/// Print quiet mode output
-fn print_quiet_report(report: &ComplianceReport) {
-    let bronze_compliant = report.bronze_compliance();
-    let has_critical = report.has_critical_warnings();
+/// Render quiet mode output
+fn render_quiet_report(report: &ComplianceReport, policy: &Policy, style: &Style) -> String {
+    let mut out = String::new();
+    let verdict = policy.evaluate(report);
+    let verdict = if verdict.is_met() {
+        style.paint(Color::Green, "PASS")
+    } else if verdict.exit_code == exit_codes::SECURITY_WARNING {
+        style.paint(Color::Red, "FAIL (security)")
+    } else {
+        style.paint(Color::Red, "FAIL")
+    };
+    out.push_str(&format!("{}\n", verdict));
+
+    out
+}
 
-    if bronze_compliant && !has_critical {
-        println!("PASS");
-    } else if has_critical {
-        println!("FAIL (security)");
+/// Format a duration for humans, in the most readable unit (`us` rather
+/// than `µs` without emoji)
+fn format_duration(duration: Duration, style: &Style) -> String {
+    let micros = duration.as_micros();
+    if micros < 1_000 {
+        format!("{}{}", micros, if style.emoji { "µs" } else { "us" })
+    } else if micros < 1_000_000 {
+        format!("{:.1}ms", micros as f64 / 1_000.0)
     } else {
-        println!("FAIL");
+        format!("{:.2}s", duration.as_secs_f64())
     }
 }
 
-/// Print verbose report
-fn print_verbose_report(report: &ComplianceReport) {
-    println!("🤖 Rhodibot - RSR Compliance Report (Verbose)");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("Repository: {}", report.repository_path.display());
-    println!("Verified:   {}", format_timestamp(report.verified_at));
-    println!("Version:    {}", VERSION);
-    println!();
+/// Render verbose report
+fn render_verbose_report(report: &ComplianceReport, policy: &Policy, style: &Style) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{}\n",
+        style.label(Icon::Bot, "Rhodibot - RSR Compliance Report (Verbose)")
+    ));
+    out.push_str(&style.rule(58));
+    out.push_str(&format!("Repository: {}\n", report.repository_display()));
+    if let Some(timestamp) = report.timestamp() {
+        out.push_str(&format!("Verified:   {}\n", timestamp));
+    }
+    out.push_str(&format!("Standard:   RSR {}\n", report.standard_version));
+    out.push_str(&format!("Tool:       {}\n", identity_line(report)));
+    out.push('\n');
 
-    let mut current_category = String::new();
-    for check in &report.checks {
-        if check.category != current_category {
-            println!("\n📋 {}", check.category);
-            current_category = check.category.clone();
+    render_categories(report, style, &mut out, |check, out| {
+        let duration =
+            (!report.reproducible).then(|| format_duration(check.duration, style));
+        out.push_str(&check_line(
+            check,
+            style,
+            duration.as_deref(),
+            check.description.as_deref(),
+        ));
+    });
+
+    if !report.reproducible {
+        let timing = format!(
+            "Timing: {} total, {} in checks",
+            format_duration(report.duration, style),
+            format_duration(report.checks_duration(), style)
+        );
+        out.push_str(&format!("\n{}\n", style.label(Icon::Timer, &timing)));
+        for check in report.slowest_checks(SLOWEST_CHECKS) {
+            out.push_str(&format!(
+                "  {:>10}  {} ({})\n",
+                format_duration(check.duration, style),
+                check.id,
+                check.item
+            ));
         }
+    }
 
-        let icon = if check.passed { "✅" } else { "❌" };
-        let level = format!("{:?}", check.required_for);
-        println!("  {} {} [{}]", icon, check.item, level);
+    if !report.skipped_checks.is_empty() {
+        out.push_str(&format!(
+            "\n{} Skipped: {}\n",
+            style.icon(Icon::Skipped),
+            report.skipped_checks.join(", ")
+        ));
     }
 
+    render_waivers(report, style, &mut out);
+
     if !report.warnings.is_empty() {
-        println!("\n🛡️  Security Warnings ({} total)", report.warnings.len());
+        let heading = format!("Security Warnings ({} total)", report.warnings.len());
+        out.push_str(&format!("\n{}\n", style.label(Icon::Shield, &heading)));
         for warning in &report.warnings {
             let icon = match warning.level {
-                WarningLevel::Info => "ℹ️ ",
-                WarningLevel::Warning => "⚠️ ",
-                WarningLevel::Critical => "🚨",
+                WarningLevel::Info => style.icon(Icon::Info),
+                WarningLevel::Warning => style.icon(Icon::Warning),
+                WarningLevel::Critical => style.icon(Icon::Critical),
             };
             let level_str = match warning.level {
                 WarningLevel::Info => "[INFO]",
                 WarningLevel::Warning => "[WARN]",
                 WarningLevel::Critical => "[CRITICAL]",
             };
-            println!("  {} {} {}", icon, level_str, warning.message);
+            if style.accessible {
+                // The icon already reads as the level
+                out.push_str(&format!("  {} {}\n", icon, warning.message));
+            } else {
+                out.push_str(&format!("  {} {} {}\n", icon, level_str, warning.message));
+            }
             if let Some(ref path) = warning.path {
-                println!("      Path: {}", path.display());
+                out.push_str(&format!("      Path: {}\n", path.display()));
             }
         }
     }
 
-    println!();
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!(
-        "Score: {}/{} checks passed ({:.1}%)",
+    out.push('\n');
+    out.push_str(&style.rule(58));
+    out.push_str(&format!(
+        "Score: {}/{} checks passed ({:.1}%)\n",
         report.passed_count(),
         report.total_count(),
         report.percentage()
-    );
+    ));
 
     if report.has_critical_warnings() {
-        println!("🚨 CRITICAL: Security warnings detected - review required");
-        println!(
-            "   Exit code: {} (SECURITY_WARNING)",
-            exit_codes::SECURITY_WARNING
-        );
+        out.push_str(&format!(
+            "{} {}\n",
+            style.icon(Icon::Critical),
+            style.paint(Color::Red, "CRITICAL: Security warnings detected - review required")
+        ));
+    }
+
+    let verdict = policy.evaluate(report);
+    render_outcome(report, policy, &verdict, style, &mut out);
+    out.push_str(&format!(
+        "   Exit code: {} ({})\n",
+        verdict.exit_code,
+        exit_codes::name(verdict.exit_code).unwrap_or("UNKNOWN")
+    ));
+    out.push('\n');
+    out
+}
+
+/// Emit rendered output to stdout, or atomically to the `--output` file
+fn emit(output: Option<&PathBuf>, content: &str) {
+    match output {
+        Some(path) => {
+            if let Err(e) = write_atomic(path, content) {
+                eprintln!("Error: Cannot write {}: {}", path.display(), e);
+                process::exit(exit_codes::INVALID_PATH);
+            }
+        }
+        None => print!("{}", content),
+    }
+}
+
+/// Emit rendered output, then sign the `--output` file when `--sign` was given
+fn emit_signed(options: &CliOptions, content: &str) {
+    emit(options.output.as_ref(), content);
+    let (Some(key), Some(path)) = (options.sign.as_ref(), options.output.as_ref()) else {
+        return;
+    };
+    let signature = match sign::sign(content.as_bytes(), key) {
+        Ok(signature) => signature,
+        Err(e) => {
+            eprintln!("Error: Cannot sign {}: {}", path.display(), e);
+            process::exit(exit_codes::INVALID_ARGS);
+        }
+    };
+    let sig_path = sign::signature_path(path);
+    if let Err(e) = write_atomic(&sig_path, &signature.render()) {
+        eprintln!("Error: Cannot write {}: {}", sig_path.display(), e);
+        process::exit(exit_codes::INVALID_PATH);
     }
+}
+
+/// Logger selected by `--log-level`; silent by default
+fn logger_for(options: &CliOptions) -> Box<dyn Logger> {
+    match options.log_level {
+        Some(level) => Box::new(StderrLogger::new(level)),
+        None => Box::new(NoopLogger),
+    }
+}
 
-    if report.bronze_compliance() && !report.has_critical_warnings() {
-        println!("🏆 Bronze-level RSR compliance: ACHIEVED");
-        println!("   Exit code: {} (SUCCESS)", exit_codes::SUCCESS);
-    } else if report.bronze_compliance() && report.has_critical_warnings() {
-        println!("⚠️  Bronze-level RSR compliance: ACHIEVED (with warnings)");
-        println!(
-            "   Exit code: {} (SECURITY_WARNING)",
-            exit_codes::SECURITY_WARNING
+/// Draw a one-line progress indicator on stderr, erasing it when done
+fn draw_progress(progress: &Progress, style: Style) {
+    let mut stderr = io::stderr();
+    if progress.is_done() {
+        let _ = write!(stderr, "\r\x1b[2K");
+    } else {
+        let _ = write!(
+            stderr,
+            "\r\x1b[2K{} [{:>2}/{}] {}",
+            style.icon(Icon::Progress),
+            progress.completed, progress.total, progress.category
         );
+    }
+    let _ = stderr.flush();
+}
+
+/// Progress indicator for interactive runs
+///
+/// Only drawn when stderr is a terminal, so redirected output and CI logs
+/// stay clean, and never alongside quiet mode, `--log-level` output or
+/// `--accessible`, where a redrawn line would be read out over and over.
+fn progress_for(options: &CliOptions) -> &'static dyn ProgressSink {
+    if io::stderr().is_terminal()
+        && options.verbosity != Verbosity::Quiet
+        && options.log_level.is_none()
+        && !options.style.accessible
+    {
+        if options.style.emoji {
+            &|p: &Progress| draw_progress(p, Style::default())
+        } else {
+            &|p: &Progress| draw_progress(p, Style::PLAIN)
+        }
+    } else {
+        &NoProgress
+    }
+}
+
+/// Bot configuration from a repository's config file and the CLI flags
+fn bot_config_for(options: &CliOptions, config: &Config) -> BotConfig {
+    let mut bot_config = BotConfig {
+        action: options.action,
+        ..BotConfig::default()
+    };
+    bot_config.apply_policy(&config.policy);
+    if let Some(fail_on_warning) = options.fail_on_warning {
+        bot_config.fail_on_warning = fail_on_warning;
+    }
+    bot_config.distinguish_waived |= options.distinguish_waived;
+    bot_config.create_pr = options.create_pr;
+    bot_config
+}
+
+/// Render batch results as a per-repository status table
+fn render_batch_report(batch: &BatchReport, style: &Style) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{}\n",
+        style.label(Icon::Bot, "Rhodibot - Batch RSR Compliance Report")
+    ));
+    out.push_str(&style.rule(46));
+    for entry in &batch.entries {
+        let icon = match entry.exit_code {
+            exit_codes::SUCCESS => style.icon(Icon::Pass),
+            exit_codes::WAIVED_COMPLIANCE => style.icon(Icon::Warning),
+            exit_codes::COMPLIANCE_FAILED => style.icon(Icon::Fail),
+            exit_codes::SECURITY_WARNING => style.icon(Icon::Critical),
+            _ => style.icon(Icon::Error),
+        };
+        match entry.outcome {
+            Ok(ref report) => {
+                let level = report
+                    .highest_level()
+                    .map(|l| l.display_name())
+                    .unwrap_or("-");
+                out.push_str(&format!(
+                    "  {} {:>5.1}%  {:>3}/{:<3} {:<8} {}\n",
+                    icon,
+                    report.percentage(),
+                    report.passed_count(),
+                    report.total_count(),
+                    level,
+                    entry.source
+                ));
+            }
+            Err(ref message) => {
+                out.push_str(&format!("  {} {}: {}\n", icon, entry.source, message));
+            }
+        }
+    }
+    out.push_str(&style.rule(46));
+    out.push_str(&format!(
+        "Repositories: {}  Passed: {}  Failed: {}  Errors: {}\n",
+        batch.entries.len(),
+        batch.passed_count(),
+        batch.entries.len() - batch.passed_count() - batch.error_count(),
+        batch.error_count()
+    ));
+    out
+}
+
+/// Render batch results as one JSON document embedding each repository report
+fn render_batch_json(batch: &BatchReport) -> String {
+    let mut out = String::new();
+    out.push_str("{\n");
+    out.push_str("  \"tool\": \"rhodibot\",\n");
+    out.push_str(&format!("  \"version\": \"{}\",\n", VERSION));
+    out.push_str("  \"summary\": {\n");
+    out.push_str(&format!("    \"repositories\": {},\n", batch.entries.len()));
+    out.push_str(&format!("    \"passed\": {},\n", batch.passed_count()));
+    out.push_str(&format!("    \"errors\": {},\n", batch.error_count()));
+    out.push_str(&format!("    \"exit_code\": {}\n", batch.worst_exit_code()));
+    out.push_str("  },\n");
+    out.push_str("  \"repositories\": [\n");
+    for (i, entry) in batch.entries.iter().enumerate() {
+        let comma = if i + 1 < batch.entries.len() { "," } else { "" };
+        out.push_str("    {\n");
+        out.push_str(&format!("      \"source\": \"{}\",\n", json::escape(&entry.source)));
+        out.push_str(&format!("      \"exit_code\": {},\n", entry.exit_code));
+        match entry.outcome {
+            Ok(ref report) => {
+                let nested = render_json_report(report);
+                out.push_str(&format!(
+                    "      \"report\": {}\n",
+                    nested.trim_end().replace('\n', "\n      ")
+                ));
+            }
+            Err(ref message) => {
+                out.push_str(&format!("      \"error\": \"{}\"\n", json::escape(message)));
+            }
+        }
+        out.push_str(&format!("    }}{}\n", comma));
+    }
+    out.push_str("  ]\n");
+    out.push_str("}\n");
+    out
+}
+
+/// Verify every repository in the list file
+///
+/// An unreadable list file is reported on stderr and yields its exit code.
+fn collect_batch(options: &CliOptions) -> Result<BatchReport, i32> {
+    let list_path = &options.repo_path;
+    let text = match fs::read_to_string(list_path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("Error: Cannot read {}: {}", list_path.display(), e);
+            return Err(exit_codes::INVALID_PATH);
+        }
+    };
+    let base = list_path.parent().unwrap_or_else(|| Path::new("."));
+
+    let logger = logger_for(options);
+    let hooks = Hooks {
+        logger: logger.as_ref(),
+        progress: progress_for(options),
+    };
+    let mut batch = BatchReport::new();
+    for (source, path) in parse_repo_list(&text, base) {
+        match verify(&path, options, &hooks) {
+            Ok((config, report)) => {
+                let policy = bot_config_for(options, &config).policy();
+                let exit_code = compute_exit_code(&report, &policy);
+                batch.add_report(&source, report, exit_code);
+            }
+            Err((exit_code, message)) => batch.add_error(&source, &message, exit_code),
+        }
+    }
+    Ok(batch)
+}
+
+/// Verify every repository in the list file and emit the aggregated report
+///
+/// Returns the worst exit code of all repositories.
+fn run_batch(options: &CliOptions) -> i32 {
+    let batch = match collect_batch(options) {
+        Ok(batch) => batch,
+        Err(exit_code) => return exit_code,
+    };
+
+    let rendered = match (options.format, options.verbosity) {
+        (OutputFormat::Json, _) => render_batch_json(&batch),
+        (_, Verbosity::Quiet) => {
+            let worst = batch.worst_exit_code();
+            let status = if matches!(worst, exit_codes::SUCCESS | exit_codes::WAIVED_COMPLIANCE) {
+                "PASS"
+            } else {
+                "FAIL"
+            };
+            format!("{}\n", status)
+        }
+        _ => render_batch_report(&batch, &options.style),
+    };
+    emit_signed(options, &rendered);
+    batch.worst_exit_code()
+}
+
+/// Write the conformity document into the repository as CONFORMITY.md
+fn write_conformity(options: &CliOptions, report: &mut ComplianceReport) -> i32 {
+    let path = options.repo_path.join(conformity::CONFORMITY_FILE);
+    conformity::mark_in_sync(report);
+    if let Err(e) = write_atomic(&path, &format!("{}\n", generate_conformity_doc(report))) {
+        eprintln!("Error: Cannot write {}: {}", path.display(), e);
+        return exit_codes::INVALID_PATH;
+    }
+    if options.verbosity != Verbosity::Quiet {
+        println!("{} written", path.display());
+    }
+    exit_codes::SUCCESS
+}
+
+/// Compare the run with the stored state and record it (`drift`)
+fn run_drift(options: &CliOptions, report: &ComplianceReport) -> i32 {
+    let path = match options.state {
+        Some(ref path) => path.clone(),
+        None => options.repo_path.join(state::DEFAULT_STATE_FILE),
+    };
+    let previous = match ComplianceState::load(&path) {
+        Ok(previous) => previous,
+        Err(e) => {
+            eprintln!("Error: Invalid state file {}", e);
+            return exit_codes::INVALID_ARGS;
+        }
+    };
+    let current = ComplianceState::from_report(report);
+    if let Err(e) = current.save(&path) {
+        eprintln!("Error: Cannot write {}: {}", path.display(), e);
+        return exit_codes::INVALID_PATH;
+    }
+    // The first run has nothing to compare with, so nothing changes
+    let change = StateChange::between(previous.as_ref().unwrap_or(&current), &current);
+    let rendered = match (options.format, previous.as_ref()) {
+        (OutputFormat::Json, previous) => state::render_change_json(report, previous, &change),
+        (OutputFormat::Markdown, previous) => {
+            state::render_change_markdown(report, previous, &change)
+        }
+        _ if options.verbosity == Verbosity::Quiet => String::new(),
+        (_, None) => format!(
+            "Recorded compliance state in {} ({} failing checks); later runs report changes\n",
+            path.display(),
+            current.failing.len()
+        ),
+        (_, Some(previous)) => render_drift(&change, previous, report, &options.style),
+    };
+    emit(options.output.as_ref(), &rendered);
+    if change.is_regression() {
+        exit_codes::COMPLIANCE_FAILED
     } else {
-        println!("⚠️  Bronze-level RSR compliance: NOT MET");
-        println!(
-            "   Exit code: {} (COMPLIANCE_FAILED)",
+        exit_codes::SUCCESS
+    }
+}
+
+/// Render the changes since the previous state (human format)
+fn render_drift(
+    change: &StateChange,
+    previous: &ComplianceState,
+    report: &ComplianceReport,
+    style: &Style,
+) -> String {
+    let since = previous.verified_at.as_deref().unwrap_or("the last run");
+    if change.is_empty() {
+        return format!("No compliance changes since {}\n", since);
+    }
+    let level_name = |level: Option<ComplianceLevel>| level.map_or("none", |l| l.display_name());
+    let mut out = format!("Compliance changed since {}:\n", since);
+    if change.level != change.previous_level {
+        out.push_str(&format!(
+            "  Level: {} -> {}\n",
+            level_name(change.previous_level),
+            level_name(change.level)
+        ));
+    }
+    let item = |id: &str| {
+        report
+            .checks
+            .iter()
+            .find(|c| c.id == id)
+            .map_or(String::new(), |c| format!(" {}", c.item))
+    };
+    for (heading, ids, icon) in [
+        ("Newly failing", &change.newly_failing, Icon::Fail),
+        ("Resolved", &change.resolved, Icon::Pass),
+    ] {
+        if ids.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("  {}:\n", heading));
+        for id in ids {
+            out.push_str(&format!("    {} {}{}\n", style.icon(icon), id, item(id)));
+        }
+    }
+    out
+}
+
+/// Write the badge section into the repository's README.md
+fn update_readme(options: &CliOptions, report: &ComplianceReport) -> i32 {
+    let path = options.repo_path.join(readme::README_FILE);
+    let current = match fs::read_to_string(&path) {
+        Ok(current) => current,
+        Err(e) => {
+            eprintln!("Error: Cannot read {}: {}", path.display(), e);
+            return exit_codes::INVALID_PATH;
+        }
+    };
+    let updated = match readme::update(&current, &readme::render_section(report)) {
+        Ok(updated) => updated,
+        Err(e) => {
+            eprintln!("Error: Cannot update {}: {}", path.display(), e);
+            return exit_codes::INVALID_ARGS;
+        }
+    };
+    let message = if updated == current {
+        "is up to date"
+    } else if let Err(e) = write_atomic(&path, &updated) {
+        eprintln!("Error: Cannot write {}: {}", path.display(), e);
+        return exit_codes::INVALID_PATH;
+    } else {
+        "updated"
+    };
+    if options.verbosity != Verbosity::Quiet {
+        println!("{} {}", path.display(), message);
+    }
+    exit_codes::SUCCESS
+}
+
+/// Verify every repository in the list file and write the HTML dashboard
+fn run_dashboard(options: &CliOptions) -> i32 {
+    let batch = match collect_batch(options) {
+        Ok(batch) => batch,
+        Err(exit_code) => return exit_code,
+    };
+    let dir = options
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_DASHBOARD_DIR));
+    match write_dashboard(&batch, &dir) {
+        Ok(index) => {
+            if options.verbosity != Verbosity::Quiet {
+                println!(
+                    "Dashboard for {} repositories written to {}",
+                    batch.entries.len(),
+                    index.display()
+                );
+            }
+            exit_codes::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: Cannot write dashboard to {}: {}", dir.display(), e);
+            exit_codes::INVALID_PATH
+        }
+    }
+}
+
+/// Load the configuration for a repository and apply the check selection
+/// flags on top of it
+fn load_config(repo_path: &Path, options: &CliOptions) -> Result<Config, String> {
+    let mut config = match options.config_path {
+        Some(ref path) => Config::load(path),
+        None => Config::discover(repo_path),
+    }
+    .map_err(|e| e.to_string())?;
+    for dir in &options.rule_packs {
+        let pack = RulePack::load(dir).map_err(|e| e.to_string())?;
+        config.add_rule_pack(pack).map_err(|e| e.to_string())?;
+    }
+    config.checks.disable.extend(options.skip.iter().cloned());
+    if !options.only_categories.is_empty() {
+        config.checks.only_categories = options.only_categories.clone();
+    }
+    config.checks.fail_fast |= options.fail_fast;
+    config.links.online |= options.check_links;
+    if let Some(version) = options.standard_version {
+        config.standard_version = version;
+    }
+    if let Some(level) = options.level {
+        config.policy.fail_on_level = level;
+    }
+    Ok(config)
+}
+
+/// Load configuration and suppressions for a repository and verify it
+///
+/// Errors carry the exit code to use and a message for stderr.
+fn verify(
+    repo_path: &Path,
+    options: &CliOptions,
+    hooks: &Hooks,
+) -> Result<(Config, ComplianceReport), (i32, String)> {
+    if !repo_path.exists() {
+        return Err((
+            exit_codes::INVALID_PATH,
+            format!("Path does not exist: {}", repo_path.display()),
+        ));
+    }
+
+    if !repo_path.is_dir() {
+        return Err((
+            exit_codes::INVALID_PATH,
+            format!("Path is not a directory: {}", repo_path.display()),
+        ));
+    }
+
+    let config = load_config(repo_path, options)
+        .map_err(|e| (exit_codes::INVALID_ARGS, format!("Invalid configuration: {}", e)))?;
+
+    let suppressions = Suppressions::discover(repo_path)
+        .map_err(|e| (exit_codes::INVALID_ARGS, format!("Invalid {}: {}", SUPPRESSIONS_FILE, e)))?;
+
+    let mut report = verify_repository_with_hooks(repo_path, &config, hooks);
+    report.apply_suppressions(&suppressions);
+    let mut builder = ReportBuilder::from_report(report);
+    for (key, value) in &options.metadata {
+        builder = builder.metadata(key, value);
+    }
+    let mut report = builder.build();
+    if options.reproducible {
+        report.make_reproducible();
+    } else if options.relative_paths {
+        report.make_paths_relative();
+    }
+    Ok((config, report))
+}
+
+/// Write or send the spans recorded for a run, as requested on the command line
+#[cfg(feature = "otel")]
+fn export_spans(
+    options: &CliOptions,
+    recorder: &rhodibot::otel::SpanRecorder,
+    report: &ComplianceReport,
+) {
+    if options.otel_file.is_none() && options.otel_endpoint.is_none() {
+        return;
+    }
+    let spans = recorder.render_otlp_json(report);
+    if let Some(ref path) = options.otel_file {
+        if let Err(e) = write_atomic(path, &spans) {
+            eprintln!("Warning: cannot write spans to {}: {}", path.display(), e);
+        }
+    }
+    if let Some(ref endpoint) = options.otel_endpoint {
+        if let Err(e) = rhodibot::otel::export_to_collector(&spans, endpoint) {
+            eprintln!("Warning: span export failed: {}", e);
+        }
+    }
+}
+
+/// Render benchmark statistics
+fn render_bench(
+    repo_path: &Path,
+    stats: &BenchStats,
+    format: OutputFormat,
+    style: &Style,
+) -> String {
+    match format {
+        OutputFormat::Json => format!(
+            "{{\n  \"repository\": \"{}\",\n  \"iterations\": {},\n  \"warmup\": {},\n  \"min_ms\": {},\n  \"avg_ms\": {},\n  \"p95_ms\": {},\n  \"max_ms\": {}\n}}\n",
+            json::escape(&repo_path.display().to_string()),
+            stats.iterations,
+            WARMUP_ITERATIONS,
+            duration_ms(stats.min),
+            duration_ms(stats.avg),
+            duration_ms(stats.p95),
+            duration_ms(stats.max)
+        ),
+        _ => {
+            let mut out = String::new();
+            let heading = format!(
+                "Verification benchmark: {} ({} iterations, {} warmup)",
+                repo_path.display(),
+                stats.iterations,
+                WARMUP_ITERATIONS
+            );
+            out.push_str(&format!("{}\n", style.label(Icon::Timer, &heading)));
+            for (label, value) in [
+                ("min", stats.min),
+                ("avg", stats.avg),
+                ("p95", stats.p95),
+                ("max", stats.max),
+            ] {
+                out.push_str(&format!("  {}  {:>10}\n", label, format_duration(value, style)));
+            }
+            out
+        }
+    }
+}
+
+/// Run the `bench` command
+fn run_bench(options: &CliOptions) -> i32 {
+    let repo_path = options.repo_path.as_path();
+    if !repo_path.is_dir() {
+        eprintln!("Error: Path is not a directory: {}", repo_path.display());
+        return exit_codes::INVALID_PATH;
+    }
+    let config = match load_config(repo_path, options) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: Invalid configuration: {}", e);
+            return exit_codes::INVALID_ARGS;
+        }
+    };
+
+    let (stats, _) = bench_verification(repo_path, &config, options.iterations);
+    emit(
+        options.output.as_ref(),
+        &render_bench(repo_path, &stats, options.format, &options.style),
+    );
+    exit_codes::SUCCESS
+}
+
+/// Write a git hook into the repository (`install-hook`)
+fn run_install_hook(options: &CliOptions) -> i32 {
+    let Some(kind) = options.hook else {
+        return exit_codes::INVALID_ARGS;
+    };
+    match hook::install(&options.repo_path, kind, options.level) {
+        Ok(path) => {
+            if options.verbosity != Verbosity::Quiet {
+                println!("Installed {} hook: {}", kind, path.display());
+            }
+            exit_codes::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            exit_codes::INVALID_PATH
+        }
+    }
+}
+
+/// Write the composite action into the repository (`generate-action`)
+fn run_generate_action(options: &CliOptions) -> i32 {
+    let path = match options.output {
+        Some(ref path) => path.clone(),
+        None => options.repo_path.join(bot::ACTION_FILE),
+    };
+    if let Err(e) = write_atomic(&path, &bot::generate_github_action()) {
+        eprintln!("Error: Cannot write {}: {}", path.display(), e);
+        return exit_codes::INVALID_PATH;
+    }
+    if options.verbosity != Verbosity::Quiet {
+        println!("{} written", path.display());
+    }
+    exit_codes::SUCCESS
+}
+
+/// Write starter files for failing checks, optionally opening a pull
+/// request with them (`fix`)
+fn run_fix(options: &CliOptions, report: &ComplianceReport, create_pr: bool) -> i32 {
+    let fixes = fix::plan(report);
+    let written = match fix::apply(&options.repo_path, &fixes) {
+        Ok(written) => written,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return exit_codes::INVALID_PATH;
+        }
+    };
+    let quiet = options.verbosity == Verbosity::Quiet;
+    if written.is_empty() {
+        if !quiet {
+            println!("Nothing to fix: no failing check has a starter file");
+        }
+        return exit_codes::SUCCESS;
+    }
+    let fixes: Vec<Fix> = fixes.into_iter().filter(|f| written.contains(&f.path)).collect();
+    if !quiet {
+        for fix in &fixes {
+            println!("Created {} ({})", fix.path.display(), fix.id);
+        }
+        println!("Replace the TODO(rhodibot) placeholders before committing");
+    }
+    if create_pr {
+        let platform = options.ci.unwrap_or_else(CIPlatform::detect);
+        match pull_request::open(&options.repo_path, &fixes, platform) {
+            Ok(pull_request::Outcome::Opened(url)) => eprintln!("Opened pull request {}", url),
+            Ok(pull_request::Outcome::Updated(url)) => eprintln!("Updated pull request {}", url),
+            Err(e) => eprintln!("Warning: pull request not opened: {}", e),
+        }
+    }
+    exit_codes::SUCCESS
+}
+
+/// Write a CI configuration into the repository (`setup-ci`)
+fn run_setup_ci(options: &CliOptions) -> i32 {
+    let Some(platform) = options.setup_ci else {
+        return exit_codes::INVALID_ARGS;
+    };
+    match bot::setup_ci(&options.repo_path, platform, options.force) {
+        Ok(path) => {
+            if options.verbosity != Verbosity::Quiet {
+                println!("{} configuration written: {}", platform.name(), path.display());
+            }
+            exit_codes::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            exit_codes::INVALID_PATH
+        }
+    }
+}
+
+/// Check a report against its detached signature (`verify-report`)
+fn run_verify_report(options: &CliOptions) -> i32 {
+    // parse_args already requires --key; never exit here without saying why
+    let Some(ref key) = options.key else {
+        eprintln!("Error: verify-report requires --key");
+        return exit_codes::INVALID_ARGS;
+    };
+    let report_path = PathBuf::from(&options.command_args[0]);
+    let sig_path = match options.command_args.get(1) {
+        Some(path) => PathBuf::from(path),
+        None => sign::signature_path(&report_path),
+    };
+    let report = match fs::read(&report_path) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Error: Cannot read {}: {}", report_path.display(), e);
+            return exit_codes::INVALID_PATH;
+        }
+    };
+    let signature = match fs::read_to_string(&sig_path) {
+        Ok(text) => Signature::parse(&text),
+        Err(e) => {
+            eprintln!("Error: Cannot read {}: {}", sig_path.display(), e);
+            return exit_codes::INVALID_PATH;
+        }
+    };
+    let signature = match signature {
+        Ok(signature) => signature,
+        Err(e) => {
+            eprintln!("Error: {}: {}", sig_path.display(), e);
+            return exit_codes::INVALID_ARGS;
+        }
+    };
+    match sign::verify(&report, &signature, key) {
+        Ok(()) => {
+            println!(
+                "Signature OK: {} ({})",
+                report_path.display(),
+                signature.algorithm
+            );
+            exit_codes::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Signature INVALID: {}: {}", report_path.display(), e);
             exit_codes::COMPLIANCE_FAILED
-        );
+        }
+    }
+}
+
+/// Print the requirement catalog (`standard list`, `show` or `export`)
+fn run_standard(options: &CliOptions) -> i32 {
+    let version = options.standard_version.unwrap_or_default();
+    let args: Vec<&str> = options.command_args.iter().map(String::as_str).collect();
+    let rendered = match args.as_slice() {
+        ["list"] => Ok(standard::render_list(version)),
+        ["show", name] => standard::render_show(version, name),
+        ["export"] => Ok(standard::render_export_json(version)),
+        _ => Err("standard requires 'list', 'show <LEVEL|ID>' or 'export'".to_string()),
+    };
+    match rendered {
+        Ok(text) => {
+            emit(options.output.as_ref(), &text);
+            exit_codes::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            exit_codes::INVALID_ARGS
+        }
+    }
+}
+
+/// Answer one `serve` request; `GET /check` paths must resolve inside
+/// `root` when there is one
+fn handle_serve_request(
+    options: &CliOptions,
+    root: Option<&Path>,
+    logger: &dyn Logger,
+    request: &Request,
+) -> Response {
+    logger.log(
+        LogLevel::Info,
+        &format!("{} {}", request.method, request.path),
+    );
+    let status_for = |code: i32| if code == exit_codes::INVALID_PATH { 404 } else { 422 };
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/health") => Response::text(200, "ok\n"),
+        ("GET", "/check") => {
+            let path = match request.query_param("path") {
+                Some(path) if !path.is_empty() => path,
+                _ => return Response::error(400, "missing 'path' query parameter"),
+            };
+            if let Some(root) = root {
+                // Canonical, so neither `..` nor a symlink leads out
+                let inside = fs::canonicalize(path).map_or(false, |path| path.starts_with(root));
+                if !inside {
+                    let message = format!("'{}' is not inside {}", path, root.display());
+                    return Response::error(403, &message);
+                }
+            }
+            match verify(Path::new(path), options, &Hooks::default()) {
+                Ok((_, report)) => Response::json(200, render_json_report(&report)),
+                Err((code, message)) => Response::error(status_for(code), &message),
+            }
+        }
+        ("POST", "/check") => {
+            let label = request.query_param("name").unwrap_or("upload");
+            let checkout = match RemoteCheckout::unpack(&request.body, label) {
+                Ok(checkout) => checkout,
+                Err(e) => return Response::error(400, &e),
+            };
+            match verify(checkout.path(), options, &Hooks::default()) {
+                Ok((_, mut report)) => {
+                    checkout.relocate_report(&mut report);
+                    Response::json(200, render_json_report(&report))
+                }
+                Err((code, message)) => Response::error(status_for(code), &message),
+            }
+        }
+        (_, "/health") | (_, "/check") => Response::error(405, "method not allowed"),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+/// Run the `serve` command; only returns if the listener fails
+fn run_serve(options: &CliOptions) -> i32 {
+    let root = match &options.allow_root {
+        Some(dir) => match fs::canonicalize(dir) {
+            Ok(root) => Some(root),
+            Err(e) => {
+                eprintln!("Error: Cannot use {} as --allow-root: {}", dir.display(), e);
+                return exit_codes::INVALID_PATH;
+            }
+        },
+        None => None,
+    };
+    let listener = match TcpListener::bind(&options.listen) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Error: Cannot listen on {}: {}", options.listen, e);
+            return exit_codes::INVALID_ARGS;
+        }
+    };
+    if let Ok(addr) = listener.local_addr() {
+        let address = format!("http://{}", addr);
+        eprintln!("Listening on {}", address);
+        println!("{}", status_json(&[("event", "listening"), ("address", &address)]));
+    }
+
+    let logger = logger_for(options);
+    let handler = |request: &Request| {
+        handle_serve_request(options, root.as_deref(), logger.as_ref(), request)
+    };
+    match serve(&listener, &handler) {
+        Ok(()) => exit_codes::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            exit_codes::INVALID_ARGS
+        }
     }
-    println!();
 }
 
 fn main() {
@@ -370,67 +2355,175 @@ fn main() {
         }
     };
 
-    if !options.repo_path.exists() {
-        eprintln!(
-            "Error: Path does not exist: {}",
-            options.repo_path.display()
-        );
-        process::exit(exit_codes::INVALID_PATH);
+    if options.action == BotAction::Batch {
+        process::exit(run_batch(&options));
     }
-
-    if !options.repo_path.is_dir() {
-        eprintln!(
-            "Error: Path is not a directory: {}",
-            options.repo_path.display()
-        );
-        process::exit(exit_codes::INVALID_PATH);
+    if options.action == BotAction::Dashboard {
+        process::exit(run_dashboard(&options));
+    }
+    if options.action == BotAction::Serve {
+        process::exit(run_serve(&options));
+    }
+    if options.action == BotAction::Bench {
+        process::exit(run_bench(&options));
+    }
+    if options.action == BotAction::Standard {
+        process::exit(run_standard(&options));
+    }
+    if options.action == BotAction::VerifyReport {
+        process::exit(run_verify_report(&options));
+    }
+    if options.action == BotAction::InstallHook {
+        process::exit(run_install_hook(&options));
+    }
+    if options.action == BotAction::GenerateAction {
+        process::exit(run_generate_action(&options));
+    }
+    if options.action == BotAction::SetupCi {
+        process::exit(run_setup_ci(&options));
     }
 
-    let report = verify_repository(&options.repo_path);
+    // The checkout is removed when dropped, so it must be dropped before any
+    // process::exit below
+    let checkout = match options.remote {
+        Some(ref url) => match RemoteCheckout::fetch(url) {
+            Ok(checkout) => Some(checkout),
+            Err(e) => {
+                eprintln!("Error: Cannot fetch remote repository: {}", e);
+                process::exit(exit_codes::INVALID_PATH);
+            }
+        },
+        None => None,
+    };
+    let repo_path = checkout
+        .as_ref()
+        .map_or(options.repo_path.as_path(), |c| c.path());
+    let logger = logger_for(&options);
+    #[cfg(feature = "otel")]
+    let recorder = rhodibot::otel::SpanRecorder::new();
+    #[cfg(feature = "otel")]
+    let traced = |p: &Progress| {
+        recorder.update(p);
+        progress_for(&options).update(p);
+    };
+    #[cfg(feature = "otel")]
+    let progress: &dyn ProgressSink = &traced;
+    #[cfg(not(feature = "otel"))]
+    let progress = progress_for(&options);
+    let hooks = Hooks {
+        logger: logger.as_ref(),
+        progress,
+    };
+    let verified = verify(repo_path, &options, &hooks);
+    let (config, mut report) = match (verified, checkout) {
+        (Ok((config, mut report)), Some(checkout)) => {
+            checkout.relocate_report(&mut report);
+            (config, report)
+        }
+        (Ok(verified), None) => verified,
+        (Err((code, message)), checkout) => {
+            drop(checkout);
+            eprintln!("Error: {}", message);
+            process::exit(code);
+        }
+    };
+
+    #[cfg(feature = "otel")]
+    export_spans(&options, &recorder, &report);
+
+    let bot_config = bot_config_for(&options, &config);
 
     // Handle different actions
-    match options.action {
+    match bot_config.action {
+        BotAction::Badge if options.update_readme => {
+            process::exit(update_readme(&options, &report));
+        }
         BotAction::Badge => {
             let level = report.highest_level().unwrap_or(ComplianceLevel::Bronze);
-            println!("{}", generate_badge(level));
+            emit_signed(&options, &format!("{}\n", generate_badge(level)));
             process::exit(exit_codes::SUCCESS);
         }
+        BotAction::Conformity if options.write => {
+            process::exit(write_conformity(&options, &mut report));
+        }
         BotAction::Conformity => {
-            println!("{}", generate_conformity_doc(&report));
+            emit_signed(&options, &format!("{}\n", generate_conformity_doc(&report)));
             process::exit(exit_codes::SUCCESS);
         }
+        BotAction::Drift => {
+            process::exit(run_drift(&options, &report));
+        }
         BotAction::Fix => {
-            eprintln!("Error: 'fix' action not yet implemented");
-            eprintln!("This will automatically create missing RSR files in a future version.");
-            process::exit(exit_codes::INVALID_ARGS);
+            process::exit(run_fix(&options, &report, bot_config.create_pr));
         }
-        BotAction::Check => {
+        BotAction::Check
+        | BotAction::Batch
+        | BotAction::Dashboard
+        | BotAction::Serve
+        | BotAction::Bench
+        | BotAction::Standard
+        | BotAction::VerifyReport
+        | BotAction::InstallHook
+        | BotAction::GenerateAction
+        | BotAction::SetupCi => {
             // Continue with normal output
         }
     }
 
     // Output based on format and verbosity
-    match options.format {
-        OutputFormat::Json => print_json_report(&report),
-        OutputFormat::Human => match options.verbosity {
-            Verbosity::Quiet => print_quiet_report(&report),
-            Verbosity::Normal => print_report(&report),
-            Verbosity::Verbose => print_verbose_report(&report),
-        },
-        OutputFormat::Sarif => {
-            eprintln!("Error: SARIF output not yet implemented");
-            process::exit(exit_codes::INVALID_ARGS);
+    let rendered = match options.format {
+        OutputFormat::Json if options.verbosity == Verbosity::Quiet => {
+            render_json_summary(&report)
+        }
+        OutputFormat::Json => render_json_report(&report),
+        OutputFormat::Tap => render_tap(&report),
+        OutputFormat::CodeQuality => render_code_quality(&report),
+        OutputFormat::Prometheus => render_prometheus(&report),
+        OutputFormat::Porcelain => render_porcelain(&report),
+        OutputFormat::Markdown => bot::render_summary_markdown(&report),
+        OutputFormat::Human => {
+            let policy = bot_config.policy();
+            match options.verbosity {
+                Verbosity::Quiet => render_quiet_report(&report, &policy, &options.style),
+                Verbosity::Summary => render_summary_report(&report, &policy, &options.style),
+                Verbosity::Normal => render_report(&report, &policy, &options.style),
+                Verbosity::Verbose => render_verbose_report(&report, &policy, &options.style),
+            }
+        }
+        OutputFormat::Sarif => render_sarif(&report),
+    };
+    emit_signed(&options, &rendered);
+
+    // Log-command extras would corrupt a machine-readable report on stdout
+    let stdout_free = options.output.is_some() || options.format == OutputFormat::Human;
+    options
+        .ci
+        .unwrap_or_else(CIPlatform::detect)
+        .output_report(&report, stdout_free);
+
+    if options.upload_sarif {
+        // Code scanning locates results in the checkout, so paths must be
+        // relative whatever the other output shows
+        let mut upload = report.clone();
+        upload.make_paths_relative();
+        match github_actions::upload_sarif(&render_sarif(&upload)) {
+            Ok(()) => eprintln!("Uploaded SARIF results to GitHub code scanning"),
+            Err(e) => eprintln!("Warning: SARIF upload failed: {}", e),
         }
     }
 
-    // Exit with appropriate code
-    let exit_code = if report.has_critical_warnings() {
-        exit_codes::SECURITY_WARNING
-    } else if !report.bronze_compliance() {
-        exit_codes::COMPLIANCE_FAILED
-    } else {
-        exit_codes::SUCCESS
-    };
+    let exit_code = compute_exit_code(&report, &bot_config.policy());
+    if options.track_issue {
+        let platform = options.ci.unwrap_or_else(CIPlatform::detect);
+        let compliant = matches!(exit_code, exit_codes::SUCCESS | exit_codes::WAIVED_COMPLIANCE);
+        match issues::sync(&report, platform, compliant) {
+            Ok(IssueAction::Open) => eprintln!("Opened the compliance tracking issue"),
+            Ok(IssueAction::Update(n)) => eprintln!("Updated compliance tracking issue #{}", n),
+            Ok(IssueAction::Close(n)) => eprintln!("Closed compliance tracking issue #{}", n),
+            Ok(IssueAction::Nothing) => {}
+            Err(e) => eprintln!("Warning: tracking issue not updated: {}", e),
+        }
+    }
 
     process::exit(exit_code);
 }
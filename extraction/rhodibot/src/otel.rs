@@ -0,0 +1,361 @@
+//! OpenTelemetry span export (`otel` feature)
+//!
+//! A [`SpanRecorder`] is a [`ProgressSink`] that timestamps every check as it
+//! finishes. After the run it renders an OTLP/JSON trace with one root span
+//! for the verification, a child span per category and a grandchild span per
+//! check, which can be written to a file or POSTed to a collector's
+//! `/v1/traces` endpoint.
+//!
+//! Checks run one after another, so a check is taken to start when the
+//! previous one finished (or when the recorder was created, for the first).
+
+use crate::output::fnv1a64;
+use crate::progress::{Progress, ProgressSink};
+use crate::{json, ComplianceReport, VERSION};
+use crate::subprocess;
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Timing of one finished check
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CheckTiming {
+    id: String,
+    category: String,
+    start: SystemTime,
+    end: SystemTime,
+}
+
+/// Records check timings for span export
+#[derive(Debug)]
+pub struct SpanRecorder {
+    start: SystemTime,
+    last: Cell<SystemTime>,
+    timings: RefCell<Vec<CheckTiming>>,
+}
+
+impl Default for SpanRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressSink for SpanRecorder {
+    fn update(&self, progress: &Progress) {
+        let now = SystemTime::now();
+        let start = self.last.replace(now);
+        self.timings.borrow_mut().push(CheckTiming {
+            id: progress.check_id.to_string(),
+            category: progress.category.to_string(),
+            start,
+            end: now,
+        });
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
+/// Span and trace IDs as lowercase hex, unique per run
+struct IdGenerator {
+    seed: String,
+    next: Cell<u64>,
+}
+
+impl IdGenerator {
+    fn new(start: SystemTime) -> Self {
+        Self {
+            seed: format!("{}-{}", std::process::id(), unix_nanos(start)),
+            next: Cell::new(0),
+        }
+    }
+
+    fn next_u64(&self) -> u64 {
+        let n = self.next.get();
+        self.next.set(n + 1);
+        // FNV alone barely changes between consecutive inputs; finish with
+        // the splitmix64 mixer so IDs look random. Zero is an invalid ID.
+        let mut z = fnv1a64(&format!("{}-{}", self.seed, n));
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        (z ^ (z >> 31)).max(1)
+    }
+
+    fn span_id(&self) -> String {
+        format!("{:016x}", self.next_u64())
+    }
+
+    fn trace_id(&self) -> String {
+        format!("{:016x}{:016x}", self.next_u64(), self.next_u64())
+    }
+}
+
+fn string_attribute(key: &str, value: &str) -> String {
+    format!(
+        "{{\"key\": \"{}\", \"value\": {{\"stringValue\": \"{}\"}}}}",
+        key,
+        json::escape(value)
+    )
+}
+
+/// A span ready for rendering
+struct Span {
+    id: String,
+    parent: Option<String>,
+    name: String,
+    start: SystemTime,
+    end: SystemTime,
+    attributes: Vec<String>,
+    /// `None` leaves the status unset
+    ok: Option<bool>,
+}
+
+impl Span {
+    fn render(&self, trace_id: &str) -> String {
+        let mut out = String::from("{");
+        out.push_str(&format!("\"traceId\": \"{}\", ", trace_id));
+        out.push_str(&format!("\"spanId\": \"{}\", ", self.id));
+        if let Some(ref parent) = self.parent {
+            out.push_str(&format!("\"parentSpanId\": \"{}\", ", parent));
+        }
+        out.push_str(&format!("\"name\": \"{}\", ", json::escape(&self.name)));
+        // SPAN_KIND_INTERNAL
+        out.push_str("\"kind\": 1, ");
+        out.push_str(&format!(
+            "\"startTimeUnixNano\": \"{}\", \"endTimeUnixNano\": \"{}\", ",
+            unix_nanos(self.start),
+            unix_nanos(self.end)
+        ));
+        out.push_str(&format!("\"attributes\": [{}]", self.attributes.join(", ")));
+        if let Some(ok) = self.ok {
+            // STATUS_CODE_OK / STATUS_CODE_ERROR
+            out.push_str(&format!(", \"status\": {{\"code\": {}}}", if ok { 1 } else { 2 }));
+        }
+        out.push('}');
+        out
+    }
+}
+
+impl SpanRecorder {
+    /// Start recording; the root span starts now
+    pub fn new() -> Self {
+        let now = SystemTime::now();
+        Self {
+            start: now,
+            last: Cell::new(now),
+            timings: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Number of checks recorded so far
+    pub fn len(&self) -> usize {
+        self.timings.borrow().len()
+    }
+
+    /// Whether no check has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Render the recorded run as an OTLP/JSON `ExportTraceServiceRequest`
+    ///
+    /// Checks left out of the report (disabled, or opt-in and not enabled)
+    /// get no span; failed checks get an error status.
+    pub fn render_otlp_json(&self, report: &ComplianceReport) -> String {
+        let ids = IdGenerator::new(self.start);
+        let trace_id = ids.trace_id();
+        let end = self.last.get();
+
+        let root = Span {
+            id: ids.span_id(),
+            parent: None,
+            name: "rhodibot verify".to_string(),
+            start: self.start,
+            end,
+            attributes: vec![
+                string_attribute(
+                    "rsr.repository",
+                    &report.repository_path.display().to_string(),
+                ),
+                format!(
+                    "{{\"key\": \"rsr.checks.passed\", \"value\": {{\"intValue\": \"{}\"}}}}",
+                    report.passed_count()
+                ),
+                format!(
+                    "{{\"key\": \"rsr.checks.total\", \"value\": {{\"intValue\": \"{}\"}}}}",
+                    report.total_count()
+                ),
+            ],
+            ok: None,
+        };
+        let mut spans = Vec::new();
+        let mut category: Option<Span> = None;
+
+        for timing in self.timings.borrow().iter() {
+            if !report.checks.iter().any(|c| c.id == timing.id) {
+                continue;
+            }
+            let same_category = category.as_ref().map_or(false, |c| c.name == timing.category);
+            if !same_category {
+                spans.extend(category.take());
+                category = Some(Span {
+                    id: ids.span_id(),
+                    parent: Some(root.id.clone()),
+                    name: timing.category.clone(),
+                    start: timing.start,
+                    end: timing.end,
+                    attributes: vec![string_attribute("rsr.category", &timing.category)],
+                    ok: None,
+                });
+            }
+            let parent = category.as_mut().expect("category span just set");
+            parent.end = timing.end;
+
+            let passed = report
+                .checks
+                .iter()
+                .find(|c| c.id == timing.id)
+                .map(|c| c.passed());
+            spans.push(Span {
+                id: ids.span_id(),
+                parent: Some(parent.id.clone()),
+                name: timing.id.clone(),
+                start: timing.start,
+                end: timing.end,
+                attributes: vec![
+                    string_attribute("rsr.check.id", &timing.id),
+                    string_attribute("rsr.category", &timing.category),
+                ],
+                ok: passed,
+            });
+        }
+        spans.extend(category);
+        spans.insert(0, root);
+
+        let rendered: Vec<String> = spans
+            .iter()
+            .map(|span| format!("            {}", span.render(&trace_id)))
+            .collect();
+
+        let mut out = String::new();
+        out.push_str("{\n");
+        out.push_str("  \"resourceSpans\": [\n");
+        out.push_str("    {\n");
+        out.push_str(&format!(
+            "      \"resource\": {{\"attributes\": [{}]}},\n",
+            [
+                string_attribute("service.name", "rhodibot"),
+                string_attribute("rsr.standard_version", report.standard_version.as_str())
+            ]
+            .join(", ")
+        ));
+        out.push_str("      \"scopeSpans\": [\n");
+        out.push_str("        {\n");
+        out.push_str(&format!(
+            "          \"scope\": {{\"name\": \"rhodibot\", \"version\": \"{}\"}},\n",
+            VERSION
+        ));
+        out.push_str("          \"spans\": [\n");
+        out.push_str(&rendered.join(",\n"));
+        out.push('\n');
+        out.push_str("          ]\n");
+        out.push_str("        }\n");
+        out.push_str("      ]\n");
+        out.push_str("    }\n");
+        out.push_str("  ]\n");
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Longest an export to the collector may take
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// POST OTLP/JSON trace data to a collector using the system `curl` binary
+///
+/// `endpoint` is the collector base URL (e.g. `http://localhost:4318`);
+/// `/v1/traces` is appended unless already present.
+pub fn export_to_collector(otlp_json: &str, endpoint: &str) -> Result<(), String> {
+    let endpoint = endpoint.trim_end_matches('/');
+    let url = if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    };
+
+    let mut command = Command::new("curl");
+    command
+        .args(["-sS", "--fail", "-X", "POST"])
+        .arg("--max-time")
+        .arg(EXPORT_TIMEOUT.as_secs().to_string())
+        .args(["-H", "Content-Type: application/json"])
+        .args(["--data-binary", "@-"])
+        .arg(&url);
+    // curl enforces --max-time itself; the kill is for a curl that hangs
+    let kill_after = EXPORT_TIMEOUT + Duration::from_secs(5);
+    let output = subprocess::run(&mut command, Some(otlp_json.as_bytes()), kill_after)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::TimedOut => format!("POST {} {}", url, e),
+            _ => format!("cannot run curl: {}", e),
+        })?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "POST {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::{verify_repository_with_hooks, Hooks};
+    use std::path::Path;
+
+    #[test]
+    fn test_render_otlp_json() {
+        let recorder = SpanRecorder::new();
+        let hooks = Hooks {
+            progress: &recorder,
+            ..Hooks::default()
+        };
+        let config = Config::parse(
+            "[checks]\ndisable = [\"RSR-BLD-002\"]\n\
+             [version_control]\nconventional_commits = true",
+        )
+        .unwrap();
+        let report =
+            verify_repository_with_hooks(Path::new("/nonexistent/rhodibot"), &config, &hooks);
+        assert_eq!(recorder.len(), crate::catalog::BUILTIN_CHECKS.len());
+
+        let json = recorder.render_otlp_json(&report);
+        assert!(json.contains("\"stringValue\": \"rhodibot\""));
+        assert!(json.contains("\"name\": \"rhodibot verify\""));
+        let passed = format!(
+            "{{\"key\": \"rsr.checks.passed\", \"value\": {{\"intValue\": \"{}\"}}}}",
+            report.passed_count()
+        );
+        assert!(json.contains(&passed));
+        assert!(json.contains("{\"key\": \"rsr.checks.total\", \"value\": {\"intValue\": \""));
+        // One root, one span per category, every check that ran
+        let categories = crate::catalog::categories().len();
+        assert_eq!(report.category_totals().len(), categories);
+        let checks = report.checks.len();
+        assert_eq!(json.matches("\"spanId\"").count(), 1 + categories + checks);
+        assert_eq!(json.matches("\"parentSpanId\"").count(), categories + checks);
+        assert!(json.contains("\"name\": \"RSR-DOC-001\""));
+        assert!(!json.contains("\"name\": \"RSR-BLD-002\""));
+        assert!(json.contains("\"status\": {\"code\": 2}"));
+
+        let trace_id = json.split("\"traceId\": \"").nth(1).unwrap();
+        assert_eq!(trace_id.find('"'), Some(32));
+    }
+}
@@ -0,0 +1,1215 @@
+//! Report rendering and output helpers
+//!
+//! Machine-oriented report formats are rendered to strings here so they can be
+//! shared by the CLI and library users. Reports can be written to stdout or
+//! directly to a file. File output uses write-and-rename so that readers (CI
+//! artifact uploaders, downstream jobs) never observe a partially written
+//! report.
+
+use crate::builder::ReportMetadata;
+use crate::{
+    catalog, json, CheckResult, CheckStatus, ComplianceLevel, ComplianceReport, WarningLevel,
+    VERSION,
+};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Build the temporary sibling path used while writing `path`
+fn temp_path_for(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "report".to_string());
+    let temp_name = format!(".{}.tmp-{}", file_name, std::process::id());
+    match path.parent() {
+        Some(parent) => parent.join(temp_name),
+        None => PathBuf::from(temp_name),
+    }
+}
+
+/// Atomically write `contents` to `path`
+///
+/// The data is written to a temporary file in the same directory, flushed to
+/// disk, and then renamed over the destination. Missing parent directories are
+/// created. On failure the temporary file is removed and the destination is
+/// left untouched.
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    let temp = temp_path_for(path);
+    let result = (|| {
+        let mut file = fs::File::create(&temp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Escape text for a TAP test description
+///
+/// `#` starts a directive in TAP, so it is escaped as `\#`.
+fn tap_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('#', "\\#").replace('\n', " ")
+}
+
+/// Render report metadata as a one-line JSON object
+pub fn render_metadata_json(metadata: &ReportMetadata) -> String {
+    let entries: Vec<String> = metadata
+        .entries()
+        .into_iter()
+        .map(|(key, value)| format!("{}: {}", json::string(key), json::string(value)))
+        .collect();
+    format!("{{{}}}", entries.join(", "))
+}
+
+/// Version of the layout of rhodibot's own report formats
+///
+/// Raised when a field of the JSON, porcelain or summary output is removed
+/// or changes meaning, so consumers of archived reports know which layout
+/// they are reading. New fields do not raise it.
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Operating system and architecture this build runs on, e.g. `linux-x86_64`
+pub fn platform() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+/// Tool, version, report schema and platform, for the header of every
+/// output format
+///
+/// The platform is left out of reproducible reports, which must not depend
+/// on the machine that produced them.
+pub fn identity_line(report: &ComplianceReport) -> String {
+    let mut line = format!("rhodibot {} (report schema {}", VERSION, REPORT_SCHEMA_VERSION);
+    if !report.reproducible {
+        line.push_str(&format!(", {}", platform()));
+    }
+    line.push(')');
+    line
+}
+
+/// Number of slowest checks listed in verbose and JSON output
+pub const SLOWEST_CHECKS: usize = 3;
+
+/// Duration in milliseconds for JSON output
+pub fn duration_ms(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1_000.0)
+}
+
+/// Render report as JSON
+pub fn render_json_report(report: &ComplianceReport) -> String {
+    let mut out = String::new();
+    let passed = report.passed_count();
+    let total = report.total_count();
+    let percentage = report.percentage();
+    let bronze_compliant = report.bronze_compliance();
+    let has_critical = report.has_critical_warnings();
+
+    out.push_str("{\n");
+    out.push_str("  \"tool\": \"rhodibot\",\n");
+    out.push_str(&format!("  \"version\": \"{}\",\n", VERSION));
+    out.push_str(&format!("  \"standard_version\": \"{}\",\n", report.standard_version));
+    out.push_str(&format!("  \"schema_version\": {},\n", REPORT_SCHEMA_VERSION));
+    if !report.reproducible {
+        out.push_str(&format!("  \"platform\": {},\n", json::string(&platform())));
+    }
+    out.push_str(&format!(
+        "  \"repository\": \"{}\",\n",
+        json::escape(&report.repository_display())
+    ));
+    if let Some(timestamp) = report.timestamp() {
+        out.push_str(&format!("  \"verified_at\": \"{}\",\n", timestamp));
+    }
+    if !report.metadata.is_empty() {
+        out.push_str(&format!("  \"metadata\": {},\n", render_metadata_json(&report.metadata)));
+    }
+    out.push_str("  \"score\": {\n");
+    out.push_str(&format!("    \"passed\": {},\n", passed));
+    out.push_str(&format!("    \"total\": {},\n", total));
+    out.push_str(&format!("    \"percentage\": {:.1}\n", percentage));
+    out.push_str("  },\n");
+    out.push_str(&format!("  \"bronze_compliant\": {},\n", bronze_compliant));
+    out.push_str(&format!(
+        "  \"waived_compliance\": {},\n",
+        report.meets_level_by_waivers(ComplianceLevel::Bronze)
+    ));
+    out.push_str(&format!("  \"has_critical_warnings\": {},\n", has_critical));
+    // Levels up to the highest achieved, so `levels` never contradicts it
+    let highest = report.highest_level();
+    out.push_str("  \"levels\": {\n");
+    for (i, level) in ComplianceLevel::ALL.into_iter().enumerate() {
+        let comma = if i < ComplianceLevel::ALL.len() - 1 { "," } else { "" };
+        out.push_str(&format!(
+            "    \"{}\": {}{}\n",
+            level.display_name().to_lowercase(),
+            highest.map_or(false, |h| level <= h),
+            comma
+        ));
+    }
+    out.push_str("  },\n");
+    match highest {
+        Some(level) => out.push_str(&format!("  \"highest_level\": \"{}\",\n", level)),
+        None => out.push_str("  \"highest_level\": null,\n"),
+    }
+
+    out.push_str("  \"checks\": [\n");
+    for (i, check) in report.checks.iter().enumerate() {
+        let comma = if i < report.checks.len() - 1 { "," } else { "" };
+        out.push_str("    {\n");
+        out.push_str(&format!("      \"id\": \"{}\",\n", json::escape(&check.id)));
+        out.push_str(&format!("      \"category\": \"{}\",\n", json::escape(&check.category)));
+        out.push_str(&format!("      \"item\": \"{}\",\n", json::escape(&check.item)));
+        out.push_str(&format!("      \"passed\": {},\n", check.passed()));
+        out.push_str(&format!("      \"waived\": {},\n", check.waived));
+        out.push_str(&format!("      \"status\": \"{}\",\n", check.status));
+        match check.blocked_by {
+            Some(ref prerequisite) => out.push_str(&format!(
+                "      \"blocked_by\": \"{}\",\n",
+                json::escape(prerequisite)
+            )),
+            None => out.push_str("      \"blocked_by\": null,\n"),
+        }
+        match check.description {
+            Some(ref description) => out.push_str(&format!(
+                "      \"description\": \"{}\",\n",
+                json::escape(description)
+            )),
+            None => out.push_str("      \"description\": null,\n"),
+        }
+        if !report.reproducible {
+            out.push_str(&format!("      \"duration_ms\": {},\n", duration_ms(check.duration)));
+        }
+        out.push_str(&format!("      \"level\": \"{}\"\n", check.required_for));
+        out.push_str(&format!("    }}{}\n", comma));
+    }
+    out.push_str("  ],\n");
+
+    if !report.reproducible {
+        let slowest: Vec<String> = report
+            .slowest_checks(SLOWEST_CHECKS)
+            .iter()
+            .map(|c| {
+                format!(
+                    "{{ \"id\": \"{}\", \"duration_ms\": {} }}",
+                    json::escape(&c.id),
+                    duration_ms(c.duration)
+                )
+            })
+            .collect();
+        out.push_str("  \"timing\": {\n");
+        out.push_str(&format!("    \"total_ms\": {},\n", duration_ms(report.duration)));
+        out.push_str(&format!(
+            "    \"checks_ms\": {},\n",
+            duration_ms(report.checks_duration())
+        ));
+        out.push_str(&format!("    \"slowest\": [{}]\n", slowest.join(", ")));
+        out.push_str("  },\n");
+    }
+
+    let skipped: Vec<String> = report
+        .skipped_checks
+        .iter()
+        .map(|id| format!("\"{}\"", json::escape(id)))
+        .collect();
+    out.push_str(&format!("  \"skipped_checks\": [{}],\n", skipped.join(", ")));
+
+    let waivers = report
+        .waivers
+        .iter()
+        .map(|w| (w, false))
+        .chain(report.expired_waivers.iter().map(|w| (w, true)));
+    let waivers: Vec<String> = waivers
+        .map(|(waiver, expired)| {
+            let expires = match waiver.expires {
+                Some(ref date) => format!("\"{}\"", json::escape(date)),
+                None => "null".to_string(),
+            };
+            format!(
+                "    {{ \"id\": \"{}\", \"reason\": \"{}\", \"expires\": {}, \"expired\": {} }}",
+                json::escape(&waiver.id),
+                json::escape(&waiver.reason),
+                expires,
+                expired
+            )
+        })
+        .collect();
+    if waivers.is_empty() {
+        out.push_str("  \"waivers\": [],\n");
+    } else {
+        out.push_str(&format!("  \"waivers\": [\n{}\n  ],\n", waivers.join(",\n")));
+    }
+
+    out.push_str("  \"warnings\": [\n");
+    for (i, warning) in report.warnings.iter().enumerate() {
+        let comma = if i < report.warnings.len() - 1 {
+            ","
+        } else {
+            ""
+        };
+        let level = match warning.level {
+            WarningLevel::Info => "info",
+            WarningLevel::Warning => "warning",
+            WarningLevel::Critical => "critical",
+        };
+        out.push_str("    {\n");
+        let path = match warning.path {
+            Some(ref path) => json::string(&relative_display(path, &report.repository_path)),
+            None => "null".to_string(),
+        };
+        out.push_str(&format!("      \"level\": \"{}\",\n", level));
+        out.push_str(&format!("      \"message\": \"{}\",\n", json::escape(&warning.message)));
+        out.push_str(&format!("      \"path\": {}\n", path));
+        out.push_str(&format!("    }}{}\n", comma));
+    }
+    out.push_str("  ]\n");
+    out.push_str("}\n");
+
+    out
+}
+
+/// Render the report summary as a single-line JSON object
+///
+/// This is what `--format json --quiet` prints: the score, the highest level
+/// achieved (`null` when none is) and the warning counts, small enough to
+/// grep or feed to a log pipeline without the full report. Keys are flat so
+/// shell scripts can pick values out without a JSON parser.
+pub fn render_json_summary(report: &ComplianceReport) -> String {
+    let level = match report.highest_level() {
+        Some(level) => json::string(level.display_name()),
+        None => "null".to_string(),
+    };
+    let critical = report
+        .warnings
+        .iter()
+        .filter(|w| w.level == WarningLevel::Critical)
+        .count();
+    format!(
+        "{{\"repository\":{},\"standard_version\":\"{}\",\"passed\":{},\"total\":{},\
+         \"percentage\":{:.1},\"level\":{},\"bronze_compliant\":{},\
+         \"waived_compliance\":{},\"critical_warnings\":{},\"warnings\":{}}}\n",
+        json::string(&report.repository_display()),
+        report.standard_version,
+        report.passed_count(),
+        report.total_count(),
+        report.percentage(),
+        level,
+        report.bronze_compliance(),
+        report.meets_level_by_waivers(ComplianceLevel::Bronze),
+        critical,
+        report.warnings.len()
+    )
+}
+
+/// Quote a string as a YAML double-quoted scalar for TAP diagnostics
+fn yaml_quote(s: &str) -> String {
+    format!("\"{}\"", json::escape(s))
+}
+
+/// Render a report in TAP (Test Anything Protocol) version 13
+///
+/// Each check becomes one test point. Failed checks carry a YAML diagnostic
+/// block; security warnings are emitted as TAP comments.
+pub fn render_tap(report: &ComplianceReport) -> String {
+    let mut out = String::new();
+    out.push_str("TAP version 13\n");
+    out.push_str(&format!("1..{}\n", report.checks.len()));
+
+    for (i, check) in report.checks.iter().enumerate() {
+        // Waived checks are reported as skipped so TAP consumers still pass them,
+        // as are checks whose prerequisite failed or that a fail-fast run did
+        // not reach (that failure is reported) and checks that do not apply
+        let waiver = report.waivers.iter().find(|w| check.waived && w.id == check.id);
+        let directive = match (waiver, check.status) {
+            (Some(waiver), _) => format!(" # SKIP waived: {}", tap_escape(&waiver.reason)),
+            (None, CheckStatus::Skipped) => format!(
+                " # SKIP {}",
+                tap_escape(&check.skip_reason().unwrap_or_default())
+            ),
+            (None, CheckStatus::NotApplicable) => " # SKIP not applicable".to_string(),
+            (None, _) => String::new(),
+        };
+        let status = if check.is_failure() { "not ok" } else { "ok" };
+        out.push_str(&format!(
+            "{} {} - {}: {}{}\n",
+            status,
+            i + 1,
+            tap_escape(&check.category),
+            tap_escape(&check.item),
+            directive
+        ));
+        if status == "not ok" {
+            out.push_str("  ---\n");
+            out.push_str(&format!("  id: {}\n", yaml_quote(&check.id)));
+            out.push_str(&format!("  category: {}\n", yaml_quote(&check.category)));
+            out.push_str(&format!("  item: {}\n", yaml_quote(&check.item)));
+            out.push_str(&format!("  level: {}\n", check.required_for.display_name()));
+            if let Some(ref description) = check.description {
+                out.push_str(&format!("  message: {}\n", yaml_quote(description)));
+            }
+            out.push_str("  ...\n");
+        }
+    }
+
+    for warning in &report.warnings {
+        let level = match warning.level {
+            WarningLevel::Info => "info",
+            WarningLevel::Warning => "warning",
+            WarningLevel::Critical => "critical",
+        };
+        out.push_str(&format!(
+            "# {}: {}\n",
+            level,
+            warning.message.replace('\n', " ")
+        ));
+    }
+
+    out.push_str(&format!(
+        "# {}/{} checks passed ({:.1}%) against RSR {}\n",
+        report.passed_count(),
+        report.total_count(),
+        report.percentage(),
+        report.standard_version
+    ));
+    out
+}
+
+/// Make a porcelain field safe: tabs and line breaks become spaces
+fn porcelain_field(s: &str) -> String {
+    s.replace(['\t', '\r', '\n'], " ")
+}
+
+/// Render the porcelain format for scripts (`check --porcelain`)
+///
+/// One record per line, fields separated by tabs, the record type first:
+///
+/// ```text
+/// check\tRSR-DOC-001\tpass
+/// check\tRSR-BLD-002\twaived
+/// skipped\tRSR-SRC-002
+/// warning\tcritical\tSymlink escapes repository: link -> /etc
+/// level\tbronze
+/// ```
+///
+/// Check states are `pass`, `fail` (which includes checks skipped for a
+/// failed prerequisite and checks that could not be evaluated) and `waived`;
+/// checks that do not apply, and those a `--fail-fast` run stopped before,
+/// are `skipped` records. `level` is the highest
+/// compliance level reached, or `none`. This format is stable: records and
+/// fields are never changed or removed between minor versions. New record
+/// types may be added, so readers should skip types they do not know.
+pub fn render_porcelain(report: &ComplianceReport) -> String {
+    let mut out = String::new();
+    let not_run = |c: &&CheckResult| c.status == CheckStatus::Skipped && c.blocked_by.is_none();
+    for check in report.checks.iter().filter(|c| c.is_applicable() && !not_run(c)) {
+        let state = match (check.passed(), check.waived) {
+            (true, true) => "waived",
+            (true, false) => "pass",
+            (false, _) => "fail",
+        };
+        out.push_str(&format!("check\t{}\t{}\n", porcelain_field(&check.id), state));
+    }
+    let not_applicable = report.checks.iter().filter(|c| !c.is_applicable()).map(|c| &c.id);
+    let stopped = report.checks.iter().filter(not_run).map(|c| &c.id);
+    for id in report.skipped_checks.iter().chain(not_applicable).chain(stopped) {
+        out.push_str(&format!("skipped\t{}\n", porcelain_field(id)));
+    }
+    for warning in &report.warnings {
+        let level = match warning.level {
+            WarningLevel::Info => "info",
+            WarningLevel::Warning => "warning",
+            WarningLevel::Critical => "critical",
+        };
+        out.push_str(&format!("warning\t{}\t{}\n", level, porcelain_field(&warning.message)));
+    }
+    let level = report
+        .highest_level()
+        .map_or("none".to_string(), |level| level.display_name().to_lowercase());
+    out.push_str(&format!("level\t{}\n", level));
+    out
+}
+
+/// Escape text for XML character data and attribute values
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab and newlines are not valid XML 1.0
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Render a report as a JUnit XML document
+///
+/// Each check becomes a test case, classed by category. Waived checks and
+/// checks whose prerequisite failed are reported as skipped; security
+/// warnings go to `<system-err>`.
+pub fn render_junit(report: &ComplianceReport) -> String {
+    let count = |status: CheckStatus| {
+        report.checks.iter().filter(|c| c.status == status && !c.waived).count()
+    };
+    let failures = count(CheckStatus::Fail);
+    let errors = count(CheckStatus::Error);
+    let skipped =
+        report.waived_count() + count(CheckStatus::Skipped) + count(CheckStatus::NotApplicable);
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuites name=\"rhodibot\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" \
+         errors=\"{}\">\n",
+        report.checks.len(),
+        failures,
+        skipped,
+        errors
+    ));
+    out.push_str(&format!(
+        "  <testsuite name=\"RSR compliance\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" \
+         errors=\"{}\"",
+        report.checks.len(),
+        failures,
+        skipped,
+        errors
+    ));
+    if let Some(timestamp) = report.timestamp() {
+        out.push_str(&format!(" timestamp=\"{}\"", timestamp));
+    }
+    out.push_str(">\n");
+    out.push_str("    <properties>\n");
+    out.push_str(&format!(
+        "      <property name=\"rsr.standard_version\" value=\"{}\"/>\n",
+        report.standard_version
+    ));
+    out.push_str(&format!(
+        "      <property name=\"rhodibot.version\" value=\"{}\"/>\n",
+        VERSION
+    ));
+    out.push_str(&format!(
+        "      <property name=\"rhodibot.schema_version\" value=\"{}\"/>\n",
+        REPORT_SCHEMA_VERSION
+    ));
+    if !report.reproducible {
+        out.push_str(&format!(
+            "      <property name=\"rhodibot.platform\" value=\"{}\"/>\n",
+            platform()
+        ));
+    }
+    out.push_str("    </properties>\n");
+
+    for check in &report.checks {
+        let opening = format!(
+            "    <testcase classname=\"rhodibot.{}\" name=\"{}\"",
+            xml_escape(&check.category),
+            xml_escape(&format!("{} {}", check.id, check.item))
+        );
+        let waiver = report.waivers.iter().find(|w| check.waived && w.id == check.id);
+        if let Some(waiver) = waiver {
+            out.push_str(&opening);
+            out.push_str(">\n");
+            out.push_str(&format!(
+                "      <skipped message=\"waived: {}\"/>\n",
+                xml_escape(&waiver.reason)
+            ));
+            out.push_str("    </testcase>\n");
+        } else if check.status == CheckStatus::Skipped {
+            out.push_str(&opening);
+            out.push_str(">\n");
+            out.push_str(&format!(
+                "      <skipped message=\"{}\"/>\n",
+                xml_escape(&check.skip_reason().unwrap_or_default())
+            ));
+            out.push_str("    </testcase>\n");
+        } else if check.status == CheckStatus::NotApplicable {
+            out.push_str(&opening);
+            out.push_str(">\n      <skipped message=\"not applicable\"/>\n    </testcase>\n");
+        } else if check.status == CheckStatus::Error {
+            out.push_str(&opening);
+            out.push_str(">\n");
+            out.push_str(&format!(
+                "      <error message=\"{}\"/>\n",
+                xml_escape(check.description.as_deref().unwrap_or("could not be evaluated"))
+            ));
+            out.push_str("    </testcase>\n");
+        } else if !check.passed() {
+            out.push_str(&opening);
+            out.push_str(">\n");
+            out.push_str(&format!(
+                "      <failure message=\"RSR {} check failed: {} - {}\" type=\"{}\">{}</failure>\n",
+                check.required_for.display_name(),
+                xml_escape(&check.category),
+                xml_escape(&check.item),
+                check.required_for.display_name(),
+                xml_escape(check.description.as_deref().unwrap_or(""))
+            ));
+            out.push_str("    </testcase>\n");
+        } else {
+            out.push_str(&opening);
+            out.push_str("/>\n");
+        }
+    }
+
+    if !report.warnings.is_empty() {
+        out.push_str("    <system-err>");
+        for warning in &report.warnings {
+            let level = match warning.level {
+                WarningLevel::Info => "info",
+                WarningLevel::Warning => "warning",
+                WarningLevel::Critical => "critical",
+            };
+            out.push_str(&format!("{}: {}\n", level, xml_escape(&warning.message)));
+        }
+        out.push_str("</system-err>\n");
+    }
+
+    out.push_str("  </testsuite>\n");
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// Escape a Prometheus label value
+fn prometheus_label(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Render a report as gauges in the Prometheus text exposition format
+///
+/// Every sample carries a `repository` label so that files for several
+/// repositories can sit side by side in a textfile collector directory.
+pub fn render_prometheus(report: &ComplianceReport) -> String {
+    let repository = prometheus_label(&report.repository_display());
+    let gauges = [
+        (
+            "rsr_checks_passed",
+            "Number of RSR checks that passed",
+            report.passed_count().to_string(),
+        ),
+        (
+            "rsr_checks_total",
+            "Number of RSR checks run",
+            report.total_count().to_string(),
+        ),
+        (
+            "rsr_bronze_compliant",
+            "Whether Bronze-level RSR compliance is met (1) or not (0)",
+            u8::from(report.bronze_compliance()).to_string(),
+        ),
+        (
+            "rsr_compliance_percentage",
+            "Percentage of RSR checks that passed",
+            format!("{:.1}", report.percentage()),
+        ),
+    ];
+
+    let mut out = String::new();
+    for (name, help, value) in gauges {
+        out.push_str(&format!("# HELP {} {}\n", name, help));
+        out.push_str(&format!("# TYPE {} gauge\n", name));
+        out.push_str(&format!(
+            "{}{{repository=\"{}\"}} {}\n",
+            name, repository, value
+        ));
+    }
+    out.push_str("# HELP rsr_standard_info Version of the RSR standard checked against\n");
+    out.push_str("# TYPE rsr_standard_info gauge\n");
+    out.push_str(&format!(
+        "rsr_standard_info{{repository=\"{}\",version=\"{}\"}} 1\n",
+        repository,
+        report.standard_version
+    ));
+    out
+}
+
+/// 64-bit FNV-1a hash, used for stable, dependency-free fingerprints
+pub(crate) fn fnv1a64(data: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in data.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Repository-relative location reported for a check
+///
+/// `.well-known` entries are placed in the report's web root.
+fn check_location(report: &ComplianceReport, check: &crate::CheckResult) -> String {
+    let item = check.item.trim_end_matches(" directory").trim_end_matches('/');
+    if check.category != "Well-Known" {
+        item.to_string()
+    } else if item.starts_with(".well-known") {
+        crate::site::join(&report.web_root, item)
+    } else {
+        crate::site::join(&report.web_root, &format!(".well-known/{}", item))
+    }
+}
+
+/// Repository-relative path for a warning location
+pub fn relative_display(path: &Path, repo_root: &Path) -> String {
+    path.strip_prefix(repo_root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Append one Code Climate issue object to `out`
+fn push_code_climate_issue(
+    out: &mut String,
+    first: &mut bool,
+    check_name: &str,
+    category: &str,
+    description: &str,
+    severity: &str,
+    path: &str,
+) {
+    let fingerprint = format!(
+        "{:016x}",
+        fnv1a64(&format!("{}\u{0}{}\u{0}{}", check_name, description, path))
+    );
+    if !*first {
+        out.push_str(",\n");
+    }
+    *first = false;
+    out.push_str("  {\n");
+    out.push_str("    \"type\": \"issue\",\n");
+    out.push_str(&format!("    \"check_name\": \"{}\",\n", json::escape(check_name)));
+    out.push_str(&format!("    \"description\": \"{}\",\n", json::escape(description)));
+    out.push_str(&format!("    \"categories\": [\"{}\"],\n", category));
+    out.push_str(&format!("    \"fingerprint\": \"{}\",\n", fingerprint));
+    out.push_str(&format!("    \"severity\": \"{}\",\n", severity));
+    out.push_str("    \"location\": {\n");
+    out.push_str(&format!("      \"path\": \"{}\",\n", json::escape(path)));
+    out.push_str("      \"lines\": { \"begin\": 1 }\n");
+    out.push_str("    }\n");
+    out.push_str("  }");
+}
+
+/// Render a report as a GitLab Code Quality (Code Climate) JSON array
+///
+/// Only failed checks and security warnings are reported; passing checks are
+/// omitted as GitLab shows every entry as a finding in the merge request
+/// widget, and so are checks skipped because a prerequisite failed. Upload
+/// the result as a `codequality` report artifact.
+pub fn render_code_quality(report: &ComplianceReport) -> String {
+    let mut out = String::from("[\n");
+    let mut first = true;
+
+    for check in report.checks.iter().filter(|c| c.is_failure()) {
+        let severity = match check.required_for {
+            ComplianceLevel::Bronze => "major",
+            ComplianceLevel::Silver => "minor",
+            ComplianceLevel::Gold | ComplianceLevel::Platinum => "info",
+        };
+        let description = format!(
+            "RSR {} check failed: {} - {}",
+            check.required_for.display_name(),
+            check.category,
+            check.item
+        );
+        push_code_climate_issue(
+            &mut out,
+            &mut first,
+            &check.id,
+            "Style",
+            &description,
+            severity,
+            &check_location(report, check),
+        );
+    }
+
+    for warning in &report.warnings {
+        let severity = match warning.level {
+            WarningLevel::Info => "info",
+            WarningLevel::Warning => "minor",
+            WarningLevel::Critical => "critical",
+        };
+        let path = warning
+            .path
+            .as_ref()
+            .map(|p| relative_display(p, &report.repository_path))
+            .unwrap_or_else(|| ".".to_string());
+        push_code_climate_issue(
+            &mut out,
+            &mut first,
+            "rsr/security",
+            "Security",
+            &warning.message,
+            severity,
+            &path,
+        );
+    }
+
+    if !first {
+        out.push('\n');
+    }
+    out.push_str("]\n");
+    out
+}
+
+/// Append one SARIF result object to `out`
+fn push_sarif_result(
+    out: &mut String,
+    first: &mut bool,
+    rule_id: &str,
+    level: &str,
+    message: &str,
+    path: &str,
+) {
+    if !*first {
+        out.push_str(",\n");
+    }
+    *first = false;
+    out.push_str("        {\n");
+    out.push_str(&format!("          \"ruleId\": \"{}\",\n", json::escape(rule_id)));
+    out.push_str(&format!("          \"level\": \"{}\",\n", level));
+    out.push_str(&format!(
+        "          \"message\": {{ \"text\": \"{}\" }},\n",
+        json::escape(message)
+    ));
+    out.push_str("          \"locations\": [\n");
+    out.push_str("            {\n");
+    out.push_str("              \"physicalLocation\": {\n");
+    out.push_str(&format!(
+        "                \"artifactLocation\": {{ \"uri\": \"{}\" }},\n",
+        json::escape(path)
+    ));
+    out.push_str("                \"region\": { \"startLine\": 1 }\n");
+    out.push_str("              }\n");
+    out.push_str("            }\n");
+    out.push_str("          ]\n");
+    out.push_str("        }");
+}
+
+/// Render a report as SARIF 2.1.0
+///
+/// Every check is declared as a rule; failed checks and security warnings are
+/// reported as results. Suitable for GitHub code scanning and other SARIF
+/// consumers.
+pub fn render_sarif(report: &ComplianceReport) -> String {
+    let mut out = String::new();
+    out.push_str("{\n");
+    out.push_str("  \"$schema\": \"https://json.schemastore.org/sarif-2.1.0.json\",\n");
+    out.push_str("  \"version\": \"2.1.0\",\n");
+    out.push_str("  \"runs\": [\n");
+    out.push_str("    {\n");
+    out.push_str("      \"tool\": {\n");
+    out.push_str("        \"driver\": {\n");
+    out.push_str("          \"name\": \"rhodibot\",\n");
+    out.push_str(&format!("          \"version\": \"{}\",\n", crate::VERSION));
+    out.push_str(&format!("          \"informationUri\": \"{}\",\n", crate::STANDARD_URL));
+    let platform = if report.reproducible {
+        String::new()
+    } else {
+        format!(", \"platform\": {}", json::string(&platform()))
+    };
+    out.push_str(&format!(
+        "          \"properties\": {{\"standardVersion\": \"{}\", \"schemaVersion\": {}{}}},\n",
+        report.standard_version, REPORT_SCHEMA_VERSION, platform
+    ));
+    out.push_str("          \"rules\": [\n");
+    for (i, check) in report.checks.iter().enumerate() {
+        out.push_str("            {\n");
+        out.push_str(&format!(
+            "              \"id\": \"{}\",\n",
+            json::escape(&check.id)
+        ));
+        out.push_str(&format!(
+            "              \"shortDescription\": {{ \"text\": \"{}: {} ({})\" }}",
+            json::escape(&check.category),
+            json::escape(&check.item),
+            check.required_for.display_name()
+        ));
+        if let Some(ref description) = check.description {
+            out.push_str(&format!(
+                ",\n              \"fullDescription\": {{ \"text\": \"{}\" }}",
+                json::escape(description)
+            ));
+        }
+        if let Some(spec) = catalog::lookup(&check.id) {
+            out.push_str(&format!(
+                ",\n              \"helpUri\": \"{}\"",
+                json::escape(&spec.help_url())
+            ));
+        }
+        out.push_str("\n            }");
+        out.push_str(if i + 1 < report.checks.len() { ",\n" } else { "\n" });
+    }
+    if !report.warnings.is_empty() {
+        if !report.checks.is_empty() {
+            // Replace the trailing newline of the last rule with a separator
+            out.pop();
+            out.push_str(",\n");
+        }
+        out.push_str("            {\n");
+        out.push_str("              \"id\": \"rsr/security\",\n");
+        out.push_str(
+            "              \"shortDescription\": { \"text\": \"Repository security warning\" }\n",
+        );
+        out.push_str("            }\n");
+    }
+    out.push_str("          ]\n");
+    out.push_str("        }\n");
+    out.push_str("      },\n");
+    if !report.metadata.is_empty() {
+        let properties = render_metadata_json(&report.metadata);
+        out.push_str(&format!("      \"properties\": {},\n", properties));
+    }
+    out.push_str("      \"results\": [\n");
+
+    let mut first = true;
+    // A check skipped because its prerequisite failed adds nothing to the
+    // prerequisite's own result
+    for check in report.checks.iter().filter(|c| c.is_failure()) {
+        let level = match check.required_for {
+            ComplianceLevel::Bronze => "error",
+            _ => "warning",
+        };
+        let message = format!(
+            "RSR {} check failed: {} - {}",
+            check.required_for.display_name(),
+            check.category,
+            check.item
+        );
+        push_sarif_result(
+            &mut out,
+            &mut first,
+            &check.id,
+            level,
+            &message,
+            &check_location(report, check),
+        );
+    }
+    for warning in &report.warnings {
+        let level = match warning.level {
+            WarningLevel::Info => "note",
+            WarningLevel::Warning => "warning",
+            WarningLevel::Critical => "error",
+        };
+        let path = warning
+            .path
+            .as_ref()
+            .map(|p| relative_display(p, &report.repository_path))
+            .unwrap_or_else(|| ".".to_string());
+        push_sarif_result(&mut out, &mut first, "rsr/security", level, &warning.message, &path);
+    }
+    if !first {
+        out.push('\n');
+    }
+
+    out.push_str("      ]\n");
+    out.push_str("    }\n");
+    out.push_str("  ]\n");
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_creates_and_replaces() {
+        let dir = std::env::temp_dir().join("rhodibot_output_test");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("nested").join("report.json");
+
+        write_atomic(&path, "first").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+
+        write_atomic(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+
+        // No temporary files should be left behind
+        let leftovers = fs::read_dir(dir.join("nested")).unwrap().count();
+        assert_eq!(leftovers, 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_temp_path_is_sibling() {
+        let temp = temp_path_for(Path::new("out/report.sarif"));
+        assert_eq!(temp.parent(), Some(Path::new("out")));
+        assert!(temp
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with(".report.sarif.tmp-"));
+    }
+
+    #[test]
+    fn test_render_json_summary() {
+        let mut report = ComplianceReport::new(PathBuf::from("/srv/\"repo\""));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_warning(WarningLevel::Info, "Symlink inside repository", None);
+
+        let summary = render_json_summary(&report);
+        assert_eq!(summary.lines().count(), 1);
+        assert!(summary.starts_with("{\"repository\":\"/srv/\\\"repo\\\"\",\"standard_version\""));
+        assert!(summary.contains("\"passed\":1,\"total\":1,\"percentage\":100.0,"));
+        assert!(summary.ends_with("\"critical_warnings\":0,\"warnings\":1}\n"));
+
+        report.add_warning(WarningLevel::Critical, "Symlink escapes", None);
+        let summary = render_json_summary(&report);
+        assert!(summary
+            .contains("\"level\":null,\"bronze_compliant\":true,\"waived_compliance\":false,"));
+        assert!(summary.contains("\"critical_warnings\":1,"));
+    }
+
+    #[test]
+    fn test_json_levels() {
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_check("Documentation", "CONTRIBUTING.md", false, ComplianceLevel::Silver);
+
+        let json = render_json_report(&report);
+        assert!(json.contains(
+            "  \"levels\": {\n    \"bronze\": true,\n    \"silver\": false,\n    \
+             \"gold\": false,\n    \"platinum\": false\n  },\n  \"highest_level\": \"Bronze\",\n"
+        ));
+
+        report.add_warning(WarningLevel::Critical, "Symlink escapes", None);
+        let json = render_json_report(&report);
+        assert!(json.contains("    \"bronze\": false,\n"));
+        assert!(json.contains("  \"highest_level\": null,\n"));
+    }
+
+    #[test]
+    fn test_render_tap() {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/test"));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_check("Documentation", "Issue #1", false, ComplianceLevel::Bronze);
+        report.add_warning(WarningLevel::Critical, "Symlink escapes", None);
+
+        let tap = render_tap(&report);
+        let lines: Vec<&str> = tap.lines().collect();
+        assert_eq!(lines[0], "TAP version 13");
+        assert_eq!(lines[1], "1..2");
+        assert_eq!(lines[2], "ok 1 - Documentation: README.md");
+        assert_eq!(lines[3], "not ok 2 - Documentation: Issue \\#1");
+        assert_eq!(lines[4], "  ---");
+        assert_eq!(lines[5], "  id: \"rsr/documentation/issue-1\"");
+        assert!(tap.contains("  level: Bronze\n  ...\n"));
+        assert!(tap.contains("# critical: Symlink escapes\n"));
+    }
+
+    #[test]
+    fn test_render_tap_waived_check_is_skipped() {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/test"));
+        report.add_spec_check(catalog::lookup("RSR-BLD-002").unwrap(), false);
+        let suppressions =
+            crate::suppressions::Suppressions::parse("[RSR-BLD-002]\nreason = \"Guix\"").unwrap();
+        report.apply_suppressions(&suppressions);
+
+        let tap = render_tap(&report);
+        assert!(tap.contains("ok 1 - Build System: flake.nix # SKIP waived: Guix\n"));
+        assert!(!tap.contains("not ok"));
+    }
+
+    #[test]
+    fn test_render_porcelain() {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/test"));
+        report.add_spec_check(catalog::lookup("RSR-DOC-001").unwrap(), true);
+        report.add_spec_check(catalog::lookup("RSR-DOC-002").unwrap(), false);
+        report.add_spec_check(catalog::lookup("RSR-BLD-002").unwrap(), false);
+        let suppressions =
+            crate::suppressions::Suppressions::parse("[RSR-BLD-002]\nreason = \"Guix\"").unwrap();
+        report.apply_suppressions(&suppressions);
+        report.skipped_checks.push("RSR-SRC-002".to_string());
+        report.add_warning(WarningLevel::Warning, "Symlink\tto\nsomewhere", None);
+
+        assert_eq!(
+            render_porcelain(&report),
+            "check\tRSR-DOC-001\tpass\n\
+             check\tRSR-DOC-002\tfail\n\
+             check\tRSR-BLD-002\twaived\n\
+             skipped\tRSR-SRC-002\n\
+             warning\twarning\tSymlink to somewhere\n\
+             level\tnone\n"
+        );
+    }
+
+    #[test]
+    fn test_render_junit() {
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_spec_check(catalog::lookup("RSR-WKN-003").unwrap(), false);
+        report.add_spec_check(catalog::lookup("RSR-BLD-002").unwrap(), false);
+        let suppressions =
+            crate::suppressions::Suppressions::parse("[RSR-BLD-002]\nreason = \"Nix & Guix\"")
+                .unwrap();
+        report.apply_suppressions(&suppressions);
+        report.add_warning(WarningLevel::Critical, "Symlink <x> escapes", None);
+
+        let xml = render_junit(&report);
+        assert!(xml.starts_with("<?xml version=\"1.0\""));
+        assert!(xml.contains("tests=\"3\" failures=\"1\" skipped=\"1\""));
+        assert!(xml.contains(
+            "<testcase classname=\"rhodibot.Documentation\" name=\"rsr/documentation/readme.md README.md\"/>"
+        ));
+        assert!(xml.contains("<failure message=\"RSR Bronze check failed: Well-Known - ai.txt\""));
+        assert!(xml.contains("<skipped message=\"waived: Nix &amp; Guix\"/>"));
+        assert!(xml.contains("critical: Symlink &lt;x&gt; escapes"));
+        assert_eq!(xml_escape("a\u{1}b"), "ab");
+    }
+
+    #[test]
+    fn test_identity_in_every_format() {
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        let platform = platform();
+        assert_eq!(
+            identity_line(&report),
+            format!("rhodibot {} (report schema 1, {})", VERSION, platform)
+        );
+        assert!(render_json_report(&report).contains("  \"schema_version\": 1,\n"));
+        assert!(render_json_report(&report).contains(&format!("\"platform\": \"{}\"", platform)));
+        assert!(render_sarif(&report).contains("\"schemaVersion\": 1, \"platform\""));
+        assert!(render_junit(&report)
+            .contains("<property name=\"rhodibot.schema_version\" value=\"1\"/>"));
+
+        // Reproducible reports must not depend on the machine
+        report.make_reproducible();
+        assert!(identity_line(&report).ends_with("(report schema 1)"));
+        assert!(!render_json_report(&report).contains("\"platform\""));
+        assert!(!render_sarif(&report).contains("\"platform\""));
+        assert!(!render_junit(&report).contains("rhodibot.platform"));
+    }
+
+    #[test]
+    fn test_blocked_checks_are_skipped() {
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_spec_check(catalog::lookup("RSR-WKN-001").unwrap(), false);
+        report.add_spec_check(catalog::lookup("RSR-WKN-002").unwrap(), false);
+        report.checks[1].status = CheckStatus::Skipped;
+        report.checks[1].blocked_by = Some("RSR-WKN-001".to_string());
+
+        let xml = render_junit(&report);
+        assert!(xml.contains("tests=\"2\" failures=\"1\" skipped=\"1\""));
+        assert!(xml.contains("<skipped message=\"prerequisite RSR-WKN-001 failed\"/>"));
+        let tap = render_tap(&report);
+        assert!(tap.contains("ok 2 - Well-Known: security.txt # SKIP prerequisite RSR-WKN-001"));
+        assert!(render_json_report(&report).contains("\"blocked_by\": \"RSR-WKN-001\""));
+        let sarif = render_sarif(&report);
+        assert_eq!(sarif.matches("\"ruleId\": \"RSR-WKN-001\"").count(), 1);
+        assert!(!sarif.contains("\"ruleId\": \"RSR-WKN-002\""));
+        assert!(!render_code_quality(&report).contains("RSR-WKN-002"));
+
+        // Checks a fail-fast run did not reach are not blocked by anything
+        report.add_spec_check(catalog::lookup("RSR-WKN-003").unwrap(), false);
+        report.checks[2].status = CheckStatus::Skipped;
+        assert!(render_junit(&report).contains("<skipped message=\"not run: --fail-fast\"/>"));
+        assert!(render_tap(&report).contains(" # SKIP not run: --fail-fast\n"));
+        let porcelain = render_porcelain(&report);
+        assert!(porcelain.contains("check\tRSR-WKN-002\tfail\nskipped\tRSR-WKN-003\n"));
+    }
+
+    #[test]
+    fn test_check_status_in_reports() {
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_spec_check(catalog::lookup("RSR-DOC-001").unwrap(), false);
+        report.add_spec_check(catalog::lookup("RSR-DOC-002").unwrap(), false);
+        report.checks[0].status = CheckStatus::Error;
+        report.checks[1].status = CheckStatus::NotApplicable;
+
+        let json = render_json_report(&report);
+        assert!(json.contains("\"waived\": false,\n      \"status\": \"error\","));
+        assert!(json.contains("\"status\": \"not_applicable\""));
+        let xml = render_junit(&report);
+        assert!(xml.contains("tests=\"2\" failures=\"0\" skipped=\"1\" errors=\"1\""));
+        assert!(xml.contains("<skipped message=\"not applicable\"/>"));
+        let tap = render_tap(&report);
+        assert!(tap.starts_with("TAP version 13\n1..2\nnot ok 1 - "));
+        assert!(tap.contains("ok 2 - Documentation: LICENSE.txt # SKIP not applicable"));
+        let porcelain = render_porcelain(&report);
+        assert!(porcelain.starts_with("check\tRSR-DOC-001\tfail\nskipped\tRSR-DOC-002\n"));
+        assert!(render_sarif(&report).contains("\"ruleId\": \"RSR-DOC-001\""));
+        assert!(!render_sarif(&report).contains("\"ruleId\": \"RSR-DOC-002\""));
+    }
+
+    #[test]
+    fn test_render_prometheus() {
+        let mut report = ComplianceReport::new(PathBuf::from("/srv/my \"repo\""));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_check("Documentation", "LICENSE.txt", false, ComplianceLevel::Bronze);
+
+        let metrics = render_prometheus(&report);
+        assert!(metrics.starts_with("# HELP rsr_checks_passed "));
+        assert!(metrics.contains("# TYPE rsr_checks_total gauge\n"));
+        assert!(metrics.contains("rsr_checks_passed{repository=\"/srv/my \\\"repo\\\"\"} 1\n"));
+        assert!(metrics.contains("rsr_checks_total{repository=\"/srv/my \\\"repo\\\"\"} 2\n"));
+        assert!(metrics.contains("rsr_bronze_compliant{repository=\"/srv/my \\\"repo\\\"\"} 0\n"));
+    }
+
+    #[test]
+    fn test_render_code_quality() {
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_spec_check(catalog::lookup("RSR-WKN-003").unwrap(), false);
+        report.add_warning(
+            WarningLevel::Critical,
+            "Symlink escapes",
+            Some(PathBuf::from("/repo/LICENSE.txt")),
+        );
+
+        let json = render_code_quality(&report);
+        assert!(json.starts_with("[\n"));
+        assert!(json.ends_with("]\n"));
+        assert_eq!(json.matches("\"type\": \"issue\"").count(), 2);
+        assert!(json.contains("\"check_name\": \"RSR-WKN-003\""));
+        assert!(json.contains("\"path\": \".well-known/ai.txt\""));
+        assert!(json.contains("\"path\": \"LICENSE.txt\""));
+        assert!(json.contains("\"severity\": \"critical\""));
+        assert!(!json.contains("README.md"));
+
+        // Fingerprints are stable across runs
+        assert_eq!(json, render_code_quality(&report));
+    }
+
+    #[test]
+    fn test_render_code_quality_empty() {
+        let report = ComplianceReport::new(PathBuf::from("/repo"));
+        assert_eq!(render_code_quality(&report), "[\n]\n");
+    }
+
+    #[test]
+    fn test_render_sarif() {
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_spec_check(catalog::lookup("RSR-SRC-001").unwrap(), false);
+        report.add_warning(WarningLevel::Info, "'README.md' is a symlink", None);
+
+        let sarif = render_sarif(&report);
+        assert!(sarif.contains("\"version\": \"2.1.0\""));
+        assert!(sarif.contains("\"id\": \"rsr/documentation/readme.md\""));
+        assert!(sarif.contains("\"ruleId\": \"RSR-SRC-001\""));
+        assert!(sarif.contains("\"fullDescription\": { \"text\": \"src/ keeps source code"));
+        assert!(sarif.contains("\"helpUri\": \"https://github.com/hyperpolymath/rhodium-standard-repositories#rsr-src-001\""));
+        assert!(sarif.contains("\"uri\": \"src\""));
+        assert!(sarif.contains("\"level\": \"note\""));
+        // Passing checks are rules but not results
+        assert_eq!(sarif.matches("\"ruleId\"").count(), 2);
+        // Balanced brackets are a cheap structural sanity check
+        assert_eq!(sarif.matches('{').count(), sarif.matches('}').count());
+        assert_eq!(sarif.matches('[').count(), sarif.matches(']').count());
+    }
+}
@@ -0,0 +1,200 @@
+//! Platform-aware path handling for the symlink security checks
+//!
+//! Deciding whether a link escapes the repository means comparing paths, and
+//! on Windows a plain `Path::starts_with` is not enough: drive letters and the
+//! rest of the path compare case-insensitively, NTFS junctions behave like
+//! directory symlinks that always carry an absolute target, and
+//! `canonicalize` returns verbatim (`\\?\`) paths that never share a prefix
+//! with the ordinary form of the same path. Every path the security checks
+//! compare goes through [`normalize`] first.
+
+use std::borrow::Cow;
+use std::fs::Metadata;
+use std::path::{Component, Path, PathBuf};
+
+/// Remove a Windows verbatim or NT namespace prefix from a path string
+///
+/// `\\?\C:\dir` and `\??\C:\dir` become `C:\dir`, and `\\?\UNC\server\share`
+/// becomes `\\server\share`. Verbatim paths without a drive letter (such as
+/// volume GUID paths) have no ordinary form and are returned unchanged.
+pub fn strip_verbatim_prefix(path: &str) -> Cow<'_, str> {
+    for prefix in [r"\\?\", r"\??\"] {
+        if let Some(rest) = path.strip_prefix(prefix) {
+            if let Some(share) = rest
+                .strip_prefix(r"UNC\")
+                .or_else(|| rest.strip_prefix(r"unc\"))
+            {
+                return Cow::Owned(format!(r"\\{}", share));
+            }
+            let bytes = rest.as_bytes();
+            if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+                return Cow::Borrowed(rest);
+            }
+        }
+    }
+    Cow::Borrowed(path)
+}
+
+/// Bring a path into the form used for containment checks
+///
+/// Strips verbatim prefixes added by `canonicalize` on Windows; other paths
+/// are returned as they are.
+pub fn normalize(path: &Path) -> PathBuf {
+    match path.to_str().map(strip_verbatim_prefix) {
+        Some(Cow::Owned(stripped)) => PathBuf::from(stripped),
+        Some(Cow::Borrowed(stripped)) => PathBuf::from(stripped),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Whether `metadata` (from `symlink_metadata`) describes a link to follow
+///
+/// On Windows this covers symbolic links and junctions (mount points), which
+/// the standard library both reports as symlinks. Other reparse points, such
+/// as cloud-file placeholders, are treated as ordinary files.
+pub fn is_link(metadata: &Metadata) -> bool {
+    metadata.file_type().is_symlink()
+}
+
+/// Whether the link at `path` is an NTFS junction rather than a symlink
+///
+/// The standard library does not expose reparse tags, so junctions are
+/// recognised by their shape: a directory link whose target is an absolute
+/// NT path (surfaced as a `\\?\` verbatim path). An absolute directory symlink
+/// looks the same, but has identical security semantics.
+#[cfg(windows)]
+pub fn is_junction(path: &Path, metadata: &Metadata) -> bool {
+    use std::os::windows::fs::FileTypeExt;
+
+    metadata.file_type().is_symlink_dir()
+        && std::fs::read_link(path)
+            .map(|target| target.as_os_str().to_string_lossy().starts_with(r"\\?\"))
+            .unwrap_or(false)
+}
+
+/// Junctions only exist on Windows
+#[cfg(not(windows))]
+pub fn is_junction(_path: &Path, _metadata: &Metadata) -> bool {
+    false
+}
+
+/// Comparable form of one path component
+///
+/// Windows paths are case-insensitive, and `c:` and `C:` name the same drive.
+fn component_key(component: Component) -> String {
+    let text = component.as_os_str().to_string_lossy();
+    if cfg!(windows) {
+        text.to_lowercase()
+    } else {
+        text.into_owned()
+    }
+}
+
+/// Whether `path` lies inside `root`, comparing whole components
+///
+/// Both paths are normalized first. Case-sensitive on Unix and
+/// case-insensitive on Windows.
+pub fn path_starts_with(path: &Path, root: &Path) -> bool {
+    let (path, root) = (normalize(path), normalize(root));
+    let mut path_components = path.components();
+    root.components().all(|root_component| {
+        path_components
+            .next()
+            .map_or(false, |c| component_key(c) == component_key(root_component))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_verbatim_prefix() {
+        assert_eq!(strip_verbatim_prefix(r"\\?\C:\repo\src"), r"C:\repo\src");
+        assert_eq!(strip_verbatim_prefix(r"\??\d:\repo"), r"d:\repo");
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\server\share\repo"),
+            r"\\server\share\repo"
+        );
+        // No ordinary equivalent: left alone
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\Volume{0b1c}\repo"),
+            r"\\?\Volume{0b1c}\repo"
+        );
+        assert_eq!(strip_verbatim_prefix("/home/dev/repo"), "/home/dev/repo");
+        assert_eq!(strip_verbatim_prefix(r"C:\repo"), r"C:\repo");
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(Path::new(r"\\?\C:\repo")), PathBuf::from(r"C:\repo"));
+        assert_eq!(normalize(Path::new("/srv/repo")), PathBuf::from("/srv/repo"));
+    }
+
+    #[test]
+    fn test_path_starts_with_whole_components() {
+        assert!(path_starts_with(Path::new("/repo/docs/README.md"), Path::new("/repo")));
+        assert!(path_starts_with(Path::new("/repo"), Path::new("/repo")));
+        assert!(!path_starts_with(Path::new("/repository"), Path::new("/repo")));
+        assert!(!path_starts_with(Path::new("/etc/passwd"), Path::new("/repo")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_path_starts_with_case_sensitive_on_unix() {
+        assert!(!path_starts_with(Path::new("/Repo/file"), Path::new("/repo")));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_path_starts_with_ignores_case_on_windows() {
+        assert!(path_starts_with(
+            Path::new(r"c:\Users\Dev\Repo\README.md"),
+            Path::new(r"C:\users\dev\repo")
+        ));
+        assert!(path_starts_with(Path::new(r"C:/Repo/src"), Path::new(r"c:\repo")));
+        assert!(!path_starts_with(Path::new(r"D:\repo\src"), Path::new(r"C:\repo")));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_canonicalized_paths_stay_inside_root() {
+        let root = std::env::current_dir().unwrap();
+        let canonical = root.canonicalize().unwrap();
+        // canonicalize adds a \\?\ prefix the plain working directory lacks
+        assert!(canonical.to_string_lossy().starts_with(r"\\?\"));
+        assert!(path_starts_with(&canonical.join("file.txt"), &root));
+        assert!(path_starts_with(&root.join("file.txt"), &canonical));
+        assert!(path_starts_with(
+            Path::new(r"\\?\UNC\Server\Share\repo\a"),
+            Path::new(r"\\server\share\repo")
+        ));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_junction_detection() {
+        use std::fs;
+        use std::process::Command;
+
+        let base = std::env::temp_dir().join(format!("rhodibot_junction_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        fs::create_dir_all(base.join("target")).unwrap();
+        let link = base.join("link");
+        let created = Command::new("cmd")
+            .args(["/C", "mklink", "/J"])
+            .arg(&link)
+            .arg(base.join("target"))
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if created {
+            let metadata = fs::symlink_metadata(&link).unwrap();
+            assert!(is_link(&metadata));
+            assert!(is_junction(&link, &metadata));
+            let plain = fs::symlink_metadata(base.join("target")).unwrap();
+            assert!(!is_junction(&base.join("target"), &plain));
+        }
+        let _ = fs::remove_dir_all(&base);
+    }
+}
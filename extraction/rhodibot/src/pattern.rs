@@ -0,0 +1,152 @@
+//! Line patterns for content rules
+//!
+//! A small, std-only stand-in for regular expressions, enough for the
+//! `must_match` assertions of organization rules: `*` matches any run of
+//! characters and `?` any single one, `^` and `$` anchor the pattern to the
+//! start and end of a line, `\` takes the next character literally, and a
+//! leading `(?i)` makes the match case-insensitive. A pattern matches a text
+//! if it matches within one of its lines.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Char(char),
+    /// `?`
+    One,
+    /// `*`
+    Any,
+}
+
+/// A parsed `must_match` pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinePattern {
+    source: String,
+    case_insensitive: bool,
+    tokens: Vec<Token>,
+}
+
+impl LinePattern {
+    /// Parse a pattern, e.g. `(?i)^contact: *@*`
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let (case_insensitive, body) = match source.strip_prefix("(?i)") {
+            Some(rest) => (true, rest.to_lowercase()),
+            None => (false, source.to_string()),
+        };
+        if body.is_empty() {
+            return Err(format!("empty pattern '{}'", source));
+        }
+
+        let mut tokens = Vec::new();
+        let mut chars = body.chars().peekable();
+        let mut anchored_start = false;
+        let mut anchored_end = false;
+        if chars.peek() == Some(&'^') {
+            chars.next();
+            anchored_start = true;
+        }
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(escaped) => tokens.push(Token::Char(escaped)),
+                    None => return Err(format!("pattern '{}' ends with '\\'", source)),
+                },
+                '$' if chars.peek().is_none() => anchored_end = true,
+                '*' => tokens.push(Token::Any),
+                '?' => tokens.push(Token::One),
+                c => tokens.push(Token::Char(c)),
+            }
+        }
+        if !anchored_start {
+            tokens.insert(0, Token::Any);
+        }
+        if !anchored_end {
+            tokens.push(Token::Any);
+        }
+        Ok(Self {
+            source: source.to_string(),
+            case_insensitive,
+            tokens,
+        })
+    }
+
+    /// Whether the pattern matches `line` (which should not contain newlines)
+    pub fn is_match(&self, line: &str) -> bool {
+        let text: Vec<char> = if self.case_insensitive {
+            line.to_lowercase().chars().collect()
+        } else {
+            line.chars().collect()
+        };
+        wildcard_match(&self.tokens, &text)
+    }
+
+    /// 1-based number of the first line of `text` the pattern matches
+    pub fn find_line(&self, text: &str) -> Option<usize> {
+        text.lines().position(|line| self.is_match(line)).map(|i| i + 1)
+    }
+}
+
+impl fmt::Display for LinePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Match the whole of `text`, backtracking only to the last `*`
+fn wildcard_match(tokens: &[Token], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` and the text position it was tried at
+    let mut retry: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match tokens.get(p) {
+            Some(Token::Any) => {
+                retry = Some((p + 1, t));
+                p += 1;
+            }
+            Some(Token::One) => {
+                p += 1;
+                t += 1;
+            }
+            Some(Token::Char(c)) if *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match retry {
+                Some((after_star, tried)) => {
+                    p = after_star;
+                    t = tried + 1;
+                    retry = Some((after_star, tried + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    tokens[p..].iter().all(|token| *token == Token::Any)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_pattern() {
+        let contact = LinePattern::parse("(?i)contact").unwrap();
+        assert!(contact.is_match("Security CONTACT: sec@example.org"));
+        assert!(!contact.is_match("Report issues privately"));
+
+        let field = LinePattern::parse("^Contact: *@?*.org$").unwrap();
+        assert!(field.is_match("Contact: sec@example.org"));
+        assert!(!field.is_match(" Contact: sec@example.org"));
+        assert!(!field.is_match("Contact: sec@example.com"));
+        assert_eq!(field.find_line("# Policy\nContact: a@b.org\n"), Some(2));
+        assert_eq!(field.to_string(), "^Contact: *@?*.org$");
+
+        let literal = LinePattern::parse("cost \\*\\?\\$").unwrap();
+        assert!(literal.is_match("the cost *?$ here"));
+        assert!(!literal.is_match("the cost 5"));
+        assert!(LinePattern::parse("a$b").unwrap().is_match("xa$by"));
+
+        assert!(LinePattern::parse("(?i)").is_err());
+        assert!(LinePattern::parse("trailing\\").is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Judging a report against an organization's policy
+//!
+//! Verification produces a [`ComplianceReport`]; whether that report is
+//! good enough is a separate question, answered by a [`Policy`] (the
+//! `[policy]` section of `.rhodibot.toml`). Keeping the two apart lets an
+//! embedder store reports and judge them again later, under another
+//! organization's policy, without scanning the repository a second time:
+//!
+//! ```rust
+//! use rhodibot::policy::{Outcome, Policy};
+//! use rhodibot::{ComplianceLevel, ComplianceReport};
+//! use std::path::PathBuf;
+//!
+//! let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+//! report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+//! report.add_check("Documentation", "AUTHORS", false, ComplianceLevel::Silver);
+//!
+//! assert_eq!(Policy::default().evaluate(&report).outcome, Outcome::Pass);
+//! let silver = Policy {
+//!     fail_on_level: ComplianceLevel::Silver,
+//!     ..Policy::default()
+//! };
+//! assert_eq!(silver.evaluate(&report).outcome, Outcome::Fail);
+//! ```
+
+use crate::{exit_codes, ComplianceReport, WarningLevel};
+use std::fmt;
+
+pub use crate::config::PolicyConfig as Policy;
+
+/// How a report fares under a policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The policy is met
+    Pass,
+    /// The policy is met, with something to look at: waivers carry the
+    /// required level, or security warnings the policy lets through
+    Warn,
+    /// The policy is not met
+    Fail,
+}
+
+impl Outcome {
+    /// Identifier used in machine-readable output
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Pass => "pass",
+            Outcome::Warn => "warn",
+            Outcome::Fail => "fail",
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The judgement of a report under a policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    pub outcome: Outcome,
+    /// Why the outcome is not a plain pass, one reason per line; empty for
+    /// a pass
+    pub reasons: Vec<String>,
+    /// Process exit code the CLI would use (see [`exit_codes`])
+    pub exit_code: i32,
+}
+
+impl Verdict {
+    /// Whether the policy is met, with or without warnings
+    pub fn is_met(&self) -> bool {
+        self.outcome != Outcome::Fail
+    }
+}
+
+impl Policy {
+    /// Judge `report` under this policy
+    ///
+    /// Security warnings are weighed first: critical ones always fail, and
+    /// Warning-level ones fail with `fail_on_warning`. Then the required
+    /// level and the minimum percentage. A report that passes both is a
+    /// warning when waivers carry the level or when it has Warning-level
+    /// security warnings.
+    pub fn evaluate(&self, report: &ComplianceReport) -> Verdict {
+        let mut security = Vec::new();
+        let mut warnings = Vec::new();
+        for warning in &report.warnings {
+            match warning.level {
+                WarningLevel::Critical => {
+                    security.push(format!("critical security warning: {}", warning.message))
+                }
+                WarningLevel::Warning if self.fail_on_warning => security.push(format!(
+                    "security warning (fail_on_warning): {}",
+                    warning.message
+                )),
+                WarningLevel::Warning => {
+                    warnings.push(format!("security warning: {}", warning.message))
+                }
+                WarningLevel::Info => {}
+            }
+        }
+
+        let level = self.fail_on_level;
+        let mut compliance: Vec<String> = report
+            .checks
+            .iter()
+            .filter(|c| c.required_for <= level && c.is_applicable() && !c.passed())
+            .map(|c| format!("{} {} {} (required for {})", c.id, c.item, c.status, level))
+            .collect();
+        if let Some(minimum) = self.fail_below_percentage {
+            if report.percentage() < minimum {
+                compliance.push(format!(
+                    "{:.1}% of checks passed, below the minimum of {}%",
+                    report.percentage(),
+                    minimum
+                ));
+            }
+        }
+
+        let waived = report.meets_level_by_waivers(level);
+        if waived {
+            let ids: Vec<&str> = report
+                .checks
+                .iter()
+                .filter(|c| c.waived && c.required_for <= level)
+                .map(|c| c.id.as_str())
+                .collect();
+            warnings.insert(0, format!("{} met only through waivers: {}", level, ids.join(", ")));
+        }
+
+        let (outcome, exit_code, reasons) = if !security.is_empty() {
+            security.extend(compliance);
+            (Outcome::Fail, exit_codes::SECURITY_WARNING, security)
+        } else if !compliance.is_empty() {
+            (Outcome::Fail, exit_codes::COMPLIANCE_FAILED, compliance)
+        } else {
+            let exit_code = if self.distinguish_waived && waived {
+                exit_codes::WAIVED_COMPLIANCE
+            } else {
+                exit_codes::SUCCESS
+            };
+            let outcome = if warnings.is_empty() { Outcome::Pass } else { Outcome::Warn };
+            (outcome, exit_code, warnings)
+        };
+        Verdict {
+            outcome,
+            reasons,
+            exit_code,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ComplianceLevel;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_evaluate() {
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Docs", "README", true, ComplianceLevel::Bronze);
+        report.add_check("Docs", "LICENSE", false, ComplianceLevel::Bronze);
+        report.add_check("Docs", "AUTHORS", false, ComplianceLevel::Silver);
+        let verdict = Policy::default().evaluate(&report);
+        assert_eq!(verdict.outcome, Outcome::Fail);
+        assert_eq!(verdict.exit_code, exit_codes::COMPLIANCE_FAILED);
+        assert_eq!(
+            verdict.reasons,
+            ["rsr/docs/license LICENSE fail (required for Bronze)"]
+        );
+
+        // The same report, judged again: waived into Bronze
+        report.checks[1].waived = true;
+        let verdict = Policy::default().evaluate(&report);
+        assert_eq!(verdict.outcome, Outcome::Warn);
+        assert!(verdict.is_met());
+        assert_eq!(verdict.reasons, ["Bronze met only through waivers: rsr/docs/license"]);
+        assert_eq!(verdict.exit_code, exit_codes::SUCCESS);
+
+        let strict = Policy {
+            fail_below_percentage: Some(70.0),
+            fail_on_warning: true,
+            ..Policy::default()
+        };
+        report.add_warning(WarningLevel::Warning, "'docs' is a symlink", None);
+        let verdict = strict.evaluate(&report);
+        assert_eq!(verdict.exit_code, exit_codes::SECURITY_WARNING);
+        assert_eq!(
+            verdict.reasons,
+            [
+                "security warning (fail_on_warning): 'docs' is a symlink",
+                "66.7% of checks passed, below the minimum of 70%",
+            ]
+        );
+
+        report.checks[2].status = crate::CheckStatus::Pass;
+        let verdict = Policy::default().evaluate(&report);
+        assert_eq!(verdict.outcome, Outcome::Warn);
+        assert_eq!(verdict.reasons.len(), 2);
+        assert_eq!(verdict.reasons[1], "security warning: 'docs' is a symlink");
+    }
+}
@@ -0,0 +1,110 @@
+//! Repository types (`[repository] type`)
+//!
+//! The standard is written for code repositories. A standards or
+//! documentation repository has no `src/` or `tests/` to show, and a
+//! repository of templates or organization settings has nothing to build,
+//! so each [`RepositoryType`] names the categories that do not apply to it:
+//!
+//! - `code` (the default): every category applies
+//! - `docs`: no Source Structure, and the documentation is held to
+//!   [`STRICT_DOCUMENTATION`] since it is what the repository delivers
+//! - `data`: no Source Structure; the Build System still applies to the
+//!   pipelines that validate and publish the data
+//! - `meta` (organization profiles, templates, governance): no Source
+//!   Structure and no Build System
+//!
+//! Checks in those categories are reported as not applicable instead of
+//! being left out, so a report still shows why they did not count.
+
+use crate::config::DocumentationConfig;
+use std::fmt;
+use std::str::FromStr;
+
+/// Documentation thresholds of `docs` repositories, unless `[documentation]`
+/// sets them
+pub const STRICT_DOCUMENTATION: DocumentationConfig = DocumentationConfig {
+    min_bytes: 256,
+    min_lines: 5,
+    detect_placeholders: true,
+};
+
+/// What a repository holds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RepositoryType {
+    #[default]
+    Code,
+    Docs,
+    Data,
+    Meta,
+}
+
+impl RepositoryType {
+    pub const ALL: [RepositoryType; 4] = [
+        RepositoryType::Code,
+        RepositoryType::Docs,
+        RepositoryType::Data,
+        RepositoryType::Meta,
+    ];
+
+    /// Name in configuration and reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            RepositoryType::Code => "code",
+            RepositoryType::Docs => "docs",
+            RepositoryType::Data => "data",
+            RepositoryType::Meta => "meta",
+        }
+    }
+
+    /// Categories whose checks do not apply to this type
+    pub fn excluded_categories(&self) -> &'static [&'static str] {
+        match self {
+            RepositoryType::Code => &[],
+            RepositoryType::Docs | RepositoryType::Data => &["Source Structure"],
+            RepositoryType::Meta => &["Source Structure", "Build System"],
+        }
+    }
+
+    /// Whether checks of `category` apply to this type
+    pub fn applies_to(&self, category: &str) -> bool {
+        !self.excluded_categories().contains(&category)
+    }
+}
+
+impl fmt::Display for RepositoryType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for RepositoryType {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        RepositoryType::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "unknown repository type '{}' (expected code, docs, data or meta)",
+                    name
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repository_types() {
+        assert_eq!("Docs".parse::<RepositoryType>(), Ok(RepositoryType::Docs));
+        assert!("library".parse::<RepositoryType>().is_err());
+        assert!(RepositoryType::Code.applies_to("Source Structure"));
+        assert!(!RepositoryType::Docs.applies_to("Source Structure"));
+        assert!(RepositoryType::Data.applies_to("Build System"));
+        assert!(!RepositoryType::Meta.applies_to("Build System"));
+        assert!(RepositoryType::Meta.applies_to("Documentation"));
+    }
+}
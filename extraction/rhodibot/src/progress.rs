@@ -0,0 +1,46 @@
+//! Progress reporting for long scans
+//!
+//! The verification engine calls a [`ProgressSink`] after every check with the
+//! number of checks completed so far, the total, and the check and category
+//! just finished. Any `Fn(&Progress)` closure is a sink, so library users can feed
+//! progress bars or UIs without defining a type.
+
+/// A snapshot of verification progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress<'a> {
+    /// Checks finished (including disabled ones) so far
+    pub completed: usize,
+    /// Checks in this run
+    pub total: usize,
+    /// Category of the check that just finished
+    pub category: &'a str,
+    /// ID of the check that just finished
+    pub check_id: &'a str,
+}
+
+impl Progress<'_> {
+    /// Whether every check has finished
+    pub fn is_done(&self) -> bool {
+        self.completed >= self.total
+    }
+}
+
+/// Receiver for progress updates
+pub trait ProgressSink {
+    /// Called after each check finishes
+    fn update(&self, progress: &Progress);
+}
+
+impl<F: Fn(&Progress)> ProgressSink for F {
+    fn update(&self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+/// Ignores all progress updates
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn update(&self, _progress: &Progress) {}
+}
@@ -0,0 +1,152 @@
+//! Managed README section (`rhodibot badge --update-readme`)
+//!
+//! The badge and a short compliance summary are kept between two HTML
+//! comment markers in `README.md`, which Markdown renderers hide. Everything
+//! outside the markers belongs to the maintainers and is never touched, so
+//! the section can be refreshed on every CI run. It carries no timestamp:
+//! a README only changes when the compliance result does.
+
+use crate::{generate_badge, ComplianceLevel, ComplianceReport};
+
+/// File the section is maintained in
+pub const README_FILE: &str = "README.md";
+/// Line opening the managed section
+pub const START_MARKER: &str = "<!-- rhodibot:start -->";
+/// Line closing the managed section
+pub const END_MARKER: &str = "<!-- rhodibot:end -->";
+
+/// The managed section for `report`, markers included
+pub fn render_section(report: &ComplianceReport) -> String {
+    let level = report.highest_level();
+    let mut out = String::new();
+    out.push_str(START_MARKER);
+    out.push('\n');
+    out.push_str(&generate_badge(level.unwrap_or(ComplianceLevel::Bronze)));
+    out.push_str("\n\n");
+    out.push_str(&format!(
+        "**RSR compliance**: {} - {}/{} checks passed ({:.1}%) against RSR {}\n",
+        level.map_or("not met", |l| l.display_name()),
+        report.passed_count(),
+        report.total_count(),
+        report.percentage(),
+        report.standard_version
+    ));
+    let failing: Vec<&str> = report
+        .checks
+        .iter()
+        .filter(|c| c.is_failure())
+        .map(|c| c.id.as_str())
+        .collect();
+    if !failing.is_empty() {
+        out.push_str(&format!("\nFailing: {}\n", failing.join(", ")));
+    }
+    out.push_str(END_MARKER);
+    out.push('\n');
+    out
+}
+
+/// Put `section` into `readme`, replacing an existing managed section
+///
+/// Without markers the section goes after the first top-level heading, or
+/// at the top when there is none. Markers that are unpaired or out of order
+/// are an error rather than a guess at what to overwrite.
+pub fn update(readme: &str, section: &str) -> Result<String, String> {
+    let section = if readme.contains("\r\n") {
+        section.replace('\n', "\r\n")
+    } else {
+        section.to_string()
+    };
+    match (readme.find(START_MARKER), readme.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => {
+            let after = end + END_MARKER.len();
+            let rest = &readme[after..];
+            let rest = rest
+                .strip_prefix("\r\n")
+                .or_else(|| rest.strip_prefix('\n'))
+                .unwrap_or(rest);
+            Ok(format!("{}{}{}", &readme[..start], section, rest))
+        }
+        (None, None) => Ok(insert(readme, &section)),
+        (Some(_), Some(_)) => Err(format!("{} comes before {}", END_MARKER, START_MARKER)),
+        (Some(_), None) => Err(format!("{} has no matching {}", START_MARKER, END_MARKER)),
+        (None, Some(_)) => Err(format!("{} has no matching {}", END_MARKER, START_MARKER)),
+    }
+}
+
+/// Insert a new section after the first `# ` heading
+fn insert(readme: &str, section: &str) -> String {
+    let mut offset = 0;
+    for line in readme.split_inclusive('\n') {
+        offset += line.len();
+        if line.starts_with("# ") {
+            let newline = if line.ends_with("\r\n") { "\r\n" } else { "\n" };
+            let (head, tail) = readme.split_at(offset);
+            let head = if head.ends_with('\n') {
+                head.to_string()
+            } else {
+                format!("{}{}", head, newline)
+            };
+            let tail = tail.trim_start_matches(['\r', '\n']);
+            let gap = if tail.is_empty() { "" } else { newline };
+            return format!("{}{}{}{}{}", head, newline, section, gap, tail);
+        }
+    }
+    if readme.is_empty() {
+        section.to_string()
+    } else {
+        let newline = if readme.contains("\r\n") { "\r\n" } else { "\n" };
+        format!("{}{}{}", section, newline, readme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog;
+    use std::path::PathBuf;
+
+    fn sample_report() -> ComplianceReport {
+        let mut report = ComplianceReport::new(PathBuf::from("/tmp/project"));
+        report.add_spec_check(catalog::lookup("RSR-DOC-001").unwrap(), true);
+        report.add_spec_check(catalog::lookup("RSR-DOC-002").unwrap(), false);
+        report
+    }
+
+    #[test]
+    fn test_render_section() {
+        let section = render_section(&sample_report());
+        assert!(section.starts_with("<!-- rhodibot:start -->\n[![Rhodium Standard Bronze]"));
+        assert!(section.contains("**RSR compliance**: not met - 1/2 checks passed (50.0%)"));
+        assert!(section.contains("\nFailing: RSR-DOC-002\n"));
+        assert!(section.ends_with("<!-- rhodibot:end -->\n"));
+    }
+
+    #[test]
+    fn test_update_inserts_after_heading() {
+        let section = "<!-- rhodibot:start -->\nnew\n<!-- rhodibot:end -->\n";
+        let updated = update("# Project\n\nIntro text.\n", section).unwrap();
+        assert_eq!(
+            updated,
+            "# Project\n\n<!-- rhodibot:start -->\nnew\n<!-- rhodibot:end -->\n\nIntro text.\n"
+        );
+        assert_eq!(update(&updated, section).unwrap(), updated);
+
+        let headless = update("Intro text.\n", section).unwrap();
+        assert!(headless.starts_with(section) && headless.ends_with("\nIntro text.\n"));
+        assert_eq!(update("", section).unwrap(), section);
+    }
+
+    #[test]
+    fn test_update_replaces_section() {
+        let readme =
+            "# P\r\n\r\n<!-- rhodibot:start -->\r\nold\r\n<!-- rhodibot:end -->\r\nEnd\r\n";
+        let section = "<!-- rhodibot:start -->\nnew\n<!-- rhodibot:end -->\n";
+        assert_eq!(
+            update(readme, section).unwrap(),
+            "# P\r\n\r\n<!-- rhodibot:start -->\r\nnew\r\n<!-- rhodibot:end -->\r\nEnd\r\n"
+        );
+
+        assert!(update("<!-- rhodibot:start -->\n", section).is_err());
+        assert!(update("<!-- rhodibot:end -->\n<!-- rhodibot:start -->\n", section).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+//! Verification of remote repositories
+//!
+//! Rhodibot works offline by default. `rhodibot check --remote <url>` opts in
+//! to fetching a repository: it shallow-clones the URL with the system `git`
+//! binary into a temporary directory, which is removed again when the
+//! [`RemoteCheckout`] is dropped. Archives uploaded to `rhodibot serve` are
+//! unpacked the same way, with the system `tar`.
+
+use crate::subprocess;
+use crate::ComplianceReport;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Longest a clone may take before git is killed
+const CLONE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Seconds a clone may transfer less than 1 KB/s before git gives up
+const CLONE_STALL_SECONDS: &str = "30";
+
+/// Longest unpacking an archive may take before tar is killed
+const UNPACK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A temporary shallow clone of a remote repository
+#[derive(Debug)]
+pub struct RemoteCheckout {
+    url: String,
+    dir: PathBuf,
+}
+
+impl RemoteCheckout {
+    /// A checkout of `url` in a fresh, not yet created, temporary directory
+    fn new(url: &str) -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let dir = std::env::temp_dir().join(format!(
+            "rhodibot-remote-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+        Self {
+            url: url.to_string(),
+            dir,
+        }
+    }
+
+    /// Shallow-clone `url` (depth 1) into a fresh temporary directory
+    pub fn fetch(url: &str) -> Result<Self, String> {
+        if url.trim().is_empty() {
+            return Err("remote URL is empty".to_string());
+        }
+        // Construct before cloning so a partial clone is cleaned up on failure
+        let checkout = Self::new(url);
+
+        let mut command = Command::new("git");
+        command
+            // Give up on a stalled transfer rather than wait for the kill
+            .args(["-c", "http.lowSpeedLimit=1000", "-c"])
+            .arg(format!("http.lowSpeedTime={}", CLONE_STALL_SECONDS))
+            .args(["clone", "--depth", "1", "--quiet", "--no-tags", "--"])
+            .arg(url)
+            .arg(&checkout.dir)
+            // Never block on a credential prompt
+            .env("GIT_TERMINAL_PROMPT", "0");
+        let output = subprocess::run(&mut command, None, CLONE_TIMEOUT).map_err(|e| {
+            match e.kind() {
+                io::ErrorKind::TimedOut => format!("git clone {} {}", url, e),
+                _ => format!("cannot run git: {}", e),
+            }
+        })?;
+        if !output.status.success() {
+            return Err(format!(
+                "git clone {} failed: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(checkout)
+    }
+
+    /// Unpack a tar archive (optionally gzip-compressed) into a fresh
+    /// temporary directory; `label` stands in for the URL in reports
+    pub fn unpack(archive: &[u8], label: &str) -> Result<Self, String> {
+        if archive.is_empty() {
+            return Err("archive is empty".to_string());
+        }
+        let checkout = Self::new(label);
+        fs::create_dir_all(&checkout.dir)
+            .map_err(|e| format!("cannot create {}: {}", checkout.dir.display(), e))?;
+        let archive_path = checkout.dir.with_extension("tar");
+        fs::write(&archive_path, archive)
+            .map_err(|e| format!("cannot write {}: {}", archive_path.display(), e))?;
+
+        let unpacked = unpack_archive(&archive_path, &checkout.dir);
+        let _ = fs::remove_file(&archive_path);
+        unpacked.map(|()| checkout)
+    }
+
+    /// Local path of the checkout
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// URL the checkout was cloned from
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Rewrite a report made from this checkout to refer to the remote URL
+    ///
+    /// The temporary directory is replaced by the URL both as the repository
+    /// path and as the prefix of any warning paths.
+    pub fn relocate_report(&self, report: &mut ComplianceReport) {
+        let base = PathBuf::from(&self.url);
+        for warning in &mut report.warnings {
+            if let Some(ref path) = warning.path {
+                if let Ok(rel) = path.strip_prefix(&self.dir) {
+                    warning.path = Some(base.join(rel));
+                }
+            }
+            warning.message = warning
+                .message
+                .replace(&self.dir.display().to_string(), &self.url);
+        }
+        report.repository_path = base;
+    }
+}
+
+/// Run a tar command, returning its output or why it failed or timed out
+fn run_tar(command: &mut Command) -> Result<String, String> {
+    let output = subprocess::run(command, None, UNPACK_TIMEOUT).map_err(|e| {
+        match e.kind() {
+            io::ErrorKind::TimedOut => format!("unpacking the archive {}", e),
+            _ => format!("cannot run tar: {}", e),
+        }
+    })?;
+    if !output.status.success() {
+        return Err(format!(
+            "cannot unpack archive: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Unpack `archive` into `dir`
+///
+/// tar detects gzip compression itself and refuses absolute paths and `..`
+/// members by default. Symlink and hardlink members are refused here, as a
+/// link could point a check, or a later member, outside `dir`; ownership and
+/// permission bits from the archive are not applied.
+fn unpack_archive(archive: &Path, dir: &Path) -> Result<(), String> {
+    // `tar -tv` lists members like `ls -l`, a link's mode starting with `l`
+    // (symlink) or `h` (hardlink)
+    let listing = run_tar(Command::new("tar").arg("-tvf").arg(archive))?;
+    if let Some(link) = listing.lines().find(|line| line.starts_with(['l', 'h'])) {
+        return Err(format!("archive contains a link, which is not accepted: {}", link));
+    }
+    let mut command = Command::new("tar");
+    command
+        .args(["--no-same-owner", "--no-same-permissions", "-xf"])
+        .arg(archive)
+        .arg("-C")
+        .arg(dir);
+    run_tar(&mut command).map(|_| ())
+}
+
+impl Drop for RemoteCheckout {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
@@ -0,0 +1,370 @@
+//! Minimal HTTP server for `rhodibot serve`
+//!
+//! Internal platforms can ask a long-running rhodibot for a report instead of
+//! spawning a process per repository. The server speaks just enough HTTP/1.1
+//! for that: one request per connection, `Content-Length` bodies, and
+//! `Connection: close` responses. Connections are handled one at a time, in
+//! order of arrival; each client has 30 seconds to send its whole request,
+//! so a slow one holds up the others for no longer than that.
+//!
+//! Request routing is left to the caller, which passes a handler mapping a
+//! [`Request`] to a [`Response`].
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+/// Address `serve` listens on by default (loopback only)
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:8080";
+
+/// Largest request body accepted, in bytes
+pub const MAX_BODY: usize = 64 * 1024 * 1024;
+
+/// Largest request line or header line accepted, in bytes
+const MAX_LINE: usize = 8 * 1024;
+
+/// How long a client may take to send its whole request
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A parsed HTTP request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Decoded query parameters, in order
+    pub query: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// First value of the query parameter `name`
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Read a request from a client
+    pub fn read_from(reader: &mut impl BufRead) -> Result<Self, String> {
+        let request_line = read_line(reader)?;
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/1.") => {
+                (method.to_string(), target)
+            }
+            _ => return Err(format!("malformed request line '{}'", request_line)),
+        };
+
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, parse_query(query)),
+            None => (target, Vec::new()),
+        };
+
+        let mut content_length = 0;
+        loop {
+            let line = read_line(reader)?;
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("malformed header '{}'", line))?;
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid Content-Length '{}'", value.trim()))?;
+            } else if name.trim().eq_ignore_ascii_case("transfer-encoding") {
+                return Err("chunked request bodies are not supported".to_string());
+            }
+        }
+        if content_length > MAX_BODY {
+            return Err(format!("request body exceeds {} bytes", MAX_BODY));
+        }
+
+        // Read as the body arrives rather than allocating what the client
+        // claims up front
+        let mut body = Vec::new();
+        reader
+            .take(content_length as u64)
+            .read_to_end(&mut body)
+            .map_err(|e| format!("cannot read request body: {}", e))?;
+        if body.len() < content_length {
+            return Err("unexpected end of request body".to_string());
+        }
+
+        Ok(Self {
+            method,
+            path: percent_decode(path),
+            query,
+            body,
+        })
+    }
+}
+
+/// An HTTP response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    /// A JSON response
+    pub fn json(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: body.into(),
+        }
+    }
+
+    /// A plain-text response
+    pub fn text(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.into(),
+        }
+    }
+
+    /// A JSON `{"error": ...}` response
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(
+            status,
+            format!("{{\"error\": \"{}\"}}\n", crate::json::escape(message)),
+        )
+    }
+
+    /// Write the response, including headers, to a client
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            reason_phrase(self.status),
+            self.content_type,
+            self.body.len()
+        )?;
+        writer.write_all(self.body.as_bytes())?;
+        writer.flush()
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        500 => "Internal Server Error",
+        _ => "Unknown",
+    }
+}
+
+/// Read one CRLF- or LF-terminated line, without the terminator
+fn read_line(reader: &mut impl BufRead) -> Result<String, String> {
+    let mut line = Vec::new();
+    reader
+        .take(MAX_LINE as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .map_err(|e| format!("cannot read request: {}", e))?;
+    if line.len() > MAX_LINE {
+        return Err("request line too long".to_string());
+    }
+    if line.last() != Some(&b'\n') {
+        return Err("unexpected end of request".to_string());
+    }
+    let line = String::from_utf8(line).map_err(|_| "request is not UTF-8".to_string())?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Parse an `a=1&b=2` query string
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (percent_decode(key), percent_decode(value)),
+            None => (percent_decode(pair), String::new()),
+        })
+        .collect()
+}
+
+/// Decode `%XX` escapes and `+` (as space) in a URL component
+///
+/// Malformed escapes are kept literally.
+pub fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = |b: u8| (b as char).to_digit(16);
+                match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                    (Some(hi), Some(lo)) => {
+                        out.push((hi * 16 + lo) as u8);
+                        i += 2;
+                    }
+                    _ => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Client connection that fails reads once its deadline has passed
+///
+/// A read timeout alone restarts with every read, so a client sending a
+/// byte at a time could keep the connection forever.
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "request took too long"));
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        let mut stream = self.stream;
+        stream.read(buf)
+    }
+}
+
+/// Serve a single client connection
+pub fn handle_connection(stream: TcpStream, handler: &dyn Fn(&Request) -> Response) {
+    handle_connection_within(stream, handler, READ_TIMEOUT);
+}
+
+/// Serve a single client connection, which has `timeout` to send its request
+fn handle_connection_within(
+    stream: TcpStream,
+    handler: &dyn Fn(&Request) -> Response,
+    timeout: Duration,
+) {
+    let mut reader = BufReader::new(DeadlineReader {
+        stream: &stream,
+        deadline: Instant::now() + timeout,
+    });
+    let response = match Request::read_from(&mut reader) {
+        Ok(request) => handler(&request),
+        Err(e) if e.starts_with("request body exceeds") => Response::error(413, &e),
+        Err(e) => Response::error(400, &e),
+    };
+    let mut writer = &stream;
+    let _ = response.write_to(&mut writer);
+}
+
+/// Accept connections forever, answering each with `handler`
+///
+/// Only returns if accepting a connection fails.
+pub fn serve(listener: &TcpListener, handler: &dyn Fn(&Request) -> Response) -> io::Result<()> {
+    for stream in listener.incoming() {
+        handle_connection(stream?, handler);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_request() {
+        let raw = "POST /check?path=%2Frepo%20one&verbose HTTP/1.1\r\nHost: x\r\nContent-Length: 4\r\n\r\nbody";
+        let request = Request::read_from(&mut Cursor::new(raw)).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/check");
+        assert_eq!(request.query_param("path"), Some("/repo one"));
+        assert_eq!(request.query_param("verbose"), Some(""));
+        assert_eq!(request.query_param("missing"), None);
+        assert_eq!(request.body, b"body");
+
+        assert!(Request::read_from(&mut Cursor::new("GET /\r\n\r\n")).is_err());
+        assert!(Request::read_from(&mut Cursor::new("GET / HTTP/1.1\r\n")).is_err());
+        let huge = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY + 1);
+        assert!(Request::read_from(&mut Cursor::new(huge))
+            .unwrap_err()
+            .starts_with("request body exceeds"));
+        let short = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\nbody", MAX_BODY);
+        assert_eq!(
+            Request::read_from(&mut Cursor::new(short)).unwrap_err(),
+            "unexpected end of request body"
+        );
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%2Fb+c"), "a/b c");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+        assert_eq!(percent_decode("%C3%A9"), "é");
+    }
+
+    #[test]
+    fn test_write_response() {
+        let mut out = Vec::new();
+        Response::error(404, "no \"such\" path").write_to(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\n"));
+        assert!(text.ends_with("\r\n\r\n{\"error\": \"no \\\"such\\\" path\"}\n"));
+    }
+
+    #[test]
+    fn test_serve_round_trip() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let handler = |request: &Request| Response::text(200, request.path.clone());
+            let _ = serve(&listener, &handler);
+        });
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"GET /health HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\n/health"));
+    }
+
+    #[test]
+    fn test_request_deadline() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            // Every byte arrives in time, the whole request does not
+            for byte in b"GET /health HTTP/1.1\r\n" {
+                if stream.write_all(&[*byte]).is_err() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            let mut response = Vec::new();
+            let _ = stream.read_to_end(&mut response);
+            String::from_utf8_lossy(&response).into_owned()
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let handler = |_: &Request| Response::text(200, "ok");
+        handle_connection_within(stream, &handler, Duration::from_millis(100));
+        let response = client.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"), "{}", response);
+        assert!(response.contains("request took too long"));
+    }
+}
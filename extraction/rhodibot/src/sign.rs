@@ -0,0 +1,419 @@
+//! Detached report signatures (`--sign`, `verify-report`)
+//!
+//! A report or conformity document written with `--output FILE --sign KEY`
+//! gets a detached signature in `FILE.sig`, so downstream consumers can
+//! check it was produced by a holder of the key and not edited since:
+//!
+//! ```text
+//! rhodibot-signature: 1
+//! algorithm: hmac-sha256
+//! sha256: 9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08
+//! signature: 4b3c...
+//! ```
+//!
+//! Two kinds of key are supported:
+//!
+//! - a shared secret: any file that is not PEM. Its contents (without a
+//!   trailing newline) are the HMAC-SHA256 key, computed std-only.
+//! - an Ed25519 PEM key (`openssl genpkey -algorithm ed25519`), signed and
+//!   verified with the system `openssl` binary. Needs the `ed25519` cargo
+//!   feature. Verification takes the public key, or the private key.
+
+use crate::codec::{constant_time_eq, hex_decode, hex_encode, hmac_sha256, sha256};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extension of detached signature files (`report.json.sig`)
+pub const SIGNATURE_EXTENSION: &str = "sig";
+
+/// Format version written in the signature header
+const FORMAT_VERSION: &str = "1";
+
+/// Signature algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    HmacSha256,
+    Ed25519,
+}
+
+impl Algorithm {
+    /// Name used in signature files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Algorithm::HmacSha256 => "hmac-sha256",
+            Algorithm::Ed25519 => "ed25519",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "hmac-sha256" => Some(Algorithm::HmacSha256),
+            "ed25519" => Some(Algorithm::Ed25519),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Key used to sign or verify
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    /// Shared secret for HMAC-SHA256
+    Secret(Vec<u8>),
+    /// Ed25519 key in a PEM file
+    Pem { path: PathBuf, public: bool },
+}
+
+impl Key {
+    /// Read a key file, telling PEM keys from shared secrets by their header
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents =
+            fs::read(path).map_err(|e| format!("Cannot read key {}: {}", path.display(), e))?;
+        if contents.starts_with(b"-----BEGIN ") {
+            let public = contents
+                .split(|&b| b == b'\n')
+                .next()
+                .map_or(false, |header| header.windows(10).any(|w| w == b"PUBLIC KEY"));
+            return Ok(Key::Pem {
+                path: path.to_path_buf(),
+                public,
+            });
+        }
+        Key::from_secret(&contents)
+    }
+
+    /// A shared secret, ignoring one trailing newline (as written by `echo`)
+    pub fn from_secret(secret: &[u8]) -> Result<Self, String> {
+        let secret = secret
+            .strip_suffix(b"\n")
+            .map(|s| s.strip_suffix(b"\r").unwrap_or(s))
+            .unwrap_or(secret);
+        if secret.is_empty() {
+            return Err("Signing key is empty".to_string());
+        }
+        Ok(Key::Secret(secret.to_vec()))
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        match self {
+            Key::Secret(_) => Algorithm::HmacSha256,
+            Key::Pem { .. } => Algorithm::Ed25519,
+        }
+    }
+}
+
+/// A parsed signature file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub algorithm: Algorithm,
+    /// SHA-256 of the signed content
+    pub sha256: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+impl Signature {
+    /// Render as a signature file
+    pub fn render(&self) -> String {
+        format!(
+            "rhodibot-signature: {}\nalgorithm: {}\nsha256: {}\nsignature: {}\n",
+            FORMAT_VERSION,
+            self.algorithm,
+            hex_encode(&self.sha256),
+            hex_encode(&self.signature)
+        )
+    }
+
+    /// Parse a signature file
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut version = None;
+        let mut algorithm = None;
+        let mut digest = None;
+        let mut signature = None;
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("Malformed signature line: {}", line))?;
+            let value = value.trim();
+            match key.trim() {
+                "rhodibot-signature" => version = Some(value),
+                "algorithm" => {
+                    algorithm = Some(
+                        Algorithm::parse(value)
+                            .ok_or_else(|| format!("Unknown signature algorithm: {}", value))?,
+                    )
+                }
+                "sha256" => digest = hex_decode(value).and_then(|d| <[u8; 32]>::try_from(d).ok()),
+                "signature" => signature = hex_decode(value),
+                _ => {}
+            }
+        }
+
+        match version {
+            Some(FORMAT_VERSION) => {}
+            Some(other) => return Err(format!("Unsupported signature format version {}", other)),
+            None => return Err("Not a rhodibot signature file".to_string()),
+        }
+        Ok(Self {
+            algorithm: algorithm.ok_or("Signature file has no algorithm")?,
+            sha256: digest.ok_or("Signature file has no valid sha256 digest")?,
+            signature: signature.ok_or("Signature file has no valid signature")?,
+        })
+    }
+}
+
+/// Path of the detached signature for `path` (`report.json` → `report.json.sig`)
+pub fn signature_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(SIGNATURE_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Sign `data` with `key`
+pub fn sign(data: &[u8], key: &Key) -> Result<Signature, String> {
+    let signature = match key {
+        Key::Secret(secret) => hmac_sha256(secret, data).to_vec(),
+        Key::Pem { path, public } => {
+            if *public {
+                return Err("Cannot sign with a public key; use the private key".to_string());
+            }
+            openssl::sign(data, path)?
+        }
+    };
+    Ok(Signature {
+        algorithm: key.algorithm(),
+        sha256: sha256(data),
+        signature,
+    })
+}
+
+/// Check that `signature` was made over `data` with `key`
+pub fn verify(data: &[u8], signature: &Signature, key: &Key) -> Result<(), String> {
+    if signature.algorithm != key.algorithm() {
+        return Err(format!(
+            "Signature uses {}, but the key is for {}",
+            signature.algorithm,
+            key.algorithm()
+        ));
+    }
+    if !constant_time_eq(&sha256(data), &signature.sha256) {
+        return Err("Content does not match the signed digest; it was modified".to_string());
+    }
+    let valid = match key {
+        Key::Secret(secret) => constant_time_eq(&hmac_sha256(secret, data), &signature.signature),
+        Key::Pem { path, public } => openssl::verify(data, &signature.signature, path, *public)?,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err("Signature does not match; wrong key or forged signature".to_string())
+    }
+}
+
+/// Ed25519 through the system `openssl` binary
+#[cfg(feature = "ed25519")]
+mod openssl {
+    use crate::bot::write_private;
+    use crate::subprocess;
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use std::process::{Command, Output};
+    use std::time::Duration;
+
+    /// How long openssl may take to sign or verify before it is killed
+    const OPENSSL_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Temporary file for openssl input, in a private directory removed
+    /// when dropped
+    struct TempFile {
+        dir: PathBuf,
+        path: PathBuf,
+    }
+
+    impl TempFile {
+        fn write(label: &str, data: &[u8]) -> Result<Self, String> {
+            let dir = write_private(label, data)
+                .map_err(|e| format!("Cannot write {}: {}", label, e))?;
+            let path = dir.join(label);
+            Ok(Self { dir, path })
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    /// `openssl pkeyutl` over the raw bytes of `data` with `key`
+    fn pkeyutl(key: &Path, data: &TempFile) -> Command {
+        let mut command = Command::new("openssl");
+        command
+            .arg("pkeyutl")
+            .arg("-inkey")
+            .arg(key)
+            .args(["-rawin", "-in"])
+            .arg(&data.path);
+        command
+    }
+
+    /// Run an openssl command, killing it after [`OPENSSL_TIMEOUT`]
+    fn run(command: &mut Command) -> Result<Output, String> {
+        subprocess::run(command, None, OPENSSL_TIMEOUT).map_err(|e| match e.kind() {
+            io::ErrorKind::TimedOut => format!("openssl {}", e),
+            _ => format!("Cannot run openssl: {}", e),
+        })
+    }
+
+    pub(super) fn sign(data: &[u8], key: &Path) -> Result<Vec<u8>, String> {
+        let input = TempFile::write("data", data)?;
+        let output = run(pkeyutl(key, &input).arg("-sign"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "openssl could not sign with {}: {}",
+                key.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(output.stdout)
+    }
+
+    pub(super) fn verify(
+        data: &[u8],
+        signature: &[u8],
+        key: &Path,
+        public: bool,
+    ) -> Result<bool, String> {
+        let input = TempFile::write("data", data)?;
+        let sigfile = TempFile::write("signature", signature)?;
+        let mut command = pkeyutl(key, &input);
+        command.arg("-verify").arg("-sigfile").arg(&sigfile.path);
+        if public {
+            command.arg("-pubin");
+        }
+        let output = run(&mut command)?;
+        Ok(output.status.success())
+    }
+}
+
+/// Stand-in when built without the `ed25519` feature
+#[cfg(not(feature = "ed25519"))]
+mod openssl {
+    use std::path::Path;
+
+    const UNSUPPORTED: &str = "Ed25519 keys need rhodibot built with the 'ed25519' feature";
+
+    pub(super) fn sign(_data: &[u8], _key: &Path) -> Result<Vec<u8>, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub(super) fn verify(
+        _data: &[u8],
+        _signature: &[u8],
+        _key: &Path,
+        _public: bool,
+    ) -> Result<bool, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_sign_and_verify() {
+        let key = Key::from_secret(b"s3cret\n").unwrap();
+        assert_eq!(key, Key::Secret(b"s3cret".to_vec()));
+
+        let report = b"{\"passed\": 21}\n";
+        let signature = sign(report, &key).unwrap();
+        assert_eq!(signature.algorithm, Algorithm::HmacSha256);
+        let text = signature.render();
+        assert!(text.starts_with("rhodibot-signature: 1\nalgorithm: hmac-sha256\nsha256: "));
+
+        let parsed = Signature::parse(&text).unwrap();
+        assert_eq!(parsed, signature);
+        assert!(verify(report, &parsed, &key).is_ok());
+
+        let tampered = verify(b"{\"passed\": 22}\n", &parsed, &key).unwrap_err();
+        assert!(tampered.contains("modified"));
+        let other_key = Key::from_secret(b"guess").unwrap();
+        assert!(verify(report, &parsed, &other_key).unwrap_err().contains("wrong key"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Key::from_secret(b"\n").is_err());
+        assert!(Signature::parse("hello").is_err());
+        assert!(Signature::parse("algorithm: hmac-sha256\n")
+            .unwrap_err()
+            .contains("Not a rhodibot signature"));
+        assert!(Signature::parse("rhodibot-signature: 2\n")
+            .unwrap_err()
+            .contains("version 2"));
+        assert!(Signature::parse("rhodibot-signature: 1\nalgorithm: rot13\n")
+            .unwrap_err()
+            .contains("rot13"));
+        assert_eq!(
+            signature_path(Path::new("out/report.json")),
+            PathBuf::from("out/report.json.sig")
+        );
+    }
+
+    #[test]
+    fn test_load_pem_key() {
+        let dir = std::env::temp_dir().join("rhodibot_sign_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("key.pem");
+        fs::write(&path, "-----BEGIN PUBLIC KEY-----\nMCowBQYDK2VwAyEA\n").unwrap();
+        let key = Key::load(&path).unwrap();
+        assert_eq!(key, Key::Pem { path: path.clone(), public: true });
+        assert!(sign(b"x", &key).unwrap_err().contains("public key"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_ed25519_round_trip() {
+        use std::process::Command;
+
+        let dir = std::env::temp_dir().join("rhodibot_sign_ed25519_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let private = dir.join("key.pem");
+        let public = dir.join("pub.pem");
+        let generated = Command::new("openssl")
+            .args(["genpkey", "-algorithm", "ed25519", "-out"])
+            .arg(&private)
+            .status();
+        if !generated.map_or(false, |s| s.success()) {
+            eprintln!("skipping: openssl cannot generate Ed25519 keys");
+            return;
+        }
+        Command::new("openssl")
+            .args(["pkey", "-pubout", "-in"])
+            .arg(&private)
+            .arg("-out")
+            .arg(&public)
+            .status()
+            .unwrap();
+
+        let signature = sign(b"report", &Key::load(&private).unwrap()).unwrap();
+        assert_eq!(signature.algorithm, Algorithm::Ed25519);
+        assert_eq!(signature.signature.len(), 64);
+        let public = Key::load(&public).unwrap();
+        assert!(verify(b"report", &signature, &public).is_ok());
+        let mut forged = signature.clone();
+        forged.signature[0] ^= 1;
+        assert!(verify(b"report", &forged, &public).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,105 @@
+//! Web roots for the Well-Known checks
+//!
+//! `.well-known/` only means something where a site is served from. A
+//! repository that publishes `docs/` with GitHub Pages, or the `static/`
+//! directory of a Hugo or Docusaurus site, keeps its `.well-known/` there
+//! rather than in the repository root. `[site] web_root` names that
+//! directory; unless it is set, [`detect`] picks it:
+//!
+//! 1. the repository root, if it has a `.well-known/`
+//! 2. the web root of a detected static site generator, or one of the
+//!    common web roots (`public/`, `docs/`, `static/`, `site/`), whichever
+//!    first has a `.well-known/`
+//! 3. the detected generator's web root, so the checks report what is
+//!    missing where the site is published
+//! 4. the repository root
+
+use crate::vfs::FileSystem;
+use std::path::Path;
+
+/// Files that identify a static site generator, with the directory whose
+/// contents it publishes as-is
+const GENERATORS: &[(&str, &str)] = &[
+    ("docusaurus.config.js", "static"),
+    ("docusaurus.config.ts", "static"),
+    ("hugo.toml", "static"),
+    ("mkdocs.yml", "docs"),
+    ("docs/_config.yml", "docs"),
+    ("astro.config.mjs", "public"),
+    ("next.config.js", "public"),
+    ("vite.config.js", "public"),
+    ("vite.config.ts", "public"),
+];
+
+/// Directories commonly published without a generator
+const COMMON_ROOTS: &[&str] = &["public", "docs", "static", "site"];
+
+/// Web root of the repository at `root`, relative to it; empty for the
+/// root itself
+pub fn detect(fs: &dyn FileSystem, root: &Path) -> String {
+    let has_well_known = |dir: &Path| has_dir(fs, dir, ".well-known");
+    if has_well_known(root) {
+        return String::new();
+    }
+    let generator = GENERATORS
+        .iter()
+        .find(|(marker, _)| fs.is_file(&root.join(marker)))
+        .map(|(_, web_root)| *web_root);
+    generator
+        .iter()
+        .chain(COMMON_ROOTS)
+        .find(|dir| has_well_known(&root.join(dir)))
+        .copied()
+        .or(generator)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Whether `dir` has a subdirectory called `name`, ignoring ASCII case as
+/// the checks do
+fn has_dir(fs: &dyn FileSystem, dir: &Path, name: &str) -> bool {
+    fs.read_dir(dir).unwrap_or_default().iter().any(|path| {
+        path.file_name()
+            .map_or(false, |n| n.to_string_lossy().eq_ignore_ascii_case(name))
+            && fs.is_dir(path)
+    })
+}
+
+/// `relative_path` inside `web_root`
+pub fn join(web_root: &str, relative_path: &str) -> String {
+    if web_root.is_empty() {
+        relative_path.to_string()
+    } else {
+        format!("{}/{}", web_root.trim_end_matches('/'), relative_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vfs::MemoryFs;
+
+    #[test]
+    fn test_detect() {
+        let root = Path::new("/repo");
+        let mut fs = MemoryFs::new();
+        fs.add_dir("/repo");
+        assert_eq!(detect(&fs, root), "");
+
+        // A generator's web root is used even before it has .well-known/
+        fs.add_file("/repo/mkdocs.yml", "site_name: Demo\n");
+        assert_eq!(detect(&fs, root), "docs");
+
+        // A common web root with .well-known/ wins over an empty one
+        fs.add_dir("/repo/public/.Well-Known");
+        assert_eq!(detect(&fs, root), "public");
+        fs.add_dir("/repo/docs/.well-known");
+        assert_eq!(detect(&fs, root), "docs");
+
+        fs.add_dir("/repo/.well-known");
+        assert_eq!(detect(&fs, root), "");
+
+        assert_eq!(join("", ".well-known"), ".well-known");
+        assert_eq!(join("site/", ".well-known/ai.txt"), "site/.well-known/ai.txt");
+    }
+}
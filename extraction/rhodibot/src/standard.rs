@@ -0,0 +1,180 @@
+//! Requirement catalog listings (`rhodibot standard`)
+//!
+//! Renders the checks of an RSR [`StandardVersion`] from the embedded
+//! `rsr-spec` catalog, so users can see what will be checked without a
+//! network connection or reading the source, and exports the catalog as
+//! JSON for documentation sites and portals.
+
+use crate::catalog::CheckSpec;
+use crate::{json, ComplianceLevel, StandardVersion, STANDARD_URL};
+
+/// Append the checks in `specs` grouped under their category headings
+fn push_grouped(out: &mut String, specs: &[&CheckSpec], show_level: bool) {
+    let mut current_category = "";
+    for spec in specs {
+        if spec.category != current_category {
+            out.push_str(&format!("\n{}\n", spec.category));
+            current_category = spec.category;
+        }
+        if show_level {
+            out.push_str(&format!(
+                "  {}  {:<8}  {}\n",
+                spec.id,
+                spec.level.display_name(),
+                spec.item
+            ));
+        } else {
+            out.push_str(&format!("  {}  {}\n", spec.id, spec.item));
+            out.push_str(&format!("      {}\n", spec.description));
+        }
+    }
+}
+
+/// Every check in `version`, one line each (`rhodibot standard list`)
+pub fn render_list(version: StandardVersion) -> String {
+    let specs: Vec<&CheckSpec> = version.checks().iter().collect();
+    let mut out = format!("RSR Standard {} - {} checks\n", version, specs.len());
+    push_grouped(&mut out, &specs, true);
+    out
+}
+
+/// Checks required for `level`, including those of the levels below it
+fn render_level(version: StandardVersion, level: ComplianceLevel) -> String {
+    let specs: Vec<&CheckSpec> = version
+        .checks()
+        .iter()
+        .filter(|spec| spec.level <= level)
+        .collect();
+    let mut out = format!(
+        "RSR Standard {} - {} requirements ({} checks",
+        version,
+        level.display_name(),
+        specs.len()
+    );
+    let below: Vec<&str> = ComplianceLevel::ALL
+        .iter()
+        .filter(|l| **l < level)
+        .map(|l| l.display_name())
+        .collect();
+    if !below.is_empty() {
+        out.push_str(&format!(", including {}", below.join(" and ")));
+    }
+    out.push_str(")\n");
+    if specs.is_empty() {
+        out.push_str("\nNo automated checks.\n");
+    }
+    push_grouped(&mut out, &specs, false);
+    out
+}
+
+/// Full details of one check
+fn render_check(version: StandardVersion, spec: &CheckSpec) -> String {
+    let mut out = format!("{} - {}\n\n", spec.id, spec.item);
+    out.push_str(&format!("Standard:    RSR {}\n", version));
+    out.push_str(&format!("Category:    {}\n", spec.category));
+    out.push_str(&format!("Level:       {}\n", spec.level.display_name()));
+    out.push_str(&format!("Files:       {}\n", spec.paths.join(", ")));
+    out.push_str(&format!("Description: {}\n", spec.description));
+    out.push_str(&format!("Remediation: {}\n", spec.remediation));
+    out.push_str(&format!("Reference:   {}\n", spec.help_url()));
+    out
+}
+
+/// Requirements for a level, or one check by ID (`rhodibot standard show`)
+pub fn render_show(version: StandardVersion, name: &str) -> Result<String, String> {
+    if let Some(level) = ComplianceLevel::parse(name) {
+        return Ok(render_level(version, level));
+    }
+    match version.lookup(&name.to_uppercase()) {
+        Some(spec) => Ok(render_check(version, spec)),
+        None => Err(format!(
+            "Unknown level or check ID: {}. Use a level name or an ID from 'standard list'",
+            name
+        )),
+    }
+}
+
+/// The full requirement catalog as JSON (`rhodibot standard export`)
+pub fn render_export_json(version: StandardVersion) -> String {
+    let specs = version.checks();
+    let mut out = String::new();
+    out.push_str("{\n");
+    out.push_str("  \"standard\": \"Rhodium Standard Repository\",\n");
+    out.push_str(&format!("  \"standard_version\": \"{}\",\n", version));
+    out.push_str(&format!("  \"url\": {},\n", json::string(STANDARD_URL)));
+    let levels: Vec<String> = ComplianceLevel::ALL
+        .iter()
+        .map(|level| json::string(level.display_name()))
+        .collect();
+    out.push_str(&format!("  \"levels\": [{}],\n", levels.join(", ")));
+    out.push_str("  \"requirements\": [\n");
+    for (i, spec) in specs.iter().enumerate() {
+        let comma = if i < specs.len() - 1 { "," } else { "" };
+        let files: Vec<String> = spec.paths.iter().map(|path| json::string(path)).collect();
+        out.push_str("    {\n");
+        out.push_str(&format!("      \"id\": {},\n", json::string(spec.id)));
+        out.push_str(&format!("      \"category\": {},\n", json::string(spec.category)));
+        out.push_str(&format!("      \"item\": {},\n", json::string(spec.item)));
+        out.push_str(&format!("      \"level\": \"{}\",\n", spec.level));
+        out.push_str(&format!("      \"description\": {},\n", json::string(spec.description)));
+        out.push_str(&format!("      \"remediation\": {},\n", json::string(spec.remediation)));
+        out.push_str(&format!("      \"files\": [{}],\n", files.join(", ")));
+        out.push_str(&format!("      \"help_url\": {}\n", json::string(&spec.help_url())));
+        out.push_str(&format!("    }}{}\n", comma));
+    }
+    out.push_str("  ]\n");
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_list() {
+        let list = render_list(StandardVersion::V1_0);
+        let total = StandardVersion::V1_0.checks().len();
+        assert!(list.starts_with(&format!("RSR Standard 1.0 - {} checks\n", total)));
+        assert!(list.contains("\nDocumentation\n  RSR-DOC-001  Bronze    README.md\n"));
+        assert!(list.contains("  RSR-BLD-004  Silver  "));
+    }
+
+    #[test]
+    fn test_render_show() {
+        let bronze = render_show(StandardVersion::V1_0, "bronze").unwrap();
+        assert!(bronze.contains("Bronze requirements (16 checks)\n"));
+        assert!(bronze.contains("  RSR-DOC-001  README.md\n      README.md or README.adoc"));
+        assert!(!bronze.contains("RSR-BLD-004"));
+
+        let silver = render_show(StandardVersion::V1_0, "Silver").unwrap();
+        assert!(silver.contains("(23 checks, including Bronze)"));
+        let gold = render_show(StandardVersion::V1_0, "gold").unwrap();
+        assert!(gold.contains("including Bronze and Silver"));
+
+        let check = render_show(StandardVersion::V1_0, "rsr-doc-001").unwrap();
+        assert!(check.starts_with("RSR-DOC-001 - README.md\n"));
+        assert!(check.contains("Files:       README.md, README.adoc\n"));
+        assert!(check.contains("Reference:   https://"));
+        assert!(check.contains("Remediation: Add a README.md"));
+
+        assert!(render_show(StandardVersion::V1_0, "mithril").is_err());
+    }
+
+    #[test]
+    fn test_render_export_json() {
+        let export = render_export_json(StandardVersion::V1_0);
+        assert!(export.starts_with("{\n  \"standard\": \"Rhodium Standard Repository\",\n"));
+        assert!(export.contains("  \"standard_version\": \"1.0\",\n"));
+        assert!(export.contains("\"levels\": [\"Bronze\", \"Silver\", \"Gold\", \"Platinum\"]"));
+        assert_eq!(
+            export.matches("\"remediation\": ").count(),
+            StandardVersion::V1_0.checks().len()
+        );
+        assert!(export
+            .contains("      \"id\": \"RSR-DOC-001\",\n      \"category\": \"Documentation\",\n"));
+        assert!(export.contains("\"files\": [\"README.md\", \"README.adoc\"]"));
+        assert!(export.contains("      \"level\": \"Silver\",\n"));
+        assert!(export.trim_end().ends_with("}\n  ]\n}"));
+    }
+}
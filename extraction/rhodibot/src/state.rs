@@ -0,0 +1,381 @@
+//! Last-known compliance for scheduled drift runs (`rhodibot drift`)
+//!
+//! A weekly CI job that fails on every known problem is soon ignored. The
+//! `drift` command instead records the outcome of each run in a state file
+//! (by default `.rhodibot/state.json`, kept in a CI cache or committed) and
+//! compares the next run with it: only checks that started failing, or a
+//! level lower than before, fail the job. Known failures stay quiet until
+//! they are fixed, and each regression is reported once.
+//!
+//! Checks count as failing as in [`CheckResult::is_failure`], so waived
+//! checks are not failures and a newly waived check counts as resolved.
+//!
+//! What changed can also be rendered as Markdown or JSON, for a bot to post
+//! as an issue, a comment or a chat message.
+
+use crate::json::{self, Value};
+use crate::output::write_atomic;
+use crate::{CheckResult, ComplianceLevel, ComplianceReport};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// State file used when `--state` is not given, relative to the repository
+pub const DEFAULT_STATE_FILE: &str = ".rhodibot/state.json";
+
+/// What a run established about a repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComplianceState {
+    pub standard_version: String,
+    /// When the run verified the repository (absent for reproducible runs)
+    pub verified_at: Option<String>,
+    /// Highest level achieved, if any
+    pub level: Option<ComplianceLevel>,
+    /// IDs of failing checks, sorted
+    pub failing: Vec<String>,
+}
+
+impl ComplianceState {
+    /// The state a report establishes
+    pub fn from_report(report: &ComplianceReport) -> Self {
+        let mut failing: Vec<String> = report
+            .checks
+            .iter()
+            .filter(|c| c.is_failure())
+            .map(|c| c.id.clone())
+            .collect();
+        failing.sort();
+        Self {
+            standard_version: report.standard_version.to_string(),
+            verified_at: report.timestamp(),
+            level: report.highest_level(),
+            failing,
+        }
+    }
+
+    /// Render as the JSON state file
+    pub fn render(&self) -> String {
+        let optional =
+            |value: Option<String>| value.map_or("null".to_string(), |v| json::string(&v));
+        let mut out = String::new();
+        out.push_str("{\n");
+        out.push_str(&format!(
+            "  \"standard_version\": {},\n",
+            json::string(&self.standard_version)
+        ));
+        out.push_str(&format!("  \"verified_at\": {},\n", optional(self.verified_at.clone())));
+        out.push_str(&format!(
+            "  \"level\": {},\n",
+            optional(self.level.map(|l| l.to_string()))
+        ));
+        // One ID per line, so a committed state file diffs cleanly
+        if self.failing.is_empty() {
+            out.push_str("  \"failing\": []\n");
+        } else {
+            let failing: Vec<String> =
+                self.failing.iter().map(|id| format!("    {}", json::string(id))).collect();
+            out.push_str(&format!("  \"failing\": [\n{}\n  ]\n", failing.join(",\n")));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Parse a state file written by [`ComplianceState::render`]
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let doc = json::parse(text)?;
+        let string = |key: &str| match doc.get(key) {
+            Some(Value::String(s)) => Ok(Some(s.clone())),
+            Some(Value::Null) | None => Ok(None),
+            Some(_) => Err(format!("'{}' must be a string or null", key)),
+        };
+        let level = string("level")?
+            .map(|name| name.parse::<ComplianceLevel>())
+            .transpose()?;
+        let failing = doc
+            .get("failing")
+            .and_then(Value::as_array)
+            .ok_or("'failing' must be an array of check IDs")?
+            .iter()
+            .map(|id| id.as_str().map(str::to_string))
+            .collect::<Option<Vec<String>>>()
+            .ok_or("'failing' must be an array of check IDs")?;
+        Ok(Self {
+            standard_version: string("standard_version")?.unwrap_or_default(),
+            verified_at: string("verified_at")?,
+            level,
+            failing,
+        })
+    }
+
+    /// Load the state file at `path`, or `None` if there is none yet
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text)
+                .map(Some)
+                .map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("cannot read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Write the state file at `path`, creating its directory
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_atomic(path, &self.render())
+    }
+}
+
+/// How compliance changed between two runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChange {
+    /// Checks failing now that did not fail before
+    pub newly_failing: Vec<String>,
+    /// Checks that failed before and no longer do
+    pub resolved: Vec<String>,
+    pub previous_level: Option<ComplianceLevel>,
+    pub level: Option<ComplianceLevel>,
+}
+
+impl StateChange {
+    /// Compare the current state with the previous one
+    pub fn between(previous: &ComplianceState, current: &ComplianceState) -> Self {
+        let not_in = |ids: &[String], other: &[String]| -> Vec<String> {
+            ids.iter().filter(|id| !other.contains(id)).cloned().collect()
+        };
+        Self {
+            newly_failing: not_in(&current.failing, &previous.failing),
+            resolved: not_in(&previous.failing, &current.failing),
+            previous_level: previous.level,
+            level: current.level,
+        }
+    }
+
+    /// Whether anything changed at all
+    pub fn is_empty(&self) -> bool {
+        self.newly_failing.is_empty()
+            && self.resolved.is_empty()
+            && self.level == self.previous_level
+    }
+
+    /// Whether compliance got worse: new failures or a lower level
+    ///
+    /// `None` (no level) is below every level.
+    pub fn is_regression(&self) -> bool {
+        !self.newly_failing.is_empty() || self.level < self.previous_level
+    }
+}
+
+/// The result of check `id` in `report`, if it ran
+fn find<'a>(report: &'a ComplianceReport, id: &str) -> Option<&'a CheckResult> {
+    report.checks.iter().find(|c| c.id == id)
+}
+
+fn level_name(level: Option<ComplianceLevel>) -> &'static str {
+    level.map_or("none", |l| l.display_name())
+}
+
+/// Render what changed as Markdown, e.g. for an issue or chat message
+///
+/// `previous` is `None` on the first run, when `change` is empty.
+pub fn render_change_markdown(
+    report: &ComplianceReport,
+    previous: Option<&ComplianceState>,
+    change: &StateChange,
+) -> String {
+    let since = previous
+        .and_then(|p| p.verified_at.as_deref())
+        .unwrap_or("the last run");
+    let mut md = String::new();
+    if previous.is_none() {
+        md.push_str("## 🤖 RSR compliance state recorded\n\n");
+        md.push_str(&format!(
+            "`{}`: {} failing checks, level {}. Later runs report what changes.\n",
+            report.repository_display(),
+            report.checks.iter().filter(|c| c.is_failure()).count(),
+            level_name(change.level)
+        ));
+        return md;
+    }
+    if change.is_empty() {
+        md.push_str(&format!("## 🤖 No RSR compliance changes since {}\n\n", since));
+        md.push_str(&format!(
+            "`{}`: level {}.\n",
+            report.repository_display(),
+            level_name(change.level)
+        ));
+        return md;
+    }
+
+    md.push_str(&format!("## 🤖 RSR compliance changed since {}\n\n", since));
+    md.push_str(&format!("`{}`", report.repository_display()));
+    if change.level != change.previous_level {
+        md.push_str(&format!(
+            ": **level {} → {}**",
+            level_name(change.previous_level),
+            level_name(change.level)
+        ));
+    }
+    md.push('\n');
+    for (heading, ids) in [
+        ("❌ Newly failing", &change.newly_failing),
+        ("✅ Resolved", &change.resolved),
+    ] {
+        if ids.is_empty() {
+            continue;
+        }
+        md.push_str(&format!("\n### {} ({})\n\n", heading, ids.len()));
+        for id in ids {
+            match find(report, id) {
+                Some(check) => md.push_str(&format!(
+                    "- `{}` {}: {}\n",
+                    id, check.category, check.item
+                )),
+                None => md.push_str(&format!("- `{}`\n", id)),
+            }
+        }
+    }
+    md
+}
+
+/// Render what changed as a JSON object
+///
+/// `previous` is `None` on the first run, when `change` is empty.
+pub fn render_change_json(
+    report: &ComplianceReport,
+    previous: Option<&ComplianceState>,
+    change: &StateChange,
+) -> String {
+    let optional = |value: Option<&str>| value.map_or("null".to_string(), json::string);
+    let level = |level: Option<ComplianceLevel>| optional(level.map(|l| l.display_name()));
+    let checks = |ids: &[String]| {
+        if ids.is_empty() {
+            return "[]".to_string();
+        }
+        let entries: Vec<String> = ids
+            .iter()
+            .map(|id| {
+                let check = find(report, id);
+                format!(
+                    "    {{ \"id\": {}, \"category\": {}, \"item\": {} }}",
+                    json::string(id),
+                    optional(check.map(|c| c.category.as_str())),
+                    optional(check.map(|c| c.item.as_str()))
+                )
+            })
+            .collect();
+        format!("[\n{}\n  ]", entries.join(",\n"))
+    };
+    let mut out = String::new();
+    out.push_str("{\n");
+    out.push_str("  \"tool\": \"rhodibot\",\n");
+    out.push_str(&format!(
+        "  \"repository\": {},\n",
+        json::string(&report.repository_display())
+    ));
+    out.push_str(&format!("  \"first_run\": {},\n", previous.is_none()));
+    out.push_str(&format!(
+        "  \"since\": {},\n",
+        optional(previous.and_then(|p| p.verified_at.as_deref()))
+    ));
+    out.push_str(&format!("  \"changed\": {},\n", !change.is_empty()));
+    out.push_str(&format!("  \"regression\": {},\n", change.is_regression()));
+    out.push_str(&format!("  \"previous_level\": {},\n", level(change.previous_level)));
+    out.push_str(&format!("  \"level\": {},\n", level(change.level)));
+    out.push_str(&format!("  \"newly_failing\": {},\n", checks(&change.newly_failing)));
+    out.push_str(&format!("  \"resolved\": {}\n", checks(&change.resolved)));
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn state(level: Option<ComplianceLevel>, failing: &[&str]) -> ComplianceState {
+        ComplianceState {
+            standard_version: "1.0".to_string(),
+            verified_at: None,
+            level,
+            failing: failing.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", true, ComplianceLevel::Bronze);
+        report.add_check("Well-Known", "security.txt", false, ComplianceLevel::Silver);
+        report.add_check("Documentation", "CHANGELOG.md", false, ComplianceLevel::Silver);
+
+        let current = ComplianceState::from_report(&report);
+        assert_eq!(current.level, Some(ComplianceLevel::Bronze));
+        assert_eq!(
+            current.failing,
+            ["rsr/documentation/changelog.md", "rsr/well-known/security.txt"]
+        );
+        assert_eq!(ComplianceState::parse(&current.render()), Ok(current.clone()));
+
+        assert!(ComplianceState::parse("{\"level\": \"tin\", \"failing\": []}").is_err());
+        assert!(ComplianceState::parse("{\"failing\": [1]}").is_err());
+        assert!(ComplianceState::parse("[]").is_err());
+    }
+
+    #[test]
+    fn test_state_change() {
+        let bronze = Some(ComplianceLevel::Bronze);
+        let silver = Some(ComplianceLevel::Silver);
+        let previous = state(silver, &["RSR-DOC-004", "RSR-WKN-003"]);
+
+        let same = StateChange::between(&previous, &previous);
+        assert!(same.is_empty() && !same.is_regression());
+
+        // A fix alone is a change, but not a regression
+        let fixed = StateChange::between(&previous, &state(silver, &["RSR-WKN-003"]));
+        assert_eq!(fixed.resolved, ["RSR-DOC-004"]);
+        assert!(!fixed.is_empty() && !fixed.is_regression());
+
+        let broken = state(silver, &["RSR-DOC-001", "RSR-WKN-003"]);
+        let broke = StateChange::between(&previous, &broken);
+        assert_eq!(broke.newly_failing, ["RSR-DOC-001"]);
+        assert!(broke.is_regression());
+
+        assert!(StateChange::between(&previous, &state(bronze, &[])).is_regression());
+        assert!(StateChange::between(&previous, &state(None, &[])).is_regression());
+        let unranked = state(None, &["RSR-DOC-004", "RSR-WKN-003"]);
+        assert!(!StateChange::between(&unranked, &previous).is_regression());
+    }
+
+    #[test]
+    fn test_render_change() {
+        let mut report = ComplianceReport::new(PathBuf::from("/repo"));
+        report.add_check("Documentation", "README.md", false, ComplianceLevel::Bronze);
+        report.add_check("Well-Known", "security.txt", true, ComplianceLevel::Bronze);
+        let previous = ComplianceState {
+            verified_at: Some("2026-10-09T00:00:00Z".to_string()),
+            ..state(Some(ComplianceLevel::Bronze), &["RSR-OLD-001", "rsr/well-known/security.txt"])
+        };
+        let current = ComplianceState::from_report(&report);
+        let change = StateChange::between(&previous, &current);
+
+        let md = render_change_markdown(&report, Some(&previous), &change);
+        assert!(md.starts_with("## 🤖 RSR compliance changed since 2026-10-09T00:00:00Z\n\n"));
+        assert!(md.contains("`/repo`: **level Bronze → none**\n"));
+        assert!(md.contains("### ❌ Newly failing (1)\n\n- `rsr/documentation/readme.md` \
+                             Documentation: README.md\n"));
+        // A check that no longer runs is listed by ID alone
+        assert!(md.ends_with("- `RSR-OLD-001`\n- `rsr/well-known/security.txt` Well-Known: \
+                              security.txt\n"));
+
+        let json = render_change_json(&report, Some(&previous), &change);
+        assert!(json.contains("  \"first_run\": false,\n  \"since\": \"2026-10-09T00:00:00Z\",\n"));
+        assert!(json.contains("  \"regression\": true,\n  \"previous_level\": \"Bronze\",\n"));
+        assert!(json.contains("{ \"id\": \"RSR-OLD-001\", \"category\": null, \"item\": null }"));
+
+        let unchanged = StateChange::between(&current, &current);
+        let first = render_change_json(&report, None, &unchanged);
+        assert!(first.contains("  \"first_run\": true,\n  \"since\": null,\n"));
+        assert!(first.contains("  \"changed\": false,\n  \"regression\": false,\n"));
+        assert!(render_change_markdown(&report, None, &unchanged)
+            .starts_with("## 🤖 RSR compliance state recorded\n"));
+    }
+}
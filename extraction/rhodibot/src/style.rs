@@ -0,0 +1,339 @@
+//! Terminal styling for human-readable output
+//!
+//! Human reports use emoji icons and box-drawing rules, which some Windows
+//! consoles and log aggregators mangle. Printers ask a [`Style`] for every
+//! icon, rule and highlight instead of hard-coding them, so `--no-emoji`
+//! switches the whole report to plain ASCII and `--color` decides whether
+//! ANSI colors are used.
+//!
+//! `--accessible` goes further for screen readers and braille displays:
+//! statuses become words (`PASS`, `FAIL`, `WARNING`), decorative icons and
+//! rules are left out, colors are off, and each check is one line with the
+//! same fields in the same order.
+//!
+//! With `--color auto` (the default) colors are only used when the output
+//! goes to a terminal, `NO_COLOR` is unset and `TERM` is not `dumb`. A dumb
+//! terminal also gets ASCII icons.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// When to use ANSI colors (`--color`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            other => Err(format!(
+                "Unknown color mode: {}. Use 'auto', 'always' or 'never'",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Icons used in human output
+///
+/// Emoji that render narrow carry a trailing space so the text after them
+/// lines up either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    Bot,
+    Search,
+    Category,
+    Pass,
+    Fail,
+    Waived,
+    Skipped,
+    Shield,
+    Info,
+    Warning,
+    Critical,
+    Trophy,
+    Timer,
+    Expired,
+    Error,
+    Progress,
+}
+
+impl Icon {
+    const fn emoji(self) -> &'static str {
+        match self {
+            Icon::Bot => "🤖",
+            Icon::Search => "🔍",
+            Icon::Category => "📋",
+            Icon::Pass => "✅",
+            Icon::Fail => "❌",
+            Icon::Waived => "🔕",
+            Icon::Skipped => "⏭️ ",
+            Icon::Shield => "🛡️ ",
+            Icon::Info => "ℹ️ ",
+            Icon::Warning => "⚠️ ",
+            Icon::Critical => "🚨",
+            Icon::Trophy => "🏆",
+            Icon::Timer => "⏱️ ",
+            Icon::Expired => "⌛",
+            Icon::Error => "⛔",
+            Icon::Progress => "⏳",
+        }
+    }
+
+    const fn ascii(self) -> &'static str {
+        match self {
+            Icon::Bot | Icon::Search => "*",
+            Icon::Category => "#",
+            Icon::Pass => "[PASS]",
+            Icon::Fail => "[FAIL]",
+            Icon::Waived => "[WAIVED]",
+            Icon::Skipped => ">>",
+            Icon::Shield => "!!",
+            Icon::Info => "[i]",
+            Icon::Warning => "[!]",
+            Icon::Critical => "[!!]",
+            Icon::Trophy => "**",
+            Icon::Timer => "~",
+            Icon::Expired => "[EXPIRED]",
+            Icon::Error => "[ERROR]",
+            Icon::Progress => "..",
+        }
+    }
+
+    /// Word read out for the icon in accessible output; decorative icons
+    /// have none
+    const fn word(self) -> &'static str {
+        match self {
+            Icon::Pass => "PASS",
+            Icon::Fail => "FAIL",
+            Icon::Waived => "WAIVED",
+            Icon::Skipped => "SKIPPED",
+            Icon::Info => "INFO",
+            Icon::Warning => "WARNING",
+            Icon::Critical => "CRITICAL",
+            Icon::Expired => "EXPIRED",
+            Icon::Error => "ERROR",
+            Icon::Bot
+            | Icon::Search
+            | Icon::Category
+            | Icon::Shield
+            | Icon::Trophy
+            | Icon::Timer
+            | Icon::Progress => "",
+        }
+    }
+
+    /// Color an icon is painted in when it has one
+    const fn color(self) -> Option<Color> {
+        match self {
+            Icon::Pass | Icon::Trophy => Some(Color::Green),
+            Icon::Fail | Icon::Critical | Icon::Error => Some(Color::Red),
+            Icon::Warning | Icon::Expired => Some(Color::Yellow),
+            Icon::Info | Icon::Waived => Some(Color::Cyan),
+            _ => None,
+        }
+    }
+}
+
+/// ANSI colors used for highlights
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Cyan,
+    Bold,
+}
+
+impl Color {
+    const fn code(self) -> &'static str {
+        match self {
+            Color::Red => "31",
+            Color::Green => "32",
+            Color::Yellow => "33",
+            Color::Cyan => "36",
+            Color::Bold => "1",
+        }
+    }
+}
+
+/// How human output is decorated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    /// Wrap highlights in ANSI color codes
+    pub color: bool,
+    /// Use emoji icons and box-drawing rules rather than ASCII
+    pub emoji: bool,
+    /// Words instead of icons, no rules and one check per line
+    /// (`--accessible`)
+    pub accessible: bool,
+}
+
+impl Default for Style {
+    /// Emoji without color, the output of earlier releases
+    fn default() -> Self {
+        Self {
+            color: false,
+            emoji: true,
+            accessible: false,
+        }
+    }
+}
+
+impl Style {
+    /// ASCII without color
+    pub const PLAIN: Style = Style {
+        color: false,
+        emoji: false,
+        accessible: false,
+    };
+
+    /// Words without color, icons or rules, for screen readers
+    pub const ACCESSIBLE: Style = Style {
+        color: false,
+        emoji: false,
+        accessible: true,
+    };
+
+    /// Style for output to a stream, reading `NO_COLOR` and `TERM`
+    ///
+    /// `is_terminal` tells whether the stream is a terminal; it only matters
+    /// for [`ColorChoice::Auto`].
+    pub fn detect(choice: ColorChoice, emoji: bool, is_terminal: bool) -> Self {
+        let dumb = std::env::var("TERM").map_or(false, |term| term == "dumb");
+        Self::resolve(choice, emoji, is_terminal, crate::env::no_color(), dumb)
+    }
+
+    /// [`Style::detect`] with the environment passed in
+    pub fn resolve(
+        choice: ColorChoice,
+        emoji: bool,
+        is_terminal: bool,
+        no_color: bool,
+        dumb_terminal: bool,
+    ) -> Self {
+        let color = match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => is_terminal && !no_color && !dumb_terminal,
+        };
+        Self {
+            color,
+            emoji: emoji && !dumb_terminal,
+            accessible: false,
+        }
+    }
+
+    /// An icon, painted in its color
+    ///
+    /// Accessible output gets the icon's word, empty for decorative icons;
+    /// see [`Style::label`] for icons that start a line.
+    pub fn icon(&self, icon: Icon) -> String {
+        if self.accessible {
+            return icon.word().to_string();
+        }
+        let text = if self.emoji { icon.emoji() } else { icon.ascii() };
+        match icon.color() {
+            Some(color) if !self.emoji => self.paint(color, text),
+            _ => text.to_string(),
+        }
+    }
+
+    /// `text` after `icon`, or alone when the icon has nothing to show
+    pub fn label(&self, icon: Icon, text: &str) -> String {
+        let icon = self.icon(icon);
+        if icon.is_empty() {
+            text.to_string()
+        } else {
+            format!("{} {}", icon, text)
+        }
+    }
+
+    /// A horizontal rule `width` columns wide, with a trailing newline;
+    /// nothing in accessible output
+    pub fn rule(&self, width: usize) -> String {
+        if self.accessible {
+            return String::new();
+        }
+        let line = if self.emoji { "━" } else { "=" };
+        format!("{}\n", line.repeat(width))
+    }
+
+    /// `text` in `color`, or unchanged without color
+    pub fn paint(&self, color: Color, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", color.code(), text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let auto = ColorChoice::Auto;
+        assert!(Style::resolve(auto, true, true, false, false).color);
+        assert!(!Style::resolve(auto, true, false, false, false).color);
+        assert!(!Style::resolve(auto, true, true, true, false).color);
+        assert!(Style::resolve(ColorChoice::Always, true, false, true, false).color);
+        assert!(!Style::resolve(ColorChoice::Never, true, true, false, false).color);
+
+        let dumb = Style::resolve(auto, true, true, false, true);
+        assert_eq!(dumb, Style::PLAIN);
+        assert!(!Style::resolve(auto, false, true, false, false).emoji);
+
+        assert_eq!("never".parse(), Ok(ColorChoice::Never));
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+
+    #[test]
+    fn test_rendering() {
+        let fancy = Style::default();
+        assert_eq!(fancy.icon(Icon::Pass), "✅");
+        assert_eq!(fancy.rule(3), "━━━\n");
+        assert_eq!(fancy.paint(Color::Red, "FAIL"), "FAIL");
+
+        assert_eq!(Style::PLAIN.icon(Icon::Fail), "[FAIL]");
+        assert_eq!(Style::PLAIN.rule(3), "===\n");
+        assert!(Style::PLAIN.icon(Icon::Warning).is_ascii());
+
+        let colored = Style {
+            color: true,
+            emoji: false,
+            accessible: false,
+        };
+        assert_eq!(colored.icon(Icon::Pass), "\x1b[32m[PASS]\x1b[0m");
+        assert_eq!(colored.icon(Icon::Category), "#");
+        assert_eq!(colored.paint(Color::Bold, "x"), "\x1b[1mx\x1b[0m");
+
+        let accessible = Style::ACCESSIBLE;
+        assert_eq!(accessible.icon(Icon::Warning), "WARNING");
+        assert_eq!(accessible.label(Icon::Bot, "Rhodibot"), "Rhodibot");
+        assert_eq!(accessible.label(Icon::Fail, "x"), "FAIL x");
+        assert_eq!(accessible.rule(46), "");
+    }
+}
@@ -0,0 +1,88 @@
+//! External commands with a deadline
+//!
+//! Rhodibot shells out to `git`, `curl`, `tar` and `openssl`. A command that stalls on
+//! a dead network connection or an unanswered prompt would stall the run,
+//! and CI, with it, so [`run`] waits for a command only until its timeout
+//! and kills it after that. The checks pass the time left in their budget
+//! (see [`crate::config::LimitsConfig`]); other callers pass a fixed limit.
+
+use std::io::{self, Read, Write};
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a running command is checked on
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Run `command` with `input` on its standard input (none when `None`),
+/// and collect its output
+///
+/// A command still running after `timeout` is killed, and an error of kind
+/// [`io::ErrorKind::TimedOut`] is returned.
+pub fn run(command: &mut Command, input: Option<&[u8]>, timeout: Duration) -> io::Result<Output> {
+    let stdin = if input.is_some() { Stdio::piped() } else { Stdio::null() };
+    let mut child = command
+        .stdin(stdin)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Pipes are served on their own threads, so a command blocked writing
+    // a full pipe still exits
+    if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+        let input = input.to_vec();
+        thread::spawn(move || stdin.write_all(&input));
+    }
+    let read = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut data = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut data);
+            }
+            data
+        })
+    };
+    let stdout = read(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = read(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("timed out after {}s", timeout.as_secs_f64()),
+            ));
+        }
+        thread::sleep(POLL_INTERVAL.min(deadline - now));
+    };
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let output = run(Command::new("cat").arg("-"), Some(b"hello"), Duration::from_secs(10));
+        let output = output.unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hello");
+
+        let started = Instant::now();
+        let error = run(Command::new("sleep").arg("10"), None, Duration::from_millis(50))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(error.to_string(), "timed out after 0.05s");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
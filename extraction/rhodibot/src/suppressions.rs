@@ -0,0 +1,194 @@
+//! Per-check suppressions (waivers)
+//!
+//! A repository can waive individual checks in `.rhodibot/suppressions.toml`.
+//! Every waiver names a check ID in its section header and must give a
+//! `reason`; an optional `expires` date (`YYYY-MM-DD`) limits how long it
+//! applies. Once expired the waiver no longer counts and the check fails again.
+//!
+//! ```toml
+//! [RSR-BLD-002]
+//! reason = "Builds are reproducible via Guix, see docs/BUILD.md"
+//! expires = "2026-06-30"
+//! ```
+
+use crate::catalog;
+use crate::config::{expect_string, toml, ConfigError};
+use crate::vfs::{FileSystem, RealFs};
+use std::path::Path;
+
+/// Suppressions file location, relative to the repository root
+pub const SUPPRESSIONS_FILE: &str = ".rhodibot/suppressions.toml";
+
+/// A single waived check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    /// Check ID being waived
+    pub id: String,
+    /// Why the check does not apply to this repository
+    pub reason: String,
+    /// Last day (inclusive) the waiver applies, as `YYYY-MM-DD`
+    pub expires: Option<String>,
+}
+
+impl Suppression {
+    /// Whether the waiver has lapsed on `today` (`YYYY-MM-DD`)
+    pub fn is_expired(&self, today: &str) -> bool {
+        match self.expires {
+            // ISO dates compare correctly as strings
+            Some(ref expires) => expires.as_str() < today,
+            None => false,
+        }
+    }
+}
+
+/// All suppressions declared for a repository
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Suppressions {
+    pub entries: Vec<Suppression>,
+}
+
+/// Whether `s` looks like a `YYYY-MM-DD` date
+fn is_iso_date(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+        && matches!(s[5..7].parse::<u8>(), Ok(1..=12))
+        && matches!(s[8..10].parse::<u8>(), Ok(1..=31))
+}
+
+impl Suppressions {
+    /// Parse a suppressions file
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let doc = toml::parse(text)?;
+        if let Some(entry) = doc.root.entries.first() {
+            return Err(ConfigError::new(
+                entry.line,
+                format!("key '{}' must follow a [CHECK-ID] header", entry.key),
+            ));
+        }
+
+        let mut entries: Vec<Suppression> = Vec::new();
+        for table in &doc.tables {
+            let id = table.name.as_str();
+            if catalog::lookup(id).is_none() {
+                return Err(ConfigError::new(table.line, format!("unknown check id '{}'", id)));
+            }
+            let mut suppression = Suppression {
+                id: id.to_string(),
+                reason: String::new(),
+                expires: None,
+            };
+            for entry in &table.entries {
+                let (key, value, line_no) = (entry.key.as_str(), &entry.value, entry.line);
+                match key {
+                    "reason" => suppression.reason = expect_string(key, value, line_no)?,
+                    "expires" => {
+                        let date = expect_string(key, value, line_no)?;
+                        if !is_iso_date(&date) {
+                            return Err(ConfigError::new(
+                                line_no,
+                                format!("'expires' must be a YYYY-MM-DD date, found '{}'", date),
+                            ));
+                        }
+                        suppression.expires = Some(date);
+                    }
+                    _ => {
+                        return Err(ConfigError::new(
+                            line_no,
+                            format!("unknown key '{}' in [{}]", key, id),
+                        ))
+                    }
+                }
+            }
+            if suppression.reason.trim().is_empty() {
+                return Err(ConfigError::new(
+                    table.line,
+                    format!("suppression for {} needs a 'reason'", id),
+                ));
+            }
+            entries.push(suppression);
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Load suppressions from a file
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        Self::load_with_fs(&RealFs, path)
+    }
+
+    /// Load suppressions from a file in `fs`
+    pub fn load_with_fs(fs: &dyn FileSystem, path: &Path) -> Result<Self, ConfigError> {
+        let text = fs.read_to_string(path).map_err(|e| {
+            ConfigError::new(0, format!("cannot read {}: {}", path.display(), e))
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Load `.rhodibot/suppressions.toml` from the repository, or none if absent
+    pub fn discover(repo_path: &Path) -> Result<Self, ConfigError> {
+        Self::discover_with_fs(&RealFs, repo_path)
+    }
+
+    /// Like [`Suppressions::discover`], for a repository in `fs`
+    pub fn discover_with_fs(fs: &dyn FileSystem, repo_path: &Path) -> Result<Self, ConfigError> {
+        let path = repo_path.join(SUPPRESSIONS_FILE);
+        if fs.is_file(&path) {
+            Self::load_with_fs(fs, &path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Find the suppression for a check ID
+    pub fn get(&self, id: &str) -> Option<&Suppression> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_suppressions() {
+        let s = Suppressions::parse(
+            r#"
+# Waivers agreed with the platform team
+[RSR-BLD-002]
+reason = "Guix instead of Nix"
+expires = "2026-06-30"
+
+[RSR-WKN-003]
+reason = "No AI policy yet" # tracked in #42
+"#,
+        )
+        .unwrap();
+        assert_eq!(s.entries.len(), 2);
+        let bld = s.get("RSR-BLD-002").unwrap();
+        assert_eq!(bld.expires.as_deref(), Some("2026-06-30"));
+        assert!(!bld.is_expired("2026-06-30"));
+        assert!(bld.is_expired("2026-07-01"));
+        assert!(!s.get("RSR-WKN-003").unwrap().is_expired("2999-01-01"));
+    }
+
+    #[test]
+    fn test_suppression_errors() {
+        // Reason is mandatory
+        let err = Suppressions::parse("[RSR-BLD-002]\nexpires = \"2026-01-01\"\n").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("reason"));
+
+        let err = Suppressions::parse("[RSR-BLD-002]\nreason = \"x\"\n[RSR-BLD-002]\nreason = \"y\"")
+            .unwrap_err();
+        assert_eq!(err.line, 3);
+
+        assert!(Suppressions::parse("[RSR-NOPE-001]\nreason = \"x\"").is_err());
+        assert!(Suppressions::parse("reason = \"x\"").is_err());
+        assert!(Suppressions::parse("[RSR-BLD-002]\nreason = \"x\"\nexpires = \"soon\"").is_err());
+        assert!(Suppressions::parse("[RSR-BLD-002]\nreason = \"x\"\nexpires = \"2026-13-01\"").is_err());
+    }
+}
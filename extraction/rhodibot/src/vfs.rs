@@ -0,0 +1,641 @@
+//! Filesystem abstraction used by the checks
+//!
+//! Every check reads the repository through a [`FileSystem`]. [`RealFs`]
+//! forwards to `std::fs`; [`MemoryFs`] is an in-memory tree of files,
+//! directories and symlinks, so checks can be unit tested without creating
+//! temporary directories (and symlink handling can be tested on platforms
+//! where creating symlinks needs privileges).
+//!
+//! The configuration and suppressions files are read through the same trait
+//! (`Config::discover_with_fs`, `Suppressions::discover_with_fs`), so a whole
+//! run can work on a virtual tree. This is how the engine runs when built for
+//! `wasm32-wasi`, inside WASM CI sandboxes or a browser-based repository
+//! viewer that has no host filesystem to offer:
+//!
+//! ```
+//! use rhodibot::config::Config;
+//! use rhodibot::suppressions::Suppressions;
+//! use rhodibot::vfs::MemoryFs;
+//! use rhodibot::{verify_repository_with_fs, Hooks};
+//! use std::path::Path;
+//!
+//! let mut fs = MemoryFs::new();
+//! fs.add_file("/repo/README.md", "# Demo\n")
+//!     .add_file("/repo/.rhodibot.toml", "[checks]\ndisable = [\"RSR-BLD-002\"]\n")
+//!     .add_file("/repo/.rhodibot/suppressions.toml", "[RSR-DOC-002]\nreason = \"pending\"\n");
+//! let repo = Path::new("/repo");
+//!
+//! let config = Config::discover_with_fs(&fs, repo).unwrap();
+//! let mut report = verify_repository_with_fs(repo, &config, &Hooks::default(), &fs);
+//! report.apply_suppressions(&Suppressions::discover_with_fs(&fs, repo).unwrap());
+//! assert!(report.skipped_checks.contains(&"RSR-BLD-002".to_string()));
+//! assert!(report.checks.iter().any(|c| c.id == "RSR-DOC-002" && c.waived));
+//! ```
+//!
+//! A verification run wraps its filesystem in an [`FsCache`], so the dozens
+//! of lookups the checks make cost one `read_dir` per directory they look in
+//! plus one `stat` per entry that exists, which matters on network
+//! filesystems.
+
+use crate::paths;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Kind of filesystem entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    File,
+    Dir,
+    /// A symlink (or, on Windows, a junction), reported only when not followed
+    Symlink,
+    /// Anything else: sockets, devices, FIFOs
+    Other,
+}
+
+/// The subset of file metadata the checks use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileMeta {
+    pub kind: FileKind,
+    /// Size in bytes
+    pub len: u64,
+    /// The link is an NTFS junction (Windows only)
+    pub is_junction: bool,
+}
+
+impl FileMeta {
+    pub fn is_file(&self) -> bool {
+        self.kind == FileKind::File
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.kind == FileKind::Dir
+    }
+
+    pub fn is_symlink(&self) -> bool {
+        self.kind == FileKind::Symlink
+    }
+}
+
+/// Read-only view of a filesystem
+pub trait FileSystem {
+    /// Metadata of `path`, following symlinks
+    fn metadata(&self, path: &Path) -> io::Result<FileMeta>;
+
+    /// Metadata of `path` itself, without following a final symlink
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMeta>;
+
+    /// Target of the symlink at `path`
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// Contents of the file at `path`
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+
+    /// Raw contents of the file at `path`, for binary files
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.read_to_string(path).map(String::into_bytes)
+    }
+
+    /// Paths of the entries in the directory at `path`, sorted
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+
+    /// Absolute path with every symlink resolved
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// Whether `path` exists, following symlinks
+    fn exists(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok()
+    }
+
+    /// Whether `path` is a regular file, following symlinks
+    fn is_file(&self, path: &Path) -> bool {
+        self.metadata(path).map_or(false, |m| m.is_file())
+    }
+
+    /// Whether `path` is a directory, following symlinks
+    fn is_dir(&self, path: &Path) -> bool {
+        self.metadata(path).map_or(false, |m| m.is_dir())
+    }
+}
+
+/// The real filesystem, via `std::fs`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealFs;
+
+fn meta_from_std(path: &Path, metadata: &fs::Metadata) -> FileMeta {
+    let file_type = metadata.file_type();
+    let kind = if paths::is_link(metadata) {
+        FileKind::Symlink
+    } else if file_type.is_dir() {
+        FileKind::Dir
+    } else if file_type.is_file() {
+        FileKind::File
+    } else {
+        FileKind::Other
+    };
+    FileMeta {
+        kind,
+        len: metadata.len(),
+        is_junction: kind == FileKind::Symlink && paths::is_junction(path, metadata),
+    }
+}
+
+impl FileSystem for RealFs {
+    fn metadata(&self, path: &Path) -> io::Result<FileMeta> {
+        fs::metadata(path).map(|m| meta_from_std(path, &m))
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMeta> {
+        fs::symlink_metadata(path).map(|m| meta_from_std(path, &m))
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        fs::read_link(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut entries = fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        entries.sort();
+        Ok(entries)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        path.canonicalize()
+    }
+}
+
+/// A cached lookup: the value, or the error's kind and message
+type Cached<T> = Result<T, (io::ErrorKind, String)>;
+
+/// Memoizing view of another [`FileSystem`] for the length of one run
+///
+/// Metadata, link targets, canonical paths and directory listings are each
+/// asked of the inner filesystem at most once per path. Once a directory
+/// has been listed, every path in it that is not among its entries is
+/// reported missing without another call, so checking many names in the
+/// repository root or `.well-known/` costs a single `read_dir`. File
+/// contents are not cached. The view does not notice changes made after a
+/// path was first looked up.
+pub struct FsCache<'a> {
+    inner: &'a dyn FileSystem,
+    metadata: RefCell<HashMap<PathBuf, Cached<FileMeta>>>,
+    symlink_metadata: RefCell<HashMap<PathBuf, Cached<FileMeta>>>,
+    links: RefCell<HashMap<PathBuf, Cached<PathBuf>>>,
+    listings: RefCell<HashMap<PathBuf, Cached<Vec<PathBuf>>>>,
+    canonical: RefCell<HashMap<PathBuf, Cached<PathBuf>>>,
+}
+
+impl<'a> FsCache<'a> {
+    pub fn new(inner: &'a dyn FileSystem) -> Self {
+        Self {
+            inner,
+            metadata: RefCell::default(),
+            symlink_metadata: RefCell::default(),
+            links: RefCell::default(),
+            listings: RefCell::default(),
+            canonical: RefCell::default(),
+        }
+    }
+
+    /// Whether `path` is known to be missing from its directory's listing
+    fn is_unlisted(&self, path: &Path) -> bool {
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            return false;
+        };
+        match self.listings.borrow().get(parent) {
+            Some(Ok(entries)) => !entries.iter().any(|e| e.file_name() == Some(name)),
+            _ => false,
+        }
+    }
+
+    /// Look `path` up in `cache`, asking `lookup` on a miss
+    fn cached<T: Clone>(
+        &self,
+        cache: &RefCell<HashMap<PathBuf, Cached<T>>>,
+        path: &Path,
+        lookup: impl FnOnce() -> io::Result<T>,
+    ) -> io::Result<T> {
+        if let Some(hit) = cache.borrow().get(path) {
+            return hit.clone().map_err(|(kind, message)| io::Error::new(kind, message));
+        }
+        let result = if self.is_unlisted(path) {
+            Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{}: not found", path.display()),
+            ))
+        } else {
+            lookup()
+        };
+        let entry = match &result {
+            Ok(value) => Ok(value.clone()),
+            Err(e) => Err((e.kind(), e.to_string())),
+        };
+        cache.borrow_mut().insert(path.to_path_buf(), entry);
+        result
+    }
+}
+
+impl FileSystem for FsCache<'_> {
+    fn metadata(&self, path: &Path) -> io::Result<FileMeta> {
+        self.cached(&self.metadata, path, || {
+            // An entry that is not a symlink has the same metadata either way
+            match self.symlink_metadata(path) {
+                Ok(meta) if !meta.is_symlink() => Ok(meta),
+                _ => self.inner.metadata(path),
+            }
+        })
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMeta> {
+        self.cached(&self.symlink_metadata, path, || self.inner.symlink_metadata(path))
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        self.cached(&self.links, path, || self.inner.read_link(path))
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.inner.read_to_string(path)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.inner.read(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.cached(&self.listings, path, || self.inner.read_dir(path))
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.cached(&self.canonical, path, || self.inner.canonicalize(path))
+    }
+}
+
+/// An entry in a [`MemoryFs`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    File(String),
+    Dir,
+    Symlink(PathBuf),
+}
+
+/// Symlink hops allowed while resolving one path, as on Linux
+const MAX_LINK_HOPS: usize = 40;
+
+/// An in-memory filesystem, for tests and for virtual trees
+///
+/// Paths should be absolute. Adding an entry creates its missing parent
+/// directories; symlink targets may be relative to the link's directory and
+/// need not exist.
+///
+/// ```
+/// use rhodibot::vfs::{FileSystem, MemoryFs};
+/// use std::path::Path;
+///
+/// let mut fs = MemoryFs::new();
+/// fs.add_file("/repo/README.md", "# Demo\n")
+///     .add_symlink("/repo/LICENSE.txt", "/etc/license");
+/// assert!(fs.is_file(Path::new("/repo/README.md")));
+/// assert!(fs.symlink_metadata(Path::new("/repo/LICENSE.txt")).unwrap().is_symlink());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFs {
+    nodes: BTreeMap<PathBuf, Node>,
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{}: no such file or directory", path.display()),
+    )
+}
+
+/// Resolve `.` and `..` components without touching the filesystem
+fn lexical_normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+impl MemoryFs {
+    /// An empty filesystem containing only the root directory
+    pub fn new() -> Self {
+        let mut nodes = BTreeMap::new();
+        nodes.insert(PathBuf::from("/"), Node::Dir);
+        Self { nodes }
+    }
+
+    fn insert(&mut self, path: &Path, node: Node) -> &mut Self {
+        let path = lexical_normalize(path);
+        for ancestor in path.ancestors().skip(1) {
+            if ancestor.as_os_str().is_empty() {
+                break;
+            }
+            self.nodes
+                .entry(ancestor.to_path_buf())
+                .or_insert(Node::Dir);
+        }
+        self.nodes.insert(path, node);
+        self
+    }
+
+    /// Add a file with the given contents
+    pub fn add_file(&mut self, path: impl AsRef<Path>, contents: &str) -> &mut Self {
+        self.insert(path.as_ref(), Node::File(contents.to_string()))
+    }
+
+    /// Add an empty directory
+    pub fn add_dir(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.insert(path.as_ref(), Node::Dir)
+    }
+
+    /// Add a symlink pointing at `target`
+    pub fn add_symlink(&mut self, path: impl AsRef<Path>, target: impl AsRef<Path>) -> &mut Self {
+        self.insert(path.as_ref(), Node::Symlink(target.as_ref().to_path_buf()))
+    }
+
+    /// Resolve symlinks in `path`; the final component is followed only when
+    /// `follow_last` is set
+    fn resolve(&self, path: &Path, follow_last: bool) -> io::Result<PathBuf> {
+        let mut pending: Vec<PathBuf> = vec![lexical_normalize(path)];
+        let mut resolved = PathBuf::new();
+        let mut hops = 0;
+
+        while let Some(current) = pending.pop() {
+            let components: Vec<Component> = current.components().collect();
+            let mut restart = None;
+            for (i, component) in components.iter().enumerate() {
+                match component {
+                    Component::ParentDir => {
+                        resolved.pop();
+                        continue;
+                    }
+                    Component::CurDir => continue,
+                    _ => resolved.push(component),
+                }
+                let is_last = i + 1 == components.len() && pending.is_empty();
+                if let Some(Node::Symlink(target)) = self.nodes.get(&resolved) {
+                    if is_last && !follow_last {
+                        continue;
+                    }
+                    hops += 1;
+                    if hops > MAX_LINK_HOPS {
+                        return Err(io::Error::new(
+                            io::ErrorKind::Other,
+                            format!("{}: too many levels of symbolic links", path.display()),
+                        ));
+                    }
+                    resolved.pop();
+                    let target = if target.is_absolute() {
+                        target.clone()
+                    } else {
+                        resolved.join(target)
+                    };
+                    let rest: PathBuf = components[i + 1..].iter().collect();
+                    restart = Some((target, rest));
+                    break;
+                }
+            }
+            if let Some((target, rest)) = restart {
+                if !rest.as_os_str().is_empty() {
+                    pending.push(rest);
+                }
+                pending.push(target);
+                resolved = PathBuf::new();
+            }
+        }
+        Ok(resolved)
+    }
+
+    fn node(&self, path: &Path, follow_last: bool) -> io::Result<(PathBuf, &Node)> {
+        let resolved = self.resolve(path, follow_last)?;
+        match self.nodes.get(&resolved) {
+            Some(node) => Ok((resolved, node)),
+            None => Err(not_found(path)),
+        }
+    }
+}
+
+fn meta_for(node: &Node) -> FileMeta {
+    let (kind, len) = match node {
+        Node::File(contents) => (FileKind::File, contents.len() as u64),
+        Node::Dir => (FileKind::Dir, 0),
+        Node::Symlink(target) => (FileKind::Symlink, target.as_os_str().len() as u64),
+    };
+    FileMeta {
+        kind,
+        len,
+        is_junction: false,
+    }
+}
+
+impl FileSystem for MemoryFs {
+    fn metadata(&self, path: &Path) -> io::Result<FileMeta> {
+        self.node(path, true).map(|(_, node)| meta_for(node))
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMeta> {
+        self.node(path, false).map(|(_, node)| meta_for(node))
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        match self.node(path, false)? {
+            (_, Node::Symlink(target)) => Ok(target.clone()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{}: not a symlink", path.display()),
+            )),
+        }
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        match self.node(path, true)? {
+            (_, Node::File(contents)) => Ok(contents.clone()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{}: not a file", path.display()),
+            )),
+        }
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let (resolved, node) = self.node(path, true)?;
+        if *node != Node::Dir {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{}: not a directory", path.display()),
+            ));
+        }
+        // Entries are listed under the path as given, like std::fs::read_dir
+        let base = lexical_normalize(path);
+        Ok(self
+            .nodes
+            .keys()
+            .filter(|p| p.parent() == Some(resolved.as_path()))
+            .filter_map(|p| p.file_name().map(|name| base.join(name)))
+            .collect())
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.node(path, true).map(|(resolved, _)| resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> MemoryFs {
+        let mut fs = MemoryFs::new();
+        fs.add_file("/repo/README.md", "# Demo\n")
+            .add_dir("/repo/src")
+            .add_symlink("/repo/docs", "src")
+            .add_symlink("/repo/LICENSE.txt", "/outside/LICENSE")
+            .add_file("/outside/LICENSE", "MIT\n")
+            .add_symlink("/repo/dangling", "missing")
+            .add_symlink("/repo/loop", "loop");
+        fs
+    }
+
+    #[test]
+    fn test_memory_fs_metadata() {
+        let fs = sample();
+        assert!(fs.is_file(Path::new("/repo/README.md")));
+        assert!(fs.is_dir(Path::new("/repo")));
+        assert!(fs.is_dir(Path::new("/repo/docs")));
+        assert!(fs.symlink_metadata(Path::new("/repo/docs")).unwrap().is_symlink());
+        assert_eq!(fs.metadata(Path::new("/repo/README.md")).unwrap().len, 7);
+        assert!(!fs.exists(Path::new("/repo/dangling")));
+        assert!(fs.symlink_metadata(Path::new("/repo/dangling")).is_ok());
+        assert!(fs.metadata(Path::new("/repo/loop")).is_err());
+    }
+
+    #[test]
+    fn test_memory_fs_links_and_contents() {
+        let fs = sample();
+        assert_eq!(
+            fs.read_link(Path::new("/repo/LICENSE.txt")).unwrap(),
+            PathBuf::from("/outside/LICENSE")
+        );
+        assert_eq!(fs.read_to_string(Path::new("/repo/LICENSE.txt")).unwrap(), "MIT\n");
+        assert_eq!(
+            fs.canonicalize(Path::new("/repo/docs/../README.md")).unwrap(),
+            PathBuf::from("/repo/README.md")
+        );
+        assert_eq!(
+            fs.canonicalize(Path::new("/repo/LICENSE.txt")).unwrap(),
+            PathBuf::from("/outside/LICENSE")
+        );
+        assert!(fs.read_to_string(Path::new("/repo/src")).is_err());
+        assert!(fs.read_link(Path::new("/repo/README.md")).is_err());
+    }
+
+    #[test]
+    fn test_memory_fs_read_dir() {
+        let mut fs = sample();
+        fs.add_file("/repo/src/lib.rs", "");
+        let entries = fs.read_dir(Path::new("/repo/docs")).unwrap();
+        assert_eq!(entries, vec![PathBuf::from("/repo/docs/lib.rs")]);
+        assert_eq!(fs.read_dir(Path::new("/repo")).unwrap().len(), 6);
+        assert!(fs.read_dir(Path::new("/repo/README.md")).is_err());
+    }
+
+    /// Counts the metadata lookups that reach a [`MemoryFs`]
+    struct CountingFs {
+        inner: MemoryFs,
+        stats: std::cell::Cell<usize>,
+    }
+
+    impl FileSystem for CountingFs {
+        fn metadata(&self, path: &Path) -> io::Result<FileMeta> {
+            self.stats.set(self.stats.get() + 1);
+            self.inner.metadata(path)
+        }
+
+        fn symlink_metadata(&self, path: &Path) -> io::Result<FileMeta> {
+            self.stats.set(self.stats.get() + 1);
+            self.inner.symlink_metadata(path)
+        }
+
+        fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+            self.inner.read_link(path)
+        }
+
+        fn read_to_string(&self, path: &Path) -> io::Result<String> {
+            self.inner.read_to_string(path)
+        }
+
+        fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+            self.inner.read_dir(path)
+        }
+
+        fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+            self.inner.canonicalize(path)
+        }
+    }
+
+    #[test]
+    fn test_fs_cache() {
+        let counting = CountingFs {
+            inner: sample(),
+            stats: std::cell::Cell::new(0),
+        };
+        let cache = FsCache::new(&counting);
+        assert!(cache.is_file(Path::new("/repo/README.md")));
+        assert!(cache.is_file(Path::new("/repo/README.md")));
+        assert!(cache.symlink_metadata(Path::new("/repo/README.md")).is_ok());
+        assert_eq!(counting.stats.get(), 1);
+
+        // Listed directories answer for names they do not contain
+        assert_eq!(cache.read_dir(Path::new("/repo")).unwrap().len(), 6);
+        assert!(!cache.exists(Path::new("/repo/SECURITY.md")));
+        let err = cache.symlink_metadata(Path::new("/repo/CHANGELOG.md")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert_eq!(counting.stats.get(), 1);
+
+        // Symlinks are still followed by the inner filesystem
+        assert!(cache.is_dir(Path::new("/repo/docs")));
+        assert!(!cache.exists(Path::new("/repo/dangling")));
+        assert_eq!(counting.stats.get(), 5);
+    }
+
+    #[test]
+    fn test_real_fs_matches_std() {
+        let dir = std::env::temp_dir().join("rhodibot_vfs_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("b.txt"), "bee").unwrap();
+        fs::write(dir.join("a.txt"), "a").unwrap();
+
+        let real = RealFs;
+        assert!(real.is_file(&dir.join("a.txt")));
+        assert!(real.is_dir(&dir.join("sub")));
+        assert_eq!(real.metadata(&dir.join("b.txt")).unwrap().len, 3);
+        assert_eq!(real.read_to_string(&dir.join("b.txt")).unwrap(), "bee");
+        assert_eq!(
+            real.read_dir(&dir).unwrap(),
+            vec![dir.join("a.txt"), dir.join("b.txt"), dir.join("sub")]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,125 @@
+//! Grouping of similar security warnings for human output
+//!
+//! A deep scan can produce hundreds of near-identical warnings, such as one
+//! per symlink in a vendored tree. Warnings are grouped by level, message
+//! template (the message with its quoted names replaced by `'*'`) and path
+//! prefix (the top-level directory the path is in), so human output can
+//! show one line per group with a count. Machine-readable reports keep the
+//! full list.
+
+use crate::{SecurityWarning, WarningLevel};
+use std::path::Path;
+
+/// Warnings that share a level, message template and path prefix
+#[derive(Debug, Clone)]
+pub struct WarningGroup<'a> {
+    pub level: WarningLevel,
+    /// Message with each `'quoted'` part replaced by `'*'`
+    pub template: String,
+    /// Top-level directory of the paths, with a trailing `/`, or empty for
+    /// warnings at the root or without a path
+    pub prefix: String,
+    /// Members in report order
+    pub warnings: Vec<&'a SecurityWarning>,
+}
+
+/// Replace every single-quoted part of `message` with `'*'`
+pub fn message_template(message: &str) -> String {
+    let mut template = String::new();
+    let mut parts = message.split('\'');
+    if let Some(first) = parts.next() {
+        template.push_str(first);
+    }
+    let rest: Vec<&str> = parts.collect();
+    // An unmatched quote keeps the tail as it is
+    let pairs = rest.len() / 2;
+    for (i, part) in rest.iter().enumerate() {
+        template.push('\'');
+        template.push_str(if i % 2 == 0 && i / 2 < pairs { "*" } else { part });
+    }
+    template
+}
+
+/// Top-level directory of `path` below `root`, with a trailing `/`
+fn path_prefix(path: Option<&Path>, root: &Path) -> String {
+    let Some(path) = path else {
+        return String::new();
+    };
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    let mut components = rel.components();
+    match (components.next(), components.next()) {
+        (Some(first), Some(_)) => format!("{}/", first.as_os_str().to_string_lossy()),
+        _ => String::new(),
+    }
+}
+
+/// Group `warnings` of the repository at `root`, in order of first appearance
+pub fn group<'a>(warnings: &'a [SecurityWarning], root: &Path) -> Vec<WarningGroup<'a>> {
+    let mut groups: Vec<WarningGroup<'a>> = Vec::new();
+    for warning in warnings {
+        let template = message_template(&warning.message);
+        let prefix = path_prefix(warning.path.as_deref(), root);
+        let existing = groups
+            .iter_mut()
+            .find(|g| g.level == warning.level && g.template == template && g.prefix == prefix);
+        match existing {
+            Some(group) => group.warnings.push(warning),
+            None => groups.push(WarningGroup {
+                level: warning.level,
+                template,
+                prefix,
+                warnings: vec![warning],
+            }),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn warning(level: WarningLevel, message: &str, path: &str) -> SecurityWarning {
+        SecurityWarning {
+            level,
+            message: message.to_string(),
+            path: Some(PathBuf::from(path)),
+        }
+    }
+
+    #[test]
+    fn test_message_template() {
+        assert_eq!(
+            message_template("Symlink 'vendor/a' points outside repository to '/etc'"),
+            "Symlink '*' points outside repository to '*'"
+        );
+        assert_eq!(message_template("no quotes"), "no quotes");
+        assert_eq!(message_template("unbalanced 'quote"), "unbalanced 'quote");
+    }
+
+    #[test]
+    fn test_group() {
+        let root = Path::new("/repo");
+        let escape = |name: &str| {
+            warning(
+                WarningLevel::Critical,
+                &format!("Symlink '{}' points outside repository to '/etc'", name),
+                &format!("/repo/{}", name),
+            )
+        };
+        let warnings = vec![
+            escape("vendor/a/link"),
+            escape("vendor/b/link"),
+            escape("docs"),
+            warning(WarningLevel::Info, "'vendor/c' is a symlink", "/repo/vendor/c"),
+            escape("vendor/c/link"),
+        ];
+        let groups = group(&warnings, root);
+        let summary: Vec<(&str, usize)> =
+            groups.iter().map(|g| (g.prefix.as_str(), g.warnings.len())).collect();
+        assert_eq!(summary, [("vendor/", 3), ("", 1), ("vendor/", 1)]);
+        assert_eq!(groups[0].template, "Symlink '*' points outside repository to '*'");
+        assert_eq!(groups[0].warnings[2].message, warnings[4].message);
+    }
+}
@@ -0,0 +1,81 @@
+//! Reports covering several members of one workspace
+//!
+//! A monorepo or a multi-tool pipeline produces one [`ComplianceReport`] per
+//! member or scan. A [`WorkspaceReport`] collects them under the workspace
+//! root, keeps each member's report for drill-down and summarizes them into
+//! one report with [`ComplianceReport::merge`] semantics: the workspace
+//! passes a check only if every member does.
+
+use crate::ComplianceReport;
+use std::path::PathBuf;
+
+/// Reports of the members of a workspace
+#[derive(Debug, Clone)]
+pub struct WorkspaceReport {
+    /// Workspace root; the summary is a report for this path
+    pub root: PathBuf,
+    /// Member name (e.g. its path relative to the root) and its report
+    pub members: Vec<(String, ComplianceReport)>,
+}
+
+impl WorkspaceReport {
+    /// Create an empty workspace report for `root`
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            members: Vec::new(),
+        }
+    }
+
+    /// Record the report of one member
+    pub fn add_member(&mut self, name: &str, report: ComplianceReport) {
+        self.members.push((name.to_string(), report));
+    }
+
+    /// One report for the whole workspace
+    ///
+    /// Member reports are merged in the order they were added into a report
+    /// for [`WorkspaceReport::root`]; with no members the summary is empty.
+    pub fn summarize(&self) -> ComplianceReport {
+        let mut members = self.members.iter().map(|(_, report)| report.clone());
+        let Some(mut summary) = members.next() else {
+            return ComplianceReport::new(self.root.clone());
+        };
+        for report in members {
+            summary.merge(report);
+        }
+        summary.repository_path = self.root.clone();
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{catalog, WarningLevel};
+
+    #[test]
+    fn test_summarize() {
+        let mut workspace = WorkspaceReport::new(PathBuf::from("/work"));
+        assert_eq!(workspace.summarize().total_count(), 0);
+
+        let readme = catalog::lookup("RSR-DOC-001").unwrap();
+        let license = catalog::lookup("RSR-DOC-002").unwrap();
+        let mut api = ComplianceReport::new(PathBuf::from("/work/api"));
+        api.add_spec_check(readme, true);
+        api.add_spec_check(license, true);
+        let mut web = ComplianceReport::new(PathBuf::from("/work/web"));
+        web.add_spec_check(readme, true);
+        web.add_spec_check(license, false);
+        web.add_warning(WarningLevel::Critical, "Symlink escapes repository", None);
+        workspace.add_member("api", api);
+        workspace.add_member("web", web);
+
+        let summary = workspace.summarize();
+        assert_eq!(summary.repository_path, PathBuf::from("/work"));
+        assert_eq!(summary.total_count(), 2);
+        assert_eq!(summary.passed_count(), 1);
+        assert!(summary.has_critical_warnings());
+        assert_eq!(workspace.members[0].1.passed_count(), 2);
+    }
+}
@@ -0,0 +1,318 @@
+//! Randomized tests for JSON escaping and timestamp formatting
+//!
+//! A small xorshift generator drives thousands of cases per run. The seed is
+//! fixed so failures reproduce; set `RHODIBOT_FUZZ_SEED` to explore other
+//! inputs and `RHODIBOT_FUZZ_ITERATIONS` to run longer. Every assertion
+//! message includes the seed.
+
+use rhodibot::{format_timestamp, json, json_escape};
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_SEED: u64 = 0x5eed_2326_a1e7_4e1a;
+const DEFAULT_ITERATIONS: usize = 5_000;
+
+/// xorshift64* — plenty for test input generation
+struct Rng(u64);
+
+impl Rng {
+    fn from_env() -> (Self, u64) {
+        let seed = std::env::var("RHODIBOT_FUZZ_SEED")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_SEED);
+        (Rng(seed.max(1)), seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Uniform in `lo..=hi`
+    fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        let span = (hi - lo) as u64 + 1;
+        lo + (self.next() % span) as i64
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[(self.next() % items.len() as u64) as usize]
+    }
+}
+
+fn iterations() -> usize {
+    std::env::var("RHODIBOT_FUZZ_ITERATIONS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_ITERATIONS)
+}
+
+/// A code point from a class chosen to hit escaping edge cases
+fn random_char(rng: &mut Rng) -> char {
+    let code = match rng.next() % 8 {
+        // C0 controls, space and DEL
+        0 => rng.range(0x00, 0x20),
+        1 => rng.pick(&[0x22, 0x5c, 0x2f, 0x7f, 0x27, 0x3c, 0x3e, 0x26]),
+        // Printable ASCII
+        2 => rng.range(0x21, 0x7e),
+        // C1 controls and Latin-1
+        3 => rng.range(0x80, 0xff),
+        // Either side of the surrogate block, noncharacters, BOM, separators
+        4 => rng.pick(&[
+            0xd7ff, 0xe000, 0xfffd, 0xfffe, 0xffff, 0xfeff, 0x2028, 0x2029, 0x200e, 0x202e,
+        ]),
+        // Any other BMP scalar value
+        5 => rng.range(0x100, 0xffff),
+        // Astral planes, including the very first and last
+        6 => rng.pick(&[0x10000, 0x1f600, 0x10ffff, 0xe0001]),
+        _ => rng.range(0x10000, 0x10ffff),
+    } as u32;
+    // Surrogates are not chars; shift them out of the block
+    char::from_u32(code).unwrap_or('\u{e000}')
+}
+
+fn random_string(rng: &mut Rng) -> String {
+    let len = rng.range(0, 24);
+    (0..len).map(|_| random_char(rng)).collect()
+}
+
+/// Decode the contents of a JSON string (without the quotes) per RFC 8259
+///
+/// Deliberately strict: raw control characters, unknown escapes, short
+/// `\u` escapes and unpaired surrogates are all errors.
+fn decode_json_string(s: &str) -> Result<String, String> {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    let hex4 = |chars: &mut std::str::Chars| -> Result<u32, String> {
+        let digits: String = chars.by_ref().take(4).collect();
+        if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("bad \\u escape '{}'", digits));
+        }
+        Ok(u32::from_str_radix(&digits, 16).unwrap())
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Err("unescaped quote".to_string()),
+            c if (c as u32) < 0x20 => return Err(format!("raw control U+{:04X}", c as u32)),
+            '\\' => match chars.next() {
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                Some('/') => out.push('/'),
+                Some('b') => out.push('\u{8}'),
+                Some('f') => out.push('\u{c}'),
+                Some('n') => out.push('\n'),
+                Some('r') => out.push('\r'),
+                Some('t') => out.push('\t'),
+                Some('u') => {
+                    let unit = hex4(&mut chars)?;
+                    let code = match unit {
+                        0xd800..=0xdbff => {
+                            if chars.next() != Some('\\') || chars.next() != Some('u') {
+                                return Err("high surrogate without low surrogate".to_string());
+                            }
+                            let low = hex4(&mut chars)?;
+                            if !(0xdc00..=0xdfff).contains(&low) {
+                                return Err("high surrogate without low surrogate".to_string());
+                            }
+                            0x10000 + ((unit - 0xd800) << 10) + (low - 0xdc00)
+                        }
+                        0xdc00..=0xdfff => return Err("lone low surrogate".to_string()),
+                        unit => unit,
+                    };
+                    out.push(char::from_u32(code).ok_or("invalid code point")?);
+                }
+                other => return Err(format!("bad escape {:?}", other)),
+            },
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+#[test]
+fn fuzz_json_escape_round_trips() {
+    let (mut rng, seed) = Rng::from_env();
+    for _ in 0..iterations() {
+        let input = random_string(&mut rng);
+        let escaped = json_escape(&input);
+        match decode_json_string(&escaped) {
+            Ok(decoded) => assert_eq!(
+                decoded, input,
+                "seed {}: {:?} escaped to {:?}",
+                seed, input, escaped
+            ),
+            Err(e) => panic!(
+                "seed {}: {:?} escaped to invalid JSON {:?}: {}",
+                seed, input, escaped, e
+            ),
+        }
+    }
+}
+
+#[test]
+fn fuzz_json_escape_leaves_no_raw_controls() {
+    let (mut rng, seed) = Rng::from_env();
+    for _ in 0..iterations() {
+        let input = random_string(&mut rng);
+        let escaped = json_escape(&input);
+        assert!(
+            !escaped.chars().any(|c| c.is_control()),
+            "seed {}: control character left raw in {:?}",
+            seed,
+            escaped
+        );
+    }
+}
+
+#[test]
+fn fuzz_json_escape_ascii_round_trips() {
+    let (mut rng, seed) = Rng::from_env();
+    for _ in 0..iterations() {
+        let input = random_string(&mut rng);
+        let escaped = json::escape_ascii(&input);
+        assert!(escaped.is_ascii(), "seed {}: non-ASCII output {:?}", seed, escaped);
+        assert_eq!(
+            decode_json_string(&escaped).as_deref(),
+            Ok(input.as_str()),
+            "seed {}: {:?} escaped to {:?}",
+            seed,
+            input,
+            escaped
+        );
+    }
+}
+
+#[test]
+fn fuzz_json_escape_hides_no_formatting_characters() {
+    let (mut rng, seed) = Rng::from_env();
+    let hidden = |c: char| {
+        matches!(c, '\u{7f}'..='\u{9f}' | '\u{2028}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+            || matches!(c, '\u{61c}' | '\u{200e}' | '\u{200f}' | '\u{feff}')
+    };
+    for _ in 0..iterations() {
+        let escaped = json::escape(&random_string(&mut rng));
+        assert!(
+            !escaped.chars().any(hidden),
+            "seed {}: formatting character left raw in {:?}",
+            seed,
+            escaped
+        );
+    }
+}
+
+#[test]
+fn test_decoder_rejects_invalid_json() {
+    assert!(decode_json_string("\\ud83d").is_err());
+    assert!(decode_json_string("\\ude00").is_err());
+    assert!(decode_json_string("\\u12").is_err());
+    assert!(decode_json_string("\\x41").is_err());
+    assert!(decode_json_string("a\nb").is_err());
+    assert_eq!(decode_json_string("\\ud83d\\ude00").unwrap(), "😀");
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn system_time(secs: i64) -> SystemTime {
+    let offset = Duration::from_secs(secs.unsigned_abs());
+    if secs < 0 {
+        SystemTime::UNIX_EPOCH - offset
+    } else {
+        SystemTime::UNIX_EPOCH + offset
+    }
+}
+
+/// Parse `YYYY-MM-DDTHH:MM:SSZ` back into seconds, checking every field
+fn parse_timestamp(s: &str) -> Result<i64, String> {
+    let body = s.strip_suffix('Z').ok_or("missing Z")?;
+    let (date, time) = body.split_once('T').ok_or("missing T")?;
+    let (year, rest) = date.split_at(date.len() - 6);
+    let field = |s: &str| s.parse::<i64>().map_err(|_| format!("bad field '{}'", s));
+    let year = field(year)?;
+    let (month, day) = (field(&rest[1..3])?, field(&rest[4..6])?);
+    let parts: Vec<&str> = time.split(':').collect();
+    if parts.len() != 3 || parts.iter().any(|p| p.len() != 2) {
+        return Err("bad time".to_string());
+    }
+    let (hour, minute, second) = (field(parts[0])?, field(parts[1])?, field(parts[2])?);
+
+    let month_len = [31, if is_leap(year) { 29 } else { 28 }, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    if !(1..=12).contains(&month) || day < 1 || day > month_len[month as usize - 1] {
+        return Err(format!("invalid date {}-{}-{}", year, month, day));
+    }
+    if hour > 23 || minute > 59 || second > 59 {
+        return Err("invalid time".to_string());
+    }
+    Ok(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+fn assert_timestamp_round_trips(secs: i64, seed: u64) {
+    let formatted = format_timestamp(system_time(secs));
+    match parse_timestamp(&formatted) {
+        Ok(parsed) => assert_eq!(parsed, secs, "seed {}: {} formatted as {}", seed, secs, formatted),
+        Err(e) => panic!("seed {}: {} formatted as {}: {}", seed, secs, formatted, e),
+    }
+}
+
+#[test]
+fn fuzz_format_timestamp_round_trips() {
+    let (mut rng, seed) = Rng::from_env();
+    // 0001-01-01 to 9999-12-31
+    let (min, max) = (-62_135_596_800, 253_402_300_799);
+    for _ in 0..iterations() {
+        assert_timestamp_round_trips(rng.range(min, max), seed);
+    }
+}
+
+#[test]
+fn fuzz_format_timestamp_century_boundaries() {
+    let (mut rng, seed) = Rng::from_env();
+    for century in (100..=9900).step_by(100) {
+        // Around New Year and the end of February of every century year
+        for (month, day) in [(1, 1), (3, 1)] {
+            let boundary = days_from_civil(century, month, day) * 86400;
+            for delta in [-86400, -1, 0, 1, 86399] {
+                assert_timestamp_round_trips(boundary + delta, seed);
+            }
+            assert_timestamp_round_trips(boundary + rng.range(-86400 * 2, 86400 * 2), seed);
+        }
+        let feb_end = format_timestamp(system_time(days_from_civil(century, 3, 1) * 86400 - 1));
+        let expected_day = if is_leap(century) { "29" } else { "28" };
+        assert_eq!(
+            &feb_end[feb_end.len() - 12..feb_end.len() - 10],
+            expected_day,
+            "seed {}: last second of February {} formatted as {}",
+            seed,
+            century,
+            feb_end
+        );
+    }
+}
+
+#[test]
+fn fuzz_format_timestamp_is_monotonic() {
+    let (mut rng, seed) = Rng::from_env();
+    for _ in 0..iterations() {
+        let a = rng.range(-62_135_596_800, 253_402_300_798);
+        let b = a + rng.range(1, 86400 * 400);
+        if b > 253_402_300_799 {
+            continue;
+        }
+        let (fa, fb) = (format_timestamp(system_time(a)), format_timestamp(system_time(b)));
+        // Four-digit years sort lexicographically
+        assert!(fa < fb, "seed {}: {} ({}) !< {} ({})", seed, fa, a, fb, b);
+    }
+}
@@ -1,6 +1,6 @@
 //! Integration tests for Rhodibot
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Get path to the rhodibot binary
@@ -99,6 +99,52 @@ fn test_json_output() {
     assert!(stdout.contains("\"checks\""));
 }
 
+#[test]
+fn test_quiet_json_summary() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", ".", "--format", "json", "--quiet"])
+        .output()
+        .expect("Failed to execute rhodibot");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.starts_with("{\"repository\":"));
+    assert!(stdout.contains("\"critical_warnings\":"));
+    assert!(!stdout.contains("\"checks\""));
+}
+
+#[test]
+fn test_porcelain_output() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", ".", "--porcelain"])
+        .output()
+        .expect("Failed to execute rhodibot");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("check\tRSR-DOC-001\t"), "{}", stdout);
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields[0] {
+            "check" => assert!(matches!(fields[2], "pass" | "fail" | "waived"), "{}", line),
+            "skipped" | "warning" | "level" => {}
+            _ => panic!("unexpected record: {}", line),
+        }
+    }
+    assert!(stdout.lines().last().unwrap().starts_with("level\t"));
+
+    for args in [&["check", ".", "--porcelain", "-f", "json"][..], &["badge", ".", "--porcelain"]] {
+        let output = Command::new(rhodibot_binary())
+            .args(args)
+            .output()
+            .expect("Failed to execute rhodibot");
+        assert_eq!(output.status.code(), Some(4));
+    }
+}
+
 #[test]
 fn test_quiet_mode() {
     let _ = Command::new("cargo").args(["build"]).output();
@@ -112,6 +158,171 @@ fn test_quiet_mode() {
     assert!(stdout.contains("PASS") || stdout.contains("FAIL"));
 }
 
+#[test]
+fn test_summary_mode() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_summary");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("README.md"), "# Summary\n").unwrap();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--summary", "--no-emoji"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\n  [FAIL] Documentation     1/9\n"));
+    assert!(stdout.contains("\n  [FAIL] Source Structure  0/2\n"));
+    assert!(!stdout.contains("RSR-DOC-001"), "summary lists no individual checks");
+    assert!(stdout.contains("Bronze-level RSR compliance: NOT MET"));
+    assert_eq!(output.status.code(), Some(1));
+
+    // The verdict names the configured level and says why it is not met
+    std::fs::write(dir.join(".rhodibot.toml"), "[policy]\nfail_on_level = \"silver\"\n").unwrap();
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--summary", "--no-emoji"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Silver-level RSR compliance: NOT MET\n"));
+    assert!(stdout.contains("\n   - RSR-DOC-002 "));
+    assert!(!stdout.contains("Bronze-level"));
+    assert_eq!(output.status.code(), Some(1));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_category_subtotals() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_subtotals");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("README.md"), "# Subtotals\n").unwrap();
+    std::fs::write(
+        dir.join(".rhodibot.toml"),
+        "[rules.adr]\npath = \"docs/adr/\"\ncategory = \"Documentation\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--no-emoji"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("# Documentation").count(), 1);
+    assert!(stdout.contains("\n# Documentation: 1/10\n"));
+    // The organization rule is listed with the rest of its category
+    let rule = stdout.find("custom/adr").unwrap();
+    assert!(rule < stdout.find("# Well-Known: 0/6").unwrap());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_style_flags() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    // Not a terminal: emoji but no color by default
+    let output = Command::new(rhodibot_binary())
+        .args(["check", ".", "-v"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains('📋'));
+    assert!(!stdout.contains('\x1b'));
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", ".", "-v", "--no-emoji"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.is_ascii(), "--no-emoji output must be ASCII");
+    assert!(stdout.contains("\n# Documentation: "));
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", ".", "--no-emoji", "--color", "always"])
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("\x1b[1mDocumentation\x1b[0m"));
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", ".", "--color", "sometimes"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+}
+
+#[test]
+fn test_accessible_output() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", ".", "--accessible", "--color", "always"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.is_ascii(), "--accessible output must be plain text");
+    assert!(!stdout.contains("==="));
+    assert!(stdout.starts_with("Rhodibot - RSR Compliance Report\n"));
+    assert!(stdout.contains("\nDocumentation: "));
+    // One line per check, fields in a fixed order
+    let line = stdout
+        .lines()
+        .find(|line| line.contains(", RSR-DOC-001, "))
+        .expect("RSR-DOC-001 line");
+    assert!(line.ends_with(", Bronze, Documentation, README.md"), "{}", line);
+    assert!(stdout.contains("Bronze-level RSR compliance: "));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_grouped_warnings() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_grouped_warnings");
+    let _ = std::fs::remove_dir_all(&dir);
+    let repo = dir.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    let files = ["README.md", "LICENSE.txt", "SECURITY.md", "CONTRIBUTING.md", "CHANGELOG.md"];
+    for file in files {
+        std::fs::write(dir.join(file), "outside\n").unwrap();
+        std::os::unix::fs::symlink(dir.join(file), repo.join(file)).unwrap();
+    }
+
+    let run = |args: &[&str]| {
+        let output = Command::new(rhodibot_binary())
+            .args(["check", "--no-emoji"])
+            .args(args)
+            .arg(&repo)
+            .output()
+            .expect("Failed to execute rhodibot");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let human = run(&[]);
+    assert!(
+        human.contains("Symlink '*' points outside repository to '*' (5 warnings)\n"),
+        "{}",
+        human
+    );
+    assert!(human.contains("      ... and 2 more (see --verbose or --format json)\n"));
+    assert!(human.contains("/LICENSE.txt' (LICENSE.txt)\n"), "{}", human);
+    // Grouped out of the warnings section, though the verdict's reasons name it
+    assert!(!human.contains("      Symlink 'CHANGELOG.md'"));
+    assert!(human.contains("   - critical security warning: Symlink 'CHANGELOG.md'"));
+    assert!(run(&["--verbose"]).contains("Symlink 'CHANGELOG.md'"));
+    let json = run(&["-f", "json"]);
+    assert_eq!(json.matches("points outside repository").count(), 5);
+    assert!(json.contains("\"path\": \"SECURITY.md\"\n"), "{}", json);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 #[test]
 fn test_invalid_path() {
     let _ = Command::new("cargo").args(["build"]).output();
@@ -137,3 +348,1210 @@ fn test_invalid_format() {
     assert!(!output.status.success());
     assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
 }
+
+#[test]
+fn test_output_file() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let out_dir = std::env::temp_dir().join("rhodibot_it_output");
+    let _ = std::fs::remove_dir_all(&out_dir);
+    let report_path = out_dir.join("report.json");
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", ".", "--format", "json", "-o"])
+        .arg(&report_path)
+        .output()
+        .expect("Failed to execute rhodibot");
+
+    // Nothing goes to stdout when writing to a file
+    assert!(output.stdout.is_empty());
+    let written = std::fs::read_to_string(&report_path).expect("report not written");
+    assert!(written.contains("\"tool\": \"rhodibot\""));
+
+    let _ = std::fs::remove_dir_all(&out_dir);
+}
+
+#[test]
+fn test_policy_config() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_policy");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // Invalid configuration is reported as an argument error
+    std::fs::write(dir.join(".rhodibot.toml"), "[policy]\nfail_on_level = \"tin\"\n").unwrap();
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--quiet"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+    assert!(String::from_utf8_lossy(&output.stderr).contains("line 2"));
+
+    // A valid policy is applied: an empty repository fails compliance
+    std::fs::write(dir.join(".rhodibot.toml"), "[policy]\nfail_below_percentage = 50\n").unwrap();
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--quiet"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(1)); // COMPLIANCE_FAILED
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_environment_variables() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_env");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("ci.toml");
+    std::fs::write(&config, "[policy]\nfail_on_level = \"tin\"\n").unwrap();
+
+    // Variables stand in for flags, and flags win over variables
+    let output = Command::new(rhodibot_binary())
+        .arg(&dir)
+        .env("RHODIBOT_FORMAT", "json")
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with('{'));
+    let output = Command::new(rhodibot_binary())
+        .args(["--format", "tap"])
+        .arg(&dir)
+        .env("RHODIBOT_FORMAT", "json")
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("TAP version"));
+
+    // RHODIBOT_CONFIG selects the config file
+    let output = Command::new(rhodibot_binary())
+        .arg(&dir)
+        .env("RHODIBOT_CONFIG", &config)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+    assert!(String::from_utf8_lossy(&output.stderr).contains("line 2"));
+
+    // Invalid values name the variable
+    let output = Command::new(rhodibot_binary())
+        .arg(&dir)
+        .env("RHODIBOT_LEVEL", "tin")
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("RHODIBOT_LEVEL"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_signed_report() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_sign");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let key = dir.join("secret");
+    std::fs::write(&key, "ci-shared-secret\n").unwrap();
+    let report = dir.join("report.json");
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", ".", "-f", "json", "-o"])
+        .arg(&report)
+        .arg("--sign")
+        .arg(&key)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_ne!(output.status.code(), Some(4)); // not INVALID_ARGS
+    let signature = std::fs::read_to_string(dir.join("report.json.sig")).unwrap();
+    assert!(signature.contains("algorithm: hmac-sha256\n"));
+
+    let verify = |key: &Path| {
+        Command::new(rhodibot_binary())
+            .arg("verify-report")
+            .arg(&report)
+            .arg("--key")
+            .arg(key)
+            .output()
+            .expect("Failed to execute rhodibot")
+    };
+    let output = verify(&key);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("Signature OK: "));
+
+    // A different key or an edited report is rejected
+    let wrong = dir.join("wrong");
+    std::fs::write(&wrong, "guess").unwrap();
+    assert_eq!(verify(&wrong).status.code(), Some(1));
+    let mut edited = std::fs::read_to_string(&report).unwrap();
+    edited = edited.replace("\"passed\": false", "\"passed\": true");
+    std::fs::write(&report, edited + " ").unwrap();
+    let output = verify(&key);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("modified"));
+
+    // Signing needs a file to sign
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "."])
+        .arg("--sign")
+        .arg(&key)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_disabled_checks() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_disable");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join(".rhodibot.toml"),
+        "[checks]\ndisable = [\"RSR-BLD-002\"]\n",
+    )
+    .unwrap();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--format", "json"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"id\": \"RSR-DOC-001\""));
+    assert!(!stdout.contains("\"id\": \"RSR-BLD-002\""));
+    assert!(stdout.contains("\"skipped_checks\": [\"RSR-BLD-002\"]"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_standard_version() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_standard_version");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(".rhodibot.toml"), "standard_version = \"1.0\"\n").unwrap();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--format", "json"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"standard_version\": \"1.0\""));
+
+    // The flag overrides the config, and unknown versions are rejected
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--standard-version=2.0"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'2.0'"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_standard_command() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["standard", "show", "bronze"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("RSR Standard 1.0 - Bronze requirements"));
+    assert!(stdout.contains("RSR-DOC-001"));
+
+    let output = Command::new(rhodibot_binary())
+        .args(["standard", "list"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("RSR-BLD-004  Silver"));
+
+    let output = Command::new(rhodibot_binary())
+        .args(["standard", "show"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+
+    let output = Command::new(rhodibot_binary())
+        .args(["standard", "export", "--format", "json"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"standard_version\": \"1.0\""));
+    assert!(stdout.contains("\"remediation\": "));
+}
+
+#[test]
+fn test_check_selection_flags() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_selection");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "-f", "json", "--only-category", "documentation"])
+        .args(["--skip", "RSR-DOC-006,RSR-DOC-007"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"id\": \"RSR-DOC-001\""));
+    assert!(!stdout.contains("\"id\": \"RSR-DOC-006\""));
+    assert!(!stdout.contains("\"id\": \"RSR-WKN-001\""));
+    assert!(stdout.contains("\"total\": 7,"));
+    assert!(stdout.contains("\"RSR-DOC-006\", \"RSR-DOC-007\", \"RSR-WKN-001\""));
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--skip=RSR-BLD-002"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Skipped: RSR-BLD-002"));
+
+    for bad in [["--skip", "RSR-NOPE-001"], ["--only-category", "Tests"]] {
+        let output = Command::new(rhodibot_binary())
+            .args(bad)
+            .arg(&dir)
+            .output()
+            .expect("Failed to execute rhodibot");
+        assert_eq!(output.status.code(), Some(4));
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_suppressions_file() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_suppressions");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join(".rhodibot")).unwrap();
+    std::fs::write(
+        dir.join(".rhodibot/suppressions.toml"),
+        "[RSR-BLD-002]\nreason = \"Guix instead of Nix\"\n\n\
+         [RSR-BLD-003]\nreason = \"Old waiver\"\nexpires = \"2001-01-01\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--format", "json"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"reason\": \"Guix instead of Nix\", \"expires\": null, \"expired\": false"));
+    assert!(stdout.contains("\"expires\": \"2001-01-01\", \"expired\": true"));
+    assert!(stdout.contains("\"item\": \"flake.nix\",\n      \"passed\": true,\n      \"waived\": true"));
+    assert!(stdout.contains("\"item\": \".gitlab-ci.yml\",\n      \"passed\": false"));
+
+    // A waiver without a reason is rejected
+    std::fs::write(dir.join(".rhodibot/suppressions.toml"), "[RSR-BLD-002]\n").unwrap();
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--quiet"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_waived_compliance_exit_code() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_waived_exit");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join(".rhodibot")).unwrap();
+    std::fs::write(dir.join("justfile"), "build:\n\tcargo build\n").unwrap();
+    std::fs::write(dir.join(".rhodibot/suppressions.toml"), "[RSR-BLD-002]\nreason = \"Guix\"\n")
+        .unwrap();
+
+    let run = |extra: &[&str]| {
+        let output = Command::new(rhodibot_binary())
+            .args(["check", "--only-category", "Build System", "--skip", "RSR-BLD-003"])
+            .args(extra)
+            .arg(&dir)
+            .output()
+            .expect("Failed to execute rhodibot");
+        (output.status.code(), String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let (code, json) = run(&["-f", "json"]);
+    assert_eq!(code, Some(0));
+    assert!(json.contains("\"bronze_compliant\": true,\n  \"waived_compliance\": true,"));
+    assert_eq!(run(&["--distinguish-waived"]).0, Some(5));
+
+    std::fs::write(dir.join(".rhodibot.toml"), "[policy]\ndistinguish_waived = true\n").unwrap();
+    assert_eq!(run(&["--quiet"]).0, Some(5));
+    std::fs::write(dir.join("flake.nix"), "{ }\n").unwrap();
+    assert_eq!(run(&["--quiet"]).0, Some(0));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_remote_check() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_remote");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("README.md"), "# Remote\n").unwrap();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.org"])
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    };
+    if !git(&["init", "--quiet"]) {
+        // git is not available; remote checks cannot be exercised
+        return;
+    }
+    assert!(git(&["add", "README.md"]));
+    assert!(git(&["commit", "--quiet", "-m", "Initial commit"]));
+
+    let url = format!("file://{}", dir.display());
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--format", "json", "--remote", &url])
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&format!("\"repository\": \"{}\"", url)));
+    assert!(stdout.contains("\"item\": \"README.md\",\n      \"passed\": true"));
+
+    // Unreachable remotes are reported as an invalid path
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--quiet", "--remote", "file:///nonexistent/rhodibot"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(3)); // INVALID_PATH
+
+    // A path and --remote are mutually exclusive
+    let output = Command::new(rhodibot_binary())
+        .args(["check", ".", "--remote", &url])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_batch_command() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_batch");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("empty")).unwrap();
+    let repo_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    std::fs::write(
+        dir.join("repos.txt"),
+        format!("# fleet\n{}\nempty\n", repo_root.display()),
+    )
+    .unwrap();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["batch", "--format", "json"])
+        .arg(dir.join("repos.txt"))
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"repositories\": 2"));
+    assert!(stdout.contains("\"source\": \"empty\""));
+    // The empty repository fails, so the batch fails
+    assert_eq!(output.status.code(), Some(1)); // COMPLIANCE_FAILED
+
+    // Missing repositories make the whole batch an invalid-path failure
+    std::fs::write(dir.join("repos.txt"), "empty\nmissing\n").unwrap();
+    let output = Command::new(rhodibot_binary())
+        .arg("batch")
+        .arg(dir.join("repos.txt"))
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Repositories: 2  Passed: 0  Failed: 1  Errors: 1"));
+    assert_eq!(output.status.code(), Some(3)); // INVALID_PATH
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_dashboard_command() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_dashboard");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("empty")).unwrap();
+    std::fs::write(dir.join("repos.txt"), "empty\nmissing\n").unwrap();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["dashboard", "--quiet", "-o"])
+        .arg(dir.join("site"))
+        .arg(dir.join("repos.txt"))
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(output.status.success());
+
+    let index = std::fs::read_to_string(dir.join("site/index.html")).unwrap();
+    assert!(index.contains("href=\"repo-1-empty.html\""));
+    assert!(index.contains("href=\"repo-2-missing.html\""));
+    let page = std::fs::read_to_string(dir.join("site/repo-1-empty.html")).unwrap();
+    assert!(page.contains("RSR-DOC-001"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_ci_extras() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_ci");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // Detected from the environment
+    let output = Command::new(rhodibot_binary())
+        .arg(&dir)
+        .env("TEAMCITY_VERSION", "2024.03")
+        .output()
+        .expect("Failed to execute rhodibot");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("##teamcity[testSuiteStarted name='rhodibot']"));
+    assert!(stdout.contains("##teamcity[testFailed name='RSR-DOC-001 README.md'"));
+
+    // Overridden
+    let output = Command::new(rhodibot_binary())
+        .args(["--ci", "none"])
+        .arg(&dir)
+        .env("TEAMCITY_VERSION", "2024.03")
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("##teamcity"));
+
+    // Never mixed into a machine-readable report on stdout
+    let output = Command::new(rhodibot_binary())
+        .args(["--ci", "teamcity", "--format", "json"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("##teamcity"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Start `rhodibot serve` on a free port with `args`, returning the child,
+/// its address and its startup line
+fn spawn_serve(args: &[&str]) -> (std::process::Child, String, String) {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let mut child = Command::new(rhodibot_binary())
+        .args(["serve", "--listen", "127.0.0.1:0"])
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute rhodibot");
+    let mut banner = String::new();
+    BufReader::new(child.stderr.take().unwrap())
+        .read_line(&mut banner)
+        .unwrap();
+    let addr = banner.trim().strip_prefix("Listening on http://").unwrap().to_string();
+    let mut started = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut started)
+        .unwrap();
+    (child, addr, started)
+}
+
+/// Send `raw` to a server at `addr` and read the whole response
+fn http_request(addr: &str, raw: &[u8]) -> String {
+    use std::io::{Read, Write};
+
+    let mut stream = std::net::TcpStream::connect(addr).unwrap();
+    stream.write_all(raw).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn test_serve_command() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_serve");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("repo")).unwrap();
+    std::fs::write(dir.join("repo/README.md"), "# Served\n").unwrap();
+
+    let (mut child, addr, started) = spawn_serve(&[]);
+    assert!(started.starts_with("{\"tool\":\"rhodibot\",\"version\":"));
+    let event = format!(",\"event\":\"listening\",\"address\":\"http://{}\"}}\n", addr);
+    assert!(started.ends_with(&event));
+
+    let request = |raw: &[u8]| http_request(&addr, raw);
+
+    let health = request(b"GET /health HTTP/1.1\r\n\r\n");
+    assert!(health.starts_with("HTTP/1.1 200 OK"));
+
+    let path = dir.join("repo").display().to_string().replace(' ', "%20");
+    let report = request(format!("GET /check?path={} HTTP/1.1\r\n\r\n", path).as_bytes());
+    assert!(report.starts_with("HTTP/1.1 200 OK"));
+    assert!(report.contains("\"item\": \"README.md\",\n      \"passed\": true"));
+
+    let missing = request(b"GET /check?path=/no/such/dir HTTP/1.1\r\n\r\n");
+    assert!(missing.starts_with("HTTP/1.1 404"));
+
+    let tarred = Command::new("tar")
+        .args(["-cf", "repo.tar", "-C", "repo", "."])
+        .current_dir(&dir)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+    if tarred {
+        let archive = std::fs::read(dir.join("repo.tar")).unwrap();
+        let mut raw = format!(
+            "POST /check?name=demo HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            archive.len()
+        )
+        .into_bytes();
+        raw.extend_from_slice(&archive);
+        let uploaded = request(&raw);
+        assert!(uploaded.starts_with("HTTP/1.1 200 OK"));
+        assert!(uploaded.contains("\"repository\": \"demo\""));
+        assert!(uploaded.contains("\"item\": \"README.md\",\n      \"passed\": true"));
+
+        // Links could lead the checks, or later members, out of the checkout
+        std::os::unix::fs::symlink("/etc/passwd", dir.join("repo/LICENSE")).unwrap();
+        Command::new("tar")
+            .args(["-cf", "repo.tar", "-C", "repo", "."])
+            .current_dir(&dir)
+            .status()
+            .unwrap();
+        let archive = std::fs::read(dir.join("repo.tar")).unwrap();
+        let mut raw =
+            format!("POST /check HTTP/1.1\r\nContent-Length: {}\r\n\r\n", archive.len())
+                .into_bytes();
+        raw.extend_from_slice(&archive);
+        let linked = request(&raw);
+        assert!(linked.starts_with("HTTP/1.1 400"), "{}", linked);
+        assert!(linked.contains("archive contains a link"));
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+
+    let root = dir.join("repo").display().to_string();
+    let (mut child, addr, _) = spawn_serve(&["--allow-root", &root]);
+    let check = |path: &str| {
+        let path = path.replace(' ', "%20");
+        http_request(&addr, format!("GET /check?path={} HTTP/1.1\r\n\r\n", path).as_bytes())
+    };
+    let inside = check(&root);
+    assert!(inside.starts_with("HTTP/1.1 200 OK"), "{}", inside);
+    let outside = check(&format!("{}/..", root));
+    assert!(outside.starts_with("HTTP/1.1 403 Forbidden"), "{}", outside);
+    assert!(outside.contains("is not inside"));
+
+    let _ = child.kill();
+    let _ = child.wait();
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_bench_command() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["bench", "--iterations", "3", "-f", "json", "."])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"iterations\": 3,"));
+    assert!(stdout.contains("\"p95_ms\": "));
+
+    let output = Command::new(rhodibot_binary())
+        .args(["bench", "--iterations", "0"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+}
+
+#[test]
+fn test_reproducible_output() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let repo = std::env::current_dir().unwrap();
+    let run = |args: &[&str]| {
+        let output = Command::new(rhodibot_binary())
+            .args(args)
+            .arg("--reproducible")
+            .arg(&repo)
+            .output()
+            .expect("Failed to execute rhodibot");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let json = run(&["check", "-f", "json"]);
+    assert_eq!(json, run(&["check", "-f", "json"]));
+    assert!(json.contains("\"repository\": \".\",\n"));
+    assert!(!json.contains("verified_at"));
+    assert!(!json.contains("duration_ms"));
+    assert!(!json.contains(&repo.display().to_string()));
+
+    let verbose = run(&["check", "-v", "--no-emoji"]);
+    assert_eq!(verbose, run(&["check", "-v", "--no-emoji"]));
+    assert!(verbose.contains("Repository: .\n"));
+    assert!(!verbose.contains("Verified:") && !verbose.contains("Timing:"));
+
+    let junit = run(&["check", "-f", "junit"]);
+    assert!(!junit.contains("timestamp="));
+}
+
+#[test]
+fn test_relative_paths() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_relative_paths");
+    let _ = std::fs::remove_dir_all(&dir);
+    let repo = dir.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    #[cfg(unix)]
+    {
+        std::fs::write(dir.join("README.md"), "outside\n").unwrap();
+        std::os::unix::fs::symlink(dir.join("README.md"), repo.join("README.md")).unwrap();
+    }
+    let run = |args: &[&str]| {
+        let output = Command::new(rhodibot_binary())
+            .arg("check")
+            .args(args)
+            .arg(&repo)
+            .output()
+            .expect("Failed to execute rhodibot");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let absolute = run(&["-f", "json"]);
+    assert!(absolute.contains(&format!("\"repository\": \"{}\",", repo.display())));
+    let relative = run(&["-f", "json", "--relative-paths"]);
+    assert!(relative.contains("\"repository\": \".\",\n"), "{}", relative);
+    assert!(relative.contains("\"verified_at\""));
+
+    let verbose = run(&["-v", "--no-emoji", "--relative-paths"]);
+    assert!(verbose.contains("Repository: .\n"));
+    #[cfg(unix)]
+    {
+        assert!(verbose.contains("      Path: README.md\n"), "{}", verbose);
+        assert!(!run(&["-v"]).contains("      Path: README.md\n"));
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_metadata_in_reports() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let repo = std::env::current_dir().unwrap();
+    let run = |format: &str, metadata: &[&str]| {
+        let mut command = Command::new(rhodibot_binary());
+        command.args(["check", "-f", format]);
+        for entry in metadata {
+            command.args(["--metadata", entry]);
+        }
+        let output = command.arg(&repo).output().expect("Failed to execute rhodibot");
+        (output.status.code(), String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let metadata = ["commit=4f2a9c1", "ci_run_url=https://ci.example/runs/7", "pipeline=nightly"];
+    let (_, json) = run("json", &metadata);
+    assert!(json.contains(
+        "  \"metadata\": {\"commit\": \"4f2a9c1\", \"ci_run_url\": \"https://ci.example/runs/7\", \
+         \"pipeline\": \"nightly\"},\n"
+    ));
+    let (_, sarif) = run("sarif", &metadata[..1]);
+    assert!(sarif.contains("      \"properties\": {\"commit\": \"4f2a9c1\"},\n"));
+    assert!(!run("json", &[]).1.contains("\"metadata\""));
+
+    let (code, _) = run("json", &["=oops"]);
+    assert_eq!(code, Some(4));
+}
+
+#[test]
+fn test_vcs_metadata_from_git_dir() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_vcs_metadata");
+    let _ = std::fs::remove_dir_all(&dir);
+    let sha = "0123456789abcdef0123456789abcdef01234567";
+    std::fs::create_dir_all(dir.join(".git/refs/heads")).unwrap();
+    std::fs::write(dir.join(".git/HEAD"), "ref: refs/heads/trunk\n").unwrap();
+    std::fs::write(dir.join(".git/refs/heads/trunk"), format!("{}\n", sha)).unwrap();
+    std::fs::write(
+        dir.join(".git/config"),
+        "[remote \"origin\"]\n\turl = https://token@example.org/demo.git\n",
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let output = Command::new(rhodibot_binary())
+            .args(args)
+            .arg(&dir)
+            .output()
+            .expect("Failed to execute rhodibot");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let json = run(&["check", "-f", "json"]);
+    assert!(json.contains(&format!(
+        "\"metadata\": {{\"commit\": \"{}\", \"branch\": \"trunk\", \
+         \"remote\": \"https://example.org/demo.git\"}}",
+        sha
+    )));
+    let conformity = run(&["conformity"]);
+    assert!(conformity.contains(&format!("**Commit**: {}\n**Branch**: trunk\n", sha)));
+    let overridden = run(&["check", "-f", "sarif", "--metadata", "branch=release"]);
+    assert!(overridden.contains("\"branch\": \"release\""));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_badge_update_readme() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_update_readme");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let readme = dir.join("README.md");
+    std::fs::write(&readme, "# Demo\n\nSome text.\n").unwrap();
+
+    let run = || {
+        Command::new(rhodibot_binary())
+            .args(["badge", "--update-readme"])
+            .arg(&dir)
+            .output()
+            .expect("Failed to execute rhodibot")
+    };
+    let output = run();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).ends_with("README.md updated\n"));
+    let first = std::fs::read_to_string(&readme).unwrap();
+    assert!(first.starts_with("# Demo\n\n<!-- rhodibot:start -->\n[![Rhodium Standard "));
+    assert!(first.contains("<!-- rhodibot:end -->\n\nSome text.\n"));
+
+    let output = run();
+    assert!(String::from_utf8_lossy(&output.stdout).ends_with("README.md is up to date\n"));
+    assert_eq!(std::fs::read_to_string(&readme).unwrap(), first);
+
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--update-readme"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_conformity_write_and_drift() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_conformity_write");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("README.md"), "# Demo\n").unwrap();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["conformity", "--write"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(output.status.success());
+    let doc = std::fs::read_to_string(dir.join("CONFORMITY.md")).unwrap();
+    assert!(doc.contains("| RSR-DOC-001 | README.md | Pass |"));
+    assert!(doc.contains("| RSR-CNF-001 | CONFORMITY.md up to date | Pass |"));
+
+    let check = || {
+        let output = Command::new(rhodibot_binary())
+            .args(["check", "-f", "json"])
+            .arg(&dir)
+            .output()
+            .expect("Failed to execute rhodibot");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    let in_sync = "\"item\": \"CONFORMITY.md up to date\",\n      \"passed\": true";
+    assert!(check().contains(in_sync));
+
+    std::fs::write(dir.join("LICENSE.txt"), "MIT\n").unwrap();
+    let drifted = check();
+    assert!(!drifted.contains(in_sync));
+    assert!(drifted.contains("RSR-DOC-002 is Pass but CONFORMITY.md says Fail"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_install_hook() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_install_hook");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let git_init = Command::new("git")
+        .args(["init", "--quiet"])
+        .current_dir(&dir)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+    if !git_init {
+        return; // git is not available
+    }
+
+    let install = |hook: &str| {
+        Command::new(rhodibot_binary())
+            .args(["install-hook", hook, "--level", "silver"])
+            .arg(&dir)
+            .output()
+            .expect("Failed to execute rhodibot")
+    };
+    let output = install("pre-push");
+    assert!(output.status.success());
+    let hook = dir.join(".git/hooks/pre-push");
+    let script = std::fs::read_to_string(&hook).unwrap();
+    assert!(script.contains("exec rhodibot check --quiet --fail-fast --level silver .\n"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&hook).unwrap().permissions().mode();
+        assert_eq!(mode & 0o111, 0o111);
+    }
+    assert!(install("pre-push").status.success(), "Should replace its own hook");
+
+    std::fs::write(dir.join(".git/hooks/pre-commit"), "#!/bin/sh\nmake lint\n").unwrap();
+    assert_eq!(install("pre-commit").status.code(), Some(3)); // INVALID_PATH
+    assert_eq!(install("post-merge").status.code(), Some(4)); // INVALID_ARGS
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_drift() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_drift");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("repo")).unwrap();
+    std::fs::write(dir.join("repo/README.md"), "# Drift\n").unwrap();
+    let state = dir.join("cache/state.json");
+
+    let drift = || {
+        let output = Command::new(rhodibot_binary())
+            .args(["drift", "--no-emoji", "--state"])
+            .arg(&state)
+            .arg(dir.join("repo"))
+            .output()
+            .expect("Failed to execute rhodibot");
+        (output.status.code(), String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let (code, stdout) = drift();
+    assert_eq!(code, Some(0));
+    assert!(stdout.starts_with("Recorded compliance state in "));
+    assert!(std::fs::read_to_string(&state).unwrap().contains("\"RSR-WKN-001\""));
+    let (code, stdout) = drift();
+    assert_eq!(code, Some(0), "Known failures should not fail the run");
+    assert!(stdout.starts_with("No compliance changes since "));
+
+    std::fs::remove_file(dir.join("repo/README.md")).unwrap();
+    let (code, stdout) = drift();
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("  Newly failing:\n    [FAIL] RSR-DOC-001 README.md\n"));
+    assert_eq!(drift().0, Some(0), "A regression should be reported once");
+
+    std::fs::write(dir.join("repo/README.md"), "# Drift\n").unwrap();
+    let (code, stdout) = drift();
+    assert_eq!(code, Some(0));
+    assert!(stdout.contains("  Resolved:\n    [PASS] RSR-DOC-001 README.md\n"));
+
+    std::fs::remove_file(dir.join("repo/README.md")).unwrap();
+    let output = Command::new(rhodibot_binary())
+        .args(["drift", "-f", "json", "--state"])
+        .arg(&state)
+        .arg(dir.join("repo"))
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(1));
+    let json = String::from_utf8_lossy(&output.stdout);
+    assert!(json.contains("  \"changed\": true,\n  \"regression\": true,\n"));
+    assert!(json.contains(
+        "  \"newly_failing\": [\n    { \"id\": \"RSR-DOC-001\", \"category\": \"Documentation\", \
+         \"item\": \"README.md\" }\n  ],\n"
+    ));
+
+    std::fs::write(&state, "not json").unwrap();
+    assert_eq!(drift().0, Some(4)); // INVALID_ARGS
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_health_probe() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    // A no-op: neither the repository nor a broken environment matters
+    let output = Command::new(rhodibot_binary())
+        .args(["--health", "/no/such/dir"])
+        .env("RHODIBOT_LEVEL", "tin")
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("{\"tool\":\"rhodibot\",\"version\":\""));
+    assert!(stdout.ends_with(",\"status\":\"ok\"}\n"));
+}
+
+#[test]
+fn test_explain_exit() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let output = Command::new(rhodibot_binary())
+        .args(["--explain-exit", "5"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("5 WAIVED_COMPLIANCE\n\n"));
+    assert!(stdout.contains("distinguish_waived"));
+
+    let output = Command::new(rhodibot_binary())
+        .args(["--explain-exit", "42"])
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+}
+
+#[test]
+fn test_generate_action() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_generate_action");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let output = Command::new(rhodibot_binary())
+        .arg("generate-action")
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(output.status.success());
+    let action = std::fs::read_to_string(dir.join("action.yml")).unwrap();
+    assert!(action.contains("  using: 'composite'\n"));
+    assert!(action.contains("\n  level_achieved:\n"));
+
+    let elsewhere = dir.join("ci/action.yml");
+    let output = Command::new(rhodibot_binary())
+        .args(["generate-action", "--quiet", "-o"])
+        .arg(&elsewhere)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert!(output.status.success() && output.stdout.is_empty());
+    assert_eq!(std::fs::read_to_string(&elsewhere).unwrap(), action);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_setup_ci() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_setup_ci");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(".gitlab-ci.yml"), "build:\n  script: make\n").unwrap();
+
+    let setup = |args: &[&str]| {
+        Command::new(rhodibot_binary())
+            .arg("setup-ci")
+            .args(args)
+            .arg(&dir)
+            .output()
+            .expect("Failed to execute rhodibot")
+    };
+    assert!(setup(&["github"]).status.success());
+    let workflow = std::fs::read_to_string(dir.join(".github/workflows/rhodibot.yml")).unwrap();
+    assert!(workflow.starts_with("# Rhodibot RSR Compliance Check\n"));
+
+    let output = setup(&["gitlab"]);
+    assert_eq!(output.status.code(), Some(3)); // INVALID_PATH
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pass --force to replace it"));
+    assert!(setup(&["gitlab", "--force"]).status.success());
+    let config = std::fs::read_to_string(dir.join(".gitlab-ci.yml")).unwrap();
+    assert!(config.contains("\nrhodibot:\n  stage: test\n"));
+    assert_eq!(setup(&["jenkins"]).status.code(), Some(4)); // INVALID_ARGS
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_conventional_commits() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_conventional_commits");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.org"])
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    };
+    if !git(&["init", "--quiet", "--initial-branch=main"]) {
+        return; // git is not available
+    }
+    std::fs::write(
+        dir.join(".rhodibot.toml"),
+        "[version_control]\nconventional_commits = true\n",
+    )
+    .unwrap();
+    git(&["add", "."]);
+    git(&["commit", "--quiet", "-m", "chore: configure rhodibot"]);
+    git(&["commit", "--quiet", "--allow-empty", "-m", "Update things"]);
+
+    let check = || {
+        let output = Command::new(rhodibot_binary())
+            .args(["check", "-f", "json"])
+            .arg(&dir)
+            .output()
+            .expect("Failed to execute rhodibot");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    let json = check();
+    assert!(json.contains("\"id\": \"RSR-VCS-001\""));
+    assert!(json.contains("(problems: 'Update things' has no 'type: description' prefix)"));
+
+    // Packed commits are read with git log
+    git(&["commit", "--quiet", "--allow-empty", "-m", "fix: tidy up"]);
+    git(&["gc", "--quiet"]);
+    let json = check();
+    assert!(json.contains("'Update things' has no"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_fix() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_fix");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("README.md"), "# Mine\n").unwrap();
+
+    let fix = |args: &[&str]| {
+        Command::new(rhodibot_binary())
+            .arg("fix")
+            .args(args)
+            .arg(&dir)
+            .env_remove("GITHUB_ACTIONS")
+            .env_remove("GITLAB_CI")
+            .env_remove("GITHUB_TOKEN")
+            .output()
+            .expect("Failed to execute rhodibot")
+    };
+    let output = fix(&["--create-pr", "--ci", "github"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Created SECURITY.md (RSR-DOC-003)\n"));
+    assert!(stdout.contains("Created .well-known/security.txt (RSR-WKN-002)\n"));
+    assert!(!stdout.contains("README.md"));
+    // Without a token the files are only written
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Warning: pull request not opened"));
+    assert_eq!(std::fs::read_to_string(dir.join("README.md")).unwrap(), "# Mine\n");
+    assert!(!dir.join("LICENSE.txt").exists());
+
+    let output = fix(&[]);
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("Nothing to fix"));
+    let output = Command::new(rhodibot_binary())
+        .args(["check", "--create-pr"])
+        .arg(&dir)
+        .output()
+        .expect("Failed to execute rhodibot");
+    assert_eq!(output.status.code(), Some(4)); // INVALID_ARGS
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_rule_packs() {
+    let _ = Command::new("cargo").args(["build"]).output();
+
+    let dir = std::env::temp_dir().join("rhodibot_it_rule_packs");
+    let _ = std::fs::remove_dir_all(&dir);
+    let pack = dir.join("org-policies");
+    let repo = dir.join("repo");
+    std::fs::create_dir_all(&pack).unwrap();
+    std::fs::create_dir_all(repo.join("docs/adr")).unwrap();
+    std::fs::write(
+        pack.join("docs.toml"),
+        "[rules.adr]\npath = \"docs/adr/\"\ncategory = \"Governance\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        pack.join("security.toml"),
+        "[rules.sbom]\npath = \"sbom.json\"\ncategory = \"Security\"\nlevel = \"silver\"\n",
+    )
+    .unwrap();
+    std::fs::write(pack.join("README.md"), "Not a rule file\n").unwrap();
+
+    let run = |packs: &[&Path]| {
+        let mut command = Command::new(rhodibot_binary());
+        command.args(["check", "-f", "json"]);
+        for pack in packs {
+            command.arg("--rules").arg(pack);
+        }
+        let output = command.arg(&repo).output().expect("Failed to execute rhodibot");
+        (
+            output.status.code(),
+            String::from_utf8_lossy(&output.stdout).to_string(),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        )
+    };
+
+    let (_, json, _) = run(&[&pack]);
+    assert!(json.contains("\"id\": \"org-policies/adr\""), "{}", json);
+    assert!(json.contains("\"id\": \"org-policies/sbom\""), "{}", json);
+    assert!(!run(&[]).1.contains("org-policies/"));
+
+    let (code, _, stderr) = run(&[&pack, &pack]);
+    assert_eq!(code, Some(4));
+    assert!(stderr.contains("rule pack 'org-policies' is loaded twice"), "{}", stderr);
+    let (code, _, _) = run(&[&dir.join("missing")]);
+    assert_eq!(code, Some(4));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -10,6 +10,14 @@
 //! - RSR compliance enforcement
 
 use std::path::Path;
+use validate::{CiKind, Severity};
+
+pub mod stack;
+pub mod upgrade;
+pub mod validate;
+
+pub use rsr_spec::{yaml, STANDARD_VERSION};
+pub use stack::{Language, Os};
 
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -25,6 +33,7 @@ pub enum Platform {
 
 impl Platform {
     /// Parse platform from string
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "github" | "gh" => Some(Platform::GitHub),
@@ -47,13 +56,10 @@ pub fn default_path(&self) -> &'static str {
 }
 
 /// Pipeline compliance level
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum PipelineLevel {
-    Bronze,
-    Silver,
-    Gold,
-    Platinum,
-}
+///
+/// This is the RSR compliance level from `rsr-spec`: levels are ordered, and
+/// each one generates everything the level below it does.
+pub use rsr_spec::ComplianceLevel as PipelineLevel;
 
 /// Pipeline generation options
 #[derive(Debug, Clone)]
@@ -63,6 +69,33 @@ pub struct PipelineOptions {
     pub include_deploy: bool,
     pub project_name: String,
     pub rust_version: String,
+    /// Languages the project is written in; empty means Rust
+    pub languages: Vec<Language>,
+    /// Operating systems to build on
+    pub os: Vec<Os>,
+}
+
+impl PipelineOptions {
+    /// The project's languages, defaulting to Rust
+    pub fn languages(&self) -> Vec<Language> {
+        if self.languages.is_empty() {
+            vec![Language::Rust]
+        } else {
+            self.languages.clone()
+        }
+    }
+
+    /// Check that the platform can generate what was asked for
+    pub fn check(&self) -> Result<(), String> {
+        if self.platform != Platform::GitHub && self.os != Os::ALL {
+            return Err("--os is only supported for GitHub Actions".to_string());
+        }
+        let rust_only = matches!(self.platform, Platform::CircleCI | Platform::Jenkins);
+        if rust_only && self.languages() != [Language::Rust] {
+            return Err("--lang is only supported for GitHub Actions and GitLab CI".to_string());
+        }
+        Ok(())
+    }
 }
 
 impl Default for PipelineOptions {
@@ -73,16 +106,69 @@ fn default() -> Self {
             include_deploy: false,
             project_name: String::from("project"),
             rust_version: String::from("stable"),
+            languages: vec![Language::Rust],
+            os: Os::ALL.to_vec(),
+        }
+    }
+}
+
+/// Steps that run `command` under `name`, indented as job steps
+fn github_run(name: &str, command: &str) -> String {
+    if command.contains('\n') {
+        let mut step = format!("      - name: {}\n        run: |\n", name);
+        for line in command.lines() {
+            step.push_str(&format!("          {}\n", line));
+        }
+        step
+    } else {
+        format!("      - name: {}\n        run: {}\n", name, command)
+    }
+}
+
+/// Add `if: <condition>` to every step in `steps`
+fn github_when(steps: &str, condition: &str) -> String {
+    let mut out = String::new();
+    for line in steps.lines() {
+        out.push_str(line);
+        out.push('\n');
+        if line.starts_with("      - ") {
+            out.push_str(&format!("        if: {}\n", condition));
         }
     }
+    out
+}
+
+/// Toolchain setup and dependency installation for `language`
+fn github_setup(options: &PipelineOptions, language: Language, label: &str) -> String {
+    let mut steps = language.github_setup(&options.rust_version);
+    if let Some(install) = language.install_command() {
+        steps.push_str(&github_run(&format!("Install dependencies{}", label), install));
+    }
+    steps
 }
 
 /// Generate GitHub Actions workflow
+///
+/// Check and test jobs run every language's tools in turn; the build job is
+/// a matrix over `options.os` and, for multi-language projects, over the
+/// languages.
 pub fn generate_github_actions(options: &PipelineOptions) -> String {
-    format!(
+    let languages = options.languages();
+    let multi = languages.len() > 1;
+    let rust = languages.contains(&Language::Rust);
+    let label = |language: Language| {
+        if multi {
+            format!(" ({})", language.name())
+        } else {
+            String::new()
+        }
+    };
+
+    let mut out = format!(
         r#"# RSR-Compliant CI/CD Pipeline
-# Generated by Rhodium Pipeline v{version}
-# Level: {level:?}
+# Generated by Rhodium Pipeline v{}
+# Level: {:?}
+# RSR Standard: {}
 
 name: CI
 
@@ -93,26 +179,37 @@ pub fn generate_github_actions(options: &PipelineOptions) -> String {
     branches: [main, master]
   schedule:
     - cron: '0 0 * * 1' # Weekly
+"#,
+        VERSION, options.level, STANDARD_VERSION
+    );
+    if rust {
+        out.push_str("\nenv:\n  CARGO_TERM_COLOR: always\n  RUSTFLAGS: -Dwarnings\n");
+    }
 
-env:
-  CARGO_TERM_COLOR: always
-  RUSTFLAGS: -Dwarnings
-
-jobs:
-  # Stage 1: Check
-  check:
-    name: Check
-    runs-on: ubuntu-latest
-    steps:
-      - uses: actions/checkout@v4
-      - uses: dtolnay/rust-action@{rust_version}
-
-      - name: Check formatting
-        run: cargo fmt --check
-
-      - name: Clippy
-        run: cargo clippy -- -D warnings
-
+    // Stage 1: Check
+    out.push_str(
+        "\njobs:\n  # Stage 1: Check\n  check:\n    name: Check\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout@v4\n",
+    );
+    for (n, &language) in languages.iter().enumerate() {
+        if n > 0 {
+            out.push('\n');
+        }
+        out.push_str(&github_setup(options, language, &label(language)));
+        out.push('\n');
+        out.push_str(&github_run(
+            &format!("Check formatting{}", label(language)),
+            language.format_command(),
+        ));
+        out.push('\n');
+        let lint = if language == Language::Rust { "Clippy" } else { "Lint" };
+        out.push_str(&github_run(
+            &format!("{}{}", lint, label(language)),
+            language.lint_command(),
+        ));
+    }
+    if rust {
+        out.push_str(
+            r#"
       - name: Check for unsafe code
         run: |
           if grep -r "unsafe" src/; then
@@ -128,48 +225,75 @@ pub fn generate_github_actions(options: &PipelineOptions) -> String {
             cargo tree
             exit 1
           fi
+"#,
+        );
+    }
 
-  # Stage 2: Test
-  test:
-    name: Test
-    runs-on: ubuntu-latest
-    needs: check
-    steps:
-      - uses: actions/checkout@v4
-      - uses: dtolnay/rust-action@{rust_version}
-
-      - name: Run tests
-        run: cargo test --verbose
-
-      - name: Run tests (release)
-        run: cargo test --release --verbose
-
-      - name: Doc tests
-        run: cargo test --doc
-
-  # Stage 3: Build
-  build:
-    name: Build
-    runs-on: ${{{{ matrix.os }}}}
-    needs: test
-    strategy:
-      matrix:
-        os: [ubuntu-latest, macos-latest, windows-latest]
-    steps:
-      - uses: actions/checkout@v4
-      - uses: dtolnay/rust-action@{rust_version}
-
-      - name: Build release
-        run: cargo build --release
+    // Stage 2: Test
+    out.push_str(
+        "\n  # Stage 2: Test\n  test:\n    name: Test\n    runs-on: ubuntu-latest\n    needs: check\n    steps:\n      - uses: actions/checkout@v4\n",
+    );
+    for (n, &language) in languages.iter().enumerate() {
+        if n > 0 {
+            out.push('\n');
+        }
+        out.push_str(&github_setup(options, language, &label(language)));
+        out.push('\n');
+        out.push_str(&github_run(
+            &format!("Run tests{}", label(language)),
+            language.test_command(),
+        ));
+        if language == Language::Rust {
+            out.push('\n');
+            out.push_str(&github_run("Run tests (release)", "cargo test --release --verbose"));
+            out.push('\n');
+            out.push_str(&github_run("Doc tests", "cargo test --doc"));
+        }
+    }
 
+    // Stage 3: Build
+    let runners: Vec<&str> = options.os.iter().map(|os| os.github_runner()).collect();
+    out.push_str(&format!(
+        "\n  # Stage 3: Build\n  build:\n    name: Build\n    runs-on: ${{{{ matrix.os }}}}\n    needs: test\n    strategy:\n      matrix:\n        os: [{}]\n",
+        runners.join(", ")
+    ));
+    if multi {
+        let names: Vec<&str> = languages.iter().map(|l| l.name()).collect();
+        out.push_str(&format!("        language: [{}]\n", names.join(", ")));
+    }
+    out.push_str("    steps:\n      - uses: actions/checkout@v4\n");
+    for (n, &language) in languages.iter().enumerate() {
+        if n > 0 {
+            out.push('\n');
+        }
+        let mut steps = github_setup(options, language, "");
+        steps.push('\n');
+        steps.push_str(&github_run("Build release", language.build_command()));
+        if language == Language::Rust {
+            steps.push_str(&format!(
+                r#"
       - name: Upload binary
         uses: actions/upload-artifact@v4
         with:
-          name: {project_name}-${{{{ matrix.os }}}}
+          name: {0}-${{{{ matrix.os }}}}
           path: |
-            target/release/{project_name}
-            target/release/{project_name}.exe
+            target/release/{0}
+            target/release/{0}.exe
+"#,
+                options.project_name
+            ));
+        }
+        if multi {
+            let condition = format!("matrix.language == '{}'", language.name());
+            out.push_str(&github_when(&steps, &condition));
+        } else {
+            out.push_str(&steps);
+        }
+    }
 
+    // Stage 4: Verify
+    out.push_str(&format!(
+        r#"
   # Stage 4: Verify RSR Compliance
   verify:
     name: RSR Compliance
@@ -177,155 +301,269 @@ pub fn generate_github_actions(options: &PipelineOptions) -> String {
     needs: build
     steps:
       - uses: actions/checkout@v4
-      - uses: dtolnay/rust-action@{rust_version}
-
+      - uses: dtolnay/rust-action@{}
+      - uses: Swatinem/rust-cache@v2
+"#,
+        options.rust_version
+    ));
+    if rust {
+        out.push_str(
+            r#"
       - name: Build
         run: cargo build --release
 
       - name: Self-verify
         run: cargo run -- check . || true
-
+"#,
+        );
+    } else {
+        out.push_str(
+            r#"
+      - name: Install rhodibot
+        run: cargo install rhodibot --locked
+
+      - name: Verify
+        run: rhodibot check . || true
+"#,
+        );
+    }
+    out.push_str(&format!(
+        r#"
       - name: Generate badge
         run: |
-          echo "![RSR Bronze](https://img.shields.io/badge/RSR-Bronze-cd7f32)" > RSR_BADGE.md
+          echo "![RSR Bronze]({})" > RSR_BADGE.md
 "#,
-        version = VERSION,
-        level = options.level,
-        rust_version = options.rust_version,
-        project_name = options.project_name,
-    )
+        rsr_spec::badge::image_url(PipelineLevel::Bronze)
+    ));
+    out
 }
 
 /// Generate GitLab CI configuration
+///
+/// Each language gets a hidden `.<language>-template` with its image and
+/// cache, and its own check, test and build jobs (suffixed with the language
+/// name when there is more than one).
 pub fn generate_gitlab_ci(options: &PipelineOptions) -> String {
-    format!(
-        r#"# RSR-Compliant CI/CD Pipeline
-# Generated by Rhodium Pipeline v{version}
-# Level: {level:?}
-
-stages:
-  - check
-  - test
-  - build
-  - verify
-
-variables:
-  CARGO_HOME: ${{CI_PROJECT_DIR}}/.cargo
-  RUSTFLAGS: "-Dwarnings"
-
-.rust-template:
-  image: rust:{rust_version}
-  cache:
-    key: ${{CI_COMMIT_REF_SLUG}}
-    paths:
-      - .cargo/
-      - target/
-
-# Stage 1: Check
-fmt:
-  extends: .rust-template
-  stage: check
-  script:
-    - cargo fmt --check
-
-clippy:
-  extends: .rust-template
-  stage: check
-  script:
-    - rustup component add clippy
-    - cargo clippy -- -D warnings
-
-unsafe-check:
-  extends: .rust-template
-  stage: check
-  script:
-    - |
-      if grep -r "unsafe" src/; then
-        echo "Unsafe code detected!"
-        exit 1
-      fi
-
-deps-check:
-  extends: .rust-template
-  stage: check
-  script:
-    - |
-      deps=$(cargo tree --depth 0 | grep -c "^")
-      if [ "$deps" -gt 1 ]; then
-        echo "External dependencies detected!"
-        exit 1
-      fi
-
-# Stage 2: Test
-test:
-  extends: .rust-template
-  stage: test
-  script:
-    - cargo test --verbose
-
-test-release:
-  extends: .rust-template
-  stage: test
-  script:
-    - cargo test --release --verbose
-
-# Stage 3: Build
-build-debug:
-  extends: .rust-template
-  stage: build
-  script:
-    - cargo build
-  artifacts:
-    paths:
-      - target/debug/{project_name}
-    expire_in: 1 week
-
-build-release:
-  extends: .rust-template
-  stage: build
-  script:
-    - cargo build --release
-  artifacts:
-    paths:
-      - target/release/{project_name}
-    expire_in: 1 month
-
-# Stage 4: Verify
-verify:
-  extends: .rust-template
-  stage: verify
-  dependencies:
-    - build-release
-  script:
-    - ./target/release/{project_name} check . || true
-"#,
-        version = VERSION,
-        level = options.level,
-        rust_version = options.rust_version,
-        project_name = options.project_name,
-    )
+    let languages = options.languages();
+    let multi = languages.len() > 1;
+    let rust = languages.contains(&Language::Rust);
+    let name = &options.project_name;
+
+    let mut out = format!(
+        "# RSR-Compliant CI/CD Pipeline\n\
+         # Generated by Rhodium Pipeline v{}\n\
+         # Level: {:?}\n\
+         # RSR Standard: {}\n\
+         \n\
+         stages:\n\
+         \x20 - check\n\
+         \x20 - test\n\
+         \x20 - build\n\
+         \x20 - verify\n\
+         \n\
+         variables:\n",
+        VERSION, options.level, STANDARD_VERSION
+    );
+    for language in &languages {
+        let (variable, value) = language.gitlab_cache_variable();
+        out.push_str(&format!("  {}: {}\n", variable, value));
+    }
+    if rust {
+        out.push_str("  RUSTFLAGS: \"-Dwarnings\"\n");
+    }
+
+    for &language in &languages {
+        let key = if multi {
+            format!("{}-${{CI_COMMIT_REF_SLUG}}", language.name())
+        } else {
+            String::from("${CI_COMMIT_REF_SLUG}")
+        };
+        out.push_str(&format!(
+            "\n.{}-template:\n  image: {}\n  cache:\n    key: {}\n    paths:\n",
+            language.name(),
+            language.gitlab_image(&options.rust_version),
+            key
+        ));
+        for path in language.gitlab_cache_paths() {
+            out.push_str(&format!("      - {}\n", path));
+        }
+        if let Some(install) = language.install_command() {
+            out.push_str(&format!("  before_script:\n    - {}\n", install));
+        }
+    }
+
+    let job = |out: &mut String, job: &str, language: Language, stage: &str, script: &[&str]| {
+        let suffix = if multi {
+            format!("-{}", language.name())
+        } else {
+            String::new()
+        };
+        out.push_str(&format!(
+            "\n{}{}:\n  extends: .{}-template\n  stage: {}\n  script:\n",
+            job,
+            suffix,
+            language.name(),
+            stage
+        ));
+        for line in script {
+            if line.contains('\n') {
+                out.push_str("    - |\n");
+                for part in line.lines() {
+                    out.push_str(&format!("      {}\n", part));
+                }
+            } else {
+                out.push_str(&format!("    - {}\n", line));
+            }
+        }
+    };
+
+    out.push_str("\n# Stage 1: Check");
+    for &language in &languages {
+        job(&mut out, "fmt", language, "check", &[language.format_command()]);
+        if language == Language::Rust {
+            job(
+                &mut out,
+                "clippy",
+                language,
+                "check",
+                &["rustup component add clippy", language.lint_command()],
+            );
+            job(
+                &mut out,
+                "unsafe-check",
+                language,
+                "check",
+                &["if grep -r \"unsafe\" src/; then\n  echo \"Unsafe code detected!\"\n  exit 1\nfi"],
+            );
+            job(
+                &mut out,
+                "deps-check",
+                language,
+                "check",
+                &["deps=$(cargo tree --depth 0 | grep -c \"^\")\nif [ \"$deps\" -gt 1 ]; then\n  echo \"External dependencies detected!\"\n  exit 1\nfi"],
+            );
+        } else {
+            job(&mut out, "lint", language, "check", &[language.lint_command()]);
+        }
+    }
+
+    out.push_str("\n# Stage 2: Test");
+    for &language in &languages {
+        job(&mut out, "test", language, "test", &[language.test_command()]);
+        if language == Language::Rust {
+            job(&mut out, "test-release", language, "test", &["cargo test --release --verbose"]);
+        }
+    }
+
+    out.push_str("\n# Stage 3: Build");
+    for &language in &languages {
+        if language == Language::Rust {
+            job(&mut out, "build-debug", language, "build", &["cargo build"]);
+            out.push_str(&format!(
+                "  artifacts:\n    paths:\n      - target/debug/{}\n    expire_in: 1 week\n",
+                name
+            ));
+            job(&mut out, "build-release", language, "build", &[language.build_command()]);
+            out.push_str(&format!(
+                "  artifacts:\n    paths:\n      - target/release/{}\n    expire_in: 1 month\n",
+                name
+            ));
+        } else {
+            job(&mut out, "build", language, "build", &[language.build_command()]);
+        }
+    }
+
+    out.push_str("\n# Stage 4: Verify\nverify:\n");
+    if rust {
+        let build = if multi { "build-release-rust" } else { "build-release" };
+        out.push_str(&format!(
+            "  extends: .rust-template\n  stage: verify\n  dependencies:\n    - {}\n  script:\n    - ./target/release/{} check . || true\n",
+            build, name
+        ));
+    } else {
+        out.push_str(&format!(
+            "  image: rust:{}\n  stage: verify\n  script:\n    - cargo install rhodibot --locked\n    - rhodibot check . || true\n",
+            options.rust_version
+        ));
+    }
+    out
 }
 
 /// Generate CircleCI configuration
+///
+/// Every level runs check, test, build and verify jobs. Silver adds the
+/// dependency check, doc tests, Cargo caching and a strict compliance gate;
+/// Gold builds on amd64 and arm64 through a matrix; Platinum adds a security
+/// audit, release checksums and a nightly workflow. A deploy job publishing
+/// from `main` is added when `include_deploy` is set.
 pub fn generate_circleci(options: &PipelineOptions) -> String {
-    format!(
-        r#"# RSR-Compliant CI/CD Pipeline
-# Generated by Rhodium Pipeline v{version}
-# Level: {level:?}
+    let level = options.level;
+    let silver = level >= PipelineLevel::Silver;
+    let gold = level >= PipelineLevel::Gold;
+    let platinum = level >= PipelineLevel::Platinum;
+    let name = &options.project_name;
+
+    let mut out = format!(
+        "# RSR-Compliant CI/CD Pipeline\n\
+         # Generated by Rhodium Pipeline v{}\n\
+         # Level: {:?}\n\
+         # RSR Standard: {}\n\
+         \n\
+         version: 2.1\n\
+         \n\
+         executors:\n\
+         \x20 rust:\n",
+        VERSION, level, STANDARD_VERSION
+    );
+    if gold {
+        out.push_str("    parameters:\n");
+        out.push_str("      resource_class:\n");
+        out.push_str("        type: string\n");
+        out.push_str("        default: medium\n");
+    }
+    out.push_str("    docker:\n");
+    out.push_str(&format!("      - image: rust:{}\n", options.rust_version));
+    if gold {
+        out.push_str("    resource_class: << parameters.resource_class >>\n");
+    }
 
-version: 2.1
+    if silver {
+        out.push_str(
+            r#"
+commands:
+  restore-cargo-cache:
+    steps:
+      - restore_cache:
+          keys:
+            - cargo-v1-{{ checksum "Cargo.lock" }}
+            - cargo-v1-
+  save-cargo-cache:
+    steps:
+      - save_cache:
+          key: cargo-v1-{{ checksum "Cargo.lock" }}
+          paths:
+            - ~/.cargo/registry
+            - target
+"#,
+        );
+    }
 
-executors:
-  rust:
-    docker:
-      - image: rust:{rust_version}
+    let restore = if silver {
+        "      - restore-cargo-cache\n"
+    } else {
+        ""
+    };
+    let save = if silver {
+        "      - save-cargo-cache\n"
+    } else {
+        ""
+    };
 
-jobs:
-  check:
-    executor: rust
-    steps:
-      - checkout
-      - run:
+    // Check
+    out.push_str("\njobs:\n  check:\n    executor: rust\n    steps:\n      - checkout\n");
+    out.push_str(restore);
+    out.push_str(
+        r#"      - run:
           name: Check formatting
           command: cargo fmt --check
       - run:
@@ -338,38 +576,119 @@ pub fn generate_circleci(options: &PipelineOptions) -> String {
               echo "Unsafe code detected!"
               exit 1
             fi
+"#,
+    );
+    if silver {
+        out.push_str(
+            r#"      - run:
+          name: Check for dependencies
+          command: |
+            deps=$(cargo tree --depth 1 --edges normal | grep -c "^")
+            if [ "$deps" -gt 1 ]; then
+              echo "External dependencies detected!"
+              cargo tree
+              exit 1
+            fi
+"#,
+        );
+    }
+    out.push_str(save);
 
-  test:
-    executor: rust
-    steps:
-      - checkout
-      - run:
+    // Test
+    out.push_str("\n  test:\n    executor: rust\n    steps:\n      - checkout\n");
+    out.push_str(restore);
+    out.push_str(
+        r#"      - run:
           name: Run tests
           command: cargo test --verbose
       - run:
           name: Run release tests
           command: cargo test --release --verbose
+"#,
+    );
+    if silver {
+        out.push_str("      - run:\n");
+        out.push_str("          name: Doc tests\n");
+        out.push_str("          command: cargo test --doc\n");
+    }
 
-  build:
+    // Build
+    out.push_str("\n  build:\n");
+    if gold {
+        out.push_str("    parameters:\n");
+        out.push_str("      resource_class:\n");
+        out.push_str("        type: string\n");
+        out.push_str("        default: medium\n");
+        out.push_str("    executor:\n");
+        out.push_str("      name: rust\n");
+        out.push_str("      resource_class: << parameters.resource_class >>\n");
+    } else {
+        out.push_str("    executor: rust\n");
+    }
+    out.push_str("    steps:\n      - checkout\n");
+    out.push_str(restore);
+    out.push_str("      - run:\n");
+    out.push_str("          name: Build release\n");
+    out.push_str("          command: cargo build --release\n");
+    if platinum {
+        out.push_str("      - run:\n");
+        out.push_str("          name: Checksum\n");
+        out.push_str(&format!(
+            "          command: sha256sum target/release/{0} > target/release/{0}.sha256\n",
+            name
+        ));
+        out.push_str("      - store_artifacts:\n");
+        out.push_str(&format!("          path: target/release/{}.sha256\n", name));
+    }
+    out.push_str("      - store_artifacts:\n");
+    out.push_str(&format!("          path: target/release/{}\n", name));
+    out.push_str(save);
+
+    // Audit
+    if platinum {
+        out.push_str(
+            r#"
+  audit:
     executor: rust
     steps:
       - checkout
       - run:
-          name: Build release
-          command: cargo build --release
-      - store_artifacts:
-          path: target/release/{project_name}
+          name: Security audit
+          command: |
+            cargo install cargo-audit --locked
+            cargo audit
+"#,
+        );
+    }
 
-  verify:
+    // Verify
+    let gate = if silver { "" } else { " || true" };
+    out.push_str("\n  verify:\n    executor: rust\n    steps:\n      - checkout\n");
+    out.push_str(restore);
+    out.push_str("      - run:\n");
+    out.push_str("          name: Build and verify\n");
+    out.push_str("          command: |\n");
+    out.push_str("            cargo build --release\n");
+    out.push_str(&format!("            ./target/release/{} check .{}\n", name, gate));
+
+    // Deploy
+    if options.include_deploy {
+        out.push_str(
+            r#"
+  deploy:
     executor: rust
     steps:
       - checkout
       - run:
-          name: Build and verify
-          command: |
-            cargo build --release
-            ./target/release/{project_name} check . || true
+          name: Publish
+          command: cargo publish
+"#,
+        );
+    }
 
+    // Workflows
+    out.push_str(
+        r#"
 workflows:
   rsr-pipeline:
     jobs:
@@ -378,37 +697,104 @@ pub fn generate_circleci(options: &PipelineOptions) -> String {
           requires:
             - check
       - build:
+"#,
+    );
+    if gold {
+        out.push_str("          matrix:\n");
+        out.push_str("            parameters:\n");
+        out.push_str("              resource_class: [medium, arm.medium]\n");
+    }
+    out.push_str("          requires:\n            - test\n");
+    if platinum {
+        out.push_str("      - audit:\n          requires:\n            - check\n");
+    }
+    out.push_str("      - verify:\n          requires:\n            - build\n");
+    if platinum {
+        out.push_str("            - audit\n");
+    }
+    if options.include_deploy {
+        out.push_str(
+            r#"      - deploy:
+          context: release
           requires:
-            - test
-      - verify:
-          requires:
-            - build
+            - verify
+          filters:
+            branches:
+              only:
+                - main
 "#,
-        version = VERSION,
-        level = options.level,
-        rust_version = options.rust_version,
-        project_name = options.project_name,
-    )
+        );
+    }
+    if platinum {
+        out.push_str(
+            r#"
+  nightly:
+    triggers:
+      - schedule:
+          cron: "0 0 * * *"
+          filters:
+            branches:
+              only:
+                - main
+    jobs:
+      - audit
+      - test
+"#,
+        );
+    }
+
+    out
 }
 
 /// Generate Jenkinsfile
+///
+/// A declarative pipeline with Check, Test, Build and Verify stages at every
+/// level. Silver adds the dependency check, doc tests, a timeout and a strict
+/// compliance gate; Gold runs the build as a matrix over `linux` and `macos`
+/// agents; Platinum adds a security audit, release checksums, build
+/// retention and a nightly trigger. A Deploy stage publishing from `main` is
+/// added when `include_deploy` is set.
 pub fn generate_jenkinsfile(options: &PipelineOptions) -> String {
-    format!(
-        r#"// RSR-Compliant CI/CD Pipeline
-// Generated by Rhodium Pipeline v{version}
-// Level: {level:?}
-
-pipeline {{
-    agent any
+    let level = options.level;
+    let silver = level >= PipelineLevel::Silver;
+    let gold = level >= PipelineLevel::Gold;
+    let platinum = level >= PipelineLevel::Platinum;
+    let name = &options.project_name;
+
+    let mut out = format!(
+        "// RSR-Compliant CI/CD Pipeline\n\
+         // Generated by Rhodium Pipeline v{}\n\
+         // Level: {:?}\n\
+         // RSR Standard: {}\n\
+         \n\
+         pipeline {{\n\
+         \x20   agent any\n",
+        VERSION, level, STANDARD_VERSION
+    );
+
+    if silver {
+        out.push_str("\n    options {\n");
+        out.push_str("        timeout(time: 60, unit: 'MINUTES')\n");
+        out.push_str("        disableConcurrentBuilds()\n");
+        if platinum {
+            out.push_str("        buildDiscarder(logRotator(numToKeepStr: '30'))\n");
+        }
+        out.push_str("    }\n");
+    }
+    if platinum {
+        out.push_str("\n    triggers {\n        cron('H 0 * * *')\n    }\n");
+    }
 
-    environment {{
-        CARGO_HOME = "${{WORKSPACE}}/.cargo"
+    out.push_str(
+        r#"
+    environment {
+        CARGO_HOME = "${WORKSPACE}/.cargo"
         RUSTFLAGS = '-Dwarnings'
-    }}
+    }
 
-    stages {{
-        stage('Check') {{
-            steps {{
+    stages {
+        stage('Check') {
+            steps {
                 sh 'cargo fmt --check'
                 sh 'cargo clippy -- -D warnings'
                 sh '''
@@ -417,45 +803,128 @@ pub fn generate_jenkinsfile(options: &PipelineOptions) -> String {
                         exit 1
                     fi
                 '''
-            }}
-        }}
-
-        stage('Test') {{
-            steps {{
-                sh 'cargo test --verbose'
-                sh 'cargo test --release --verbose'
-            }}
-        }}
-
-        stage('Build') {{
-            steps {{
-                sh 'cargo build --release'
-            }}
-            post {{
-                success {{
-                    archiveArtifacts artifacts: 'target/release/{project_name}', fingerprint: true
-                }}
-            }}
-        }}
-
-        stage('Verify') {{
-            steps {{
-                sh './target/release/{project_name} check . || true'
-            }}
-        }}
-    }}
-
-    post {{
-        always {{
+"#,
+    );
+    if silver {
+        out.push_str(
+            r#"                sh '''
+                    deps=$(cargo tree --depth 1 --edges normal | grep -c "^")
+                    if [ "$deps" -gt 1 ]; then
+                        echo "External dependencies detected!"
+                        exit 1
+                    fi
+                '''
+"#,
+        );
+    }
+    out.push_str("            }\n        }\n");
+
+    out.push_str("\n        stage('Test') {\n            steps {\n");
+    out.push_str("                sh 'cargo test --verbose'\n");
+    out.push_str("                sh 'cargo test --release --verbose'\n");
+    if silver {
+        out.push_str("                sh 'cargo test --doc'\n");
+    }
+    out.push_str("            }\n        }\n");
+
+    // The build steps are shared between the single-agent and matrix forms
+    let pad = if gold { "            " } else { "" };
+    let mut build = String::new();
+    build.push_str(&format!("{}            steps {{\n", pad));
+    build.push_str(&format!("{}                sh 'cargo build --release'\n", pad));
+    if platinum {
+        build.push_str(&format!(
+            "{0}                sh 'shasum -a 256 target/release/{1} > target/release/{1}.sha256'\n",
+            pad, name
+        ));
+    }
+    build.push_str(&format!("{}            }}\n", pad));
+    build.push_str(&format!("{}            post {{\n", pad));
+    build.push_str(&format!("{}                success {{\n", pad));
+    build.push_str(&format!(
+        "{}                    archiveArtifacts artifacts: 'target/release/{}*', fingerprint: true\n",
+        pad, name
+    ));
+    build.push_str(&format!("{}                }}\n", pad));
+    build.push_str(&format!("{}            }}\n", pad));
+
+    out.push_str("\n        stage('Build') {\n");
+    if gold {
+        out.push_str(
+            r#"            matrix {
+                axes {
+                    axis {
+                        name 'PLATFORM'
+                        values 'linux', 'macos'
+                    }
+                }
+                agent {
+                    label "${PLATFORM}"
+                }
+                stages {
+                    stage('Build release') {
+"#,
+        );
+        out.push_str(&build);
+        out.push_str("                    }\n                }\n            }\n");
+    } else {
+        out.push_str(&build);
+    }
+    out.push_str("        }\n");
+
+    if platinum {
+        out.push_str(
+            r#"
+        stage('Audit') {
+            steps {
+                sh 'cargo install cargo-audit --locked'
+                sh 'cargo audit'
+            }
+        }
+"#,
+        );
+    }
+
+    let gate = if silver { "" } else { " || true" };
+    out.push_str("\n        stage('Verify') {\n            steps {\n");
+    out.push_str("                sh 'cargo build --release'\n");
+    out.push_str(&format!(
+        "                sh './target/release/{} check .{}'\n",
+        name, gate
+    ));
+    out.push_str("            }\n        }\n");
+
+    if options.include_deploy {
+        out.push_str(
+            r#"
+        stage('Deploy') {
+            when {
+                branch 'main'
+            }
+            environment {
+                CARGO_REGISTRY_TOKEN = credentials('cargo-registry-token')
+            }
+            steps {
+                sh 'cargo publish'
+            }
+        }
+"#,
+        );
+    }
+
+    out.push_str(
+        r#"    }
+
+    post {
+        always {
             cleanWs()
-        }}
-    }}
-}}
+        }
+    }
+}
 "#,
-        version = VERSION,
-        level = options.level,
-        project_name = options.project_name,
-    )
+    );
+
+    out
 }
 
 /// Generate pipeline configuration for the specified platform
@@ -476,7 +945,53 @@ pub struct ValidationResult {
     pub warnings: Vec<String>,
 }
 
+/// CI configuration files under `path`, relative to it, in the order
+/// GitHub workflows (sorted), GitLab CI, CircleCI, Jenkins
+fn ci_files(path: &Path) -> Vec<(String, CiKind)> {
+    let mut files: Vec<(String, CiKind)> = Vec::new();
+    let mut workflows: Vec<String> = std::fs::read_dir(path.join(".github/workflows"))
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| e.file_name().into_string().ok())
+                .filter(|name| name.ends_with(".yml") || name.ends_with(".yaml"))
+                .collect()
+        })
+        .unwrap_or_default();
+    workflows.sort();
+    for name in workflows {
+        files.push((format!(".github/workflows/{}", name), CiKind::GitHub));
+    }
+    for (relative, kind) in [
+        (".gitlab-ci.yml", CiKind::GitLab),
+        (".circleci/config.yml", CiKind::CircleCI),
+        ("Jenkinsfile", CiKind::Jenkins),
+    ] {
+        if path.join(relative).is_file() {
+            files.push((relative.to_string(), kind));
+        }
+    }
+    files
+}
+
+/// Add the RSR-required jobs missing from the pipeline under `path`
+///
+/// See [`upgrade`] for how each platform's file is patched.
+pub fn upgrade_pipeline(path: &Path, options: &PipelineOptions) -> Result<upgrade::Upgrade, String> {
+    let mut files = Vec::new();
+    for (relative, kind) in ci_files(path) {
+        let source = std::fs::read_to_string(path.join(&relative))
+            .map_err(|e| format!("{}: cannot read: {}", relative, e))?;
+        files.push((relative, kind, source));
+    }
+    upgrade::upgrade(&files, options)
+}
+
 /// Validate an existing pipeline configuration
+///
+/// Every CI file under `path` is parsed and checked for dependency caching
+/// and pinned action and image versions; together they must provide the
+/// jobs RSR requires. See [`validate`] for the details.
 pub fn validate_pipeline(path: &Path) -> ValidationResult {
     let mut result = ValidationResult {
         valid: true,
@@ -484,36 +999,53 @@ pub fn validate_pipeline(path: &Path) -> ValidationResult {
         warnings: Vec::new(),
     };
 
-    // Check for GitHub Actions
     let github_path = path.join(".github/workflows");
-    let gitlab_path = path.join(".gitlab-ci.yml");
-    let circleci_path = path.join(".circleci/config.yml");
-    let jenkins_path = path.join("Jenkinsfile");
-
-    let has_ci = github_path.exists()
-        || gitlab_path.exists()
-        || circleci_path.exists()
-        || jenkins_path.exists();
+    let files = ci_files(path);
+    if github_path.is_dir() && !files.iter().any(|(_, kind)| *kind == CiKind::GitHub) {
+        result
+            .warnings
+            .push("No workflow files in .github/workflows/".to_string());
+    }
 
-    if !has_ci {
+    if files.is_empty() && !github_path.is_dir() {
         result.errors.push("No CI/CD configuration found".to_string());
         result.valid = false;
+        return result;
     }
 
-    // Check for required elements (basic validation)
-    if github_path.exists() {
-        if let Ok(entries) = std::fs::read_dir(&github_path) {
-            let has_workflow = entries
-                .filter_map(|e| e.ok())
-                .any(|e| e.path().extension().map(|ext| ext == "yml").unwrap_or(false));
-            if !has_workflow {
-                result
-                    .warnings
-                    .push("No workflow files in .github/workflows/".to_string());
+    let mut jobs = Vec::new();
+    for (relative, kind) in &files {
+        let source = match std::fs::read_to_string(path.join(relative)) {
+            Ok(source) => source,
+            Err(e) => {
+                result.errors.push(format!("{}: cannot read: {}", relative, e));
+                continue;
+            }
+        };
+        let analysis = validate::analyze(*kind, &source);
+        for finding in analysis.findings {
+            let location = match finding.line {
+                Some(line) => format!("{}:{}", relative, line),
+                None => relative.clone(),
+            };
+            let message = format!("{}: {}", location, finding.message);
+            match finding.severity {
+                Severity::Error => result.errors.push(message),
+                Severity::Warning => result.warnings.push(message),
             }
         }
+        jobs.extend(analysis.jobs);
+    }
+
+    for required in validate::missing_jobs(&jobs) {
+        result.errors.push(format!(
+            "Missing required RSR job: {} (no job mentions {})",
+            required.role,
+            required.keywords.join(" or ")
+        ));
     }
 
+    result.valid = result.errors.is_empty();
     result
 }
 
@@ -556,4 +1088,407 @@ fn test_gitlab_generation() {
         assert!(result.contains("stages:"));
         assert!(result.contains("cargo test"));
     }
+
+    const LEVELS: [PipelineLevel; 4] = [
+        PipelineLevel::Bronze,
+        PipelineLevel::Silver,
+        PipelineLevel::Gold,
+        PipelineLevel::Platinum,
+    ];
+
+    fn options(platform: Platform, level: PipelineLevel, include_deploy: bool) -> PipelineOptions {
+        PipelineOptions {
+            platform,
+            level,
+            include_deploy,
+            project_name: String::from("demo"),
+            ..Default::default()
+        }
+    }
+
+    fn indent(line: &str) -> usize {
+        line.len() - line.trim_start().len()
+    }
+
+    /// Check the indentation structure of a generated YAML document and
+    /// return the names of the jobs it defines and the jobs its workflows
+    /// reference
+    fn yaml_structure(doc: &str) -> (Vec<String>, Vec<String>) {
+        let mut defined = Vec::new();
+        let mut referenced = Vec::new();
+        let mut section = "";
+        let mut previous: Option<&str> = None;
+        let mut scalar_indent: Option<usize> = None;
+        let mut in_requires = false;
+        let mut workflow_key = "";
+
+        for (number, line) in doc.lines().enumerate() {
+            let number = number + 1;
+            assert!(!line.contains('\t'), "line {}: tab in YAML", number);
+            assert_eq!(line, line.trim_end(), "line {}: trailing whitespace", number);
+            let text = line.trim_start();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let depth = indent(line);
+            if let Some(block) = scalar_indent {
+                if depth > block {
+                    continue;
+                }
+                scalar_indent = None;
+            }
+            assert_eq!(depth % 2, 0, "line {}: odd indentation", number);
+            if let Some(prev) = previous {
+                let prev_text = prev.trim_start();
+                let allowed = if prev_text.starts_with("- ") && prev_text.ends_with(':') {
+                    indent(prev) + 4
+                } else if prev_text.ends_with(':') {
+                    indent(prev) + 2
+                } else {
+                    indent(prev)
+                };
+                assert!(depth <= allowed, "line {}: unexpected indentation", number);
+            }
+            let item = text.strip_prefix("- ").unwrap_or(text);
+            assert!(
+                text.starts_with("- ") || item.contains(':'),
+                "line {}: neither a key nor a list item: {}",
+                number,
+                text
+            );
+            if item.ends_with('|') {
+                scalar_indent = Some(depth);
+            }
+
+            if depth <= 10 {
+                in_requires = depth == 10 && text == "requires:";
+            }
+            if depth == 4 {
+                workflow_key = item.trim_end_matches(':');
+            }
+            if depth == 0 {
+                section = item.trim_end_matches(':');
+            } else if section == "jobs" && depth == 2 {
+                defined.push(item.trim_end_matches(':').to_string());
+            } else if section == "workflows"
+                && workflow_key == "jobs"
+                && ((depth == 6 && text.starts_with("- ")) || (depth == 12 && in_requires))
+            {
+                referenced.push(item.trim_end_matches(':').to_string());
+            }
+            previous = Some(line);
+        }
+        (defined, referenced)
+    }
+
+    /// Remove Groovy string literals so that braces inside them do not count
+    fn strip_groovy_strings(source: &str) -> String {
+        let mut out = String::new();
+        let mut rest = source;
+        while let Some(c) = rest.chars().next() {
+            let delimiter = if rest.starts_with("'''") {
+                "'''"
+            } else if c == '\'' {
+                "'"
+            } else if c == '"' {
+                "\""
+            } else {
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            };
+            let body = &rest[delimiter.len()..];
+            let end = body.find(delimiter).expect("unterminated string literal");
+            assert!(
+                delimiter == "'''" || !body[..end].contains('\n'),
+                "string literal spans lines"
+            );
+            rest = &body[end + delimiter.len()..];
+        }
+        out
+    }
+
+    /// The body of the block opened by the first `{` at or after `start`
+    fn block_body(source: &str, start: usize) -> &str {
+        let open = start + source[start..].find('{').unwrap();
+        let mut depth = 0;
+        for (i, c) in source[open..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return &source[open + 1..open + i];
+                    }
+                }
+                _ => {}
+            }
+        }
+        panic!("unbalanced block at {}", start);
+    }
+
+    fn assert_valid_jenkinsfile(source: &str) {
+        let code = strip_groovy_strings(source);
+        let opens = code.matches('{').count();
+        assert_eq!(opens, code.matches('}').count(), "unbalanced braces");
+
+        let code = code
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("//"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(code.trim_start().starts_with("pipeline {"));
+        let pipeline = block_body(&code, 0);
+        assert!(pipeline.contains("agent "), "pipeline has no agent");
+        assert!(pipeline.contains("stages {"), "pipeline has no stages");
+
+        // Every stage needs steps, a matrix or nested stages
+        let mut stages = 0;
+        for (start, _) in code.match_indices("stage(") {
+            let body = block_body(&code, start);
+            assert!(
+                body.contains("steps {") || body.contains("matrix {"),
+                "stage without steps"
+            );
+            stages += 1;
+        }
+        assert!(stages >= 4);
+    }
+
+    #[test]
+    fn test_circleci_structure_at_every_level() {
+        for level in LEVELS {
+            for include_deploy in [false, true] {
+                let config = generate_circleci(&options(Platform::CircleCI, level, include_deploy));
+                assert!(config.contains("\nversion: 2.1\n"));
+                let (defined, referenced) = yaml_structure(&config);
+                for job in ["check", "test", "build", "verify"] {
+                    assert!(defined.iter().any(|j| j == job), "{:?}: no {} job", level, job);
+                }
+                assert!(!referenced.is_empty());
+                for job in &referenced {
+                    assert!(
+                        defined.contains(job),
+                        "{:?}: workflow references undefined job {}",
+                        level,
+                        job
+                    );
+                }
+                for job in &defined {
+                    assert!(referenced.contains(job), "{:?}: job {} never runs", level, job);
+                }
+                assert_eq!(defined.iter().any(|j| j == "deploy"), include_deploy);
+            }
+        }
+    }
+
+    #[test]
+    fn test_circleci_levels_are_cumulative() {
+        let generate = |level| generate_circleci(&options(Platform::CircleCI, level, false));
+        let bronze = generate(PipelineLevel::Bronze);
+        assert!(bronze.contains("check . || true"));
+        assert!(!bronze.contains("restore_cache"));
+
+        let silver = generate(PipelineLevel::Silver);
+        assert!(silver.contains("restore_cache"));
+        assert!(silver.contains("cargo test --doc"));
+        assert!(silver.contains("External dependencies"));
+        assert!(!silver.contains("|| true"));
+
+        let gold = generate(PipelineLevel::Gold);
+        assert!(gold.contains("resource_class: [medium, arm.medium]"));
+        assert!(!gold.contains("cargo audit"));
+
+        let platinum = generate(PipelineLevel::Platinum);
+        assert!(platinum.contains("cargo audit"));
+        assert!(platinum.contains("demo.sha256"));
+        assert!(platinum.contains("nightly:"));
+        assert!(platinum.contains("resource_class: [medium, arm.medium]"));
+    }
+
+    #[test]
+    fn test_jenkinsfile_structure_at_every_level() {
+        for level in LEVELS {
+            for include_deploy in [false, true] {
+                let source = generate_jenkinsfile(&options(Platform::Jenkins, level, include_deploy));
+                assert_valid_jenkinsfile(&source);
+                assert_eq!(source.contains("stage('Deploy')"), include_deploy);
+            }
+        }
+    }
+
+    #[test]
+    fn test_jenkinsfile_levels_are_cumulative() {
+        let generate = |level| generate_jenkinsfile(&options(Platform::Jenkins, level, false));
+        let bronze = generate(PipelineLevel::Bronze);
+        assert!(bronze.contains("check . || true"));
+        assert!(!bronze.contains("options {"));
+
+        let silver = generate(PipelineLevel::Silver);
+        assert!(silver.contains("timeout(time: 60, unit: 'MINUTES')"));
+        assert!(silver.contains("cargo test --doc"));
+        assert!(!silver.contains("|| true"));
+        assert!(!silver.contains("matrix {"));
+
+        let gold = generate(PipelineLevel::Gold);
+        assert!(gold.contains("matrix {"));
+        assert!(gold.contains("values 'linux', 'macos'"));
+
+        let platinum = generate(PipelineLevel::Platinum);
+        assert!(platinum.contains("stage('Audit')"));
+        assert!(platinum.contains("cron('H 0 * * *')"));
+        assert!(platinum.contains("demo.sha256"));
+    }
+
+    fn temp_repo(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("rhodium_pipeline_{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_generated_pipelines_validate() {
+        for platform in [
+            Platform::GitHub,
+            Platform::GitLab,
+            Platform::CircleCI,
+            Platform::Jenkins,
+        ] {
+            for level in LEVELS {
+                let dir = temp_repo("generated");
+                let path = dir.join(platform.default_path());
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(&path, generate_pipeline(&options(platform, level, true))).unwrap();
+
+                let result = validate_pipeline(&dir);
+                assert!(result.valid, "{:?} {:?}: {:?}", platform, level, result.errors);
+                if platform != Platform::CircleCI || level >= PipelineLevel::Silver {
+                    assert!(result.warnings.is_empty(), "{:?}: {:?}", platform, result.warnings);
+                }
+                let _ = std::fs::remove_dir_all(&dir);
+            }
+        }
+    }
+
+    #[test]
+    fn test_language_pipelines_validate() {
+        let stacks = [
+            vec![Language::Node],
+            vec![Language::Python],
+            vec![Language::Go],
+            vec![Language::Rust, Language::Node, Language::Python, Language::Go],
+        ];
+        for platform in [Platform::GitHub, Platform::GitLab] {
+            for languages in &stacks {
+                let dir = temp_repo(&format!("languages_{:?}", platform));
+                let path = dir.join(platform.default_path());
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                let options = PipelineOptions {
+                    languages: languages.clone(),
+                    ..options(platform, PipelineLevel::Gold, false)
+                };
+                std::fs::write(&path, generate_pipeline(&options)).unwrap();
+
+                let result = validate_pipeline(&dir);
+                assert!(result.valid, "{:?} {:?}: {:?}", platform, languages, result.errors);
+                assert!(result.warnings.is_empty(), "{:?}: {:?}", languages, result.warnings);
+                let _ = std::fs::remove_dir_all(&dir);
+            }
+        }
+    }
+
+    #[test]
+    fn test_github_matrix() {
+        let options = PipelineOptions {
+            languages: vec![Language::Rust, Language::Node],
+            os: vec![Os::Linux, Os::Windows],
+            ..options(Platform::GitHub, PipelineLevel::Gold, false)
+        };
+        let doc = yaml::parse(&generate_github_actions(&options)).unwrap();
+        let build = doc.get("jobs").unwrap().get("build").unwrap();
+        let matrix = build.get("strategy").unwrap().get("matrix").unwrap();
+        let values = |key| -> Vec<&str> {
+            matrix.get(key).unwrap().items().iter().filter_map(yaml::Node::as_str).collect()
+        };
+        assert_eq!(values("os"), ["ubuntu-latest", "windows-latest"]);
+        assert_eq!(values("language"), ["rust", "node"]);
+        for step in build.get("steps").unwrap().items().iter().skip(1) {
+            let condition = step.get("if").and_then(yaml::Node::as_str).unwrap();
+            assert!(condition.starts_with("matrix.language == "), "{}", condition);
+        }
+
+        // A single-language project has no language axis or conditions
+        let single = generate_github_actions(&PipelineOptions {
+            languages: vec![Language::Go],
+            ..options
+        });
+        assert!(!single.contains("matrix.language"));
+        assert!(single.contains("go test ./..."));
+        assert!(!single.contains("cargo test"));
+        assert!(single.contains("rhodibot check ."));
+    }
+
+    #[test]
+    fn test_options_check() {
+        let mut options = options(Platform::GitHub, PipelineLevel::Bronze, false);
+        options.languages = vec![Language::Node];
+        options.os = vec![Os::Linux];
+        assert!(options.check().is_ok());
+        options.platform = Platform::GitLab;
+        assert!(options.check().unwrap_err().contains("--os"));
+        options.os = Os::ALL.to_vec();
+        assert!(options.check().is_ok());
+        options.platform = Platform::Jenkins;
+        assert!(options.check().unwrap_err().contains("--lang"));
+        options.languages = Vec::new();
+        assert!(options.check().is_ok());
+    }
+
+    #[test]
+    fn test_validate_reports_missing_jobs_and_locations() {
+        let dir = temp_repo("missing_jobs");
+        std::fs::create_dir_all(dir.join(".github/workflows")).unwrap();
+        std::fs::write(
+            dir.join(".github/workflows/ci.yml"),
+            "on: push\njobs:\n  test:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout\n      - run: cargo test\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join(".gitlab-ci.yml"), "build:\n  script: [\n").unwrap();
+
+        let result = validate_pipeline(&dir);
+        assert!(!result.valid);
+        assert!(result
+            .errors
+            .contains(&".github/workflows/ci.yml:6: action 'actions/checkout' is not pinned to a version".to_string()));
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.starts_with(".gitlab-ci.yml:2: multi-line flow")));
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.starts_with("Missing required RSR job: lint")));
+        assert!(!result
+            .errors
+            .iter()
+            .any(|e| e.starts_with("Missing required RSR job: test")));
+        assert!(result
+            .warnings
+            .contains(&".github/workflows/ci.yml: no dependency caching configured".to_string()));
+
+        let empty = temp_repo("no_ci");
+        let result = validate_pipeline(&empty);
+        assert_eq!(result.errors, ["No CI/CD configuration found"]);
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_dir_all(&empty);
+    }
+
+    #[test]
+    fn test_structure_helpers_reject_malformed_input() {
+        let result = std::panic::catch_unwind(|| assert_valid_jenkinsfile("pipeline {\n    agent any\n"));
+        assert!(result.is_err());
+        let result = std::panic::catch_unwind(|| yaml_structure("jobs:\n   check:\n"));
+        assert!(result.is_err());
+    }
 }
@@ -3,7 +3,8 @@
 //! Generate RSR-compliant CI/CD configurations.
 
 use rhodium_pipeline::{
-    generate_pipeline, validate_pipeline, Platform, PipelineLevel, PipelineOptions, VERSION,
+    generate_pipeline, upgrade_pipeline, validate_pipeline, Language, Os, Platform, PipelineLevel, PipelineOptions,
+    VERSION,
 };
 use std::fs;
 use std::path::PathBuf;
@@ -19,6 +20,7 @@ fn print_help() {
 COMMANDS:
     generate <platform>    Generate CI/CD configuration
     validate [path]        Validate existing pipeline
+    upgrade [path]         Add missing RSR jobs to an existing pipeline
     list                   List available templates
 
 PLATFORMS:
@@ -31,14 +33,20 @@ fn print_help() {
     -o, --output <path>    Output path (default: stdout)
     -n, --name <name>      Project name (default: project)
     -l, --level <level>    RSR level: bronze, silver, gold (default: bronze)
+    --lang <list>          Languages: rust, node, python, go (default: rust)
+    --os <list>            Build OSes: linux, macos, windows (default: all)
     -f, --force            Overwrite existing files
+    --diff                 upgrade: print a unified diff instead of the file
     -h, --help             Print help information
     -V, --version          Print version information
 
 EXAMPLES:
     rhodium-pipeline generate github
     rhodium-pipeline generate gitlab -o .gitlab-ci.yml
+    rhodium-pipeline generate github -l gold --lang rust,node --os linux,macos,windows
     rhodium-pipeline validate .
+    rhodium-pipeline upgrade . --diff
+    rhodium-pipeline upgrade . -o .gitlab-ci.yml --force
     rhodium-pipeline list
 "#
     );
@@ -57,6 +65,12 @@ fn list_templates() {
     println!("    circle   - CircleCI configuration");
     println!("    jenkins  - Jenkinsfile");
     println!();
+    println!("  Languages (--lang, GitHub and GitLab):");
+    println!("    rust, node, python, go");
+    println!();
+    println!("  Operating systems (--os, GitHub):");
+    println!("    linux, macos, windows");
+    println!();
     println!("  Levels:");
     println!("    bronze   - Basic RSR compliance (default)");
     println!("    silver   - Extended checks and testing");
@@ -64,6 +78,26 @@ fn list_templates() {
     println!("    platinum - Full enterprise pipeline");
 }
 
+/// Parse a comma-separated list with `parse`, naming `what` in errors
+fn parse_list<T: PartialEq>(value: &str, what: &str, parse: fn(&str) -> Option<T>) -> Vec<T> {
+    let mut items = Vec::new();
+    for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        match parse(name) {
+            Some(item) if !items.contains(&item) => items.push(item),
+            Some(_) => {}
+            None => {
+                eprintln!("Unknown {}: {}", what, name);
+                process::exit(1);
+            }
+        }
+    }
+    if items.is_empty() {
+        eprintln!("Error: --{} needs at least one value", what);
+        process::exit(1);
+    }
+    items
+}
+
 fn main() {
     let args: Vec<String> = std::env::args().collect();
 
@@ -76,8 +110,12 @@ fn main() {
     let mut project_name = String::from("project");
     let mut level = PipelineLevel::Bronze;
     let mut force = false;
+    let mut diff = false;
+    let mut languages = vec![Language::Rust];
+    let mut os = Os::ALL.to_vec();
+    let mut positional: Vec<String> = Vec::new();
 
-    // Parse global options first
+    // Options may appear before or after the command and its argument
     let mut i = 1;
     while i < args.len() {
         match args[i].as_str() {
@@ -104,41 +142,55 @@ fn main() {
             "-l" | "--level" => {
                 i += 1;
                 if i < args.len() {
-                    level = match args[i].as_str() {
-                        "bronze" => PipelineLevel::Bronze,
-                        "silver" => PipelineLevel::Silver,
-                        "gold" => PipelineLevel::Gold,
-                        "platinum" => PipelineLevel::Platinum,
-                        _ => {
+                    level = match PipelineLevel::parse(&args[i]) {
+                        Some(level) => level,
+                        None => {
                             eprintln!("Unknown level: {}", args[i]);
                             process::exit(1);
                         }
                     };
                 }
             }
+            "--lang" => {
+                i += 1;
+                if i < args.len() {
+                    languages = parse_list(&args[i], "lang", Language::from_str);
+                }
+            }
+            "--os" => {
+                i += 1;
+                if i < args.len() {
+                    os = parse_list(&args[i], "os", Os::from_str);
+                }
+            }
             "-f" | "--force" => force = true,
-            _ => break,
+            "--diff" => diff = true,
+            arg if arg.starts_with('-') && arg.len() > 1 => {
+                eprintln!("Unknown option: {}", arg);
+                print_help();
+                process::exit(1);
+            }
+            arg => positional.push(arg.to_string()),
         }
         i += 1;
     }
 
-    if i >= args.len() {
+    let Some(command) = positional.first() else {
         print_help();
         process::exit(0);
-    }
+    };
 
-    match args[i].as_str() {
+    match command.as_str() {
         "generate" => {
-            i += 1;
-            if i >= args.len() {
+            let Some(name) = positional.get(1) else {
                 eprintln!("Error: Platform required. Use: github, gitlab, circle, jenkins");
                 process::exit(1);
-            }
+            };
 
-            let platform = match Platform::from_str(&args[i]) {
+            let platform = match Platform::from_str(name) {
                 Some(p) => p,
                 None => {
-                    eprintln!("Unknown platform: {}", args[i]);
+                    eprintln!("Unknown platform: {}", name);
                     process::exit(1);
                 }
             };
@@ -149,7 +201,13 @@ fn main() {
                 include_deploy: false,
                 project_name,
                 rust_version: String::from("stable"),
+                languages,
+                os,
             };
+            if let Err(e) = options.check() {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
 
             let config = generate_pipeline(&options);
 
@@ -174,12 +232,7 @@ fn main() {
             }
         }
         "validate" => {
-            i += 1;
-            let path = if i < args.len() {
-                PathBuf::from(&args[i])
-            } else {
-                PathBuf::from(".")
-            };
+            let path = PathBuf::from(positional.get(1).map_or(".", String::as_str));
 
             let result = validate_pipeline(&path);
 
@@ -205,6 +258,44 @@ fn main() {
                 process::exit(1);
             }
         }
+        "upgrade" => {
+            let path = PathBuf::from(positional.get(1).map_or(".", String::as_str));
+            let options = PipelineOptions {
+                level,
+                project_name,
+                languages,
+                ..Default::default()
+            };
+
+            let upgrade = match upgrade_pipeline(&path, &options) {
+                Ok(upgrade) => upgrade,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            };
+            if upgrade.is_unchanged() {
+                eprintln!("All required RSR jobs are present in {}.", upgrade.file);
+                process::exit(0);
+            }
+            eprintln!("Adding to {}: {}", upgrade.file, upgrade.added.join(", "));
+
+            if diff {
+                print!("{}", upgrade.diff());
+            } else if let Some(path) = output_path {
+                if path.exists() && !force {
+                    eprintln!("Error: {} already exists. Use --force to overwrite.", path.display());
+                    process::exit(1);
+                }
+                if let Err(e) = fs::write(&path, &upgrade.patched) {
+                    eprintln!("Error writing {}: {}", path.display(), e);
+                    process::exit(1);
+                }
+                println!("Upgraded: {}", path.display());
+            } else {
+                print!("{}", upgrade.patched);
+            }
+        }
         "list" => {
             list_templates();
         }
@@ -0,0 +1,196 @@
+//! Languages and operating systems a pipeline builds for
+//!
+//! Each [`Language`] knows how to set up its toolchain and which commands
+//! format-check, lint, test and build a project written in it. The
+//! generators combine these into per-language jobs, or a build matrix when a
+//! project uses more than one language or targets several [`Os`]es.
+
+/// A language the generated pipeline can check, test and build
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Node,
+    Python,
+    Go,
+}
+
+impl Language {
+    /// All supported languages
+    pub const ALL: [Language; 4] = [Language::Rust, Language::Node, Language::Python, Language::Go];
+
+    /// Parse a language from its name or a common alias
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "rust" | "rs" => Some(Language::Rust),
+            "node" | "nodejs" | "javascript" | "js" | "typescript" | "ts" => Some(Language::Node),
+            "python" | "py" => Some(Language::Python),
+            "go" | "golang" => Some(Language::Go),
+            _ => None,
+        }
+    }
+
+    /// Name used in job names and matrix values
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::Rust => "rust",
+            Language::Node => "node",
+            Language::Python => "python",
+            Language::Go => "go",
+        }
+    }
+
+    /// Command that installs the project's dependencies, if one is needed
+    pub fn install_command(&self) -> Option<&'static str> {
+        match self {
+            Language::Rust | Language::Go => None,
+            Language::Node => Some("npm ci"),
+            Language::Python => Some("python -m pip install ruff pytest -e ."),
+        }
+    }
+
+    pub fn format_command(&self) -> &'static str {
+        match self {
+            Language::Rust => "cargo fmt --check",
+            Language::Node => "npx prettier --check .",
+            Language::Python => "ruff format --check .",
+            Language::Go => "test -z \"$(gofmt -l .)\"",
+        }
+    }
+
+    pub fn lint_command(&self) -> &'static str {
+        match self {
+            Language::Rust => "cargo clippy -- -D warnings",
+            Language::Node => "npm run lint --if-present",
+            Language::Python => "ruff check .",
+            Language::Go => "go vet ./...",
+        }
+    }
+
+    pub fn test_command(&self) -> &'static str {
+        match self {
+            Language::Rust => "cargo test --verbose",
+            Language::Node => "npm test",
+            Language::Python => "python -m pytest",
+            Language::Go => "go test ./...",
+        }
+    }
+
+    pub fn build_command(&self) -> &'static str {
+        match self {
+            Language::Rust => "cargo build --release",
+            Language::Node => "npm run build --if-present",
+            Language::Python => "python -m pip wheel --no-deps -w dist .",
+            Language::Go => "go build ./...",
+        }
+    }
+
+    /// GitHub Actions steps that install the toolchain and cache
+    /// dependencies, indented as job steps
+    pub fn github_setup(&self, rust_version: &str) -> String {
+        match self {
+            Language::Rust => format!(
+                "      - uses: dtolnay/rust-action@{}\n      - uses: Swatinem/rust-cache@v2\n",
+                rust_version
+            ),
+            Language::Node => "      - uses: actions/setup-node@v4\n\
+                               \x20       with:\n\
+                               \x20         node-version: 20\n\
+                               \x20         cache: npm\n"
+                .to_string(),
+            Language::Python => "      - uses: actions/setup-python@v5\n\
+                                 \x20       with:\n\
+                                 \x20         python-version: '3.12'\n\
+                                 \x20         cache: pip\n"
+                .to_string(),
+            Language::Go => "      - uses: actions/setup-go@v5\n\
+                             \x20       with:\n\
+                             \x20         go-version: stable\n\
+                             \x20         cache: true\n"
+                .to_string(),
+        }
+    }
+
+    /// Container image for GitLab CI jobs
+    pub fn gitlab_image(&self, rust_version: &str) -> String {
+        match self {
+            Language::Rust => format!("rust:{}", rust_version),
+            Language::Node => String::from("node:20"),
+            Language::Python => String::from("python:3.12"),
+            Language::Go => String::from("golang:1.22"),
+        }
+    }
+
+    /// GitLab CI variable that moves the package cache into the project
+    /// directory, where `cache:` can save it
+    pub fn gitlab_cache_variable(&self) -> (&'static str, &'static str) {
+        match self {
+            Language::Rust => ("CARGO_HOME", "${CI_PROJECT_DIR}/.cargo"),
+            Language::Node => ("npm_config_cache", "${CI_PROJECT_DIR}/.npm"),
+            Language::Python => ("PIP_CACHE_DIR", "${CI_PROJECT_DIR}/.cache/pip"),
+            Language::Go => ("GOPATH", "${CI_PROJECT_DIR}/.go"),
+        }
+    }
+
+    /// Paths GitLab CI caches between jobs
+    pub fn gitlab_cache_paths(&self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &[".cargo/", "target/"],
+            Language::Node => &[".npm/"],
+            Language::Python => &[".cache/pip/"],
+            Language::Go => &[".go/pkg/mod/"],
+        }
+    }
+}
+
+/// An operating system to build on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Os {
+    Linux,
+    MacOS,
+    Windows,
+}
+
+impl Os {
+    /// All supported operating systems
+    pub const ALL: [Os; 3] = [Os::Linux, Os::MacOS, Os::Windows];
+
+    /// Parse an operating system from its name or a common alias
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "linux" | "ubuntu" => Some(Os::Linux),
+            "macos" | "mac" | "darwin" | "osx" => Some(Os::MacOS),
+            "windows" | "win" => Some(Os::Windows),
+            _ => None,
+        }
+    }
+
+    /// GitHub-hosted runner label
+    pub fn github_runner(&self) -> &'static str {
+        match self {
+            Os::Linux => "ubuntu-latest",
+            Os::MacOS => "macos-latest",
+            Os::Windows => "windows-latest",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing() {
+        assert_eq!(Language::from_str("Rust"), Some(Language::Rust));
+        assert_eq!(Language::from_str("ts"), Some(Language::Node));
+        assert_eq!(Language::from_str("golang"), Some(Language::Go));
+        assert_eq!(Language::from_str("cobol"), None);
+        assert_eq!(Os::from_str("darwin"), Some(Os::MacOS));
+        assert_eq!(Os::from_str("WINDOWS"), Some(Os::Windows));
+        assert_eq!(Os::from_str("plan9"), None);
+        for language in Language::ALL {
+            assert_eq!(Language::from_str(language.name()), Some(language));
+        }
+    }
+}
@@ -0,0 +1,621 @@
+//! Upgrading existing pipelines
+//!
+//! Rather than regenerating a pipeline from scratch, `upgrade` adds only the
+//! RSR-required jobs (see [`REQUIRED_JOBS`](crate::validate::REQUIRED_JOBS)) that no CI file provides yet.
+//! The primary configuration file is patched as text, so comments, ordering
+//! and formatting of everything already there are kept:
+//!
+//! - GitLab CI: jobs are appended, and their stages added to `stages:`
+//! - GitHub Actions and CircleCI: jobs are inserted at the end of `jobs:`;
+//!   on CircleCI they also get a workflow of their own
+//! - Jenkins: stages are inserted at the end of `stages { }`
+
+use crate::stack::Language;
+use crate::validate::{self, CiKind, RequiredJob};
+use crate::{github_run, github_setup, yaml, PipelineOptions};
+
+/// The result of upgrading one configuration file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Upgrade {
+    /// Path of the patched file, relative to the repository
+    pub file: String,
+    pub original: String,
+    pub patched: String,
+    /// Names of the jobs (or Jenkins stages) that were added
+    pub added: Vec<String>,
+}
+
+impl Upgrade {
+    /// Whether the pipeline already had every required job
+    pub fn is_unchanged(&self) -> bool {
+        self.added.is_empty()
+    }
+
+    /// The change as a unified diff
+    pub fn diff(&self) -> String {
+        unified_diff(&self.original, &self.patched, &self.file)
+    }
+}
+
+/// Add the jobs missing from `files` to the first of them
+///
+/// `files` are `(relative path, kind, contents)`, as found in a repository;
+/// GitLab CI is preferred as the file to patch, then the first GitHub
+/// workflow, CircleCI and Jenkins. Jobs use the first of the options'
+/// languages.
+pub fn upgrade(
+    files: &[(String, CiKind, String)],
+    options: &PipelineOptions,
+) -> Result<Upgrade, String> {
+    let mut jobs = Vec::new();
+    for (relative, kind, source) in files {
+        let analysis = validate::analyze(*kind, source);
+        if let Some(error) = analysis
+            .findings
+            .iter()
+            .find(|f| f.severity == validate::Severity::Error && f.line.is_some())
+        {
+            return Err(format!(
+                "{}:{}: {}",
+                relative,
+                error.line.unwrap_or(0),
+                error.message
+            ));
+        }
+        jobs.extend(analysis.jobs);
+    }
+
+    let rank = |kind: CiKind| match kind {
+        CiKind::GitLab => 0,
+        CiKind::GitHub => 1,
+        CiKind::CircleCI => 2,
+        CiKind::Jenkins => 3,
+    };
+    let Some((file, kind, original)) = files.iter().min_by_key(|(_, kind, _)| rank(*kind)) else {
+        return Err("No CI/CD configuration found".to_string());
+    };
+
+    let missing = validate::missing_jobs(&jobs);
+    let language = options.languages()[0];
+    let mut upgrade = Upgrade {
+        file: file.clone(),
+        original: original.clone(),
+        patched: original.clone(),
+        added: Vec::new(),
+    };
+    if missing.is_empty() {
+        return Ok(upgrade);
+    }
+
+    let existing: Vec<String> = jobs.iter().map(|job| job.name.clone()).collect();
+    let names: Vec<String> = missing
+        .iter()
+        .map(|required| unique_name(required.name, &existing))
+        .collect();
+    let mut lines: Vec<String> = original.lines().map(String::from).collect();
+    match kind {
+        CiKind::GitLab => patch_gitlab(&mut lines, &missing, &names, language, options)?,
+        CiKind::GitHub => {
+            let text = missing
+                .iter()
+                .zip(&names)
+                .map(|(required, name)| github_job(required, name, language, options))
+                .collect::<Vec<_>>()
+                .join("\n");
+            insert_into_block(&mut lines, "jobs", &text)?;
+        }
+        CiKind::CircleCI => {
+            let text = missing
+                .iter()
+                .zip(&names)
+                .map(|(required, name)| circleci_job(required, name, language, options))
+                .collect::<Vec<_>>()
+                .join("\n");
+            insert_into_block(&mut lines, "jobs", &text)?;
+            let mut workflow = String::from("  rsr-required:\n    jobs:\n");
+            for name in &names {
+                workflow.push_str(&format!("      - {}\n", name));
+            }
+            if insert_into_block(&mut lines, "workflows", &workflow).is_err() {
+                lines.push(String::new());
+                lines.push("workflows:".to_string());
+                lines.extend(workflow.lines().map(String::from));
+            }
+        }
+        CiKind::Jenkins => patch_jenkinsfile(&mut lines, &missing, language)?,
+    }
+
+    upgrade.patched = lines.join("\n");
+    upgrade.patched.push('\n');
+    upgrade.added = if *kind == CiKind::Jenkins {
+        missing.iter().map(|required| jenkins_stage_name(required).to_string()).collect()
+    } else {
+        names
+    };
+    Ok(upgrade)
+}
+
+/// `name`, or `name-2`, `name-3`, … if a job already has it
+fn unique_name(name: &str, existing: &[String]) -> String {
+    let mut candidate = name.to_string();
+    let mut n = 2;
+    while existing.iter().any(|job| *job == candidate) {
+        candidate = format!("{}-{}", name, n);
+        n += 1;
+    }
+    candidate
+}
+
+/// Shell commands a required job runs
+fn commands(required: &RequiredJob, language: Language) -> Vec<&'static str> {
+    match required.name {
+        "fmt" => vec![language.format_command()],
+        "lint" => vec![language.lint_command()],
+        "test" => vec![language.test_command()],
+        "build" => vec![language.build_command()],
+        _ => vec!["cargo install rhodibot --locked", "rhodibot check ."],
+    }
+}
+
+/// Whether the job runs in the language's toolchain rather than Rust's
+fn uses_language(required: &RequiredJob) -> bool {
+    required.name != "verify"
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn is_significant(line: &str) -> bool {
+    let text = line.trim_start();
+    !text.is_empty() && !text.starts_with('#')
+}
+
+/// Insert `text` (written with its entries at two spaces) as the last
+/// entries of the top-level mapping `key`, re-indented to match the
+/// entries already there
+fn insert_into_block(lines: &mut Vec<String>, key: &str, text: &str) -> Result<(), String> {
+    let header = format!("{}:", key);
+    let Some(start) = lines
+        .iter()
+        .position(|line| line.split('#').next().unwrap_or("").trim_end() == header)
+    else {
+        return Err(format!("no top-level '{}:' block to add jobs to", key));
+    };
+
+    // The block ends at the next top-level line; trailing blank lines and
+    // comments stay after the inserted text
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| is_significant(line) && indent_of(line) == 0)
+        .map_or(lines.len(), |offset| start + 1 + offset);
+    let mut insert_at = end;
+    while insert_at > start + 1 && !is_significant(&lines[insert_at - 1]) {
+        insert_at -= 1;
+    }
+
+    let indent = lines[start + 1..end]
+        .iter()
+        .find(|line| is_significant(line))
+        .map_or(2, |line| indent_of(line));
+    let extra = " ".repeat(indent.saturating_sub(2));
+    let mut block = vec![String::new()];
+    for line in text.lines() {
+        if line.is_empty() {
+            block.push(String::new());
+        } else {
+            block.push(format!("{}{}", extra, line));
+        }
+    }
+    if insert_at == start + 1 {
+        block.remove(0);
+    }
+    lines.splice(insert_at..insert_at, block);
+    Ok(())
+}
+
+fn github_job(
+    required: &RequiredJob,
+    name: &str,
+    language: Language,
+    options: &PipelineOptions,
+) -> String {
+    let toolchain = if uses_language(required) {
+        language
+    } else {
+        Language::Rust
+    };
+    let mut job = format!(
+        "  {}:\n    name: RSR {}\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout@v4\n",
+        name, required.role
+    );
+    job.push_str(&github_setup(options, toolchain, ""));
+    for command in commands(required, language) {
+        job.push_str(&github_run(command, command));
+    }
+    job
+}
+
+fn circleci_job(
+    required: &RequiredJob,
+    name: &str,
+    language: Language,
+    options: &PipelineOptions,
+) -> String {
+    let toolchain = if uses_language(required) {
+        language
+    } else {
+        Language::Rust
+    };
+    let mut job = format!(
+        "  {}:\n    docker:\n      - image: {}\n    steps:\n      - checkout\n",
+        name,
+        toolchain.gitlab_image(&options.rust_version)
+    );
+    let install = toolchain.install_command().filter(|_| uses_language(required));
+    for command in install.into_iter().chain(commands(required, language)) {
+        job.push_str(&format!(
+            "      - run:\n          name: {}\n          command: {}\n",
+            command, command
+        ));
+    }
+    job
+}
+
+fn patch_gitlab(
+    lines: &mut Vec<String>,
+    missing: &[&RequiredJob],
+    names: &[String],
+    language: Language,
+    options: &PipelineOptions,
+) -> Result<(), String> {
+    let source = lines.join("\n");
+    let doc = yaml::parse(&source).map_err(|e| format!(".gitlab-ci.yml: {}", e))?;
+
+    let mut needed: Vec<&str> = Vec::new();
+    for required in missing {
+        if !needed.contains(&required.stage) {
+            needed.push(required.stage);
+        }
+    }
+    match doc.get("stages") {
+        Some(stages) => {
+            let present: Vec<&str> = stages.items().iter().filter_map(yaml::Node::as_str).collect();
+            let new: Vec<&str> = needed
+                .iter()
+                .copied()
+                .filter(|stage| !present.contains(stage))
+                .collect();
+            if !new.is_empty() {
+                add_stages(lines, stages, &new)?;
+            }
+        }
+        None => {
+            // Declaring stages replaces GitLab's defaults, so keep them
+            let mut stages: Vec<&str> = Vec::new();
+            if needed.contains(&"check") {
+                stages.push("check");
+            }
+            stages.extend(["build", "test", "deploy"]);
+            if needed.contains(&"verify") {
+                stages.push("verify");
+            }
+            let at = lines.iter().position(|line| is_significant(line)).unwrap_or(0);
+            let mut block = vec!["stages:".to_string()];
+            block.extend(stages.iter().map(|stage| format!("  - {}", stage)));
+            block.push(String::new());
+            lines.splice(at..at, block);
+        }
+    }
+
+    while lines.last().map_or(false, |line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    lines.push(String::new());
+    lines.push("# RSR-required jobs added by rhodium-pipeline upgrade".to_string());
+    for (required, name) in missing.iter().zip(names) {
+        let toolchain = if uses_language(required) {
+            language
+        } else {
+            Language::Rust
+        };
+        lines.push(format!("{}:", name));
+        lines.push(format!("  image: {}", toolchain.gitlab_image(&options.rust_version)));
+        lines.push(format!("  stage: {}", required.stage));
+        lines.push("  script:".to_string());
+        let install = toolchain.install_command().filter(|_| uses_language(required));
+        for command in install.into_iter().chain(commands(required, language)) {
+            lines.push(format!("    - {}", command));
+        }
+        lines.push(String::new());
+    }
+    lines.pop();
+    Ok(())
+}
+
+/// Add `new` to the `stages:` sequence, in either block or flow style;
+/// `check` goes first so that fast checks gate the rest, anything else last
+fn add_stages(lines: &mut Vec<String>, stages: &yaml::Node, new: &[&str]) -> Result<(), String> {
+    let (Some(first), Some(last)) = (stages.items().first(), stages.items().last()) else {
+        return Err(".gitlab-ci.yml: 'stages:' is empty".to_string());
+    };
+    let (front, back): (Vec<&str>, Vec<&str>) = new.iter().partition(|stage| **stage == "check");
+
+    let line = &mut lines[last.line - 1];
+    if let (Some(open), Some(close)) = (line.find('['), line.rfind(']')) {
+        for stage in &back {
+            line.insert_str(close, &format!(", {}", stage));
+        }
+        for stage in &front {
+            line.insert_str(open + 1, &format!("{}, ", stage));
+        }
+        return Ok(());
+    }
+    let prefix = line[..indent_of(line)].to_string();
+    let item = |stage: &&str| format!("{}- {}", prefix, stage);
+    lines.splice(last.line..last.line, back.iter().map(item));
+    lines.splice(first.line - 1..first.line - 1, front.iter().map(item));
+    Ok(())
+}
+
+fn jenkins_stage_name(required: &RequiredJob) -> &'static str {
+    match required.name {
+        "fmt" => "Format check",
+        "lint" => "Lint",
+        "test" => "Test",
+        "build" => "Build",
+        _ => "RSR Verify",
+    }
+}
+
+fn patch_jenkinsfile(
+    lines: &mut Vec<String>,
+    missing: &[&RequiredJob],
+    language: Language,
+) -> Result<(), String> {
+    let Some(start) = lines.iter().position(|line| line.trim_start().starts_with("stages {")) else {
+        return Err("Jenkinsfile: no 'stages { }' block to add stages to".to_string());
+    };
+
+    // Find the line closing the stages block, ignoring braces in strings
+    let mut depth = 0i32;
+    let mut close = None;
+    'lines: for (n, line) in lines.iter().enumerate().skip(start) {
+        let mut quote = None;
+        for c in line.chars() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None => match c {
+                    '\'' | '"' => quote = Some(c),
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            close = Some(n);
+                            break 'lines;
+                        }
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+    let Some(close) = close else {
+        return Err("Jenkinsfile: unterminated 'stages { }' block".to_string());
+    };
+
+    let pad = " ".repeat(indent_of(&lines[start]) + 4);
+    let mut block = Vec::new();
+    for required in missing {
+        block.push(String::new());
+        block.push(format!("{}stage('{}') {{", pad, jenkins_stage_name(required)));
+        block.push(format!("{}    steps {{", pad));
+        for command in commands(required, language) {
+            block.push(format!("{}        sh '{}'", pad, command));
+        }
+        block.push(format!("{}    }}", pad));
+        block.push(format!("{}}}", pad));
+    }
+    lines.splice(close..close, block);
+    Ok(())
+}
+
+/// A unified diff between `old` and `new`, with three lines of context
+pub fn unified_diff(old: &str, new: &str, file: &str) -> String {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    if a == b {
+        return String::new();
+    }
+
+    // Longest common subsequence table, filled from the end
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // Edit script as (tag, old index, new index)
+    let mut ops: Vec<(char, usize, usize)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((' ', i, j));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', i, j));
+            i += 1;
+        } else {
+            ops.push(('+', i, j));
+            j += 1;
+        }
+    }
+
+    const CONTEXT: usize = 3;
+    let mut out = format!("--- a/{0}\n+++ b/{0}\n", file);
+    let changes: Vec<usize> = (0..ops.len()).filter(|&k| ops[k].0 != ' ').collect();
+    let mut k = 0;
+    while k < changes.len() {
+        // Grow the hunk while the next change is within reach of context
+        let first = changes[k];
+        let mut last = first;
+        while k + 1 < changes.len() && changes[k + 1] <= last + 2 * CONTEXT + 1 {
+            k += 1;
+            last = changes[k];
+        }
+        k += 1;
+        let start = first.saturating_sub(CONTEXT);
+        let end = (last + CONTEXT + 1).min(ops.len());
+        let hunk = &ops[start..end];
+
+        let old_count = hunk.iter().filter(|op| op.0 != '+').count();
+        let new_count = hunk.iter().filter(|op| op.0 != '-').count();
+        let range = |start: usize, count: usize| match count {
+            0 => format!("{},0", start),
+            1 => format!("{}", start + 1),
+            _ => format!("{},{}", start + 1, count),
+        };
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(hunk[0].1, old_count),
+            range(hunk[0].2, new_count)
+        ));
+        for &(tag, i, j) in hunk {
+            let text = if tag == '+' { b[j] } else { a[i] };
+            out.push_str(&format!("{}{}\n", tag, text));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_pipeline, Platform};
+
+    fn run(kind: CiKind, source: &str) -> Upgrade {
+        let files = [(String::from("ci"), kind, source.to_string())];
+        upgrade(&files, &PipelineOptions::default()).unwrap()
+    }
+
+    /// The patched file parses, keeps the original lines and has every job
+    fn assert_complete(kind: CiKind, upgrade: &Upgrade) {
+        let analysis = validate::analyze(kind, &upgrade.patched);
+        assert!(
+            analysis.findings.iter().all(|f| f.line.is_none()),
+            "{:?}\n{}",
+            analysis.findings,
+            upgrade.patched
+        );
+        let missing: Vec<_> = validate::missing_jobs(&analysis.jobs).iter().map(|r| r.role).collect();
+        assert!(missing.is_empty(), "still missing {:?}:\n{}", missing, upgrade.patched);
+        let mut patched = upgrade.patched.lines();
+        // Only a flow-style `stages:` line is edited in place
+        for line in upgrade.original.lines().filter(|l| !l.starts_with("stages: [")) {
+            assert!(patched.any(|p| p == line), "lost line {:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_gitlab_block_stages() {
+        let source = "# Existing pipeline\nstages:\n  - test\n  - deploy\n\nunit:\n  stage: test\n  image: rust:1.75\n  script:\n    - cargo test\n";
+        let upgrade = run(CiKind::GitLab, source);
+        assert_eq!(upgrade.added, ["fmt", "lint", "build", "verify"]);
+        assert_complete(CiKind::GitLab, &upgrade);
+        assert!(upgrade
+            .patched
+            .starts_with("# Existing pipeline\nstages:\n  - check\n  - test\n  - deploy\n  - build\n  - verify\n"));
+        assert!(upgrade.patched.contains("\nfmt:\n  image: rust:stable\n  stage: check\n"));
+    }
+
+    #[test]
+    fn test_gitlab_flow_and_default_stages() {
+        let upgrade = run(CiKind::GitLab, "stages: [build, test]\ncompile:\n  stage: build\n  script: cargo build\n");
+        assert!(upgrade.patched.starts_with("stages: [check, build, test, verify]\n"));
+        assert_complete(CiKind::GitLab, &upgrade);
+
+        let upgrade = run(CiKind::GitLab, "# header\ntest:\n  script: cargo test\n");
+        assert!(upgrade
+            .patched
+            .starts_with("# header\nstages:\n  - check\n  - build\n  - test\n  - deploy\n  - verify\n\ntest:"));
+        assert_eq!(upgrade.added, ["fmt", "lint", "build", "verify"]);
+        assert_complete(CiKind::GitLab, &upgrade);
+    }
+
+    #[test]
+    fn test_github_insertion_keeps_indentation_and_later_keys() {
+        let source = "on: push\njobs:\n    test:\n        runs-on: ubuntu-latest\n        steps:\n            - uses: actions/checkout@v4\n            - run: cargo test\n\n# trailing comment\npermissions:\n    contents: read\n";
+        let upgrade = run(CiKind::GitHub, source);
+        assert_complete(CiKind::GitHub, &upgrade);
+        assert!(upgrade.patched.contains("\n    fmt:\n      name: RSR format check\n"));
+        assert!(upgrade.patched.ends_with("\n\n# trailing comment\npermissions:\n    contents: read\n"));
+        let doc = yaml::parse(&upgrade.patched).unwrap();
+        assert_eq!(doc.get("jobs").unwrap().entries().len(), 5);
+    }
+
+    #[test]
+    fn test_circleci_adds_jobs_and_workflow() {
+        let source = "version: 2.1\njobs:\n  build:\n    docker:\n      - image: rust:1.75\n    steps:\n      - checkout\n      - run: cargo build\nworkflows:\n  main:\n    jobs:\n      - build\n";
+        let upgrade = run(CiKind::CircleCI, source);
+        assert_complete(CiKind::CircleCI, &upgrade);
+        let doc = yaml::parse(&upgrade.patched).unwrap();
+        let workflow = doc.get("workflows").unwrap().get("rsr-required").unwrap();
+        let jobs: Vec<_> = workflow.get("jobs").unwrap().items().iter().filter_map(yaml::Node::as_str).collect();
+        assert_eq!(jobs, ["fmt", "lint", "test", "verify"]);
+    }
+
+    #[test]
+    fn test_jenkins_stages_and_unique_names() {
+        let source = "pipeline {\n    agent any\n    stages {\n        stage('Build') {\n            steps {\n                sh 'echo \"{\"; cargo build'\n            }\n        }\n    }\n}\n";
+        let upgrade = run(CiKind::Jenkins, source);
+        assert_eq!(upgrade.added, ["Format check", "Lint", "Test", "RSR Verify"]);
+        assert_complete(CiKind::Jenkins, &upgrade);
+        assert!(upgrade.patched.ends_with("                sh 'rhodibot check .'\n            }\n        }\n    }\n}\n"));
+
+        assert_eq!(unique_name("test", &["test".to_string(), "test-2".to_string()]), "test-3");
+    }
+
+    #[test]
+    fn test_complete_pipeline_is_unchanged() {
+        let options = PipelineOptions {
+            platform: Platform::GitLab,
+            ..Default::default()
+        };
+        let source = generate_pipeline(&options);
+        let upgrade = run(CiKind::GitLab, &source);
+        assert!(upgrade.is_unchanged());
+        assert_eq!(upgrade.patched, source);
+        assert_eq!(upgrade.diff(), "");
+    }
+
+    #[test]
+    fn test_parse_errors_are_reported() {
+        let files = [(String::from(".gitlab-ci.yml"), CiKind::GitLab, String::from("a: 1\na: 2\n"))];
+        let err = upgrade(&files, &PipelineOptions::default()).unwrap_err();
+        assert_eq!(err, ".gitlab-ci.yml:2: duplicate key 'a' (column 1)");
+        assert!(upgrade(&[], &PipelineOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+        assert_eq!(
+            unified_diff(old, new, "x.yml"),
+            "--- a/x.yml\n+++ b/x.yml\n\
+             @@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n\
+             @@ -9,3 +9,4 @@\n i\n j\n k\n+l\n"
+        );
+        assert_eq!(
+            unified_diff("", "x\n", "f"),
+            "--- a/f\n+++ b/f\n@@ -0,0 +1 @@\n+x\n"
+        );
+    }
+}
@@ -0,0 +1,461 @@
+//! Deep validation of existing CI configuration
+//!
+//! Each configuration file is read with the [`yaml`](crate::yaml) reader (a
+//! Jenkinsfile is searched as text) and checked for:
+//!
+//! - the jobs RSR requires: format check, lint, test, build and compliance
+//!   verification (across all files, since a project may split its pipeline)
+//! - dependency caching
+//! - actions and container images pinned to a version
+
+use crate::yaml::{self, Node};
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One problem found in a configuration file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Finding {
+    fn error(line: Option<usize>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            line,
+            message: message.into(),
+        }
+    }
+
+    fn warning(line: Option<usize>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+/// Which CI system a file belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiKind {
+    GitHub,
+    GitLab,
+    CircleCI,
+    Jenkins,
+}
+
+/// A job found in a configuration file
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub name: String,
+    pub line: usize,
+    /// Lowercased name, stage, commands and other values, for role matching
+    text: String,
+}
+
+/// What was learned from one configuration file
+#[derive(Debug, Clone, Default)]
+pub struct Analysis {
+    pub jobs: Vec<Job>,
+    pub findings: Vec<Finding>,
+}
+
+/// A job RSR requires, recognised by keywords in the job's name, stage or
+/// commands
+pub struct RequiredJob {
+    pub role: &'static str,
+    pub keywords: &'static [&'static str],
+    /// Name of the job `upgrade` adds when this one is missing
+    pub name: &'static str,
+    /// Stage the added job runs in
+    pub stage: &'static str,
+}
+
+/// The jobs every RSR pipeline must have
+pub const REQUIRED_JOBS: &[RequiredJob] = &[
+    RequiredJob {
+        role: "format check",
+        keywords: &["fmt", "format"],
+        name: "fmt",
+        stage: "check",
+    },
+    RequiredJob {
+        role: "lint",
+        keywords: &["clippy", "lint"],
+        name: "lint",
+        stage: "check",
+    },
+    RequiredJob {
+        role: "test",
+        keywords: &["test"],
+        name: "test",
+        stage: "test",
+    },
+    RequiredJob {
+        role: "build",
+        keywords: &["build"],
+        name: "build",
+        stage: "build",
+    },
+    RequiredJob {
+        role: "compliance verification",
+        keywords: &["rhodibot", "rsr", "verify"],
+        name: "verify",
+        stage: "verify",
+    },
+];
+
+/// GitLab top-level keys that are not jobs
+const GITLAB_RESERVED_KEYS: &[&str] = &[
+    "default",
+    "include",
+    "stages",
+    "variables",
+    "workflow",
+    "image",
+    "services",
+    "cache",
+    "before_script",
+    "after_script",
+];
+
+/// Branch-like refs that move under a pinned action
+const MOVING_REFS: &[&str] = &["main", "master", "head", "latest", "trunk", "develop"];
+
+/// Analyse one configuration file
+pub fn analyze(kind: CiKind, source: &str) -> Analysis {
+    if kind == CiKind::Jenkins {
+        return analyze_jenkinsfile(source);
+    }
+    let doc = match yaml::parse(source) {
+        Ok(doc) => doc,
+        Err(e) => {
+            return Analysis {
+                jobs: Vec::new(),
+                findings: vec![Finding::error(
+                    Some(e.line),
+                    format!("{} (column {})", e.message, e.column),
+                )],
+            }
+        }
+    };
+
+    let mut analysis = Analysis {
+        jobs: jobs(kind, &doc),
+        findings: Vec::new(),
+    };
+    if analysis.jobs.is_empty() {
+        analysis
+            .findings
+            .push(Finding::error(None, "no jobs defined"));
+        return analysis;
+    }
+    if !has_cache(&doc) {
+        analysis
+            .findings
+            .push(Finding::warning(None, "no dependency caching configured"));
+    }
+    check_pins(&doc, &mut analysis.findings);
+    analysis
+}
+
+/// Required RSR jobs missing from all of `jobs`
+pub fn missing_jobs(jobs: &[Job]) -> Vec<&'static RequiredJob> {
+    REQUIRED_JOBS
+        .iter()
+        .filter(|required| {
+            !jobs.iter().any(|job| {
+                required
+                    .keywords
+                    .iter()
+                    .any(|keyword| job.text.contains(keyword))
+            })
+        })
+        .collect()
+}
+
+fn job(name: &str, node: &Node) -> Job {
+    let mut text = name.to_lowercase();
+    for (_, value) in node.scalars() {
+        text.push('\n');
+        text.push_str(&value.to_lowercase());
+    }
+    Job {
+        name: name.to_string(),
+        line: node.line,
+        text,
+    }
+}
+
+fn jobs(kind: CiKind, doc: &Node) -> Vec<Job> {
+    match kind {
+        CiKind::GitLab => doc
+            .entries()
+            .iter()
+            .filter(|(name, node)| {
+                !name.starts_with('.')
+                    && !GITLAB_RESERVED_KEYS.contains(&name.as_str())
+                    && !node.entries().is_empty()
+            })
+            .map(|(name, node)| job(name, node))
+            .collect(),
+        _ => doc
+            .get("jobs")
+            .map(|jobs| {
+                jobs.entries()
+                    .iter()
+                    .map(|(name, node)| job(name, node))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+fn has_cache(doc: &Node) -> bool {
+    doc.pairs().iter().any(|(key, value)| match *key {
+        "cache" | "restore_cache" | "save_cache" => true,
+        "uses" => value.as_str().map_or(false, |action| {
+            action.starts_with("actions/cache") || action.contains("rust-cache")
+        }),
+        _ => false,
+    })
+}
+
+fn check_pins(doc: &Node, findings: &mut Vec<Finding>) {
+    for (key, value) in doc.pairs() {
+        let target = match (key, value.as_str()) {
+            ("uses", Some(action)) => {
+                if let Some(image) = action.strip_prefix("docker://") {
+                    check_image(value.line, image, findings);
+                } else {
+                    check_action(value.line, action, findings);
+                }
+                continue;
+            }
+            ("image", Some(image)) | ("container", Some(image)) => image,
+            // `image: { name: ... }` in GitLab
+            ("image", None) => match value.get("name").and_then(Node::as_str) {
+                Some(image) => image,
+                None => continue,
+            },
+            // GitLab services are image names or `{ name: ... }` mappings
+            ("services", None) => {
+                for service in value.items() {
+                    let image = service
+                        .as_str()
+                        .or_else(|| service.get("name").and_then(Node::as_str));
+                    if let Some(image) = image {
+                        check_image(service.line, image, findings);
+                    }
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        check_image(value.line, target, findings);
+    }
+}
+
+fn check_action(line: usize, action: &str, findings: &mut Vec<Finding>) {
+    if action.starts_with("./") {
+        return;
+    }
+    match action.rsplit_once('@') {
+        None | Some((_, "")) => findings.push(Finding::error(
+            Some(line),
+            format!("action '{}' is not pinned to a version", action),
+        )),
+        Some((_, reference)) if MOVING_REFS.contains(&reference.to_lowercase().as_str()) => {
+            findings.push(Finding::warning(
+                Some(line),
+                format!("action '{}' is pinned to a moving branch", action),
+            ))
+        }
+        Some(_) => {}
+    }
+}
+
+fn check_image(line: usize, image: &str, findings: &mut Vec<Finding>) {
+    // Digests are exact; images chosen through variables are checked where
+    // the variable is set
+    if image.contains("@sha256:") || image.starts_with('$') {
+        return;
+    }
+    let name = image.rsplit('/').next().unwrap_or(image);
+    match name.split_once(':') {
+        None => findings.push(Finding::error(
+            Some(line),
+            format!("image '{}' has no tag and follows latest", image),
+        )),
+        Some((_, "latest")) => findings.push(Finding::warning(
+            Some(line),
+            format!("image '{}' uses the moving 'latest' tag", image),
+        )),
+        Some(_) => {}
+    }
+}
+
+/// Jenkinsfiles are Groovy, so each `stage('...')` block is found by text
+fn analyze_jenkinsfile(source: &str) -> Analysis {
+    let mut analysis = Analysis::default();
+    let starts: Vec<(usize, usize)> = source
+        .match_indices("stage(")
+        .map(|(i, _)| (i, source[..i].matches('\n').count() + 1))
+        .collect();
+    for (n, &(start, line)) in starts.iter().enumerate() {
+        let end = starts.get(n + 1).map_or(source.len(), |&(next, _)| next);
+        let body = &source[start..end];
+        let name = body
+            .split(|c| c == '\'' || c == '"')
+            .nth(1)
+            .unwrap_or("")
+            .to_string();
+        analysis.jobs.push(Job {
+            text: body.to_lowercase(),
+            name,
+            line,
+        });
+    }
+    if analysis.jobs.is_empty() {
+        analysis
+            .findings
+            .push(Finding::error(None, "no stages defined"));
+    }
+    analysis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(analysis: &Analysis, severity: Severity) -> Vec<String> {
+        analysis
+            .findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .map(|f| f.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_gitlab_jobs_and_required_roles() {
+        let analysis = analyze(
+            CiKind::GitLab,
+            "stages: [test]\n\
+             image: rust:1.75\n\
+             cache:\n\
+             \x20 paths: [target/]\n\
+             .template:\n\
+             \x20 script: [cargo build]\n\
+             test:\n\
+             \x20 stage: test\n\
+             \x20 script:\n\
+             \x20   - cargo test\n",
+        );
+        let names: Vec<_> = analysis.jobs.iter().map(|j| j.name.as_str()).collect();
+        assert_eq!(names, ["test"]);
+        assert!(analysis.findings.is_empty(), "{:?}", analysis.findings);
+        let missing: Vec<_> = missing_jobs(&analysis.jobs).iter().map(|r| r.role).collect();
+        assert_eq!(missing, ["format check", "lint", "build", "compliance verification"]);
+    }
+
+    #[test]
+    fn test_missing_cache_and_unpinned_images() {
+        let analysis = analyze(
+            CiKind::GitLab,
+            "build:\n\
+             \x20 image: rust\n\
+             \x20 services:\n\
+             \x20   - name: postgres:latest\n\
+             \x20 script: cargo build\n\
+             lint:\n\
+             \x20 image: { name: \"registry.example.com:5000/tools\" }\n\
+             \x20 script: cargo clippy\n\
+             pinned:\n\
+             \x20 image: alpine@sha256:abc\n\
+             \x20 script: true\n",
+        );
+        assert_eq!(
+            messages(&analysis, Severity::Error),
+            [
+                "image 'rust' has no tag and follows latest",
+                "image 'registry.example.com:5000/tools' has no tag and follows latest",
+            ]
+        );
+        assert_eq!(
+            messages(&analysis, Severity::Warning),
+            [
+                "no dependency caching configured",
+                "image 'postgres:latest' uses the moving 'latest' tag",
+            ]
+        );
+        assert_eq!(analysis.findings[1].line, Some(2));
+    }
+
+    #[test]
+    fn test_github_actions_pinning() {
+        let analysis = analyze(
+            CiKind::GitHub,
+            "on: push\n\
+             jobs:\n\
+             \x20 check:\n\
+             \x20   runs-on: ubuntu-latest\n\
+             \x20   container: node:latest\n\
+             \x20   steps:\n\
+             \x20     - uses: actions/checkout@v4\n\
+             \x20     - uses: actions/cache@v4\n\
+             \x20     - uses: some/action\n\
+             \x20     - uses: other/action@main\n\
+             \x20     - uses: ./local-action\n\
+             \x20     - uses: docker://alpine\n\
+             \x20     - run: cargo fmt --check\n",
+        );
+        assert_eq!(analysis.jobs.len(), 1);
+        assert_eq!(
+            messages(&analysis, Severity::Error),
+            [
+                "action 'some/action' is not pinned to a version",
+                "image 'alpine' has no tag and follows latest",
+            ]
+        );
+        assert_eq!(
+            messages(&analysis, Severity::Warning),
+            [
+                "image 'node:latest' uses the moving 'latest' tag",
+                "action 'other/action@main' is pinned to a moving branch",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_and_empty_files() {
+        let analysis = analyze(CiKind::GitHub, "jobs:\n  a: 1\n a: 2\n");
+        assert_eq!(analysis.findings.len(), 1);
+        assert_eq!(analysis.findings[0].severity, Severity::Error);
+        assert_eq!(analysis.findings[0].line, Some(3));
+
+        let analysis = analyze(CiKind::GitHub, "name: CI\non: push\n");
+        assert_eq!(messages(&analysis, Severity::Error), ["no jobs defined"]);
+    }
+
+    #[test]
+    fn test_jenkinsfile_stages() {
+        let analysis = analyze(
+            CiKind::Jenkins,
+            "pipeline {\n  stages {\n    stage('Lint') {\n      steps { sh 'cargo fmt --check && cargo clippy' }\n    }\n    stage(\"Test\") {\n      steps { sh 'cargo test' }\n    }\n  }\n}\n",
+        );
+        let names: Vec<_> = analysis.jobs.iter().map(|j| j.name.as_str()).collect();
+        assert_eq!(names, ["Lint", "Test"]);
+        assert_eq!(analysis.jobs[1].line, 6);
+        let missing: Vec<_> = missing_jobs(&analysis.jobs).iter().map(|r| r.role).collect();
+        assert_eq!(missing, ["build", "compliance verification"]);
+    }
+}
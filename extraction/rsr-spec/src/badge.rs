@@ -0,0 +1,39 @@
+//! Compliance badge data
+//!
+//! Badges are shields.io static badges linking to the standard, so every
+//! tool renders the same badge for the same level.
+
+use crate::{ComplianceLevel, STANDARD_URL};
+
+/// shields.io image URL for `level`
+pub fn image_url(level: ComplianceLevel) -> String {
+    format!(
+        "https://img.shields.io/badge/RSR-{}-{}",
+        level.display_name(),
+        level.badge_color()
+    )
+}
+
+/// Markdown badge for `level`, linking to the standard
+pub fn markdown(level: ComplianceLevel) -> String {
+    format!(
+        "[![Rhodium Standard {}]({})]({})",
+        level.display_name(),
+        image_url(level),
+        STANDARD_URL
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(
+            markdown(ComplianceLevel::Silver),
+            "[![Rhodium Standard Silver](https://img.shields.io/badge/RSR-Silver-c0c0c0)]\
+             (https://github.com/hyperpolymath/rhodium-standard-repositories)"
+        );
+    }
+}
@@ -0,0 +1,403 @@
+//! Catalog of built-in RSR checks
+//!
+//! Every built-in check has a stable identifier (e.g. `RSR-DOC-001`). The
+//! identifier is what users reference in configuration and what SARIF rules
+//! and documentation links are keyed on, so display text can change without
+//! breaking anyone's setup. Identifiers are never reused.
+
+use crate::{ComplianceLevel, STANDARD_URL};
+
+/// Static definition of a built-in check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckSpec {
+    pub id: &'static str,
+    pub category: &'static str,
+    pub item: &'static str,
+    pub level: ComplianceLevel,
+    /// Repository-relative files or directories the check looks at; the
+    /// first is the canonical name, the rest accepted alternatives
+    pub paths: &'static [&'static str],
+    /// What the check looks for and why it matters
+    pub description: &'static str,
+    /// What to change in the repository to make the check pass
+    pub remediation: &'static str,
+}
+
+impl CheckSpec {
+    /// Documentation link for this check
+    pub fn help_url(&self) -> String {
+        format!("{}#{}", STANDARD_URL, self.id.to_lowercase())
+    }
+}
+
+const fn spec(
+    id: &'static str,
+    category: &'static str,
+    item: &'static str,
+    level: ComplianceLevel,
+    paths: &'static [&'static str],
+    description: &'static str,
+    remediation: &'static str,
+) -> CheckSpec {
+    CheckSpec {
+        id,
+        category,
+        item,
+        level,
+        paths,
+        description,
+        remediation,
+    }
+}
+
+/// All built-in checks, in report order
+pub const BUILTIN_CHECKS: &[CheckSpec] = &[
+    spec(
+        "RSR-DOC-001",
+        "Documentation",
+        "README.md",
+        ComplianceLevel::Bronze,
+        &["README.md", "README.adoc"],
+        "README.md or README.adoc explains what the project is and how to use it",
+        "Add a README.md describing what the project does, how to install it and a usage example",
+    ),
+    spec(
+        "RSR-DOC-002",
+        "Documentation",
+        "LICENSE.txt",
+        ComplianceLevel::Bronze,
+        &["LICENSE.txt"],
+        "LICENSE.txt states the terms under which the code may be used and redistributed",
+        "Add LICENSE.txt with the full license text; an SPDX identifier alone is not enough",
+    ),
+    spec(
+        "RSR-DOC-003",
+        "Documentation",
+        "SECURITY.md",
+        ComplianceLevel::Bronze,
+        &["SECURITY.md"],
+        "SECURITY.md tells reporters how to disclose vulnerabilities privately",
+        "Add SECURITY.md with a private reporting channel and the supported versions",
+    ),
+    spec(
+        "RSR-DOC-004",
+        "Documentation",
+        "CONTRIBUTING.md",
+        ComplianceLevel::Bronze,
+        &["CONTRIBUTING.md"],
+        "CONTRIBUTING.md describes how to propose changes and what reviewers expect",
+        "Add CONTRIBUTING.md describing the branch, review and testing workflow",
+    ),
+    spec(
+        "RSR-DOC-005",
+        "Documentation",
+        "CODE_OF_CONDUCT.md",
+        ComplianceLevel::Bronze,
+        &["CODE_OF_CONDUCT.md"],
+        "CODE_OF_CONDUCT.md sets behavioural expectations and how to report violations",
+        "Add CODE_OF_CONDUCT.md, e.g. the Contributor Covenant, with a contact for reports",
+    ),
+    spec(
+        "RSR-DOC-006",
+        "Documentation",
+        "MAINTAINERS.md",
+        ComplianceLevel::Bronze,
+        &["MAINTAINERS.md"],
+        "MAINTAINERS.md names the people responsible for the project and how to reach them",
+        "Add MAINTAINERS.md listing maintainers and how to reach them",
+    ),
+    spec(
+        "RSR-DOC-007",
+        "Documentation",
+        "CHANGELOG.md",
+        ComplianceLevel::Bronze,
+        &["CHANGELOG.md"],
+        "CHANGELOG.md records notable changes for each release",
+        "Add CHANGELOG.md in Keep a Changelog format with an Unreleased section",
+    ),
+    spec(
+        "RSR-DOC-008",
+        "Documentation",
+        "CONTRIBUTING.md content",
+        ComplianceLevel::Silver,
+        &["CONTRIBUTING.md", "CONTRIBUTING.adoc"],
+        "CONTRIBUTING.md explains how to run the tests and how to submit changes",
+        "Document in CONTRIBUTING.md how to run the tests and open a pull request",
+    ),
+    spec(
+        "RSR-DOC-009",
+        "Documentation",
+        "CODE_OF_CONDUCT.md content",
+        ComplianceLevel::Silver,
+        &["CODE_OF_CONDUCT.md"],
+        "CODE_OF_CONDUCT.md follows a known template such as the Contributor Covenant, \
+         or says how to reach whoever enforces it",
+        "Base CODE_OF_CONDUCT.md on a known template and include an enforcement contact",
+    ),
+    spec(
+        "RSR-WKN-001",
+        "Well-Known",
+        ".well-known/ directory",
+        ComplianceLevel::Bronze,
+        &[".well-known"],
+        ".well-known/ holds machine-readable project metadata (RFC 8615)",
+        "Create a .well-known/ directory in the repository root",
+    ),
+    spec(
+        "RSR-WKN-002",
+        "Well-Known",
+        "security.txt",
+        ComplianceLevel::Bronze,
+        &[".well-known/security.txt"],
+        ".well-known/security.txt gives a security contact in RFC 9116 format",
+        "Add .well-known/security.txt with at least Contact: and Expires: fields (RFC 9116)",
+    ),
+    spec(
+        "RSR-WKN-003",
+        "Well-Known",
+        "ai.txt",
+        ComplianceLevel::Bronze,
+        &[".well-known/ai.txt"],
+        ".well-known/ai.txt declares the project's policy on AI training and usage",
+        "Add .well-known/ai.txt stating whether the code may be used for AI training",
+    ),
+    spec(
+        "RSR-WKN-004",
+        "Well-Known",
+        "humans.txt",
+        ComplianceLevel::Bronze,
+        &[".well-known/humans.txt"],
+        ".well-known/humans.txt credits the people behind the project",
+        "Add .well-known/humans.txt crediting the team",
+    ),
+    spec(
+        "RSR-WKN-005",
+        "Well-Known",
+        "ai.txt content",
+        ComplianceLevel::Silver,
+        &[".well-known/ai.txt"],
+        "ai.txt has a User-agent group stating the AI training policy with Allow/Disallow \
+         directives",
+        "Add a User-agent: * group with Allow or Disallow rules to .well-known/ai.txt",
+    ),
+    spec(
+        "RSR-WKN-006",
+        "Well-Known",
+        "humans.txt content",
+        ComplianceLevel::Silver,
+        &[".well-known/humans.txt"],
+        "humans.txt has a /* TEAM */ section naming the people behind the project and how to \
+         contact them",
+        "Add a /* TEAM */ section with names and contacts to .well-known/humans.txt",
+    ),
+    spec(
+        "RSR-BLD-001",
+        "Build System",
+        "justfile",
+        ComplianceLevel::Bronze,
+        &["justfile"],
+        "justfile provides discoverable build, test and release recipes",
+        "Add a justfile with build, test and release recipes",
+    ),
+    spec(
+        "RSR-BLD-002",
+        "Build System",
+        "flake.nix",
+        ComplianceLevel::Bronze,
+        &["flake.nix"],
+        "flake.nix pins the toolchain for reproducible builds",
+        "Add a flake.nix pinning the toolchain and exposing a dev shell",
+    ),
+    spec(
+        "RSR-BLD-003",
+        "Build System",
+        ".gitlab-ci.yml",
+        ComplianceLevel::Bronze,
+        &[".gitlab-ci.yml"],
+        ".gitlab-ci.yml runs the build and tests on every change",
+        "Add .gitlab-ci.yml running the build and tests (see rhodium-pipeline)",
+    ),
+    spec(
+        "RSR-BLD-004",
+        "Build System",
+        "CI configuration content",
+        ComplianceLevel::Silver,
+        &[".gitlab-ci.yml", ".github/workflows"],
+        "CI configuration (.gitlab-ci.yml, .github/workflows) defines jobs, runs the tests and \
+         avoids deprecated or unsafe constructs such as plain-text secrets",
+        "Fix the reported CI problems and make sure some job runs the test suite",
+    ),
+    spec(
+        "RSR-SRC-001",
+        "Source Structure",
+        "src/ directory",
+        ComplianceLevel::Bronze,
+        &["src"],
+        "src/ keeps source code separate from documentation and tooling",
+        "Move source code into a src/ directory",
+    ),
+    spec(
+        "RSR-SRC-002",
+        "Source Structure",
+        "tests/ directory",
+        ComplianceLevel::Bronze,
+        &["tests", "test"],
+        "tests/ (or test/) holds the project's automated tests",
+        "Put automated tests in a tests/ (or test/) directory",
+    ),
+    spec(
+        "RSR-VCS-001",
+        "Version Control",
+        "Conventional Commits",
+        ComplianceLevel::Silver,
+        &[".git"],
+        "The most recent commit subjects follow Conventional Commits (type(scope)!: description) \
+         and the checked-out branch is a default branch or named type/description; runs only \
+         with conventional_commits = true under [version_control]",
+        "Reword the listed commit subjects (e.g. 'fix: handle empty input') and rename the \
+         branch to type/description",
+    ),
+    spec(
+        "RSR-CNF-001",
+        "Conformity",
+        "CONFORMITY.md up to date",
+        ComplianceLevel::Silver,
+        &["CONFORMITY.md"],
+        "CONFORMITY.md records the same check results as a fresh verification, so the \
+         published conformity statement has not drifted",
+        "Run 'rhodibot conformity --write' and commit the updated CONFORMITY.md",
+    ),
+];
+
+/// Checks that only make sense once another has passed, as
+/// `(check, prerequisite)`
+///
+/// The files below `.well-known/` need the directory, and the content of a
+/// file can only be judged once the file is there. A check whose
+/// prerequisite did not pass is reported as skipped rather than failed, so
+/// one missing directory yields one failure instead of several.
+pub const PREREQUISITES: &[(&str, &str)] = &[
+    ("RSR-DOC-008", "RSR-DOC-004"),
+    ("RSR-DOC-009", "RSR-DOC-005"),
+    ("RSR-WKN-002", "RSR-WKN-001"),
+    ("RSR-WKN-003", "RSR-WKN-001"),
+    ("RSR-WKN-004", "RSR-WKN-001"),
+    ("RSR-WKN-005", "RSR-WKN-003"),
+    ("RSR-WKN-006", "RSR-WKN-004"),
+];
+
+/// Prerequisites of the check with this ID
+pub fn prerequisites(id: &str) -> impl Iterator<Item = &'static str> + '_ {
+    PREREQUISITES
+        .iter()
+        .filter(move |(check, _)| *check == id)
+        .map(|(_, prerequisite)| *prerequisite)
+}
+
+/// Look up a built-in check by its identifier
+pub fn lookup(id: &str) -> Option<&'static CheckSpec> {
+    BUILTIN_CHECKS.iter().find(|spec| spec.id == id)
+}
+
+/// Categories of the built-in checks, in report order
+pub fn categories() -> Vec<&'static str> {
+    let mut categories: Vec<&'static str> = Vec::new();
+    for spec in BUILTIN_CHECKS {
+        if !categories.contains(&spec.category) {
+            categories.push(spec.category);
+        }
+    }
+    categories
+}
+
+/// The category name matching `name` case-insensitively
+pub fn find_category(name: &str) -> Option<&'static str> {
+    categories()
+        .into_iter()
+        .find(|category| category.eq_ignore_ascii_case(name))
+}
+
+/// Canonical paths a repository needs for `level`, in report order and
+/// without duplicates
+pub fn required_paths(level: ComplianceLevel) -> Vec<&'static str> {
+    let mut paths: Vec<&'static str> = Vec::new();
+    for spec in BUILTIN_CHECKS.iter().filter(|spec| spec.level <= level) {
+        if let Some(path) = spec.paths.first() {
+            if !paths.contains(path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categories() {
+        assert_eq!(
+            categories(),
+            vec![
+                "Documentation",
+                "Well-Known",
+                "Build System",
+                "Source Structure",
+                "Version Control",
+                "Conformity"
+            ]
+        );
+        assert_eq!(find_category("build system"), Some("Build System"));
+        assert_eq!(find_category("Tests"), None);
+    }
+
+    #[test]
+    fn test_ids_are_unique_and_well_formed() {
+        for (i, spec) in BUILTIN_CHECKS.iter().enumerate() {
+            assert!(spec.id.starts_with("RSR-"), "{}", spec.id);
+            assert_eq!(spec.id.len(), 11, "{}", spec.id);
+            assert!(!spec.description.is_empty(), "{} has no description", spec.id);
+            assert!(
+                BUILTIN_CHECKS[i + 1..].iter().all(|other| other.id != spec.id),
+                "duplicate id {}",
+                spec.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_prerequisites() {
+        assert_eq!(prerequisites("RSR-WKN-002").collect::<Vec<_>>(), ["RSR-WKN-001"]);
+        assert_eq!(prerequisites("RSR-WKN-001").count(), 0);
+        for (check, prerequisite) in PREREQUISITES {
+            let (check, prerequisite) = (lookup(check).unwrap(), lookup(prerequisite).unwrap());
+            // Prerequisites run first and never demand more than the check
+            let position = |id: &str| BUILTIN_CHECKS.iter().position(|s| s.id == id);
+            assert!(position(prerequisite.id) < position(check.id), "{}", check.id);
+            assert!(prerequisite.level <= check.level, "{}", check.id);
+        }
+    }
+
+    #[test]
+    fn test_required_paths() {
+        let bronze = required_paths(ComplianceLevel::Bronze);
+        assert_eq!(bronze.len(), 16);
+        assert_eq!(bronze[0], "README.md");
+        assert!(bronze.contains(&".well-known/security.txt"));
+        assert!(!bronze.contains(&"README.adoc"));
+        let silver = required_paths(ComplianceLevel::Silver);
+        assert_eq!(silver.len(), bronze.len() + 2);
+        assert!(silver.contains(&".git"));
+        assert_eq!(silver.last(), Some(&"CONFORMITY.md"));
+        assert!(BUILTIN_CHECKS.iter().all(|spec| !spec.paths.is_empty()));
+    }
+
+    #[test]
+    fn test_lookup() {
+        let spec = lookup("RSR-DOC-001").unwrap();
+        assert_eq!(spec.item, "README.md");
+        assert!(spec.help_url().ends_with("#rsr-doc-001"));
+        assert!(lookup("RSR-XXX-999").is_none());
+    }
+}
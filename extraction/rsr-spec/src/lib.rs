@@ -0,0 +1,113 @@
+//! RSR Spec - Rhodium Standard Repository definitions
+//!
+//! The single definition of the RSR standard that aletheia, rhodibot and
+//! rhodium-pipeline share: compliance levels, the catalog of checks with
+//! their stable IDs and the files they look for, badge data, and the
+//! [`yaml`] reader rhodibot and rhodium-pipeline check CI configuration
+//! with. Every output that reports against the standard records the
+//! [`StandardVersion`] it was checked against, so a report can always be
+//! traced back to its requirements.
+//!
+//! # Features
+//!
+//! - Zero dependencies (uses only Rust std library)
+//! - Plain `const` data, usable from any tool
+
+pub mod badge;
+pub mod catalog;
+pub mod version;
+pub mod yaml;
+
+pub use version::StandardVersion;
+
+/// Latest version of the RSR standard these definitions describe
+pub const STANDARD_VERSION: &str = StandardVersion::LATEST.as_str();
+
+/// Base URL of the published RSR standard
+pub const STANDARD_URL: &str = "https://github.com/hyperpolymath/rhodium-standard-repositories";
+
+/// RSR Compliance levels
+///
+/// Levels are ordered from lowest to highest, and each level includes every
+/// level below it: a check with `required_for <= level` must pass for `level`
+/// to be met.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ComplianceLevel {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+impl ComplianceLevel {
+    /// All levels, from lowest to highest
+    pub const ALL: [ComplianceLevel; 4] = [
+        ComplianceLevel::Bronze,
+        ComplianceLevel::Silver,
+        ComplianceLevel::Gold,
+        ComplianceLevel::Platinum,
+    ];
+
+    /// Get the badge color for this compliance level
+    pub fn badge_color(&self) -> &'static str {
+        match self {
+            ComplianceLevel::Bronze => "cd7f32",
+            ComplianceLevel::Silver => "c0c0c0",
+            ComplianceLevel::Gold => "ffd700",
+            ComplianceLevel::Platinum => "e5e4e2",
+        }
+    }
+
+    /// Get the display name for this compliance level
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ComplianceLevel::Bronze => "Bronze",
+            ComplianceLevel::Silver => "Silver",
+            ComplianceLevel::Gold => "Gold",
+            ComplianceLevel::Platinum => "Platinum",
+        }
+    }
+
+    /// Parse a compliance level name (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        name.parse().ok()
+    }
+}
+
+impl std::str::FromStr for ComplianceLevel {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "bronze" => Ok(ComplianceLevel::Bronze),
+            "silver" => Ok(ComplianceLevel::Silver),
+            "gold" => Ok(ComplianceLevel::Gold),
+            "platinum" => Ok(ComplianceLevel::Platinum),
+            _ => Err(format!(
+                "unknown compliance level '{}' (expected bronze, silver, gold or platinum)",
+                name
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ComplianceLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_names_round_trip() {
+        for level in ComplianceLevel::ALL {
+            assert_eq!(level.display_name().parse::<ComplianceLevel>(), Ok(level));
+            assert_eq!(level.to_string(), level.display_name());
+        }
+        assert!(ComplianceLevel::parse("tin").is_none());
+        assert!(ComplianceLevel::Bronze < ComplianceLevel::Platinum);
+    }
+}
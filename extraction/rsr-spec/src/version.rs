@@ -0,0 +1,98 @@
+//! Versions of the RSR standard
+//!
+//! Each published version of the standard fixes its set of checks. Tools
+//! check against [`StandardVersion::LATEST`] unless a repository pins an
+//! older version, so updating a tool never adds requirements to a pinned
+//! repository behind its back.
+
+use crate::catalog::{self, CheckSpec};
+use std::fmt;
+use std::str::FromStr;
+
+/// A published version of the RSR standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum StandardVersion {
+    #[default]
+    V1_0,
+}
+
+impl StandardVersion {
+    /// All versions this crate defines, from oldest to newest
+    pub const ALL: [StandardVersion; 1] = [StandardVersion::V1_0];
+
+    /// The newest version this crate defines
+    pub const LATEST: StandardVersion = StandardVersion::V1_0;
+
+    /// Version number as written in configuration and reports (e.g. `1.0`)
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            StandardVersion::V1_0 => "1.0",
+        }
+    }
+
+    /// Checks required by this version of the standard, in report order
+    pub fn checks(&self) -> &'static [CheckSpec] {
+        match self {
+            StandardVersion::V1_0 => catalog::BUILTIN_CHECKS,
+        }
+    }
+
+    /// Look up a check by ID among those in this version
+    pub fn lookup(&self, id: &str) -> Option<&'static CheckSpec> {
+        self.checks().iter().find(|spec| spec.id == id)
+    }
+
+    /// Parse a version number (`1.0`, `1` or `v1.0`)
+    pub fn parse(version: &str) -> Option<Self> {
+        version.parse().ok()
+    }
+}
+
+impl FromStr for StandardVersion {
+    type Err = String;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let number = version.trim().trim_start_matches(['v', 'V']);
+        StandardVersion::ALL
+            .into_iter()
+            .find(|v| v.as_str() == number || v.as_str().strip_suffix(".0") == Some(number))
+            .ok_or_else(|| {
+                let known: Vec<&str> = StandardVersion::ALL.iter().map(|v| v.as_str()).collect();
+                format!(
+                    "unknown RSR standard version '{}' (supported: {}); a newer release may support it",
+                    version,
+                    known.join(", ")
+                )
+            })
+    }
+}
+
+impl fmt::Display for StandardVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(StandardVersion::parse("1.0"), Some(StandardVersion::V1_0));
+        assert_eq!(StandardVersion::parse("1"), Some(StandardVersion::V1_0));
+        assert_eq!(StandardVersion::parse("v1.0"), Some(StandardVersion::V1_0));
+        assert_eq!(StandardVersion::parse("2.0"), None);
+        let err = "9.9".parse::<StandardVersion>().unwrap_err();
+        assert!(err.contains("supported: 1.0"));
+        assert_eq!(StandardVersion::LATEST.to_string(), crate::STANDARD_VERSION);
+    }
+
+    #[test]
+    fn test_checks() {
+        let v1 = StandardVersion::V1_0;
+        assert_eq!(v1.checks().len(), catalog::BUILTIN_CHECKS.len());
+        assert!(v1.lookup("RSR-DOC-001").is_some());
+        assert!(v1.lookup("RSR-NOPE-001").is_none());
+    }
+}
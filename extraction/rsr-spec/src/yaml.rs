@@ -0,0 +1,663 @@
+//! Minimal YAML reader for CI configuration
+//!
+//! CI systems only use a small part of YAML: block mappings and sequences,
+//! plain and quoted scalars, block scalars for scripts, and the occasional
+//! one-line flow collection such as `branches: [main]`. This module reads
+//! that subset into a tree of [`Node`]s that keep their line numbers, so
+//! validation problems can point at the right place. Anchors are accepted and
+//! ignored; aliases are kept as plain `*name` scalars. Multi-line flow
+//! collections, tags and multi-document streams are not supported and are
+//! reported as errors rather than misread, with the line and column where
+//! reading stopped.
+//!
+//! rhodium-pipeline validates and upgrades CI configuration with this reader
+//! and rhodibot's CI content checks use it, so both read a file the same way.
+
+use std::fmt;
+
+/// A parsed value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Null,
+    Scalar(String),
+    Seq(Vec<Node>),
+    Map(Vec<(String, Node)>),
+}
+
+/// A value together with the (1-based) line it starts on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub line: usize,
+    pub value: Value,
+}
+
+impl Node {
+    fn new(line: usize, value: Value) -> Self {
+        Self { line, value }
+    }
+
+    /// The value under `key`, if this is a mapping that has it
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        self.entries()
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, node)| node)
+    }
+
+    /// Mapping entries in document order (empty for anything else)
+    pub fn entries(&self) -> &[(String, Node)] {
+        match &self.value {
+            Value::Map(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    /// Sequence items (empty for anything else)
+    pub fn items(&self) -> &[Node] {
+        match &self.value {
+            Value::Seq(items) => items,
+            _ => &[],
+        }
+    }
+
+    /// The text of a scalar
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            Value::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Every scalar in this subtree, paired with its line, in document order
+    pub fn scalars(&self) -> Vec<(usize, &str)> {
+        let mut out = Vec::new();
+        self.collect_scalars(&mut out);
+        out
+    }
+
+    fn collect_scalars<'a>(&'a self, out: &mut Vec<(usize, &'a str)>) {
+        match &self.value {
+            Value::Null => {}
+            Value::Scalar(s) => out.push((self.line, s)),
+            Value::Seq(items) => {
+                for item in items {
+                    item.collect_scalars(out);
+                }
+            }
+            Value::Map(entries) => {
+                for (_, node) in entries {
+                    node.collect_scalars(out);
+                }
+            }
+        }
+    }
+
+    /// Every `(key, value)` pair in this subtree, in document order
+    pub fn pairs(&self) -> Vec<(&str, &Node)> {
+        let mut out = Vec::new();
+        self.collect_pairs(&mut out);
+        out
+    }
+
+    fn collect_pairs<'a>(&'a self, out: &mut Vec<(&'a str, &'a Node)>) {
+        match &self.value {
+            Value::Seq(items) => {
+                for item in items {
+                    item.collect_pairs(out);
+                }
+            }
+            Value::Map(entries) => {
+                for (key, node) in entries {
+                    out.push((key, node));
+                    node.collect_pairs(out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Why a document could not be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the offending text
+    pub line: usize,
+    /// 1-based column (in characters) of the offending text
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn error<T>(line: usize, column: usize, message: impl Into<String>) -> Result<T, ParseError> {
+    Err(ParseError {
+        line,
+        column,
+        message: message.into(),
+    })
+}
+
+/// Column of `part`, a slice of `text` whose first character is at `column`
+fn column_of(text: &str, column: usize, part: &str) -> usize {
+    let offset = part.as_ptr() as usize - text.as_ptr() as usize;
+    column + text[..offset].chars().count()
+}
+
+/// Parse a document; an empty document is [`Value::Null`]
+pub fn parse(source: &str) -> Result<Node, ParseError> {
+    let mut parser = Parser {
+        lines: source.lines().map(String::from).collect(),
+        pos: 0,
+    };
+    let Some((line, indent, _)) = parser.peek()? else {
+        return Ok(Node::new(1, Value::Null));
+    };
+    if indent != 0 {
+        return error(line, indent + 1, "document must start at column 1");
+    }
+    let root = parser.block(0)?;
+    if let Some((line, indent, _)) = parser.peek()? {
+        return error(line, indent + 1, "unexpected indentation");
+    }
+    Ok(root)
+}
+
+struct Parser {
+    lines: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    /// The next significant line as (line number, indent, content without
+    /// comment), skipping blank lines, comments and document markers
+    fn peek(&mut self) -> Result<Option<(usize, usize, String)>, ParseError> {
+        while self.pos < self.lines.len() {
+            let raw = &self.lines[self.pos];
+            let number = self.pos + 1;
+            let text = raw.trim_start_matches(' ');
+            let indent = raw.len() - text.len();
+            let content = strip_comment(text).trim_end();
+            if content.is_empty() || (indent == 0 && content == "---") {
+                self.pos += 1;
+                continue;
+            }
+            if content.starts_with('\t') {
+                return error(number, indent + 1, "tab in indentation");
+            }
+            if indent == 0 && content == "..." {
+                return error(number, 1, "multiple documents are not supported");
+            }
+            return Ok(Some((number, indent, content.to_string())));
+        }
+        Ok(None)
+    }
+
+    /// A block mapping or sequence whose entries start at `indent`
+    fn block(&mut self, indent: usize) -> Result<Node, ParseError> {
+        match self.peek()? {
+            Some((_, _, content)) if is_item(&content) => self.seq(indent),
+            _ => self.map(indent),
+        }
+    }
+
+    fn seq(&mut self, indent: usize) -> Result<Node, ParseError> {
+        let (start, _, _) = self.peek()?.expect("caller checked for a line");
+        let mut items = Vec::new();
+        while let Some((line, at, content)) = self.peek()? {
+            if at < indent || (at == indent && !is_item(&content)) {
+                break;
+            }
+            if at > indent {
+                return error(line, at + 1, "unexpected indentation");
+            }
+            let rest = content[1..].trim_start();
+            if rest.is_empty() {
+                self.pos += 1;
+                items.push(self.nested(line, indent)?);
+            } else if split_key(rest).is_some() || is_item(rest) {
+                // An item that opens a mapping or sequence on the same line:
+                // blank out the dash and read it as a block at the deeper
+                // indent, which is where its continuation lines sit
+                let offset = content.len() - rest.len();
+                let raw = &mut self.lines[self.pos];
+                raw.replace_range(at..at + offset, &" ".repeat(offset));
+                items.push(self.block(indent + offset)?);
+            } else {
+                self.pos += 1;
+                let column = column_of(&content, at + 1, rest);
+                items.push(self.inline(line, column, indent, rest)?);
+            }
+        }
+        Ok(Node::new(start, Value::Seq(items)))
+    }
+
+    fn map(&mut self, indent: usize) -> Result<Node, ParseError> {
+        let (start, _, _) = self.peek()?.expect("caller checked for a line");
+        let mut entries: Vec<(String, Node)> = Vec::new();
+        while let Some((line, at, content)) = self.peek()? {
+            if at < indent {
+                break;
+            }
+            if at > indent {
+                return error(line, at + 1, "unexpected indentation");
+            }
+            if is_item(&content) {
+                return error(line, at + 1, "sequence item where a key was expected");
+            }
+            let Some((key, rest)) = split_key(&content) else {
+                let message = format!("expected 'key: value', found '{}'", content);
+                return error(line, at + 1, message);
+            };
+            if entries.iter().any(|(k, _)| *k == key) {
+                return error(line, at + 1, format!("duplicate key '{}'", key));
+            }
+            self.pos += 1;
+            let value = if rest.is_empty() {
+                match self.peek()? {
+                    // Sequences may sit at the same indent as their key
+                    Some((_, next, content)) if next == indent && is_item(&content) => {
+                        self.seq(indent)?
+                    }
+                    _ => self.nested(line, indent)?,
+                }
+            } else {
+                self.inline(line, column_of(&content, at + 1, rest), indent, rest)?
+            };
+            entries.push((key, value));
+        }
+        Ok(Node::new(start, Value::Map(entries)))
+    }
+
+    /// The block nested under a key or dash on `line`, or null if none
+    fn nested(&mut self, line: usize, indent: usize) -> Result<Node, ParseError> {
+        match self.peek()? {
+            Some((_, next, _)) if next > indent => self.block(next),
+            _ => Ok(Node::new(line, Value::Null)),
+        }
+    }
+
+    /// A value written after `key:` or `- ` on `line`, starting at `column`
+    fn inline(
+        &mut self,
+        line: usize,
+        column: usize,
+        indent: usize,
+        text: &str,
+    ) -> Result<Node, ParseError> {
+        let value = strip_anchor(text);
+        if value.is_empty() {
+            return self.nested(line, indent);
+        }
+        let column = column_of(text, column, value);
+        if value.starts_with('|') || value.starts_with('>') {
+            return self.block_scalar(line, column, indent, value);
+        }
+        if value.starts_with('!') {
+            return error(line, column, "tags are not supported");
+        }
+        flow(line, column, value)
+    }
+
+    /// A `|` or `>` block scalar whose header is on `line` at `column`
+    fn block_scalar(
+        &mut self,
+        line: usize,
+        column: usize,
+        indent: usize,
+        header: &str,
+    ) -> Result<Node, ParseError> {
+        let folded = header.starts_with('>');
+        let chomp = &header[1..];
+        if !matches!(chomp, "" | "-" | "+") {
+            let message = format!("unsupported block scalar header '{}'", header);
+            return error(line, column, message);
+        }
+
+        let mut body: Vec<&str> = Vec::new();
+        let mut content_indent = None;
+        while self.pos < self.lines.len() {
+            let raw = &self.lines[self.pos];
+            let text = raw.trim_start_matches(' ');
+            let at = raw.len() - text.len();
+            if text.is_empty() {
+                body.push("");
+                self.pos += 1;
+                continue;
+            }
+            if at <= indent {
+                break;
+            }
+            let block_indent = *content_indent.get_or_insert(at);
+            if at < block_indent {
+                break;
+            }
+            body.push(&raw[block_indent..]);
+            self.pos += 1;
+        }
+
+        // Trailing blank lines belong to the block only with `+`
+        let mut trailing = 0;
+        while body.last() == Some(&"") {
+            body.pop();
+            trailing += 1;
+        }
+        let mut text = if folded {
+            fold(&body)
+        } else {
+            body.join("\n")
+        };
+        match chomp {
+            "-" => {}
+            "+" => text.push_str(&"\n".repeat(trailing + 1)),
+            _ if body.is_empty() => {}
+            _ => text.push('\n'),
+        }
+        Ok(Node::new(line, Value::Scalar(text)))
+    }
+}
+
+/// Join folded-scalar lines: single newlines become spaces, blank lines
+/// become newlines
+fn fold(lines: &[&str]) -> String {
+    let mut out = String::new();
+    let mut pending_blank = false;
+    for (i, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            out.push('\n');
+            pending_blank = true;
+            continue;
+        }
+        if i > 0 && !pending_blank {
+            out.push(' ');
+        }
+        pending_blank = false;
+        out.push_str(line);
+    }
+    out
+}
+
+fn is_item(content: &str) -> bool {
+    content == "-" || content.starts_with("- ")
+}
+
+/// Remove a trailing `# comment`, ignoring `#` inside quotes or words
+fn strip_comment(text: &str) -> &str {
+    if text.starts_with('#') {
+        return "";
+    }
+    let mut quote = None;
+    let mut previous = ' ';
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && previous == ' ' => return &text[..i],
+            None => {}
+        }
+        previous = c;
+    }
+    text
+}
+
+/// Drop a leading `&anchor` from a value
+fn strip_anchor(text: &str) -> &str {
+    match text.strip_prefix('&') {
+        Some(rest) => rest.find(' ').map_or("", |end| rest[end..].trim_start()),
+        None => text,
+    }
+}
+
+/// Split `key: value` at the first `:` that ends the key, honouring quotes
+fn split_key(content: &str) -> Option<(String, &str)> {
+    let (key, rest) = if content.starts_with('"') || content.starts_with('\'') {
+        let quote = content.chars().next()?;
+        let end = content[1..].find(quote)? + 1;
+        let rest = content[end + 1..].strip_prefix(':')?;
+        (content[1..end].to_string(), rest)
+    } else {
+        if content.starts_with('[') || content.starts_with('{') {
+            return None;
+        }
+        let bytes = content.as_bytes();
+        let colon = (0..bytes.len()).find(|&i| {
+            bytes[i] == b':' && (i + 1 == bytes.len() || bytes[i + 1] == b' ')
+        })?;
+        (content[..colon].trim_end().to_string(), &content[colon + 1..])
+    };
+    if !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    Some((key, rest.trim()))
+}
+
+/// A scalar or one-line flow collection starting at `column`
+fn flow(line: usize, column: usize, text: &str) -> Result<Node, ParseError> {
+    let trimmed = text.trim();
+    let column = column_of(text, column, trimmed);
+    let text = trimmed;
+    if let Some(inner) = text.strip_prefix('[') {
+        let Some(inner) = inner.strip_suffix(']') else {
+            return error(line, column, "multi-line flow sequences are not supported");
+        };
+        let items = split_flow(line, column + 1, inner)?
+            .into_iter()
+            .map(|item| flow(line, column_of(inner, column + 1, item), item))
+            .collect::<Result<_, _>>()?;
+        return Ok(Node::new(line, Value::Seq(items)));
+    }
+    if let Some(inner) = text.strip_prefix('{') {
+        let Some(inner) = inner.strip_suffix('}') else {
+            return error(line, column, "multi-line flow mappings are not supported");
+        };
+        let mut entries = Vec::new();
+        for entry in split_flow(line, column + 1, inner)? {
+            let at = column_of(inner, column + 1, entry);
+            let Some((key, value)) = split_key(entry) else {
+                return error(line, at, format!("expected 'key: value' in '{}'", entry));
+            };
+            let value = if value.is_empty() {
+                Node::new(line, Value::Null)
+            } else {
+                flow(line, column_of(entry, at, value), value)?
+            };
+            entries.push((key, value));
+        }
+        return Ok(Node::new(line, Value::Map(entries)));
+    }
+    scalar(line, column, text)
+}
+
+/// Split the inside of a flow collection, starting at `column`, on top-level
+/// commas
+fn split_flow(line: usize, column: usize, inner: &str) -> Result<Vec<&str>, ParseError> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '[' | '{' => depth += 1,
+                ']' | '}' => {
+                    if depth == 0 {
+                        let at = column_of(inner, column, &inner[i..]);
+                        return error(line, at, "unbalanced brackets");
+                    }
+                    depth -= 1;
+                }
+                ',' if depth == 0 => {
+                    parts.push(inner[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            },
+        }
+    }
+    if quote.is_some() || depth != 0 {
+        // Point at the opening bracket
+        return error(line, column - 1, "unterminated flow collection");
+    }
+    let last = inner[start..].trim();
+    if !last.is_empty() {
+        parts.push(last);
+    }
+    Ok(parts)
+}
+
+fn scalar(line: usize, column: usize, text: &str) -> Result<Node, ParseError> {
+    let value = if let Some(inner) = text.strip_prefix('"') {
+        let Some(inner) = inner.strip_suffix('"') else {
+            return error(line, column, "unterminated double-quoted string");
+        };
+        unescape(inner)
+    } else if let Some(inner) = text.strip_prefix('\'') {
+        let Some(inner) = inner.strip_suffix('\'') else {
+            return error(line, column, "unterminated single-quoted string");
+        };
+        inner.replace("''", "'")
+    } else if matches!(text, "~" | "null" | "Null" | "NULL") {
+        return Ok(Node::new(line, Value::Null));
+    } else {
+        text.to_string()
+    };
+    Ok(Node::new(line, Value::Scalar(value)))
+}
+
+fn unescape(inner: &str) -> String {
+    let mut out = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar_at<'a>(node: &'a Node, path: &[&str]) -> &'a str {
+        let mut node = node;
+        for key in path {
+            node = node.get(key).unwrap_or_else(|| panic!("no key {}", key));
+        }
+        node.as_str().unwrap()
+    }
+
+    #[test]
+    fn test_mappings_and_sequences() {
+        let doc = parse(
+            "# comment\n\
+             name: CI\n\
+             on:\n\
+             \x20 push:\n\
+             \x20   branches: [main, 'release/*']\n\
+             jobs:\n\
+             \x20 test:\n\
+             \x20   runs-on: ubuntu-latest # trailing comment\n\
+             \x20   steps:\n\
+             \x20     - uses: actions/checkout@v4\n\
+             \x20       with: { fetch-depth: 0 }\n\
+             \x20     - run: cargo test\n\
+             \x20     - plain item\n",
+        )
+        .unwrap();
+        assert_eq!(scalar_at(&doc, &["name"]), "CI");
+        let branches = doc.get("on").unwrap().get("push").unwrap().get("branches").unwrap();
+        let names: Vec<_> = branches.items().iter().filter_map(Node::as_str).collect();
+        assert_eq!(names, ["main", "release/*"]);
+
+        let job = doc.get("jobs").unwrap().get("test").unwrap();
+        assert_eq!(scalar_at(job, &["runs-on"]), "ubuntu-latest");
+        let steps = job.get("steps").unwrap().items();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(scalar_at(&steps[0], &["uses"]), "actions/checkout@v4");
+        assert_eq!(scalar_at(&steps[0], &["with", "fetch-depth"]), "0");
+        assert_eq!(steps[1].line, 12);
+        assert_eq!(steps[2].as_str(), Some("plain item"));
+    }
+
+    #[test]
+    fn test_sequence_at_key_indent() {
+        let doc = parse("stages:\n- build\n- test\nvariables:\n  A: \"x # y\"\n").unwrap();
+        assert_eq!(doc.get("stages").unwrap().items().len(), 2);
+        assert_eq!(scalar_at(&doc, &["variables", "A"]), "x # y");
+    }
+
+    #[test]
+    fn test_block_scalars() {
+        let doc = parse(
+            "script: |\n\
+             \x20 if true; then\n\
+             \x20   echo hi\n\
+             \x20 fi\n\
+             \n\
+             folded: >-\n\
+             \x20 one\n\
+             \x20 two\n\
+             next: &anchor value\n",
+        )
+        .unwrap();
+        assert_eq!(scalar_at(&doc, &["script"]), "if true; then\n  echo hi\nfi\n");
+        assert_eq!(scalar_at(&doc, &["folded"]), "one two");
+        assert_eq!(scalar_at(&doc, &["next"]), "value");
+    }
+
+    #[test]
+    fn test_nested_sequence_items() {
+        let doc = parse("matrix:\n  - - a\n    - b\n  - name: x\n    value: y\n").unwrap();
+        let items = doc.get("matrix").unwrap().items();
+        assert_eq!(items[0].items().len(), 2);
+        assert_eq!(scalar_at(&items[1], &["value"]), "y");
+        assert_eq!(doc.scalars().len(), 4);
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases = [
+            ("a: 1\n   b: 2\n", (2, 4), "unexpected indentation"),
+            ("a: 1\na: 2\n", (2, 1), "duplicate key"),
+            ("a:\n  just text\n", (2, 3), "expected 'key: value'"),
+            ("a: [1, 2\n", (1, 4), "multi-line flow"),
+            ("a: \"open\n", (1, 4), "unterminated"),
+            ("a:\n\t- b\n", (2, 1), "tab"),
+            ("a: 1\n...\nb: 2\n", (2, 1), "multiple documents"),
+            ("a: &x !tag b\n", (1, 7), "tags"),
+            ("- b: [1, \"é\", {c 2}]\n", (1, 16), "expected 'key: value' in 'c 2'"),
+            ("a: [\"x]\n", (1, 4), "unterminated flow"),
+            ("a: [x]]\n", (1, 6), "unbalanced"),
+        ];
+        for (source, (line, column), message) in cases {
+            let err = parse(source).unwrap_err();
+            assert_eq!((err.line, err.column), (line, column), "{}", source);
+            assert!(err.message.contains(message), "{}: {}", source, err);
+        }
+        let err = parse("a:\n  b: |x\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 6: unsupported block scalar header '|x'");
+        assert_eq!(parse("").unwrap().value, Value::Null);
+    }
+}
@@ -9,730 +9,295 @@
 //! - Complete documentation suite
 //! - Security-first configuration
 //! - Build system compliance
-
-use std::fs;
-use std::path::{Path, PathBuf};
+//!
+//! The verification engine itself lives in the `rhodibot` library crate
+//! (`extraction/rhodibot`), so every check added there is picked up here too.
+
+use rhodibot::args::{Opt, Parsed, Spec};
+use rhodibot::env;
+use rhodibot::config::PolicyConfig;
+use rhodibot::output::{render_json_report, render_json_summary};
+use rhodibot::style::{Color, ColorChoice, Icon, Style};
+use rhodibot::{
+    compute_exit_code, exit_codes, verify_repository, CheckResult, ComplianceReport, OutputFormat,
+    Verbosity, WarningLevel,
+};
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
 use std::process;
-use std::time::SystemTime;
 
 /// Version information
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-/// Exit codes for different failure modes
-mod exit_codes {
-    pub const SUCCESS: i32 = 0;
-    pub const COMPLIANCE_FAILED: i32 = 1;
-    pub const SECURITY_WARNING: i32 = 2;
-    pub const INVALID_PATH: i32 = 3;
-    pub const INVALID_ARGS: i32 = 4;
-}
-
-/// Output format options
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum OutputFormat {
-    Human,
-    Json,
-}
-
-/// Verbosity level
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum Verbosity {
-    Quiet,   // Only pass/fail
-    Normal,  // Standard output
-    Verbose, // Include all details
-}
-
 /// CLI options
 struct CliOptions {
     repo_path: PathBuf,
     format: OutputFormat,
     verbosity: Verbosity,
+    style: Style,
+    reproducible: bool,
 }
 
-/// RSR Compliance levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(dead_code)] // Silver, Gold, Platinum reserved for future compliance levels
-enum ComplianceLevel {
-    Bronze,
-    Silver,
-    Gold,
-    Platinum,
-}
-
-/// Individual compliance check result
-#[derive(Debug)]
-struct CheckResult {
-    category: String,
-    item: String,
-    passed: bool,
-    required_for: ComplianceLevel,
-}
-
-/// Security warning levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(dead_code)] // Warning level reserved for future use
-enum WarningLevel {
-    Info,
-    Warning,
-    Critical,
-}
-
-/// Security warning
-#[derive(Debug)]
-#[allow(dead_code)] // path field used in Debug output and future enhancements
-struct SecurityWarning {
-    level: WarningLevel,
-    message: String,
-    path: Option<PathBuf>,
-}
-
-/// Overall compliance report
-#[derive(Debug)]
-struct ComplianceReport {
-    checks: Vec<CheckResult>,
-    warnings: Vec<SecurityWarning>,
-    repository_path: PathBuf,
-    verified_at: SystemTime,
-}
-
-impl ComplianceReport {
-    fn new(path: PathBuf) -> Self {
-        Self {
-            checks: Vec::new(),
-            warnings: Vec::new(),
-            repository_path: path,
-            verified_at: SystemTime::now(),
-        }
-    }
-
-    fn add_check(&mut self, category: &str, item: &str, passed: bool, level: ComplianceLevel) {
-        self.checks.push(CheckResult {
-            category: category.to_string(),
-            item: item.to_string(),
+/// Print the checks under one header per category, with the category's
+/// pass count (e.g. `Documentation: 6/7`)
+fn print_categories(report: &ComplianceReport, style: &Style, print_check: impl Fn(&CheckResult)) {
+    for (category, passed, total) in report.category_totals() {
+        println!(
+            "\n{} {}: {}/{}",
+            style.icon(Icon::Category),
+            style.paint(Color::Bold, category),
             passed,
-            required_for: level,
-        });
-    }
-
-    fn add_warning(&mut self, level: WarningLevel, message: &str, path: Option<PathBuf>) {
-        self.warnings.push(SecurityWarning {
-            level,
-            message: message.to_string(),
-            path,
-        });
-    }
-
-    fn bronze_compliance(&self) -> bool {
-        self.checks
-            .iter()
-            .filter(|c| c.required_for == ComplianceLevel::Bronze)
-            .all(|c| c.passed)
-    }
-
-    fn passed_count(&self) -> usize {
-        self.checks.iter().filter(|c| c.passed).count()
-    }
-
-    fn total_count(&self) -> usize {
-        self.checks.len()
-    }
-
-    fn has_critical_warnings(&self) -> bool {
-        self.warnings
-            .iter()
-            .any(|w| w.level == WarningLevel::Critical)
-    }
-}
-
-/// Result of checking a path for existence and symlink status
-struct PathCheckResult {
-    exists: bool,
-    is_symlink: bool,
-    escapes_repo: bool,
-    target: Option<PathBuf>,
-}
-
-/// Check if a path is a symlink and if it escapes the repository root
-fn check_path_security(path: &Path, repo_root: &Path) -> PathCheckResult {
-    // Use symlink_metadata to check the link itself, not its target
-    let metadata = match fs::symlink_metadata(path) {
-        Ok(m) => m,
-        Err(_) => {
-            return PathCheckResult {
-                exists: false,
-                is_symlink: false,
-                escapes_repo: false,
-                target: None,
-            }
-        },
-    };
-
-    let is_symlink = metadata.file_type().is_symlink();
-
-    if !is_symlink {
-        return PathCheckResult {
-            exists: true,
-            is_symlink: false,
-            escapes_repo: false,
-            target: None,
-        };
-    }
-
-    // It's a symlink - check where it points
-    let target = match fs::read_link(path) {
-        Ok(t) => t,
-        Err(_) => {
-            return PathCheckResult {
-                exists: true,
-                is_symlink: true,
-                escapes_repo: false, // Can't determine, assume safe
-                target: None,
-            };
-        },
-    };
-
-    // Resolve the target path (could be relative)
-    let resolved_target = if target.is_absolute() {
-        target.clone()
-    } else {
-        path.parent()
-            .map(|p| p.join(&target))
-            .unwrap_or(target.clone())
-    };
-
-    // Canonicalize both paths to compare
-    let canonical_root = repo_root
-        .canonicalize()
-        .unwrap_or_else(|_| repo_root.to_path_buf());
-    let canonical_target = resolved_target
-        .canonicalize()
-        .unwrap_or_else(|_| resolved_target.clone());
-
-    let escapes_repo = !canonical_target.starts_with(canonical_root);
-
-    PathCheckResult {
-        exists: true,
-        is_symlink: true,
-        escapes_repo,
-        target: Some(resolved_target),
-    }
-}
-
-/// Check if a file exists at the given path (with symlink detection)
-fn check_file(base: &Path, filename: &str, report: &mut ComplianceReport) -> bool {
-    let path = base.join(filename);
-    let security = check_path_security(&path, &report.repository_path);
-
-    if security.is_symlink {
-        if security.escapes_repo {
-            report.add_warning(
-                WarningLevel::Critical,
-                &format!(
-                    "Symlink '{}' points outside repository to '{}'",
-                    filename,
-                    security
-                        .target
-                        .as_ref()
-                        .map(|p| p.display().to_string())
-                        .unwrap_or_default()
-                ),
-                Some(path.clone()),
-            );
-        } else {
-            report.add_warning(
-                WarningLevel::Info,
-                &format!("'{}' is a symlink (within repository bounds)", filename),
-                Some(path.clone()),
-            );
-        }
-    }
-
-    // File exists if the path exists and points to a file (following symlinks)
-    security.exists && path.is_file()
-}
-
-/// Check if a directory exists at the given path (with symlink detection)
-fn check_dir(base: &Path, dirname: &str, report: &mut ComplianceReport) -> bool {
-    let path = base.join(dirname);
-    let security = check_path_security(&path, &report.repository_path);
-
-    if security.is_symlink {
-        if security.escapes_repo {
-            report.add_warning(
-                WarningLevel::Critical,
-                &format!(
-                    "Symlink directory '{}' points outside repository to '{}'",
-                    dirname,
-                    security
-                        .target
-                        .as_ref()
-                        .map(|p| p.display().to_string())
-                        .unwrap_or_default()
-                ),
-                Some(path.clone()),
-            );
-        } else {
-            report.add_warning(
-                WarningLevel::Info,
-                &format!(
-                    "'{}' is a symlink directory (within repository bounds)",
-                    dirname
-                ),
-                Some(path.clone()),
-            );
-        }
+            total
+        );
+        report.checks.iter().filter(|c| c.category == category).for_each(&print_check);
     }
-
-    // Directory exists if the path exists and points to a directory (following symlinks)
-    security.exists && path.is_dir()
 }
 
-/// Verify documentation files exist
-fn check_documentation(report: &mut ComplianceReport, repo_path: &Path) {
-    // README can be either .md or .adoc (AsciiDoc is acceptable alternative)
-    let readme_md = check_file(repo_path, "README.md", report);
-    let readme_adoc = if !readme_md {
-        check_file(repo_path, "README.adoc", report)
-    } else {
-        false
-    };
-    report.add_check(
-        "Documentation",
-        "README.md",
-        readme_md || readme_adoc,
-        ComplianceLevel::Bronze,
+/// Print the compliance report
+fn print_report(report: &ComplianceReport, style: &Style) {
+    println!(
+        "{} Aletheia - RSR Compliance Verification Report",
+        style.icon(Icon::Search)
     );
-
-    let other_required_docs = vec![
-        "LICENSE.txt",
-        "SECURITY.md",
-        "CONTRIBUTING.md",
-        "CODE_OF_CONDUCT.md",
-        "MAINTAINERS.md",
-        "CHANGELOG.md",
-    ];
-
-    for doc in other_required_docs {
-        let exists = check_file(repo_path, doc, report);
-        report.add_check("Documentation", doc, exists, ComplianceLevel::Bronze);
+    print!("{}", style.rule(46));
+    println!("Repository: {}", report.repository_display());
+    if let Some(timestamp) = report.timestamp() {
+        println!("Verified:   {}", timestamp);
     }
-}
-
-/// Verify .well-known directory and required files
-fn check_well_known(report: &mut ComplianceReport, repo_path: &Path) {
-    let has_dir = check_dir(repo_path, ".well-known", report);
-
-    report.add_check(
-        "Well-Known",
-        ".well-known/ directory",
-        has_dir,
-        ComplianceLevel::Bronze,
-    );
+    println!("Standard:   RSR {}", report.standard_version);
+    println!();
 
-    // Always emit file checks for consistent check count (16 total)
-    // Files can only pass if directory exists
-    let well_known_path = repo_path.join(".well-known");
-    let required_files = vec!["security.txt", "ai.txt", "humans.txt"];
-    for file in required_files {
-        let exists = if has_dir {
-            check_file(&well_known_path, file, report)
+    print_categories(report, style, |check| {
+        let icon = if check.passed() {
+            style.icon(Icon::Pass)
         } else {
-            false
+            style.icon(Icon::Fail)
         };
-        report.add_check("Well-Known", file, exists, ComplianceLevel::Bronze);
-    }
-}
-
-/// Verify build system files
-fn check_build_system(report: &mut ComplianceReport, repo_path: &Path) {
-    let build_files = vec![
-        ("justfile", ComplianceLevel::Bronze),
-        ("flake.nix", ComplianceLevel::Bronze),
-        (".gitlab-ci.yml", ComplianceLevel::Bronze),
-    ];
-
-    for (file, level) in build_files {
-        let exists = check_file(repo_path, file, report);
-        report.add_check("Build System", file, exists, level);
-    }
-}
-
-/// Verify source code structure (language-agnostic)
-fn check_source_structure(report: &mut ComplianceReport, repo_path: &Path) {
-    let has_src = check_dir(repo_path, "src", report);
-    let has_tests = check_dir(repo_path, "tests", report) || check_dir(repo_path, "test", report);
-
-    report.add_check(
-        "Source Structure",
-        "src/ directory",
-        has_src,
-        ComplianceLevel::Bronze,
-    );
-
-    report.add_check(
-        "Source Structure",
-        "tests/ directory",
-        has_tests,
-        ComplianceLevel::Bronze,
-    );
-}
-
-/// Run all compliance checks
-fn verify_repository(repo_path: &Path) -> ComplianceReport {
-    let mut report = ComplianceReport::new(repo_path.to_path_buf());
-
-    check_documentation(&mut report, repo_path);
-    check_well_known(&mut report, repo_path);
-    check_build_system(&mut report, repo_path);
-    check_source_structure(&mut report, repo_path);
-
-    report
-}
-
-/// Format a SystemTime as a human-readable timestamp
-fn format_timestamp(time: SystemTime) -> String {
-    match time.duration_since(SystemTime::UNIX_EPOCH) {
-        Ok(duration) => {
-            let secs = duration.as_secs();
-            // Calculate date components (simplified UTC)
-            let days = secs / 86400;
-            let time_secs = secs % 86400;
-            let hours = time_secs / 3600;
-            let minutes = (time_secs % 3600) / 60;
-            let seconds = time_secs % 60;
-
-            // Approximate year/month/day (good enough for display)
-            let mut year = 1970;
-            let mut remaining_days = days;
-
-            loop {
-                let days_in_year = if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) {
-                    366
-                } else {
-                    365
-                };
-                if remaining_days < days_in_year {
-                    break;
-                }
-                remaining_days -= days_in_year;
-                year += 1;
-            }
-
-            let is_leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
-            let days_in_months: [u64; 12] = if is_leap {
-                [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-            } else {
-                [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-            };
-
-            let mut month = 1;
-            for days_in_month in days_in_months.iter() {
-                if remaining_days < *days_in_month {
-                    break;
-                }
-                remaining_days -= days_in_month;
-                month += 1;
-            }
-            let day = remaining_days + 1;
-
-            format!(
-                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
-                year, month, day, hours, minutes, seconds
-            )
-        },
-        Err(_) => "unknown".to_string(),
-    }
-}
-
-/// Print the compliance report
-fn print_report(report: &ComplianceReport) {
-    println!("🔍 Aletheia - RSR Compliance Verification Report");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("Repository: {}", report.repository_path.display());
-    println!("Verified:   {}", format_timestamp(report.verified_at));
-    println!();
-
-    let mut current_category = String::new();
-    for check in &report.checks {
-        if check.category != current_category {
-            println!("\n📋 {}", check.category);
-            current_category = check.category.clone();
-        }
-
-        let icon = if check.passed { "✅" } else { "❌" };
         let level = format!("{:?}", check.required_for);
         println!("  {} {} [{}]", icon, check.item, level);
-    }
+    });
 
     // Print security warnings if any
     if !report.warnings.is_empty() {
-        println!("\n🛡️  Security Warnings");
+        println!("\n{} Security Warnings", style.icon(Icon::Shield));
         for warning in &report.warnings {
             let icon = match warning.level {
-                WarningLevel::Info => "ℹ️ ",
-                WarningLevel::Warning => "⚠️ ",
-                WarningLevel::Critical => "🚨",
+                WarningLevel::Info => style.icon(Icon::Info),
+                WarningLevel::Warning => style.icon(Icon::Warning),
+                WarningLevel::Critical => style.icon(Icon::Critical),
             };
             println!("  {} {}", icon, warning.message);
         }
     }
 
     println!();
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    print!("{}", style.rule(46));
     println!(
         "Score: {}/{} checks passed ({:.1}%)",
         report.passed_count(),
         report.total_count(),
-        (report.passed_count() as f64 / report.total_count() as f64) * 100.0
+        report.percentage()
     );
 
     if report.has_critical_warnings() {
-        println!("🚨 CRITICAL: Security warnings detected - review required");
+        println!(
+            "{} {}",
+            style.icon(Icon::Critical),
+            style.paint(Color::Red, "CRITICAL: Security warnings detected - review required")
+        );
     }
 
     if report.bronze_compliance() && !report.has_critical_warnings() {
-        println!("🏆 Bronze-level RSR compliance: ACHIEVED");
+        println!(
+            "{} Bronze-level RSR compliance: {}",
+            style.icon(Icon::Trophy),
+            style.paint(Color::Green, "ACHIEVED")
+        );
     } else if report.bronze_compliance() && report.has_critical_warnings() {
-        println!("⚠️  Bronze-level RSR compliance: ACHIEVED (with warnings)");
+        println!(
+            "{} Bronze-level RSR compliance: {}",
+            style.icon(Icon::Warning),
+            style.paint(Color::Yellow, "ACHIEVED (with warnings)")
+        );
     } else {
-        println!("⚠️  Bronze-level RSR compliance: NOT MET");
+        println!(
+            "{} Bronze-level RSR compliance: {}",
+            style.icon(Icon::Warning),
+            style.paint(Color::Red, "NOT MET")
+        );
     }
     println!();
 }
 
-/// Print help message
-fn print_help() {
-    println!(
-        r#"Aletheia - RSR Compliance Verification Tool
-
-USAGE:
-    aletheia [OPTIONS] [PATH]
-
-ARGS:
-    [PATH]    Repository path to verify (default: current directory)
-
-OPTIONS:
-    -f, --format <FORMAT>    Output format: human, json (default: human)
-    -q, --quiet              Quiet mode: only show pass/fail result
-    -v, --verbose            Verbose mode: show all details including symlink targets
-    -h, --help               Print help information
-    -V, --version            Print version information
-
-EXIT CODES:
+/// Command-line interface
+const CLI: Spec = Spec {
+    name: "aletheia",
+    version: VERSION,
+    about: "Aletheia - RSR Compliance Verification Tool",
+    commands: &[],
+    positional: Some(("PATH", "Repository path to verify (default: current directory)")),
+    options: &[
+        Opt::value(
+            "format",
+            Some('f'),
+            "FORMAT",
+            "Output format: human, json (default: human)",
+        ),
+        Opt::flag(
+            "quiet",
+            Some('q'),
+            "Quiet mode: only show pass/fail result (with json:\n\
+             the summary object on one line)",
+        ),
+        Opt::flag(
+            "verbose",
+            Some('v'),
+            "Verbose mode: show all details including symlink targets",
+        ),
+        Opt::value(
+            "color",
+            None,
+            "WHEN",
+            "Color output: auto, always, never (default: auto)",
+        ),
+        Opt::flag(
+            "no-emoji",
+            None,
+            "Use ASCII icons and rules instead of emoji",
+        ),
+        Opt::flag(
+            "reproducible",
+            None,
+            "Leave timestamps and absolute paths out of the\n\
+             report, so identical repositories give\n\
+             byte-identical output",
+        ),
+    ],
+    after_help: r#"EXIT CODES:
     0    Success - Bronze compliance achieved
     1    Failure - Bronze compliance not met
     2    Security - Critical security warnings detected
     3    Error - Invalid path provided
     4    Error - Invalid arguments
 
+ENVIRONMENT:
+    NO_COLOR             disable ANSI colors
+    SOURCE_DATE_EPOCH    verification time (seconds since 1970)
+
 EXAMPLES:
     aletheia                     # Verify current directory
     aletheia /path/to/repo       # Verify specific repository
     aletheia --format json       # Output as JSON
     aletheia -q                  # Quiet mode (CI-friendly)
     aletheia -v /path/to/repo    # Verbose output
-"#
-    );
-}
-
-/// Print version information
-fn print_version() {
-    println!("aletheia {}", VERSION);
-}
+    aletheia --no-emoji          # ASCII output for limited terminals
+    aletheia -f json --reproducible  # Output that can be diffed or signed
+"#,
+};
 
 /// Parse command line arguments
 fn parse_args() -> Result<CliOptions, String> {
-    let args: Vec<String> = std::env::args().collect();
-    let mut format = OutputFormat::Human;
-    let mut verbosity = Verbosity::Normal;
-    let mut repo_path: Option<PathBuf> = None;
-
-    let mut i = 1;
-    while i < args.len() {
-        let arg = &args[i];
-        match arg.as_str() {
-            "-h" | "--help" => {
-                print_help();
-                process::exit(exit_codes::SUCCESS);
-            },
-            "-V" | "--version" => {
-                print_version();
-                process::exit(exit_codes::SUCCESS);
-            },
-            "-q" | "--quiet" => {
-                verbosity = Verbosity::Quiet;
-            },
-            "-v" | "--verbose" => {
-                verbosity = Verbosity::Verbose;
-            },
-            "-f" | "--format" => {
-                i += 1;
-                if i >= args.len() {
-                    return Err("--format requires an argument".to_string());
-                }
-                format = match args[i].as_str() {
-                    "human" => OutputFormat::Human,
-                    "json" => OutputFormat::Json,
-                    other => {
-                        return Err(format!("Unknown format: {}. Use 'human' or 'json'", other))
-                    },
-                };
-            },
-            arg if arg.starts_with('-') => {
-                // Handle combined format like --format=json
-                if let Some(value) = arg.strip_prefix("--format=") {
-                    format = match value {
-                        "human" => OutputFormat::Human,
-                        "json" => OutputFormat::Json,
-                        other => {
-                            return Err(format!("Unknown format: {}. Use 'human' or 'json'", other))
-                        },
-                    };
-                } else {
-                    return Err(format!("Unknown option: {}", arg));
-                }
-            },
-            path => {
-                if repo_path.is_some() {
-                    return Err("Multiple paths provided. Only one path is allowed.".to_string());
-                }
-                repo_path = Some(PathBuf::from(path));
-            },
-        }
-        i += 1;
-    }
+    let matches = match CLI.parse(std::env::args().skip(1))? {
+        Parsed::Help => {
+            println!("{}", CLI.help());
+            process::exit(exit_codes::SUCCESS);
+        },
+        Parsed::Version => {
+            println!("{}", CLI.version_line());
+            process::exit(exit_codes::SUCCESS);
+        },
+        Parsed::Run(matches) => matches,
+    };
 
-    let repo_path =
-        repo_path.unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    let format = match matches.value("format") {
+        None | Some("human") => OutputFormat::Human,
+        Some("json") => OutputFormat::Json,
+        Some(other) => return Err(format!("Unknown format: {}. Use 'human' or 'json'", other)),
+    };
+    let verbosity = match matches.last_of(&["quiet", "verbose"]) {
+        Some("quiet") => Verbosity::Quiet,
+        Some(_) => Verbosity::Verbose,
+        None => Verbosity::Normal,
+    };
+    let color = matches
+        .value("color")
+        .map(str::parse::<ColorChoice>)
+        .transpose()?
+        .unwrap_or_default();
+    let style = Style::detect(color, !matches.flag("no-emoji"), io::stdout().is_terminal());
+    // Reject a malformed value rather than silently using the current time
+    env::source_date_epoch()?;
+    if matches.positionals.len() > 1 {
+        return Err("Multiple paths provided. Only one path is allowed.".to_string());
+    }
+
+    let repo_path = matches.positionals.first().map(PathBuf::from).unwrap_or_else(|| {
+        std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+    });
 
     Ok(CliOptions {
         repo_path,
         format,
         verbosity,
+        style,
+        reproducible: matches.flag("reproducible"),
     })
 }
 
-/// Escape a string for JSON output
-fn json_escape(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    for c in s.chars() {
-        match c {
-            '"' => result.push_str("\\\""),
-            '\\' => result.push_str("\\\\"),
-            '\n' => result.push_str("\\n"),
-            '\r' => result.push_str("\\r"),
-            '\t' => result.push_str("\\t"),
-            c if c.is_control() => {
-                result.push_str(&format!("\\u{:04x}", c as u32));
-            },
-            c => result.push(c),
-        }
-    }
-    result
-}
-
-/// Print report as JSON
-fn print_json_report(report: &ComplianceReport) {
-    let timestamp = format_timestamp(report.verified_at);
-    let passed = report.passed_count();
-    let total = report.total_count();
-    let percentage = (passed as f64 / total as f64) * 100.0;
-    let bronze_compliant = report.bronze_compliance();
-    let has_critical = report.has_critical_warnings();
-
-    println!("{{");
-    println!("  \"version\": \"{}\",", VERSION);
-    println!(
-        "  \"repository\": \"{}\",",
-        json_escape(&report.repository_path.display().to_string())
-    );
-    println!("  \"verified_at\": \"{}\",", timestamp);
-    println!("  \"score\": {{");
-    println!("    \"passed\": {},", passed);
-    println!("    \"total\": {},", total);
-    println!("    \"percentage\": {:.1}", percentage);
-    println!("  }},");
-    println!("  \"bronze_compliant\": {},", bronze_compliant);
-    println!("  \"has_critical_warnings\": {},", has_critical);
-
-    // Checks
-    println!("  \"checks\": [");
-    for (i, check) in report.checks.iter().enumerate() {
-        let comma = if i < report.checks.len() - 1 { "," } else { "" };
-        println!("    {{");
-        println!("      \"category\": \"{}\",", json_escape(&check.category));
-        println!("      \"item\": \"{}\",", json_escape(&check.item));
-        println!("      \"passed\": {},", check.passed);
-        println!("      \"level\": \"{:?}\"", check.required_for);
-        println!("    }}{}", comma);
-    }
-    println!("  ],");
-
-    // Warnings
-    println!("  \"warnings\": [");
-    for (i, warning) in report.warnings.iter().enumerate() {
-        let comma = if i < report.warnings.len() - 1 {
-            ","
-        } else {
-            ""
-        };
-        let level = match warning.level {
-            WarningLevel::Info => "info",
-            WarningLevel::Warning => "warning",
-            WarningLevel::Critical => "critical",
-        };
-        println!("    {{");
-        println!("      \"level\": \"{}\",", level);
-        println!("      \"message\": \"{}\"", json_escape(&warning.message));
-        println!("    }}{}", comma);
-    }
-    println!("  ]");
-    println!("}}");
-}
-
 /// Print quiet mode output (just pass/fail)
-fn print_quiet_report(report: &ComplianceReport) {
+fn print_quiet_report(report: &ComplianceReport, style: &Style) {
     let bronze_compliant = report.bronze_compliance();
     let has_critical = report.has_critical_warnings();
 
     if bronze_compliant && !has_critical {
-        println!("PASS");
+        println!("{}", style.paint(Color::Green, "PASS"));
     } else if has_critical {
-        println!("FAIL (security)");
+        println!("{}", style.paint(Color::Red, "FAIL (security)"));
     } else {
-        println!("FAIL");
+        println!("{}", style.paint(Color::Red, "FAIL"));
     }
 }
 
 /// Print verbose report (includes extra details)
-fn print_verbose_report(report: &ComplianceReport) {
-    println!("🔍 Aletheia - RSR Compliance Verification Report (Verbose)");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("Repository: {}", report.repository_path.display());
-    println!("Verified:   {}", format_timestamp(report.verified_at));
+fn print_verbose_report(report: &ComplianceReport, style: &Style) {
+    println!(
+        "{} Aletheia - RSR Compliance Verification Report (Verbose)",
+        style.icon(Icon::Search)
+    );
+    print!("{}", style.rule(58));
+    println!("Repository: {}", report.repository_display());
+    if let Some(timestamp) = report.timestamp() {
+        println!("Verified:   {}", timestamp);
+    }
     println!("Version:    {}", VERSION);
+    println!("Standard:   RSR {}", report.standard_version);
     println!();
 
-    let mut current_category = String::new();
-    for check in &report.checks {
-        if check.category != current_category {
-            println!("\n📋 {}", check.category);
-            current_category = check.category.clone();
-        }
-
-        let icon = if check.passed { "✅" } else { "❌" };
+    print_categories(report, style, |check| {
+        let icon = if check.passed() {
+            style.icon(Icon::Pass)
+        } else {
+            style.icon(Icon::Fail)
+        };
         let level = format!("{:?}", check.required_for);
-        println!("  {} {} [{}]", icon, check.item, level);
-    }
+        println!("  {} {} [{}] {}", icon, check.item, level, check.id);
+        if let Some(ref description) = check.description {
+            println!("      {}", description);
+        }
+    });
 
     // Print security warnings with full details
     if !report.warnings.is_empty() {
-        println!("\n🛡️  Security Warnings ({} total)", report.warnings.len());
+        println!(
+            "\n{} Security Warnings ({} total)",
+            style.icon(Icon::Shield),
+            report.warnings.len()
+        );
         for warning in &report.warnings {
             let icon = match warning.level {
-                WarningLevel::Info => "ℹ️ ",
-                WarningLevel::Warning => "⚠️ ",
-                WarningLevel::Critical => "🚨",
+                WarningLevel::Info => style.icon(Icon::Info),
+                WarningLevel::Warning => style.icon(Icon::Warning),
+                WarningLevel::Critical => style.icon(Icon::Critical),
             };
             let level_str = match warning.level {
                 WarningLevel::Info => "[INFO]",
@@ -747,16 +312,20 @@ fn print_verbose_report(report: &ComplianceReport) {
     }
 
     println!();
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    print!("{}", style.rule(58));
     println!(
         "Score: {}/{} checks passed ({:.1}%)",
         report.passed_count(),
         report.total_count(),
-        (report.passed_count() as f64 / report.total_count() as f64) * 100.0
+        report.percentage()
     );
 
     if report.has_critical_warnings() {
-        println!("🚨 CRITICAL: Security warnings detected - review required");
+        println!(
+            "{} {}",
+            style.icon(Icon::Critical),
+            style.paint(Color::Red, "CRITICAL: Security warnings detected - review required")
+        );
         println!(
             "   Exit code: {} (SECURITY_WARNING)",
             exit_codes::SECURITY_WARNING
@@ -764,16 +333,28 @@ fn print_verbose_report(report: &ComplianceReport) {
     }
 
     if report.bronze_compliance() && !report.has_critical_warnings() {
-        println!("🏆 Bronze-level RSR compliance: ACHIEVED");
+        println!(
+            "{} Bronze-level RSR compliance: {}",
+            style.icon(Icon::Trophy),
+            style.paint(Color::Green, "ACHIEVED")
+        );
         println!("   Exit code: {} (SUCCESS)", exit_codes::SUCCESS);
     } else if report.bronze_compliance() && report.has_critical_warnings() {
-        println!("⚠️  Bronze-level RSR compliance: ACHIEVED (with warnings)");
+        println!(
+            "{} Bronze-level RSR compliance: {}",
+            style.icon(Icon::Warning),
+            style.paint(Color::Yellow, "ACHIEVED (with warnings)")
+        );
         println!(
             "   Exit code: {} (SECURITY_WARNING)",
             exit_codes::SECURITY_WARNING
         );
     } else {
-        println!("⚠️  Bronze-level RSR compliance: NOT MET");
+        println!(
+            "{} Bronze-level RSR compliance: {}",
+            style.icon(Icon::Warning),
+            style.paint(Color::Red, "NOT MET")
+        );
         println!(
             "   Exit code: {} (COMPLIANCE_FAILED)",
             exit_codes::COMPLIANCE_FAILED
@@ -808,33 +389,32 @@ fn main() {
         process::exit(exit_codes::INVALID_PATH);
     }
 
-    let report = verify_repository(&options.repo_path);
+    let mut report = verify_repository(&options.repo_path);
+    if options.reproducible {
+        report.make_reproducible();
+    }
 
     // Output based on format and verbosity
     match options.format {
-        OutputFormat::Json => print_json_report(&report),
-        OutputFormat::Human => match options.verbosity {
-            Verbosity::Quiet => print_quiet_report(&report),
-            Verbosity::Normal => print_report(&report),
-            Verbosity::Verbose => print_verbose_report(&report),
+        OutputFormat::Json if options.verbosity == Verbosity::Quiet => {
+            print!("{}", render_json_summary(&report))
+        },
+        OutputFormat::Json => print!("{}", render_json_report(&report)),
+        _ => match options.verbosity {
+            Verbosity::Quiet => print_quiet_report(&report, &options.style),
+            Verbosity::Summary | Verbosity::Normal => print_report(&report, &options.style),
+            Verbosity::Verbose => print_verbose_report(&report, &options.style),
         },
     }
 
-    // Exit with appropriate code
-    let exit_code = if report.has_critical_warnings() {
-        exit_codes::SECURITY_WARNING
-    } else if !report.bronze_compliance() {
-        exit_codes::COMPLIANCE_FAILED
-    } else {
-        exit_codes::SUCCESS
-    };
-
-    process::exit(exit_code);
+    process::exit(compute_exit_code(&report, &PolicyConfig::default()));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rhodibot::{format_timestamp, ComplianceLevel};
+    use std::time::SystemTime;
 
     #[test]
     fn test_compliance_report_creation() {
@@ -849,7 +429,7 @@ fn test_add_check() {
         let mut report = ComplianceReport::new(PathBuf::from("/tmp/test"));
         report.add_check("Test", "Item", true, ComplianceLevel::Bronze);
         assert_eq!(report.checks.len(), 1);
-        assert_eq!(report.checks[0].passed, true);
+        assert!(report.checks[0].passed());
     }
 
     #[test]
@@ -916,6 +496,6 @@ fn test_format_timestamp() {
         let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1705322445);
         let formatted = format_timestamp(time);
         assert!(formatted.contains("2024"));
-        assert!(formatted.ends_with("Z"));
+        assert!(formatted.ends_with('Z'));
     }
 }
@@ -2,6 +2,9 @@
 //!
 //! These tests verify the complete end-to-end functionality of Aletheia.
 
+// Command arguments are passed as `&[..]` slices throughout
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -40,8 +43,16 @@ fn test_fully_compliant_repository() {
     create_file(&repo, "README.md", "# Test Project");
     create_file(&repo, "LICENSE.txt", "MIT License");
     create_file(&repo, "SECURITY.md", "# Security Policy");
-    create_file(&repo, "CONTRIBUTING.md", "# Contributing");
-    create_file(&repo, "CODE_OF_CONDUCT.md", "# Code of Conduct");
+    create_file(
+        &repo,
+        "CONTRIBUTING.md",
+        "# Contributing\n\nRun `cargo test`, then open a pull request.",
+    );
+    create_file(
+        &repo,
+        "CODE_OF_CONDUCT.md",
+        "# Contributor Covenant Code of Conduct",
+    );
     create_file(&repo, "MAINTAINERS.md", "# Maintainers");
     create_file(&repo, "CHANGELOG.md", "# Changelog");
 
@@ -51,8 +62,16 @@ fn test_fully_compliant_repository() {
         ".well-known/security.txt",
         "Contact: security@example.org",
     );
-    create_file(&repo, ".well-known/ai.txt", "# AI Policy");
-    create_file(&repo, ".well-known/humans.txt", "# Humans");
+    create_file(
+        &repo,
+        ".well-known/ai.txt",
+        "# AI Policy\nUser-agent: *\nDisallow: /",
+    );
+    create_file(
+        &repo,
+        ".well-known/humans.txt",
+        "/* TEAM */\nName: Test Team\nContact: team@example.org",
+    );
 
     // Create build system files
     create_file(&repo, "justfile", "build:\n\techo 'building'");
@@ -63,6 +82,11 @@ fn test_fully_compliant_repository() {
     create_file(&repo, "src/main.rs", "fn main() {}");
     create_file(&repo, "tests/test.rs", "#[test] fn test() {}");
 
+    // Commit the conformity statement, as `rhodibot conformity --write` does
+    let mut report = rhodibot::verify_repository(&repo);
+    rhodibot::conformity::mark_in_sync(&mut report);
+    create_file(&repo, "CONFORMITY.md", &rhodibot::generate_conformity_doc(&report));
+
     // Run aletheia on the test repository
     let output = Command::new("cargo")
         .args(&["run", "--", repo.to_str().unwrap()])
@@ -77,7 +101,7 @@ fn test_fully_compliant_repository() {
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
-        stdout.contains("16/16 checks passed"),
+        stdout.contains("22/22 checks passed"),
         "Should pass all checks"
     );
     assert!(
@@ -183,7 +207,7 @@ fn test_self_verification() {
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
-        stdout.contains("16/16 checks passed"),
+        stdout.contains("22/22 checks passed"),
         "Aletheia should pass all self-checks"
     );
     assert!(
@@ -322,6 +346,23 @@ fn test_quiet_mode() {
     assert_eq!(stdout.trim(), "PASS", "Quiet mode should only output PASS");
 }
 
+/// Test compact JSON summary with --format json --quiet
+#[test]
+fn test_quiet_json_summary() {
+    let output = Command::new("cargo")
+        .args(&["run", "--", "--format", "json", "-q"])
+        .output()
+        .expect("Failed to run aletheia in quiet JSON mode");
+
+    assert!(output.status.success(), "Should succeed in quiet JSON mode");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1, "Summary should be a single line");
+    assert!(stdout.contains("\"passed\":22,\"total\":22,"));
+    assert!(stdout.contains("\"critical_warnings\":0"));
+    assert!(!stdout.contains("\"checks\""), "Summary should omit the checks");
+}
+
 /// Test verbose mode output
 #[test]
 fn test_verbose_mode() {
@@ -442,7 +483,41 @@ fn test_format_equals_syntax() {
     assert!(output.status.success(), "Should succeed with --format=json");
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.starts_with("{"), "Should output JSON");
+    assert!(stdout.starts_with('{'), "Should output JSON");
+}
+
+/// Test ASCII output with --no-emoji
+#[test]
+fn test_no_emoji_output() {
+    let output = Command::new("cargo")
+        .args(&["run", "--", "--no-emoji", "--color", "never", "-v"])
+        .output()
+        .expect("Failed to run aletheia with --no-emoji");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.is_ascii(), "Output should be plain ASCII");
+    assert!(stdout.contains("[PASS] README"));
+}
+
+/// Test options given after the repository path
+#[test]
+fn test_options_after_path() {
+    let output = Command::new("cargo")
+        .args(&["run", "--", ".", "-f", "json", "--quiet", "--verbose"])
+        .output()
+        .expect("Failed to run aletheia with options after the path");
+
+    assert!(output.status.success(), "Should accept options after the path");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with('{'), "Should output JSON");
+
+    let output = Command::new("cargo")
+        .args(&["run", "--", ".", "other"])
+        .output()
+        .expect("Failed to run aletheia with two paths");
+    assert_eq!(output.status.code(), Some(4), "Should reject a second path");
 }
 
 /// Test README.adoc alternative
@@ -484,7 +559,45 @@ fn test_timestamp_in_output() {
     );
     // Check ISO 8601 format (contains T and Z)
     assert!(
-        stdout.contains("T") && stdout.contains("Z"),
+        stdout.contains('T') && stdout.contains('Z'),
         "Timestamp should be in ISO 8601 format"
     );
 }
+
+/// Test --reproducible leaves the timestamp and absolute path out
+#[test]
+fn test_reproducible_output() {
+    let repo = std::env::current_dir().unwrap();
+    let run = || {
+        let output = Command::new("cargo")
+            .args(&["run", "--", "--reproducible", "-f", "json"])
+            .arg(&repo)
+            .output()
+            .expect("Failed to run aletheia");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let first = run();
+    assert_eq!(first, run(), "Reproducible reports should be byte-identical");
+    assert!(first.contains("\"repository\": \".\","));
+    assert!(!first.contains("verified_at"));
+}
+
+/// Test SOURCE_DATE_EPOCH fixes the verification timestamp
+#[test]
+fn test_source_date_epoch() {
+    let output = Command::new("cargo")
+        .args(&["run", "--", "-f", "json"])
+        .env("SOURCE_DATE_EPOCH", "1768435200")
+        .output()
+        .expect("Failed to run aletheia");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"verified_at\": \"2026-01-15T00:00:00Z\","));
+
+    let output = Command::new("cargo")
+        .args(&["run", "--", "-q"])
+        .env("SOURCE_DATE_EPOCH", "yesterday")
+        .output()
+        .expect("Failed to run aletheia");
+    assert_eq!(output.status.code(), Some(4), "Should reject a malformed value");
+}